@@ -0,0 +1,107 @@
+use crate::filter::{self, Filter, Walked};
+use flate2::{write::GzEncoder, Compression};
+use portal::errors::PortalError;
+use std::error::Error;
+use std::io::{self, PipeReader, Read, Write};
+use std::path::Path;
+use std::thread::{self, JoinHandle};
+
+/// How a directory is archived when it's sent whole, see
+/// [`crate::validate_archive`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Archive {
+    Tar,
+
+    /// Compressed with gzip as it is built, at the cost of CPU
+    TarGz,
+}
+
+impl Archive {
+    /// The extension of the archive's name
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Tar => "tar",
+            Self::TarGz => "tar.gz",
+        }
+    }
+
+    /// Helper: the name a directory is sent under
+    pub(crate) fn name(&self, dir: &Path) -> Result<String, Box<dyn Error>> {
+        let dir = dir.canonicalize()?;
+        let stem = dir
+            .file_name()
+            .ok_or(PortalError::BadFileName)?
+            .to_str()
+            .ok_or(PortalError::BadFileName)?;
+        Ok(format!("{}.{}", stem, self.extension()))
+    }
+
+    /// Helper: the format an archive was named with
+    pub(crate) fn of(name: &str) -> Self {
+        match name.ends_with(".tar.gz") {
+            true => Self::TarGz,
+            false => Self::Tar,
+        }
+    }
+}
+
+/// Reads a tar archive of a directory as another thread builds it. The
+/// archive can only be read to the end if it was built successfully, so
+/// the peer never receives a truncated archive as complete.
+pub(crate) struct ArchiveReader {
+    pipe: PipeReader,
+    builder: Option<JoinHandle<io::Result<()>>>,
+}
+
+impl ArchiveReader {
+    /// Start archiving the directory under its own name, so that it
+    /// unpacks into a directory of that name. Links are archived as
+    /// links rather than followed, and entries the filter leaves out
+    /// are skipped.
+    pub fn new(dir: &Path, archive: Archive, filter: &Filter) -> io::Result<Self> {
+        let (pipe, writer) = io::pipe()?;
+        let dir = dir.canonicalize()?;
+        let filter = filter.clone();
+        let builder = thread::spawn(move || match archive {
+            Archive::Tar => build(writer, &dir, &filter).map(drop),
+            Archive::TarGz => {
+                let writer = GzEncoder::new(writer, Compression::default());
+                build(writer, &dir, &filter)?.finish().map(drop)
+            }
+        });
+        Ok(Self {
+            pipe,
+            builder: Some(builder),
+        })
+    }
+}
+
+/// Helper: write a tar archive of the directory into `writer`
+fn build<W: Write>(writer: W, dir: &Path, filter: &Filter) -> io::Result<W> {
+    let root = Path::new(dir.file_name().unwrap_or_default());
+    let mut tar = tar::Builder::new(writer);
+    tar.follow_symlinks(false);
+    tar.append_dir(root, dir)?;
+    filter::walk(dir, filter, &mut |path, rel, walked| match walked {
+        Walked::Dir => tar.append_dir(root.join(rel), path),
+        Walked::File => tar.append_path_with_name(path, root.join(rel)),
+        Walked::Skipped => Ok(()),
+    })?;
+    tar.into_inner()
+}
+
+impl Read for ArchiveReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.pipe.read(buf)?;
+        if len > 0 {
+            return Ok(len);
+        }
+
+        // The builder has finished, report whether it failed
+        match self.builder.take().map(JoinHandle::join) {
+            Some(Ok(result)) => result.map(|_| 0),
+            Some(Err(_)) => Err(io::Error::other("archiving thread panicked")),
+            None => Ok(0),
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use crate::{keychain, FileTypes, Relay, Route, DEFAULT_WORDS};
+use directories::UserDirs;
+use dns_lookup::lookup_host;
+use portal::errors::PortalError;
+use portal::Priority;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+
+/// Tor's default SOCKS port
+const DEFAULT_TOR_PROXY: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9050);
+
+/// Client settings, stored in portal.toml
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct AppConfig {
+    pub relay_host: String,
+    pub relay_port: u16,
+    pub download_location: PathBuf,
+
+    /// Warn before sending more than this many bytes
+    pub warn_size: u64,
+
+    /// Warn before sending more than this many files
+    pub warn_files: usize,
+
+    /// Connect to the relay through this SOCKS5 proxy. Onion relays
+    /// use Tor's default proxy if this isn't set.
+    pub socks_proxy: Option<SocketAddr>,
+
+    /// Look for the peer on the local network, transferring directly
+    /// instead of through the relay when found
+    pub lan: bool,
+
+    /// Secret shared with a private relay that requires clients
+    /// to authenticate before pairing. Prefer saving it in the OS
+    /// keychain with `portal auth login`, which this overrides.
+    pub relay_secret: Option<String>,
+
+    /// Look up the relay's token in the OS keychain when there's no
+    /// relay_secret
+    pub keychain: bool,
+
+    /// Mark transfers as "bulk" so managed networks can let other
+    /// traffic go first, also set with --bulk
+    pub priority: Priority,
+
+    /// Note sent with each transfer to tell it apart, such as who it's
+    /// from. Overridden by --note.
+    pub note: Option<String>,
+
+    /// Generate pass-phrases with at least this many words after the ID,
+    /// and refuse shorter ones given with --passphrase
+    pub min_passphrase_words: usize,
+
+    /// Unpack received .tar, .tar.gz & .zip archives into the download
+    /// location, also enabled with --extract
+    pub extract_archives: bool,
+
+    /// Don't transfer until the user confirms their peer sees the same
+    /// session fingerprint, also enabled with --require-fingerprint-confirm
+    pub confirm_fingerprint: bool,
+
+    /// Ask the relay for its protocol version after a failed handshake,
+    /// to tell whether upgrading the client would help
+    pub check_version: bool,
+
+    /// Which received files may be written to the download location.
+    /// Tables come last, since TOML can't have plain values after them.
+    pub file_types: FileTypes,
+
+    /// Named sets of settings to use instead of the above, selected
+    /// with --preset
+    pub presets: BTreeMap<String, Preset>,
+}
+
+/// Settings a preset overrides, anything unset is left as configured
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct Preset {
+    /// The relay, as host[:port]
+    pub relay: Option<String>,
+    pub download_location: Option<PathBuf>,
+    pub warn_size: Option<u64>,
+    pub warn_files: Option<usize>,
+    pub socks_proxy: Option<SocketAddr>,
+    pub lan: Option<bool>,
+    pub relay_secret: Option<String>,
+}
+
+impl ::std::default::Default for AppConfig {
+    fn default() -> Self {
+        let hdir = UserDirs::new();
+
+        // select ~/Downloads or /tmp for downloads
+        let ddir = match &hdir {
+            Some(home) => home.download_dir().map_or("/tmp", |v| v.to_str().unwrap()),
+            None => "/tmp",
+        };
+
+        Self {
+            relay_host: String::from("portal-relay.landhb.dev"),
+            relay_port: portal::DEFAULT_PORT,
+            download_location: PathBuf::from(ddir),
+            warn_size: 10_000_000_000,
+            warn_files: 10_000,
+            socks_proxy: None,
+            lan: true,
+            relay_secret: None,
+            keychain: true,
+            priority: Priority::default(),
+            note: None,
+            min_passphrase_words: DEFAULT_WORDS,
+            extract_archives: false,
+            confirm_fingerprint: false,
+            check_version: true,
+            file_types: FileTypes::default(),
+            presets: BTreeMap::new(),
+        }
+    }
+}
+
+impl AppConfig {
+    /// Load the config file, creating it with defaults on the first run
+    pub fn load() -> Result<Self, Box<dyn Error>> {
+        Ok(confy::load("portal")?)
+    }
+
+    /// Save the config file, after changing presets
+    pub fn store(&self) -> Result<(), Box<dyn Error>> {
+        Ok(confy::store("portal", self)?)
+    }
+
+    /// Apply the settings of the named preset
+    pub fn use_preset(&mut self, name: &str) -> Result<(), Box<dyn Error>> {
+        let preset = match self.presets.get(name) {
+            Some(preset) => preset.clone(),
+            None => {
+                let msg = format!("No preset named {:?} in portal.toml", name);
+                return Err(io::Error::new(io::ErrorKind::NotFound, msg).into());
+            }
+        };
+        if let Some(relay) = &preset.relay {
+            self.set_relay(relay)?;
+        }
+        if let Some(dir) = preset.download_location {
+            self.download_location = dir;
+        }
+        self.warn_size = preset.warn_size.unwrap_or(self.warn_size);
+        self.warn_files = preset.warn_files.unwrap_or(self.warn_files);
+        self.socks_proxy = preset.socks_proxy.or(self.socks_proxy);
+        self.lan = preset.lan.unwrap_or(self.lan);
+        self.relay_secret = preset.relay_secret.or(self.relay_secret.take());
+        Ok(())
+    }
+
+    /// Use the relay at host[:port] instead of the configured one
+    pub fn set_relay(&mut self, relay: &str) -> Result<(), Box<dyn Error>> {
+        if let Ok(addr) = relay.parse::<SocketAddr>() {
+            self.relay_host = addr.ip().to_string();
+            self.relay_port = addr.port();
+            return Ok(());
+        }
+        match relay.rsplit_once(':') {
+            Some((host, port)) if !host.contains(':') => {
+                self.relay_host = host.to_string();
+                self.relay_port = port.parse()?;
+            }
+            _ => self.relay_host = relay.to_string(),
+        }
+        Ok(())
+    }
+
+    /// Determine how to reach the configured relay. The host is only
+    /// resolved locally when connecting directly.
+    pub fn relay(&self) -> Result<Relay, Box<dyn Error>> {
+        Ok(Relay {
+            route: self.route()?,
+            secret: self.relay_token().map(String::into_bytes),
+            priority: self.priority,
+        })
+    }
+
+    /// The token to authenticate to the relay with, from portal.toml or
+    /// else the keychain
+    pub fn relay_token(&self) -> Option<String> {
+        match (&self.relay_secret, self.keychain) {
+            (Some(secret), _) => Some(secret.clone()),
+            (None, true) => keychain::load_token(&self.relay_host, self.relay_port),
+            (None, false) => None,
+        }
+    }
+
+    /// Save a token for the configured relay in the OS keychain
+    pub fn save_relay_token(&self, token: &str) -> Result<(), Box<dyn Error>> {
+        keychain::save_token(&self.relay_host, self.relay_port, token)
+    }
+
+    /// Remove the configured relay's token from the OS keychain,
+    /// returning whether one was saved
+    pub fn delete_relay_token(&self) -> Result<bool, Box<dyn Error>> {
+        keychain::delete_token(&self.relay_host, self.relay_port)
+    }
+
+    /// Helper: the route to the relay
+    fn route(&self) -> Result<Route, Box<dyn Error>> {
+        let onion = self.relay_host.ends_with(".onion");
+        if let Some(proxy) = self.socks_proxy.or(onion.then_some(DEFAULT_TOR_PROXY)) {
+            return Ok(Route::Socks {
+                proxy,
+                host: self.relay_host.clone(),
+                port: self.relay_port,
+            });
+        }
+
+        let ip: IpAddr = match self.relay_host.parse() {
+            Ok(res) => res,
+            Err(_) => *lookup_host(&self.relay_host)?
+                .first()
+                .ok_or(PortalError::NoPeer)?,
+        };
+        Ok(Route::Direct(SocketAddr::new(ip, self.relay_port)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_roundtrip() {
+        let mut config = AppConfig::default();
+        config.presets.insert("work".into(), Preset::default());
+        let saved = toml::to_string(&config).unwrap();
+        let loaded: AppConfig = toml::from_str(&saved).unwrap();
+        assert_eq!(loaded.relay_host, config.relay_host);
+        assert!(loaded.presets.contains_key("work"));
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{Relay, Summary};
+use portal::{FileEntry, TransferInfo};
+use std::net::SocketAddr;
+use std::path::Path;
+
+/// Progress of a transfer, reported to the [`Frontend`] as it happens
+#[derive(Debug)]
+pub enum Event<'a> {
+    /// Connected to the relay
+    Connected(&'a Relay),
+
+    /// Connected to the peer directly, on the local network
+    Direct(SocketAddr),
+
+    /// The Sender's pass-phrase, to be given to the Receiver out-of-band
+    Passphrase(&'a str),
+
+    /// The handshake with the peer has completed. Both peers see the
+    /// same fingerprint, which can be compared out-of-band
+    Paired { fingerprint: &'a str },
+
+    /// A file is about to be transferred
+    FileStarted(&'a FileEntry),
+
+    /// Total & newly transferred bytes of the current file
+    Progress { transferred: usize, delta: usize },
+
+    /// The current file has been transferred
+    FileFinished(&'a FileEntry),
+
+    /// The current file changed or became unreadable after it was
+    /// offered, it was skipped & the next file follows
+    FileAborted(&'a FileEntry),
+
+    /// A received file's type isn't allowed, so it was written to
+    /// the quarantine directory instead
+    Quarantined { path: &'a Path, reason: &'a str },
+
+    /// A received archive was unpacked into the download directory,
+    /// without the links it contained
+    Extracted {
+        archive: &'a Path,
+        entries: usize,
+        skipped: usize,
+    },
+
+    /// The session has ended, with or without errors
+    Summary(&'a Summary),
+}
+
+/// Implemented by user interfaces driving a transfer
+pub trait Frontend {
+    /// Called as the transfer progresses
+    fn event(&mut self, event: Event);
+
+    /// Asked by the Receiver whether to accept the incoming files
+    fn confirm(&mut self, info: &TransferInfo) -> bool;
+
+    /// Asked by both peers once paired, whether the peer confirmed it
+    /// sees the same session fingerprint. The session is abandoned if
+    /// not. Accepts without asking by default.
+    fn confirm_fingerprint(&mut self, _fingerprint: &str) -> bool {
+        true
+    }
+}
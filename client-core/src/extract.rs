@@ -0,0 +1,513 @@
+use crate::filetypes::{FileTypes, SNIFF_LEN};
+use flate2::read::GzDecoder;
+use portal::ReceivePolicy;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use zip::ZipArchive;
+
+/// Mask & value of a symbolic link in a unix file mode
+const S_IFMT: u32 = 0o170000;
+const S_IFLNK: u32 = 0o120000;
+
+/// An archive may unpack to at most this many times its own size, so that
+/// a small archive can't fill the disk
+pub const MAX_EXPANSION: u64 = 100;
+
+/// Archive formats that can be extracted, by extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Tar,
+    TarGz,
+    Zip,
+}
+
+impl Format {
+    /// Helper: the format of an archive, judging by its name
+    fn of(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        match () {
+            _ if name.ends_with(".tar") => Some(Self::Tar),
+            _ if name.ends_with(".tar.gz") || name.ends_with(".tgz") => Some(Self::TarGz),
+            _ if name.ends_with(".zip") => Some(Self::Zip),
+            _ => None,
+        }
+    }
+}
+
+/// What was unpacked from an archive
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Extracted {
+    /// Files & directories written
+    pub entries: usize,
+
+    /// Links & special files, which aren't recreated
+    pub skipped: usize,
+}
+
+/// Whether a received file is an archive that can be extracted
+pub fn is_archive(path: &Path) -> bool {
+    Format::of(path).is_some()
+}
+
+/// Unpack an archive into `dest`. Every entry is checked before any is
+/// written, and the archive is refused entirely if an entry would be
+/// written outside of `dest`, would overwrite an existing file or another
+/// entry, isn't allowed by `file_types`, or goes over the limits of
+/// `policy`. Archives also can't unpack to more than [`MAX_EXPANSION`]
+/// times their size. Links & special files are skipped.
+pub fn extract(
+    archive: &Path,
+    dest: &Path,
+    file_types: &FileTypes,
+    policy: &ReceivePolicy,
+) -> io::Result<Extracted> {
+    let checker = Checker::new(archive, dest, file_types, policy)?;
+    match Format::of(archive) {
+        Some(Format::Tar) => extract_tar(checker, false),
+        Some(Format::TarGz) => extract_tar(checker, true),
+        Some(Format::Zip) => extract_zip(checker),
+        None => Err(refused(archive, "isn't an archive")),
+    }
+}
+
+/// Helper: the error for an archive that won't be extracted
+fn refused(archive: &Path, reason: &str) -> io::Error {
+    let msg = format!("Not extracting {}, it {}", archive.display(), reason);
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Helper: checks the entries of an archive, keeping count of what
+/// they'd write
+struct Checker<'a> {
+    archive: &'a Path,
+    dest: &'a Path,
+    file_types: &'a FileTypes,
+    policy: ReceivePolicy,
+    max_bytes: u64,
+    bytes: u64,
+    files: usize,
+    seen: HashMap<PathBuf, bool>,
+}
+
+impl<'a> Checker<'a> {
+    fn new(
+        archive: &'a Path,
+        dest: &'a Path,
+        file_types: &'a FileTypes,
+        policy: &ReceivePolicy,
+    ) -> io::Result<Self> {
+        let expansion = fs::metadata(archive)?.len().saturating_mul(MAX_EXPANSION);
+        Ok(Self {
+            archive,
+            dest,
+            file_types,
+            policy: *policy,
+            max_bytes: policy
+                .max_total_bytes
+                .map_or(expansion, |m| m.min(expansion)),
+            bytes: 0,
+            files: 0,
+            seen: HashMap::new(),
+        })
+    }
+
+    /// Check an entry of `size` bytes that starts with `start`, returning
+    /// where it would be extracted to
+    fn check(&mut self, name: &Path, is_dir: bool, size: u64, start: &[u8]) -> io::Result<PathBuf> {
+        let path = target(self.archive, self.dest, name, is_dir)?;
+
+        // Entries of the same name would overwrite each other
+        let normalized = path.strip_prefix(self.dest).unwrap_or(&path).to_path_buf();
+        if let Some(was_dir) = self.seen.insert(normalized, is_dir) {
+            if !(was_dir && is_dir) {
+                let reason = format!("has more than one entry named {}", name.display());
+                return Err(refused(self.archive, &reason));
+            }
+        }
+        if is_dir {
+            return Ok(path);
+        }
+
+        let display = name.to_string_lossy();
+        let reason = self
+            .file_types
+            .check_name(&display)
+            .or_else(|| self.file_types.check_start(&display, start));
+        if let Some(reason) = reason {
+            return Err(refused(
+                self.archive,
+                &format!("has a file that {}", reason),
+            ));
+        }
+
+        self.bytes = self.bytes.saturating_add(size);
+        self.files += 1;
+        let too_large = self.policy.max_single_file.is_some_and(|max| size > max);
+        if too_large || self.bytes > self.max_bytes {
+            return Err(refused(self.archive, "would unpack to too much data"));
+        }
+        if self.policy.max_files.is_some_and(|max| self.files > max) {
+            return Err(refused(self.archive, "has too many files"));
+        }
+        Ok(path)
+    }
+}
+
+/// Helper: where an entry would be extracted to, checking it stays
+/// within `dest` and doesn't overwrite anything
+fn target(archive: &Path, dest: &Path, name: &Path, is_dir: bool) -> io::Result<PathBuf> {
+    let traverses = name
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir));
+    let relative: PathBuf = name
+        .components()
+        .filter(|c| matches!(c, Component::Normal(_)))
+        .collect();
+    if traverses || relative.as_os_str().is_empty() {
+        let reason = format!("has an entry outside of the directory: {}", name.display());
+        return Err(refused(archive, &reason));
+    }
+    let name = relative;
+
+    // Existing links could lead elsewhere
+    let path = dest.join(&name);
+    for ancestor in name.ancestors().filter(|a| !a.as_os_str().is_empty()) {
+        let existing = fs::symlink_metadata(dest.join(ancestor));
+        if existing.is_ok_and(|m| m.file_type().is_symlink()) {
+            let reason = format!("would extract through a link: {}", ancestor.display());
+            return Err(refused(archive, &reason));
+        }
+    }
+    if !is_dir && path.exists() {
+        let reason = format!("would overwrite {}", path.display());
+        return Err(refused(archive, &reason));
+    }
+    Ok(path)
+}
+
+/// Helper: the first bytes of an entry, to tell its type
+fn read_start<R: Read>(entry: R) -> io::Result<Vec<u8>> {
+    let mut start = Vec::with_capacity(SNIFF_LEN);
+    entry.take(SNIFF_LEN as u64).read_to_end(&mut start)?;
+    Ok(start)
+}
+
+/// Helper: read a tar archive, decompressing it if needed
+fn open_tar(archive: &Path, gzip: bool) -> io::Result<tar::Archive<Box<dyn Read>>> {
+    let file = File::open(archive)?;
+    let reader: Box<dyn Read> = match gzip {
+        true => Box::new(GzDecoder::new(file)),
+        false => Box::new(file),
+    };
+    Ok(tar::Archive::new(reader))
+}
+
+/// Helper: check every entry of a tar archive, then unpack them.
+/// An entry's size is part of its header, so it can't write more.
+fn extract_tar(mut checker: Checker, gzip: bool) -> io::Result<Extracted> {
+    let archive = checker.archive;
+    let mut paths = Vec::new();
+    for entry in open_tar(archive, gzip)?.entries()? {
+        let mut entry = entry?;
+        let kind = entry.header().entry_type();
+        if !kind.is_file() && !kind.is_dir() {
+            paths.push(None);
+            continue;
+        }
+        let start = read_start(&mut entry)?;
+        let path = checker.check(&entry.path()?, kind.is_dir(), entry.size(), &start)?;
+        paths.push(Some((path, kind.is_dir())));
+    }
+
+    let mut extracted = Extracted::default();
+    for (entry, path) in open_tar(archive, gzip)?.entries()?.zip(&paths) {
+        let mut entry = entry?;
+        match path {
+            None => extracted.skipped += 1,
+            Some((path, true)) => fs::create_dir_all(path)?,
+            Some((path, false)) => {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                entry.unpack(path)?;
+            }
+        }
+        extracted.entries += path.is_some() as usize;
+    }
+    Ok(extracted)
+}
+
+/// Helper: check every entry of a zip archive, then unpack them. Entries
+/// are cut off at the size they declare, which is what was checked.
+fn extract_zip(mut checker: Checker) -> io::Result<Extracted> {
+    let archive = checker.archive;
+    let mut zip = ZipArchive::new(File::open(archive)?)?;
+    let mut paths = Vec::with_capacity(zip.len());
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i)?;
+        let name = match entry.enclosed_name() {
+            Some(name) => name.to_path_buf(),
+            None => {
+                let reason = format!("has an entry outside of the directory: {}", entry.name());
+                return Err(refused(archive, &reason));
+            }
+        };
+        if entry.unix_mode().is_some_and(|m| m & S_IFMT == S_IFLNK) {
+            paths.push(None);
+            continue;
+        }
+        let (is_dir, size) = (entry.is_dir(), entry.size());
+        let start = read_start(&mut entry)?;
+        let path = checker.check(&name, is_dir, size, &start)?;
+        paths.push(Some((path, is_dir, size)));
+    }
+
+    let mut extracted = Extracted::default();
+    for (i, path) in paths.iter().enumerate() {
+        match path {
+            None => extracted.skipped += 1,
+            Some((path, true, _)) => fs::create_dir_all(path)?,
+            Some((path, false, size)) => {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                let mut entry = zip.by_index(i)?.take(size + 1);
+                if io::copy(&mut entry, &mut File::create(path)?)? > *size {
+                    fs::remove_file(path)?;
+                    let reason = format!("has an entry larger than it says: {}", path.display());
+                    return Err(refused(archive, &reason));
+                }
+            }
+        }
+        extracted.entries += path.is_some() as usize;
+    }
+    Ok(extracted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{write::GzEncoder, Compression};
+    use std::io::Write;
+    use tempdir::TempDir;
+    use zip::ZipWriter;
+
+    /// Helper: a tar archive of (name, contents) entries, written without
+    /// the checks tar::Builder makes on names
+    fn tar_of(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut tar = tar::Builder::new(Vec::new());
+        for (name, data) in entries {
+            let mut header = tar::Header::new_old();
+            header.as_old_mut().name[..name.len()].copy_from_slice(name.as_bytes());
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            tar.append(&header, *data).unwrap();
+        }
+        tar.into_inner().unwrap()
+    }
+
+    /// Helper: write an archive to a directory of its own, returning that
+    /// directory & an empty one to extract into
+    fn setup(name: &str, data: &[u8]) -> (TempDir, TempDir, PathBuf) {
+        let src = TempDir::new("archive").unwrap();
+        let dest = TempDir::new("extract").unwrap();
+        let path = src.path().join(name);
+        fs::write(&path, data).unwrap();
+        (src, dest, path)
+    }
+
+    /// Helper: extract with no limits but the default ones
+    fn unpack(archive: &Path, dest: &Path) -> io::Result<Extracted> {
+        extract(
+            archive,
+            dest,
+            &FileTypes::default(),
+            &ReceivePolicy::default(),
+        )
+    }
+
+    /// Helper: whether the directory is still empty
+    fn is_empty(dir: &Path) -> bool {
+        fs::read_dir(dir).unwrap().next().is_none()
+    }
+
+    #[test]
+    fn test_extract_tar() {
+        let mut tar = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Directory);
+        header.set_size(0);
+        tar.append_data(&mut header, "dir", io::empty()).unwrap();
+        let mut header = tar::Header::new_gnu();
+        header.set_size(5);
+        tar.append_data(&mut header, "dir/a.txt", &b"hello"[..])
+            .unwrap();
+        let data = tar.into_inner().unwrap().finish().unwrap();
+
+        let (_src, dest, archive) = setup("dir.tar.gz", &data);
+        let extracted = unpack(&archive, dest.path()).unwrap();
+        assert_eq!(
+            extracted,
+            Extracted {
+                entries: 2,
+                skipped: 0
+            }
+        );
+        assert_eq!(fs::read(dest.path().join("dir/a.txt")).unwrap(), b"hello");
+    }
+
+    #[test]
+    fn test_extract_zip() {
+        let mut zip = ZipWriter::new(io::Cursor::new(Vec::new()));
+        zip.add_directory("dir", Default::default()).unwrap();
+        zip.start_file("dir/a.txt", Default::default()).unwrap();
+        zip.write_all(b"hello").unwrap();
+        let data = zip.finish().unwrap().into_inner();
+
+        let (_src, dest, archive) = setup("dir.zip", &data);
+        let extracted = unpack(&archive, dest.path()).unwrap();
+        assert_eq!(
+            extracted,
+            Extracted {
+                entries: 2,
+                skipped: 0
+            }
+        );
+        assert_eq!(fs::read(dest.path().join("dir/a.txt")).unwrap(), b"hello");
+    }
+
+    #[test]
+    fn test_refuse_traversal() {
+        for name in ["../evil.txt", "/evil.txt", "dir/../../evil.txt"] {
+            let (_src, dest, archive) = setup("bad.tar", &tar_of(&[(name, b"evil")]));
+            assert!(unpack(&archive, dest.path()).is_err(), "{}", name);
+            assert!(is_empty(dest.path()));
+        }
+
+        let mut zip = ZipWriter::new(io::Cursor::new(Vec::new()));
+        zip.start_file("../evil.txt", Default::default()).unwrap();
+        zip.write_all(b"evil").unwrap();
+        let data = zip.finish().unwrap().into_inner();
+        let (_src, dest, archive) = setup("bad.zip", &data);
+        assert!(unpack(&archive, dest.path()).is_err());
+        assert!(is_empty(dest.path()));
+    }
+
+    #[test]
+    fn test_skip_links() {
+        let mut tar = tar::Builder::new(Vec::new());
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Symlink);
+        header.set_size(0);
+        tar.append_link(&mut header, "passwd", "/etc/passwd")
+            .unwrap();
+        let mut header = tar::Header::new_gnu();
+        header.set_size(2);
+        tar.append_data(&mut header, "a.txt", &b"ok"[..]).unwrap();
+        let data = tar.into_inner().unwrap();
+
+        let (_src, dest, archive) = setup("links.tar", &data);
+        let extracted = unpack(&archive, dest.path()).unwrap();
+        assert_eq!(
+            extracted,
+            Extracted {
+                entries: 1,
+                skipped: 1
+            }
+        );
+        assert!(fs::symlink_metadata(dest.path().join("passwd")).is_err());
+        assert_eq!(fs::read(dest.path().join("a.txt")).unwrap(), b"ok");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_refuse_existing_link() {
+        let outside = TempDir::new("outside").unwrap();
+        let (_src, dest, archive) = setup("bad.tar", &tar_of(&[("dir/a.txt", b"evil")]));
+        std::os::unix::fs::symlink(outside.path(), dest.path().join("dir")).unwrap();
+        assert!(unpack(&archive, dest.path()).is_err());
+        assert!(is_empty(outside.path()));
+    }
+
+    #[test]
+    fn test_refuse_duplicates() {
+        let entries: &[(&str, &[u8])] = &[("a.txt", b"first"), ("./a.txt", b"second")];
+        let (_src, dest, archive) = setup("dup.tar", &tar_of(entries));
+        assert!(unpack(&archive, dest.path()).is_err());
+        assert!(is_empty(dest.path()));
+    }
+
+    #[test]
+    fn test_refuse_overwrite() {
+        let (_src, dest, archive) = setup("a.tar", &tar_of(&[("a.txt", b"new")]));
+        fs::write(dest.path().join("a.txt"), b"old").unwrap();
+        assert!(unpack(&archive, dest.path()).is_err());
+        assert_eq!(fs::read(dest.path().join("a.txt")).unwrap(), b"old");
+    }
+
+    #[test]
+    fn test_refuse_bomb() {
+        let zeros = vec![0u8; 1 << 20];
+        let mut gz = GzEncoder::new(Vec::new(), Compression::best());
+        gz.write_all(&tar_of(&[("zeros.bin", &zeros)])).unwrap();
+        let (_src, dest, archive) = setup("bomb.tar.gz", &gz.finish().unwrap());
+        assert!(unpack(&archive, dest.path()).is_err());
+        assert!(is_empty(dest.path()));
+    }
+
+    #[test]
+    fn test_policy_limits() {
+        let entries: &[(&str, &[u8])] = &[("a.txt", b"aaaa"), ("b.txt", b"bbbb")];
+        let (_src, dest, archive) = setup("two.tar", &tar_of(entries));
+        let types = FileTypes::default();
+        let limits = [
+            ReceivePolicy {
+                max_total_bytes: Some(7),
+                ..Default::default()
+            },
+            ReceivePolicy {
+                max_files: Some(1),
+                ..Default::default()
+            },
+            ReceivePolicy {
+                max_single_file: Some(3),
+                ..Default::default()
+            },
+        ];
+        for policy in limits.iter() {
+            assert!(extract(&archive, dest.path(), &types, policy).is_err());
+            assert!(is_empty(dest.path()));
+        }
+        let policy = ReceivePolicy {
+            max_total_bytes: Some(8),
+            max_files: Some(2),
+            max_single_file: Some(4),
+        };
+        assert!(extract(&archive, dest.path(), &types, &policy).is_ok());
+    }
+
+    #[test]
+    fn test_file_types() {
+        let (_src, dest, archive) = setup("a.tar", &tar_of(&[("run.exe", b"MZ")]));
+        let types = FileTypes {
+            deny: vec!["exe".into()],
+            ..Default::default()
+        };
+        let policy = ReceivePolicy::default();
+        assert!(extract(&archive, dest.path(), &types, &policy).is_err());
+        assert!(is_empty(dest.path()));
+
+        // Caught by its contents whatever it's named
+        let (_src, dest, archive) = setup("b.tar", &tar_of(&[("notes.txt", b"\x7fELF")]));
+        let types = FileTypes {
+            deny: vec!["elf".into()],
+            sniff: true,
+            ..Default::default()
+        };
+        assert!(extract(&archive, dest.path(), &types, &policy).is_err());
+        assert!(is_empty(dest.path()));
+    }
+}
@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Signatures at the start of common file types, with the extension
+/// each type is known by
+const SIGNATURES: &[(&[u8], &str)] = &[
+    (b"\x7fELF", "elf"),
+    (b"MZ", "exe"),
+    (b"\xcf\xfa\xed\xfe", "macho"),
+    (b"\xfe\xed\xfa\xcf", "macho"),
+    (b"#!", "sh"),
+    (b"%PDF", "pdf"),
+    (b"\x89PNG", "png"),
+    (b"\xff\xd8\xff", "jpg"),
+    (b"GIF8", "gif"),
+    (b"PK\x03\x04", "zip"),
+    (b"\x1f\x8b", "gz"),
+    (b"Rar!", "rar"),
+    (b"7z\xbc\xaf", "7z"),
+    (b"\xd0\xcf\x11\xe0", "ole"),
+];
+
+/// Bytes read from the start of a file to identify it
+pub(crate) const SNIFF_LEN: usize = 8;
+
+/// Sniffed types that run as programs, which an allowlist must name
+/// explicitly. Others may be containers for allowed types, such as
+/// a .docx being a zip.
+const EXECUTABLES: &[&str] = &["elf", "exe", "macho", "sh"];
+
+/// Which received files may be written to the download directory,
+/// the [file_types] table in portal.toml
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct FileTypes {
+    /// Only accept files with these extensions, any if empty
+    pub allow: Vec<String>,
+
+    /// Never accept files with these extensions
+    pub deny: Vec<String>,
+
+    /// Also identify each file by its first bytes, to catch programs and
+    /// scripts whatever they are named. Files are received into a staging
+    /// directory until they have been checked.
+    pub sniff: bool,
+
+    /// Receive disallowed files into this directory, instead of
+    /// refusing the transfer
+    pub quarantine: Option<PathBuf>,
+}
+
+impl FileTypes {
+    /// Why a file isn't allowed, judging by its name
+    pub fn check_name(&self, name: &str) -> Option<String> {
+        let ext = Path::new(name)
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if listed(&self.deny, &ext) {
+            return Some(format!("{} has a denied extension", name));
+        }
+        if !self.allow.is_empty() && !listed(&self.allow, &ext) {
+            return Some(format!("{} doesn't have an allowed extension", name));
+        }
+        None
+    }
+
+    /// Why a received file isn't allowed, judging by its contents.
+    /// Only checked when sniffing.
+    pub fn check_contents(&self, name: &str, path: &Path) -> io::Result<Option<String>> {
+        if !self.sniff {
+            return Ok(None);
+        }
+        let mut start = Vec::with_capacity(SNIFF_LEN);
+        File::open(path)?
+            .take(SNIFF_LEN as u64)
+            .read_to_end(&mut start)?;
+        Ok(self.check_start(name, &start))
+    }
+
+    /// Why a file isn't allowed, judging by its first few bytes.
+    /// Only checked when sniffing.
+    pub fn check_start(&self, name: &str, start: &[u8]) -> Option<String> {
+        if !self.sniff {
+            return None;
+        }
+        let kind = SIGNATURES
+            .iter()
+            .find(|(magic, _)| start.starts_with(magic))
+            .map(|(_, kind)| *kind)?;
+
+        let denied = listed(&self.deny, kind)
+            || (!self.allow.is_empty()
+                && EXECUTABLES.contains(&kind)
+                && !listed(&self.allow, kind));
+        denied.then(|| {
+            format!(
+                "{} looks like a file of type {}, which isn't allowed",
+                name, kind
+            )
+        })
+    }
+}
+
+/// Helper: whether an extension is in a configured list, which may
+/// spell them with or without the dot
+fn listed(list: &[String], ext: &str) -> bool {
+    list.iter()
+        .any(|e| e.trim_start_matches('.').eq_ignore_ascii_case(ext))
+}
+
+/// Move a received file, copying it across filesystems if needed
+pub fn move_file(from: &Path, to: &Path) -> io::Result<()> {
+    if fs::rename(from, to).is_err() {
+        fs::copy(from, to)?;
+        fs::remove_file(from)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use glob::{MatchOptions, Pattern};
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Wildcards match across directories, so `*.o` matches `build/main.o`
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: false,
+    require_literal_leading_dot: false,
+};
+
+/// Which files are sent, from --include & --exclude glob patterns. Each
+/// is matched against an entry's path relative to the directory being
+/// sent, and against its name alone, so `target` or `.git` match those
+/// directories at any depth.
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+}
+
+impl Filter {
+    /// Only send files matching an include pattern, if any are given,
+    /// and nothing matching an exclude pattern
+    pub fn new(include: &[String], exclude: &[String]) -> Result<Self, Box<dyn Error>> {
+        let compile = |patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|p| Pattern::new(p))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(Self {
+            include: compile(include)?,
+            exclude: compile(exclude)?,
+        })
+    }
+
+    /// Whether an entry is sent. Excluded directories are skipped with
+    /// everything in them, the include patterns only apply to files.
+    pub fn allows(&self, rel: &Path, is_dir: bool) -> bool {
+        let matches = |patterns: &[Pattern]| {
+            let name = rel.file_name().map(Path::new);
+            patterns.iter().any(|p| {
+                p.matches_path_with(rel, MATCH_OPTIONS)
+                    || name.is_some_and(|n| p.matches_path_with(n, MATCH_OPTIONS))
+            })
+        };
+        if matches(&self.exclude) {
+            return false;
+        }
+        is_dir || self.include.is_empty() || matches(&self.include)
+    }
+}
+
+/// What was found walking a directory
+pub(crate) enum Walked {
+    Dir,
+    File,
+
+    /// Left out by the filter
+    Skipped,
+}
+
+/// Visit every entry under `dir` in name order, with its path relative to
+/// `dir`. Directories are visited before their contents, and links aren't
+/// followed.
+pub(crate) fn walk<F>(dir: &Path, filter: &Filter, visit: &mut F) -> io::Result<()>
+where
+    F: FnMut(&Path, &Path, Walked) -> io::Result<()>,
+{
+    walk_from(dir, Path::new(""), filter, visit)
+}
+
+/// Helper: walk the directory at `rel` under the root
+fn walk_from<F>(dir: &Path, rel: &Path, filter: &Filter, visit: &mut F) -> io::Result<()>
+where
+    F: FnMut(&Path, &Path, Walked) -> io::Result<()>,
+{
+    let mut entries = fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|e| e.file_name());
+    for entry in entries {
+        let (path, rel) = (entry.path(), rel.join(entry.file_name()));
+        let is_dir = entry.file_type()?.is_dir();
+        match (filter.allows(&rel, is_dir), is_dir) {
+            (false, _) => visit(&path, &rel, Walked::Skipped)?,
+            (true, true) => {
+                visit(&path, &rel, Walked::Dir)?;
+                walk_from(&path, &rel, filter, visit)?;
+            }
+            (true, false) => visit(&path, &rel, Walked::File)?,
+        }
+    }
+    Ok(())
+}
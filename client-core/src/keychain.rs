@@ -0,0 +1,43 @@
+use keyring::Entry;
+use std::error::Error;
+
+/// Service that relay tokens are saved under in the keychain
+const SERVICE: &str = "portal";
+
+/// Helper: the keychain entry holding the token for a relay
+fn entry(host: &str, port: u16) -> Result<Entry, keyring::Error> {
+    Entry::new(SERVICE, &format!("{}:{}", host, port))
+}
+
+/// Helper: explain that there's no keychain to use, e.g. without a
+/// Secret Service on Linux
+fn unavailable(e: keyring::Error) -> Box<dyn Error> {
+    match e {
+        keyring::Error::PlatformFailure(_) | keyring::Error::NoStorageAccess(_) => {
+            format!("No OS keychain is available, {}", e).into()
+        }
+        e => e.into(),
+    }
+}
+
+/// Save the token for a relay in the OS keychain, replacing any saved before
+pub fn save_token(host: &str, port: u16, token: &str) -> Result<(), Box<dyn Error>> {
+    entry(host, port)
+        .and_then(|entry| entry.set_password(token))
+        .map_err(unavailable)
+}
+
+/// Remove the saved token for a relay, returning whether there was one
+pub fn delete_token(host: &str, port: u16) -> Result<bool, Box<dyn Error>> {
+    match entry(host, port).and_then(|entry| entry.delete_credential()) {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(unavailable(e)),
+    }
+}
+
+/// The saved token for a relay. A missing or unavailable keychain, as on
+/// a headless machine, is treated as no token at all.
+pub fn load_token(host: &str, port: u16) -> Option<String> {
+    entry(host, port).ok()?.get_password().ok()
+}
@@ -0,0 +1,136 @@
+use mdns_sd::{ServiceDaemon, ServiceEvent, ServiceInfo};
+use sha2::{Digest, Sha256};
+use std::error::Error;
+use std::io;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::time::{Duration, Instant};
+
+use crate::CONNECT_TIMEOUT;
+
+/// DNS-SD service type advertised by waiting Senders
+const SERVICE_TYPE: &str = "_portal._tcp.local.";
+
+/// How long the Receiver looks for its Sender on the local network
+/// before falling back to the relay
+pub const DISCOVERY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Delay between checks for a Receiver connecting directly
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How long a direct peer has to complete the handshake, so a host on the
+/// network that connects & stays silent can't hold up the session
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Domain separation for the value advertised in place of the Portal ID
+const ADVERTISED_ID_CONTEXT: &[u8] = b"portal lan advertisement";
+
+/// A Sender listening for its Receiver on the local network. The
+/// listener is advertised until this is dropped, under a digest of the
+/// Portal ID rather than the ID itself: anyone on the network can read
+/// the advertisement, & the relay pairs each ID only once, so the plain
+/// ID would let them claim the session on the relay first.
+pub struct Advertisement {
+    daemon: ServiceDaemon,
+    listener: TcpListener,
+}
+
+impl Advertisement {
+    /// Listen on an ephemeral port & advertise it for this Portal ID
+    pub fn start(id: &str) -> Result<Self, Box<dyn Error>> {
+        let listener = TcpListener::bind("0.0.0.0:0")?;
+        listener.set_nonblocking(true)?;
+        let port = listener.local_addr()?.port();
+
+        // The digest is too long for a single DNS label
+        let advertised = advertised_id(id);
+        let name = &advertised[..16];
+        let host = format!("portal-{}.local.", name);
+        let properties = [("id", advertised.as_str())];
+        let service = ServiceInfo::new(SERVICE_TYPE, name, &host, (), port, &properties[..])?
+            .enable_addr_auto();
+
+        let daemon = ServiceDaemon::new()?;
+        daemon.register(service)?;
+        Ok(Advertisement { daemon, listener })
+    }
+
+    /// Check for a Receiver that has connected directly. The stream times
+    /// out until [`handshake_done`] is called on it.
+    pub fn accept(&self) -> io::Result<Option<(TcpStream, SocketAddr)>> {
+        match self.listener.accept() {
+            Ok((stream, addr)) => {
+                stream.set_nonblocking(false)?;
+                set_timeouts(&stream, Some(HANDSHAKE_TIMEOUT))?;
+                Ok(Some((stream, addr)))
+            }
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                std::thread::sleep(ACCEPT_POLL_INTERVAL);
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl Drop for Advertisement {
+    fn drop(&mut self) {
+        let _ = self.daemon.shutdown();
+    }
+}
+
+/// Lift the handshake timeout from a direct connection once paired, the
+/// transfer itself can wait on the user at either end
+pub fn handshake_done(stream: &TcpStream) -> io::Result<()> {
+    set_timeouts(stream, None)
+}
+
+/// Helper: bound both reads & writes on the stream
+fn set_timeouts(stream: &TcpStream, timeout: Option<Duration>) -> io::Result<()> {
+    stream.set_read_timeout(timeout)?;
+    stream.set_write_timeout(timeout)
+}
+
+/// Helper: the value advertised for a Portal ID, which identifies the
+/// Sender to its Receiver without revealing the ID
+fn advertised_id(id: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(ADVERTISED_ID_CONTEXT);
+    hasher.update(id.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// Look for a Sender advertising this Portal ID on the local network,
+/// returning a direct connection to it if one is found in time. The
+/// stream times out until [`handshake_done`] is called on it.
+pub fn discover(id: &str, timeout: Duration) -> Option<(TcpStream, SocketAddr)> {
+    let advertised = advertised_id(id);
+    let daemon = ServiceDaemon::new().ok()?;
+    let events = daemon.browse(SERVICE_TYPE).ok()?;
+    let deadline = Instant::now() + timeout;
+
+    let mut found = None;
+    while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+        let info = match events.recv_timeout(remaining) {
+            Ok(ServiceEvent::ServiceResolved(info)) => info,
+            Ok(_) => continue,
+            Err(_) => break,
+        };
+        if info.get_property_val_str("id") != Some(advertised.as_str()) {
+            continue;
+        }
+
+        // Try each address the Sender is reachable on
+        found = info.get_addresses().iter().find_map(|ip| {
+            let addr = SocketAddr::new(*ip, info.get_port());
+            let stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT).ok()?;
+            set_timeouts(&stream, Some(HANDSHAKE_TIMEOUT)).ok()?;
+            Some((stream, addr))
+        });
+        if found.is_some() {
+            break;
+        }
+    }
+
+    let _ = daemon.shutdown();
+    found
+}
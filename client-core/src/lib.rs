@@ -0,0 +1,89 @@
+//! The send & receive orchestration of the Portal client, without any
+//! user interface. Frontends, such as the `portal` CLI or a GUI, implement
+//! [`Frontend`] to display progress and confirm incoming transfers.
+//!
+//! ```no_run
+//! use portal_client_core::{AppConfig, Event, Filter, Frontend, TransferInfo};
+//!
+//! struct Quiet;
+//!
+//! impl Frontend for Quiet {
+//!     fn event(&mut self, event: Event) {
+//!         if let Event::Passphrase(phrase) = event {
+//!             println!("pass-phrase: {}", phrase);
+//!         }
+//!     }
+//!
+//!     fn confirm(&mut self, _info: &TransferInfo) -> bool {
+//!         true
+//!     }
+//! }
+//!
+//! let cfg = AppConfig::load().unwrap();
+//! let relay = cfg.relay().unwrap();
+//! let filter = Filter::default();
+//! let (info, _) = portal_client_core::validate_files(vec!["file.txt".into()], &filter).unwrap();
+//! let phrase = portal_client_core::generate_passphrase(portal_client_core::DEFAULT_WORDS);
+//! portal_client_core::send_all(&relay, info, &filter, &phrase, cfg.lan, &mut Quiet).unwrap();
+//! ```
+extern crate portal_lib as portal;
+
+mod config;
+pub use config::{AppConfig, Preset};
+
+/// Relay tokens saved in the OS keychain
+mod keychain;
+
+/// Which received files are allowed
+mod filetypes;
+pub use filetypes::FileTypes;
+
+/// Unpacking received archives
+mod extract;
+
+/// What happened to each file of a session
+mod summary;
+pub use summary::{FileStatus, FileSummary, Summary};
+
+mod events;
+pub use events::{Event, Frontend};
+
+/// Local network discovery
+mod lan;
+pub use lan::DISCOVERY_TIMEOUT;
+
+mod relay;
+pub use relay::{Relay, Route, CONNECT_TIMEOUT, MAX_REDIRECTS};
+
+/// Generating & judging pass-phrases
+mod phrase;
+pub use phrase::{generate_passphrase, passphrase_entropy, passphrase_words, DEFAULT_WORDS};
+
+/// Receiver path
+mod receive;
+pub use receive::{recv_all, recv_into, split_passphrase};
+
+/// Checks before sending
+mod preflight;
+pub use preflight::{preflight, probe_bandwidth, Warning};
+
+/// Hints about version skew after a failed handshake
+mod version;
+pub use version::{relay_version, VersionHint};
+
+/// Which files are sent
+mod filter;
+pub use filter::Filter;
+
+/// Directories sent as a single archive
+mod archive;
+pub use archive::Archive;
+
+/// Sender path
+mod send;
+pub use send::{
+    copy_passphrase, send_all, send_copies, validate_archive, validate_files, CopyResult,
+    MAX_COPIES,
+};
+
+pub use portal::{FileEntry, Metadata, TransferInfo};
@@ -0,0 +1,32 @@
+use crate::split_passphrase;
+use portal::passphrase;
+
+/// Password words in a generated pass-phrase, after the ID word
+pub const DEFAULT_WORDS: usize = 3;
+
+/// Generate a pass-phrase for the Sender, an ID word followed by this
+/// many password words
+pub fn generate_passphrase(words: usize) -> String {
+    format!(
+        "{}{}{}",
+        passphrase::generate(1),
+        passphrase::SEPARATOR,
+        passphrase::generate(words)
+    )
+}
+
+/// Number of password words in a pass-phrase, not counting the ID
+pub fn passphrase_words(phrase: &str) -> usize {
+    split_passphrase(phrase).map_or(0, |(_, password)| {
+        password
+            .split(passphrase::SEPARATOR)
+            .filter(|word| !word.is_empty())
+            .count()
+    })
+}
+
+/// Estimated entropy in bits of a pass-phrase. Only the password words
+/// count, the ID is sent to the relay in the clear to find the peer.
+pub fn passphrase_entropy(phrase: &str) -> f64 {
+    split_passphrase(phrase).map_or(0.0, |(_, password)| passphrase::estimate_entropy(&password))
+}
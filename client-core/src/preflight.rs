@@ -0,0 +1,50 @@
+use crate::{AppConfig, Relay};
+use portal::{errors::PortalError, Protocol, RelayControl, TransferInfo};
+use std::error::Error;
+use std::time::Instant;
+
+/// Bytes requested from the relay to estimate bandwidth
+const PROBE_SIZE: u32 = 1024 * 1024;
+
+/// Reasons to double check an outgoing transfer with the user
+#[derive(Debug, PartialEq, Eq)]
+pub enum Warning {
+    /// The files total more than the configured number of bytes
+    TooLarge { size: u64, limit: u64 },
+
+    /// There are more than the configured number of files
+    TooManyFiles { count: usize, limit: usize },
+}
+
+/// Check the outgoing files against the configured thresholds
+pub fn preflight(info: &TransferInfo, cfg: &AppConfig) -> Vec<Warning> {
+    let size = info.all.iter().map(|m| m.filesize).sum::<u64>();
+    let count = info.all.iter().map(|m| 1 + m.copies.len()).sum::<usize>();
+
+    let mut warnings = Vec::new();
+    if size > cfg.warn_size {
+        warnings.push(Warning::TooLarge {
+            size,
+            limit: cfg.warn_size,
+        });
+    }
+    if count > cfg.warn_files {
+        warnings.push(Warning::TooManyFiles {
+            count,
+            limit: cfg.warn_files,
+        });
+    }
+    warnings
+}
+
+/// Estimate the bandwidth to the relay in bytes per second, by timing
+/// the download of a small amount of filler
+pub fn probe_bandwidth(relay: &Relay) -> Result<f64, Box<dyn Error>> {
+    let mut stream = relay.connect()?;
+    let start = Instant::now();
+    let len = match Protocol::relay_control(&mut stream, RelayControl::Probe(PROBE_SIZE))? {
+        RelayControl::ProbeData(data) => data.len(),
+        _ => return Err(PortalError::BadMsg.into()),
+    };
+    Ok(len as f64 / start.elapsed().as_secs_f64())
+}
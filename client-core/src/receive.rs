@@ -0,0 +1,363 @@
+use crate::extract;
+use crate::filetypes::{self, FileTypes};
+use crate::lan::{self, DISCOVERY_TIMEOUT};
+use crate::summary::{FileStatus, Summary};
+use crate::{Event, Frontend, Relay, MAX_REDIRECTS};
+use portal::{
+    errors::PortalError, passphrase, Direction, FileEntry, Portal, ReceivePolicy, Reconnecting,
+};
+use std::fs;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::time::Instant;
+use std::{error::Error, path::Path};
+
+/// Where files are held in the download directory until their
+/// contents have been checked
+const STAGING_DIR: &str = ".portal-incoming";
+
+/// Split a pass-phrase entered by the Receiver into its (id, password)
+pub fn split_passphrase(input: &str) -> Result<(String, String), Box<dyn Error>> {
+    let mut input = input.split(passphrase::SEPARATOR);
+    let id = input.next().ok_or(PortalError::NoneError)?.to_string();
+    let opass = input
+        .collect::<Vec<&str>>()
+        .join(&passphrase::SEPARATOR.to_string());
+    Ok((id, opass))
+}
+
+/// Helper: the names a received file was written under, itself & its copies
+fn written_names(metadata: &FileEntry) -> impl Iterator<Item = &std::ffi::OsStr> {
+    std::iter::once(&metadata.filename)
+        .chain(&metadata.copies)
+        .filter_map(|name| Path::new(name).file_name())
+}
+
+/// Helper: receive each file, recording it in the summary
+fn recv_files<R: Read, F: Frontend>(
+    portal: &mut Portal,
+    client: &mut R,
+    incoming: Vec<FileEntry>,
+    download_directory: &Path,
+    file_types: &FileTypes,
+    frontend: &mut F,
+    summary: &mut Summary,
+) -> Result<(), Box<dyn Error>> {
+    let staging = download_directory.join(STAGING_DIR);
+    for metadata in incoming {
+        frontend.event(Event::FileStarted(&metadata));
+
+        // Report progress to the frontend
+        let started = Instant::now();
+        let mut received = 0;
+        let progress = |transferred: usize, delta: usize| {
+            received = transferred;
+            frontend.event(Event::Progress { transferred, delta });
+        };
+
+        // Receive the file, straight into quarantine if its name isn't allowed
+        let by_name = std::iter::once(&metadata.filename)
+            .chain(&metadata.copies)
+            .find_map(|name| file_types.check_name(name));
+        let outdir = match (&by_name, &file_types.quarantine) {
+            (Some(_), Some(quarantine)) => quarantine.as_path(),
+            _ if file_types.sniff => staging.as_path(),
+            _ => download_directory,
+        };
+        let received = match portal.recv_file(client, outdir, Some(&metadata), Some(progress)) {
+            Ok(received) => received.filesize as usize,
+            Err(e) if e.downcast_ref() == Some(&PortalError::FileChangedDuringTransfer) => {
+                summary.record(&metadata, received, started, None, FileStatus::Aborted);
+                frontend.event(Event::FileAborted(&metadata));
+                continue;
+            }
+            Err(e) => {
+                summary.record(&metadata, received, started, None, FileStatus::Failed);
+                return Err(e);
+            }
+        };
+
+        // Then decide where a staged file belongs
+        let staged = outdir == staging;
+        let reason = match (staged, written_names(&metadata).next()) {
+            (true, Some(name)) => {
+                file_types.check_contents(&metadata.filename, &staging.join(name))?
+            }
+            _ => by_name,
+        };
+        let dest = match (&reason, &file_types.quarantine) {
+            (None, _) => download_directory,
+            (Some(_), Some(quarantine)) => quarantine.as_path(),
+            (Some(reason), None) => {
+                for name in written_names(&metadata) {
+                    let _ = fs::remove_file(staging.join(name));
+                }
+                summary.record(&metadata, received, started, None, FileStatus::Refused);
+                return Err(io::Error::new(io::ErrorKind::PermissionDenied, reason.clone()).into());
+            }
+        };
+        if staged {
+            for name in written_names(&metadata) {
+                filetypes::move_file(&staging.join(name), &dest.join(name))?;
+            }
+        }
+        if let Some(reason) = &reason {
+            frontend.event(Event::Quarantined { path: dest, reason });
+        }
+        let status = match reason {
+            Some(_) => FileStatus::Quarantined,
+            None => FileStatus::Complete,
+        };
+        let path = written_names(&metadata).next().map(|name| dest.join(name));
+        summary.record(&metadata, received, started, path.as_deref(), status);
+
+        frontend.event(Event::FileFinished(&metadata));
+    }
+
+    Ok(())
+}
+
+/// Helper: unpack the archives received into the download directory.
+/// Only files that were received completely are extracted, after their
+/// digest has been taken for the summary. What they contain must be
+/// allowed like any other received file.
+fn extract_archives<F: Frontend>(
+    summary: &Summary,
+    download_directory: &Path,
+    file_types: &FileTypes,
+    policy: &ReceivePolicy,
+    frontend: &mut F,
+) -> Result<(), Box<dyn Error>> {
+    let archives = summary
+        .files
+        .iter()
+        .filter(|f| f.status == FileStatus::Complete && f.digest.is_some())
+        .filter_map(|f| f.path.as_deref())
+        .filter(|path| extract::is_archive(path));
+    for archive in archives {
+        let extracted = extract::extract(archive, download_directory, file_types, policy)?;
+        frontend.event(Event::Extracted {
+            archive,
+            entries: extracted.entries,
+            skipped: extracted.skipped,
+        });
+    }
+    Ok(())
+}
+
+/// Helper: pair with the peer that has this pass-phrase, directly if it's
+/// on the local network and `lan` is set, otherwise (or should that
+/// handshake fail) through the relay.
+/// The session is only used once the frontend confirms its fingerprint.
+/// A connection through a relay is resumed if it drops, should the relay
+/// be buffering.
+fn connect<F: Frontend>(
+    relay: &Relay,
+    passphrase: &str,
+    lan: bool,
+    frontend: &mut F,
+) -> Result<(Portal, Reconnecting<TcpStream>), Box<dyn Error>> {
+    // Initialize portal
+    let (id, pass) = split_passphrase(passphrase)?;
+    let init = || -> Result<Portal, Box<dyn Error>> {
+        let mut portal = Portal::init(Direction::Receiver, id.clone(), pass.clone())?;
+        portal.set_relay_secret(relay.secret.clone());
+        portal.set_priority(relay.priority);
+        Ok(portal)
+    };
+    let mut portal = init()?;
+
+    // Connect directly to the Sender if it's nearby, otherwise the relay
+    let mut direct = match lan {
+        true => lan::discover(portal.get_id(), DISCOVERY_TIMEOUT),
+        false => None,
+    };
+    if let Some((stream, addr)) = &mut direct {
+        frontend.event(Event::Direct(*addr));
+        let paired = portal.handshake_direct(stream).and_then(|_| {
+            lan::handshake_done(stream)?;
+            Ok(())
+        });
+
+        // The failed attempt used up this portal's key exchange, so the
+        // relay gets a fresh one
+        if paired.is_err() {
+            direct = None;
+            portal = init()?;
+        }
+    }
+    let client = match direct {
+        Some((stream, _)) => Reconnecting::new(stream),
+        None => {
+            let mut stream = Reconnecting::new(relay.connect()?);
+            let mut paired_by = relay.clone();
+            frontend.event(Event::Connected(relay));
+
+            // Follow a relay at capacity to the alternate its Sender was sent to
+            let mut redirects = 0;
+            while let Err(err) = portal.handshake(&mut stream) {
+                match err.downcast_ref() {
+                    Some(PortalError::Redirected(alternates)) if redirects < MAX_REDIRECTS => {
+                        let (alternate, next) = relay.redirect(alternates)?;
+                        frontend.event(Event::Connected(&alternate));
+                        stream = Reconnecting::new(next);
+                        paired_by = alternate;
+                        redirects += 1;
+                    }
+                    _ => return Err(err),
+                }
+            }
+
+            // Resume through the relay that paired us
+            let mut stream = stream.with_reconnect(Box::new(move || paired_by.connect()));
+            if let Some(token) = portal.resume_token() {
+                stream.arm(portal.get_id(), token);
+            }
+            stream
+        }
+    };
+    let fingerprint = portal.session_fingerprint().unwrap_or_default();
+    frontend.event(Event::Paired {
+        fingerprint: &fingerprint,
+    });
+    if !frontend.confirm_fingerprint(&fingerprint) {
+        return Err(PortalError::Cancelled.into());
+    }
+
+    Ok((portal, client))
+}
+
+/// Receive files from the peer with this pass-phrase into the download
+/// directory, once the frontend has confirmed them. The peer is looked for
+/// on the local network first if `lan` is set. Files whose type isn't
+/// allowed are quarantined, or the transfer refused. Received archives
+/// are unpacked if `extract` is set.
+pub fn recv_all<F: Frontend>(
+    relay: &Relay,
+    passphrase: &str,
+    download_directory: &Path,
+    lan: bool,
+    file_types: &FileTypes,
+    extract: bool,
+    frontend: &mut F,
+) -> Result<(), Box<dyn Error>> {
+    let (mut portal, mut client) = connect(relay, passphrase, lan, frontend)?;
+
+    // TODO: Establish P2P QUIC connection here?
+
+    // Refuse files that aren't allowed by name before any are sent,
+    // unless they can be quarantined. Otherwise let the frontend
+    // confirm/deny the transfer.
+    let mut refused = None;
+    let confirm = |info: &portal::TransferInfo| {
+        if file_types.quarantine.is_none() {
+            refused = info
+                .all
+                .iter()
+                .flat_map(|m| std::iter::once(&m.filename).chain(&m.copies))
+                .find_map(|name| file_types.check_name(name));
+        }
+        refused.is_none() && frontend.confirm(info)
+    };
+    let incoming = portal
+        .incoming(&mut client, Some(confirm))
+        .map(|incoming| incoming.collect::<Vec<_>>());
+    let incoming = match (incoming, refused) {
+        (Ok(incoming), _) => incoming,
+        (Err(_), Some(reason)) => {
+            return Err(io::Error::new(io::ErrorKind::PermissionDenied, reason).into())
+        }
+        (Err(e), None) => return Err(e),
+    };
+
+    // Files are checked before they land in the download directory
+    let staging = download_directory.join(STAGING_DIR);
+    if file_types.sniff {
+        fs::create_dir_all(&staging)?;
+    }
+    if let Some(dir) = &file_types.quarantine {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut summary = Summary {
+        rtt: portal.get_rtt(),
+        ..Default::default()
+    };
+    let mut result = recv_files(
+        &mut portal,
+        &mut client,
+        incoming,
+        download_directory,
+        file_types,
+        frontend,
+        &mut summary,
+    );
+    if file_types.sniff {
+        let _ = fs::remove_dir(&staging);
+    }
+    if extract && result.is_ok() {
+        let policy = portal.get_receive_policy();
+        result = extract_archives(&summary, download_directory, file_types, &policy, frontend);
+    }
+    frontend.event(Event::Summary(&summary));
+    result
+}
+
+/// Receive a single file from the peer with this pass-phrase into a writer,
+/// such as stdout, once the frontend has confirmed it. Transfers of more
+/// than one file are refused, since their contents couldn't be told apart.
+/// File type checks are skipped, as nothing is written to disk.
+pub fn recv_into<F: Frontend, W: Write>(
+    relay: &Relay,
+    passphrase: &str,
+    lan: bool,
+    writer: &mut W,
+    frontend: &mut F,
+) -> Result<(), Box<dyn Error>> {
+    let (mut portal, mut client) = connect(relay, passphrase, lan, frontend)?;
+
+    let mut single = true;
+    let confirm = |info: &portal::TransferInfo| {
+        single = info.all.len() == 1;
+        single && frontend.confirm(info)
+    };
+    let incoming = portal
+        .incoming(&mut client, Some(confirm))
+        .map(|incoming| incoming.collect::<Vec<_>>());
+    let metadata = match (incoming, single) {
+        (Ok(mut incoming), _) => incoming.pop().ok_or(PortalError::NoneError)?,
+        (Err(_), false) => {
+            let reason = "only a single file can be received into stdout";
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, reason).into());
+        }
+        (Err(e), true) => return Err(e),
+    };
+
+    let mut summary = Summary {
+        rtt: portal.get_rtt(),
+        ..Default::default()
+    };
+    frontend.event(Event::FileStarted(&metadata));
+    let started = Instant::now();
+    let mut received = 0;
+    let progress = |transferred: usize, delta: usize| {
+        received = transferred;
+        frontend.event(Event::Progress { transferred, delta });
+    };
+    let result = portal.recv_into(&mut client, writer, Some(&metadata), Some(progress));
+    let status = match &result {
+        Ok(_) => FileStatus::Complete,
+        Err(e) if e.downcast_ref() == Some(&PortalError::FileChangedDuringTransfer) => {
+            FileStatus::Aborted
+        }
+        Err(_) => FileStatus::Failed,
+    };
+    summary.record(&metadata, received, started, None, status);
+    match status {
+        FileStatus::Complete => frontend.event(Event::FileFinished(&metadata)),
+        FileStatus::Aborted => frontend.event(Event::FileAborted(&metadata)),
+        _ => {}
+    }
+    frontend.event(Event::Summary(&summary));
+    result.map(|_| ())
+}
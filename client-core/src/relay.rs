@@ -0,0 +1,175 @@
+use portal::Priority;
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+/// How long to wait when connecting to the relay
+pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(6);
+
+/// How long a SOCKS proxy may take to reach the relay. Building
+/// a Tor circuit to an onion service can take a while.
+const SOCKS_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How many times to follow a relay at capacity to one of its alternates
+pub const MAX_REDIRECTS: usize = 3;
+
+/// The relay to pair through
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Relay {
+    pub route: Route,
+
+    /// Secret shared with a private relay, from which the access
+    /// token presented during the handshake is made
+    pub secret: Option<Vec<u8>>,
+
+    /// How the traffic to & from the relay is marked
+    pub priority: Priority,
+}
+
+/// Where to reach the relay
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Route {
+    /// Connect straight to the relay's address
+    Direct(SocketAddr),
+
+    /// Connect through a SOCKS5 proxy such as Tor, which resolves the
+    /// host itself so no DNS lookup is made locally
+    Socks {
+        proxy: SocketAddr,
+        host: String,
+        port: u16,
+    },
+}
+
+impl Relay {
+    /// Open a new connection to the relay, marked with the priority
+    /// where the platform allows it
+    pub fn connect(&self) -> io::Result<TcpStream> {
+        let stream = self.open()?;
+        if self.priority != Priority::Normal {
+            let _ = self.priority.mark(&stream);
+        }
+        Ok(stream)
+    }
+
+    /// Helper: connect along the route
+    fn open(&self) -> io::Result<TcpStream> {
+        match &self.route {
+            Route::Direct(addr) => TcpStream::connect_timeout(addr, CONNECT_TIMEOUT),
+            Route::Socks { proxy, host, port } => {
+                let mut stream = TcpStream::connect_timeout(proxy, CONNECT_TIMEOUT)?;
+                stream.set_read_timeout(Some(SOCKS_TIMEOUT))?;
+                stream.set_write_timeout(Some(SOCKS_TIMEOUT))?;
+                socks5_connect(&mut stream, host, *port)?;
+                stream.set_read_timeout(None)?;
+                stream.set_write_timeout(None)?;
+                Ok(stream)
+            }
+        }
+    }
+
+    /// Connect to the first reachable alternate a relay at capacity
+    /// redirected us to, keeping the same secret & proxy
+    pub fn redirect(&self, alternates: &[String]) -> io::Result<(Relay, TcpStream)> {
+        let mut last = io::Error::new(io::ErrorKind::NotFound, "No alternate relays given");
+        for alternate in alternates {
+            let relay = match self.alternate(alternate) {
+                Ok(relay) => relay,
+                Err(e) => {
+                    last = e;
+                    continue;
+                }
+            };
+            match relay.connect() {
+                Ok(stream) => return Ok((relay, stream)),
+                Err(e) => last = e,
+            }
+        }
+        Err(last)
+    }
+
+    /// Helper: this relay, reached at host:port instead
+    fn alternate(&self, address: &str) -> io::Result<Relay> {
+        let route = match &self.route {
+            Route::Direct(_) => Route::Direct(
+                address
+                    .to_socket_addrs()?
+                    .next()
+                    .ok_or(io::ErrorKind::NotFound)?,
+            ),
+            Route::Socks { proxy, .. } => {
+                let (host, port) = address
+                    .rsplit_once(':')
+                    .and_then(|(host, port)| Some((host, port.parse().ok()?)))
+                    .ok_or(io::ErrorKind::InvalidInput)?;
+                Route::Socks {
+                    proxy: *proxy,
+                    host: host.to_string(),
+                    port,
+                }
+            }
+        };
+        Ok(Relay {
+            route,
+            secret: self.secret.clone(),
+            priority: self.priority,
+        })
+    }
+}
+
+impl fmt::Display for Relay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.route {
+            Route::Direct(addr) => write!(f, "{}", addr),
+            Route::Socks { proxy, host, port } => write!(f, "{}:{} via {}", host, port, proxy),
+        }
+    }
+}
+
+fn socks_error(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::ConnectionRefused, format!("SOCKS5: {}", msg))
+}
+
+/// Ask the proxy to connect to host:port, RFC 1928 without authentication
+fn socks5_connect<S: Read + Write>(stream: &mut S, host: &str, port: u16) -> io::Result<()> {
+    let len = u8::try_from(host.len()).map_err(|_| socks_error("host name too long"))?;
+
+    // Greeting, offering only "no authentication"
+    stream.write_all(&[5, 1, 0])?;
+    let mut reply = [0u8; 2];
+    stream.read_exact(&mut reply)?;
+    if reply != [5, 0] {
+        return Err(socks_error("proxy requires authentication"));
+    }
+
+    // CONNECT by domain name
+    let mut request = vec![5, 1, 0, 3, len];
+    request.extend_from_slice(host.as_bytes());
+    request.extend_from_slice(&port.to_be_bytes());
+    stream.write_all(&request)?;
+
+    let mut header = [0u8; 4];
+    stream.read_exact(&mut header)?;
+    if header[1] != 0 {
+        return Err(socks_error(&format!(
+            "connect failed with code {}",
+            header[1]
+        )));
+    }
+
+    // Discard the bound address
+    let remaining = match header[3] {
+        1 => 4 + 2,
+        4 => 16 + 2,
+        3 => {
+            let mut len = [0u8; 1];
+            stream.read_exact(&mut len)?;
+            len[0] as usize + 2
+        }
+        _ => return Err(socks_error("bad address type")),
+    };
+    stream.read_exact(&mut vec![0u8; remaining])?;
+    Ok(())
+}
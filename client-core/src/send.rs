@@ -0,0 +1,406 @@
+use crate::archive::{Archive, ArchiveReader};
+use crate::filter::{self, Filter, Walked};
+use crate::lan::{self, Advertisement};
+use crate::summary::{FileStatus, Summary};
+use crate::{split_passphrase, Event, Frontend, Relay, MAX_REDIRECTS};
+use portal::{errors::PortalError, Direction, Portal, Shard, TransferInfo};
+use std::fs::DirEntry;
+use std::io::{self, Write};
+use std::net::{Shutdown, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+};
+
+/// How long to keep reconnecting if the relay restarts while
+/// waiting for the receiver
+const RECONNECT_GRACE: Duration = Duration::from_secs(60);
+
+/// Most receivers the same files can be sent to with [`send_copies`]
+pub const MAX_COPIES: u32 = 16;
+
+// Helper method to enumerate directories depth 1
+fn add_all(
+    info: &mut TransferInfo,
+    dir: PathBuf,
+    filter: &Filter,
+    skipped: &mut Vec<PathBuf>,
+) -> Result<(), Box<dyn Error>> {
+    fn check_file(entry: &DirEntry) -> Option<PathBuf> {
+        if !entry.metadata().is_ok_and(|f| f.is_file()) {
+            return None;
+        }
+        Some(entry.path())
+    }
+
+    // Collect all entries
+    let entries = std::fs::read_dir(dir)?
+        .filter_map(|res| res.as_ref().map_or(None, check_file))
+        .collect::<Vec<PathBuf>>();
+
+    // Add them individually, unless filtered out
+    for entry in entries {
+        let name = Path::new(entry.file_name().unwrap_or_default());
+        if !filter.allows(name, false) {
+            skipped.push(entry);
+            continue;
+        }
+        info.add_file(&entry)?;
+    }
+
+    Ok(())
+}
+
+/// Converts a list of input files into TransferInfo, along with the
+/// files the filter left out
+pub fn validate_files(
+    files: Vec<PathBuf>,
+    filter: &Filter,
+) -> Result<(TransferInfo, Vec<PathBuf>), Box<dyn Error>> {
+    collect_files(files, None, filter)
+}
+
+/// Converts a list of input files into TransferInfo, sending each directory
+/// whole as a single archive that is built while it is sent. Much faster
+/// than sending many small files individually. The files the filter left
+/// out are returned too, the same filter must be given to [`send_all`].
+pub fn validate_archive(
+    files: Vec<PathBuf>,
+    archive: Archive,
+    filter: &Filter,
+) -> Result<(TransferInfo, Vec<PathBuf>), Box<dyn Error>> {
+    collect_files(files, Some(archive), filter)
+}
+
+/// Helper: add each file, and each directory either file by file
+/// or as an archive
+fn collect_files(
+    files: Vec<PathBuf>,
+    archive: Option<Archive>,
+    filter: &Filter,
+) -> Result<(TransferInfo, Vec<PathBuf>), Box<dyn Error>> {
+    // Validate that there is at least one file to send
+    if files.is_empty() {
+        return Err(PortalError::BadFileName.into());
+    }
+
+    // Begin adding files to this transfer
+    let mut info = TransferInfo::empty();
+    let mut skipped = Vec::new();
+    for item in files {
+        let name = Path::new(item.file_name().unwrap_or_default());
+        match (item.is_dir(), archive) {
+            (true, Some(archive)) => {
+                filter::walk(&item, filter, &mut |path, _, walked| {
+                    if let Walked::Skipped = walked {
+                        skipped.push(path.to_path_buf());
+                    }
+                    Ok(())
+                })?;
+                info.add_stream(&item, &archive.name(&item)?)?;
+            }
+            (true, None) => {
+                add_all(&mut info, item, filter, &mut skipped)?;
+            }
+            (false, _) if !filter.allows(name, false) => {
+                skipped.push(item);
+            }
+            (false, _) => {
+                info.add_file(item.as_path())?;
+            }
+        }
+    }
+
+    Ok((info, skipped))
+}
+
+/// Allows a relay handshake running on another thread to be abandoned
+#[derive(Default, Clone)]
+struct Cancel {
+    cancelled: Arc<AtomicBool>,
+    stream: Arc<Mutex<Option<TcpStream>>>,
+}
+
+impl Cancel {
+    /// Track the current connection to the relay, refusing new
+    /// connections once cancelled
+    fn track(&self, stream: &TcpStream) -> io::Result<()> {
+        let mut current = self.stream.lock().unwrap();
+        if self.cancelled.load(Ordering::SeqCst) {
+            return Err(io::ErrorKind::Interrupted.into());
+        }
+        *current = Some(stream.try_clone()?);
+        Ok(())
+    }
+
+    /// Interrupt the handshake by closing the current connection
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        if let Some(stream) = self.stream.lock().unwrap().take() {
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+/// Keep PortalErrors intact when passing errors between threads
+fn sendable(e: Box<dyn Error>) -> Box<dyn Error + Send + Sync> {
+    match e.downcast::<PortalError>() {
+        Ok(e) => e,
+        Err(e) => e.to_string().into(),
+    }
+}
+
+/// Wait for the Receiver through the relay, re-registering if it restarts
+/// and following it to an alternate if it is at capacity
+fn relay_handshake(
+    relay: &Relay,
+    mut client: TcpStream,
+    id: String,
+    pass: String,
+    cancel: &Cancel,
+) -> Result<(Portal, TcpStream), Box<dyn Error>> {
+    let mut portal = Portal::init(Direction::Sender, id, pass)?;
+    let mut relay = relay.clone();
+    for redirects in 0.. {
+        portal.set_relay_secret(relay.secret.clone());
+        portal.set_priority(relay.priority);
+        cancel.track(&client)?;
+
+        let reconnect = || {
+            let stream = relay.connect()?;
+            cancel.track(&stream)?;
+            Ok(stream)
+        };
+        let err = match portal.handshake_reconnecting(&mut client, reconnect, RECONNECT_GRACE) {
+            Ok(()) => break,
+            Err(e) => e,
+        };
+        match err.downcast_ref() {
+            Some(PortalError::Redirected(alternates)) if redirects < MAX_REDIRECTS => {
+                (relay, client) = relay.redirect(alternates)?;
+            }
+            _ => return Err(err),
+        }
+    }
+    Ok((portal, client))
+}
+
+/// Wait for the Receiver both through the relay & directly on the local
+/// network, continuing with whichever pairs first
+fn lan_handshake<F: Frontend>(
+    relay: &Relay,
+    client: TcpStream,
+    id: String,
+    pass: String,
+    frontend: &mut F,
+) -> Result<(Portal, TcpStream), Box<dyn Error>> {
+    let mut portal = Portal::init(Direction::Sender, id.clone(), pass.clone())?;
+    let cancel = Cancel::default();
+
+    // Without multicast, only the relay can be used
+    let advertisement = match Advertisement::start(portal.get_id()) {
+        Ok(advertisement) => advertisement,
+        Err(_) => return relay_handshake(relay, client, id, pass, &cancel),
+    };
+
+    let (tx, rx) = mpsc::channel();
+    let (relay, background) = (relay.clone(), cancel.clone());
+    std::thread::spawn(move || {
+        let result = relay_handshake(&relay, client, id, pass, &background);
+        let _ = tx.send(result.map_err(sendable));
+    });
+
+    loop {
+        if let Ok(result) = rx.try_recv() {
+            return result.map_err(|e| e as Box<dyn Error>);
+        }
+        let (mut stream, addr) = match advertisement.accept() {
+            Ok(Some(peer)) => peer,
+            Ok(None) => continue,
+            Err(_) => break,
+        };
+
+        // The relay may have paired while the Receiver was connecting
+        if let Ok(result) = rx.try_recv() {
+            return result.map_err(|e| e as Box<dyn Error>);
+        }
+
+        // Like the relay, only allow a single attempt at the password
+        if portal.handshake_direct(&mut stream).is_err() || lan::handshake_done(&stream).is_err() {
+            break;
+        }
+        cancel.cancel();
+        frontend.event(Event::Direct(addr));
+        return Ok((portal, stream));
+    }
+
+    // Stop advertising & wait on the relay alone
+    drop(advertisement);
+    rx.recv()?.map_err(|e| e as Box<dyn Error>)
+}
+
+/// Helper: send each file, recording it in the summary
+fn send_files<W: Write, F: Frontend>(
+    portal: &mut Portal,
+    client: &mut W,
+    info: &TransferInfo,
+    filter: &Filter,
+    frontend: &mut F,
+    summary: &mut Summary,
+) -> Result<(), Box<dyn Error>> {
+    for (fullpath, metadata) in portal.outgoing(client, info)? {
+        frontend.event(Event::FileStarted(metadata));
+
+        // Report progress to the frontend
+        let started = Instant::now();
+        let mut sent = 0;
+        let progress = |transferred: usize, delta: usize| {
+            sent = transferred;
+            frontend.event(Event::Progress { transferred, delta });
+        };
+
+        // Begin the transfer, archiving directories as they are sent
+        let result = match metadata.is_stream() {
+            true => ArchiveReader::new(fullpath, Archive::of(&metadata.filename), filter)
+                .map_err(Into::into)
+                .and_then(|mut archive| {
+                    let name = &metadata.filename;
+                    portal.send_stream(client, &mut archive, name, Some(progress))
+                }),
+            false => portal.send_file(client, fullpath, Some(progress)),
+        };
+        match result {
+            Ok(sent) => {
+                let path = Some(fullpath.as_path()).filter(|_| !metadata.is_stream());
+                summary.record(metadata, sent, started, path, FileStatus::Complete)
+            }
+            Err(e) if e.downcast_ref() == Some(&PortalError::FileChangedDuringTransfer) => {
+                summary.record(metadata, sent, started, None, FileStatus::Aborted);
+                frontend.event(Event::FileAborted(metadata));
+                continue;
+            }
+            Err(e) => {
+                summary.record(metadata, sent, started, None, FileStatus::Failed);
+                return Err(e);
+            }
+        }
+
+        frontend.event(Event::FileFinished(metadata));
+    }
+    Ok(())
+}
+
+/// Send the files to a peer through the relay, or directly if the peer is
+/// found on the local network. The pass-phrase, usually from
+/// [`crate::generate_passphrase`], is reported to the frontend to deliver
+/// out-of-band. Directories sent as archives leave out what the
+/// filter does.
+pub fn send_all<F: Frontend>(
+    relay: &Relay,
+    info: TransferInfo,
+    filter: &Filter,
+    passphrase: &str,
+    lan: bool,
+    frontend: &mut F,
+) -> Result<(), Box<dyn Error>> {
+    let client = relay.connect()?;
+    frontend.event(Event::Connected(relay));
+
+    // The Receiver is given the same pass-phrase
+    let (id, pass) = split_passphrase(passphrase)?;
+    frontend.event(Event::Passphrase(passphrase));
+
+    // Complete handshake with the Receiver
+    let (mut portal, mut client) = match lan {
+        true => lan_handshake(relay, client, id, pass, frontend)?,
+        false => relay_handshake(relay, client, id, pass, &Cancel::default())?,
+    };
+    let fingerprint = portal.session_fingerprint().unwrap_or_default();
+    frontend.event(Event::Paired {
+        fingerprint: &fingerprint,
+    });
+    if !frontend.confirm_fingerprint(&fingerprint) {
+        return Err(PortalError::Cancelled.into());
+    }
+
+    // TODO: Establish P2P QUIC connection here?
+
+    let mut summary = Summary {
+        rtt: portal.get_rtt(),
+        ..Default::default()
+    };
+    let result = send_files(
+        &mut portal,
+        &mut client,
+        &info,
+        filter,
+        frontend,
+        &mut summary,
+    );
+    frontend.event(Event::Summary(&summary));
+    result
+}
+
+/// How the session with one receiver of [`send_copies`] ended
+pub type CopyResult = Result<(), Box<dyn Error>>;
+
+/// The pass-phrase of receiver `index` in [`send_copies`], derived from
+/// the Sender's pass-phrase. See [`Shard`].
+///
+/// ```
+/// use portal_client_core::copy_passphrase;
+///
+/// let first = copy_passphrase("id-some-pass-phrase", 0).unwrap();
+/// let second = copy_passphrase("id-some-pass-phrase", 1).unwrap();
+/// assert!(first.starts_with("id.0-"));
+/// assert_ne!(first, second);
+/// ```
+pub fn copy_passphrase(passphrase: &str, index: u32) -> Result<String, Box<dyn Error>> {
+    let (id, pass) = split_passphrase(passphrase)?;
+    let shard = Shard::derive(&id, &pass, index)?;
+    Ok(format!("{}-{}", shard.id, shard.password))
+}
+
+/// Send the same files to several receivers at once, one per frontend.
+/// Each receiver gets its own pass-phrase, see [`copy_passphrase`], and
+/// is paired & confirmed independently through the relay. Returns the
+/// result of each session once all of them have ended, or an error if
+/// there are more than [`MAX_COPIES`] frontends.
+pub fn send_copies<F: Frontend + Send>(
+    relay: &Relay,
+    info: &TransferInfo,
+    filter: &Filter,
+    passphrase: &str,
+    frontends: &mut [F],
+) -> Result<Vec<CopyResult>, Box<dyn Error>> {
+    if frontends.len() > MAX_COPIES as usize {
+        let limit = format!("at most {} copies may be sent", MAX_COPIES);
+        return Err(PortalError::BadConfig(limit).into());
+    }
+
+    let results: Vec<_> = std::thread::scope(|scope| {
+        let sessions: Vec<_> = (0..)
+            .zip(frontends.iter_mut())
+            .map(|(index, frontend)| {
+                scope.spawn(move || {
+                    copy_passphrase(passphrase, index)
+                        .and_then(|phrase| {
+                            send_all(relay, info.clone(), filter, &phrase, false, frontend)
+                        })
+                        .map_err(sendable)
+                })
+            })
+            .collect();
+        sessions
+            .into_iter()
+            .map(|session| session.join().unwrap_or_else(|_| Err("panicked".into())))
+            .collect()
+    });
+    Ok(results
+        .into_iter()
+        .map(|result| result.map_err(|e| e as Box<dyn Error>))
+        .collect())
+}
@@ -0,0 +1,110 @@
+use portal::FileEntry;
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// How a file in the session ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    Complete,
+
+    /// Received into the quarantine directory, its type isn't allowed
+    Quarantined,
+
+    /// Received, then removed as its type isn't allowed
+    Refused,
+
+    /// The transfer failed part way through this file
+    Failed,
+
+    /// The file changed or became unreadable after it was offered, so it
+    /// was skipped & the session continued
+    Aborted,
+}
+
+/// A single file of the session
+#[derive(Debug, Clone)]
+pub struct FileSummary {
+    pub filename: String,
+
+    /// Bytes transferred, which is less than the file's size if it failed
+    pub size: u64,
+    pub duration: Duration,
+
+    /// Where the file was written, once transferred
+    pub path: Option<PathBuf>,
+
+    /// Hex SHA-256 of the file on disk, once transferred
+    pub digest: Option<String>,
+    pub status: FileStatus,
+}
+
+impl FileSummary {
+    /// Average bytes per second
+    pub fn rate(&self) -> f64 {
+        self.size as f64 / self.duration.as_secs_f64().max(0.001)
+    }
+}
+
+/// Every file of a session, reported to the [`crate::Frontend`] once it
+/// has ended, whether or not it succeeded
+#[derive(Debug, Clone, Default)]
+pub struct Summary {
+    pub files: Vec<FileSummary>,
+
+    /// Round-trip time to the peer, measured during the handshake
+    pub rtt: Option<Duration>,
+}
+
+impl Summary {
+    /// Total bytes transferred
+    pub fn size(&self) -> u64 {
+        self.files.iter().map(|f| f.size).sum()
+    }
+
+    /// Time spent transferring files
+    pub fn duration(&self) -> Duration {
+        self.files.iter().map(|f| f.duration).sum()
+    }
+
+    /// Average bytes per second over the session
+    pub fn rate(&self) -> f64 {
+        self.size() as f64 / self.duration().as_secs_f64().max(0.001)
+    }
+
+    /// Record a file once it has been transferred, or has failed. The
+    /// digest is taken from the file at `path`, when given.
+    pub(crate) fn record(
+        &mut self,
+        metadata: &FileEntry,
+        size: usize,
+        started: Instant,
+        path: Option<&Path>,
+        status: FileStatus,
+    ) {
+        self.files.push(FileSummary {
+            filename: metadata.filename.clone(),
+            size: size as u64,
+            duration: started.elapsed(),
+            path: path.map(Path::to_path_buf),
+            digest: path.and_then(|p| digest_file(p).ok()),
+            status,
+        });
+    }
+}
+
+/// Helper: hex SHA-256 of a file's contents
+fn digest_file(path: &Path) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; portal::CHUNK_SIZE];
+    loop {
+        match file.read(&mut buffer)? {
+            0 => break,
+            len => hasher.update(&buffer[..len]),
+        }
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
@@ -0,0 +1,49 @@
+use crate::{Relay, CONNECT_TIMEOUT};
+use portal::{errors::PortalError, Protocol, RelayBanner, RelayControl, PROTOCOL_VERSION};
+use std::error::Error;
+
+/// What the relay's version says about a failed handshake
+#[derive(Debug, PartialEq, Eq)]
+pub enum VersionHint {
+    /// The relay speaks a newer protocol than this client, or no
+    /// longer pairs clients this old. Upgrading will likely help.
+    UpgradeClient { relay: u32 },
+
+    /// The relay speaks an older protocol than this client
+    RelayOutdated { relay: u32 },
+
+    /// The relay & this client speak the same protocol, the pass-phrase
+    /// or the peer's client is more likely at fault
+    Compatible,
+
+    /// The relay didn't say, it predates advertising its version
+    Unknown,
+}
+
+impl VersionHint {
+    /// Compare the relay's banner, if it sent one, with this client
+    pub fn new(banner: Option<&RelayBanner>) -> Self {
+        match banner {
+            Some(b) if b.version > PROTOCOL_VERSION || b.min_version > PROTOCOL_VERSION => {
+                VersionHint::UpgradeClient { relay: b.version }
+            }
+            Some(b) if b.version < PROTOCOL_VERSION => {
+                VersionHint::RelayOutdated { relay: b.version }
+            }
+            Some(_) => VersionHint::Compatible,
+            None => VersionHint::Unknown,
+        }
+    }
+}
+
+/// Ask the relay which protocol versions it supports, without pairing.
+/// Relays that predate the question close the connection instead,
+/// which is reported as an error.
+pub fn relay_version(relay: &Relay) -> Result<RelayBanner, Box<dyn Error>> {
+    let mut stream = relay.connect()?;
+    stream.set_read_timeout(Some(CONNECT_TIMEOUT))?;
+    match Protocol::relay_control(&mut stream, RelayControl::Version)? {
+        RelayControl::Banner(banner) => Ok(banner),
+        _ => Err(PortalError::BadMsg.into()),
+    }
+}
@@ -0,0 +1,66 @@
+use colored::*;
+use dialoguer::Password;
+use portal_client_core::AppConfig;
+use std::error::Error;
+use std::io::{self, IsTerminal};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+pub enum AuthCommand {
+    /// Save the token for a private relay in the OS keychain
+    Login {
+        /// The relay, as host[:port], instead of the config file's
+        #[structopt(long)]
+        relay: Option<String>,
+    },
+
+    /// Remove the token for a relay from the OS keychain
+    Logout {
+        /// The relay, as host[:port], instead of the config file's
+        #[structopt(long)]
+        relay: Option<String>,
+    },
+}
+
+/// Helper: prompt for the token, or read it from stdin when piped in
+fn read_token(relay: &str) -> Result<String, Box<dyn Error>> {
+    if io::stdin().is_terminal() {
+        let token = Password::new()
+            .with_prompt(prompt!("Token for {}: ", relay))
+            .interact()?;
+        return Ok(token);
+    }
+    let mut token = String::new();
+    io::stdin().read_line(&mut token)?;
+    match token.trim_end_matches(&['\r', '\n'][..]) {
+        "" => Err(io::Error::new(io::ErrorKind::InvalidInput, "no token was given").into()),
+        token => Ok(token.to_string()),
+    }
+}
+
+/// Save or remove a relay's token in the OS keychain
+pub fn run(cmd: AuthCommand, mut cfg: AppConfig) -> Result<(), Box<dyn Error>> {
+    let (AuthCommand::Login { relay } | AuthCommand::Logout { relay }) = &cmd;
+    if let Some(relay) = relay {
+        cfg.set_relay(relay)?;
+    }
+    let relay = format!("{}:{}", cfg.relay_host, cfg.relay_port);
+
+    match cmd {
+        AuthCommand::Login { .. } => {
+            let token = read_token(&relay)?;
+            cfg.save_relay_token(&token)?;
+            log_success!("Saved the token for {} in the keychain", relay.yellow());
+            if cfg.relay_secret.is_some() {
+                log_status!(
+                    "portal.toml's relay_secret is used instead, remove it to use the keychain"
+                );
+            }
+        }
+        AuthCommand::Logout { .. } => match cfg.delete_relay_token()? {
+            true => log_success!("Removed the token for {}", relay.yellow()),
+            false => log_error!("No token saved for {}", relay.yellow()),
+        },
+    }
+    Ok(())
+}
@@ -0,0 +1,345 @@
+use crate::{MULTI, PSTYLE, SSTYLE};
+use colored::*;
+use dialoguer::Confirm;
+use indicatif::{HumanBytes, ProgressBar};
+use portal::{errors::PortalError, Direction, PROTOCOL_VERSION};
+use portal_client_core::{
+    passphrase_entropy, relay_version, AppConfig, Event, FileStatus, Frontend, Relay, Summary,
+    TransferInfo, VersionHint,
+};
+use prettytable::Table;
+use std::error::Error;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+
+/// How often progress is printed with --plain
+const PLAIN_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Display what happened to each file once the session has ended
+fn display_summary(summary: &Summary) {
+    if summary.files.is_empty() {
+        return;
+    }
+    let seconds = |d: Duration| format!("{:.2}s", d.as_secs_f64());
+    let speed = |rate: f64| format!("{}/s", HumanBytes(rate as u64));
+
+    let mut table = Table::new();
+    table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    table.add_row(
+        row![Fy->"Name", Fy->"Size", Fy->"Time", Fy->"Speed", Fy->"SHA-256", Fy->"Status"],
+    );
+    for file in &summary.files {
+        let status = match file.status {
+            FileStatus::Complete => "complete",
+            FileStatus::Quarantined => "quarantined",
+            FileStatus::Refused => "refused",
+            FileStatus::Failed => "failed",
+            FileStatus::Aborted => "aborted",
+        };
+        table.add_row(row![
+            file.filename,
+            HumanBytes(file.size),
+            seconds(file.duration),
+            speed(file.rate()),
+            file.digest.as_deref().unwrap_or("-"),
+            status
+        ]);
+    }
+    table.add_row(row![
+        b->format!("Total ({} files)", summary.files.len()),
+        b->HumanBytes(summary.size()),
+        b->seconds(summary.duration()),
+        b->speed(summary.rate()),
+        "",
+        ""
+    ]);
+    crate::print_table(&table);
+    if let Some(rtt) = summary.rtt {
+        log_status!("Round trip to peer: {:.1}ms", rtt.as_secs_f64() * 1000.0);
+    }
+}
+
+/// Progress of the current file, printed as single lines with --plain
+struct PlainProgress {
+    filename: String,
+
+    /// Unknown for streams
+    filesize: Option<u64>,
+    transferred: u64,
+    started: Instant,
+    reported: Instant,
+}
+
+impl PlainProgress {
+    /// Print the progress if it hasn't been for a while, or if forced
+    fn report(&mut self, force: bool) {
+        if !force && self.reported.elapsed() < PLAIN_INTERVAL {
+            return;
+        }
+        self.reported = Instant::now();
+        let rate = self.transferred as f64 / self.started.elapsed().as_secs_f64().max(0.001);
+        let done = match self.filesize {
+            Some(size) => format!(
+                "{} of {} ({}%)",
+                HumanBytes(self.transferred),
+                HumanBytes(size),
+                (self.transferred * 100).checked_div(size).unwrap_or(100)
+            ),
+            None => HumanBytes(self.transferred).to_string(),
+        };
+        log_status!(
+            "{}: {} at {}/s",
+            self.filename,
+            done,
+            HumanBytes(rate as u64)
+        );
+    }
+}
+
+/// Renders transfer events to the terminal
+pub struct Terminal {
+    dir: Direction,
+    bar: Option<ProgressBar>,
+    plain: Option<PlainProgress>,
+    connected: bool,
+    paired: bool,
+
+    /// Ask the user to confirm the session fingerprint before transferring
+    confirm_fingerprint: bool,
+
+    /// Ask the relay for its version to explain a failed handshake
+    check_version: bool,
+
+    /// Names the peer when several sessions run at once
+    label: Option<String>,
+}
+
+impl Terminal {
+    pub fn new(dir: Direction, cfg: &AppConfig) -> Self {
+        Terminal {
+            dir,
+            bar: None,
+            plain: None,
+            connected: false,
+            paired: false,
+            confirm_fingerprint: cfg.confirm_fingerprint,
+            check_version: cfg.check_version,
+            label: None,
+        }
+    }
+
+    /// Name the peer in this session's output, to tell it apart from
+    /// other sessions running at the same time
+    pub fn labelled(mut self, label: String) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// Helper: how the peer is referred to
+    fn peer(&self) -> &str {
+        self.label.as_deref().unwrap_or("your peer")
+    }
+
+    /// Helper: a file's name, along with the label if any
+    fn file(&self, filename: &str) -> String {
+        match &self.label {
+            Some(label) => format!("{}: {}", label, filename),
+            None => filename.to_string(),
+        }
+    }
+
+    /// Explain a failed transfer based on how far it got
+    pub fn explain_failure(&self, err: &(dyn Error + 'static), relay: &Relay) {
+        match err.downcast_ref() {
+            Some(PortalError::PossibleInterference) => log_error!(
+                "Handshakes with this pass-phrase keep failing, someone may be interfering.
+            Start over with a new pass-phrase."
+            ),
+            Some(PortalError::RelayTooNew(min)) => log_error!(
+                "The relay requires protocol v{} or newer, this client speaks v{}.
+            Upgrade portal to use this relay.",
+                min,
+                PROTOCOL_VERSION
+            ),
+            Some(PortalError::RelayTooOld(version)) => log_error!(
+                "The relay speaks protocol v{}, which this client no longer supports.
+            Use another relay, or ask its operator to upgrade.",
+                version
+            ),
+            Some(PortalError::PeerTooOld) => log_error!(
+                "The peer's portal is too old to transfer with this one.
+            Ask them to upgrade."
+            ),
+            _ if !self.connected => log_error!("Failed to connect to relay"),
+            _ if !self.paired => {
+                log_error!("Failed to complete portal handshake.");
+                self.hint_version(relay);
+            }
+            _ => {}
+        }
+    }
+
+    /// Helper: suggest whether version skew is to blame for a failed
+    /// handshake, asking the relay unless disabled with check_version
+    fn hint_version(&self, relay: &Relay) {
+        if !self.check_version {
+            log_status!("Verify client version & passphrase.");
+            return;
+        }
+        let banner = relay_version(relay).ok();
+        match VersionHint::new(banner.as_ref()) {
+            VersionHint::UpgradeClient { relay } => log_status!(
+                "The relay speaks protocol v{}, newer than this client's v{}.
+            Upgrading portal will likely fix this.",
+                relay,
+                PROTOCOL_VERSION
+            ),
+            VersionHint::RelayOutdated { relay } => log_status!(
+                "The relay speaks protocol v{}, older than this client's v{}.
+            If your peer has upgraded too, verify the passphrase.",
+                relay,
+                PROTOCOL_VERSION
+            ),
+            VersionHint::Compatible => log_status!(
+                "This client & the relay both speak protocol v{}.
+            Verify the passphrase, and that your peer's client is up to date.",
+                PROTOCOL_VERSION
+            ),
+            VersionHint::Unknown => log_status!(
+                "The relay didn't report its version, it may be outdated.
+            Verify client version & passphrase."
+            ),
+        }
+    }
+}
+
+impl Frontend for Terminal {
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Connected(relay) => {
+                self.connected = true;
+                log_success!("Connected to {}!", relay);
+            }
+            Event::Direct(addr) => {
+                self.connected = true;
+                log_success!("Found peer on the local network at {}!", addr);
+            }
+            Event::Passphrase(phrase) => {
+                log_success!("Tell {} their pass-phrase is: {:?}", self.peer(), phrase);
+                log_status!(
+                    "Pass-phrase strength: about {:.0} bits",
+                    passphrase_entropy(phrase)
+                );
+            }
+            Event::Paired { fingerprint } => {
+                self.paired = true;
+                log_success!(
+                    "Session fingerprint: {}, verify these match with {}",
+                    fingerprint.bold(),
+                    self.peer()
+                );
+                match self.dir {
+                    Direction::Sender => log_status!("Starting transfer..."),
+                    Direction::Receiver => {
+                        log_success!("Completed portal handshake with peer.");
+                        log_status!("Waiting for peer to begin transfer...");
+                    }
+                }
+            }
+            Event::FileStarted(metadata) if crate::PLAIN.load(Ordering::Relaxed) => {
+                let progress = PlainProgress {
+                    filename: self.file(&metadata.filename),
+                    filesize: (!metadata.is_stream()).then_some(metadata.filesize),
+                    transferred: 0,
+                    started: Instant::now(),
+                    reported: Instant::now(),
+                };
+                log_status!("{}: started", progress.filename);
+                self.plain = Some(progress);
+            }
+            Event::FileStarted(metadata) => {
+                // Create a new bar, with the filename as the message.
+                // Streams have no known length, so get a spinner instead
+                let pb = match metadata.is_stream() {
+                    true => MULTI.add(ProgressBar::new_spinner()),
+                    false => MULTI.add(ProgressBar::new(metadata.filesize)),
+                };
+                pb.set_style(match metadata.is_stream() {
+                    true => SSTYLE.clone(),
+                    false => PSTYLE.clone(),
+                });
+                pb.set_message(self.file(&metadata.filename));
+
+                // Required to render
+                pb.tick();
+                self.bar = Some(pb);
+            }
+            Event::Progress { transferred, .. } => {
+                if let Some(pb) = &self.bar {
+                    pb.set_position(transferred as u64);
+                }
+                if let Some(progress) = &mut self.plain {
+                    progress.transferred = transferred as u64;
+                    progress.report(false);
+                }
+            }
+            Event::FileFinished(_) => {
+                if let Some(pb) = self.bar.take() {
+                    pb.finish();
+                }
+                if let Some(mut progress) = self.plain.take() {
+                    progress.report(true);
+                }
+            }
+            Event::FileAborted(metadata) => {
+                if let Some(pb) = self.bar.take() {
+                    pb.abandon();
+                }
+                self.plain = None;
+                log_error!(
+                    "{}: changed after it was offered, skipped",
+                    self.file(&metadata.filename)
+                );
+            }
+            Event::Quarantined { path, reason } => {
+                log_error!("{}, quarantined in {}", reason, path.display());
+            }
+            Event::Extracted {
+                archive,
+                entries,
+                skipped,
+            } => {
+                log_success!("Extracted {} entries from {}", entries, archive.display());
+                if skipped > 0 {
+                    log_status!("Skipped {} links or special files", skipped);
+                }
+            }
+            Event::Summary(summary) => display_summary(summary),
+        }
+    }
+
+    // User callback to confirm/deny a transfer
+    fn confirm(&mut self, info: &TransferInfo) -> bool {
+        log_status!("Incoming files:");
+        crate::display_info(info);
+        Confirm::new()
+            .with_prompt(prompt!("Download the file(s)?"))
+            .interact()
+            .is_ok_and(|r| r)
+    }
+
+    // Only transfer once the user confirms their peer has the same key
+    fn confirm_fingerprint(&mut self, _fingerprint: &str) -> bool {
+        if !self.confirm_fingerprint {
+            return true;
+        }
+        let confirmed = Confirm::new()
+            .with_prompt(prompt!("Does your peer see the same fingerprint?"))
+            .interact()
+            .is_ok_and(|r| r);
+        if !confirmed {
+            log_error!("Fingerprints weren't confirmed, abandoning the session");
+        }
+        confirmed
+    }
+}
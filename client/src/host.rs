@@ -0,0 +1,36 @@
+use colored::*;
+use std::error::Error;
+use std::net::{IpAddr, Ipv4Addr, UdpSocket};
+use structopt::StructOpt;
+
+/// Best guess at the address peers can reach this machine on, the
+/// source address of the default route
+fn local_ip() -> IpAddr {
+    let probe = || -> std::io::Result<IpAddr> {
+        // Connecting a UDP socket only selects a route, nothing is sent
+        let socket = UdpSocket::bind("0.0.0.0:0")?;
+        socket.connect("192.0.2.1:9")?;
+        Ok(socket.local_addr()?.ip())
+    };
+    probe().unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST))
+}
+
+/// Run a relay on this machine until the first session has
+/// finished, or until interrupted when persistent
+pub fn host_relay(port: u16, persistent: bool) -> Result<(), Box<dyn Error>> {
+    let port = port.to_string();
+    let mut args = vec!["portal-relay", "--port", &port];
+    if !persistent {
+        args.push("--one-shot");
+    }
+    let opt = portal_relay::Opt::from_iter(args);
+
+    let addr = format!("{}:{}", local_ip(), port);
+    log_status!(
+        "Relaying on {}, give this address to your peer",
+        addr.yellow()
+    );
+    log_status!("Both sides connect with: portal send|recv --relay {}", addr);
+
+    portal_relay::run(opt)
+}
@@ -1,16 +1,16 @@
 #[macro_export]
 macro_rules! log_status {
-    ($($arg:tt)*) => (println!("{} {}", "[*]".blue().bold(), format_args!($($arg)*)));
+    ($($arg:tt)*) => ($crate::say(format_args!("{} {}", "[*]".blue().bold(), format_args!($($arg)*))));
 }
 
 #[macro_export]
 macro_rules! log_error {
-    ($($arg:tt)*) => (println!("{} {}", "[!]".red().bold(), format_args!($($arg)*)));
+    ($($arg:tt)*) => ($crate::say(format_args!("{} {}", "[!]".red().bold(), format_args!($($arg)*))));
 }
 
 #[macro_export]
 macro_rules! log_success {
-    ($($arg:tt)*) => (println!("{} {}", "[+]".green().bold(), format_args!($($arg)*)));
+    ($($arg:tt)*) => ($crate::say(format_args!("{} {}", "[+]".green().bold(), format_args!($($arg)*))));
 }
 
 #[macro_export]
@@ -1,13 +1,14 @@
 extern crate portal_lib as portal;
 
 use colored::*;
-use dns_lookup::lookup_host;
 use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
-use portal::{errors::PortalError, TransferInfo};
+use portal::{Direction, Priority};
+use portal_client_core::{AppConfig, Archive, Filter, TransferInfo};
 use prettytable::Table;
 use std::error::Error;
-use std::net::TcpStream;
+use std::fmt;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use structopt::StructOpt;
 
 #[macro_use]
@@ -18,29 +19,55 @@ extern crate prettytable;
 
 #[macro_use]
 mod macros;
-mod config;
-use config::AppConfig;
 
-/// EFF's dice generated wordlist
-mod wordlist;
+/// Saving relay tokens in the OS keychain
+mod auth;
+use auth::AuthCommand;
+
+/// Terminal rendering of transfer events
+mod frontend;
+use frontend::Terminal;
+
+/// Hosting a relay
+#[cfg(target_os = "linux")]
+mod host;
+
+/// Choosing files to send interactively
+mod picker;
+
+/// Named presets of settings
+mod preset;
+use preset::PresetCommand;
 
 /// Receiver path
 mod receive;
-use receive::recv_all;
+use receive::{recv_all, recv_stdout};
 
 /// Sender path
 mod send;
-use send::send_all;
+use send::{send_all, send_queue, Outgoing};
+
+/// Print progress as occasional plain lines instead of bars, set by --plain
+pub static PLAIN: AtomicBool = AtomicBool::new(false);
+
+/// Set by recv --stdout, whose output is the received file, so that
+/// everything else is printed to stderr instead
+pub static STDOUT_TAKEN: AtomicBool = AtomicBool::new(false);
 
 lazy_static! {
     /// Global multi-bar that contains other progress bars
     pub static ref MULTI: MultiProgress =
-        MultiProgress::with_draw_target(ProgressDrawTarget::stdout());
+        MultiProgress::with_draw_target(match STDOUT_TAKEN.load(Ordering::Relaxed) {
+            true => ProgressDrawTarget::stderr(),
+            false => ProgressDrawTarget::stdout(),
+        });
 
     /// All bars have the same style
     pub static ref PSTYLE: ProgressStyle = ProgressStyle::default_bar()
         .template("[{elapsed_precise}] [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({eta}) {msg}")
         .progress_chars("#>-");
+    pub static ref SSTYLE: ProgressStyle = ProgressStyle::default_spinner()
+        .template("[{elapsed_precise}] {spinner} {bytes} ({bytes_per_sec}) {msg}");
 }
 
 #[derive(Debug, StructOpt)]
@@ -52,9 +79,77 @@ lazy_static! {
 enum Command {
     /// Send file(s) to a peer
     Send {
-        /// List of files to send
+        /// List of files to send, picked interactively if none are given
         #[structopt(parse(from_os_str))]
         files: Vec<PathBuf>,
+
+        /// Afterwards, keep sending each file or directory placed in
+        /// this directory in its own session
+        #[structopt(long, parse(from_os_str))]
+        queue: Option<PathBuf>,
+
+        /// Send without confirming unusually large transfers or
+        /// weak pass-phrases
+        #[structopt(long)]
+        force: bool,
+
+        /// Send each directory as a single tar archive, built while it
+        /// is sent, instead of file by file
+        #[structopt(long)]
+        archive: bool,
+
+        /// Compress archives with gzip
+        #[structopt(long, requires = "archive")]
+        gzip: bool,
+
+        /// Only send files matching this glob, matched against paths
+        /// within the directories sent and against names alone
+        #[structopt(long, number_of_values = 1)]
+        include: Vec<String>,
+
+        /// Leave out files & directories matching this glob, i.e.
+        /// --exclude target --exclude .git
+        #[structopt(long, number_of_values = 1)]
+        exclude: Vec<String>,
+
+        /// Tell the receiver who this is from, or what it is, i.e.
+        /// --note "From: build server 7"
+        #[structopt(long)]
+        note: Option<String>,
+
+        /// Use this pass-phrase, an ID word followed by password words
+        /// joined with dashes, instead of generating one. It is used for
+        /// every session when sending a --queue.
+        #[structopt(long)]
+        passphrase: Option<String>,
+
+        /// Use this relay, as host[:port], instead of the config file's
+        #[structopt(long)]
+        relay: Option<String>,
+
+        /// Use the settings saved in this preset
+        #[structopt(long)]
+        preset: Option<String>,
+
+        /// Print progress as periodic plain lines without colors, for
+        /// logs, CI & dumb terminals
+        #[structopt(long)]
+        plain: bool,
+
+        /// Mark the transfer as bulk traffic, which managed networks
+        /// may let other traffic go ahead of
+        #[structopt(long)]
+        bulk: bool,
+
+        /// Ask to confirm that your peer sees the same session
+        /// fingerprint before anything is transferred
+        #[structopt(long)]
+        require_fingerprint_confirm: bool,
+
+        /// Send the same files to this many receivers, each given a
+        /// pass-phrase of its own. Only sent through the relay.
+        #[structopt(long, conflicts_with = "queue")]
+        copies: Option<u32>,
     },
 
     /// Receive file(s) from a peer
@@ -62,6 +157,57 @@ enum Command {
         /// Optional: override the download directory in the config file.
         #[structopt(short, long)]
         download_dir: Option<PathBuf>,
+
+        /// Use this relay, as host[:port], instead of the config file's
+        #[structopt(long)]
+        relay: Option<String>,
+
+        /// Use the settings saved in this preset
+        #[structopt(long)]
+        preset: Option<String>,
+
+        /// Print progress as periodic plain lines without colors, for
+        /// logs, CI & dumb terminals
+        #[structopt(long)]
+        plain: bool,
+
+        /// Unpack received .tar, .tar.gz & .zip archives into the
+        /// download directory
+        #[structopt(long)]
+        extract: bool,
+
+        /// Write the received file to stdout, for piping into another
+        /// program. Only a single file can be received this way.
+        #[structopt(long, conflicts_with_all = &["extract", "download-dir"])]
+        stdout: bool,
+
+        /// Mark the transfer as bulk traffic, which managed networks
+        /// may let other traffic go ahead of
+        #[structopt(long)]
+        bulk: bool,
+
+        /// Ask to confirm that your peer sees the same session
+        /// fingerprint before anything is transferred
+        #[structopt(long)]
+        require_fingerprint_confirm: bool,
+    },
+
+    /// Save, remove or list named presets of settings in portal.toml
+    Preset(PresetCommand),
+
+    /// Save or remove the token for a private relay in the OS keychain
+    Auth(AuthCommand),
+
+    /// Run a relay on this machine for you & your peer to use
+    #[cfg(target_os = "linux")]
+    Relay {
+        /// Listen on this port
+        #[structopt(long, default_value = "13265")]
+        port: u16,
+
+        /// Keep relaying after the first session has finished
+        #[structopt(long)]
+        persistent: bool,
     },
 }
 
@@ -72,10 +218,45 @@ fn display_info(info: &TransferInfo) {
     table.add_row(row![Fy->"Name", Fy->"Size"]);
 
     for entry in &info.all {
+        if entry.is_stream() {
+            table.add_row(row![entry.filename, "streamed"]);
+            continue;
+        }
         table.add_row(row![entry.filename, entry.filesize]);
+        for copy in &entry.copies {
+            table.add_row(row![copy, entry.filesize]);
+        }
+    }
+
+    print_table(&table);
+
+    // Chosen by the sender, so keep it from moving the cursor etc.
+    if let Some(note) = &info.note {
+        let note = note.chars().filter(|c| !c.is_control()).collect::<String>();
+        log_status!("Note: {}", note);
     }
+}
 
-    table.printstd();
+/// Print a line of output, to stderr if stdout is taken
+pub fn say(args: fmt::Arguments) {
+    match STDOUT_TAKEN.load(Ordering::Relaxed) {
+        true => eprintln!("{}", args),
+        false => println!("{}", args),
+    }
+}
+
+/// Print a table, without colors if --plain was given
+fn print_table(table: &Table) {
+    if STDOUT_TAKEN.load(Ordering::Relaxed) {
+        eprint!("{}", table);
+        return;
+    }
+    match PLAIN.load(Ordering::Relaxed) {
+        true => print!("{}", table),
+        false => {
+            table.printstd();
+        }
+    }
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
@@ -86,38 +267,89 @@ fn main() -> Result<(), Box<dyn Error>> {
     #[cfg(target_os = "windows")]
     control::set_virtual_terminal(true).unwrap();
 
+    // Hosting a relay doesn't need the config
+    #[cfg(target_os = "linux")]
+    if let Command::Relay { port, persistent } = cmd {
+        return host::host_relay(port, persistent);
+    }
+
+    // Keep stdout for the received file
+    if let Command::Recv { stdout: true, .. } = &cmd {
+        STDOUT_TAKEN.store(true, Ordering::Relaxed);
+    }
+
     // Load/create config location
-    let mut cfg: AppConfig = confy::load("portal")?;
+    let mut cfg = AppConfig::load()?;
+    if let Command::Preset(cmd) = cmd {
+        return preset::run(cmd, cfg);
+    }
+    if let Command::Auth(cmd) = cmd {
+        return auth::run(cmd, cfg);
+    }
+
+    // Keep the output free of escape codes if asked
+    if let Command::Send { plain: true, .. } | Command::Recv { plain: true, .. } = &cmd {
+        PLAIN.store(true, Ordering::Relaxed);
+        colored::control::set_override(false);
+        dialoguer::console::set_colors_enabled(false);
+    }
+
+    // Apply the preset before any other overrides
+    if let Command::Send {
+        preset: Some(preset),
+        ..
+    }
+    | Command::Recv {
+        preset: Some(preset),
+        ..
+    } = &cmd
+    {
+        cfg.use_preset(preset)?;
+    }
+
+    // Check if we need to override the relay
+    if let Command::Send {
+        relay: Some(relay), ..
+    }
+    | Command::Recv {
+        relay: Some(relay), ..
+    } = &cmd
+    {
+        cfg.set_relay(relay)?;
+    }
+
     log_status!(
         "Using portal.toml config, relay: {}!",
         cfg.relay_host.yellow()
     );
 
     // Check if we need to override the download location
-    if let Command::Recv { download_dir } = &cmd {
+    if let Command::Recv { download_dir, .. } = &cmd {
         cfg.download_location = download_dir
             .as_ref()
             .map_or(cfg.download_location, |val| val.clone());
     }
 
-    // Determin the IP address to connect to
-    let addr: std::net::IpAddr = match cfg.relay_host.parse() {
-        Ok(res) => res,
-        Err(_) => *lookup_host(&cfg.relay_host)?
-            .first()
-            .ok_or(PortalError::NoPeer)?,
-    };
+    // Check if the fingerprint must be confirmed
+    if let Command::Send {
+        require_fingerprint_confirm: true,
+        ..
+    }
+    | Command::Recv {
+        require_fingerprint_confirm: true,
+        ..
+    } = &cmd
+    {
+        cfg.confirm_fingerprint = true;
+    }
 
-    // Use the port config value to create an IP/port pair
-    let addr: std::net::SocketAddr = format!("{}:{}", addr, cfg.relay_port).parse()?;
+    // Check if the transfer should yield to other traffic
+    if let Command::Send { bulk: true, .. } | Command::Recv { bulk: true, .. } = &cmd {
+        cfg.priority = Priority::Bulk;
+    }
 
-    // Connect to the relay
-    let timeout = std::time::Duration::new(6, 0);
-    let mut client = TcpStream::connect_timeout(&addr, timeout).map_err(|e| {
-        log_error!("Failed to connect to relay");
-        e
-    })?;
-    log_success!("Connected to {:?}!", addr);
+    // Determine how to reach the relay
+    let relay = cfg.relay()?;
 
     // Create a hidden bar so the progress bar doesn't
     // go out of scope.
@@ -130,8 +362,66 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     // Begin the transfer
     let result = match cmd {
-        Command::Send { files } => send_all(&mut client, files),
-        Command::Recv { .. } => recv_all(&mut client, cfg.download_location),
+        Command::Send {
+            mut files,
+            queue,
+            force,
+            archive,
+            gzip,
+            include,
+            exclude,
+            note,
+            passphrase,
+            copies,
+            ..
+        } => {
+            if let Some(phrase) = &passphrase {
+                send::check_passphrase(phrase, &cfg, force)?;
+            }
+            if files.is_empty() && queue.is_none() {
+                files = picker::pick_files()?;
+            }
+            let outgoing = Outgoing {
+                archive: match (archive, gzip) {
+                    (false, _) => None,
+                    (true, false) => Some(Archive::Tar),
+                    (true, true) => Some(Archive::TarGz),
+                },
+                filter: Filter::new(&include, &exclude)?,
+                note: note.or(cfg.note.clone()),
+                copies,
+            };
+            let mut result = Ok(());
+            let phrase = passphrase.as_deref();
+            if !files.is_empty() || queue.is_none() {
+                let mut terminal = Terminal::new(Direction::Sender, &cfg);
+                result = send_all(&relay, files, &outgoing, phrase, &cfg, force, &mut terminal);
+            }
+            match queue {
+                Some(dir) if result.is_ok() => {
+                    send_queue(&relay, &dir, &outgoing, phrase, &cfg, force)
+                }
+                _ => result,
+            }
+        }
+        Command::Recv { stdout: true, .. } => recv_stdout(
+            &relay,
+            cfg.lan,
+            &mut Terminal::new(Direction::Receiver, &cfg),
+        ),
+        Command::Recv { extract, .. } => recv_all(
+            &relay,
+            &cfg.download_location,
+            cfg.lan,
+            &cfg.file_types,
+            extract || cfg.extract_archives,
+            &mut Terminal::new(Direction::Receiver, &cfg),
+        ),
+        Command::Preset(_) | Command::Auth(_) => {
+            unreachable!("handled after loading the config")
+        }
+        #[cfg(target_os = "linux")]
+        Command::Relay { .. } => unreachable!("handled before loading the config"),
     };
 
     // Allow the hidden bar to go out of scope
@@ -0,0 +1,86 @@
+use colored::*;
+use dialoguer::console::user_attended;
+use dialoguer::Select;
+use portal::errors::PortalError;
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+/// Entries shown at once, the rest are reached by scrolling
+const PAGE_SIZE: usize = 15;
+
+/// Browse from the current directory for files & directories to send,
+/// for when none were given on the command line
+pub fn pick_files() -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    if !user_attended() {
+        return Ok(Vec::new());
+    }
+
+    let mut dir = std::env::current_dir()?;
+    let mut picked = BTreeSet::new();
+    let mut cursor = 0;
+    log_status!("No files given, pick some to send (Esc to cancel)");
+
+    loop {
+        // Directories first, hidden entries left out
+        let mut entries = fs::read_dir(&dir)?
+            .filter_map(Result::ok)
+            .filter(|e| !e.file_name().to_string_lossy().starts_with('.'))
+            .map(|e| (!e.path().is_dir(), e.path()))
+            .collect::<Vec<_>>();
+        entries.sort();
+
+        let mut items = vec![
+            format!("Send {} selected", picked.len())
+                .green()
+                .to_string(),
+            match picked.contains(&dir) {
+                true => "[x] This directory".to_string(),
+                false => "[ ] This directory".to_string(),
+            },
+            "../".to_string(),
+        ];
+        for (is_file, path) in &entries {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            items.push(match (is_file, picked.contains(path)) {
+                (false, _) => format!("{}/", name).blue().to_string(),
+                (true, true) => format!("[x] {}", name),
+                (true, false) => format!("[ ] {}", name),
+            });
+        }
+
+        let choice = Select::new()
+            .with_prompt(dir.display().to_string())
+            .items(&items)
+            .default(cursor)
+            .max_length(PAGE_SIZE)
+            .interact_opt()?;
+        // Stay on a toggled entry, start at the top of a new directory
+        cursor = choice.unwrap_or_default();
+        match choice {
+            None => return Err(PortalError::Cancelled.into()),
+            Some(0) => break,
+            Some(1) => toggle(&mut picked, &dir),
+            Some(2) => {
+                dir.pop();
+                cursor = 0;
+            }
+            Some(n) => match &entries[n - 3] {
+                (false, path) => {
+                    dir = path.clone();
+                    cursor = 0;
+                }
+                (true, path) => toggle(&mut picked, path),
+            },
+        }
+    }
+    Ok(picked.into_iter().collect())
+}
+
+/// Helper: select a path, or deselect it if it already was
+fn toggle(picked: &mut BTreeSet<PathBuf>, path: &PathBuf) {
+    if !picked.remove(path) {
+        picked.insert(path.clone());
+    }
+}
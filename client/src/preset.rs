@@ -0,0 +1,115 @@
+use colored::*;
+use portal_client_core::{AppConfig, Preset};
+use prettytable::Table;
+use std::error::Error;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+pub enum PresetCommand {
+    /// Save a preset, replacing any with the same name
+    Add {
+        /// Name to select the preset by
+        name: String,
+
+        /// Use this relay, as host[:port]
+        #[structopt(long)]
+        relay: Option<String>,
+
+        /// Download received files to this directory
+        #[structopt(long, parse(from_os_str))]
+        download_dir: Option<PathBuf>,
+
+        /// Warn before sending more than this many bytes
+        #[structopt(long)]
+        warn_size: Option<u64>,
+
+        /// Warn before sending more than this many files
+        #[structopt(long)]
+        warn_files: Option<usize>,
+
+        /// Connect to the relay through this SOCKS5 proxy
+        #[structopt(long)]
+        socks_proxy: Option<SocketAddr>,
+
+        /// Whether to look for the peer on the local network
+        #[structopt(long)]
+        lan: Option<bool>,
+
+        /// Secret shared with a private relay
+        #[structopt(long)]
+        relay_secret: Option<String>,
+    },
+
+    /// Remove a preset
+    Remove {
+        /// Name of the preset
+        name: String,
+    },
+
+    /// List the saved presets
+    List,
+}
+
+/// Add, remove or list the presets in portal.toml
+pub fn run(cmd: PresetCommand, mut cfg: AppConfig) -> Result<(), Box<dyn Error>> {
+    match cmd {
+        PresetCommand::Add {
+            name,
+            relay,
+            download_dir,
+            warn_size,
+            warn_files,
+            socks_proxy,
+            lan,
+            relay_secret,
+        } => {
+            let preset = Preset {
+                relay,
+                download_location: download_dir,
+                warn_size,
+                warn_files,
+                socks_proxy,
+                lan,
+                relay_secret,
+            };
+            cfg.presets.insert(name.clone(), preset);
+            cfg.store()?;
+            log_success!("Saved preset {}", name.yellow());
+        }
+        PresetCommand::Remove { name } => match cfg.presets.remove(&name) {
+            Some(_) => {
+                cfg.store()?;
+                log_success!("Removed preset {}", name.yellow());
+            }
+            None => log_error!("No preset named {}", name.yellow()),
+        },
+        PresetCommand::List => {
+            if cfg.presets.is_empty() {
+                log_status!("No presets, add one with: portal preset add <name>");
+                return Ok(());
+            }
+            let show = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+            let mut table = Table::new();
+            table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+            table.add_row(row![Fy->"Name", Fy->"Relay", Fy->"Downloads", Fy->"LAN", Fy->"Proxy"]);
+            for (name, preset) in &cfg.presets {
+                table.add_row(row![
+                    name,
+                    show(preset.relay.clone()),
+                    show(
+                        preset
+                            .download_location
+                            .as_ref()
+                            .map(|d| d.display().to_string())
+                    ),
+                    show(preset.lan.map(|l| l.to_string())),
+                    show(preset.socks_proxy.map(|p| p.to_string())),
+                ]);
+            }
+            table.printstd();
+        }
+    }
+    Ok(())
+}
@@ -1,89 +1,49 @@
-use crate::{MULTI, PSTYLE};
+use crate::frontend::Terminal;
 use colored::*;
-use dialoguer::{Confirm, Input};
-use indicatif::ProgressBar;
-use portal::{errors::PortalError, Direction, Portal, TransferInfo};
-use std::{
-    error::Error,
-    net::TcpStream,
-    path::{Path, PathBuf},
-};
+use dialoguer::Input;
+use portal_client_core::{FileTypes, Relay};
+use std::{error::Error, path::Path};
 
 /// The receiver must prompt the user for the pass-phrase
-/// Splits the input and returns a tuple (id, password)
-fn prompt_password() -> Result<(String, String), Box<dyn Error>> {
+fn prompt_password() -> Result<String, Box<dyn Error>> {
     let input: String = Input::new()
         .with_prompt(prompt!("Enter pass-phrase: "))
         .interact_text()?;
-    let mut input = input.split('-');
-    let id = input.next().ok_or(PortalError::NoneError)?.to_string();
-    let opass = input.collect::<Vec<&str>>().join("-");
-    Ok((id, opass))
-}
-
-// User callback to confirm/deny a transfer
-fn confirm_download(info: &TransferInfo) -> bool {
-    log_status!("Incoming files:");
-    crate::display_info(info);
-    Confirm::new()
-        .with_prompt(prompt!("Download the file(s)?"))
-        .interact()
-        .map_or(false, |r| r)
+    Ok(input)
 }
 
 /// Recv a file
-pub fn recv_all(client: &mut TcpStream, download_directory: PathBuf) -> Result<(), Box<dyn Error>> {
+pub fn recv_all(
+    relay: &Relay,
+    download_directory: &Path,
+    lan: bool,
+    file_types: &FileTypes,
+    extract: bool,
+    terminal: &mut Terminal,
+) -> Result<(), Box<dyn Error>> {
     // Receiver must enter the password
-    let (id, pass) = prompt_password()?;
-
-    // Initialize portal
-    let mut portal = Portal::init(Direction::Receiver, id, pass).map_err(|e| {
-        log_error!("Failed to initialize portal");
-        e
-    })?;
-
-    // Complete handshake
-    portal.handshake(client).map_err(|e| {
-        log_error!(
-            "Failed to complete portal handshake.
-            Verify client version & passphrase."
-        );
-        e
-    })?;
-
-    log_success!("Completed portal handshake with peer.");
-
-    // TODO: Establish P2P QUIC connection here?
-
-    log_status!("Waiting for peer to begin transfer...");
-
-    // For each file create a new progress bar
-    for metadata in portal.incoming(client, Some(confirm_download))? {
-        // Create a new bar
-        let pb = MULTI.add(ProgressBar::new(metadata.filesize));
-        pb.set_style(PSTYLE.clone());
-
-        // Set filename as the message
-        pb.set_message(metadata.filename.clone());
-
-        // User callback to display progress
-        let progress = |transferred: usize| {
-            pb.set_position(transferred as u64);
-        };
-
-        // Required to render
-        pb.tick();
-
-        // Receive the file
-        let _metadata = portal.recv_file(
-            client,
-            Path::new(&download_directory),
-            Some(&metadata),
-            Some(progress),
-        )?;
-
-        pb.finish();
-    }
+    let passphrase = prompt_password()?;
+    let download = download_directory;
+    portal_client_core::recv_all(
+        relay,
+        &passphrase,
+        download,
+        lan,
+        file_types,
+        extract,
+        terminal,
+    )
+    .inspect_err(|e| terminal.explain_failure(e.as_ref(), relay))
+}
 
-    Ok(())
+/// Recv a single file into stdout
+pub fn recv_stdout(
+    relay: &Relay,
+    lan: bool,
+    terminal: &mut Terminal,
+) -> Result<(), Box<dyn Error>> {
+    let passphrase = prompt_password()?;
+    let mut stdout = std::io::stdout().lock();
+    portal_client_core::recv_into(relay, &passphrase, lan, &mut stdout, terminal)
+        .inspect_err(|e| terminal.explain_failure(e.as_ref(), relay))
 }
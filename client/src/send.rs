@@ -1,119 +1,297 @@
-use crate::wordlist::gen_phrase;
-use crate::{MULTI, PSTYLE};
+use crate::frontend::Terminal;
 use colored::*;
-use indicatif::ProgressBar;
-use portal::{errors::PortalError, Direction, Portal, TransferInfo};
-use std::fs::DirEntry;
-use std::{error::Error, net::TcpStream, path::PathBuf};
-
-/// As the sender, a pass-phrase muse be created to deliver
-/// out-of-band (in secret) to the receiver.
-fn create_password() -> (String, String) {
-    let (id, pass) = (gen_phrase(1), gen_phrase(3));
-    log_success!(
-        "Tell your peer their pass-phrase is: {:?}",
-        format!("{}-{}", id, pass)
-    );
-    (id, pass)
-}
+use dialoguer::Confirm;
+use indicatif::{HumanBytes, HumanDuration};
+use portal::{errors::PortalError, passphrase, Direction};
+use portal_client_core::{
+    generate_passphrase, passphrase_entropy, passphrase_words, preflight, probe_bandwidth,
+    validate_archive, validate_files, AppConfig, Archive, Filter, Relay, TransferInfo, Warning,
+    DEFAULT_WORDS, MAX_COPIES,
+};
+use std::fs;
+use std::thread;
+use std::time::{Duration, SystemTime};
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+};
 
-// Helper method to enumerate directories depth 1
-fn add_all(info: &mut TransferInfo, dir: PathBuf) -> Result<(), Box<dyn Error>> {
-    fn check_file(entry: &DirEntry) -> Option<PathBuf> {
-        if !entry.metadata().map_or(false, |f| f.is_file()) {
-            return None;
-        }
-        Some(entry.path())
+/// How often the queue directory is checked for new file sets
+const QUEUE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Queued items must be unmodified this long before they are sent,
+/// so files still being copied in aren't picked up
+const QUEUE_SETTLE_TIME: Duration = Duration::from_secs(2);
+
+/// Warn about unusually large transfers, with an estimate of how long
+/// they will take, and ask the user to confirm them
+fn confirm_preflight(
+    relay: &Relay,
+    info: &TransferInfo,
+    cfg: &AppConfig,
+) -> Result<(), Box<dyn Error>> {
+    let warnings = preflight(info, cfg);
+    if warnings.is_empty() {
+        return Ok(());
     }
 
-    // Collect all entries
-    let entries = std::fs::read_dir(dir)?
-        .filter_map(|res| res.as_ref().map_or(None, check_file))
-        .collect::<Vec<PathBuf>>();
+    for warning in warnings {
+        match warning {
+            Warning::TooLarge { size, limit } => log_error!(
+                "Sending {}, more than the {} configured as warn_size",
+                HumanBytes(size),
+                HumanBytes(limit)
+            ),
+            Warning::TooManyFiles { count, limit } => log_error!(
+                "Sending {} files, more than the {} configured as warn_files",
+                count,
+                limit
+            ),
+        }
+    }
 
-    // Add them individually
-    for entry in entries {
-        info.add_file(&entry)?;
+    // Estimate the transfer time from the bandwidth to the relay
+    let size = info.all.iter().map(|m| m.filesize).sum::<u64>();
+    match probe_bandwidth(relay) {
+        Ok(rate) => log_status!(
+            "Estimated transfer time: {} at {}/s",
+            HumanDuration(Duration::from_secs_f64(size as f64 / rate)),
+            HumanBytes(rate as u64)
+        ),
+        Err(_) => log_status!("Unable to estimate the transfer time"),
     }
 
-    Ok(())
+    match Confirm::new()
+        .with_prompt(prompt!("Send anyway? (skip this check with --force)"))
+        .interact()
+        .is_ok_and(|r| r)
+    {
+        true => Ok(()),
+        false => Err(PortalError::Cancelled.into()),
+    }
 }
 
-/// Converts a list of input files into TransferInfo
-pub fn validate_files(files: Vec<PathBuf>) -> Result<TransferInfo, Box<dyn Error>> {
-    // Validate that there is at least one file to send
-    if files.is_empty() {
-        log_error!("Provide at least one file to send");
-        return Err(PortalError::BadFileName.into());
+/// Refuse a pass-phrase given with --passphrase that is shorter than the
+/// config allows, and warn about one that is easy to guess
+pub fn check_passphrase(phrase: &str, cfg: &AppConfig, force: bool) -> Result<(), Box<dyn Error>> {
+    let words = passphrase_words(phrase);
+    if words < cfg.min_passphrase_words {
+        log_error!(
+            "The pass-phrase has {} words after the ID, your config requires at least {}",
+            words,
+            cfg.min_passphrase_words
+        );
+        return Err(PortalError::Cancelled.into());
     }
 
-    // Begin adding files to this transfer
-    let mut info = TransferInfo::empty();
-    for item in files {
-        match item.is_dir() {
-            true => {
-                add_all(&mut info, item)?;
-            }
-            false => {
-                info.add_file(item.as_path())?;
-            }
-        }
+    // Compare with what would have been generated
+    let bits = passphrase_entropy(phrase);
+    let generated = passphrase::entropy(DEFAULT_WORDS);
+    if bits >= generated {
+        return Ok(());
     }
+    log_error!(
+        "{} The pass-phrase has about {:.0} bits of entropy, generated ones have {:.0}.",
+        "WEAK PASS-PHRASE!".red().bold(),
+        bits,
+        generated
+    );
+    log_error!("Anyone who guesses it while you wait can receive your files.");
 
-    Ok(info)
+    match force
+        || Confirm::new()
+            .with_prompt(prompt!("Use it anyway? (skip this check with --force)"))
+            .interact()
+            .is_ok_and(|r| r)
+    {
+        true => Ok(()),
+        false => Err(PortalError::Cancelled.into()),
+    }
+}
+
+/// Files shown when listing those left out by --include & --exclude
+const SKIPPED_SHOWN: usize = 10;
+
+/// How the files given to send are collected
+#[derive(Default)]
+pub struct Outgoing {
+    /// Send directories whole as archives
+    pub archive: Option<Archive>,
+
+    /// From --include & --exclude
+    pub filter: Filter,
+
+    /// Shown to the receiver, from --note or the config
+    pub note: Option<String>,
+
+    /// Send to this many receivers at once, from --copies
+    pub copies: Option<u32>,
 }
 
-/// Send a file
-pub fn send_all(client: &mut TcpStream, files: Vec<PathBuf>) -> Result<(), Box<dyn Error>> {
+/// List the files left out by --include & --exclude
+fn display_skipped(skipped: &[PathBuf]) {
+    if skipped.is_empty() {
+        return;
+    }
+    log_status!("Skipping {} filtered files & directories:", skipped.len());
+    for path in skipped.iter().take(SKIPPED_SHOWN) {
+        println!("    {}", path.display());
+    }
+    if skipped.len() > SKIPPED_SHOWN {
+        println!("    ...and {} more", skipped.len() - SKIPPED_SHOWN);
+    }
+}
+
+/// Send a file, with the given pass-phrase or a new one
+pub fn send_all(
+    relay: &Relay,
+    files: Vec<PathBuf>,
+    outgoing: &Outgoing,
+    passphrase: Option<&str>,
+    cfg: &AppConfig,
+    force: bool,
+    terminal: &mut Terminal,
+) -> Result<(), Box<dyn Error>> {
     // Parse the input files
-    let info = validate_files(files)?;
+    let filter = &outgoing.filter;
+    let validated = match outgoing.archive {
+        Some(archive) => validate_archive(files, archive, filter),
+        None => validate_files(files, filter),
+    };
+    let (mut info, skipped) = validated.inspect_err(|_| {
+        log_error!("Provide at least one readable file to send");
+    })?;
+
+    display_skipped(&skipped);
+    if info.all.is_empty() && !skipped.is_empty() {
+        log_error!("Every file was left out by --include or --exclude");
+        return Err(PortalError::Cancelled.into());
+    }
+
+    if let Some(note) = &outgoing.note {
+        info.set_note(note)?;
+    }
 
     log_status!("Outgoing files:");
     crate::display_info(&info);
 
-    // Sender must generate the password
-    let (id, pass) = create_password();
-
-    // Initialize portal
-    let mut portal = Portal::init(Direction::Sender, id, pass).map_err(|e| {
-        log_error!("Failed to initialize portal");
-        e
-    })?;
+    if !force {
+        confirm_preflight(relay, &info, cfg)?;
+    }
 
-    // Complete handshake
-    portal.handshake(client).map_err(|e| {
-        log_error!(
-            "Failed to complete portal handshake.
-            Verify client version & passphrase."
-        );
-        e
-    })?;
+    let phrase = match passphrase {
+        Some(phrase) => phrase.to_string(),
+        None => generate_passphrase(cfg.min_passphrase_words.max(DEFAULT_WORDS)),
+    };
+    if let Some(copies) = outgoing.copies {
+        return send_copies(relay, &info, filter, &phrase, copies, cfg);
+    }
+    portal_client_core::send_all(relay, info, filter, &phrase, cfg.lan, terminal)
+        .inspect_err(|e| terminal.explain_failure(e.as_ref(), relay))
+}
 
-    // TODO: Establish P2P QUIC connection here?
+/// Send the files to several receivers at once, each with a pass-phrase
+/// of its own derived from `phrase`, only through the relay
+fn send_copies(
+    relay: &Relay,
+    info: &TransferInfo,
+    filter: &Filter,
+    phrase: &str,
+    copies: u32,
+    cfg: &AppConfig,
+) -> Result<(), Box<dyn Error>> {
+    if !(1..=MAX_COPIES).contains(&copies) {
+        log_error!("--copies must be between 1 and {}", MAX_COPIES);
+        return Err(PortalError::Cancelled.into());
+    }
 
-    log_status!("Starting transfer...");
+    let mut terminals: Vec<Terminal> = (1..=copies)
+        .map(|n| Terminal::new(Direction::Sender, cfg).labelled(format!("receiver {}", n)))
+        .collect();
+    let results = portal_client_core::send_copies(relay, info, filter, phrase, &mut terminals)?;
 
-    for (fullpath, metadata) in portal.outgoing(client, &info)? {
-        // Start the progress bar
-        let pb = MULTI.add(ProgressBar::new(metadata.filesize));
-        pb.set_style(PSTYLE.clone());
+    let mut first = None;
+    for (n, (terminal, result)) in (1..).zip(terminals.iter().zip(results)) {
+        if let Err(e) = result {
+            log_error!("Sending to receiver {} failed: {}", n, e);
+            terminal.explain_failure(e.as_ref(), relay);
+            first.get_or_insert(e);
+        }
+    }
+    match first {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
 
-        // Required to render
-        pb.tick();
+/// Send each file or directory placed in the queue directory in its own
+/// session, with a fresh pass-phrase. Sent items are moved into `.sent`,
+/// failed ones into `.failed`. Runs until interrupted.
+pub fn send_queue(
+    relay: &Relay,
+    dir: &Path,
+    outgoing: &Outgoing,
+    passphrase: Option<&str>,
+    cfg: &AppConfig,
+    force: bool,
+) -> Result<(), Box<dyn Error>> {
+    let sent = dir.join(".sent");
+    let failed = dir.join(".failed");
+    fs::create_dir_all(&sent)?;
+    fs::create_dir_all(&failed)?;
 
-        // Set filename as the message
-        pb.set_message(metadata.filename.clone());
+    log_status!("Watching {:?} for files to send...", dir);
+    loop {
+        let item = match next_queued(dir)? {
+            Some(item) => item,
+            None => {
+                thread::sleep(QUEUE_POLL_INTERVAL);
+                continue;
+            }
+        };
 
-        // User callback to display progress
-        let progress = |transferred: usize| {
-            pb.set_position(transferred as u64);
+        let mut terminal = Terminal::new(Direction::Sender, cfg);
+        let outdir = match send_all(
+            relay,
+            vec![item.clone()],
+            outgoing,
+            passphrase,
+            cfg,
+            force,
+            &mut terminal,
+        ) {
+            Ok(_) => {
+                log_success!("Sent {:?}", item);
+                &sent
+            }
+            Err(e) => {
+                log_error!("Failed to send {:?}: {:?}", item, e);
+                &failed
+            }
         };
 
-        // Begin the transfer
-        let _sent = portal.send_file(client, fullpath, Some(progress))?;
+        // Only ever send an item once
+        let name = item.file_name().ok_or(PortalError::BadFileName)?;
+        fs::rename(&item, outdir.join(name))?;
+    }
+}
+
+/// The oldest settled item in the queue directory, ignoring hidden entries
+fn next_queued(dir: &Path) -> Result<Option<PathBuf>, Box<dyn Error>> {
+    let settled = |modified: SystemTime| modified.elapsed().is_ok_and(|e| e > QUEUE_SETTLE_TIME);
 
-        pb.finish();
+    let mut items = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let modified = entry.metadata()?.modified()?;
+        if settled(modified) {
+            items.push((modified, entry.path()));
+        }
     }
 
-    Ok(())
+    items.sort();
+    Ok(items.into_iter().next().map(|(_, path)| path))
 }
@@ -1,7813 +0,0 @@
-use rand::Rng;
-use std::collections::HashMap;
-
-/**
- * Generates a pass-phrase with the EFF's dice generated
- * word list
- *
- * <https://www.eff.org/dice>
- */
-pub fn gen_phrase(count: usize) -> String {
-    let mut phrase = vec![];
-
-    let mut rng = rand::thread_rng();
-    let mut get_index = || {
-        let mut pos = 1;
-        let mut res = 0;
-        for _i in 0..5 {
-            res += rng.gen_range(1, 6) * pos;
-            pos *= 10;
-        }
-        res
-    };
-
-    for _i in 0..count {
-        let y: u32 = get_index();
-        phrase.push(*WORDS.get(&y).unwrap());
-    }
-
-    phrase.join("-")
-}
-
-lazy_static! {
-    pub static ref WORDS: HashMap<u32, &'static str> = {
-        let mut words = HashMap::new();
-        words.insert(11111, "abacus");
-        words.insert(11112, "abdomen");
-        words.insert(11113, "abdominal");
-        words.insert(11114, "abide");
-        words.insert(11115, "abiding");
-        words.insert(11116, "ability");
-        words.insert(11121, "ablaze");
-        words.insert(11122, "able");
-        words.insert(11123, "abnormal");
-        words.insert(11124, "abrasion");
-        words.insert(11125, "abrasive");
-        words.insert(11126, "abreast");
-        words.insert(11131, "abridge");
-        words.insert(11132, "abroad");
-        words.insert(11133, "abruptly");
-        words.insert(11134, "absence");
-        words.insert(11135, "absentee");
-        words.insert(11136, "absently");
-        words.insert(11141, "absinthe");
-        words.insert(11142, "absolute");
-        words.insert(11143, "absolve");
-        words.insert(11144, "abstain");
-        words.insert(11145, "abstract");
-        words.insert(11146, "absurd");
-        words.insert(11151, "accent");
-        words.insert(11152, "acclaim");
-        words.insert(11153, "acclimate");
-        words.insert(11154, "accompany");
-        words.insert(11155, "account");
-        words.insert(11156, "accuracy");
-        words.insert(11161, "accurate");
-        words.insert(11162, "accustom");
-        words.insert(11163, "acetone");
-        words.insert(11164, "achiness");
-        words.insert(11165, "aching");
-        words.insert(11166, "acid");
-        words.insert(11211, "acorn");
-        words.insert(11212, "acquaint");
-        words.insert(11213, "acquire");
-        words.insert(11214, "acre");
-        words.insert(11215, "acrobat");
-        words.insert(11216, "acronym");
-        words.insert(11221, "acting");
-        words.insert(11222, "action");
-        words.insert(11223, "activate");
-        words.insert(11224, "activator");
-        words.insert(11225, "active");
-        words.insert(11226, "activism");
-        words.insert(11231, "activist");
-        words.insert(11232, "activity");
-        words.insert(11233, "actress");
-        words.insert(11234, "acts");
-        words.insert(11235, "acutely");
-        words.insert(11236, "acuteness");
-        words.insert(11241, "aeration");
-        words.insert(11242, "aerobics");
-        words.insert(11243, "aerosol");
-        words.insert(11244, "aerospace");
-        words.insert(11245, "afar");
-        words.insert(11246, "affair");
-        words.insert(11251, "affected");
-        words.insert(11252, "affecting");
-        words.insert(11253, "affection");
-        words.insert(11254, "affidavit");
-        words.insert(11255, "affiliate");
-        words.insert(11256, "affirm");
-        words.insert(11261, "affix");
-        words.insert(11262, "afflicted");
-        words.insert(11263, "affluent");
-        words.insert(11264, "afford");
-        words.insert(11265, "affront");
-        words.insert(11266, "aflame");
-        words.insert(11311, "afloat");
-        words.insert(11312, "aflutter");
-        words.insert(11313, "afoot");
-        words.insert(11314, "afraid");
-        words.insert(11315, "afterglow");
-        words.insert(11316, "afterlife");
-        words.insert(11321, "aftermath");
-        words.insert(11322, "aftermost");
-        words.insert(11323, "afternoon");
-        words.insert(11324, "aged");
-        words.insert(11325, "ageless");
-        words.insert(11326, "agency");
-        words.insert(11331, "agenda");
-        words.insert(11332, "agent");
-        words.insert(11333, "aggregate");
-        words.insert(11334, "aghast");
-        words.insert(11335, "agile");
-        words.insert(11336, "agility");
-        words.insert(11341, "aging");
-        words.insert(11342, "agnostic");
-        words.insert(11343, "agonize");
-        words.insert(11344, "agonizing");
-        words.insert(11345, "agony");
-        words.insert(11346, "agreeable");
-        words.insert(11351, "agreeably");
-        words.insert(11352, "agreed");
-        words.insert(11353, "agreeing");
-        words.insert(11354, "agreement");
-        words.insert(11355, "aground");
-        words.insert(11356, "ahead");
-        words.insert(11361, "ahoy");
-        words.insert(11362, "aide");
-        words.insert(11363, "aids");
-        words.insert(11364, "aim");
-        words.insert(11365, "ajar");
-        words.insert(11366, "alabaster");
-        words.insert(11411, "alarm");
-        words.insert(11412, "albatross");
-        words.insert(11413, "album");
-        words.insert(11414, "alfalfa");
-        words.insert(11415, "algebra");
-        words.insert(11416, "algorithm");
-        words.insert(11421, "alias");
-        words.insert(11422, "alibi");
-        words.insert(11423, "alienable");
-        words.insert(11424, "alienate");
-        words.insert(11425, "aliens");
-        words.insert(11426, "alike");
-        words.insert(11431, "alive");
-        words.insert(11432, "alkaline");
-        words.insert(11433, "alkalize");
-        words.insert(11434, "almanac");
-        words.insert(11435, "almighty");
-        words.insert(11436, "almost");
-        words.insert(11441, "aloe");
-        words.insert(11442, "aloft");
-        words.insert(11443, "aloha");
-        words.insert(11444, "alone");
-        words.insert(11445, "alongside");
-        words.insert(11446, "aloof");
-        words.insert(11451, "alphabet");
-        words.insert(11452, "alright");
-        words.insert(11453, "although");
-        words.insert(11454, "altitude");
-        words.insert(11455, "alto");
-        words.insert(11456, "aluminum");
-        words.insert(11461, "alumni");
-        words.insert(11462, "always");
-        words.insert(11463, "amaretto");
-        words.insert(11464, "amaze");
-        words.insert(11465, "amazingly");
-        words.insert(11466, "amber");
-        words.insert(11511, "ambiance");
-        words.insert(11512, "ambiguity");
-        words.insert(11513, "ambiguous");
-        words.insert(11514, "ambition");
-        words.insert(11515, "ambitious");
-        words.insert(11516, "ambulance");
-        words.insert(11521, "ambush");
-        words.insert(11522, "amendable");
-        words.insert(11523, "amendment");
-        words.insert(11524, "amends");
-        words.insert(11525, "amenity");
-        words.insert(11526, "amiable");
-        words.insert(11531, "amicably");
-        words.insert(11532, "amid");
-        words.insert(11533, "amigo");
-        words.insert(11534, "amino");
-        words.insert(11535, "amiss");
-        words.insert(11536, "ammonia");
-        words.insert(11541, "ammonium");
-        words.insert(11542, "amnesty");
-        words.insert(11543, "amniotic");
-        words.insert(11544, "among");
-        words.insert(11545, "amount");
-        words.insert(11546, "amperage");
-        words.insert(11551, "ample");
-        words.insert(11552, "amplifier");
-        words.insert(11553, "amplify");
-        words.insert(11554, "amply");
-        words.insert(11555, "amuck");
-        words.insert(11556, "amulet");
-        words.insert(11561, "amusable");
-        words.insert(11562, "amused");
-        words.insert(11563, "amusement");
-        words.insert(11564, "amuser");
-        words.insert(11565, "amusing");
-        words.insert(11566, "anaconda");
-        words.insert(11611, "anaerobic");
-        words.insert(11612, "anagram");
-        words.insert(11613, "anatomist");
-        words.insert(11614, "anatomy");
-        words.insert(11615, "anchor");
-        words.insert(11616, "anchovy");
-        words.insert(11621, "ancient");
-        words.insert(11622, "android");
-        words.insert(11623, "anemia");
-        words.insert(11624, "anemic");
-        words.insert(11625, "aneurism");
-        words.insert(11626, "anew");
-        words.insert(11631, "angelfish");
-        words.insert(11632, "angelic");
-        words.insert(11633, "anger");
-        words.insert(11634, "angled");
-        words.insert(11635, "angler");
-        words.insert(11636, "angles");
-        words.insert(11641, "angling");
-        words.insert(11642, "angrily");
-        words.insert(11643, "angriness");
-        words.insert(11644, "anguished");
-        words.insert(11645, "angular");
-        words.insert(11646, "animal");
-        words.insert(11651, "animate");
-        words.insert(11652, "animating");
-        words.insert(11653, "animation");
-        words.insert(11654, "animator");
-        words.insert(11655, "anime");
-        words.insert(11656, "animosity");
-        words.insert(11661, "ankle");
-        words.insert(11662, "annex");
-        words.insert(11663, "annotate");
-        words.insert(11664, "announcer");
-        words.insert(11665, "annoying");
-        words.insert(11666, "annually");
-        words.insert(12111, "annuity");
-        words.insert(12112, "anointer");
-        words.insert(12113, "another");
-        words.insert(12114, "answering");
-        words.insert(12115, "antacid");
-        words.insert(12116, "antarctic");
-        words.insert(12121, "anteater");
-        words.insert(12122, "antelope");
-        words.insert(12123, "antennae");
-        words.insert(12124, "anthem");
-        words.insert(12125, "anthill");
-        words.insert(12126, "anthology");
-        words.insert(12131, "antibody");
-        words.insert(12132, "antics");
-        words.insert(12133, "antidote");
-        words.insert(12134, "antihero");
-        words.insert(12135, "antiquely");
-        words.insert(12136, "antiques");
-        words.insert(12141, "antiquity");
-        words.insert(12142, "antirust");
-        words.insert(12143, "antitoxic");
-        words.insert(12144, "antitrust");
-        words.insert(12145, "antiviral");
-        words.insert(12146, "antivirus");
-        words.insert(12151, "antler");
-        words.insert(12152, "antonym");
-        words.insert(12153, "antsy");
-        words.insert(12154, "anvil");
-        words.insert(12155, "anybody");
-        words.insert(12156, "anyhow");
-        words.insert(12161, "anymore");
-        words.insert(12162, "anyone");
-        words.insert(12163, "anyplace");
-        words.insert(12164, "anything");
-        words.insert(12165, "anytime");
-        words.insert(12166, "anyway");
-        words.insert(12211, "anywhere");
-        words.insert(12212, "aorta");
-        words.insert(12213, "apache");
-        words.insert(12214, "apostle");
-        words.insert(12215, "appealing");
-        words.insert(12216, "appear");
-        words.insert(12221, "appease");
-        words.insert(12222, "appeasing");
-        words.insert(12223, "appendage");
-        words.insert(12224, "appendix");
-        words.insert(12225, "appetite");
-        words.insert(12226, "appetizer");
-        words.insert(12231, "applaud");
-        words.insert(12232, "applause");
-        words.insert(12233, "apple");
-        words.insert(12234, "appliance");
-        words.insert(12235, "applicant");
-        words.insert(12236, "applied");
-        words.insert(12241, "apply");
-        words.insert(12242, "appointee");
-        words.insert(12243, "appraisal");
-        words.insert(12244, "appraiser");
-        words.insert(12245, "apprehend");
-        words.insert(12246, "approach");
-        words.insert(12251, "approval");
-        words.insert(12252, "approve");
-        words.insert(12253, "apricot");
-        words.insert(12254, "april");
-        words.insert(12255, "apron");
-        words.insert(12256, "aptitude");
-        words.insert(12261, "aptly");
-        words.insert(12262, "aqua");
-        words.insert(12263, "aqueduct");
-        words.insert(12264, "arbitrary");
-        words.insert(12265, "arbitrate");
-        words.insert(12266, "ardently");
-        words.insert(12311, "area");
-        words.insert(12312, "arena");
-        words.insert(12313, "arguable");
-        words.insert(12314, "arguably");
-        words.insert(12315, "argue");
-        words.insert(12316, "arise");
-        words.insert(12321, "armadillo");
-        words.insert(12322, "armband");
-        words.insert(12323, "armchair");
-        words.insert(12324, "armed");
-        words.insert(12325, "armful");
-        words.insert(12326, "armhole");
-        words.insert(12331, "arming");
-        words.insert(12332, "armless");
-        words.insert(12333, "armoire");
-        words.insert(12334, "armored");
-        words.insert(12335, "armory");
-        words.insert(12336, "armrest");
-        words.insert(12341, "army");
-        words.insert(12342, "aroma");
-        words.insert(12343, "arose");
-        words.insert(12344, "around");
-        words.insert(12345, "arousal");
-        words.insert(12346, "arrange");
-        words.insert(12351, "array");
-        words.insert(12352, "arrest");
-        words.insert(12353, "arrival");
-        words.insert(12354, "arrive");
-        words.insert(12355, "arrogance");
-        words.insert(12356, "arrogant");
-        words.insert(12361, "arson");
-        words.insert(12362, "art");
-        words.insert(12363, "ascend");
-        words.insert(12364, "ascension");
-        words.insert(12365, "ascent");
-        words.insert(12366, "ascertain");
-        words.insert(12411, "ashamed");
-        words.insert(12412, "ashen");
-        words.insert(12413, "ashes");
-        words.insert(12414, "ashy");
-        words.insert(12415, "aside");
-        words.insert(12416, "askew");
-        words.insert(12421, "asleep");
-        words.insert(12422, "asparagus");
-        words.insert(12423, "aspect");
-        words.insert(12424, "aspirate");
-        words.insert(12425, "aspire");
-        words.insert(12426, "aspirin");
-        words.insert(12431, "astonish");
-        words.insert(12432, "astound");
-        words.insert(12433, "astride");
-        words.insert(12434, "astrology");
-        words.insert(12435, "astronaut");
-        words.insert(12436, "astronomy");
-        words.insert(12441, "astute");
-        words.insert(12442, "atlantic");
-        words.insert(12443, "atlas");
-        words.insert(12444, "atom");
-        words.insert(12445, "atonable");
-        words.insert(12446, "atop");
-        words.insert(12451, "atrium");
-        words.insert(12452, "atrocious");
-        words.insert(12453, "atrophy");
-        words.insert(12454, "attach");
-        words.insert(12455, "attain");
-        words.insert(12456, "attempt");
-        words.insert(12461, "attendant");
-        words.insert(12462, "attendee");
-        words.insert(12463, "attention");
-        words.insert(12464, "attentive");
-        words.insert(12465, "attest");
-        words.insert(12466, "attic");
-        words.insert(12511, "attire");
-        words.insert(12512, "attitude");
-        words.insert(12513, "attractor");
-        words.insert(12514, "attribute");
-        words.insert(12515, "atypical");
-        words.insert(12516, "auction");
-        words.insert(12521, "audacious");
-        words.insert(12522, "audacity");
-        words.insert(12523, "audible");
-        words.insert(12524, "audibly");
-        words.insert(12525, "audience");
-        words.insert(12526, "audio");
-        words.insert(12531, "audition");
-        words.insert(12532, "augmented");
-        words.insert(12533, "august");
-        words.insert(12534, "authentic");
-        words.insert(12535, "author");
-        words.insert(12536, "autism");
-        words.insert(12541, "autistic");
-        words.insert(12542, "autograph");
-        words.insert(12543, "automaker");
-        words.insert(12544, "automated");
-        words.insert(12545, "automatic");
-        words.insert(12546, "autopilot");
-        words.insert(12551, "available");
-        words.insert(12552, "avalanche");
-        words.insert(12553, "avatar");
-        words.insert(12554, "avenge");
-        words.insert(12555, "avenging");
-        words.insert(12556, "avenue");
-        words.insert(12561, "average");
-        words.insert(12562, "aversion");
-        words.insert(12563, "avert");
-        words.insert(12564, "aviation");
-        words.insert(12565, "aviator");
-        words.insert(12566, "avid");
-        words.insert(12611, "avoid");
-        words.insert(12612, "await");
-        words.insert(12613, "awaken");
-        words.insert(12614, "award");
-        words.insert(12615, "aware");
-        words.insert(12616, "awhile");
-        words.insert(12621, "awkward");
-        words.insert(12622, "awning");
-        words.insert(12623, "awoke");
-        words.insert(12624, "awry");
-        words.insert(12625, "axis");
-        words.insert(12626, "babble");
-        words.insert(12631, "babbling");
-        words.insert(12632, "babied");
-        words.insert(12633, "baboon");
-        words.insert(12634, "backache");
-        words.insert(12635, "backboard");
-        words.insert(12636, "backboned");
-        words.insert(12641, "backdrop");
-        words.insert(12642, "backed");
-        words.insert(12643, "backer");
-        words.insert(12644, "backfield");
-        words.insert(12645, "backfire");
-        words.insert(12646, "backhand");
-        words.insert(12651, "backing");
-        words.insert(12652, "backlands");
-        words.insert(12653, "backlash");
-        words.insert(12654, "backless");
-        words.insert(12655, "backlight");
-        words.insert(12656, "backlit");
-        words.insert(12661, "backlog");
-        words.insert(12662, "backpack");
-        words.insert(12663, "backpedal");
-        words.insert(12664, "backrest");
-        words.insert(12665, "backroom");
-        words.insert(12666, "backshift");
-        words.insert(13111, "backside");
-        words.insert(13112, "backslid");
-        words.insert(13113, "backspace");
-        words.insert(13114, "backspin");
-        words.insert(13115, "backstab");
-        words.insert(13116, "backstage");
-        words.insert(13121, "backtalk");
-        words.insert(13122, "backtrack");
-        words.insert(13123, "backup");
-        words.insert(13124, "backward");
-        words.insert(13125, "backwash");
-        words.insert(13126, "backwater");
-        words.insert(13131, "backyard");
-        words.insert(13132, "bacon");
-        words.insert(13133, "bacteria");
-        words.insert(13134, "bacterium");
-        words.insert(13135, "badass");
-        words.insert(13136, "badge");
-        words.insert(13141, "badland");
-        words.insert(13142, "badly");
-        words.insert(13143, "badness");
-        words.insert(13144, "baffle");
-        words.insert(13145, "baffling");
-        words.insert(13146, "bagel");
-        words.insert(13151, "bagful");
-        words.insert(13152, "baggage");
-        words.insert(13153, "bagged");
-        words.insert(13154, "baggie");
-        words.insert(13155, "bagginess");
-        words.insert(13156, "bagging");
-        words.insert(13161, "baggy");
-        words.insert(13162, "bagpipe");
-        words.insert(13163, "baguette");
-        words.insert(13164, "baked");
-        words.insert(13165, "bakery");
-        words.insert(13166, "bakeshop");
-        words.insert(13211, "baking");
-        words.insert(13212, "balance");
-        words.insert(13213, "balancing");
-        words.insert(13214, "balcony");
-        words.insert(13215, "balmy");
-        words.insert(13216, "balsamic");
-        words.insert(13221, "bamboo");
-        words.insert(13222, "banana");
-        words.insert(13223, "banish");
-        words.insert(13224, "banister");
-        words.insert(13225, "banjo");
-        words.insert(13226, "bankable");
-        words.insert(13231, "bankbook");
-        words.insert(13232, "banked");
-        words.insert(13233, "banker");
-        words.insert(13234, "banking");
-        words.insert(13235, "banknote");
-        words.insert(13236, "bankroll");
-        words.insert(13241, "banner");
-        words.insert(13242, "bannister");
-        words.insert(13243, "banshee");
-        words.insert(13244, "banter");
-        words.insert(13245, "barbecue");
-        words.insert(13246, "barbed");
-        words.insert(13251, "barbell");
-        words.insert(13252, "barber");
-        words.insert(13253, "barcode");
-        words.insert(13254, "barge");
-        words.insert(13255, "bargraph");
-        words.insert(13256, "barista");
-        words.insert(13261, "baritone");
-        words.insert(13262, "barley");
-        words.insert(13263, "barmaid");
-        words.insert(13264, "barman");
-        words.insert(13265, "barn");
-        words.insert(13266, "barometer");
-        words.insert(13311, "barrack");
-        words.insert(13312, "barracuda");
-        words.insert(13313, "barrel");
-        words.insert(13314, "barrette");
-        words.insert(13315, "barricade");
-        words.insert(13316, "barrier");
-        words.insert(13321, "barstool");
-        words.insert(13322, "bartender");
-        words.insert(13323, "barterer");
-        words.insert(13324, "bash");
-        words.insert(13325, "basically");
-        words.insert(13326, "basics");
-        words.insert(13331, "basil");
-        words.insert(13332, "basin");
-        words.insert(13333, "basis");
-        words.insert(13334, "basket");
-        words.insert(13335, "batboy");
-        words.insert(13336, "batch");
-        words.insert(13341, "bath");
-        words.insert(13342, "baton");
-        words.insert(13343, "bats");
-        words.insert(13344, "battalion");
-        words.insert(13345, "battered");
-        words.insert(13346, "battering");
-        words.insert(13351, "battery");
-        words.insert(13352, "batting");
-        words.insert(13353, "battle");
-        words.insert(13354, "bauble");
-        words.insert(13355, "bazooka");
-        words.insert(13356, "blabber");
-        words.insert(13361, "bladder");
-        words.insert(13362, "blade");
-        words.insert(13363, "blah");
-        words.insert(13364, "blame");
-        words.insert(13365, "blaming");
-        words.insert(13366, "blanching");
-        words.insert(13411, "blandness");
-        words.insert(13412, "blank");
-        words.insert(13413, "blaspheme");
-        words.insert(13414, "blasphemy");
-        words.insert(13415, "blast");
-        words.insert(13416, "blatancy");
-        words.insert(13421, "blatantly");
-        words.insert(13422, "blazer");
-        words.insert(13423, "blazing");
-        words.insert(13424, "bleach");
-        words.insert(13425, "bleak");
-        words.insert(13426, "bleep");
-        words.insert(13431, "blemish");
-        words.insert(13432, "blend");
-        words.insert(13433, "bless");
-        words.insert(13434, "blighted");
-        words.insert(13435, "blimp");
-        words.insert(13436, "bling");
-        words.insert(13441, "blinked");
-        words.insert(13442, "blinker");
-        words.insert(13443, "blinking");
-        words.insert(13444, "blinks");
-        words.insert(13445, "blip");
-        words.insert(13446, "blissful");
-        words.insert(13451, "blitz");
-        words.insert(13452, "blizzard");
-        words.insert(13453, "bloated");
-        words.insert(13454, "bloating");
-        words.insert(13455, "blob");
-        words.insert(13456, "blog");
-        words.insert(13461, "bloomers");
-        words.insert(13462, "blooming");
-        words.insert(13463, "blooper");
-        words.insert(13464, "blot");
-        words.insert(13465, "blouse");
-        words.insert(13466, "blubber");
-        words.insert(13511, "bluff");
-        words.insert(13512, "bluish");
-        words.insert(13513, "blunderer");
-        words.insert(13514, "blunt");
-        words.insert(13515, "blurb");
-        words.insert(13516, "blurred");
-        words.insert(13521, "blurry");
-        words.insert(13522, "blurt");
-        words.insert(13523, "blush");
-        words.insert(13524, "blustery");
-        words.insert(13525, "boaster");
-        words.insert(13526, "boastful");
-        words.insert(13531, "boasting");
-        words.insert(13532, "boat");
-        words.insert(13533, "bobbed");
-        words.insert(13534, "bobbing");
-        words.insert(13535, "bobble");
-        words.insert(13536, "bobcat");
-        words.insert(13541, "bobsled");
-        words.insert(13542, "bobtail");
-        words.insert(13543, "bodacious");
-        words.insert(13544, "body");
-        words.insert(13545, "bogged");
-        words.insert(13546, "boggle");
-        words.insert(13551, "bogus");
-        words.insert(13552, "boil");
-        words.insert(13553, "bok");
-        words.insert(13554, "bolster");
-        words.insert(13555, "bolt");
-        words.insert(13556, "bonanza");
-        words.insert(13561, "bonded");
-        words.insert(13562, "bonding");
-        words.insert(13563, "bondless");
-        words.insert(13564, "boned");
-        words.insert(13565, "bonehead");
-        words.insert(13566, "boneless");
-        words.insert(13611, "bonelike");
-        words.insert(13612, "boney");
-        words.insert(13613, "bonfire");
-        words.insert(13614, "bonnet");
-        words.insert(13615, "bonsai");
-        words.insert(13616, "bonus");
-        words.insert(13621, "bony");
-        words.insert(13622, "boogeyman");
-        words.insert(13623, "boogieman");
-        words.insert(13624, "book");
-        words.insert(13625, "boondocks");
-        words.insert(13626, "booted");
-        words.insert(13631, "booth");
-        words.insert(13632, "bootie");
-        words.insert(13633, "booting");
-        words.insert(13634, "bootlace");
-        words.insert(13635, "bootleg");
-        words.insert(13636, "boots");
-        words.insert(13641, "boozy");
-        words.insert(13642, "borax");
-        words.insert(13643, "boring");
-        words.insert(13644, "borough");
-        words.insert(13645, "borrower");
-        words.insert(13646, "borrowing");
-        words.insert(13651, "boss");
-        words.insert(13652, "botanical");
-        words.insert(13653, "botanist");
-        words.insert(13654, "botany");
-        words.insert(13655, "botch");
-        words.insert(13656, "both");
-        words.insert(13661, "bottle");
-        words.insert(13662, "bottling");
-        words.insert(13663, "bottom");
-        words.insert(13664, "bounce");
-        words.insert(13665, "bouncing");
-        words.insert(13666, "bouncy");
-        words.insert(14111, "bounding");
-        words.insert(14112, "boundless");
-        words.insert(14113, "bountiful");
-        words.insert(14114, "bovine");
-        words.insert(14115, "boxcar");
-        words.insert(14116, "boxer");
-        words.insert(14121, "boxing");
-        words.insert(14122, "boxlike");
-        words.insert(14123, "boxy");
-        words.insert(14124, "breach");
-        words.insert(14125, "breath");
-        words.insert(14126, "breeches");
-        words.insert(14131, "breeching");
-        words.insert(14132, "breeder");
-        words.insert(14133, "breeding");
-        words.insert(14134, "breeze");
-        words.insert(14135, "breezy");
-        words.insert(14136, "brethren");
-        words.insert(14141, "brewery");
-        words.insert(14142, "brewing");
-        words.insert(14143, "briar");
-        words.insert(14144, "bribe");
-        words.insert(14145, "brick");
-        words.insert(14146, "bride");
-        words.insert(14151, "bridged");
-        words.insert(14152, "brigade");
-        words.insert(14153, "bright");
-        words.insert(14154, "brilliant");
-        words.insert(14155, "brim");
-        words.insert(14156, "bring");
-        words.insert(14161, "brink");
-        words.insert(14162, "brisket");
-        words.insert(14163, "briskly");
-        words.insert(14164, "briskness");
-        words.insert(14165, "bristle");
-        words.insert(14166, "brittle");
-        words.insert(14211, "broadband");
-        words.insert(14212, "broadcast");
-        words.insert(14213, "broaden");
-        words.insert(14214, "broadly");
-        words.insert(14215, "broadness");
-        words.insert(14216, "broadside");
-        words.insert(14221, "broadways");
-        words.insert(14222, "broiler");
-        words.insert(14223, "broiling");
-        words.insert(14224, "broken");
-        words.insert(14225, "broker");
-        words.insert(14226, "bronchial");
-        words.insert(14231, "bronco");
-        words.insert(14232, "bronze");
-        words.insert(14233, "bronzing");
-        words.insert(14234, "brook");
-        words.insert(14235, "broom");
-        words.insert(14236, "brought");
-        words.insert(14241, "browbeat");
-        words.insert(14242, "brownnose");
-        words.insert(14243, "browse");
-        words.insert(14244, "browsing");
-        words.insert(14245, "bruising");
-        words.insert(14246, "brunch");
-        words.insert(14251, "brunette");
-        words.insert(14252, "brunt");
-        words.insert(14253, "brush");
-        words.insert(14254, "brussels");
-        words.insert(14255, "brute");
-        words.insert(14256, "brutishly");
-        words.insert(14261, "bubble");
-        words.insert(14262, "bubbling");
-        words.insert(14263, "bubbly");
-        words.insert(14264, "buccaneer");
-        words.insert(14265, "bucked");
-        words.insert(14266, "bucket");
-        words.insert(14311, "buckle");
-        words.insert(14312, "buckshot");
-        words.insert(14313, "buckskin");
-        words.insert(14314, "bucktooth");
-        words.insert(14315, "buckwheat");
-        words.insert(14316, "buddhism");
-        words.insert(14321, "buddhist");
-        words.insert(14322, "budding");
-        words.insert(14323, "buddy");
-        words.insert(14324, "budget");
-        words.insert(14325, "buffalo");
-        words.insert(14326, "buffed");
-        words.insert(14331, "buffer");
-        words.insert(14332, "buffing");
-        words.insert(14333, "buffoon");
-        words.insert(14334, "buggy");
-        words.insert(14335, "bulb");
-        words.insert(14336, "bulge");
-        words.insert(14341, "bulginess");
-        words.insert(14342, "bulgur");
-        words.insert(14343, "bulk");
-        words.insert(14344, "bulldog");
-        words.insert(14345, "bulldozer");
-        words.insert(14346, "bullfight");
-        words.insert(14351, "bullfrog");
-        words.insert(14352, "bullhorn");
-        words.insert(14353, "bullion");
-        words.insert(14354, "bullish");
-        words.insert(14355, "bullpen");
-        words.insert(14356, "bullring");
-        words.insert(14361, "bullseye");
-        words.insert(14362, "bullwhip");
-        words.insert(14363, "bully");
-        words.insert(14364, "bunch");
-        words.insert(14365, "bundle");
-        words.insert(14366, "bungee");
-        words.insert(14411, "bunion");
-        words.insert(14412, "bunkbed");
-        words.insert(14413, "bunkhouse");
-        words.insert(14414, "bunkmate");
-        words.insert(14415, "bunny");
-        words.insert(14416, "bunt");
-        words.insert(14421, "busboy");
-        words.insert(14422, "bush");
-        words.insert(14423, "busily");
-        words.insert(14424, "busload");
-        words.insert(14425, "bust");
-        words.insert(14426, "busybody");
-        words.insert(14431, "buzz");
-        words.insert(14432, "cabana");
-        words.insert(14433, "cabbage");
-        words.insert(14434, "cabbie");
-        words.insert(14435, "cabdriver");
-        words.insert(14436, "cable");
-        words.insert(14441, "caboose");
-        words.insert(14442, "cache");
-        words.insert(14443, "cackle");
-        words.insert(14444, "cacti");
-        words.insert(14445, "cactus");
-        words.insert(14446, "caddie");
-        words.insert(14451, "caddy");
-        words.insert(14452, "cadet");
-        words.insert(14453, "cadillac");
-        words.insert(14454, "cadmium");
-        words.insert(14455, "cage");
-        words.insert(14456, "cahoots");
-        words.insert(14461, "cake");
-        words.insert(14462, "calamari");
-        words.insert(14463, "calamity");
-        words.insert(14464, "calcium");
-        words.insert(14465, "calculate");
-        words.insert(14466, "calculus");
-        words.insert(14511, "caliber");
-        words.insert(14512, "calibrate");
-        words.insert(14513, "calm");
-        words.insert(14514, "caloric");
-        words.insert(14515, "calorie");
-        words.insert(14516, "calzone");
-        words.insert(14521, "camcorder");
-        words.insert(14522, "cameo");
-        words.insert(14523, "camera");
-        words.insert(14524, "camisole");
-        words.insert(14525, "camper");
-        words.insert(14526, "campfire");
-        words.insert(14531, "camping");
-        words.insert(14532, "campsite");
-        words.insert(14533, "campus");
-        words.insert(14534, "canal");
-        words.insert(14535, "canary");
-        words.insert(14536, "cancel");
-        words.insert(14541, "candied");
-        words.insert(14542, "candle");
-        words.insert(14543, "candy");
-        words.insert(14544, "cane");
-        words.insert(14545, "canine");
-        words.insert(14546, "canister");
-        words.insert(14551, "cannabis");
-        words.insert(14552, "canned");
-        words.insert(14553, "canning");
-        words.insert(14554, "cannon");
-        words.insert(14555, "cannot");
-        words.insert(14556, "canola");
-        words.insert(14561, "canon");
-        words.insert(14562, "canopener");
-        words.insert(14563, "canopy");
-        words.insert(14564, "canteen");
-        words.insert(14565, "canyon");
-        words.insert(14566, "capable");
-        words.insert(14611, "capably");
-        words.insert(14612, "capacity");
-        words.insert(14613, "cape");
-        words.insert(14614, "capillary");
-        words.insert(14615, "capital");
-        words.insert(14616, "capitol");
-        words.insert(14621, "capped");
-        words.insert(14622, "capricorn");
-        words.insert(14623, "capsize");
-        words.insert(14624, "capsule");
-        words.insert(14625, "caption");
-        words.insert(14626, "captivate");
-        words.insert(14631, "captive");
-        words.insert(14632, "captivity");
-        words.insert(14633, "capture");
-        words.insert(14634, "caramel");
-        words.insert(14635, "carat");
-        words.insert(14636, "caravan");
-        words.insert(14641, "carbon");
-        words.insert(14642, "cardboard");
-        words.insert(14643, "carded");
-        words.insert(14644, "cardiac");
-        words.insert(14645, "cardigan");
-        words.insert(14646, "cardinal");
-        words.insert(14651, "cardstock");
-        words.insert(14652, "carefully");
-        words.insert(14653, "caregiver");
-        words.insert(14654, "careless");
-        words.insert(14655, "caress");
-        words.insert(14656, "caretaker");
-        words.insert(14661, "cargo");
-        words.insert(14662, "caring");
-        words.insert(14663, "carless");
-        words.insert(14664, "carload");
-        words.insert(14665, "carmaker");
-        words.insert(14666, "carnage");
-        words.insert(15111, "carnation");
-        words.insert(15112, "carnival");
-        words.insert(15113, "carnivore");
-        words.insert(15114, "carol");
-        words.insert(15115, "carpenter");
-        words.insert(15116, "carpentry");
-        words.insert(15121, "carpool");
-        words.insert(15122, "carport");
-        words.insert(15123, "carried");
-        words.insert(15124, "carrot");
-        words.insert(15125, "carrousel");
-        words.insert(15126, "carry");
-        words.insert(15131, "cartel");
-        words.insert(15132, "cartload");
-        words.insert(15133, "carton");
-        words.insert(15134, "cartoon");
-        words.insert(15135, "cartridge");
-        words.insert(15136, "cartwheel");
-        words.insert(15141, "carve");
-        words.insert(15142, "carving");
-        words.insert(15143, "carwash");
-        words.insert(15144, "cascade");
-        words.insert(15145, "case");
-        words.insert(15146, "cash");
-        words.insert(15151, "casing");
-        words.insert(15152, "casino");
-        words.insert(15153, "casket");
-        words.insert(15154, "cassette");
-        words.insert(15155, "casually");
-        words.insert(15156, "casualty");
-        words.insert(15161, "catacomb");
-        words.insert(15162, "catalog");
-        words.insert(15163, "catalyst");
-        words.insert(15164, "catalyze");
-        words.insert(15165, "catapult");
-        words.insert(15166, "cataract");
-        words.insert(15211, "catatonic");
-        words.insert(15212, "catcall");
-        words.insert(15213, "catchable");
-        words.insert(15214, "catcher");
-        words.insert(15215, "catching");
-        words.insert(15216, "catchy");
-        words.insert(15221, "caterer");
-        words.insert(15222, "catering");
-        words.insert(15223, "catfight");
-        words.insert(15224, "catfish");
-        words.insert(15225, "cathedral");
-        words.insert(15226, "cathouse");
-        words.insert(15231, "catlike");
-        words.insert(15232, "catnap");
-        words.insert(15233, "catnip");
-        words.insert(15234, "catsup");
-        words.insert(15235, "cattail");
-        words.insert(15236, "cattishly");
-        words.insert(15241, "cattle");
-        words.insert(15242, "catty");
-        words.insert(15243, "catwalk");
-        words.insert(15244, "caucasian");
-        words.insert(15245, "caucus");
-        words.insert(15246, "causal");
-        words.insert(15251, "causation");
-        words.insert(15252, "cause");
-        words.insert(15253, "causing");
-        words.insert(15254, "cauterize");
-        words.insert(15255, "caution");
-        words.insert(15256, "cautious");
-        words.insert(15261, "cavalier");
-        words.insert(15262, "cavalry");
-        words.insert(15263, "caviar");
-        words.insert(15264, "cavity");
-        words.insert(15265, "cedar");
-        words.insert(15266, "celery");
-        words.insert(15311, "celestial");
-        words.insert(15312, "celibacy");
-        words.insert(15313, "celibate");
-        words.insert(15314, "celtic");
-        words.insert(15315, "cement");
-        words.insert(15316, "census");
-        words.insert(15321, "ceramics");
-        words.insert(15322, "ceremony");
-        words.insert(15323, "certainly");
-        words.insert(15324, "certainty");
-        words.insert(15325, "certified");
-        words.insert(15326, "certify");
-        words.insert(15331, "cesarean");
-        words.insert(15332, "cesspool");
-        words.insert(15333, "chafe");
-        words.insert(15334, "chaffing");
-        words.insert(15335, "chain");
-        words.insert(15336, "chair");
-        words.insert(15341, "chalice");
-        words.insert(15342, "challenge");
-        words.insert(15343, "chamber");
-        words.insert(15344, "chamomile");
-        words.insert(15345, "champion");
-        words.insert(15346, "chance");
-        words.insert(15351, "change");
-        words.insert(15352, "channel");
-        words.insert(15353, "chant");
-        words.insert(15354, "chaos");
-        words.insert(15355, "chaperone");
-        words.insert(15356, "chaplain");
-        words.insert(15361, "chapped");
-        words.insert(15362, "chaps");
-        words.insert(15363, "chapter");
-        words.insert(15364, "character");
-        words.insert(15365, "charbroil");
-        words.insert(15366, "charcoal");
-        words.insert(15411, "charger");
-        words.insert(15412, "charging");
-        words.insert(15413, "chariot");
-        words.insert(15414, "charity");
-        words.insert(15415, "charm");
-        words.insert(15416, "charred");
-        words.insert(15421, "charter");
-        words.insert(15422, "charting");
-        words.insert(15423, "chase");
-        words.insert(15424, "chasing");
-        words.insert(15425, "chaste");
-        words.insert(15426, "chastise");
-        words.insert(15431, "chastity");
-        words.insert(15432, "chatroom");
-        words.insert(15433, "chatter");
-        words.insert(15434, "chatting");
-        words.insert(15435, "chatty");
-        words.insert(15436, "cheating");
-        words.insert(15441, "cheddar");
-        words.insert(15442, "cheek");
-        words.insert(15443, "cheer");
-        words.insert(15444, "cheese");
-        words.insert(15445, "cheesy");
-        words.insert(15446, "chef");
-        words.insert(15451, "chemicals");
-        words.insert(15452, "chemist");
-        words.insert(15453, "chemo");
-        words.insert(15454, "cherisher");
-        words.insert(15455, "cherub");
-        words.insert(15456, "chess");
-        words.insert(15461, "chest");
-        words.insert(15462, "chevron");
-        words.insert(15463, "chevy");
-        words.insert(15464, "chewable");
-        words.insert(15465, "chewer");
-        words.insert(15466, "chewing");
-        words.insert(15511, "chewy");
-        words.insert(15512, "chief");
-        words.insert(15513, "chihuahua");
-        words.insert(15514, "childcare");
-        words.insert(15515, "childhood");
-        words.insert(15516, "childish");
-        words.insert(15521, "childless");
-        words.insert(15522, "childlike");
-        words.insert(15523, "chili");
-        words.insert(15524, "chill");
-        words.insert(15525, "chimp");
-        words.insert(15526, "chip");
-        words.insert(15531, "chirping");
-        words.insert(15532, "chirpy");
-        words.insert(15533, "chitchat");
-        words.insert(15534, "chivalry");
-        words.insert(15535, "chive");
-        words.insert(15536, "chloride");
-        words.insert(15541, "chlorine");
-        words.insert(15542, "choice");
-        words.insert(15543, "chokehold");
-        words.insert(15544, "choking");
-        words.insert(15545, "chomp");
-        words.insert(15546, "chooser");
-        words.insert(15551, "choosing");
-        words.insert(15552, "choosy");
-        words.insert(15553, "chop");
-        words.insert(15554, "chosen");
-        words.insert(15555, "chowder");
-        words.insert(15556, "chowtime");
-        words.insert(15561, "chrome");
-        words.insert(15562, "chubby");
-        words.insert(15563, "chuck");
-        words.insert(15564, "chug");
-        words.insert(15565, "chummy");
-        words.insert(15566, "chump");
-        words.insert(15611, "chunk");
-        words.insert(15612, "churn");
-        words.insert(15613, "chute");
-        words.insert(15614, "cider");
-        words.insert(15615, "cilantro");
-        words.insert(15616, "cinch");
-        words.insert(15621, "cinema");
-        words.insert(15622, "cinnamon");
-        words.insert(15623, "circle");
-        words.insert(15624, "circling");
-        words.insert(15625, "circular");
-        words.insert(15626, "circulate");
-        words.insert(15631, "circus");
-        words.insert(15632, "citable");
-        words.insert(15633, "citadel");
-        words.insert(15634, "citation");
-        words.insert(15635, "citizen");
-        words.insert(15636, "citric");
-        words.insert(15641, "citrus");
-        words.insert(15642, "city");
-        words.insert(15643, "civic");
-        words.insert(15644, "civil");
-        words.insert(15645, "clad");
-        words.insert(15646, "claim");
-        words.insert(15651, "clambake");
-        words.insert(15652, "clammy");
-        words.insert(15653, "clamor");
-        words.insert(15654, "clamp");
-        words.insert(15655, "clamshell");
-        words.insert(15656, "clang");
-        words.insert(15661, "clanking");
-        words.insert(15662, "clapped");
-        words.insert(15663, "clapper");
-        words.insert(15664, "clapping");
-        words.insert(15665, "clarify");
-        words.insert(15666, "clarinet");
-        words.insert(16111, "clarity");
-        words.insert(16112, "clash");
-        words.insert(16113, "clasp");
-        words.insert(16114, "class");
-        words.insert(16115, "clatter");
-        words.insert(16116, "clause");
-        words.insert(16121, "clavicle");
-        words.insert(16122, "claw");
-        words.insert(16123, "clay");
-        words.insert(16124, "clean");
-        words.insert(16125, "clear");
-        words.insert(16126, "cleat");
-        words.insert(16131, "cleaver");
-        words.insert(16132, "cleft");
-        words.insert(16133, "clench");
-        words.insert(16134, "clergyman");
-        words.insert(16135, "clerical");
-        words.insert(16136, "clerk");
-        words.insert(16141, "clever");
-        words.insert(16142, "clicker");
-        words.insert(16143, "client");
-        words.insert(16144, "climate");
-        words.insert(16145, "climatic");
-        words.insert(16146, "cling");
-        words.insert(16151, "clinic");
-        words.insert(16152, "clinking");
-        words.insert(16153, "clip");
-        words.insert(16154, "clique");
-        words.insert(16155, "cloak");
-        words.insert(16156, "clobber");
-        words.insert(16161, "clock");
-        words.insert(16162, "clone");
-        words.insert(16163, "cloning");
-        words.insert(16164, "closable");
-        words.insert(16165, "closure");
-        words.insert(16166, "clothes");
-        words.insert(16211, "clothing");
-        words.insert(16212, "cloud");
-        words.insert(16213, "clover");
-        words.insert(16214, "clubbed");
-        words.insert(16215, "clubbing");
-        words.insert(16216, "clubhouse");
-        words.insert(16221, "clump");
-        words.insert(16222, "clumsily");
-        words.insert(16223, "clumsy");
-        words.insert(16224, "clunky");
-        words.insert(16225, "clustered");
-        words.insert(16226, "clutch");
-        words.insert(16231, "clutter");
-        words.insert(16232, "coach");
-        words.insert(16233, "coagulant");
-        words.insert(16234, "coastal");
-        words.insert(16235, "coaster");
-        words.insert(16236, "coasting");
-        words.insert(16241, "coastland");
-        words.insert(16242, "coastline");
-        words.insert(16243, "coat");
-        words.insert(16244, "coauthor");
-        words.insert(16245, "cobalt");
-        words.insert(16246, "cobbler");
-        words.insert(16251, "cobweb");
-        words.insert(16252, "cocoa");
-        words.insert(16253, "coconut");
-        words.insert(16254, "cod");
-        words.insert(16255, "coeditor");
-        words.insert(16256, "coerce");
-        words.insert(16261, "coexist");
-        words.insert(16262, "coffee");
-        words.insert(16263, "cofounder");
-        words.insert(16264, "cognition");
-        words.insert(16265, "cognitive");
-        words.insert(16266, "cogwheel");
-        words.insert(16311, "coherence");
-        words.insert(16312, "coherent");
-        words.insert(16313, "cohesive");
-        words.insert(16314, "coil");
-        words.insert(16315, "coke");
-        words.insert(16316, "cola");
-        words.insert(16321, "cold");
-        words.insert(16322, "coleslaw");
-        words.insert(16323, "coliseum");
-        words.insert(16324, "collage");
-        words.insert(16325, "collapse");
-        words.insert(16326, "collar");
-        words.insert(16331, "collected");
-        words.insert(16332, "collector");
-        words.insert(16333, "collide");
-        words.insert(16334, "collie");
-        words.insert(16335, "collision");
-        words.insert(16336, "colonial");
-        words.insert(16341, "colonist");
-        words.insert(16342, "colonize");
-        words.insert(16343, "colony");
-        words.insert(16344, "colossal");
-        words.insert(16345, "colt");
-        words.insert(16346, "coma");
-        words.insert(16351, "come");
-        words.insert(16352, "comfort");
-        words.insert(16353, "comfy");
-        words.insert(16354, "comic");
-        words.insert(16355, "coming");
-        words.insert(16356, "comma");
-        words.insert(16361, "commence");
-        words.insert(16362, "commend");
-        words.insert(16363, "comment");
-        words.insert(16364, "commerce");
-        words.insert(16365, "commode");
-        words.insert(16366, "commodity");
-        words.insert(16411, "commodore");
-        words.insert(16412, "common");
-        words.insert(16413, "commotion");
-        words.insert(16414, "commute");
-        words.insert(16415, "commuting");
-        words.insert(16416, "compacted");
-        words.insert(16421, "compacter");
-        words.insert(16422, "compactly");
-        words.insert(16423, "compactor");
-        words.insert(16424, "companion");
-        words.insert(16425, "company");
-        words.insert(16426, "compare");
-        words.insert(16431, "compel");
-        words.insert(16432, "compile");
-        words.insert(16433, "comply");
-        words.insert(16434, "component");
-        words.insert(16435, "composed");
-        words.insert(16436, "composer");
-        words.insert(16441, "composite");
-        words.insert(16442, "compost");
-        words.insert(16443, "composure");
-        words.insert(16444, "compound");
-        words.insert(16445, "compress");
-        words.insert(16446, "comprised");
-        words.insert(16451, "computer");
-        words.insert(16452, "computing");
-        words.insert(16453, "comrade");
-        words.insert(16454, "concave");
-        words.insert(16455, "conceal");
-        words.insert(16456, "conceded");
-        words.insert(16461, "concept");
-        words.insert(16462, "concerned");
-        words.insert(16463, "concert");
-        words.insert(16464, "conch");
-        words.insert(16465, "concierge");
-        words.insert(16466, "concise");
-        words.insert(16511, "conclude");
-        words.insert(16512, "concrete");
-        words.insert(16513, "concur");
-        words.insert(16514, "condense");
-        words.insert(16515, "condiment");
-        words.insert(16516, "condition");
-        words.insert(16521, "condone");
-        words.insert(16522, "conducive");
-        words.insert(16523, "conductor");
-        words.insert(16524, "conduit");
-        words.insert(16525, "cone");
-        words.insert(16526, "confess");
-        words.insert(16531, "confetti");
-        words.insert(16532, "confidant");
-        words.insert(16533, "confident");
-        words.insert(16534, "confider");
-        words.insert(16535, "confiding");
-        words.insert(16536, "configure");
-        words.insert(16541, "confined");
-        words.insert(16542, "confining");
-        words.insert(16543, "confirm");
-        words.insert(16544, "conflict");
-        words.insert(16545, "conform");
-        words.insert(16546, "confound");
-        words.insert(16551, "confront");
-        words.insert(16552, "confused");
-        words.insert(16553, "confusing");
-        words.insert(16554, "confusion");
-        words.insert(16555, "congenial");
-        words.insert(16556, "congested");
-        words.insert(16561, "congrats");
-        words.insert(16562, "congress");
-        words.insert(16563, "conical");
-        words.insert(16564, "conjoined");
-        words.insert(16565, "conjure");
-        words.insert(16566, "conjuror");
-        words.insert(16611, "connected");
-        words.insert(16612, "connector");
-        words.insert(16613, "consensus");
-        words.insert(16614, "consent");
-        words.insert(16615, "console");
-        words.insert(16616, "consoling");
-        words.insert(16621, "consonant");
-        words.insert(16622, "constable");
-        words.insert(16623, "constant");
-        words.insert(16624, "constrain");
-        words.insert(16625, "constrict");
-        words.insert(16626, "construct");
-        words.insert(16631, "consult");
-        words.insert(16632, "consumer");
-        words.insert(16633, "consuming");
-        words.insert(16634, "contact");
-        words.insert(16635, "container");
-        words.insert(16636, "contempt");
-        words.insert(16641, "contend");
-        words.insert(16642, "contented");
-        words.insert(16643, "contently");
-        words.insert(16644, "contents");
-        words.insert(16645, "contest");
-        words.insert(16646, "context");
-        words.insert(16651, "contort");
-        words.insert(16652, "contour");
-        words.insert(16653, "contrite");
-        words.insert(16654, "control");
-        words.insert(16655, "contusion");
-        words.insert(16656, "convene");
-        words.insert(16661, "convent");
-        words.insert(16662, "copartner");
-        words.insert(16663, "cope");
-        words.insert(16664, "copied");
-        words.insert(16665, "copier");
-        words.insert(16666, "copilot");
-        words.insert(21111, "coping");
-        words.insert(21112, "copious");
-        words.insert(21113, "copper");
-        words.insert(21114, "copy");
-        words.insert(21115, "coral");
-        words.insert(21116, "cork");
-        words.insert(21121, "cornball");
-        words.insert(21122, "cornbread");
-        words.insert(21123, "corncob");
-        words.insert(21124, "cornea");
-        words.insert(21125, "corned");
-        words.insert(21126, "corner");
-        words.insert(21131, "cornfield");
-        words.insert(21132, "cornflake");
-        words.insert(21133, "cornhusk");
-        words.insert(21134, "cornmeal");
-        words.insert(21135, "cornstalk");
-        words.insert(21136, "corny");
-        words.insert(21141, "coronary");
-        words.insert(21142, "coroner");
-        words.insert(21143, "corporal");
-        words.insert(21144, "corporate");
-        words.insert(21145, "corral");
-        words.insert(21146, "correct");
-        words.insert(21151, "corridor");
-        words.insert(21152, "corrode");
-        words.insert(21153, "corroding");
-        words.insert(21154, "corrosive");
-        words.insert(21155, "corsage");
-        words.insert(21156, "corset");
-        words.insert(21161, "cortex");
-        words.insert(21162, "cosigner");
-        words.insert(21163, "cosmetics");
-        words.insert(21164, "cosmic");
-        words.insert(21165, "cosmos");
-        words.insert(21166, "cosponsor");
-        words.insert(21211, "cost");
-        words.insert(21212, "cottage");
-        words.insert(21213, "cotton");
-        words.insert(21214, "couch");
-        words.insert(21215, "cough");
-        words.insert(21216, "could");
-        words.insert(21221, "countable");
-        words.insert(21222, "countdown");
-        words.insert(21223, "counting");
-        words.insert(21224, "countless");
-        words.insert(21225, "country");
-        words.insert(21226, "county");
-        words.insert(21231, "courier");
-        words.insert(21232, "covenant");
-        words.insert(21233, "cover");
-        words.insert(21234, "coveted");
-        words.insert(21235, "coveting");
-        words.insert(21236, "coyness");
-        words.insert(21241, "cozily");
-        words.insert(21242, "coziness");
-        words.insert(21243, "cozy");
-        words.insert(21244, "crabbing");
-        words.insert(21245, "crabgrass");
-        words.insert(21246, "crablike");
-        words.insert(21251, "crabmeat");
-        words.insert(21252, "cradle");
-        words.insert(21253, "cradling");
-        words.insert(21254, "crafter");
-        words.insert(21255, "craftily");
-        words.insert(21256, "craftsman");
-        words.insert(21261, "craftwork");
-        words.insert(21262, "crafty");
-        words.insert(21263, "cramp");
-        words.insert(21264, "cranberry");
-        words.insert(21265, "crane");
-        words.insert(21266, "cranial");
-        words.insert(21311, "cranium");
-        words.insert(21312, "crank");
-        words.insert(21313, "crate");
-        words.insert(21314, "crave");
-        words.insert(21315, "craving");
-        words.insert(21316, "crawfish");
-        words.insert(21321, "crawlers");
-        words.insert(21322, "crawling");
-        words.insert(21323, "crayfish");
-        words.insert(21324, "crayon");
-        words.insert(21325, "crazed");
-        words.insert(21326, "crazily");
-        words.insert(21331, "craziness");
-        words.insert(21332, "crazy");
-        words.insert(21333, "creamed");
-        words.insert(21334, "creamer");
-        words.insert(21335, "creamlike");
-        words.insert(21336, "crease");
-        words.insert(21341, "creasing");
-        words.insert(21342, "creatable");
-        words.insert(21343, "create");
-        words.insert(21344, "creation");
-        words.insert(21345, "creative");
-        words.insert(21346, "creature");
-        words.insert(21351, "credible");
-        words.insert(21352, "credibly");
-        words.insert(21353, "credit");
-        words.insert(21354, "creed");
-        words.insert(21355, "creme");
-        words.insert(21356, "creole");
-        words.insert(21361, "crepe");
-        words.insert(21362, "crept");
-        words.insert(21363, "crescent");
-        words.insert(21364, "crested");
-        words.insert(21365, "cresting");
-        words.insert(21366, "crestless");
-        words.insert(21411, "crevice");
-        words.insert(21412, "crewless");
-        words.insert(21413, "crewman");
-        words.insert(21414, "crewmate");
-        words.insert(21415, "crib");
-        words.insert(21416, "cricket");
-        words.insert(21421, "cried");
-        words.insert(21422, "crier");
-        words.insert(21423, "crimp");
-        words.insert(21424, "crimson");
-        words.insert(21425, "cringe");
-        words.insert(21426, "cringing");
-        words.insert(21431, "crinkle");
-        words.insert(21432, "crinkly");
-        words.insert(21433, "crisped");
-        words.insert(21434, "crisping");
-        words.insert(21435, "crisply");
-        words.insert(21436, "crispness");
-        words.insert(21441, "crispy");
-        words.insert(21442, "criteria");
-        words.insert(21443, "critter");
-        words.insert(21444, "croak");
-        words.insert(21445, "crock");
-        words.insert(21446, "crook");
-        words.insert(21451, "croon");
-        words.insert(21452, "crop");
-        words.insert(21453, "cross");
-        words.insert(21454, "crouch");
-        words.insert(21455, "crouton");
-        words.insert(21456, "crowbar");
-        words.insert(21461, "crowd");
-        words.insert(21462, "crown");
-        words.insert(21463, "crucial");
-        words.insert(21464, "crudely");
-        words.insert(21465, "crudeness");
-        words.insert(21466, "cruelly");
-        words.insert(21511, "cruelness");
-        words.insert(21512, "cruelty");
-        words.insert(21513, "crumb");
-        words.insert(21514, "crummiest");
-        words.insert(21515, "crummy");
-        words.insert(21516, "crumpet");
-        words.insert(21521, "crumpled");
-        words.insert(21522, "cruncher");
-        words.insert(21523, "crunching");
-        words.insert(21524, "crunchy");
-        words.insert(21525, "crusader");
-        words.insert(21526, "crushable");
-        words.insert(21531, "crushed");
-        words.insert(21532, "crusher");
-        words.insert(21533, "crushing");
-        words.insert(21534, "crust");
-        words.insert(21535, "crux");
-        words.insert(21536, "crying");
-        words.insert(21541, "cryptic");
-        words.insert(21542, "crystal");
-        words.insert(21543, "cubbyhole");
-        words.insert(21544, "cube");
-        words.insert(21545, "cubical");
-        words.insert(21546, "cubicle");
-        words.insert(21551, "cucumber");
-        words.insert(21552, "cuddle");
-        words.insert(21553, "cuddly");
-        words.insert(21554, "cufflink");
-        words.insert(21555, "culinary");
-        words.insert(21556, "culminate");
-        words.insert(21561, "culpable");
-        words.insert(21562, "culprit");
-        words.insert(21563, "cultivate");
-        words.insert(21564, "cultural");
-        words.insert(21565, "culture");
-        words.insert(21566, "cupbearer");
-        words.insert(21611, "cupcake");
-        words.insert(21612, "cupid");
-        words.insert(21613, "cupped");
-        words.insert(21614, "cupping");
-        words.insert(21615, "curable");
-        words.insert(21616, "curator");
-        words.insert(21621, "curdle");
-        words.insert(21622, "cure");
-        words.insert(21623, "curfew");
-        words.insert(21624, "curing");
-        words.insert(21625, "curled");
-        words.insert(21626, "curler");
-        words.insert(21631, "curliness");
-        words.insert(21632, "curling");
-        words.insert(21633, "curly");
-        words.insert(21634, "curry");
-        words.insert(21635, "curse");
-        words.insert(21636, "cursive");
-        words.insert(21641, "cursor");
-        words.insert(21642, "curtain");
-        words.insert(21643, "curtly");
-        words.insert(21644, "curtsy");
-        words.insert(21645, "curvature");
-        words.insert(21646, "curve");
-        words.insert(21651, "curvy");
-        words.insert(21652, "cushy");
-        words.insert(21653, "cusp");
-        words.insert(21654, "cussed");
-        words.insert(21655, "custard");
-        words.insert(21656, "custodian");
-        words.insert(21661, "custody");
-        words.insert(21662, "customary");
-        words.insert(21663, "customer");
-        words.insert(21664, "customize");
-        words.insert(21665, "customs");
-        words.insert(21666, "cut");
-        words.insert(22111, "cycle");
-        words.insert(22112, "cyclic");
-        words.insert(22113, "cycling");
-        words.insert(22114, "cyclist");
-        words.insert(22115, "cylinder");
-        words.insert(22116, "cymbal");
-        words.insert(22121, "cytoplasm");
-        words.insert(22122, "cytoplast");
-        words.insert(22123, "dab");
-        words.insert(22124, "dad");
-        words.insert(22125, "daffodil");
-        words.insert(22126, "dagger");
-        words.insert(22131, "daily");
-        words.insert(22132, "daintily");
-        words.insert(22133, "dainty");
-        words.insert(22134, "dairy");
-        words.insert(22135, "daisy");
-        words.insert(22136, "dallying");
-        words.insert(22141, "dance");
-        words.insert(22142, "dancing");
-        words.insert(22143, "dandelion");
-        words.insert(22144, "dander");
-        words.insert(22145, "dandruff");
-        words.insert(22146, "dandy");
-        words.insert(22151, "danger");
-        words.insert(22152, "dangle");
-        words.insert(22153, "dangling");
-        words.insert(22154, "daredevil");
-        words.insert(22155, "dares");
-        words.insert(22156, "daringly");
-        words.insert(22161, "darkened");
-        words.insert(22162, "darkening");
-        words.insert(22163, "darkish");
-        words.insert(22164, "darkness");
-        words.insert(22165, "darkroom");
-        words.insert(22166, "darling");
-        words.insert(22211, "darn");
-        words.insert(22212, "dart");
-        words.insert(22213, "darwinism");
-        words.insert(22214, "dash");
-        words.insert(22215, "dastardly");
-        words.insert(22216, "data");
-        words.insert(22221, "datebook");
-        words.insert(22222, "dating");
-        words.insert(22223, "daughter");
-        words.insert(22224, "daunting");
-        words.insert(22225, "dawdler");
-        words.insert(22226, "dawn");
-        words.insert(22231, "daybed");
-        words.insert(22232, "daybreak");
-        words.insert(22233, "daycare");
-        words.insert(22234, "daydream");
-        words.insert(22235, "daylight");
-        words.insert(22236, "daylong");
-        words.insert(22241, "dayroom");
-        words.insert(22242, "daytime");
-        words.insert(22243, "dazzler");
-        words.insert(22244, "dazzling");
-        words.insert(22245, "deacon");
-        words.insert(22246, "deafening");
-        words.insert(22251, "deafness");
-        words.insert(22252, "dealer");
-        words.insert(22253, "dealing");
-        words.insert(22254, "dealmaker");
-        words.insert(22255, "dealt");
-        words.insert(22256, "dean");
-        words.insert(22261, "debatable");
-        words.insert(22262, "debate");
-        words.insert(22263, "debating");
-        words.insert(22264, "debit");
-        words.insert(22265, "debrief");
-        words.insert(22266, "debtless");
-        words.insert(22311, "debtor");
-        words.insert(22312, "debug");
-        words.insert(22313, "debunk");
-        words.insert(22314, "decade");
-        words.insert(22315, "decaf");
-        words.insert(22316, "decal");
-        words.insert(22321, "decathlon");
-        words.insert(22322, "decay");
-        words.insert(22323, "deceased");
-        words.insert(22324, "deceit");
-        words.insert(22325, "deceiver");
-        words.insert(22326, "deceiving");
-        words.insert(22331, "december");
-        words.insert(22332, "decency");
-        words.insert(22333, "decent");
-        words.insert(22334, "deception");
-        words.insert(22335, "deceptive");
-        words.insert(22336, "decibel");
-        words.insert(22341, "decidable");
-        words.insert(22342, "decimal");
-        words.insert(22343, "decimeter");
-        words.insert(22344, "decipher");
-        words.insert(22345, "deck");
-        words.insert(22346, "declared");
-        words.insert(22351, "decline");
-        words.insert(22352, "decode");
-        words.insert(22353, "decompose");
-        words.insert(22354, "decorated");
-        words.insert(22355, "decorator");
-        words.insert(22356, "decoy");
-        words.insert(22361, "decrease");
-        words.insert(22362, "decree");
-        words.insert(22363, "dedicate");
-        words.insert(22364, "dedicator");
-        words.insert(22365, "deduce");
-        words.insert(22366, "deduct");
-        words.insert(22411, "deed");
-        words.insert(22412, "deem");
-        words.insert(22413, "deepen");
-        words.insert(22414, "deeply");
-        words.insert(22415, "deepness");
-        words.insert(22416, "deface");
-        words.insert(22421, "defacing");
-        words.insert(22422, "defame");
-        words.insert(22423, "default");
-        words.insert(22424, "defeat");
-        words.insert(22425, "defection");
-        words.insert(22426, "defective");
-        words.insert(22431, "defendant");
-        words.insert(22432, "defender");
-        words.insert(22433, "defense");
-        words.insert(22434, "defensive");
-        words.insert(22435, "deferral");
-        words.insert(22436, "deferred");
-        words.insert(22441, "defiance");
-        words.insert(22442, "defiant");
-        words.insert(22443, "defile");
-        words.insert(22444, "defiling");
-        words.insert(22445, "define");
-        words.insert(22446, "definite");
-        words.insert(22451, "deflate");
-        words.insert(22452, "deflation");
-        words.insert(22453, "deflator");
-        words.insert(22454, "deflected");
-        words.insert(22455, "deflector");
-        words.insert(22456, "defog");
-        words.insert(22461, "deforest");
-        words.insert(22462, "defraud");
-        words.insert(22463, "defrost");
-        words.insert(22464, "deftly");
-        words.insert(22465, "defuse");
-        words.insert(22466, "defy");
-        words.insert(22511, "degraded");
-        words.insert(22512, "degrading");
-        words.insert(22513, "degrease");
-        words.insert(22514, "degree");
-        words.insert(22515, "dehydrate");
-        words.insert(22516, "deity");
-        words.insert(22521, "dejected");
-        words.insert(22522, "delay");
-        words.insert(22523, "delegate");
-        words.insert(22524, "delegator");
-        words.insert(22525, "delete");
-        words.insert(22526, "deletion");
-        words.insert(22531, "delicacy");
-        words.insert(22532, "delicate");
-        words.insert(22533, "delicious");
-        words.insert(22534, "delighted");
-        words.insert(22535, "delirious");
-        words.insert(22536, "delirium");
-        words.insert(22541, "deliverer");
-        words.insert(22542, "delivery");
-        words.insert(22543, "delouse");
-        words.insert(22544, "delta");
-        words.insert(22545, "deluge");
-        words.insert(22546, "delusion");
-        words.insert(22551, "deluxe");
-        words.insert(22552, "demanding");
-        words.insert(22553, "demeaning");
-        words.insert(22554, "demeanor");
-        words.insert(22555, "demise");
-        words.insert(22556, "democracy");
-        words.insert(22561, "democrat");
-        words.insert(22562, "demote");
-        words.insert(22563, "demotion");
-        words.insert(22564, "demystify");
-        words.insert(22565, "denatured");
-        words.insert(22566, "deniable");
-        words.insert(22611, "denial");
-        words.insert(22612, "denim");
-        words.insert(22613, "denote");
-        words.insert(22614, "dense");
-        words.insert(22615, "density");
-        words.insert(22616, "dental");
-        words.insert(22621, "dentist");
-        words.insert(22622, "denture");
-        words.insert(22623, "deny");
-        words.insert(22624, "deodorant");
-        words.insert(22625, "deodorize");
-        words.insert(22626, "departed");
-        words.insert(22631, "departure");
-        words.insert(22632, "depict");
-        words.insert(22633, "deplete");
-        words.insert(22634, "depletion");
-        words.insert(22635, "deplored");
-        words.insert(22636, "deploy");
-        words.insert(22641, "deport");
-        words.insert(22642, "depose");
-        words.insert(22643, "depraved");
-        words.insert(22644, "depravity");
-        words.insert(22645, "deprecate");
-        words.insert(22646, "depress");
-        words.insert(22651, "deprive");
-        words.insert(22652, "depth");
-        words.insert(22653, "deputize");
-        words.insert(22654, "deputy");
-        words.insert(22655, "derail");
-        words.insert(22656, "deranged");
-        words.insert(22661, "derby");
-        words.insert(22662, "derived");
-        words.insert(22663, "desecrate");
-        words.insert(22664, "deserve");
-        words.insert(22665, "deserving");
-        words.insert(22666, "designate");
-        words.insert(23111, "designed");
-        words.insert(23112, "designer");
-        words.insert(23113, "designing");
-        words.insert(23114, "deskbound");
-        words.insert(23115, "desktop");
-        words.insert(23116, "deskwork");
-        words.insert(23121, "desolate");
-        words.insert(23122, "despair");
-        words.insert(23123, "despise");
-        words.insert(23124, "despite");
-        words.insert(23125, "destiny");
-        words.insert(23126, "destitute");
-        words.insert(23131, "destruct");
-        words.insert(23132, "detached");
-        words.insert(23133, "detail");
-        words.insert(23134, "detection");
-        words.insert(23135, "detective");
-        words.insert(23136, "detector");
-        words.insert(23141, "detention");
-        words.insert(23142, "detergent");
-        words.insert(23143, "detest");
-        words.insert(23144, "detonate");
-        words.insert(23145, "detonator");
-        words.insert(23146, "detoxify");
-        words.insert(23151, "detract");
-        words.insert(23152, "deuce");
-        words.insert(23153, "devalue");
-        words.insert(23154, "deviancy");
-        words.insert(23155, "deviant");
-        words.insert(23156, "deviate");
-        words.insert(23161, "deviation");
-        words.insert(23162, "deviator");
-        words.insert(23163, "device");
-        words.insert(23164, "devious");
-        words.insert(23165, "devotedly");
-        words.insert(23166, "devotee");
-        words.insert(23211, "devotion");
-        words.insert(23212, "devourer");
-        words.insert(23213, "devouring");
-        words.insert(23214, "devoutly");
-        words.insert(23215, "dexterity");
-        words.insert(23216, "dexterous");
-        words.insert(23221, "diabetes");
-        words.insert(23222, "diabetic");
-        words.insert(23223, "diabolic");
-        words.insert(23224, "diagnoses");
-        words.insert(23225, "diagnosis");
-        words.insert(23226, "diagram");
-        words.insert(23231, "dial");
-        words.insert(23232, "diameter");
-        words.insert(23233, "diaper");
-        words.insert(23234, "diaphragm");
-        words.insert(23235, "diary");
-        words.insert(23236, "dice");
-        words.insert(23241, "dicing");
-        words.insert(23242, "dictate");
-        words.insert(23243, "dictation");
-        words.insert(23244, "dictator");
-        words.insert(23245, "difficult");
-        words.insert(23246, "diffused");
-        words.insert(23251, "diffuser");
-        words.insert(23252, "diffusion");
-        words.insert(23253, "diffusive");
-        words.insert(23254, "dig");
-        words.insert(23255, "dilation");
-        words.insert(23256, "diligence");
-        words.insert(23261, "diligent");
-        words.insert(23262, "dill");
-        words.insert(23263, "dilute");
-        words.insert(23264, "dime");
-        words.insert(23265, "diminish");
-        words.insert(23266, "dimly");
-        words.insert(23311, "dimmed");
-        words.insert(23312, "dimmer");
-        words.insert(23313, "dimness");
-        words.insert(23314, "dimple");
-        words.insert(23315, "diner");
-        words.insert(23316, "dingbat");
-        words.insert(23321, "dinghy");
-        words.insert(23322, "dinginess");
-        words.insert(23323, "dingo");
-        words.insert(23324, "dingy");
-        words.insert(23325, "dining");
-        words.insert(23326, "dinner");
-        words.insert(23331, "diocese");
-        words.insert(23332, "dioxide");
-        words.insert(23333, "diploma");
-        words.insert(23334, "dipped");
-        words.insert(23335, "dipper");
-        words.insert(23336, "dipping");
-        words.insert(23341, "directed");
-        words.insert(23342, "direction");
-        words.insert(23343, "directive");
-        words.insert(23344, "directly");
-        words.insert(23345, "directory");
-        words.insert(23346, "direness");
-        words.insert(23351, "dirtiness");
-        words.insert(23352, "disabled");
-        words.insert(23353, "disagree");
-        words.insert(23354, "disallow");
-        words.insert(23355, "disarm");
-        words.insert(23356, "disarray");
-        words.insert(23361, "disaster");
-        words.insert(23362, "disband");
-        words.insert(23363, "disbelief");
-        words.insert(23364, "disburse");
-        words.insert(23365, "discard");
-        words.insert(23366, "discern");
-        words.insert(23411, "discharge");
-        words.insert(23412, "disclose");
-        words.insert(23413, "discolor");
-        words.insert(23414, "discount");
-        words.insert(23415, "discourse");
-        words.insert(23416, "discover");
-        words.insert(23421, "discuss");
-        words.insert(23422, "disdain");
-        words.insert(23423, "disengage");
-        words.insert(23424, "disfigure");
-        words.insert(23425, "disgrace");
-        words.insert(23426, "dish");
-        words.insert(23431, "disinfect");
-        words.insert(23432, "disjoin");
-        words.insert(23433, "disk");
-        words.insert(23434, "dislike");
-        words.insert(23435, "disliking");
-        words.insert(23436, "dislocate");
-        words.insert(23441, "dislodge");
-        words.insert(23442, "disloyal");
-        words.insert(23443, "dismantle");
-        words.insert(23444, "dismay");
-        words.insert(23445, "dismiss");
-        words.insert(23446, "dismount");
-        words.insert(23451, "disobey");
-        words.insert(23452, "disorder");
-        words.insert(23453, "disown");
-        words.insert(23454, "disparate");
-        words.insert(23455, "disparity");
-        words.insert(23456, "dispatch");
-        words.insert(23461, "dispense");
-        words.insert(23462, "dispersal");
-        words.insert(23463, "dispersed");
-        words.insert(23464, "disperser");
-        words.insert(23465, "displace");
-        words.insert(23466, "display");
-        words.insert(23511, "displease");
-        words.insert(23512, "disposal");
-        words.insert(23513, "dispose");
-        words.insert(23514, "disprove");
-        words.insert(23515, "dispute");
-        words.insert(23516, "disregard");
-        words.insert(23521, "disrupt");
-        words.insert(23522, "dissuade");
-        words.insert(23523, "distance");
-        words.insert(23524, "distant");
-        words.insert(23525, "distaste");
-        words.insert(23526, "distill");
-        words.insert(23531, "distinct");
-        words.insert(23532, "distort");
-        words.insert(23533, "distract");
-        words.insert(23534, "distress");
-        words.insert(23535, "district");
-        words.insert(23536, "distrust");
-        words.insert(23541, "ditch");
-        words.insert(23542, "ditto");
-        words.insert(23543, "ditzy");
-        words.insert(23544, "dividable");
-        words.insert(23545, "divided");
-        words.insert(23546, "dividend");
-        words.insert(23551, "dividers");
-        words.insert(23552, "dividing");
-        words.insert(23553, "divinely");
-        words.insert(23554, "diving");
-        words.insert(23555, "divinity");
-        words.insert(23556, "divisible");
-        words.insert(23561, "divisibly");
-        words.insert(23562, "division");
-        words.insert(23563, "divisive");
-        words.insert(23564, "divorcee");
-        words.insert(23565, "dizziness");
-        words.insert(23566, "dizzy");
-        words.insert(23611, "doable");
-        words.insert(23612, "docile");
-        words.insert(23613, "dock");
-        words.insert(23614, "doctrine");
-        words.insert(23615, "document");
-        words.insert(23616, "dodge");
-        words.insert(23621, "dodgy");
-        words.insert(23622, "doily");
-        words.insert(23623, "doing");
-        words.insert(23624, "dole");
-        words.insert(23625, "dollar");
-        words.insert(23626, "dollhouse");
-        words.insert(23631, "dollop");
-        words.insert(23632, "dolly");
-        words.insert(23633, "dolphin");
-        words.insert(23634, "domain");
-        words.insert(23635, "domelike");
-        words.insert(23636, "domestic");
-        words.insert(23641, "dominion");
-        words.insert(23642, "dominoes");
-        words.insert(23643, "donated");
-        words.insert(23644, "donation");
-        words.insert(23645, "donator");
-        words.insert(23646, "donor");
-        words.insert(23651, "donut");
-        words.insert(23652, "doodle");
-        words.insert(23653, "doorbell");
-        words.insert(23654, "doorframe");
-        words.insert(23655, "doorknob");
-        words.insert(23656, "doorman");
-        words.insert(23661, "doormat");
-        words.insert(23662, "doornail");
-        words.insert(23663, "doorpost");
-        words.insert(23664, "doorstep");
-        words.insert(23665, "doorstop");
-        words.insert(23666, "doorway");
-        words.insert(24111, "doozy");
-        words.insert(24112, "dork");
-        words.insert(24113, "dormitory");
-        words.insert(24114, "dorsal");
-        words.insert(24115, "dosage");
-        words.insert(24116, "dose");
-        words.insert(24121, "dotted");
-        words.insert(24122, "doubling");
-        words.insert(24123, "douche");
-        words.insert(24124, "dove");
-        words.insert(24125, "down");
-        words.insert(24126, "dowry");
-        words.insert(24131, "doze");
-        words.insert(24132, "drab");
-        words.insert(24133, "dragging");
-        words.insert(24134, "dragonfly");
-        words.insert(24135, "dragonish");
-        words.insert(24136, "dragster");
-        words.insert(24141, "drainable");
-        words.insert(24142, "drainage");
-        words.insert(24143, "drained");
-        words.insert(24144, "drainer");
-        words.insert(24145, "drainpipe");
-        words.insert(24146, "dramatic");
-        words.insert(24151, "dramatize");
-        words.insert(24152, "drank");
-        words.insert(24153, "drapery");
-        words.insert(24154, "drastic");
-        words.insert(24155, "draw");
-        words.insert(24156, "dreaded");
-        words.insert(24161, "dreadful");
-        words.insert(24162, "dreadlock");
-        words.insert(24163, "dreamboat");
-        words.insert(24164, "dreamily");
-        words.insert(24165, "dreamland");
-        words.insert(24166, "dreamless");
-        words.insert(24211, "dreamlike");
-        words.insert(24212, "dreamt");
-        words.insert(24213, "dreamy");
-        words.insert(24214, "drearily");
-        words.insert(24215, "dreary");
-        words.insert(24216, "drench");
-        words.insert(24221, "dress");
-        words.insert(24222, "drew");
-        words.insert(24223, "dribble");
-        words.insert(24224, "dried");
-        words.insert(24225, "drier");
-        words.insert(24226, "drift");
-        words.insert(24231, "driller");
-        words.insert(24232, "drilling");
-        words.insert(24233, "drinkable");
-        words.insert(24234, "drinking");
-        words.insert(24235, "dripping");
-        words.insert(24236, "drippy");
-        words.insert(24241, "drivable");
-        words.insert(24242, "driven");
-        words.insert(24243, "driver");
-        words.insert(24244, "driveway");
-        words.insert(24245, "driving");
-        words.insert(24246, "drizzle");
-        words.insert(24251, "drizzly");
-        words.insert(24252, "drone");
-        words.insert(24253, "drool");
-        words.insert(24254, "droop");
-        words.insert(24255, "drop-down");
-        words.insert(24256, "dropbox");
-        words.insert(24261, "dropkick");
-        words.insert(24262, "droplet");
-        words.insert(24263, "dropout");
-        words.insert(24264, "dropper");
-        words.insert(24265, "drove");
-        words.insert(24266, "drown");
-        words.insert(24311, "drowsily");
-        words.insert(24312, "drudge");
-        words.insert(24313, "drum");
-        words.insert(24314, "dry");
-        words.insert(24315, "dubbed");
-        words.insert(24316, "dubiously");
-        words.insert(24321, "duchess");
-        words.insert(24322, "duckbill");
-        words.insert(24323, "ducking");
-        words.insert(24324, "duckling");
-        words.insert(24325, "ducktail");
-        words.insert(24326, "ducky");
-        words.insert(24331, "duct");
-        words.insert(24332, "dude");
-        words.insert(24333, "duffel");
-        words.insert(24334, "dugout");
-        words.insert(24335, "duh");
-        words.insert(24336, "duke");
-        words.insert(24341, "duller");
-        words.insert(24342, "dullness");
-        words.insert(24343, "duly");
-        words.insert(24344, "dumping");
-        words.insert(24345, "dumpling");
-        words.insert(24346, "dumpster");
-        words.insert(24351, "duo");
-        words.insert(24352, "dupe");
-        words.insert(24353, "duplex");
-        words.insert(24354, "duplicate");
-        words.insert(24355, "duplicity");
-        words.insert(24356, "durable");
-        words.insert(24361, "durably");
-        words.insert(24362, "duration");
-        words.insert(24363, "duress");
-        words.insert(24364, "during");
-        words.insert(24365, "dusk");
-        words.insert(24366, "dust");
-        words.insert(24411, "dutiful");
-        words.insert(24412, "duty");
-        words.insert(24413, "duvet");
-        words.insert(24414, "dwarf");
-        words.insert(24415, "dweeb");
-        words.insert(24416, "dwelled");
-        words.insert(24421, "dweller");
-        words.insert(24422, "dwelling");
-        words.insert(24423, "dwindle");
-        words.insert(24424, "dwindling");
-        words.insert(24425, "dynamic");
-        words.insert(24426, "dynamite");
-        words.insert(24431, "dynasty");
-        words.insert(24432, "dyslexia");
-        words.insert(24433, "dyslexic");
-        words.insert(24434, "each");
-        words.insert(24435, "eagle");
-        words.insert(24436, "earache");
-        words.insert(24441, "eardrum");
-        words.insert(24442, "earflap");
-        words.insert(24443, "earful");
-        words.insert(24444, "earlobe");
-        words.insert(24445, "early");
-        words.insert(24446, "earmark");
-        words.insert(24451, "earmuff");
-        words.insert(24452, "earphone");
-        words.insert(24453, "earpiece");
-        words.insert(24454, "earplugs");
-        words.insert(24455, "earring");
-        words.insert(24456, "earshot");
-        words.insert(24461, "earthen");
-        words.insert(24462, "earthlike");
-        words.insert(24463, "earthling");
-        words.insert(24464, "earthly");
-        words.insert(24465, "earthworm");
-        words.insert(24466, "earthy");
-        words.insert(24511, "earwig");
-        words.insert(24512, "easeful");
-        words.insert(24513, "easel");
-        words.insert(24514, "easiest");
-        words.insert(24515, "easily");
-        words.insert(24516, "easiness");
-        words.insert(24521, "easing");
-        words.insert(24522, "eastbound");
-        words.insert(24523, "eastcoast");
-        words.insert(24524, "easter");
-        words.insert(24525, "eastward");
-        words.insert(24526, "eatable");
-        words.insert(24531, "eaten");
-        words.insert(24532, "eatery");
-        words.insert(24533, "eating");
-        words.insert(24534, "eats");
-        words.insert(24535, "ebay");
-        words.insert(24536, "ebony");
-        words.insert(24541, "ebook");
-        words.insert(24542, "ecard");
-        words.insert(24543, "eccentric");
-        words.insert(24544, "echo");
-        words.insert(24545, "eclair");
-        words.insert(24546, "eclipse");
-        words.insert(24551, "ecologist");
-        words.insert(24552, "ecology");
-        words.insert(24553, "economic");
-        words.insert(24554, "economist");
-        words.insert(24555, "economy");
-        words.insert(24556, "ecosphere");
-        words.insert(24561, "ecosystem");
-        words.insert(24562, "edge");
-        words.insert(24563, "edginess");
-        words.insert(24564, "edging");
-        words.insert(24565, "edgy");
-        words.insert(24566, "edition");
-        words.insert(24611, "editor");
-        words.insert(24612, "educated");
-        words.insert(24613, "education");
-        words.insert(24614, "educator");
-        words.insert(24615, "eel");
-        words.insert(24616, "effective");
-        words.insert(24621, "effects");
-        words.insert(24622, "efficient");
-        words.insert(24623, "effort");
-        words.insert(24624, "eggbeater");
-        words.insert(24625, "egging");
-        words.insert(24626, "eggnog");
-        words.insert(24631, "eggplant");
-        words.insert(24632, "eggshell");
-        words.insert(24633, "egomaniac");
-        words.insert(24634, "egotism");
-        words.insert(24635, "egotistic");
-        words.insert(24636, "either");
-        words.insert(24641, "eject");
-        words.insert(24642, "elaborate");
-        words.insert(24643, "elastic");
-        words.insert(24644, "elated");
-        words.insert(24645, "elbow");
-        words.insert(24646, "eldercare");
-        words.insert(24651, "elderly");
-        words.insert(24652, "eldest");
-        words.insert(24653, "electable");
-        words.insert(24654, "election");
-        words.insert(24655, "elective");
-        words.insert(24656, "elephant");
-        words.insert(24661, "elevate");
-        words.insert(24662, "elevating");
-        words.insert(24663, "elevation");
-        words.insert(24664, "elevator");
-        words.insert(24665, "eleven");
-        words.insert(24666, "elf");
-        words.insert(25111, "eligible");
-        words.insert(25112, "eligibly");
-        words.insert(25113, "eliminate");
-        words.insert(25114, "elite");
-        words.insert(25115, "elitism");
-        words.insert(25116, "elixir");
-        words.insert(25121, "elk");
-        words.insert(25122, "ellipse");
-        words.insert(25123, "elliptic");
-        words.insert(25124, "elm");
-        words.insert(25125, "elongated");
-        words.insert(25126, "elope");
-        words.insert(25131, "eloquence");
-        words.insert(25132, "eloquent");
-        words.insert(25133, "elsewhere");
-        words.insert(25134, "elude");
-        words.insert(25135, "elusive");
-        words.insert(25136, "elves");
-        words.insert(25141, "email");
-        words.insert(25142, "embargo");
-        words.insert(25143, "embark");
-        words.insert(25144, "embassy");
-        words.insert(25145, "embattled");
-        words.insert(25146, "embellish");
-        words.insert(25151, "ember");
-        words.insert(25152, "embezzle");
-        words.insert(25153, "emblaze");
-        words.insert(25154, "emblem");
-        words.insert(25155, "embody");
-        words.insert(25156, "embolism");
-        words.insert(25161, "emboss");
-        words.insert(25162, "embroider");
-        words.insert(25163, "emcee");
-        words.insert(25164, "emerald");
-        words.insert(25165, "emergency");
-        words.insert(25166, "emission");
-        words.insert(25211, "emit");
-        words.insert(25212, "emote");
-        words.insert(25213, "emoticon");
-        words.insert(25214, "emotion");
-        words.insert(25215, "empathic");
-        words.insert(25216, "empathy");
-        words.insert(25221, "emperor");
-        words.insert(25222, "emphases");
-        words.insert(25223, "emphasis");
-        words.insert(25224, "emphasize");
-        words.insert(25225, "emphatic");
-        words.insert(25226, "empirical");
-        words.insert(25231, "employed");
-        words.insert(25232, "employee");
-        words.insert(25233, "employer");
-        words.insert(25234, "emporium");
-        words.insert(25235, "empower");
-        words.insert(25236, "emptier");
-        words.insert(25241, "emptiness");
-        words.insert(25242, "empty");
-        words.insert(25243, "emu");
-        words.insert(25244, "enable");
-        words.insert(25245, "enactment");
-        words.insert(25246, "enamel");
-        words.insert(25251, "enchanted");
-        words.insert(25252, "enchilada");
-        words.insert(25253, "encircle");
-        words.insert(25254, "enclose");
-        words.insert(25255, "enclosure");
-        words.insert(25256, "encode");
-        words.insert(25261, "encore");
-        words.insert(25262, "encounter");
-        words.insert(25263, "encourage");
-        words.insert(25264, "encroach");
-        words.insert(25265, "encrust");
-        words.insert(25266, "encrypt");
-        words.insert(25311, "endanger");
-        words.insert(25312, "endeared");
-        words.insert(25313, "endearing");
-        words.insert(25314, "ended");
-        words.insert(25315, "ending");
-        words.insert(25316, "endless");
-        words.insert(25321, "endnote");
-        words.insert(25322, "endocrine");
-        words.insert(25323, "endorphin");
-        words.insert(25324, "endorse");
-        words.insert(25325, "endowment");
-        words.insert(25326, "endpoint");
-        words.insert(25331, "endurable");
-        words.insert(25332, "endurance");
-        words.insert(25333, "enduring");
-        words.insert(25334, "energetic");
-        words.insert(25335, "energize");
-        words.insert(25336, "energy");
-        words.insert(25341, "enforced");
-        words.insert(25342, "enforcer");
-        words.insert(25343, "engaged");
-        words.insert(25344, "engaging");
-        words.insert(25345, "engine");
-        words.insert(25346, "engorge");
-        words.insert(25351, "engraved");
-        words.insert(25352, "engraver");
-        words.insert(25353, "engraving");
-        words.insert(25354, "engross");
-        words.insert(25355, "engulf");
-        words.insert(25356, "enhance");
-        words.insert(25361, "enigmatic");
-        words.insert(25362, "enjoyable");
-        words.insert(25363, "enjoyably");
-        words.insert(25364, "enjoyer");
-        words.insert(25365, "enjoying");
-        words.insert(25366, "enjoyment");
-        words.insert(25411, "enlarged");
-        words.insert(25412, "enlarging");
-        words.insert(25413, "enlighten");
-        words.insert(25414, "enlisted");
-        words.insert(25415, "enquirer");
-        words.insert(25416, "enrage");
-        words.insert(25421, "enrich");
-        words.insert(25422, "enroll");
-        words.insert(25423, "enslave");
-        words.insert(25424, "ensnare");
-        words.insert(25425, "ensure");
-        words.insert(25426, "entail");
-        words.insert(25431, "entangled");
-        words.insert(25432, "entering");
-        words.insert(25433, "entertain");
-        words.insert(25434, "enticing");
-        words.insert(25435, "entire");
-        words.insert(25436, "entitle");
-        words.insert(25441, "entity");
-        words.insert(25442, "entomb");
-        words.insert(25443, "entourage");
-        words.insert(25444, "entrap");
-        words.insert(25445, "entree");
-        words.insert(25446, "entrench");
-        words.insert(25451, "entrust");
-        words.insert(25452, "entryway");
-        words.insert(25453, "entwine");
-        words.insert(25454, "enunciate");
-        words.insert(25455, "envelope");
-        words.insert(25456, "enviable");
-        words.insert(25461, "enviably");
-        words.insert(25462, "envious");
-        words.insert(25463, "envision");
-        words.insert(25464, "envoy");
-        words.insert(25465, "envy");
-        words.insert(25466, "enzyme");
-        words.insert(25511, "epic");
-        words.insert(25512, "epidemic");
-        words.insert(25513, "epidermal");
-        words.insert(25514, "epidermis");
-        words.insert(25515, "epidural");
-        words.insert(25516, "epilepsy");
-        words.insert(25521, "epileptic");
-        words.insert(25522, "epilogue");
-        words.insert(25523, "epiphany");
-        words.insert(25524, "episode");
-        words.insert(25525, "equal");
-        words.insert(25526, "equate");
-        words.insert(25531, "equation");
-        words.insert(25532, "equator");
-        words.insert(25533, "equinox");
-        words.insert(25534, "equipment");
-        words.insert(25535, "equity");
-        words.insert(25536, "equivocal");
-        words.insert(25541, "eradicate");
-        words.insert(25542, "erasable");
-        words.insert(25543, "erased");
-        words.insert(25544, "eraser");
-        words.insert(25545, "erasure");
-        words.insert(25546, "ergonomic");
-        words.insert(25551, "errand");
-        words.insert(25552, "errant");
-        words.insert(25553, "erratic");
-        words.insert(25554, "error");
-        words.insert(25555, "erupt");
-        words.insert(25556, "escalate");
-        words.insert(25561, "escalator");
-        words.insert(25562, "escapable");
-        words.insert(25563, "escapade");
-        words.insert(25564, "escapist");
-        words.insert(25565, "escargot");
-        words.insert(25566, "eskimo");
-        words.insert(25611, "esophagus");
-        words.insert(25612, "espionage");
-        words.insert(25613, "espresso");
-        words.insert(25614, "esquire");
-        words.insert(25615, "essay");
-        words.insert(25616, "essence");
-        words.insert(25621, "essential");
-        words.insert(25622, "establish");
-        words.insert(25623, "estate");
-        words.insert(25624, "esteemed");
-        words.insert(25625, "estimate");
-        words.insert(25626, "estimator");
-        words.insert(25631, "estranged");
-        words.insert(25632, "estrogen");
-        words.insert(25633, "etching");
-        words.insert(25634, "eternal");
-        words.insert(25635, "eternity");
-        words.insert(25636, "ethanol");
-        words.insert(25641, "ether");
-        words.insert(25642, "ethically");
-        words.insert(25643, "ethics");
-        words.insert(25644, "euphemism");
-        words.insert(25645, "evacuate");
-        words.insert(25646, "evacuee");
-        words.insert(25651, "evade");
-        words.insert(25652, "evaluate");
-        words.insert(25653, "evaluator");
-        words.insert(25654, "evaporate");
-        words.insert(25655, "evasion");
-        words.insert(25656, "evasive");
-        words.insert(25661, "even");
-        words.insert(25662, "everglade");
-        words.insert(25663, "evergreen");
-        words.insert(25664, "everybody");
-        words.insert(25665, "everyday");
-        words.insert(25666, "everyone");
-        words.insert(26111, "evict");
-        words.insert(26112, "evidence");
-        words.insert(26113, "evident");
-        words.insert(26114, "evil");
-        words.insert(26115, "evoke");
-        words.insert(26116, "evolution");
-        words.insert(26121, "evolve");
-        words.insert(26122, "exact");
-        words.insert(26123, "exalted");
-        words.insert(26124, "example");
-        words.insert(26125, "excavate");
-        words.insert(26126, "excavator");
-        words.insert(26131, "exceeding");
-        words.insert(26132, "exception");
-        words.insert(26133, "excess");
-        words.insert(26134, "exchange");
-        words.insert(26135, "excitable");
-        words.insert(26136, "exciting");
-        words.insert(26141, "exclaim");
-        words.insert(26142, "exclude");
-        words.insert(26143, "excluding");
-        words.insert(26144, "exclusion");
-        words.insert(26145, "exclusive");
-        words.insert(26146, "excretion");
-        words.insert(26151, "excretory");
-        words.insert(26152, "excursion");
-        words.insert(26153, "excusable");
-        words.insert(26154, "excusably");
-        words.insert(26155, "excuse");
-        words.insert(26156, "exemplary");
-        words.insert(26161, "exemplify");
-        words.insert(26162, "exemption");
-        words.insert(26163, "exerciser");
-        words.insert(26164, "exert");
-        words.insert(26165, "exes");
-        words.insert(26166, "exfoliate");
-        words.insert(26211, "exhale");
-        words.insert(26212, "exhaust");
-        words.insert(26213, "exhume");
-        words.insert(26214, "exile");
-        words.insert(26215, "existing");
-        words.insert(26216, "exit");
-        words.insert(26221, "exodus");
-        words.insert(26222, "exonerate");
-        words.insert(26223, "exorcism");
-        words.insert(26224, "exorcist");
-        words.insert(26225, "expand");
-        words.insert(26226, "expanse");
-        words.insert(26231, "expansion");
-        words.insert(26232, "expansive");
-        words.insert(26233, "expectant");
-        words.insert(26234, "expedited");
-        words.insert(26235, "expediter");
-        words.insert(26236, "expel");
-        words.insert(26241, "expend");
-        words.insert(26242, "expenses");
-        words.insert(26243, "expensive");
-        words.insert(26244, "expert");
-        words.insert(26245, "expire");
-        words.insert(26246, "expiring");
-        words.insert(26251, "explain");
-        words.insert(26252, "expletive");
-        words.insert(26253, "explicit");
-        words.insert(26254, "explode");
-        words.insert(26255, "exploit");
-        words.insert(26256, "explore");
-        words.insert(26261, "exploring");
-        words.insert(26262, "exponent");
-        words.insert(26263, "exporter");
-        words.insert(26264, "exposable");
-        words.insert(26265, "expose");
-        words.insert(26266, "exposure");
-        words.insert(26311, "express");
-        words.insert(26312, "expulsion");
-        words.insert(26313, "exquisite");
-        words.insert(26314, "extended");
-        words.insert(26315, "extending");
-        words.insert(26316, "extent");
-        words.insert(26321, "extenuate");
-        words.insert(26322, "exterior");
-        words.insert(26323, "external");
-        words.insert(26324, "extinct");
-        words.insert(26325, "extortion");
-        words.insert(26326, "extradite");
-        words.insert(26331, "extras");
-        words.insert(26332, "extrovert");
-        words.insert(26333, "extrude");
-        words.insert(26334, "extruding");
-        words.insert(26335, "exuberant");
-        words.insert(26336, "fable");
-        words.insert(26341, "fabric");
-        words.insert(26342, "fabulous");
-        words.insert(26343, "facebook");
-        words.insert(26344, "facecloth");
-        words.insert(26345, "facedown");
-        words.insert(26346, "faceless");
-        words.insert(26351, "facelift");
-        words.insert(26352, "faceplate");
-        words.insert(26353, "faceted");
-        words.insert(26354, "facial");
-        words.insert(26355, "facility");
-        words.insert(26356, "facing");
-        words.insert(26361, "facsimile");
-        words.insert(26362, "faction");
-        words.insert(26363, "factoid");
-        words.insert(26364, "factor");
-        words.insert(26365, "factsheet");
-        words.insert(26366, "factual");
-        words.insert(26411, "faculty");
-        words.insert(26412, "fade");
-        words.insert(26413, "fading");
-        words.insert(26414, "failing");
-        words.insert(26415, "falcon");
-        words.insert(26416, "fall");
-        words.insert(26421, "false");
-        words.insert(26422, "falsify");
-        words.insert(26423, "fame");
-        words.insert(26424, "familiar");
-        words.insert(26425, "family");
-        words.insert(26426, "famine");
-        words.insert(26431, "famished");
-        words.insert(26432, "fanatic");
-        words.insert(26433, "fancied");
-        words.insert(26434, "fanciness");
-        words.insert(26435, "fancy");
-        words.insert(26436, "fanfare");
-        words.insert(26441, "fang");
-        words.insert(26442, "fanning");
-        words.insert(26443, "fantasize");
-        words.insert(26444, "fantastic");
-        words.insert(26445, "fantasy");
-        words.insert(26446, "fascism");
-        words.insert(26451, "fastball");
-        words.insert(26452, "faster");
-        words.insert(26453, "fasting");
-        words.insert(26454, "fastness");
-        words.insert(26455, "faucet");
-        words.insert(26456, "favorable");
-        words.insert(26461, "favorably");
-        words.insert(26462, "favored");
-        words.insert(26463, "favoring");
-        words.insert(26464, "favorite");
-        words.insert(26465, "fax");
-        words.insert(26466, "feast");
-        words.insert(26511, "federal");
-        words.insert(26512, "fedora");
-        words.insert(26513, "feeble");
-        words.insert(26514, "feed");
-        words.insert(26515, "feel");
-        words.insert(26516, "feisty");
-        words.insert(26521, "feline");
-        words.insert(26522, "felt-tip");
-        words.insert(26523, "feminine");
-        words.insert(26524, "feminism");
-        words.insert(26525, "feminist");
-        words.insert(26526, "feminize");
-        words.insert(26531, "femur");
-        words.insert(26532, "fence");
-        words.insert(26533, "fencing");
-        words.insert(26534, "fender");
-        words.insert(26535, "ferment");
-        words.insert(26536, "fernlike");
-        words.insert(26541, "ferocious");
-        words.insert(26542, "ferocity");
-        words.insert(26543, "ferret");
-        words.insert(26544, "ferris");
-        words.insert(26545, "ferry");
-        words.insert(26546, "fervor");
-        words.insert(26551, "fester");
-        words.insert(26552, "festival");
-        words.insert(26553, "festive");
-        words.insert(26554, "festivity");
-        words.insert(26555, "fetal");
-        words.insert(26556, "fetch");
-        words.insert(26561, "fever");
-        words.insert(26562, "fiber");
-        words.insert(26563, "fiction");
-        words.insert(26564, "fiddle");
-        words.insert(26565, "fiddling");
-        words.insert(26566, "fidelity");
-        words.insert(26611, "fidgeting");
-        words.insert(26612, "fidgety");
-        words.insert(26613, "fifteen");
-        words.insert(26614, "fifth");
-        words.insert(26615, "fiftieth");
-        words.insert(26616, "fifty");
-        words.insert(26621, "figment");
-        words.insert(26622, "figure");
-        words.insert(26623, "figurine");
-        words.insert(26624, "filing");
-        words.insert(26625, "filled");
-        words.insert(26626, "filler");
-        words.insert(26631, "filling");
-        words.insert(26632, "film");
-        words.insert(26633, "filter");
-        words.insert(26634, "filth");
-        words.insert(26635, "filtrate");
-        words.insert(26636, "finale");
-        words.insert(26641, "finalist");
-        words.insert(26642, "finalize");
-        words.insert(26643, "finally");
-        words.insert(26644, "finance");
-        words.insert(26645, "financial");
-        words.insert(26646, "finch");
-        words.insert(26651, "fineness");
-        words.insert(26652, "finer");
-        words.insert(26653, "finicky");
-        words.insert(26654, "finished");
-        words.insert(26655, "finisher");
-        words.insert(26656, "finishing");
-        words.insert(26661, "finite");
-        words.insert(26662, "finless");
-        words.insert(26663, "finlike");
-        words.insert(26664, "fiscally");
-        words.insert(26665, "fit");
-        words.insert(26666, "five");
-        words.insert(31111, "flaccid");
-        words.insert(31112, "flagman");
-        words.insert(31113, "flagpole");
-        words.insert(31114, "flagship");
-        words.insert(31115, "flagstick");
-        words.insert(31116, "flagstone");
-        words.insert(31121, "flail");
-        words.insert(31122, "flakily");
-        words.insert(31123, "flaky");
-        words.insert(31124, "flame");
-        words.insert(31125, "flammable");
-        words.insert(31126, "flanked");
-        words.insert(31131, "flanking");
-        words.insert(31132, "flannels");
-        words.insert(31133, "flap");
-        words.insert(31134, "flaring");
-        words.insert(31135, "flashback");
-        words.insert(31136, "flashbulb");
-        words.insert(31141, "flashcard");
-        words.insert(31142, "flashily");
-        words.insert(31143, "flashing");
-        words.insert(31144, "flashy");
-        words.insert(31145, "flask");
-        words.insert(31146, "flatbed");
-        words.insert(31151, "flatfoot");
-        words.insert(31152, "flatly");
-        words.insert(31153, "flatness");
-        words.insert(31154, "flatten");
-        words.insert(31155, "flattered");
-        words.insert(31156, "flatterer");
-        words.insert(31161, "flattery");
-        words.insert(31162, "flattop");
-        words.insert(31163, "flatware");
-        words.insert(31164, "flatworm");
-        words.insert(31165, "flavored");
-        words.insert(31166, "flavorful");
-        words.insert(31211, "flavoring");
-        words.insert(31212, "flaxseed");
-        words.insert(31213, "fled");
-        words.insert(31214, "fleshed");
-        words.insert(31215, "fleshy");
-        words.insert(31216, "flick");
-        words.insert(31221, "flier");
-        words.insert(31222, "flight");
-        words.insert(31223, "flinch");
-        words.insert(31224, "fling");
-        words.insert(31225, "flint");
-        words.insert(31226, "flip");
-        words.insert(31231, "flirt");
-        words.insert(31232, "float");
-        words.insert(31233, "flock");
-        words.insert(31234, "flogging");
-        words.insert(31235, "flop");
-        words.insert(31236, "floral");
-        words.insert(31241, "florist");
-        words.insert(31242, "floss");
-        words.insert(31243, "flounder");
-        words.insert(31244, "flyable");
-        words.insert(31245, "flyaway");
-        words.insert(31246, "flyer");
-        words.insert(31251, "flying");
-        words.insert(31252, "flyover");
-        words.insert(31253, "flypaper");
-        words.insert(31254, "foam");
-        words.insert(31255, "foe");
-        words.insert(31256, "fog");
-        words.insert(31261, "foil");
-        words.insert(31262, "folic");
-        words.insert(31263, "folk");
-        words.insert(31264, "follicle");
-        words.insert(31265, "follow");
-        words.insert(31266, "fondling");
-        words.insert(31311, "fondly");
-        words.insert(31312, "fondness");
-        words.insert(31313, "fondue");
-        words.insert(31314, "font");
-        words.insert(31315, "food");
-        words.insert(31316, "fool");
-        words.insert(31321, "footage");
-        words.insert(31322, "football");
-        words.insert(31323, "footbath");
-        words.insert(31324, "footboard");
-        words.insert(31325, "footer");
-        words.insert(31326, "footgear");
-        words.insert(31331, "foothill");
-        words.insert(31332, "foothold");
-        words.insert(31333, "footing");
-        words.insert(31334, "footless");
-        words.insert(31335, "footman");
-        words.insert(31336, "footnote");
-        words.insert(31341, "footpad");
-        words.insert(31342, "footpath");
-        words.insert(31343, "footprint");
-        words.insert(31344, "footrest");
-        words.insert(31345, "footsie");
-        words.insert(31346, "footsore");
-        words.insert(31351, "footwear");
-        words.insert(31352, "footwork");
-        words.insert(31353, "fossil");
-        words.insert(31354, "foster");
-        words.insert(31355, "founder");
-        words.insert(31356, "founding");
-        words.insert(31361, "fountain");
-        words.insert(31362, "fox");
-        words.insert(31363, "foyer");
-        words.insert(31364, "fraction");
-        words.insert(31365, "fracture");
-        words.insert(31366, "fragile");
-        words.insert(31411, "fragility");
-        words.insert(31412, "fragment");
-        words.insert(31413, "fragrance");
-        words.insert(31414, "fragrant");
-        words.insert(31415, "frail");
-        words.insert(31416, "frame");
-        words.insert(31421, "framing");
-        words.insert(31422, "frantic");
-        words.insert(31423, "fraternal");
-        words.insert(31424, "frayed");
-        words.insert(31425, "fraying");
-        words.insert(31426, "frays");
-        words.insert(31431, "freckled");
-        words.insert(31432, "freckles");
-        words.insert(31433, "freebase");
-        words.insert(31434, "freebee");
-        words.insert(31435, "freebie");
-        words.insert(31436, "freedom");
-        words.insert(31441, "freefall");
-        words.insert(31442, "freehand");
-        words.insert(31443, "freeing");
-        words.insert(31444, "freeload");
-        words.insert(31445, "freely");
-        words.insert(31446, "freemason");
-        words.insert(31451, "freeness");
-        words.insert(31452, "freestyle");
-        words.insert(31453, "freeware");
-        words.insert(31454, "freeway");
-        words.insert(31455, "freewill");
-        words.insert(31456, "freezable");
-        words.insert(31461, "freezing");
-        words.insert(31462, "freight");
-        words.insert(31463, "french");
-        words.insert(31464, "frenzied");
-        words.insert(31465, "frenzy");
-        words.insert(31466, "frequency");
-        words.insert(31511, "frequent");
-        words.insert(31512, "fresh");
-        words.insert(31513, "fretful");
-        words.insert(31514, "fretted");
-        words.insert(31515, "friction");
-        words.insert(31516, "friday");
-        words.insert(31521, "fridge");
-        words.insert(31522, "fried");
-        words.insert(31523, "friend");
-        words.insert(31524, "frighten");
-        words.insert(31525, "frightful");
-        words.insert(31526, "frigidity");
-        words.insert(31531, "frigidly");
-        words.insert(31532, "frill");
-        words.insert(31533, "fringe");
-        words.insert(31534, "frisbee");
-        words.insert(31535, "frisk");
-        words.insert(31536, "fritter");
-        words.insert(31541, "frivolous");
-        words.insert(31542, "frolic");
-        words.insert(31543, "from");
-        words.insert(31544, "front");
-        words.insert(31545, "frostbite");
-        words.insert(31546, "frosted");
-        words.insert(31551, "frostily");
-        words.insert(31552, "frosting");
-        words.insert(31553, "frostlike");
-        words.insert(31554, "frosty");
-        words.insert(31555, "froth");
-        words.insert(31556, "frown");
-        words.insert(31561, "frozen");
-        words.insert(31562, "fructose");
-        words.insert(31563, "frugality");
-        words.insert(31564, "frugally");
-        words.insert(31565, "fruit");
-        words.insert(31566, "frustrate");
-        words.insert(31611, "frying");
-        words.insert(31612, "gab");
-        words.insert(31613, "gaffe");
-        words.insert(31614, "gag");
-        words.insert(31615, "gainfully");
-        words.insert(31616, "gaining");
-        words.insert(31621, "gains");
-        words.insert(31622, "gala");
-        words.insert(31623, "gallantly");
-        words.insert(31624, "galleria");
-        words.insert(31625, "gallery");
-        words.insert(31626, "galley");
-        words.insert(31631, "gallon");
-        words.insert(31632, "gallows");
-        words.insert(31633, "gallstone");
-        words.insert(31634, "galore");
-        words.insert(31635, "galvanize");
-        words.insert(31636, "gambling");
-        words.insert(31641, "game");
-        words.insert(31642, "gaming");
-        words.insert(31643, "gamma");
-        words.insert(31644, "gander");
-        words.insert(31645, "gangly");
-        words.insert(31646, "gangrene");
-        words.insert(31651, "gangway");
-        words.insert(31652, "gap");
-        words.insert(31653, "garage");
-        words.insert(31654, "garbage");
-        words.insert(31655, "garden");
-        words.insert(31656, "gargle");
-        words.insert(31661, "garland");
-        words.insert(31662, "garlic");
-        words.insert(31663, "garment");
-        words.insert(31664, "garnet");
-        words.insert(31665, "garnish");
-        words.insert(31666, "garter");
-        words.insert(32111, "gas");
-        words.insert(32112, "gatherer");
-        words.insert(32113, "gathering");
-        words.insert(32114, "gating");
-        words.insert(32115, "gauging");
-        words.insert(32116, "gauntlet");
-        words.insert(32121, "gauze");
-        words.insert(32122, "gave");
-        words.insert(32123, "gawk");
-        words.insert(32124, "gazing");
-        words.insert(32125, "gear");
-        words.insert(32126, "gecko");
-        words.insert(32131, "geek");
-        words.insert(32132, "geiger");
-        words.insert(32133, "gem");
-        words.insert(32134, "gender");
-        words.insert(32135, "generic");
-        words.insert(32136, "generous");
-        words.insert(32141, "genetics");
-        words.insert(32142, "genre");
-        words.insert(32143, "gentile");
-        words.insert(32144, "gentleman");
-        words.insert(32145, "gently");
-        words.insert(32146, "gents");
-        words.insert(32151, "geography");
-        words.insert(32152, "geologic");
-        words.insert(32153, "geologist");
-        words.insert(32154, "geology");
-        words.insert(32155, "geometric");
-        words.insert(32156, "geometry");
-        words.insert(32161, "geranium");
-        words.insert(32162, "gerbil");
-        words.insert(32163, "geriatric");
-        words.insert(32164, "germicide");
-        words.insert(32165, "germinate");
-        words.insert(32166, "germless");
-        words.insert(32211, "germproof");
-        words.insert(32212, "gestate");
-        words.insert(32213, "gestation");
-        words.insert(32214, "gesture");
-        words.insert(32215, "getaway");
-        words.insert(32216, "getting");
-        words.insert(32221, "getup");
-        words.insert(32222, "giant");
-        words.insert(32223, "gibberish");
-        words.insert(32224, "giblet");
-        words.insert(32225, "giddily");
-        words.insert(32226, "giddiness");
-        words.insert(32231, "giddy");
-        words.insert(32232, "gift");
-        words.insert(32233, "gigabyte");
-        words.insert(32234, "gigahertz");
-        words.insert(32235, "gigantic");
-        words.insert(32236, "giggle");
-        words.insert(32241, "giggling");
-        words.insert(32242, "giggly");
-        words.insert(32243, "gigolo");
-        words.insert(32244, "gilled");
-        words.insert(32245, "gills");
-        words.insert(32246, "gimmick");
-        words.insert(32251, "girdle");
-        words.insert(32252, "giveaway");
-        words.insert(32253, "given");
-        words.insert(32254, "giver");
-        words.insert(32255, "giving");
-        words.insert(32256, "gizmo");
-        words.insert(32261, "gizzard");
-        words.insert(32262, "glacial");
-        words.insert(32263, "glacier");
-        words.insert(32264, "glade");
-        words.insert(32265, "gladiator");
-        words.insert(32266, "gladly");
-        words.insert(32311, "glamorous");
-        words.insert(32312, "glamour");
-        words.insert(32313, "glance");
-        words.insert(32314, "glancing");
-        words.insert(32315, "glandular");
-        words.insert(32316, "glare");
-        words.insert(32321, "glaring");
-        words.insert(32322, "glass");
-        words.insert(32323, "glaucoma");
-        words.insert(32324, "glazing");
-        words.insert(32325, "gleaming");
-        words.insert(32326, "gleeful");
-        words.insert(32331, "glider");
-        words.insert(32332, "gliding");
-        words.insert(32333, "glimmer");
-        words.insert(32334, "glimpse");
-        words.insert(32335, "glisten");
-        words.insert(32336, "glitch");
-        words.insert(32341, "glitter");
-        words.insert(32342, "glitzy");
-        words.insert(32343, "gloater");
-        words.insert(32344, "gloating");
-        words.insert(32345, "gloomily");
-        words.insert(32346, "gloomy");
-        words.insert(32351, "glorified");
-        words.insert(32352, "glorifier");
-        words.insert(32353, "glorify");
-        words.insert(32354, "glorious");
-        words.insert(32355, "glory");
-        words.insert(32356, "gloss");
-        words.insert(32361, "glove");
-        words.insert(32362, "glowing");
-        words.insert(32363, "glowworm");
-        words.insert(32364, "glucose");
-        words.insert(32365, "glue");
-        words.insert(32366, "gluten");
-        words.insert(32411, "glutinous");
-        words.insert(32412, "glutton");
-        words.insert(32413, "gnarly");
-        words.insert(32414, "gnat");
-        words.insert(32415, "goal");
-        words.insert(32416, "goatskin");
-        words.insert(32421, "goes");
-        words.insert(32422, "goggles");
-        words.insert(32423, "going");
-        words.insert(32424, "goldfish");
-        words.insert(32425, "goldmine");
-        words.insert(32426, "goldsmith");
-        words.insert(32431, "golf");
-        words.insert(32432, "goliath");
-        words.insert(32433, "gonad");
-        words.insert(32434, "gondola");
-        words.insert(32435, "gone");
-        words.insert(32436, "gong");
-        words.insert(32441, "good");
-        words.insert(32442, "gooey");
-        words.insert(32443, "goofball");
-        words.insert(32444, "goofiness");
-        words.insert(32445, "goofy");
-        words.insert(32446, "google");
-        words.insert(32451, "goon");
-        words.insert(32452, "gopher");
-        words.insert(32453, "gore");
-        words.insert(32454, "gorged");
-        words.insert(32455, "gorgeous");
-        words.insert(32456, "gory");
-        words.insert(32461, "gosling");
-        words.insert(32462, "gossip");
-        words.insert(32463, "gothic");
-        words.insert(32464, "gotten");
-        words.insert(32465, "gout");
-        words.insert(32466, "gown");
-        words.insert(32511, "grab");
-        words.insert(32512, "graceful");
-        words.insert(32513, "graceless");
-        words.insert(32514, "gracious");
-        words.insert(32515, "gradation");
-        words.insert(32516, "graded");
-        words.insert(32521, "grader");
-        words.insert(32522, "gradient");
-        words.insert(32523, "grading");
-        words.insert(32524, "gradually");
-        words.insert(32525, "graduate");
-        words.insert(32526, "graffiti");
-        words.insert(32531, "grafted");
-        words.insert(32532, "grafting");
-        words.insert(32533, "grain");
-        words.insert(32534, "granddad");
-        words.insert(32535, "grandkid");
-        words.insert(32536, "grandly");
-        words.insert(32541, "grandma");
-        words.insert(32542, "grandpa");
-        words.insert(32543, "grandson");
-        words.insert(32544, "granite");
-        words.insert(32545, "granny");
-        words.insert(32546, "granola");
-        words.insert(32551, "grant");
-        words.insert(32552, "granular");
-        words.insert(32553, "grape");
-        words.insert(32554, "graph");
-        words.insert(32555, "grapple");
-        words.insert(32556, "grappling");
-        words.insert(32561, "grasp");
-        words.insert(32562, "grass");
-        words.insert(32563, "gratified");
-        words.insert(32564, "gratify");
-        words.insert(32565, "grating");
-        words.insert(32566, "gratitude");
-        words.insert(32611, "gratuity");
-        words.insert(32612, "gravel");
-        words.insert(32613, "graveness");
-        words.insert(32614, "graves");
-        words.insert(32615, "graveyard");
-        words.insert(32616, "gravitate");
-        words.insert(32621, "gravity");
-        words.insert(32622, "gravy");
-        words.insert(32623, "gray");
-        words.insert(32624, "grazing");
-        words.insert(32625, "greasily");
-        words.insert(32626, "greedily");
-        words.insert(32631, "greedless");
-        words.insert(32632, "greedy");
-        words.insert(32633, "green");
-        words.insert(32634, "greeter");
-        words.insert(32635, "greeting");
-        words.insert(32636, "grew");
-        words.insert(32641, "greyhound");
-        words.insert(32642, "grid");
-        words.insert(32643, "grief");
-        words.insert(32644, "grievance");
-        words.insert(32645, "grieving");
-        words.insert(32646, "grievous");
-        words.insert(32651, "grill");
-        words.insert(32652, "grimace");
-        words.insert(32653, "grimacing");
-        words.insert(32654, "grime");
-        words.insert(32655, "griminess");
-        words.insert(32656, "grimy");
-        words.insert(32661, "grinch");
-        words.insert(32662, "grinning");
-        words.insert(32663, "grip");
-        words.insert(32664, "gristle");
-        words.insert(32665, "grit");
-        words.insert(32666, "groggily");
-        words.insert(33111, "groggy");
-        words.insert(33112, "groin");
-        words.insert(33113, "groom");
-        words.insert(33114, "groove");
-        words.insert(33115, "grooving");
-        words.insert(33116, "groovy");
-        words.insert(33121, "grope");
-        words.insert(33122, "ground");
-        words.insert(33123, "grouped");
-        words.insert(33124, "grout");
-        words.insert(33125, "grove");
-        words.insert(33126, "grower");
-        words.insert(33131, "growing");
-        words.insert(33132, "growl");
-        words.insert(33133, "grub");
-        words.insert(33134, "grudge");
-        words.insert(33135, "grudging");
-        words.insert(33136, "grueling");
-        words.insert(33141, "gruffly");
-        words.insert(33142, "grumble");
-        words.insert(33143, "grumbling");
-        words.insert(33144, "grumbly");
-        words.insert(33145, "grumpily");
-        words.insert(33146, "grunge");
-        words.insert(33151, "grunt");
-        words.insert(33152, "guacamole");
-        words.insert(33153, "guidable");
-        words.insert(33154, "guidance");
-        words.insert(33155, "guide");
-        words.insert(33156, "guiding");
-        words.insert(33161, "guileless");
-        words.insert(33162, "guise");
-        words.insert(33163, "gulf");
-        words.insert(33164, "gullible");
-        words.insert(33165, "gully");
-        words.insert(33166, "gulp");
-        words.insert(33211, "gumball");
-        words.insert(33212, "gumdrop");
-        words.insert(33213, "gumminess");
-        words.insert(33214, "gumming");
-        words.insert(33215, "gummy");
-        words.insert(33216, "gurgle");
-        words.insert(33221, "gurgling");
-        words.insert(33222, "guru");
-        words.insert(33223, "gush");
-        words.insert(33224, "gusto");
-        words.insert(33225, "gusty");
-        words.insert(33226, "gutless");
-        words.insert(33231, "guts");
-        words.insert(33232, "gutter");
-        words.insert(33233, "guy");
-        words.insert(33234, "guzzler");
-        words.insert(33235, "gyration");
-        words.insert(33236, "habitable");
-        words.insert(33241, "habitant");
-        words.insert(33242, "habitat");
-        words.insert(33243, "habitual");
-        words.insert(33244, "hacked");
-        words.insert(33245, "hacker");
-        words.insert(33246, "hacking");
-        words.insert(33251, "hacksaw");
-        words.insert(33252, "had");
-        words.insert(33253, "haggler");
-        words.insert(33254, "haiku");
-        words.insert(33255, "half");
-        words.insert(33256, "halogen");
-        words.insert(33261, "halt");
-        words.insert(33262, "halved");
-        words.insert(33263, "halves");
-        words.insert(33264, "hamburger");
-        words.insert(33265, "hamlet");
-        words.insert(33266, "hammock");
-        words.insert(33311, "hamper");
-        words.insert(33312, "hamster");
-        words.insert(33313, "hamstring");
-        words.insert(33314, "handbag");
-        words.insert(33315, "handball");
-        words.insert(33316, "handbook");
-        words.insert(33321, "handbrake");
-        words.insert(33322, "handcart");
-        words.insert(33323, "handclap");
-        words.insert(33324, "handclasp");
-        words.insert(33325, "handcraft");
-        words.insert(33326, "handcuff");
-        words.insert(33331, "handed");
-        words.insert(33332, "handful");
-        words.insert(33333, "handgrip");
-        words.insert(33334, "handgun");
-        words.insert(33335, "handheld");
-        words.insert(33336, "handiness");
-        words.insert(33341, "handiwork");
-        words.insert(33342, "handlebar");
-        words.insert(33343, "handled");
-        words.insert(33344, "handler");
-        words.insert(33345, "handling");
-        words.insert(33346, "handmade");
-        words.insert(33351, "handoff");
-        words.insert(33352, "handpick");
-        words.insert(33353, "handprint");
-        words.insert(33354, "handrail");
-        words.insert(33355, "handsaw");
-        words.insert(33356, "handset");
-        words.insert(33361, "handsfree");
-        words.insert(33362, "handshake");
-        words.insert(33363, "handstand");
-        words.insert(33364, "handwash");
-        words.insert(33365, "handwork");
-        words.insert(33366, "handwoven");
-        words.insert(33411, "handwrite");
-        words.insert(33412, "handyman");
-        words.insert(33413, "hangnail");
-        words.insert(33414, "hangout");
-        words.insert(33415, "hangover");
-        words.insert(33416, "hangup");
-        words.insert(33421, "hankering");
-        words.insert(33422, "hankie");
-        words.insert(33423, "hanky");
-        words.insert(33424, "haphazard");
-        words.insert(33425, "happening");
-        words.insert(33426, "happier");
-        words.insert(33431, "happiest");
-        words.insert(33432, "happily");
-        words.insert(33433, "happiness");
-        words.insert(33434, "happy");
-        words.insert(33435, "harbor");
-        words.insert(33436, "hardcopy");
-        words.insert(33441, "hardcore");
-        words.insert(33442, "hardcover");
-        words.insert(33443, "harddisk");
-        words.insert(33444, "hardened");
-        words.insert(33445, "hardener");
-        words.insert(33446, "hardening");
-        words.insert(33451, "hardhat");
-        words.insert(33452, "hardhead");
-        words.insert(33453, "hardiness");
-        words.insert(33454, "hardly");
-        words.insert(33455, "hardness");
-        words.insert(33456, "hardship");
-        words.insert(33461, "hardware");
-        words.insert(33462, "hardwired");
-        words.insert(33463, "hardwood");
-        words.insert(33464, "hardy");
-        words.insert(33465, "harmful");
-        words.insert(33466, "harmless");
-        words.insert(33511, "harmonica");
-        words.insert(33512, "harmonics");
-        words.insert(33513, "harmonize");
-        words.insert(33514, "harmony");
-        words.insert(33515, "harness");
-        words.insert(33516, "harpist");
-        words.insert(33521, "harsh");
-        words.insert(33522, "harvest");
-        words.insert(33523, "hash");
-        words.insert(33524, "hassle");
-        words.insert(33525, "haste");
-        words.insert(33526, "hastily");
-        words.insert(33531, "hastiness");
-        words.insert(33532, "hasty");
-        words.insert(33533, "hatbox");
-        words.insert(33534, "hatchback");
-        words.insert(33535, "hatchery");
-        words.insert(33536, "hatchet");
-        words.insert(33541, "hatching");
-        words.insert(33542, "hatchling");
-        words.insert(33543, "hate");
-        words.insert(33544, "hatless");
-        words.insert(33545, "hatred");
-        words.insert(33546, "haunt");
-        words.insert(33551, "haven");
-        words.insert(33552, "hazard");
-        words.insert(33553, "hazelnut");
-        words.insert(33554, "hazily");
-        words.insert(33555, "haziness");
-        words.insert(33556, "hazing");
-        words.insert(33561, "hazy");
-        words.insert(33562, "headache");
-        words.insert(33563, "headband");
-        words.insert(33564, "headboard");
-        words.insert(33565, "headcount");
-        words.insert(33566, "headdress");
-        words.insert(33611, "headed");
-        words.insert(33612, "header");
-        words.insert(33613, "headfirst");
-        words.insert(33614, "headgear");
-        words.insert(33615, "heading");
-        words.insert(33616, "headlamp");
-        words.insert(33621, "headless");
-        words.insert(33622, "headlock");
-        words.insert(33623, "headphone");
-        words.insert(33624, "headpiece");
-        words.insert(33625, "headrest");
-        words.insert(33626, "headroom");
-        words.insert(33631, "headscarf");
-        words.insert(33632, "headset");
-        words.insert(33633, "headsman");
-        words.insert(33634, "headstand");
-        words.insert(33635, "headstone");
-        words.insert(33636, "headway");
-        words.insert(33641, "headwear");
-        words.insert(33642, "heap");
-        words.insert(33643, "heat");
-        words.insert(33644, "heave");
-        words.insert(33645, "heavily");
-        words.insert(33646, "heaviness");
-        words.insert(33651, "heaving");
-        words.insert(33652, "hedge");
-        words.insert(33653, "hedging");
-        words.insert(33654, "heftiness");
-        words.insert(33655, "hefty");
-        words.insert(33656, "helium");
-        words.insert(33661, "helmet");
-        words.insert(33662, "helper");
-        words.insert(33663, "helpful");
-        words.insert(33664, "helping");
-        words.insert(33665, "helpless");
-        words.insert(33666, "helpline");
-        words.insert(34111, "hemlock");
-        words.insert(34112, "hemstitch");
-        words.insert(34113, "hence");
-        words.insert(34114, "henchman");
-        words.insert(34115, "henna");
-        words.insert(34116, "herald");
-        words.insert(34121, "herbal");
-        words.insert(34122, "herbicide");
-        words.insert(34123, "herbs");
-        words.insert(34124, "heritage");
-        words.insert(34125, "hermit");
-        words.insert(34126, "heroics");
-        words.insert(34131, "heroism");
-        words.insert(34132, "herring");
-        words.insert(34133, "herself");
-        words.insert(34134, "hertz");
-        words.insert(34135, "hesitancy");
-        words.insert(34136, "hesitant");
-        words.insert(34141, "hesitate");
-        words.insert(34142, "hexagon");
-        words.insert(34143, "hexagram");
-        words.insert(34144, "hubcap");
-        words.insert(34145, "huddle");
-        words.insert(34146, "huddling");
-        words.insert(34151, "huff");
-        words.insert(34152, "hug");
-        words.insert(34153, "hula");
-        words.insert(34154, "hulk");
-        words.insert(34155, "hull");
-        words.insert(34156, "human");
-        words.insert(34161, "humble");
-        words.insert(34162, "humbling");
-        words.insert(34163, "humbly");
-        words.insert(34164, "humid");
-        words.insert(34165, "humiliate");
-        words.insert(34166, "humility");
-        words.insert(34211, "humming");
-        words.insert(34212, "hummus");
-        words.insert(34213, "humongous");
-        words.insert(34214, "humorist");
-        words.insert(34215, "humorless");
-        words.insert(34216, "humorous");
-        words.insert(34221, "humpback");
-        words.insert(34222, "humped");
-        words.insert(34223, "humvee");
-        words.insert(34224, "hunchback");
-        words.insert(34225, "hundredth");
-        words.insert(34226, "hunger");
-        words.insert(34231, "hungrily");
-        words.insert(34232, "hungry");
-        words.insert(34233, "hunk");
-        words.insert(34234, "hunter");
-        words.insert(34235, "hunting");
-        words.insert(34236, "huntress");
-        words.insert(34241, "huntsman");
-        words.insert(34242, "hurdle");
-        words.insert(34243, "hurled");
-        words.insert(34244, "hurler");
-        words.insert(34245, "hurling");
-        words.insert(34246, "hurray");
-        words.insert(34251, "hurricane");
-        words.insert(34252, "hurried");
-        words.insert(34253, "hurry");
-        words.insert(34254, "hurt");
-        words.insert(34255, "husband");
-        words.insert(34256, "hush");
-        words.insert(34261, "husked");
-        words.insert(34262, "huskiness");
-        words.insert(34263, "hut");
-        words.insert(34264, "hybrid");
-        words.insert(34265, "hydrant");
-        words.insert(34266, "hydrated");
-        words.insert(34311, "hydration");
-        words.insert(34312, "hydrogen");
-        words.insert(34313, "hydroxide");
-        words.insert(34314, "hyperlink");
-        words.insert(34315, "hypertext");
-        words.insert(34316, "hyphen");
-        words.insert(34321, "hypnoses");
-        words.insert(34322, "hypnosis");
-        words.insert(34323, "hypnotic");
-        words.insert(34324, "hypnotism");
-        words.insert(34325, "hypnotist");
-        words.insert(34326, "hypnotize");
-        words.insert(34331, "hypocrisy");
-        words.insert(34332, "hypocrite");
-        words.insert(34333, "ibuprofen");
-        words.insert(34334, "ice");
-        words.insert(34335, "iciness");
-        words.insert(34336, "icing");
-        words.insert(34341, "icky");
-        words.insert(34342, "icon");
-        words.insert(34343, "icy");
-        words.insert(34344, "idealism");
-        words.insert(34345, "idealist");
-        words.insert(34346, "idealize");
-        words.insert(34351, "ideally");
-        words.insert(34352, "idealness");
-        words.insert(34353, "identical");
-        words.insert(34354, "identify");
-        words.insert(34355, "identity");
-        words.insert(34356, "ideology");
-        words.insert(34361, "idiocy");
-        words.insert(34362, "idiom");
-        words.insert(34363, "idly");
-        words.insert(34364, "igloo");
-        words.insert(34365, "ignition");
-        words.insert(34366, "ignore");
-        words.insert(34411, "iguana");
-        words.insert(34412, "illicitly");
-        words.insert(34413, "illusion");
-        words.insert(34414, "illusive");
-        words.insert(34415, "image");
-        words.insert(34416, "imaginary");
-        words.insert(34421, "imagines");
-        words.insert(34422, "imaging");
-        words.insert(34423, "imbecile");
-        words.insert(34424, "imitate");
-        words.insert(34425, "imitation");
-        words.insert(34426, "immature");
-        words.insert(34431, "immerse");
-        words.insert(34432, "immersion");
-        words.insert(34433, "imminent");
-        words.insert(34434, "immobile");
-        words.insert(34435, "immodest");
-        words.insert(34436, "immorally");
-        words.insert(34441, "immortal");
-        words.insert(34442, "immovable");
-        words.insert(34443, "immovably");
-        words.insert(34444, "immunity");
-        words.insert(34445, "immunize");
-        words.insert(34446, "impaired");
-        words.insert(34451, "impale");
-        words.insert(34452, "impart");
-        words.insert(34453, "impatient");
-        words.insert(34454, "impeach");
-        words.insert(34455, "impeding");
-        words.insert(34456, "impending");
-        words.insert(34461, "imperfect");
-        words.insert(34462, "imperial");
-        words.insert(34463, "impish");
-        words.insert(34464, "implant");
-        words.insert(34465, "implement");
-        words.insert(34466, "implicate");
-        words.insert(34511, "implicit");
-        words.insert(34512, "implode");
-        words.insert(34513, "implosion");
-        words.insert(34514, "implosive");
-        words.insert(34515, "imply");
-        words.insert(34516, "impolite");
-        words.insert(34521, "important");
-        words.insert(34522, "importer");
-        words.insert(34523, "impose");
-        words.insert(34524, "imposing");
-        words.insert(34525, "impotence");
-        words.insert(34526, "impotency");
-        words.insert(34531, "impotent");
-        words.insert(34532, "impound");
-        words.insert(34533, "imprecise");
-        words.insert(34534, "imprint");
-        words.insert(34535, "imprison");
-        words.insert(34536, "impromptu");
-        words.insert(34541, "improper");
-        words.insert(34542, "improve");
-        words.insert(34543, "improving");
-        words.insert(34544, "improvise");
-        words.insert(34545, "imprudent");
-        words.insert(34546, "impulse");
-        words.insert(34551, "impulsive");
-        words.insert(34552, "impure");
-        words.insert(34553, "impurity");
-        words.insert(34554, "iodine");
-        words.insert(34555, "iodize");
-        words.insert(34556, "ion");
-        words.insert(34561, "ipad");
-        words.insert(34562, "iphone");
-        words.insert(34563, "ipod");
-        words.insert(34564, "irate");
-        words.insert(34565, "irk");
-        words.insert(34566, "iron");
-        words.insert(34611, "irregular");
-        words.insert(34612, "irrigate");
-        words.insert(34613, "irritable");
-        words.insert(34614, "irritably");
-        words.insert(34615, "irritant");
-        words.insert(34616, "irritate");
-        words.insert(34621, "islamic");
-        words.insert(34622, "islamist");
-        words.insert(34623, "isolated");
-        words.insert(34624, "isolating");
-        words.insert(34625, "isolation");
-        words.insert(34626, "isotope");
-        words.insert(34631, "issue");
-        words.insert(34632, "issuing");
-        words.insert(34633, "italicize");
-        words.insert(34634, "italics");
-        words.insert(34635, "item");
-        words.insert(34636, "itinerary");
-        words.insert(34641, "itunes");
-        words.insert(34642, "ivory");
-        words.insert(34643, "ivy");
-        words.insert(34644, "jab");
-        words.insert(34645, "jackal");
-        words.insert(34646, "jacket");
-        words.insert(34651, "jackknife");
-        words.insert(34652, "jackpot");
-        words.insert(34653, "jailbird");
-        words.insert(34654, "jailbreak");
-        words.insert(34655, "jailer");
-        words.insert(34656, "jailhouse");
-        words.insert(34661, "jalapeno");
-        words.insert(34662, "jam");
-        words.insert(34663, "janitor");
-        words.insert(34664, "january");
-        words.insert(34665, "jargon");
-        words.insert(34666, "jarring");
-        words.insert(35111, "jasmine");
-        words.insert(35112, "jaundice");
-        words.insert(35113, "jaunt");
-        words.insert(35114, "java");
-        words.insert(35115, "jawed");
-        words.insert(35116, "jawless");
-        words.insert(35121, "jawline");
-        words.insert(35122, "jaws");
-        words.insert(35123, "jaybird");
-        words.insert(35124, "jaywalker");
-        words.insert(35125, "jazz");
-        words.insert(35126, "jeep");
-        words.insert(35131, "jeeringly");
-        words.insert(35132, "jellied");
-        words.insert(35133, "jelly");
-        words.insert(35134, "jersey");
-        words.insert(35135, "jester");
-        words.insert(35136, "jet");
-        words.insert(35141, "jiffy");
-        words.insert(35142, "jigsaw");
-        words.insert(35143, "jimmy");
-        words.insert(35144, "jingle");
-        words.insert(35145, "jingling");
-        words.insert(35146, "jinx");
-        words.insert(35151, "jitters");
-        words.insert(35152, "jittery");
-        words.insert(35153, "job");
-        words.insert(35154, "jockey");
-        words.insert(35155, "jockstrap");
-        words.insert(35156, "jogger");
-        words.insert(35161, "jogging");
-        words.insert(35162, "john");
-        words.insert(35163, "joining");
-        words.insert(35164, "jokester");
-        words.insert(35165, "jokingly");
-        words.insert(35166, "jolliness");
-        words.insert(35211, "jolly");
-        words.insert(35212, "jolt");
-        words.insert(35213, "jot");
-        words.insert(35214, "jovial");
-        words.insert(35215, "joyfully");
-        words.insert(35216, "joylessly");
-        words.insert(35221, "joyous");
-        words.insert(35222, "joyride");
-        words.insert(35223, "joystick");
-        words.insert(35224, "jubilance");
-        words.insert(35225, "jubilant");
-        words.insert(35226, "judge");
-        words.insert(35231, "judgingly");
-        words.insert(35232, "judicial");
-        words.insert(35233, "judiciary");
-        words.insert(35234, "judo");
-        words.insert(35235, "juggle");
-        words.insert(35236, "juggling");
-        words.insert(35241, "jugular");
-        words.insert(35242, "juice");
-        words.insert(35243, "juiciness");
-        words.insert(35244, "juicy");
-        words.insert(35245, "jujitsu");
-        words.insert(35246, "jukebox");
-        words.insert(35251, "july");
-        words.insert(35252, "jumble");
-        words.insert(35253, "jumbo");
-        words.insert(35254, "jump");
-        words.insert(35255, "junction");
-        words.insert(35256, "juncture");
-        words.insert(35261, "june");
-        words.insert(35262, "junior");
-        words.insert(35263, "juniper");
-        words.insert(35264, "junkie");
-        words.insert(35265, "junkman");
-        words.insert(35266, "junkyard");
-        words.insert(35311, "jurist");
-        words.insert(35312, "juror");
-        words.insert(35313, "jury");
-        words.insert(35314, "justice");
-        words.insert(35315, "justifier");
-        words.insert(35316, "justify");
-        words.insert(35321, "justly");
-        words.insert(35322, "justness");
-        words.insert(35323, "juvenile");
-        words.insert(35324, "kabob");
-        words.insert(35325, "kangaroo");
-        words.insert(35326, "karaoke");
-        words.insert(35331, "karate");
-        words.insert(35332, "karma");
-        words.insert(35333, "kebab");
-        words.insert(35334, "keenly");
-        words.insert(35335, "keenness");
-        words.insert(35336, "keep");
-        words.insert(35341, "keg");
-        words.insert(35342, "kelp");
-        words.insert(35343, "kennel");
-        words.insert(35344, "kept");
-        words.insert(35345, "kerchief");
-        words.insert(35346, "kerosene");
-        words.insert(35351, "kettle");
-        words.insert(35352, "kick");
-        words.insert(35353, "kiln");
-        words.insert(35354, "kilobyte");
-        words.insert(35355, "kilogram");
-        words.insert(35356, "kilometer");
-        words.insert(35361, "kilowatt");
-        words.insert(35362, "kilt");
-        words.insert(35363, "kimono");
-        words.insert(35364, "kindle");
-        words.insert(35365, "kindling");
-        words.insert(35366, "kindly");
-        words.insert(35411, "kindness");
-        words.insert(35412, "kindred");
-        words.insert(35413, "kinetic");
-        words.insert(35414, "kinfolk");
-        words.insert(35415, "king");
-        words.insert(35416, "kinship");
-        words.insert(35421, "kinsman");
-        words.insert(35422, "kinswoman");
-        words.insert(35423, "kissable");
-        words.insert(35424, "kisser");
-        words.insert(35425, "kissing");
-        words.insert(35426, "kitchen");
-        words.insert(35431, "kite");
-        words.insert(35432, "kitten");
-        words.insert(35433, "kitty");
-        words.insert(35434, "kiwi");
-        words.insert(35435, "kleenex");
-        words.insert(35436, "knapsack");
-        words.insert(35441, "knee");
-        words.insert(35442, "knelt");
-        words.insert(35443, "knickers");
-        words.insert(35444, "knoll");
-        words.insert(35445, "koala");
-        words.insert(35446, "kooky");
-        words.insert(35451, "kosher");
-        words.insert(35452, "krypton");
-        words.insert(35453, "kudos");
-        words.insert(35454, "kung");
-        words.insert(35455, "labored");
-        words.insert(35456, "laborer");
-        words.insert(35461, "laboring");
-        words.insert(35462, "laborious");
-        words.insert(35463, "labrador");
-        words.insert(35464, "ladder");
-        words.insert(35465, "ladies");
-        words.insert(35466, "ladle");
-        words.insert(35511, "ladybug");
-        words.insert(35512, "ladylike");
-        words.insert(35513, "lagged");
-        words.insert(35514, "lagging");
-        words.insert(35515, "lagoon");
-        words.insert(35516, "lair");
-        words.insert(35521, "lake");
-        words.insert(35522, "lance");
-        words.insert(35523, "landed");
-        words.insert(35524, "landfall");
-        words.insert(35525, "landfill");
-        words.insert(35526, "landing");
-        words.insert(35531, "landlady");
-        words.insert(35532, "landless");
-        words.insert(35533, "landline");
-        words.insert(35534, "landlord");
-        words.insert(35535, "landmark");
-        words.insert(35536, "landmass");
-        words.insert(35541, "landmine");
-        words.insert(35542, "landowner");
-        words.insert(35543, "landscape");
-        words.insert(35544, "landside");
-        words.insert(35545, "landslide");
-        words.insert(35546, "language");
-        words.insert(35551, "lankiness");
-        words.insert(35552, "lanky");
-        words.insert(35553, "lantern");
-        words.insert(35554, "lapdog");
-        words.insert(35555, "lapel");
-        words.insert(35556, "lapped");
-        words.insert(35561, "lapping");
-        words.insert(35562, "laptop");
-        words.insert(35563, "lard");
-        words.insert(35564, "large");
-        words.insert(35565, "lark");
-        words.insert(35566, "lash");
-        words.insert(35611, "lasso");
-        words.insert(35612, "last");
-        words.insert(35613, "latch");
-        words.insert(35614, "late");
-        words.insert(35615, "lather");
-        words.insert(35616, "latitude");
-        words.insert(35621, "latrine");
-        words.insert(35622, "latter");
-        words.insert(35623, "latticed");
-        words.insert(35624, "launch");
-        words.insert(35625, "launder");
-        words.insert(35626, "laundry");
-        words.insert(35631, "laurel");
-        words.insert(35632, "lavender");
-        words.insert(35633, "lavish");
-        words.insert(35634, "laxative");
-        words.insert(35635, "lazily");
-        words.insert(35636, "laziness");
-        words.insert(35641, "lazy");
-        words.insert(35642, "lecturer");
-        words.insert(35643, "left");
-        words.insert(35644, "legacy");
-        words.insert(35645, "legal");
-        words.insert(35646, "legend");
-        words.insert(35651, "legged");
-        words.insert(35652, "leggings");
-        words.insert(35653, "legible");
-        words.insert(35654, "legibly");
-        words.insert(35655, "legislate");
-        words.insert(35656, "lego");
-        words.insert(35661, "legroom");
-        words.insert(35662, "legume");
-        words.insert(35663, "legwarmer");
-        words.insert(35664, "legwork");
-        words.insert(35665, "lemon");
-        words.insert(35666, "lend");
-        words.insert(36111, "length");
-        words.insert(36112, "lens");
-        words.insert(36113, "lent");
-        words.insert(36114, "leotard");
-        words.insert(36115, "lesser");
-        words.insert(36116, "letdown");
-        words.insert(36121, "lethargic");
-        words.insert(36122, "lethargy");
-        words.insert(36123, "letter");
-        words.insert(36124, "lettuce");
-        words.insert(36125, "level");
-        words.insert(36126, "leverage");
-        words.insert(36131, "levers");
-        words.insert(36132, "levitate");
-        words.insert(36133, "levitator");
-        words.insert(36134, "liability");
-        words.insert(36135, "liable");
-        words.insert(36136, "liberty");
-        words.insert(36141, "librarian");
-        words.insert(36142, "library");
-        words.insert(36143, "licking");
-        words.insert(36144, "licorice");
-        words.insert(36145, "lid");
-        words.insert(36146, "life");
-        words.insert(36151, "lifter");
-        words.insert(36152, "lifting");
-        words.insert(36153, "liftoff");
-        words.insert(36154, "ligament");
-        words.insert(36155, "likely");
-        words.insert(36156, "likeness");
-        words.insert(36161, "likewise");
-        words.insert(36162, "liking");
-        words.insert(36163, "lilac");
-        words.insert(36164, "lilly");
-        words.insert(36165, "lily");
-        words.insert(36166, "limb");
-        words.insert(36211, "limeade");
-        words.insert(36212, "limelight");
-        words.insert(36213, "limes");
-        words.insert(36214, "limit");
-        words.insert(36215, "limping");
-        words.insert(36216, "limpness");
-        words.insert(36221, "line");
-        words.insert(36222, "lingo");
-        words.insert(36223, "linguini");
-        words.insert(36224, "linguist");
-        words.insert(36225, "lining");
-        words.insert(36226, "linked");
-        words.insert(36231, "linoleum");
-        words.insert(36232, "linseed");
-        words.insert(36233, "lint");
-        words.insert(36234, "lion");
-        words.insert(36235, "lip");
-        words.insert(36236, "liquefy");
-        words.insert(36241, "liqueur");
-        words.insert(36242, "liquid");
-        words.insert(36243, "lisp");
-        words.insert(36244, "list");
-        words.insert(36245, "litigate");
-        words.insert(36246, "litigator");
-        words.insert(36251, "litmus");
-        words.insert(36252, "litter");
-        words.insert(36253, "little");
-        words.insert(36254, "livable");
-        words.insert(36255, "lived");
-        words.insert(36256, "lively");
-        words.insert(36261, "liver");
-        words.insert(36262, "livestock");
-        words.insert(36263, "lividly");
-        words.insert(36264, "living");
-        words.insert(36265, "lizard");
-        words.insert(36266, "lubricant");
-        words.insert(36311, "lubricate");
-        words.insert(36312, "lucid");
-        words.insert(36313, "luckily");
-        words.insert(36314, "luckiness");
-        words.insert(36315, "luckless");
-        words.insert(36316, "lucrative");
-        words.insert(36321, "ludicrous");
-        words.insert(36322, "lugged");
-        words.insert(36323, "lukewarm");
-        words.insert(36324, "lullaby");
-        words.insert(36325, "lumber");
-        words.insert(36326, "luminance");
-        words.insert(36331, "luminous");
-        words.insert(36332, "lumpiness");
-        words.insert(36333, "lumping");
-        words.insert(36334, "lumpish");
-        words.insert(36335, "lunacy");
-        words.insert(36336, "lunar");
-        words.insert(36341, "lunchbox");
-        words.insert(36342, "luncheon");
-        words.insert(36343, "lunchroom");
-        words.insert(36344, "lunchtime");
-        words.insert(36345, "lung");
-        words.insert(36346, "lurch");
-        words.insert(36351, "lure");
-        words.insert(36352, "luridness");
-        words.insert(36353, "lurk");
-        words.insert(36354, "lushly");
-        words.insert(36355, "lushness");
-        words.insert(36356, "luster");
-        words.insert(36361, "lustfully");
-        words.insert(36362, "lustily");
-        words.insert(36363, "lustiness");
-        words.insert(36364, "lustrous");
-        words.insert(36365, "lusty");
-        words.insert(36366, "luxurious");
-        words.insert(36411, "luxury");
-        words.insert(36412, "lying");
-        words.insert(36413, "lyrically");
-        words.insert(36414, "lyricism");
-        words.insert(36415, "lyricist");
-        words.insert(36416, "lyrics");
-        words.insert(36421, "macarena");
-        words.insert(36422, "macaroni");
-        words.insert(36423, "macaw");
-        words.insert(36424, "mace");
-        words.insert(36425, "machine");
-        words.insert(36426, "machinist");
-        words.insert(36431, "magazine");
-        words.insert(36432, "magenta");
-        words.insert(36433, "maggot");
-        words.insert(36434, "magical");
-        words.insert(36435, "magician");
-        words.insert(36436, "magma");
-        words.insert(36441, "magnesium");
-        words.insert(36442, "magnetic");
-        words.insert(36443, "magnetism");
-        words.insert(36444, "magnetize");
-        words.insert(36445, "magnifier");
-        words.insert(36446, "magnify");
-        words.insert(36451, "magnitude");
-        words.insert(36452, "magnolia");
-        words.insert(36453, "mahogany");
-        words.insert(36454, "maimed");
-        words.insert(36455, "majestic");
-        words.insert(36456, "majesty");
-        words.insert(36461, "majorette");
-        words.insert(36462, "majority");
-        words.insert(36463, "makeover");
-        words.insert(36464, "maker");
-        words.insert(36465, "makeshift");
-        words.insert(36466, "making");
-        words.insert(36511, "malformed");
-        words.insert(36512, "malt");
-        words.insert(36513, "mama");
-        words.insert(36514, "mammal");
-        words.insert(36515, "mammary");
-        words.insert(36516, "mammogram");
-        words.insert(36521, "manager");
-        words.insert(36522, "managing");
-        words.insert(36523, "manatee");
-        words.insert(36524, "mandarin");
-        words.insert(36525, "mandate");
-        words.insert(36526, "mandatory");
-        words.insert(36531, "mandolin");
-        words.insert(36532, "manger");
-        words.insert(36533, "mangle");
-        words.insert(36534, "mango");
-        words.insert(36535, "mangy");
-        words.insert(36536, "manhandle");
-        words.insert(36541, "manhole");
-        words.insert(36542, "manhood");
-        words.insert(36543, "manhunt");
-        words.insert(36544, "manicotti");
-        words.insert(36545, "manicure");
-        words.insert(36546, "manifesto");
-        words.insert(36551, "manila");
-        words.insert(36552, "mankind");
-        words.insert(36553, "manlike");
-        words.insert(36554, "manliness");
-        words.insert(36555, "manly");
-        words.insert(36556, "manmade");
-        words.insert(36561, "manned");
-        words.insert(36562, "mannish");
-        words.insert(36563, "manor");
-        words.insert(36564, "manpower");
-        words.insert(36565, "mantis");
-        words.insert(36566, "mantra");
-        words.insert(36611, "manual");
-        words.insert(36612, "many");
-        words.insert(36613, "map");
-        words.insert(36614, "marathon");
-        words.insert(36615, "marauding");
-        words.insert(36616, "marbled");
-        words.insert(36621, "marbles");
-        words.insert(36622, "marbling");
-        words.insert(36623, "march");
-        words.insert(36624, "mardi");
-        words.insert(36625, "margarine");
-        words.insert(36626, "margarita");
-        words.insert(36631, "margin");
-        words.insert(36632, "marigold");
-        words.insert(36633, "marina");
-        words.insert(36634, "marine");
-        words.insert(36635, "marital");
-        words.insert(36636, "maritime");
-        words.insert(36641, "marlin");
-        words.insert(36642, "marmalade");
-        words.insert(36643, "maroon");
-        words.insert(36644, "married");
-        words.insert(36645, "marrow");
-        words.insert(36646, "marry");
-        words.insert(36651, "marshland");
-        words.insert(36652, "marshy");
-        words.insert(36653, "marsupial");
-        words.insert(36654, "marvelous");
-        words.insert(36655, "marxism");
-        words.insert(36656, "mascot");
-        words.insert(36661, "masculine");
-        words.insert(36662, "mashed");
-        words.insert(36663, "mashing");
-        words.insert(36664, "massager");
-        words.insert(36665, "masses");
-        words.insert(36666, "massive");
-        words.insert(41111, "mastiff");
-        words.insert(41112, "matador");
-        words.insert(41113, "matchbook");
-        words.insert(41114, "matchbox");
-        words.insert(41115, "matcher");
-        words.insert(41116, "matching");
-        words.insert(41121, "matchless");
-        words.insert(41122, "material");
-        words.insert(41123, "maternal");
-        words.insert(41124, "maternity");
-        words.insert(41125, "math");
-        words.insert(41126, "mating");
-        words.insert(41131, "matriarch");
-        words.insert(41132, "matrimony");
-        words.insert(41133, "matrix");
-        words.insert(41134, "matron");
-        words.insert(41135, "matted");
-        words.insert(41136, "matter");
-        words.insert(41141, "maturely");
-        words.insert(41142, "maturing");
-        words.insert(41143, "maturity");
-        words.insert(41144, "mauve");
-        words.insert(41145, "maverick");
-        words.insert(41146, "maximize");
-        words.insert(41151, "maximum");
-        words.insert(41152, "maybe");
-        words.insert(41153, "mayday");
-        words.insert(41154, "mayflower");
-        words.insert(41155, "moaner");
-        words.insert(41156, "moaning");
-        words.insert(41161, "mobile");
-        words.insert(41162, "mobility");
-        words.insert(41163, "mobilize");
-        words.insert(41164, "mobster");
-        words.insert(41165, "mocha");
-        words.insert(41166, "mocker");
-        words.insert(41211, "mockup");
-        words.insert(41212, "modified");
-        words.insert(41213, "modify");
-        words.insert(41214, "modular");
-        words.insert(41215, "modulator");
-        words.insert(41216, "module");
-        words.insert(41221, "moisten");
-        words.insert(41222, "moistness");
-        words.insert(41223, "moisture");
-        words.insert(41224, "molar");
-        words.insert(41225, "molasses");
-        words.insert(41226, "mold");
-        words.insert(41231, "molecular");
-        words.insert(41232, "molecule");
-        words.insert(41233, "molehill");
-        words.insert(41234, "mollusk");
-        words.insert(41235, "mom");
-        words.insert(41236, "monastery");
-        words.insert(41241, "monday");
-        words.insert(41242, "monetary");
-        words.insert(41243, "monetize");
-        words.insert(41244, "moneybags");
-        words.insert(41245, "moneyless");
-        words.insert(41246, "moneywise");
-        words.insert(41251, "mongoose");
-        words.insert(41252, "mongrel");
-        words.insert(41253, "monitor");
-        words.insert(41254, "monkhood");
-        words.insert(41255, "monogamy");
-        words.insert(41256, "monogram");
-        words.insert(41261, "monologue");
-        words.insert(41262, "monopoly");
-        words.insert(41263, "monorail");
-        words.insert(41264, "monotone");
-        words.insert(41265, "monotype");
-        words.insert(41266, "monoxide");
-        words.insert(41311, "monsieur");
-        words.insert(41312, "monsoon");
-        words.insert(41313, "monstrous");
-        words.insert(41314, "monthly");
-        words.insert(41315, "monument");
-        words.insert(41316, "moocher");
-        words.insert(41321, "moodiness");
-        words.insert(41322, "moody");
-        words.insert(41323, "mooing");
-        words.insert(41324, "moonbeam");
-        words.insert(41325, "mooned");
-        words.insert(41326, "moonlight");
-        words.insert(41331, "moonlike");
-        words.insert(41332, "moonlit");
-        words.insert(41333, "moonrise");
-        words.insert(41334, "moonscape");
-        words.insert(41335, "moonshine");
-        words.insert(41336, "moonstone");
-        words.insert(41341, "moonwalk");
-        words.insert(41342, "mop");
-        words.insert(41343, "morale");
-        words.insert(41344, "morality");
-        words.insert(41345, "morally");
-        words.insert(41346, "morbidity");
-        words.insert(41351, "morbidly");
-        words.insert(41352, "morphine");
-        words.insert(41353, "morphing");
-        words.insert(41354, "morse");
-        words.insert(41355, "mortality");
-        words.insert(41356, "mortally");
-        words.insert(41361, "mortician");
-        words.insert(41362, "mortified");
-        words.insert(41363, "mortify");
-        words.insert(41364, "mortuary");
-        words.insert(41365, "mosaic");
-        words.insert(41366, "mossy");
-        words.insert(41411, "most");
-        words.insert(41412, "mothball");
-        words.insert(41413, "mothproof");
-        words.insert(41414, "motion");
-        words.insert(41415, "motivate");
-        words.insert(41416, "motivator");
-        words.insert(41421, "motive");
-        words.insert(41422, "motocross");
-        words.insert(41423, "motor");
-        words.insert(41424, "motto");
-        words.insert(41425, "mountable");
-        words.insert(41426, "mountain");
-        words.insert(41431, "mounted");
-        words.insert(41432, "mounting");
-        words.insert(41433, "mourner");
-        words.insert(41434, "mournful");
-        words.insert(41435, "mouse");
-        words.insert(41436, "mousiness");
-        words.insert(41441, "moustache");
-        words.insert(41442, "mousy");
-        words.insert(41443, "mouth");
-        words.insert(41444, "movable");
-        words.insert(41445, "move");
-        words.insert(41446, "movie");
-        words.insert(41451, "moving");
-        words.insert(41452, "mower");
-        words.insert(41453, "mowing");
-        words.insert(41454, "much");
-        words.insert(41455, "muck");
-        words.insert(41456, "mud");
-        words.insert(41461, "mug");
-        words.insert(41462, "mulberry");
-        words.insert(41463, "mulch");
-        words.insert(41464, "mule");
-        words.insert(41465, "mulled");
-        words.insert(41466, "mullets");
-        words.insert(41511, "multiple");
-        words.insert(41512, "multiply");
-        words.insert(41513, "multitask");
-        words.insert(41514, "multitude");
-        words.insert(41515, "mumble");
-        words.insert(41516, "mumbling");
-        words.insert(41521, "mumbo");
-        words.insert(41522, "mummified");
-        words.insert(41523, "mummify");
-        words.insert(41524, "mummy");
-        words.insert(41525, "mumps");
-        words.insert(41526, "munchkin");
-        words.insert(41531, "mundane");
-        words.insert(41532, "municipal");
-        words.insert(41533, "muppet");
-        words.insert(41534, "mural");
-        words.insert(41535, "murkiness");
-        words.insert(41536, "murky");
-        words.insert(41541, "murmuring");
-        words.insert(41542, "muscular");
-        words.insert(41543, "museum");
-        words.insert(41544, "mushily");
-        words.insert(41545, "mushiness");
-        words.insert(41546, "mushroom");
-        words.insert(41551, "mushy");
-        words.insert(41552, "music");
-        words.insert(41553, "musket");
-        words.insert(41554, "muskiness");
-        words.insert(41555, "musky");
-        words.insert(41556, "mustang");
-        words.insert(41561, "mustard");
-        words.insert(41562, "muster");
-        words.insert(41563, "mustiness");
-        words.insert(41564, "musty");
-        words.insert(41565, "mutable");
-        words.insert(41566, "mutate");
-        words.insert(41611, "mutation");
-        words.insert(41612, "mute");
-        words.insert(41613, "mutilated");
-        words.insert(41614, "mutilator");
-        words.insert(41615, "mutiny");
-        words.insert(41616, "mutt");
-        words.insert(41621, "mutual");
-        words.insert(41622, "muzzle");
-        words.insert(41623, "myself");
-        words.insert(41624, "myspace");
-        words.insert(41625, "mystified");
-        words.insert(41626, "mystify");
-        words.insert(41631, "myth");
-        words.insert(41632, "nacho");
-        words.insert(41633, "nag");
-        words.insert(41634, "nail");
-        words.insert(41635, "name");
-        words.insert(41636, "naming");
-        words.insert(41641, "nanny");
-        words.insert(41642, "nanometer");
-        words.insert(41643, "nape");
-        words.insert(41644, "napkin");
-        words.insert(41645, "napped");
-        words.insert(41646, "napping");
-        words.insert(41651, "nappy");
-        words.insert(41652, "narrow");
-        words.insert(41653, "nastily");
-        words.insert(41654, "nastiness");
-        words.insert(41655, "national");
-        words.insert(41656, "native");
-        words.insert(41661, "nativity");
-        words.insert(41662, "natural");
-        words.insert(41663, "nature");
-        words.insert(41664, "naturist");
-        words.insert(41665, "nautical");
-        words.insert(41666, "navigate");
-        words.insert(42111, "navigator");
-        words.insert(42112, "navy");
-        words.insert(42113, "nearby");
-        words.insert(42114, "nearest");
-        words.insert(42115, "nearly");
-        words.insert(42116, "nearness");
-        words.insert(42121, "neatly");
-        words.insert(42122, "neatness");
-        words.insert(42123, "nebula");
-        words.insert(42124, "nebulizer");
-        words.insert(42125, "nectar");
-        words.insert(42126, "negate");
-        words.insert(42131, "negation");
-        words.insert(42132, "negative");
-        words.insert(42133, "neglector");
-        words.insert(42134, "negligee");
-        words.insert(42135, "negligent");
-        words.insert(42136, "negotiate");
-        words.insert(42141, "nemeses");
-        words.insert(42142, "nemesis");
-        words.insert(42143, "neon");
-        words.insert(42144, "nephew");
-        words.insert(42145, "nerd");
-        words.insert(42146, "nervous");
-        words.insert(42151, "nervy");
-        words.insert(42152, "nest");
-        words.insert(42153, "net");
-        words.insert(42154, "neurology");
-        words.insert(42155, "neuron");
-        words.insert(42156, "neurosis");
-        words.insert(42161, "neurotic");
-        words.insert(42162, "neuter");
-        words.insert(42163, "neutron");
-        words.insert(42164, "never");
-        words.insert(42165, "next");
-        words.insert(42166, "nibble");
-        words.insert(42211, "nickname");
-        words.insert(42212, "nicotine");
-        words.insert(42213, "niece");
-        words.insert(42214, "nifty");
-        words.insert(42215, "nimble");
-        words.insert(42216, "nimbly");
-        words.insert(42221, "nineteen");
-        words.insert(42222, "ninetieth");
-        words.insert(42223, "ninja");
-        words.insert(42224, "nintendo");
-        words.insert(42225, "ninth");
-        words.insert(42226, "nuclear");
-        words.insert(42231, "nuclei");
-        words.insert(42232, "nucleus");
-        words.insert(42233, "nugget");
-        words.insert(42234, "nullify");
-        words.insert(42235, "number");
-        words.insert(42236, "numbing");
-        words.insert(42241, "numbly");
-        words.insert(42242, "numbness");
-        words.insert(42243, "numeral");
-        words.insert(42244, "numerate");
-        words.insert(42245, "numerator");
-        words.insert(42246, "numeric");
-        words.insert(42251, "numerous");
-        words.insert(42252, "nuptials");
-        words.insert(42253, "nursery");
-        words.insert(42254, "nursing");
-        words.insert(42255, "nurture");
-        words.insert(42256, "nutcase");
-        words.insert(42261, "nutlike");
-        words.insert(42262, "nutmeg");
-        words.insert(42263, "nutrient");
-        words.insert(42264, "nutshell");
-        words.insert(42265, "nuttiness");
-        words.insert(42266, "nutty");
-        words.insert(42311, "nuzzle");
-        words.insert(42312, "nylon");
-        words.insert(42313, "oaf");
-        words.insert(42314, "oak");
-        words.insert(42315, "oasis");
-        words.insert(42316, "oat");
-        words.insert(42321, "obedience");
-        words.insert(42322, "obedient");
-        words.insert(42323, "obituary");
-        words.insert(42324, "object");
-        words.insert(42325, "obligate");
-        words.insert(42326, "obliged");
-        words.insert(42331, "oblivion");
-        words.insert(42332, "oblivious");
-        words.insert(42333, "oblong");
-        words.insert(42334, "obnoxious");
-        words.insert(42335, "oboe");
-        words.insert(42336, "obscure");
-        words.insert(42341, "obscurity");
-        words.insert(42342, "observant");
-        words.insert(42343, "observer");
-        words.insert(42344, "observing");
-        words.insert(42345, "obsessed");
-        words.insert(42346, "obsession");
-        words.insert(42351, "obsessive");
-        words.insert(42352, "obsolete");
-        words.insert(42353, "obstacle");
-        words.insert(42354, "obstinate");
-        words.insert(42355, "obstruct");
-        words.insert(42356, "obtain");
-        words.insert(42361, "obtrusive");
-        words.insert(42362, "obtuse");
-        words.insert(42363, "obvious");
-        words.insert(42364, "occultist");
-        words.insert(42365, "occupancy");
-        words.insert(42366, "occupant");
-        words.insert(42411, "occupier");
-        words.insert(42412, "occupy");
-        words.insert(42413, "ocean");
-        words.insert(42414, "ocelot");
-        words.insert(42415, "octagon");
-        words.insert(42416, "octane");
-        words.insert(42421, "october");
-        words.insert(42422, "octopus");
-        words.insert(42423, "ogle");
-        words.insert(42424, "oil");
-        words.insert(42425, "oink");
-        words.insert(42426, "ointment");
-        words.insert(42431, "okay");
-        words.insert(42432, "old");
-        words.insert(42433, "olive");
-        words.insert(42434, "olympics");
-        words.insert(42435, "omega");
-        words.insert(42436, "omen");
-        words.insert(42441, "ominous");
-        words.insert(42442, "omission");
-        words.insert(42443, "omit");
-        words.insert(42444, "omnivore");
-        words.insert(42445, "onboard");
-        words.insert(42446, "oncoming");
-        words.insert(42451, "ongoing");
-        words.insert(42452, "onion");
-        words.insert(42453, "online");
-        words.insert(42454, "onlooker");
-        words.insert(42455, "only");
-        words.insert(42456, "onscreen");
-        words.insert(42461, "onset");
-        words.insert(42462, "onshore");
-        words.insert(42463, "onslaught");
-        words.insert(42464, "onstage");
-        words.insert(42465, "onto");
-        words.insert(42466, "onward");
-        words.insert(42511, "onyx");
-        words.insert(42512, "oops");
-        words.insert(42513, "ooze");
-        words.insert(42514, "oozy");
-        words.insert(42515, "opacity");
-        words.insert(42516, "opal");
-        words.insert(42521, "open");
-        words.insert(42522, "operable");
-        words.insert(42523, "operate");
-        words.insert(42524, "operating");
-        words.insert(42525, "operation");
-        words.insert(42526, "operative");
-        words.insert(42531, "operator");
-        words.insert(42532, "opium");
-        words.insert(42533, "opossum");
-        words.insert(42534, "opponent");
-        words.insert(42535, "oppose");
-        words.insert(42536, "opposing");
-        words.insert(42541, "opposite");
-        words.insert(42542, "oppressed");
-        words.insert(42543, "oppressor");
-        words.insert(42544, "opt");
-        words.insert(42545, "opulently");
-        words.insert(42546, "osmosis");
-        words.insert(42551, "other");
-        words.insert(42552, "otter");
-        words.insert(42553, "ouch");
-        words.insert(42554, "ought");
-        words.insert(42555, "ounce");
-        words.insert(42556, "outage");
-        words.insert(42561, "outback");
-        words.insert(42562, "outbid");
-        words.insert(42563, "outboard");
-        words.insert(42564, "outbound");
-        words.insert(42565, "outbreak");
-        words.insert(42566, "outburst");
-        words.insert(42611, "outcast");
-        words.insert(42612, "outclass");
-        words.insert(42613, "outcome");
-        words.insert(42614, "outdated");
-        words.insert(42615, "outdoors");
-        words.insert(42616, "outer");
-        words.insert(42621, "outfield");
-        words.insert(42622, "outfit");
-        words.insert(42623, "outflank");
-        words.insert(42624, "outgoing");
-        words.insert(42625, "outgrow");
-        words.insert(42626, "outhouse");
-        words.insert(42631, "outing");
-        words.insert(42632, "outlast");
-        words.insert(42633, "outlet");
-        words.insert(42634, "outline");
-        words.insert(42635, "outlook");
-        words.insert(42636, "outlying");
-        words.insert(42641, "outmatch");
-        words.insert(42642, "outmost");
-        words.insert(42643, "outnumber");
-        words.insert(42644, "outplayed");
-        words.insert(42645, "outpost");
-        words.insert(42646, "outpour");
-        words.insert(42651, "output");
-        words.insert(42652, "outrage");
-        words.insert(42653, "outrank");
-        words.insert(42654, "outreach");
-        words.insert(42655, "outright");
-        words.insert(42656, "outscore");
-        words.insert(42661, "outsell");
-        words.insert(42662, "outshine");
-        words.insert(42663, "outshoot");
-        words.insert(42664, "outsider");
-        words.insert(42665, "outskirts");
-        words.insert(42666, "outsmart");
-        words.insert(43111, "outsource");
-        words.insert(43112, "outspoken");
-        words.insert(43113, "outtakes");
-        words.insert(43114, "outthink");
-        words.insert(43115, "outward");
-        words.insert(43116, "outweigh");
-        words.insert(43121, "outwit");
-        words.insert(43122, "oval");
-        words.insert(43123, "ovary");
-        words.insert(43124, "oven");
-        words.insert(43125, "overact");
-        words.insert(43126, "overall");
-        words.insert(43131, "overarch");
-        words.insert(43132, "overbid");
-        words.insert(43133, "overbill");
-        words.insert(43134, "overbite");
-        words.insert(43135, "overblown");
-        words.insert(43136, "overboard");
-        words.insert(43141, "overbook");
-        words.insert(43142, "overbuilt");
-        words.insert(43143, "overcast");
-        words.insert(43144, "overcoat");
-        words.insert(43145, "overcome");
-        words.insert(43146, "overcook");
-        words.insert(43151, "overcrowd");
-        words.insert(43152, "overdraft");
-        words.insert(43153, "overdrawn");
-        words.insert(43154, "overdress");
-        words.insert(43155, "overdrive");
-        words.insert(43156, "overdue");
-        words.insert(43161, "overeager");
-        words.insert(43162, "overeater");
-        words.insert(43163, "overexert");
-        words.insert(43164, "overfed");
-        words.insert(43165, "overfeed");
-        words.insert(43166, "overfill");
-        words.insert(43211, "overflow");
-        words.insert(43212, "overfull");
-        words.insert(43213, "overgrown");
-        words.insert(43214, "overhand");
-        words.insert(43215, "overhang");
-        words.insert(43216, "overhaul");
-        words.insert(43221, "overhead");
-        words.insert(43222, "overhear");
-        words.insert(43223, "overheat");
-        words.insert(43224, "overhung");
-        words.insert(43225, "overjoyed");
-        words.insert(43226, "overkill");
-        words.insert(43231, "overlabor");
-        words.insert(43232, "overlaid");
-        words.insert(43233, "overlap");
-        words.insert(43234, "overlay");
-        words.insert(43235, "overload");
-        words.insert(43236, "overlook");
-        words.insert(43241, "overlord");
-        words.insert(43242, "overlying");
-        words.insert(43243, "overnight");
-        words.insert(43244, "overpass");
-        words.insert(43245, "overpay");
-        words.insert(43246, "overplant");
-        words.insert(43251, "overplay");
-        words.insert(43252, "overpower");
-        words.insert(43253, "overprice");
-        words.insert(43254, "overrate");
-        words.insert(43255, "overreach");
-        words.insert(43256, "overreact");
-        words.insert(43261, "override");
-        words.insert(43262, "overripe");
-        words.insert(43263, "overrule");
-        words.insert(43264, "overrun");
-        words.insert(43265, "overshoot");
-        words.insert(43266, "overshot");
-        words.insert(43311, "oversight");
-        words.insert(43312, "oversized");
-        words.insert(43313, "oversleep");
-        words.insert(43314, "oversold");
-        words.insert(43315, "overspend");
-        words.insert(43316, "overstate");
-        words.insert(43321, "overstay");
-        words.insert(43322, "overstep");
-        words.insert(43323, "overstock");
-        words.insert(43324, "overstuff");
-        words.insert(43325, "oversweet");
-        words.insert(43326, "overtake");
-        words.insert(43331, "overthrow");
-        words.insert(43332, "overtime");
-        words.insert(43333, "overtly");
-        words.insert(43334, "overtone");
-        words.insert(43335, "overture");
-        words.insert(43336, "overturn");
-        words.insert(43341, "overuse");
-        words.insert(43342, "overvalue");
-        words.insert(43343, "overview");
-        words.insert(43344, "overwrite");
-        words.insert(43345, "owl");
-        words.insert(43346, "oxford");
-        words.insert(43351, "oxidant");
-        words.insert(43352, "oxidation");
-        words.insert(43353, "oxidize");
-        words.insert(43354, "oxidizing");
-        words.insert(43355, "oxygen");
-        words.insert(43356, "oxymoron");
-        words.insert(43361, "oyster");
-        words.insert(43362, "ozone");
-        words.insert(43363, "paced");
-        words.insert(43364, "pacemaker");
-        words.insert(43365, "pacific");
-        words.insert(43366, "pacifier");
-        words.insert(43411, "pacifism");
-        words.insert(43412, "pacifist");
-        words.insert(43413, "pacify");
-        words.insert(43414, "padded");
-        words.insert(43415, "padding");
-        words.insert(43416, "paddle");
-        words.insert(43421, "paddling");
-        words.insert(43422, "padlock");
-        words.insert(43423, "pagan");
-        words.insert(43424, "pager");
-        words.insert(43425, "paging");
-        words.insert(43426, "pajamas");
-        words.insert(43431, "palace");
-        words.insert(43432, "palatable");
-        words.insert(43433, "palm");
-        words.insert(43434, "palpable");
-        words.insert(43435, "palpitate");
-        words.insert(43436, "paltry");
-        words.insert(43441, "pampered");
-        words.insert(43442, "pamperer");
-        words.insert(43443, "pampers");
-        words.insert(43444, "pamphlet");
-        words.insert(43445, "panama");
-        words.insert(43446, "pancake");
-        words.insert(43451, "pancreas");
-        words.insert(43452, "panda");
-        words.insert(43453, "pandemic");
-        words.insert(43454, "pang");
-        words.insert(43455, "panhandle");
-        words.insert(43456, "panic");
-        words.insert(43461, "panning");
-        words.insert(43462, "panorama");
-        words.insert(43463, "panoramic");
-        words.insert(43464, "panther");
-        words.insert(43465, "pantomime");
-        words.insert(43466, "pantry");
-        words.insert(43511, "pants");
-        words.insert(43512, "pantyhose");
-        words.insert(43513, "paparazzi");
-        words.insert(43514, "papaya");
-        words.insert(43515, "paper");
-        words.insert(43516, "paprika");
-        words.insert(43521, "papyrus");
-        words.insert(43522, "parabola");
-        words.insert(43523, "parachute");
-        words.insert(43524, "parade");
-        words.insert(43525, "paradox");
-        words.insert(43526, "paragraph");
-        words.insert(43531, "parakeet");
-        words.insert(43532, "paralegal");
-        words.insert(43533, "paralyses");
-        words.insert(43534, "paralysis");
-        words.insert(43535, "paralyze");
-        words.insert(43536, "paramedic");
-        words.insert(43541, "parameter");
-        words.insert(43542, "paramount");
-        words.insert(43543, "parasail");
-        words.insert(43544, "parasite");
-        words.insert(43545, "parasitic");
-        words.insert(43546, "parcel");
-        words.insert(43551, "parched");
-        words.insert(43552, "parchment");
-        words.insert(43553, "pardon");
-        words.insert(43554, "parish");
-        words.insert(43555, "parka");
-        words.insert(43556, "parking");
-        words.insert(43561, "parkway");
-        words.insert(43562, "parlor");
-        words.insert(43563, "parmesan");
-        words.insert(43564, "parole");
-        words.insert(43565, "parrot");
-        words.insert(43566, "parsley");
-        words.insert(43611, "parsnip");
-        words.insert(43612, "partake");
-        words.insert(43613, "parted");
-        words.insert(43614, "parting");
-        words.insert(43615, "partition");
-        words.insert(43616, "partly");
-        words.insert(43621, "partner");
-        words.insert(43622, "partridge");
-        words.insert(43623, "party");
-        words.insert(43624, "passable");
-        words.insert(43625, "passably");
-        words.insert(43626, "passage");
-        words.insert(43631, "passcode");
-        words.insert(43632, "passenger");
-        words.insert(43633, "passerby");
-        words.insert(43634, "passing");
-        words.insert(43635, "passion");
-        words.insert(43636, "passive");
-        words.insert(43641, "passivism");
-        words.insert(43642, "passover");
-        words.insert(43643, "passport");
-        words.insert(43644, "password");
-        words.insert(43645, "pasta");
-        words.insert(43646, "pasted");
-        words.insert(43651, "pastel");
-        words.insert(43652, "pastime");
-        words.insert(43653, "pastor");
-        words.insert(43654, "pastrami");
-        words.insert(43655, "pasture");
-        words.insert(43656, "pasty");
-        words.insert(43661, "patchwork");
-        words.insert(43662, "patchy");
-        words.insert(43663, "paternal");
-        words.insert(43664, "paternity");
-        words.insert(43665, "path");
-        words.insert(43666, "patience");
-        words.insert(44111, "patient");
-        words.insert(44112, "patio");
-        words.insert(44113, "patriarch");
-        words.insert(44114, "patriot");
-        words.insert(44115, "patrol");
-        words.insert(44116, "patronage");
-        words.insert(44121, "patronize");
-        words.insert(44122, "pauper");
-        words.insert(44123, "pavement");
-        words.insert(44124, "paver");
-        words.insert(44125, "pavestone");
-        words.insert(44126, "pavilion");
-        words.insert(44131, "paving");
-        words.insert(44132, "pawing");
-        words.insert(44133, "payable");
-        words.insert(44134, "payback");
-        words.insert(44135, "paycheck");
-        words.insert(44136, "payday");
-        words.insert(44141, "payee");
-        words.insert(44142, "payer");
-        words.insert(44143, "paying");
-        words.insert(44144, "payment");
-        words.insert(44145, "payphone");
-        words.insert(44146, "payroll");
-        words.insert(44151, "pebble");
-        words.insert(44152, "pebbly");
-        words.insert(44153, "pecan");
-        words.insert(44154, "pectin");
-        words.insert(44155, "peculiar");
-        words.insert(44156, "peddling");
-        words.insert(44161, "pediatric");
-        words.insert(44162, "pedicure");
-        words.insert(44163, "pedigree");
-        words.insert(44164, "pedometer");
-        words.insert(44165, "pegboard");
-        words.insert(44166, "pelican");
-        words.insert(44211, "pellet");
-        words.insert(44212, "pelt");
-        words.insert(44213, "pelvis");
-        words.insert(44214, "penalize");
-        words.insert(44215, "penalty");
-        words.insert(44216, "pencil");
-        words.insert(44221, "pendant");
-        words.insert(44222, "pending");
-        words.insert(44223, "penholder");
-        words.insert(44224, "penknife");
-        words.insert(44225, "pennant");
-        words.insert(44226, "penniless");
-        words.insert(44231, "penny");
-        words.insert(44232, "penpal");
-        words.insert(44233, "pension");
-        words.insert(44234, "pentagon");
-        words.insert(44235, "pentagram");
-        words.insert(44236, "pep");
-        words.insert(44241, "perceive");
-        words.insert(44242, "percent");
-        words.insert(44243, "perch");
-        words.insert(44244, "percolate");
-        words.insert(44245, "perennial");
-        words.insert(44246, "perfected");
-        words.insert(44251, "perfectly");
-        words.insert(44252, "perfume");
-        words.insert(44253, "periscope");
-        words.insert(44254, "perish");
-        words.insert(44255, "perjurer");
-        words.insert(44256, "perjury");
-        words.insert(44261, "perkiness");
-        words.insert(44262, "perky");
-        words.insert(44263, "perm");
-        words.insert(44264, "peroxide");
-        words.insert(44265, "perpetual");
-        words.insert(44266, "perplexed");
-        words.insert(44311, "persecute");
-        words.insert(44312, "persevere");
-        words.insert(44313, "persuaded");
-        words.insert(44314, "persuader");
-        words.insert(44315, "pesky");
-        words.insert(44316, "peso");
-        words.insert(44321, "pessimism");
-        words.insert(44322, "pessimist");
-        words.insert(44323, "pester");
-        words.insert(44324, "pesticide");
-        words.insert(44325, "petal");
-        words.insert(44326, "petite");
-        words.insert(44331, "petition");
-        words.insert(44332, "petri");
-        words.insert(44333, "petroleum");
-        words.insert(44334, "petted");
-        words.insert(44335, "petticoat");
-        words.insert(44336, "pettiness");
-        words.insert(44341, "petty");
-        words.insert(44342, "petunia");
-        words.insert(44343, "phantom");
-        words.insert(44344, "phobia");
-        words.insert(44345, "phoenix");
-        words.insert(44346, "phonebook");
-        words.insert(44351, "phoney");
-        words.insert(44352, "phonics");
-        words.insert(44353, "phoniness");
-        words.insert(44354, "phony");
-        words.insert(44355, "phosphate");
-        words.insert(44356, "photo");
-        words.insert(44361, "phrase");
-        words.insert(44362, "phrasing");
-        words.insert(44363, "placard");
-        words.insert(44364, "placate");
-        words.insert(44365, "placidly");
-        words.insert(44366, "plank");
-        words.insert(44411, "planner");
-        words.insert(44412, "plant");
-        words.insert(44413, "plasma");
-        words.insert(44414, "plaster");
-        words.insert(44415, "plastic");
-        words.insert(44416, "plated");
-        words.insert(44421, "platform");
-        words.insert(44422, "plating");
-        words.insert(44423, "platinum");
-        words.insert(44424, "platonic");
-        words.insert(44425, "platter");
-        words.insert(44426, "platypus");
-        words.insert(44431, "plausible");
-        words.insert(44432, "plausibly");
-        words.insert(44433, "playable");
-        words.insert(44434, "playback");
-        words.insert(44435, "player");
-        words.insert(44436, "playful");
-        words.insert(44441, "playgroup");
-        words.insert(44442, "playhouse");
-        words.insert(44443, "playing");
-        words.insert(44444, "playlist");
-        words.insert(44445, "playmaker");
-        words.insert(44446, "playmate");
-        words.insert(44451, "playoff");
-        words.insert(44452, "playpen");
-        words.insert(44453, "playroom");
-        words.insert(44454, "playset");
-        words.insert(44455, "plaything");
-        words.insert(44456, "playtime");
-        words.insert(44461, "plaza");
-        words.insert(44462, "pleading");
-        words.insert(44463, "pleat");
-        words.insert(44464, "pledge");
-        words.insert(44465, "plentiful");
-        words.insert(44466, "plenty");
-        words.insert(44511, "plethora");
-        words.insert(44512, "plexiglas");
-        words.insert(44513, "pliable");
-        words.insert(44514, "plod");
-        words.insert(44515, "plop");
-        words.insert(44516, "plot");
-        words.insert(44521, "plow");
-        words.insert(44522, "ploy");
-        words.insert(44523, "pluck");
-        words.insert(44524, "plug");
-        words.insert(44525, "plunder");
-        words.insert(44526, "plunging");
-        words.insert(44531, "plural");
-        words.insert(44532, "plus");
-        words.insert(44533, "plutonium");
-        words.insert(44534, "plywood");
-        words.insert(44535, "poach");
-        words.insert(44536, "pod");
-        words.insert(44541, "poem");
-        words.insert(44542, "poet");
-        words.insert(44543, "pogo");
-        words.insert(44544, "pointed");
-        words.insert(44545, "pointer");
-        words.insert(44546, "pointing");
-        words.insert(44551, "pointless");
-        words.insert(44552, "pointy");
-        words.insert(44553, "poise");
-        words.insert(44554, "poison");
-        words.insert(44555, "poker");
-        words.insert(44556, "poking");
-        words.insert(44561, "polar");
-        words.insert(44562, "police");
-        words.insert(44563, "policy");
-        words.insert(44564, "polio");
-        words.insert(44565, "polish");
-        words.insert(44566, "politely");
-        words.insert(44611, "polka");
-        words.insert(44612, "polo");
-        words.insert(44613, "polyester");
-        words.insert(44614, "polygon");
-        words.insert(44615, "polygraph");
-        words.insert(44616, "polymer");
-        words.insert(44621, "poncho");
-        words.insert(44622, "pond");
-        words.insert(44623, "pony");
-        words.insert(44624, "popcorn");
-        words.insert(44625, "pope");
-        words.insert(44626, "poplar");
-        words.insert(44631, "popper");
-        words.insert(44632, "poppy");
-        words.insert(44633, "popsicle");
-        words.insert(44634, "populace");
-        words.insert(44635, "popular");
-        words.insert(44636, "populate");
-        words.insert(44641, "porcupine");
-        words.insert(44642, "pork");
-        words.insert(44643, "porous");
-        words.insert(44644, "porridge");
-        words.insert(44645, "portable");
-        words.insert(44646, "portal");
-        words.insert(44651, "portfolio");
-        words.insert(44652, "porthole");
-        words.insert(44653, "portion");
-        words.insert(44654, "portly");
-        words.insert(44655, "portside");
-        words.insert(44656, "poser");
-        words.insert(44661, "posh");
-        words.insert(44662, "posing");
-        words.insert(44663, "possible");
-        words.insert(44664, "possibly");
-        words.insert(44665, "possum");
-        words.insert(44666, "postage");
-        words.insert(45111, "postal");
-        words.insert(45112, "postbox");
-        words.insert(45113, "postcard");
-        words.insert(45114, "posted");
-        words.insert(45115, "poster");
-        words.insert(45116, "posting");
-        words.insert(45121, "postnasal");
-        words.insert(45122, "posture");
-        words.insert(45123, "postwar");
-        words.insert(45124, "pouch");
-        words.insert(45125, "pounce");
-        words.insert(45126, "pouncing");
-        words.insert(45131, "pound");
-        words.insert(45132, "pouring");
-        words.insert(45133, "pout");
-        words.insert(45134, "powdered");
-        words.insert(45135, "powdering");
-        words.insert(45136, "powdery");
-        words.insert(45141, "power");
-        words.insert(45142, "powwow");
-        words.insert(45143, "pox");
-        words.insert(45144, "praising");
-        words.insert(45145, "prance");
-        words.insert(45146, "prancing");
-        words.insert(45151, "pranker");
-        words.insert(45152, "prankish");
-        words.insert(45153, "prankster");
-        words.insert(45154, "prayer");
-        words.insert(45155, "praying");
-        words.insert(45156, "preacher");
-        words.insert(45161, "preaching");
-        words.insert(45162, "preachy");
-        words.insert(45163, "preamble");
-        words.insert(45164, "precinct");
-        words.insert(45165, "precise");
-        words.insert(45166, "precision");
-        words.insert(45211, "precook");
-        words.insert(45212, "precut");
-        words.insert(45213, "predator");
-        words.insert(45214, "predefine");
-        words.insert(45215, "predict");
-        words.insert(45216, "preface");
-        words.insert(45221, "prefix");
-        words.insert(45222, "preflight");
-        words.insert(45223, "preformed");
-        words.insert(45224, "pregame");
-        words.insert(45225, "pregnancy");
-        words.insert(45226, "pregnant");
-        words.insert(45231, "preheated");
-        words.insert(45232, "prelaunch");
-        words.insert(45233, "prelaw");
-        words.insert(45234, "prelude");
-        words.insert(45235, "premiere");
-        words.insert(45236, "premises");
-        words.insert(45241, "premium");
-        words.insert(45242, "prenatal");
-        words.insert(45243, "preoccupy");
-        words.insert(45244, "preorder");
-        words.insert(45245, "prepaid");
-        words.insert(45246, "prepay");
-        words.insert(45251, "preplan");
-        words.insert(45252, "preppy");
-        words.insert(45253, "preschool");
-        words.insert(45254, "prescribe");
-        words.insert(45255, "preseason");
-        words.insert(45256, "preset");
-        words.insert(45261, "preshow");
-        words.insert(45262, "president");
-        words.insert(45263, "presoak");
-        words.insert(45264, "press");
-        words.insert(45265, "presume");
-        words.insert(45266, "presuming");
-        words.insert(45311, "preteen");
-        words.insert(45312, "pretended");
-        words.insert(45313, "pretender");
-        words.insert(45314, "pretense");
-        words.insert(45315, "pretext");
-        words.insert(45316, "pretty");
-        words.insert(45321, "pretzel");
-        words.insert(45322, "prevail");
-        words.insert(45323, "prevalent");
-        words.insert(45324, "prevent");
-        words.insert(45325, "preview");
-        words.insert(45326, "previous");
-        words.insert(45331, "prewar");
-        words.insert(45332, "prewashed");
-        words.insert(45333, "prideful");
-        words.insert(45334, "pried");
-        words.insert(45335, "primal");
-        words.insert(45336, "primarily");
-        words.insert(45341, "primary");
-        words.insert(45342, "primate");
-        words.insert(45343, "primer");
-        words.insert(45344, "primp");
-        words.insert(45345, "princess");
-        words.insert(45346, "print");
-        words.insert(45351, "prior");
-        words.insert(45352, "prism");
-        words.insert(45353, "prison");
-        words.insert(45354, "prissy");
-        words.insert(45355, "pristine");
-        words.insert(45356, "privacy");
-        words.insert(45361, "private");
-        words.insert(45362, "privatize");
-        words.insert(45363, "prize");
-        words.insert(45364, "proactive");
-        words.insert(45365, "probable");
-        words.insert(45366, "probably");
-        words.insert(45411, "probation");
-        words.insert(45412, "probe");
-        words.insert(45413, "probing");
-        words.insert(45414, "probiotic");
-        words.insert(45415, "problem");
-        words.insert(45416, "procedure");
-        words.insert(45421, "process");
-        words.insert(45422, "proclaim");
-        words.insert(45423, "procreate");
-        words.insert(45424, "procurer");
-        words.insert(45425, "prodigal");
-        words.insert(45426, "prodigy");
-        words.insert(45431, "produce");
-        words.insert(45432, "product");
-        words.insert(45433, "profane");
-        words.insert(45434, "profanity");
-        words.insert(45435, "professed");
-        words.insert(45436, "professor");
-        words.insert(45441, "profile");
-        words.insert(45442, "profound");
-        words.insert(45443, "profusely");
-        words.insert(45444, "progeny");
-        words.insert(45445, "prognosis");
-        words.insert(45446, "program");
-        words.insert(45451, "progress");
-        words.insert(45452, "projector");
-        words.insert(45453, "prologue");
-        words.insert(45454, "prolonged");
-        words.insert(45455, "promenade");
-        words.insert(45456, "prominent");
-        words.insert(45461, "promoter");
-        words.insert(45462, "promotion");
-        words.insert(45463, "prompter");
-        words.insert(45464, "promptly");
-        words.insert(45465, "prone");
-        words.insert(45466, "prong");
-        words.insert(45511, "pronounce");
-        words.insert(45512, "pronto");
-        words.insert(45513, "proofing");
-        words.insert(45514, "proofread");
-        words.insert(45515, "proofs");
-        words.insert(45516, "propeller");
-        words.insert(45521, "properly");
-        words.insert(45522, "property");
-        words.insert(45523, "proponent");
-        words.insert(45524, "proposal");
-        words.insert(45525, "propose");
-        words.insert(45526, "props");
-        words.insert(45531, "prorate");
-        words.insert(45532, "protector");
-        words.insert(45533, "protegee");
-        words.insert(45534, "proton");
-        words.insert(45535, "prototype");
-        words.insert(45536, "protozoan");
-        words.insert(45541, "protract");
-        words.insert(45542, "protrude");
-        words.insert(45543, "proud");
-        words.insert(45544, "provable");
-        words.insert(45545, "proved");
-        words.insert(45546, "proven");
-        words.insert(45551, "provided");
-        words.insert(45552, "provider");
-        words.insert(45553, "providing");
-        words.insert(45554, "province");
-        words.insert(45555, "proving");
-        words.insert(45556, "provoke");
-        words.insert(45561, "provoking");
-        words.insert(45562, "provolone");
-        words.insert(45563, "prowess");
-        words.insert(45564, "prowler");
-        words.insert(45565, "prowling");
-        words.insert(45566, "proximity");
-        words.insert(45611, "proxy");
-        words.insert(45612, "prozac");
-        words.insert(45613, "prude");
-        words.insert(45614, "prudishly");
-        words.insert(45615, "prune");
-        words.insert(45616, "pruning");
-        words.insert(45621, "pry");
-        words.insert(45622, "psychic");
-        words.insert(45623, "public");
-        words.insert(45624, "publisher");
-        words.insert(45625, "pucker");
-        words.insert(45626, "pueblo");
-        words.insert(45631, "pug");
-        words.insert(45632, "pull");
-        words.insert(45633, "pulmonary");
-        words.insert(45634, "pulp");
-        words.insert(45635, "pulsate");
-        words.insert(45636, "pulse");
-        words.insert(45641, "pulverize");
-        words.insert(45642, "puma");
-        words.insert(45643, "pumice");
-        words.insert(45644, "pummel");
-        words.insert(45645, "punch");
-        words.insert(45646, "punctual");
-        words.insert(45651, "punctuate");
-        words.insert(45652, "punctured");
-        words.insert(45653, "pungent");
-        words.insert(45654, "punisher");
-        words.insert(45655, "punk");
-        words.insert(45656, "pupil");
-        words.insert(45661, "puppet");
-        words.insert(45662, "puppy");
-        words.insert(45663, "purchase");
-        words.insert(45664, "pureblood");
-        words.insert(45665, "purebred");
-        words.insert(45666, "purely");
-        words.insert(46111, "pureness");
-        words.insert(46112, "purgatory");
-        words.insert(46113, "purge");
-        words.insert(46114, "purging");
-        words.insert(46115, "purifier");
-        words.insert(46116, "purify");
-        words.insert(46121, "purist");
-        words.insert(46122, "puritan");
-        words.insert(46123, "purity");
-        words.insert(46124, "purple");
-        words.insert(46125, "purplish");
-        words.insert(46126, "purposely");
-        words.insert(46131, "purr");
-        words.insert(46132, "purse");
-        words.insert(46133, "pursuable");
-        words.insert(46134, "pursuant");
-        words.insert(46135, "pursuit");
-        words.insert(46136, "purveyor");
-        words.insert(46141, "pushcart");
-        words.insert(46142, "pushchair");
-        words.insert(46143, "pusher");
-        words.insert(46144, "pushiness");
-        words.insert(46145, "pushing");
-        words.insert(46146, "pushover");
-        words.insert(46151, "pushpin");
-        words.insert(46152, "pushup");
-        words.insert(46153, "pushy");
-        words.insert(46154, "putdown");
-        words.insert(46155, "putt");
-        words.insert(46156, "puzzle");
-        words.insert(46161, "puzzling");
-        words.insert(46162, "pyramid");
-        words.insert(46163, "pyromania");
-        words.insert(46164, "python");
-        words.insert(46165, "quack");
-        words.insert(46166, "quadrant");
-        words.insert(46211, "quail");
-        words.insert(46212, "quaintly");
-        words.insert(46213, "quake");
-        words.insert(46214, "quaking");
-        words.insert(46215, "qualified");
-        words.insert(46216, "qualifier");
-        words.insert(46221, "qualify");
-        words.insert(46222, "quality");
-        words.insert(46223, "qualm");
-        words.insert(46224, "quantum");
-        words.insert(46225, "quarrel");
-        words.insert(46226, "quarry");
-        words.insert(46231, "quartered");
-        words.insert(46232, "quarterly");
-        words.insert(46233, "quarters");
-        words.insert(46234, "quartet");
-        words.insert(46235, "quench");
-        words.insert(46236, "query");
-        words.insert(46241, "quicken");
-        words.insert(46242, "quickly");
-        words.insert(46243, "quickness");
-        words.insert(46244, "quicksand");
-        words.insert(46245, "quickstep");
-        words.insert(46246, "quiet");
-        words.insert(46251, "quill");
-        words.insert(46252, "quilt");
-        words.insert(46253, "quintet");
-        words.insert(46254, "quintuple");
-        words.insert(46255, "quirk");
-        words.insert(46256, "quit");
-        words.insert(46261, "quiver");
-        words.insert(46262, "quizzical");
-        words.insert(46263, "quotable");
-        words.insert(46264, "quotation");
-        words.insert(46265, "quote");
-        words.insert(46266, "rabid");
-        words.insert(46311, "race");
-        words.insert(46312, "racing");
-        words.insert(46313, "racism");
-        words.insert(46314, "rack");
-        words.insert(46315, "racoon");
-        words.insert(46316, "radar");
-        words.insert(46321, "radial");
-        words.insert(46322, "radiance");
-        words.insert(46323, "radiantly");
-        words.insert(46324, "radiated");
-        words.insert(46325, "radiation");
-        words.insert(46326, "radiator");
-        words.insert(46331, "radio");
-        words.insert(46332, "radish");
-        words.insert(46333, "raffle");
-        words.insert(46334, "raft");
-        words.insert(46335, "rage");
-        words.insert(46336, "ragged");
-        words.insert(46341, "raging");
-        words.insert(46342, "ragweed");
-        words.insert(46343, "raider");
-        words.insert(46344, "railcar");
-        words.insert(46345, "railing");
-        words.insert(46346, "railroad");
-        words.insert(46351, "railway");
-        words.insert(46352, "raisin");
-        words.insert(46353, "rake");
-        words.insert(46354, "raking");
-        words.insert(46355, "rally");
-        words.insert(46356, "ramble");
-        words.insert(46361, "rambling");
-        words.insert(46362, "ramp");
-        words.insert(46363, "ramrod");
-        words.insert(46364, "ranch");
-        words.insert(46365, "rancidity");
-        words.insert(46366, "random");
-        words.insert(46411, "ranged");
-        words.insert(46412, "ranger");
-        words.insert(46413, "ranging");
-        words.insert(46414, "ranked");
-        words.insert(46415, "ranking");
-        words.insert(46416, "ransack");
-        words.insert(46421, "ranting");
-        words.insert(46422, "rants");
-        words.insert(46423, "rare");
-        words.insert(46424, "rarity");
-        words.insert(46425, "rascal");
-        words.insert(46426, "rash");
-        words.insert(46431, "rasping");
-        words.insert(46432, "ravage");
-        words.insert(46433, "raven");
-        words.insert(46434, "ravine");
-        words.insert(46435, "raving");
-        words.insert(46436, "ravioli");
-        words.insert(46441, "ravishing");
-        words.insert(46442, "reabsorb");
-        words.insert(46443, "reach");
-        words.insert(46444, "reacquire");
-        words.insert(46445, "reaction");
-        words.insert(46446, "reactive");
-        words.insert(46451, "reactor");
-        words.insert(46452, "reaffirm");
-        words.insert(46453, "ream");
-        words.insert(46454, "reanalyze");
-        words.insert(46455, "reappear");
-        words.insert(46456, "reapply");
-        words.insert(46461, "reappoint");
-        words.insert(46462, "reapprove");
-        words.insert(46463, "rearrange");
-        words.insert(46464, "rearview");
-        words.insert(46465, "reason");
-        words.insert(46466, "reassign");
-        words.insert(46511, "reassure");
-        words.insert(46512, "reattach");
-        words.insert(46513, "reawake");
-        words.insert(46514, "rebalance");
-        words.insert(46515, "rebate");
-        words.insert(46516, "rebel");
-        words.insert(46521, "rebirth");
-        words.insert(46522, "reboot");
-        words.insert(46523, "reborn");
-        words.insert(46524, "rebound");
-        words.insert(46525, "rebuff");
-        words.insert(46526, "rebuild");
-        words.insert(46531, "rebuilt");
-        words.insert(46532, "reburial");
-        words.insert(46533, "rebuttal");
-        words.insert(46534, "recall");
-        words.insert(46535, "recant");
-        words.insert(46536, "recapture");
-        words.insert(46541, "recast");
-        words.insert(46542, "recede");
-        words.insert(46543, "recent");
-        words.insert(46544, "recess");
-        words.insert(46545, "recharger");
-        words.insert(46546, "recipient");
-        words.insert(46551, "recital");
-        words.insert(46552, "recite");
-        words.insert(46553, "reckless");
-        words.insert(46554, "reclaim");
-        words.insert(46555, "recliner");
-        words.insert(46556, "reclining");
-        words.insert(46561, "recluse");
-        words.insert(46562, "reclusive");
-        words.insert(46563, "recognize");
-        words.insert(46564, "recoil");
-        words.insert(46565, "recollect");
-        words.insert(46566, "recolor");
-        words.insert(46611, "reconcile");
-        words.insert(46612, "reconfirm");
-        words.insert(46613, "reconvene");
-        words.insert(46614, "recopy");
-        words.insert(46615, "record");
-        words.insert(46616, "recount");
-        words.insert(46621, "recoup");
-        words.insert(46622, "recovery");
-        words.insert(46623, "recreate");
-        words.insert(46624, "rectal");
-        words.insert(46625, "rectangle");
-        words.insert(46626, "rectified");
-        words.insert(46631, "rectify");
-        words.insert(46632, "recycled");
-        words.insert(46633, "recycler");
-        words.insert(46634, "recycling");
-        words.insert(46635, "reemerge");
-        words.insert(46636, "reenact");
-        words.insert(46641, "reenter");
-        words.insert(46642, "reentry");
-        words.insert(46643, "reexamine");
-        words.insert(46644, "referable");
-        words.insert(46645, "referee");
-        words.insert(46646, "reference");
-        words.insert(46651, "refill");
-        words.insert(46652, "refinance");
-        words.insert(46653, "refined");
-        words.insert(46654, "refinery");
-        words.insert(46655, "refining");
-        words.insert(46656, "refinish");
-        words.insert(46661, "reflected");
-        words.insert(46662, "reflector");
-        words.insert(46663, "reflex");
-        words.insert(46664, "reflux");
-        words.insert(46665, "refocus");
-        words.insert(46666, "refold");
-        words.insert(51111, "reforest");
-        words.insert(51112, "reformat");
-        words.insert(51113, "reformed");
-        words.insert(51114, "reformer");
-        words.insert(51115, "reformist");
-        words.insert(51116, "refract");
-        words.insert(51121, "refrain");
-        words.insert(51122, "refreeze");
-        words.insert(51123, "refresh");
-        words.insert(51124, "refried");
-        words.insert(51125, "refueling");
-        words.insert(51126, "refund");
-        words.insert(51131, "refurbish");
-        words.insert(51132, "refurnish");
-        words.insert(51133, "refusal");
-        words.insert(51134, "refuse");
-        words.insert(51135, "refusing");
-        words.insert(51136, "refutable");
-        words.insert(51141, "refute");
-        words.insert(51142, "regain");
-        words.insert(51143, "regalia");
-        words.insert(51144, "regally");
-        words.insert(51145, "reggae");
-        words.insert(51146, "regime");
-        words.insert(51151, "region");
-        words.insert(51152, "register");
-        words.insert(51153, "registrar");
-        words.insert(51154, "registry");
-        words.insert(51155, "regress");
-        words.insert(51156, "regretful");
-        words.insert(51161, "regroup");
-        words.insert(51162, "regular");
-        words.insert(51163, "regulate");
-        words.insert(51164, "regulator");
-        words.insert(51165, "rehab");
-        words.insert(51166, "reheat");
-        words.insert(51211, "rehire");
-        words.insert(51212, "rehydrate");
-        words.insert(51213, "reimburse");
-        words.insert(51214, "reissue");
-        words.insert(51215, "reiterate");
-        words.insert(51216, "rejoice");
-        words.insert(51221, "rejoicing");
-        words.insert(51222, "rejoin");
-        words.insert(51223, "rekindle");
-        words.insert(51224, "relapse");
-        words.insert(51225, "relapsing");
-        words.insert(51226, "relatable");
-        words.insert(51231, "related");
-        words.insert(51232, "relation");
-        words.insert(51233, "relative");
-        words.insert(51234, "relax");
-        words.insert(51235, "relay");
-        words.insert(51236, "relearn");
-        words.insert(51241, "release");
-        words.insert(51242, "relenting");
-        words.insert(51243, "reliable");
-        words.insert(51244, "reliably");
-        words.insert(51245, "reliance");
-        words.insert(51246, "reliant");
-        words.insert(51251, "relic");
-        words.insert(51252, "relieve");
-        words.insert(51253, "relieving");
-        words.insert(51254, "relight");
-        words.insert(51255, "relish");
-        words.insert(51256, "relive");
-        words.insert(51261, "reload");
-        words.insert(51262, "relocate");
-        words.insert(51263, "relock");
-        words.insert(51264, "reluctant");
-        words.insert(51265, "rely");
-        words.insert(51266, "remake");
-        words.insert(51311, "remark");
-        words.insert(51312, "remarry");
-        words.insert(51313, "rematch");
-        words.insert(51314, "remedial");
-        words.insert(51315, "remedy");
-        words.insert(51316, "remember");
-        words.insert(51321, "reminder");
-        words.insert(51322, "remindful");
-        words.insert(51323, "remission");
-        words.insert(51324, "remix");
-        words.insert(51325, "remnant");
-        words.insert(51326, "remodeler");
-        words.insert(51331, "remold");
-        words.insert(51332, "remorse");
-        words.insert(51333, "remote");
-        words.insert(51334, "removable");
-        words.insert(51335, "removal");
-        words.insert(51336, "removed");
-        words.insert(51341, "remover");
-        words.insert(51342, "removing");
-        words.insert(51343, "rename");
-        words.insert(51344, "renderer");
-        words.insert(51345, "rendering");
-        words.insert(51346, "rendition");
-        words.insert(51351, "renegade");
-        words.insert(51352, "renewable");
-        words.insert(51353, "renewably");
-        words.insert(51354, "renewal");
-        words.insert(51355, "renewed");
-        words.insert(51356, "renounce");
-        words.insert(51361, "renovate");
-        words.insert(51362, "renovator");
-        words.insert(51363, "rentable");
-        words.insert(51364, "rental");
-        words.insert(51365, "rented");
-        words.insert(51366, "renter");
-        words.insert(51411, "reoccupy");
-        words.insert(51412, "reoccur");
-        words.insert(51413, "reopen");
-        words.insert(51414, "reorder");
-        words.insert(51415, "repackage");
-        words.insert(51416, "repacking");
-        words.insert(51421, "repaint");
-        words.insert(51422, "repair");
-        words.insert(51423, "repave");
-        words.insert(51424, "repaying");
-        words.insert(51425, "repayment");
-        words.insert(51426, "repeal");
-        words.insert(51431, "repeated");
-        words.insert(51432, "repeater");
-        words.insert(51433, "repent");
-        words.insert(51434, "rephrase");
-        words.insert(51435, "replace");
-        words.insert(51436, "replay");
-        words.insert(51441, "replica");
-        words.insert(51442, "reply");
-        words.insert(51443, "reporter");
-        words.insert(51444, "repose");
-        words.insert(51445, "repossess");
-        words.insert(51446, "repost");
-        words.insert(51451, "repressed");
-        words.insert(51452, "reprimand");
-        words.insert(51453, "reprint");
-        words.insert(51454, "reprise");
-        words.insert(51455, "reproach");
-        words.insert(51456, "reprocess");
-        words.insert(51461, "reproduce");
-        words.insert(51462, "reprogram");
-        words.insert(51463, "reps");
-        words.insert(51464, "reptile");
-        words.insert(51465, "reptilian");
-        words.insert(51466, "repugnant");
-        words.insert(51511, "repulsion");
-        words.insert(51512, "repulsive");
-        words.insert(51513, "repurpose");
-        words.insert(51514, "reputable");
-        words.insert(51515, "reputably");
-        words.insert(51516, "request");
-        words.insert(51521, "require");
-        words.insert(51522, "requisite");
-        words.insert(51523, "reroute");
-        words.insert(51524, "rerun");
-        words.insert(51525, "resale");
-        words.insert(51526, "resample");
-        words.insert(51531, "rescuer");
-        words.insert(51532, "reseal");
-        words.insert(51533, "research");
-        words.insert(51534, "reselect");
-        words.insert(51535, "reseller");
-        words.insert(51536, "resemble");
-        words.insert(51541, "resend");
-        words.insert(51542, "resent");
-        words.insert(51543, "reset");
-        words.insert(51544, "reshape");
-        words.insert(51545, "reshoot");
-        words.insert(51546, "reshuffle");
-        words.insert(51551, "residence");
-        words.insert(51552, "residency");
-        words.insert(51553, "resident");
-        words.insert(51554, "residual");
-        words.insert(51555, "residue");
-        words.insert(51556, "resigned");
-        words.insert(51561, "resilient");
-        words.insert(51562, "resistant");
-        words.insert(51563, "resisting");
-        words.insert(51564, "resize");
-        words.insert(51565, "resolute");
-        words.insert(51566, "resolved");
-        words.insert(51611, "resonant");
-        words.insert(51612, "resonate");
-        words.insert(51613, "resort");
-        words.insert(51614, "resource");
-        words.insert(51615, "respect");
-        words.insert(51616, "resubmit");
-        words.insert(51621, "result");
-        words.insert(51622, "resume");
-        words.insert(51623, "resupply");
-        words.insert(51624, "resurface");
-        words.insert(51625, "resurrect");
-        words.insert(51626, "retail");
-        words.insert(51631, "retainer");
-        words.insert(51632, "retaining");
-        words.insert(51633, "retake");
-        words.insert(51634, "retaliate");
-        words.insert(51635, "retention");
-        words.insert(51636, "rethink");
-        words.insert(51641, "retinal");
-        words.insert(51642, "retired");
-        words.insert(51643, "retiree");
-        words.insert(51644, "retiring");
-        words.insert(51645, "retold");
-        words.insert(51646, "retool");
-        words.insert(51651, "retorted");
-        words.insert(51652, "retouch");
-        words.insert(51653, "retrace");
-        words.insert(51654, "retract");
-        words.insert(51655, "retrain");
-        words.insert(51656, "retread");
-        words.insert(51661, "retreat");
-        words.insert(51662, "retrial");
-        words.insert(51663, "retrieval");
-        words.insert(51664, "retriever");
-        words.insert(51665, "retry");
-        words.insert(51666, "return");
-        words.insert(52111, "retying");
-        words.insert(52112, "retype");
-        words.insert(52113, "reunion");
-        words.insert(52114, "reunite");
-        words.insert(52115, "reusable");
-        words.insert(52116, "reuse");
-        words.insert(52121, "reveal");
-        words.insert(52122, "reveler");
-        words.insert(52123, "revenge");
-        words.insert(52124, "revenue");
-        words.insert(52125, "reverb");
-        words.insert(52126, "revered");
-        words.insert(52131, "reverence");
-        words.insert(52132, "reverend");
-        words.insert(52133, "reversal");
-        words.insert(52134, "reverse");
-        words.insert(52135, "reversing");
-        words.insert(52136, "reversion");
-        words.insert(52141, "revert");
-        words.insert(52142, "revisable");
-        words.insert(52143, "revise");
-        words.insert(52144, "revision");
-        words.insert(52145, "revisit");
-        words.insert(52146, "revivable");
-        words.insert(52151, "revival");
-        words.insert(52152, "reviver");
-        words.insert(52153, "reviving");
-        words.insert(52154, "revocable");
-        words.insert(52155, "revoke");
-        words.insert(52156, "revolt");
-        words.insert(52161, "revolver");
-        words.insert(52162, "revolving");
-        words.insert(52163, "reward");
-        words.insert(52164, "rewash");
-        words.insert(52165, "rewind");
-        words.insert(52166, "rewire");
-        words.insert(52211, "reword");
-        words.insert(52212, "rework");
-        words.insert(52213, "rewrap");
-        words.insert(52214, "rewrite");
-        words.insert(52215, "rhyme");
-        words.insert(52216, "ribbon");
-        words.insert(52221, "ribcage");
-        words.insert(52222, "rice");
-        words.insert(52223, "riches");
-        words.insert(52224, "richly");
-        words.insert(52225, "richness");
-        words.insert(52226, "rickety");
-        words.insert(52231, "ricotta");
-        words.insert(52232, "riddance");
-        words.insert(52233, "ridden");
-        words.insert(52234, "ride");
-        words.insert(52235, "riding");
-        words.insert(52236, "rifling");
-        words.insert(52241, "rift");
-        words.insert(52242, "rigging");
-        words.insert(52243, "rigid");
-        words.insert(52244, "rigor");
-        words.insert(52245, "rimless");
-        words.insert(52246, "rimmed");
-        words.insert(52251, "rind");
-        words.insert(52252, "rink");
-        words.insert(52253, "rinse");
-        words.insert(52254, "rinsing");
-        words.insert(52255, "riot");
-        words.insert(52256, "ripcord");
-        words.insert(52261, "ripeness");
-        words.insert(52262, "ripening");
-        words.insert(52263, "ripping");
-        words.insert(52264, "ripple");
-        words.insert(52265, "rippling");
-        words.insert(52266, "riptide");
-        words.insert(52311, "rise");
-        words.insert(52312, "rising");
-        words.insert(52313, "risk");
-        words.insert(52314, "risotto");
-        words.insert(52315, "ritalin");
-        words.insert(52316, "ritzy");
-        words.insert(52321, "rival");
-        words.insert(52322, "riverbank");
-        words.insert(52323, "riverbed");
-        words.insert(52324, "riverboat");
-        words.insert(52325, "riverside");
-        words.insert(52326, "riveter");
-        words.insert(52331, "riveting");
-        words.insert(52332, "roamer");
-        words.insert(52333, "roaming");
-        words.insert(52334, "roast");
-        words.insert(52335, "robbing");
-        words.insert(52336, "robe");
-        words.insert(52341, "robin");
-        words.insert(52342, "robotics");
-        words.insert(52343, "robust");
-        words.insert(52344, "rockband");
-        words.insert(52345, "rocker");
-        words.insert(52346, "rocket");
-        words.insert(52351, "rockfish");
-        words.insert(52352, "rockiness");
-        words.insert(52353, "rocking");
-        words.insert(52354, "rocklike");
-        words.insert(52355, "rockslide");
-        words.insert(52356, "rockstar");
-        words.insert(52361, "rocky");
-        words.insert(52362, "rogue");
-        words.insert(52363, "roman");
-        words.insert(52364, "romp");
-        words.insert(52365, "rope");
-        words.insert(52366, "roping");
-        words.insert(52411, "roster");
-        words.insert(52412, "rosy");
-        words.insert(52413, "rotten");
-        words.insert(52414, "rotting");
-        words.insert(52415, "rotunda");
-        words.insert(52416, "roulette");
-        words.insert(52421, "rounding");
-        words.insert(52422, "roundish");
-        words.insert(52423, "roundness");
-        words.insert(52424, "roundup");
-        words.insert(52425, "roundworm");
-        words.insert(52426, "routine");
-        words.insert(52431, "routing");
-        words.insert(52432, "rover");
-        words.insert(52433, "roving");
-        words.insert(52434, "royal");
-        words.insert(52435, "rubbed");
-        words.insert(52436, "rubber");
-        words.insert(52441, "rubbing");
-        words.insert(52442, "rubble");
-        words.insert(52443, "rubdown");
-        words.insert(52444, "ruby");
-        words.insert(52445, "ruckus");
-        words.insert(52446, "rudder");
-        words.insert(52451, "rug");
-        words.insert(52452, "ruined");
-        words.insert(52453, "rule");
-        words.insert(52454, "rumble");
-        words.insert(52455, "rumbling");
-        words.insert(52456, "rummage");
-        words.insert(52461, "rumor");
-        words.insert(52462, "runaround");
-        words.insert(52463, "rundown");
-        words.insert(52464, "runner");
-        words.insert(52465, "running");
-        words.insert(52466, "runny");
-        words.insert(52511, "runt");
-        words.insert(52512, "runway");
-        words.insert(52513, "rupture");
-        words.insert(52514, "rural");
-        words.insert(52515, "ruse");
-        words.insert(52516, "rush");
-        words.insert(52521, "rust");
-        words.insert(52522, "rut");
-        words.insert(52523, "sabbath");
-        words.insert(52524, "sabotage");
-        words.insert(52525, "sacrament");
-        words.insert(52526, "sacred");
-        words.insert(52531, "sacrifice");
-        words.insert(52532, "sadden");
-        words.insert(52533, "saddlebag");
-        words.insert(52534, "saddled");
-        words.insert(52535, "saddling");
-        words.insert(52536, "sadly");
-        words.insert(52541, "sadness");
-        words.insert(52542, "safari");
-        words.insert(52543, "safeguard");
-        words.insert(52544, "safehouse");
-        words.insert(52545, "safely");
-        words.insert(52546, "safeness");
-        words.insert(52551, "saffron");
-        words.insert(52552, "saga");
-        words.insert(52553, "sage");
-        words.insert(52554, "sagging");
-        words.insert(52555, "saggy");
-        words.insert(52556, "said");
-        words.insert(52561, "saint");
-        words.insert(52562, "sake");
-        words.insert(52563, "salad");
-        words.insert(52564, "salami");
-        words.insert(52565, "salaried");
-        words.insert(52566, "salary");
-        words.insert(52611, "saline");
-        words.insert(52612, "salon");
-        words.insert(52613, "saloon");
-        words.insert(52614, "salsa");
-        words.insert(52615, "salt");
-        words.insert(52616, "salutary");
-        words.insert(52621, "salute");
-        words.insert(52622, "salvage");
-        words.insert(52623, "salvaging");
-        words.insert(52624, "salvation");
-        words.insert(52625, "same");
-        words.insert(52626, "sample");
-        words.insert(52631, "sampling");
-        words.insert(52632, "sanction");
-        words.insert(52633, "sanctity");
-        words.insert(52634, "sanctuary");
-        words.insert(52635, "sandal");
-        words.insert(52636, "sandbag");
-        words.insert(52641, "sandbank");
-        words.insert(52642, "sandbar");
-        words.insert(52643, "sandblast");
-        words.insert(52644, "sandbox");
-        words.insert(52645, "sanded");
-        words.insert(52646, "sandfish");
-        words.insert(52651, "sanding");
-        words.insert(52652, "sandlot");
-        words.insert(52653, "sandpaper");
-        words.insert(52654, "sandpit");
-        words.insert(52655, "sandstone");
-        words.insert(52656, "sandstorm");
-        words.insert(52661, "sandworm");
-        words.insert(52662, "sandy");
-        words.insert(52663, "sanitary");
-        words.insert(52664, "sanitizer");
-        words.insert(52665, "sank");
-        words.insert(52666, "santa");
-        words.insert(53111, "sapling");
-        words.insert(53112, "sappiness");
-        words.insert(53113, "sappy");
-        words.insert(53114, "sarcasm");
-        words.insert(53115, "sarcastic");
-        words.insert(53116, "sardine");
-        words.insert(53121, "sash");
-        words.insert(53122, "sasquatch");
-        words.insert(53123, "sassy");
-        words.insert(53124, "satchel");
-        words.insert(53125, "satiable");
-        words.insert(53126, "satin");
-        words.insert(53131, "satirical");
-        words.insert(53132, "satisfied");
-        words.insert(53133, "satisfy");
-        words.insert(53134, "saturate");
-        words.insert(53135, "saturday");
-        words.insert(53136, "sauciness");
-        words.insert(53141, "saucy");
-        words.insert(53142, "sauna");
-        words.insert(53143, "savage");
-        words.insert(53144, "savanna");
-        words.insert(53145, "saved");
-        words.insert(53146, "savings");
-        words.insert(53151, "savior");
-        words.insert(53152, "savor");
-        words.insert(53153, "saxophone");
-        words.insert(53154, "say");
-        words.insert(53155, "scabbed");
-        words.insert(53156, "scabby");
-        words.insert(53161, "scalded");
-        words.insert(53162, "scalding");
-        words.insert(53163, "scale");
-        words.insert(53164, "scaling");
-        words.insert(53165, "scallion");
-        words.insert(53166, "scallop");
-        words.insert(53211, "scalping");
-        words.insert(53212, "scam");
-        words.insert(53213, "scandal");
-        words.insert(53214, "scanner");
-        words.insert(53215, "scanning");
-        words.insert(53216, "scant");
-        words.insert(53221, "scapegoat");
-        words.insert(53222, "scarce");
-        words.insert(53223, "scarcity");
-        words.insert(53224, "scarecrow");
-        words.insert(53225, "scared");
-        words.insert(53226, "scarf");
-        words.insert(53231, "scarily");
-        words.insert(53232, "scariness");
-        words.insert(53233, "scarring");
-        words.insert(53234, "scary");
-        words.insert(53235, "scavenger");
-        words.insert(53236, "scenic");
-        words.insert(53241, "schedule");
-        words.insert(53242, "schematic");
-        words.insert(53243, "scheme");
-        words.insert(53244, "scheming");
-        words.insert(53245, "schilling");
-        words.insert(53246, "schnapps");
-        words.insert(53251, "scholar");
-        words.insert(53252, "science");
-        words.insert(53253, "scientist");
-        words.insert(53254, "scion");
-        words.insert(53255, "scoff");
-        words.insert(53256, "scolding");
-        words.insert(53261, "scone");
-        words.insert(53262, "scoop");
-        words.insert(53263, "scooter");
-        words.insert(53264, "scope");
-        words.insert(53265, "scorch");
-        words.insert(53266, "scorebook");
-        words.insert(53311, "scorecard");
-        words.insert(53312, "scored");
-        words.insert(53313, "scoreless");
-        words.insert(53314, "scorer");
-        words.insert(53315, "scoring");
-        words.insert(53316, "scorn");
-        words.insert(53321, "scorpion");
-        words.insert(53322, "scotch");
-        words.insert(53323, "scoundrel");
-        words.insert(53324, "scoured");
-        words.insert(53325, "scouring");
-        words.insert(53326, "scouting");
-        words.insert(53331, "scouts");
-        words.insert(53332, "scowling");
-        words.insert(53333, "scrabble");
-        words.insert(53334, "scraggly");
-        words.insert(53335, "scrambled");
-        words.insert(53336, "scrambler");
-        words.insert(53341, "scrap");
-        words.insert(53342, "scratch");
-        words.insert(53343, "scrawny");
-        words.insert(53344, "screen");
-        words.insert(53345, "scribble");
-        words.insert(53346, "scribe");
-        words.insert(53351, "scribing");
-        words.insert(53352, "scrimmage");
-        words.insert(53353, "script");
-        words.insert(53354, "scroll");
-        words.insert(53355, "scrooge");
-        words.insert(53356, "scrounger");
-        words.insert(53361, "scrubbed");
-        words.insert(53362, "scrubber");
-        words.insert(53363, "scruffy");
-        words.insert(53364, "scrunch");
-        words.insert(53365, "scrutiny");
-        words.insert(53366, "scuba");
-        words.insert(53411, "scuff");
-        words.insert(53412, "sculptor");
-        words.insert(53413, "sculpture");
-        words.insert(53414, "scurvy");
-        words.insert(53415, "scuttle");
-        words.insert(53416, "secluded");
-        words.insert(53421, "secluding");
-        words.insert(53422, "seclusion");
-        words.insert(53423, "second");
-        words.insert(53424, "secrecy");
-        words.insert(53425, "secret");
-        words.insert(53426, "sectional");
-        words.insert(53431, "sector");
-        words.insert(53432, "secular");
-        words.insert(53433, "securely");
-        words.insert(53434, "security");
-        words.insert(53435, "sedan");
-        words.insert(53436, "sedate");
-        words.insert(53441, "sedation");
-        words.insert(53442, "sedative");
-        words.insert(53443, "sediment");
-        words.insert(53444, "seduce");
-        words.insert(53445, "seducing");
-        words.insert(53446, "segment");
-        words.insert(53451, "seismic");
-        words.insert(53452, "seizing");
-        words.insert(53453, "seldom");
-        words.insert(53454, "selected");
-        words.insert(53455, "selection");
-        words.insert(53456, "selective");
-        words.insert(53461, "selector");
-        words.insert(53462, "self");
-        words.insert(53463, "seltzer");
-        words.insert(53464, "semantic");
-        words.insert(53465, "semester");
-        words.insert(53466, "semicolon");
-        words.insert(53511, "semifinal");
-        words.insert(53512, "seminar");
-        words.insert(53513, "semisoft");
-        words.insert(53514, "semisweet");
-        words.insert(53515, "senate");
-        words.insert(53516, "senator");
-        words.insert(53521, "send");
-        words.insert(53522, "senior");
-        words.insert(53523, "senorita");
-        words.insert(53524, "sensation");
-        words.insert(53525, "sensitive");
-        words.insert(53526, "sensitize");
-        words.insert(53531, "sensually");
-        words.insert(53532, "sensuous");
-        words.insert(53533, "sepia");
-        words.insert(53534, "september");
-        words.insert(53535, "septic");
-        words.insert(53536, "septum");
-        words.insert(53541, "sequel");
-        words.insert(53542, "sequence");
-        words.insert(53543, "sequester");
-        words.insert(53544, "series");
-        words.insert(53545, "sermon");
-        words.insert(53546, "serotonin");
-        words.insert(53551, "serpent");
-        words.insert(53552, "serrated");
-        words.insert(53553, "serve");
-        words.insert(53554, "service");
-        words.insert(53555, "serving");
-        words.insert(53556, "sesame");
-        words.insert(53561, "sessions");
-        words.insert(53562, "setback");
-        words.insert(53563, "setting");
-        words.insert(53564, "settle");
-        words.insert(53565, "settling");
-        words.insert(53566, "setup");
-        words.insert(53611, "sevenfold");
-        words.insert(53612, "seventeen");
-        words.insert(53613, "seventh");
-        words.insert(53614, "seventy");
-        words.insert(53615, "severity");
-        words.insert(53616, "shabby");
-        words.insert(53621, "shack");
-        words.insert(53622, "shaded");
-        words.insert(53623, "shadily");
-        words.insert(53624, "shadiness");
-        words.insert(53625, "shading");
-        words.insert(53626, "shadow");
-        words.insert(53631, "shady");
-        words.insert(53632, "shaft");
-        words.insert(53633, "shakable");
-        words.insert(53634, "shakily");
-        words.insert(53635, "shakiness");
-        words.insert(53636, "shaking");
-        words.insert(53641, "shaky");
-        words.insert(53642, "shale");
-        words.insert(53643, "shallot");
-        words.insert(53644, "shallow");
-        words.insert(53645, "shame");
-        words.insert(53646, "shampoo");
-        words.insert(53651, "shamrock");
-        words.insert(53652, "shank");
-        words.insert(53653, "shanty");
-        words.insert(53654, "shape");
-        words.insert(53655, "shaping");
-        words.insert(53656, "share");
-        words.insert(53661, "sharpener");
-        words.insert(53662, "sharper");
-        words.insert(53663, "sharpie");
-        words.insert(53664, "sharply");
-        words.insert(53665, "sharpness");
-        words.insert(53666, "shawl");
-        words.insert(54111, "sheath");
-        words.insert(54112, "shed");
-        words.insert(54113, "sheep");
-        words.insert(54114, "sheet");
-        words.insert(54115, "shelf");
-        words.insert(54116, "shell");
-        words.insert(54121, "shelter");
-        words.insert(54122, "shelve");
-        words.insert(54123, "shelving");
-        words.insert(54124, "sherry");
-        words.insert(54125, "shield");
-        words.insert(54126, "shifter");
-        words.insert(54131, "shifting");
-        words.insert(54132, "shiftless");
-        words.insert(54133, "shifty");
-        words.insert(54134, "shimmer");
-        words.insert(54135, "shimmy");
-        words.insert(54136, "shindig");
-        words.insert(54141, "shine");
-        words.insert(54142, "shingle");
-        words.insert(54143, "shininess");
-        words.insert(54144, "shining");
-        words.insert(54145, "shiny");
-        words.insert(54146, "ship");
-        words.insert(54151, "shirt");
-        words.insert(54152, "shivering");
-        words.insert(54153, "shock");
-        words.insert(54154, "shone");
-        words.insert(54155, "shoplift");
-        words.insert(54156, "shopper");
-        words.insert(54161, "shopping");
-        words.insert(54162, "shoptalk");
-        words.insert(54163, "shore");
-        words.insert(54164, "shortage");
-        words.insert(54165, "shortcake");
-        words.insert(54166, "shortcut");
-        words.insert(54211, "shorten");
-        words.insert(54212, "shorter");
-        words.insert(54213, "shorthand");
-        words.insert(54214, "shortlist");
-        words.insert(54215, "shortly");
-        words.insert(54216, "shortness");
-        words.insert(54221, "shorts");
-        words.insert(54222, "shortwave");
-        words.insert(54223, "shorty");
-        words.insert(54224, "shout");
-        words.insert(54225, "shove");
-        words.insert(54226, "showbiz");
-        words.insert(54231, "showcase");
-        words.insert(54232, "showdown");
-        words.insert(54233, "shower");
-        words.insert(54234, "showgirl");
-        words.insert(54235, "showing");
-        words.insert(54236, "showman");
-        words.insert(54241, "shown");
-        words.insert(54242, "showoff");
-        words.insert(54243, "showpiece");
-        words.insert(54244, "showplace");
-        words.insert(54245, "showroom");
-        words.insert(54246, "showy");
-        words.insert(54251, "shrank");
-        words.insert(54252, "shrapnel");
-        words.insert(54253, "shredder");
-        words.insert(54254, "shredding");
-        words.insert(54255, "shrewdly");
-        words.insert(54256, "shriek");
-        words.insert(54261, "shrill");
-        words.insert(54262, "shrimp");
-        words.insert(54263, "shrine");
-        words.insert(54264, "shrink");
-        words.insert(54265, "shrivel");
-        words.insert(54266, "shrouded");
-        words.insert(54311, "shrubbery");
-        words.insert(54312, "shrubs");
-        words.insert(54313, "shrug");
-        words.insert(54314, "shrunk");
-        words.insert(54315, "shucking");
-        words.insert(54316, "shudder");
-        words.insert(54321, "shuffle");
-        words.insert(54322, "shuffling");
-        words.insert(54323, "shun");
-        words.insert(54324, "shush");
-        words.insert(54325, "shut");
-        words.insert(54326, "shy");
-        words.insert(54331, "siamese");
-        words.insert(54332, "siberian");
-        words.insert(54333, "sibling");
-        words.insert(54334, "siding");
-        words.insert(54335, "sierra");
-        words.insert(54336, "siesta");
-        words.insert(54341, "sift");
-        words.insert(54342, "sighing");
-        words.insert(54343, "silenced");
-        words.insert(54344, "silencer");
-        words.insert(54345, "silent");
-        words.insert(54346, "silica");
-        words.insert(54351, "silicon");
-        words.insert(54352, "silk");
-        words.insert(54353, "silliness");
-        words.insert(54354, "silly");
-        words.insert(54355, "silo");
-        words.insert(54356, "silt");
-        words.insert(54361, "silver");
-        words.insert(54362, "similarly");
-        words.insert(54363, "simile");
-        words.insert(54364, "simmering");
-        words.insert(54365, "simple");
-        words.insert(54366, "simplify");
-        words.insert(54411, "simply");
-        words.insert(54412, "sincere");
-        words.insert(54413, "sincerity");
-        words.insert(54414, "singer");
-        words.insert(54415, "singing");
-        words.insert(54416, "single");
-        words.insert(54421, "singular");
-        words.insert(54422, "sinister");
-        words.insert(54423, "sinless");
-        words.insert(54424, "sinner");
-        words.insert(54425, "sinuous");
-        words.insert(54426, "sip");
-        words.insert(54431, "siren");
-        words.insert(54432, "sister");
-        words.insert(54433, "sitcom");
-        words.insert(54434, "sitter");
-        words.insert(54435, "sitting");
-        words.insert(54436, "situated");
-        words.insert(54441, "situation");
-        words.insert(54442, "sixfold");
-        words.insert(54443, "sixteen");
-        words.insert(54444, "sixth");
-        words.insert(54445, "sixties");
-        words.insert(54446, "sixtieth");
-        words.insert(54451, "sixtyfold");
-        words.insert(54452, "sizable");
-        words.insert(54453, "sizably");
-        words.insert(54454, "size");
-        words.insert(54455, "sizing");
-        words.insert(54456, "sizzle");
-        words.insert(54461, "sizzling");
-        words.insert(54462, "skater");
-        words.insert(54463, "skating");
-        words.insert(54464, "skedaddle");
-        words.insert(54465, "skeletal");
-        words.insert(54466, "skeleton");
-        words.insert(54511, "skeptic");
-        words.insert(54512, "sketch");
-        words.insert(54513, "skewed");
-        words.insert(54514, "skewer");
-        words.insert(54515, "skid");
-        words.insert(54516, "skied");
-        words.insert(54521, "skier");
-        words.insert(54522, "skies");
-        words.insert(54523, "skiing");
-        words.insert(54524, "skilled");
-        words.insert(54525, "skillet");
-        words.insert(54526, "skillful");
-        words.insert(54531, "skimmed");
-        words.insert(54532, "skimmer");
-        words.insert(54533, "skimming");
-        words.insert(54534, "skimpily");
-        words.insert(54535, "skincare");
-        words.insert(54536, "skinhead");
-        words.insert(54541, "skinless");
-        words.insert(54542, "skinning");
-        words.insert(54543, "skinny");
-        words.insert(54544, "skintight");
-        words.insert(54545, "skipper");
-        words.insert(54546, "skipping");
-        words.insert(54551, "skirmish");
-        words.insert(54552, "skirt");
-        words.insert(54553, "skittle");
-        words.insert(54554, "skydiver");
-        words.insert(54555, "skylight");
-        words.insert(54556, "skyline");
-        words.insert(54561, "skype");
-        words.insert(54562, "skyrocket");
-        words.insert(54563, "skyward");
-        words.insert(54564, "slab");
-        words.insert(54565, "slacked");
-        words.insert(54566, "slacker");
-        words.insert(54611, "slacking");
-        words.insert(54612, "slackness");
-        words.insert(54613, "slacks");
-        words.insert(54614, "slain");
-        words.insert(54615, "slam");
-        words.insert(54616, "slander");
-        words.insert(54621, "slang");
-        words.insert(54622, "slapping");
-        words.insert(54623, "slapstick");
-        words.insert(54624, "slashed");
-        words.insert(54625, "slashing");
-        words.insert(54626, "slate");
-        words.insert(54631, "slather");
-        words.insert(54632, "slaw");
-        words.insert(54633, "sled");
-        words.insert(54634, "sleek");
-        words.insert(54635, "sleep");
-        words.insert(54636, "sleet");
-        words.insert(54641, "sleeve");
-        words.insert(54642, "slept");
-        words.insert(54643, "sliceable");
-        words.insert(54644, "sliced");
-        words.insert(54645, "slicer");
-        words.insert(54646, "slicing");
-        words.insert(54651, "slick");
-        words.insert(54652, "slider");
-        words.insert(54653, "slideshow");
-        words.insert(54654, "sliding");
-        words.insert(54655, "slighted");
-        words.insert(54656, "slighting");
-        words.insert(54661, "slightly");
-        words.insert(54662, "slimness");
-        words.insert(54663, "slimy");
-        words.insert(54664, "slinging");
-        words.insert(54665, "slingshot");
-        words.insert(54666, "slinky");
-        words.insert(55111, "slip");
-        words.insert(55112, "slit");
-        words.insert(55113, "sliver");
-        words.insert(55114, "slobbery");
-        words.insert(55115, "slogan");
-        words.insert(55116, "sloped");
-        words.insert(55121, "sloping");
-        words.insert(55122, "sloppily");
-        words.insert(55123, "sloppy");
-        words.insert(55124, "slot");
-        words.insert(55125, "slouching");
-        words.insert(55126, "slouchy");
-        words.insert(55131, "sludge");
-        words.insert(55132, "slug");
-        words.insert(55133, "slum");
-        words.insert(55134, "slurp");
-        words.insert(55135, "slush");
-        words.insert(55136, "sly");
-        words.insert(55141, "small");
-        words.insert(55142, "smartly");
-        words.insert(55143, "smartness");
-        words.insert(55144, "smasher");
-        words.insert(55145, "smashing");
-        words.insert(55146, "smashup");
-        words.insert(55151, "smell");
-        words.insert(55152, "smelting");
-        words.insert(55153, "smile");
-        words.insert(55154, "smilingly");
-        words.insert(55155, "smirk");
-        words.insert(55156, "smite");
-        words.insert(55161, "smith");
-        words.insert(55162, "smitten");
-        words.insert(55163, "smock");
-        words.insert(55164, "smog");
-        words.insert(55165, "smoked");
-        words.insert(55166, "smokeless");
-        words.insert(55211, "smokiness");
-        words.insert(55212, "smoking");
-        words.insert(55213, "smoky");
-        words.insert(55214, "smolder");
-        words.insert(55215, "smooth");
-        words.insert(55216, "smother");
-        words.insert(55221, "smudge");
-        words.insert(55222, "smudgy");
-        words.insert(55223, "smuggler");
-        words.insert(55224, "smuggling");
-        words.insert(55225, "smugly");
-        words.insert(55226, "smugness");
-        words.insert(55231, "snack");
-        words.insert(55232, "snagged");
-        words.insert(55233, "snaking");
-        words.insert(55234, "snap");
-        words.insert(55235, "snare");
-        words.insert(55236, "snarl");
-        words.insert(55241, "snazzy");
-        words.insert(55242, "sneak");
-        words.insert(55243, "sneer");
-        words.insert(55244, "sneeze");
-        words.insert(55245, "sneezing");
-        words.insert(55246, "snide");
-        words.insert(55251, "sniff");
-        words.insert(55252, "snippet");
-        words.insert(55253, "snipping");
-        words.insert(55254, "snitch");
-        words.insert(55255, "snooper");
-        words.insert(55256, "snooze");
-        words.insert(55261, "snore");
-        words.insert(55262, "snoring");
-        words.insert(55263, "snorkel");
-        words.insert(55264, "snort");
-        words.insert(55265, "snout");
-        words.insert(55266, "snowbird");
-        words.insert(55311, "snowboard");
-        words.insert(55312, "snowbound");
-        words.insert(55313, "snowcap");
-        words.insert(55314, "snowdrift");
-        words.insert(55315, "snowdrop");
-        words.insert(55316, "snowfall");
-        words.insert(55321, "snowfield");
-        words.insert(55322, "snowflake");
-        words.insert(55323, "snowiness");
-        words.insert(55324, "snowless");
-        words.insert(55325, "snowman");
-        words.insert(55326, "snowplow");
-        words.insert(55331, "snowshoe");
-        words.insert(55332, "snowstorm");
-        words.insert(55333, "snowsuit");
-        words.insert(55334, "snowy");
-        words.insert(55335, "snub");
-        words.insert(55336, "snuff");
-        words.insert(55341, "snuggle");
-        words.insert(55342, "snugly");
-        words.insert(55343, "snugness");
-        words.insert(55344, "speak");
-        words.insert(55345, "spearfish");
-        words.insert(55346, "spearhead");
-        words.insert(55351, "spearman");
-        words.insert(55352, "spearmint");
-        words.insert(55353, "species");
-        words.insert(55354, "specimen");
-        words.insert(55355, "specked");
-        words.insert(55356, "speckled");
-        words.insert(55361, "specks");
-        words.insert(55362, "spectacle");
-        words.insert(55363, "spectator");
-        words.insert(55364, "spectrum");
-        words.insert(55365, "speculate");
-        words.insert(55366, "speech");
-        words.insert(55411, "speed");
-        words.insert(55412, "spellbind");
-        words.insert(55413, "speller");
-        words.insert(55414, "spelling");
-        words.insert(55415, "spendable");
-        words.insert(55416, "spender");
-        words.insert(55421, "spending");
-        words.insert(55422, "spent");
-        words.insert(55423, "spew");
-        words.insert(55424, "sphere");
-        words.insert(55425, "spherical");
-        words.insert(55426, "sphinx");
-        words.insert(55431, "spider");
-        words.insert(55432, "spied");
-        words.insert(55433, "spiffy");
-        words.insert(55434, "spill");
-        words.insert(55435, "spilt");
-        words.insert(55436, "spinach");
-        words.insert(55441, "spinal");
-        words.insert(55442, "spindle");
-        words.insert(55443, "spinner");
-        words.insert(55444, "spinning");
-        words.insert(55445, "spinout");
-        words.insert(55446, "spinster");
-        words.insert(55451, "spiny");
-        words.insert(55452, "spiral");
-        words.insert(55453, "spirited");
-        words.insert(55454, "spiritism");
-        words.insert(55455, "spirits");
-        words.insert(55456, "spiritual");
-        words.insert(55461, "splashed");
-        words.insert(55462, "splashing");
-        words.insert(55463, "splashy");
-        words.insert(55464, "splatter");
-        words.insert(55465, "spleen");
-        words.insert(55466, "splendid");
-        words.insert(55511, "splendor");
-        words.insert(55512, "splice");
-        words.insert(55513, "splicing");
-        words.insert(55514, "splinter");
-        words.insert(55515, "splotchy");
-        words.insert(55516, "splurge");
-        words.insert(55521, "spoilage");
-        words.insert(55522, "spoiled");
-        words.insert(55523, "spoiler");
-        words.insert(55524, "spoiling");
-        words.insert(55525, "spoils");
-        words.insert(55526, "spoken");
-        words.insert(55531, "spokesman");
-        words.insert(55532, "sponge");
-        words.insert(55533, "spongy");
-        words.insert(55534, "sponsor");
-        words.insert(55535, "spoof");
-        words.insert(55536, "spookily");
-        words.insert(55541, "spooky");
-        words.insert(55542, "spool");
-        words.insert(55543, "spoon");
-        words.insert(55544, "spore");
-        words.insert(55545, "sporting");
-        words.insert(55546, "sports");
-        words.insert(55551, "sporty");
-        words.insert(55552, "spotless");
-        words.insert(55553, "spotlight");
-        words.insert(55554, "spotted");
-        words.insert(55555, "spotter");
-        words.insert(55556, "spotting");
-        words.insert(55561, "spotty");
-        words.insert(55562, "spousal");
-        words.insert(55563, "spouse");
-        words.insert(55564, "spout");
-        words.insert(55565, "sprain");
-        words.insert(55566, "sprang");
-        words.insert(55611, "sprawl");
-        words.insert(55612, "spray");
-        words.insert(55613, "spree");
-        words.insert(55614, "sprig");
-        words.insert(55615, "spring");
-        words.insert(55616, "sprinkled");
-        words.insert(55621, "sprinkler");
-        words.insert(55622, "sprint");
-        words.insert(55623, "sprite");
-        words.insert(55624, "sprout");
-        words.insert(55625, "spruce");
-        words.insert(55626, "sprung");
-        words.insert(55631, "spry");
-        words.insert(55632, "spud");
-        words.insert(55633, "spur");
-        words.insert(55634, "sputter");
-        words.insert(55635, "spyglass");
-        words.insert(55636, "squabble");
-        words.insert(55641, "squad");
-        words.insert(55642, "squall");
-        words.insert(55643, "squander");
-        words.insert(55644, "squash");
-        words.insert(55645, "squatted");
-        words.insert(55646, "squatter");
-        words.insert(55651, "squatting");
-        words.insert(55652, "squeak");
-        words.insert(55653, "squealer");
-        words.insert(55654, "squealing");
-        words.insert(55655, "squeamish");
-        words.insert(55656, "squeegee");
-        words.insert(55661, "squeeze");
-        words.insert(55662, "squeezing");
-        words.insert(55663, "squid");
-        words.insert(55664, "squiggle");
-        words.insert(55665, "squiggly");
-        words.insert(55666, "squint");
-        words.insert(56111, "squire");
-        words.insert(56112, "squirt");
-        words.insert(56113, "squishier");
-        words.insert(56114, "squishy");
-        words.insert(56115, "stability");
-        words.insert(56116, "stabilize");
-        words.insert(56121, "stable");
-        words.insert(56122, "stack");
-        words.insert(56123, "stadium");
-        words.insert(56124, "staff");
-        words.insert(56125, "stage");
-        words.insert(56126, "staging");
-        words.insert(56131, "stagnant");
-        words.insert(56132, "stagnate");
-        words.insert(56133, "stainable");
-        words.insert(56134, "stained");
-        words.insert(56135, "staining");
-        words.insert(56136, "stainless");
-        words.insert(56141, "stalemate");
-        words.insert(56142, "staleness");
-        words.insert(56143, "stalling");
-        words.insert(56144, "stallion");
-        words.insert(56145, "stamina");
-        words.insert(56146, "stammer");
-        words.insert(56151, "stamp");
-        words.insert(56152, "stand");
-        words.insert(56153, "stank");
-        words.insert(56154, "staple");
-        words.insert(56155, "stapling");
-        words.insert(56156, "starboard");
-        words.insert(56161, "starch");
-        words.insert(56162, "stardom");
-        words.insert(56163, "stardust");
-        words.insert(56164, "starfish");
-        words.insert(56165, "stargazer");
-        words.insert(56166, "staring");
-        words.insert(56211, "stark");
-        words.insert(56212, "starless");
-        words.insert(56213, "starlet");
-        words.insert(56214, "starlight");
-        words.insert(56215, "starlit");
-        words.insert(56216, "starring");
-        words.insert(56221, "starry");
-        words.insert(56222, "starship");
-        words.insert(56223, "starter");
-        words.insert(56224, "starting");
-        words.insert(56225, "startle");
-        words.insert(56226, "startling");
-        words.insert(56231, "startup");
-        words.insert(56232, "starved");
-        words.insert(56233, "starving");
-        words.insert(56234, "stash");
-        words.insert(56235, "state");
-        words.insert(56236, "static");
-        words.insert(56241, "statistic");
-        words.insert(56242, "statue");
-        words.insert(56243, "stature");
-        words.insert(56244, "status");
-        words.insert(56245, "statute");
-        words.insert(56246, "statutory");
-        words.insert(56251, "staunch");
-        words.insert(56252, "stays");
-        words.insert(56253, "steadfast");
-        words.insert(56254, "steadier");
-        words.insert(56255, "steadily");
-        words.insert(56256, "steadying");
-        words.insert(56261, "steam");
-        words.insert(56262, "steed");
-        words.insert(56263, "steep");
-        words.insert(56264, "steerable");
-        words.insert(56265, "steering");
-        words.insert(56266, "steersman");
-        words.insert(56311, "stegosaur");
-        words.insert(56312, "stellar");
-        words.insert(56313, "stem");
-        words.insert(56314, "stench");
-        words.insert(56315, "stencil");
-        words.insert(56316, "step");
-        words.insert(56321, "stereo");
-        words.insert(56322, "sterile");
-        words.insert(56323, "sterility");
-        words.insert(56324, "sterilize");
-        words.insert(56325, "sterling");
-        words.insert(56326, "sternness");
-        words.insert(56331, "sternum");
-        words.insert(56332, "stew");
-        words.insert(56333, "stick");
-        words.insert(56334, "stiffen");
-        words.insert(56335, "stiffly");
-        words.insert(56336, "stiffness");
-        words.insert(56341, "stifle");
-        words.insert(56342, "stifling");
-        words.insert(56343, "stillness");
-        words.insert(56344, "stilt");
-        words.insert(56345, "stimulant");
-        words.insert(56346, "stimulate");
-        words.insert(56351, "stimuli");
-        words.insert(56352, "stimulus");
-        words.insert(56353, "stinger");
-        words.insert(56354, "stingily");
-        words.insert(56355, "stinging");
-        words.insert(56356, "stingray");
-        words.insert(56361, "stingy");
-        words.insert(56362, "stinking");
-        words.insert(56363, "stinky");
-        words.insert(56364, "stipend");
-        words.insert(56365, "stipulate");
-        words.insert(56366, "stir");
-        words.insert(56411, "stitch");
-        words.insert(56412, "stock");
-        words.insert(56413, "stoic");
-        words.insert(56414, "stoke");
-        words.insert(56415, "stole");
-        words.insert(56416, "stomp");
-        words.insert(56421, "stonewall");
-        words.insert(56422, "stoneware");
-        words.insert(56423, "stonework");
-        words.insert(56424, "stoning");
-        words.insert(56425, "stony");
-        words.insert(56426, "stood");
-        words.insert(56431, "stooge");
-        words.insert(56432, "stool");
-        words.insert(56433, "stoop");
-        words.insert(56434, "stoplight");
-        words.insert(56435, "stoppable");
-        words.insert(56436, "stoppage");
-        words.insert(56441, "stopped");
-        words.insert(56442, "stopper");
-        words.insert(56443, "stopping");
-        words.insert(56444, "stopwatch");
-        words.insert(56445, "storable");
-        words.insert(56446, "storage");
-        words.insert(56451, "storeroom");
-        words.insert(56452, "storewide");
-        words.insert(56453, "storm");
-        words.insert(56454, "stout");
-        words.insert(56455, "stove");
-        words.insert(56456, "stowaway");
-        words.insert(56461, "stowing");
-        words.insert(56462, "straddle");
-        words.insert(56463, "straggler");
-        words.insert(56464, "strained");
-        words.insert(56465, "strainer");
-        words.insert(56466, "straining");
-        words.insert(56511, "strangely");
-        words.insert(56512, "stranger");
-        words.insert(56513, "strangle");
-        words.insert(56514, "strategic");
-        words.insert(56515, "strategy");
-        words.insert(56516, "stratus");
-        words.insert(56521, "straw");
-        words.insert(56522, "stray");
-        words.insert(56523, "streak");
-        words.insert(56524, "stream");
-        words.insert(56525, "street");
-        words.insert(56526, "strength");
-        words.insert(56531, "strenuous");
-        words.insert(56532, "strep");
-        words.insert(56533, "stress");
-        words.insert(56534, "stretch");
-        words.insert(56535, "strewn");
-        words.insert(56536, "stricken");
-        words.insert(56541, "strict");
-        words.insert(56542, "stride");
-        words.insert(56543, "strife");
-        words.insert(56544, "strike");
-        words.insert(56545, "striking");
-        words.insert(56546, "strive");
-        words.insert(56551, "striving");
-        words.insert(56552, "strobe");
-        words.insert(56553, "strode");
-        words.insert(56554, "stroller");
-        words.insert(56555, "strongbox");
-        words.insert(56556, "strongly");
-        words.insert(56561, "strongman");
-        words.insert(56562, "struck");
-        words.insert(56563, "structure");
-        words.insert(56564, "strudel");
-        words.insert(56565, "struggle");
-        words.insert(56566, "strum");
-        words.insert(56611, "strung");
-        words.insert(56612, "strut");
-        words.insert(56613, "stubbed");
-        words.insert(56614, "stubble");
-        words.insert(56615, "stubbly");
-        words.insert(56616, "stubborn");
-        words.insert(56621, "stucco");
-        words.insert(56622, "stuck");
-        words.insert(56623, "student");
-        words.insert(56624, "studied");
-        words.insert(56625, "studio");
-        words.insert(56626, "study");
-        words.insert(56631, "stuffed");
-        words.insert(56632, "stuffing");
-        words.insert(56633, "stuffy");
-        words.insert(56634, "stumble");
-        words.insert(56635, "stumbling");
-        words.insert(56636, "stump");
-        words.insert(56641, "stung");
-        words.insert(56642, "stunned");
-        words.insert(56643, "stunner");
-        words.insert(56644, "stunning");
-        words.insert(56645, "stunt");
-        words.insert(56646, "stupor");
-        words.insert(56651, "sturdily");
-        words.insert(56652, "sturdy");
-        words.insert(56653, "styling");
-        words.insert(56654, "stylishly");
-        words.insert(56655, "stylist");
-        words.insert(56656, "stylized");
-        words.insert(56661, "stylus");
-        words.insert(56662, "suave");
-        words.insert(56663, "subarctic");
-        words.insert(56664, "subatomic");
-        words.insert(56665, "subdivide");
-        words.insert(56666, "subdued");
-        words.insert(61111, "subduing");
-        words.insert(61112, "subfloor");
-        words.insert(61113, "subgroup");
-        words.insert(61114, "subheader");
-        words.insert(61115, "subject");
-        words.insert(61116, "sublease");
-        words.insert(61121, "sublet");
-        words.insert(61122, "sublevel");
-        words.insert(61123, "sublime");
-        words.insert(61124, "submarine");
-        words.insert(61125, "submerge");
-        words.insert(61126, "submersed");
-        words.insert(61131, "submitter");
-        words.insert(61132, "subpanel");
-        words.insert(61133, "subpar");
-        words.insert(61134, "subplot");
-        words.insert(61135, "subprime");
-        words.insert(61136, "subscribe");
-        words.insert(61141, "subscript");
-        words.insert(61142, "subsector");
-        words.insert(61143, "subside");
-        words.insert(61144, "subsiding");
-        words.insert(61145, "subsidize");
-        words.insert(61146, "subsidy");
-        words.insert(61151, "subsoil");
-        words.insert(61152, "subsonic");
-        words.insert(61153, "substance");
-        words.insert(61154, "subsystem");
-        words.insert(61155, "subtext");
-        words.insert(61156, "subtitle");
-        words.insert(61161, "subtly");
-        words.insert(61162, "subtotal");
-        words.insert(61163, "subtract");
-        words.insert(61164, "subtype");
-        words.insert(61165, "suburb");
-        words.insert(61166, "subway");
-        words.insert(61211, "subwoofer");
-        words.insert(61212, "subzero");
-        words.insert(61213, "succulent");
-        words.insert(61214, "such");
-        words.insert(61215, "suction");
-        words.insert(61216, "sudden");
-        words.insert(61221, "sudoku");
-        words.insert(61222, "suds");
-        words.insert(61223, "sufferer");
-        words.insert(61224, "suffering");
-        words.insert(61225, "suffice");
-        words.insert(61226, "suffix");
-        words.insert(61231, "suffocate");
-        words.insert(61232, "suffrage");
-        words.insert(61233, "sugar");
-        words.insert(61234, "suggest");
-        words.insert(61235, "suing");
-        words.insert(61236, "suitable");
-        words.insert(61241, "suitably");
-        words.insert(61242, "suitcase");
-        words.insert(61243, "suitor");
-        words.insert(61244, "sulfate");
-        words.insert(61245, "sulfide");
-        words.insert(61246, "sulfite");
-        words.insert(61251, "sulfur");
-        words.insert(61252, "sulk");
-        words.insert(61253, "sullen");
-        words.insert(61254, "sulphate");
-        words.insert(61255, "sulphuric");
-        words.insert(61256, "sultry");
-        words.insert(61261, "superbowl");
-        words.insert(61262, "superglue");
-        words.insert(61263, "superhero");
-        words.insert(61264, "superior");
-        words.insert(61265, "superjet");
-        words.insert(61266, "superman");
-        words.insert(61311, "supermom");
-        words.insert(61312, "supernova");
-        words.insert(61313, "supervise");
-        words.insert(61314, "supper");
-        words.insert(61315, "supplier");
-        words.insert(61316, "supply");
-        words.insert(61321, "support");
-        words.insert(61322, "supremacy");
-        words.insert(61323, "supreme");
-        words.insert(61324, "surcharge");
-        words.insert(61325, "surely");
-        words.insert(61326, "sureness");
-        words.insert(61331, "surface");
-        words.insert(61332, "surfacing");
-        words.insert(61333, "surfboard");
-        words.insert(61334, "surfer");
-        words.insert(61335, "surgery");
-        words.insert(61336, "surgical");
-        words.insert(61341, "surging");
-        words.insert(61342, "surname");
-        words.insert(61343, "surpass");
-        words.insert(61344, "surplus");
-        words.insert(61345, "surprise");
-        words.insert(61346, "surreal");
-        words.insert(61351, "surrender");
-        words.insert(61352, "surrogate");
-        words.insert(61353, "surround");
-        words.insert(61354, "survey");
-        words.insert(61355, "survival");
-        words.insert(61356, "survive");
-        words.insert(61361, "surviving");
-        words.insert(61362, "survivor");
-        words.insert(61363, "sushi");
-        words.insert(61364, "suspect");
-        words.insert(61365, "suspend");
-        words.insert(61366, "suspense");
-        words.insert(61411, "sustained");
-        words.insert(61412, "sustainer");
-        words.insert(61413, "swab");
-        words.insert(61414, "swaddling");
-        words.insert(61415, "swagger");
-        words.insert(61416, "swampland");
-        words.insert(61421, "swan");
-        words.insert(61422, "swapping");
-        words.insert(61423, "swarm");
-        words.insert(61424, "sway");
-        words.insert(61425, "swear");
-        words.insert(61426, "sweat");
-        words.insert(61431, "sweep");
-        words.insert(61432, "swell");
-        words.insert(61433, "swept");
-        words.insert(61434, "swerve");
-        words.insert(61435, "swifter");
-        words.insert(61436, "swiftly");
-        words.insert(61441, "swiftness");
-        words.insert(61442, "swimmable");
-        words.insert(61443, "swimmer");
-        words.insert(61444, "swimming");
-        words.insert(61445, "swimsuit");
-        words.insert(61446, "swimwear");
-        words.insert(61451, "swinger");
-        words.insert(61452, "swinging");
-        words.insert(61453, "swipe");
-        words.insert(61454, "swirl");
-        words.insert(61455, "switch");
-        words.insert(61456, "swivel");
-        words.insert(61461, "swizzle");
-        words.insert(61462, "swooned");
-        words.insert(61463, "swoop");
-        words.insert(61464, "swoosh");
-        words.insert(61465, "swore");
-        words.insert(61466, "sworn");
-        words.insert(61511, "swung");
-        words.insert(61512, "sycamore");
-        words.insert(61513, "sympathy");
-        words.insert(61514, "symphonic");
-        words.insert(61515, "symphony");
-        words.insert(61516, "symptom");
-        words.insert(61521, "synapse");
-        words.insert(61522, "syndrome");
-        words.insert(61523, "synergy");
-        words.insert(61524, "synopses");
-        words.insert(61525, "synopsis");
-        words.insert(61526, "synthesis");
-        words.insert(61531, "synthetic");
-        words.insert(61532, "syrup");
-        words.insert(61533, "system");
-        words.insert(61534, "t-shirt");
-        words.insert(61535, "tabasco");
-        words.insert(61536, "tabby");
-        words.insert(61541, "tableful");
-        words.insert(61542, "tables");
-        words.insert(61543, "tablet");
-        words.insert(61544, "tableware");
-        words.insert(61545, "tabloid");
-        words.insert(61546, "tackiness");
-        words.insert(61551, "tacking");
-        words.insert(61552, "tackle");
-        words.insert(61553, "tackling");
-        words.insert(61554, "tacky");
-        words.insert(61555, "taco");
-        words.insert(61556, "tactful");
-        words.insert(61561, "tactical");
-        words.insert(61562, "tactics");
-        words.insert(61563, "tactile");
-        words.insert(61564, "tactless");
-        words.insert(61565, "tadpole");
-        words.insert(61566, "taekwondo");
-        words.insert(61611, "tag");
-        words.insert(61612, "tainted");
-        words.insert(61613, "take");
-        words.insert(61614, "taking");
-        words.insert(61615, "talcum");
-        words.insert(61616, "talisman");
-        words.insert(61621, "tall");
-        words.insert(61622, "talon");
-        words.insert(61623, "tamale");
-        words.insert(61624, "tameness");
-        words.insert(61625, "tamer");
-        words.insert(61626, "tamper");
-        words.insert(61631, "tank");
-        words.insert(61632, "tanned");
-        words.insert(61633, "tannery");
-        words.insert(61634, "tanning");
-        words.insert(61635, "tantrum");
-        words.insert(61636, "tapeless");
-        words.insert(61641, "tapered");
-        words.insert(61642, "tapering");
-        words.insert(61643, "tapestry");
-        words.insert(61644, "tapioca");
-        words.insert(61645, "tapping");
-        words.insert(61646, "taps");
-        words.insert(61651, "tarantula");
-        words.insert(61652, "target");
-        words.insert(61653, "tarmac");
-        words.insert(61654, "tarnish");
-        words.insert(61655, "tarot");
-        words.insert(61656, "tartar");
-        words.insert(61661, "tartly");
-        words.insert(61662, "tartness");
-        words.insert(61663, "task");
-        words.insert(61664, "tassel");
-        words.insert(61665, "taste");
-        words.insert(61666, "tastiness");
-        words.insert(62111, "tasting");
-        words.insert(62112, "tasty");
-        words.insert(62113, "tattered");
-        words.insert(62114, "tattle");
-        words.insert(62115, "tattling");
-        words.insert(62116, "tattoo");
-        words.insert(62121, "taunt");
-        words.insert(62122, "tavern");
-        words.insert(62123, "thank");
-        words.insert(62124, "that");
-        words.insert(62125, "thaw");
-        words.insert(62126, "theater");
-        words.insert(62131, "theatrics");
-        words.insert(62132, "thee");
-        words.insert(62133, "theft");
-        words.insert(62134, "theme");
-        words.insert(62135, "theology");
-        words.insert(62136, "theorize");
-        words.insert(62141, "thermal");
-        words.insert(62142, "thermos");
-        words.insert(62143, "thesaurus");
-        words.insert(62144, "these");
-        words.insert(62145, "thesis");
-        words.insert(62146, "thespian");
-        words.insert(62151, "thicken");
-        words.insert(62152, "thicket");
-        words.insert(62153, "thickness");
-        words.insert(62154, "thieving");
-        words.insert(62155, "thievish");
-        words.insert(62156, "thigh");
-        words.insert(62161, "thimble");
-        words.insert(62162, "thing");
-        words.insert(62163, "think");
-        words.insert(62164, "thinly");
-        words.insert(62165, "thinner");
-        words.insert(62166, "thinness");
-        words.insert(62211, "thinning");
-        words.insert(62212, "thirstily");
-        words.insert(62213, "thirsting");
-        words.insert(62214, "thirsty");
-        words.insert(62215, "thirteen");
-        words.insert(62216, "thirty");
-        words.insert(62221, "thong");
-        words.insert(62222, "thorn");
-        words.insert(62223, "those");
-        words.insert(62224, "thousand");
-        words.insert(62225, "thrash");
-        words.insert(62226, "thread");
-        words.insert(62231, "threaten");
-        words.insert(62232, "threefold");
-        words.insert(62233, "thrift");
-        words.insert(62234, "thrill");
-        words.insert(62235, "thrive");
-        words.insert(62236, "thriving");
-        words.insert(62241, "throat");
-        words.insert(62242, "throbbing");
-        words.insert(62243, "throng");
-        words.insert(62244, "throttle");
-        words.insert(62245, "throwaway");
-        words.insert(62246, "throwback");
-        words.insert(62251, "thrower");
-        words.insert(62252, "throwing");
-        words.insert(62253, "thud");
-        words.insert(62254, "thumb");
-        words.insert(62255, "thumping");
-        words.insert(62256, "thursday");
-        words.insert(62261, "thus");
-        words.insert(62262, "thwarting");
-        words.insert(62263, "thyself");
-        words.insert(62264, "tiara");
-        words.insert(62265, "tibia");
-        words.insert(62266, "tidal");
-        words.insert(62311, "tidbit");
-        words.insert(62312, "tidiness");
-        words.insert(62313, "tidings");
-        words.insert(62314, "tidy");
-        words.insert(62315, "tiger");
-        words.insert(62316, "tighten");
-        words.insert(62321, "tightly");
-        words.insert(62322, "tightness");
-        words.insert(62323, "tightrope");
-        words.insert(62324, "tightwad");
-        words.insert(62325, "tigress");
-        words.insert(62326, "tile");
-        words.insert(62331, "tiling");
-        words.insert(62332, "till");
-        words.insert(62333, "tilt");
-        words.insert(62334, "timid");
-        words.insert(62335, "timing");
-        words.insert(62336, "timothy");
-        words.insert(62341, "tinderbox");
-        words.insert(62342, "tinfoil");
-        words.insert(62343, "tingle");
-        words.insert(62344, "tingling");
-        words.insert(62345, "tingly");
-        words.insert(62346, "tinker");
-        words.insert(62351, "tinkling");
-        words.insert(62352, "tinsel");
-        words.insert(62353, "tinsmith");
-        words.insert(62354, "tint");
-        words.insert(62355, "tinwork");
-        words.insert(62356, "tiny");
-        words.insert(62361, "tipoff");
-        words.insert(62362, "tipped");
-        words.insert(62363, "tipper");
-        words.insert(62364, "tipping");
-        words.insert(62365, "tiptoeing");
-        words.insert(62366, "tiptop");
-        words.insert(62411, "tiring");
-        words.insert(62412, "tissue");
-        words.insert(62413, "trace");
-        words.insert(62414, "tracing");
-        words.insert(62415, "track");
-        words.insert(62416, "traction");
-        words.insert(62421, "tractor");
-        words.insert(62422, "trade");
-        words.insert(62423, "trading");
-        words.insert(62424, "tradition");
-        words.insert(62425, "traffic");
-        words.insert(62426, "tragedy");
-        words.insert(62431, "trailing");
-        words.insert(62432, "trailside");
-        words.insert(62433, "train");
-        words.insert(62434, "traitor");
-        words.insert(62435, "trance");
-        words.insert(62436, "tranquil");
-        words.insert(62441, "transfer");
-        words.insert(62442, "transform");
-        words.insert(62443, "translate");
-        words.insert(62444, "transpire");
-        words.insert(62445, "transport");
-        words.insert(62446, "transpose");
-        words.insert(62451, "trapdoor");
-        words.insert(62452, "trapeze");
-        words.insert(62453, "trapezoid");
-        words.insert(62454, "trapped");
-        words.insert(62455, "trapper");
-        words.insert(62456, "trapping");
-        words.insert(62461, "traps");
-        words.insert(62462, "trash");
-        words.insert(62463, "travel");
-        words.insert(62464, "traverse");
-        words.insert(62465, "travesty");
-        words.insert(62466, "tray");
-        words.insert(62511, "treachery");
-        words.insert(62512, "treading");
-        words.insert(62513, "treadmill");
-        words.insert(62514, "treason");
-        words.insert(62515, "treat");
-        words.insert(62516, "treble");
-        words.insert(62521, "tree");
-        words.insert(62522, "trekker");
-        words.insert(62523, "tremble");
-        words.insert(62524, "trembling");
-        words.insert(62525, "tremor");
-        words.insert(62526, "trench");
-        words.insert(62531, "trend");
-        words.insert(62532, "trespass");
-        words.insert(62533, "triage");
-        words.insert(62534, "trial");
-        words.insert(62535, "triangle");
-        words.insert(62536, "tribesman");
-        words.insert(62541, "tribunal");
-        words.insert(62542, "tribune");
-        words.insert(62543, "tributary");
-        words.insert(62544, "tribute");
-        words.insert(62545, "triceps");
-        words.insert(62546, "trickery");
-        words.insert(62551, "trickily");
-        words.insert(62552, "tricking");
-        words.insert(62553, "trickle");
-        words.insert(62554, "trickster");
-        words.insert(62555, "tricky");
-        words.insert(62556, "tricolor");
-        words.insert(62561, "tricycle");
-        words.insert(62562, "trident");
-        words.insert(62563, "tried");
-        words.insert(62564, "trifle");
-        words.insert(62565, "trifocals");
-        words.insert(62566, "trillion");
-        words.insert(62611, "trilogy");
-        words.insert(62612, "trimester");
-        words.insert(62613, "trimmer");
-        words.insert(62614, "trimming");
-        words.insert(62615, "trimness");
-        words.insert(62616, "trinity");
-        words.insert(62621, "trio");
-        words.insert(62622, "tripod");
-        words.insert(62623, "tripping");
-        words.insert(62624, "triumph");
-        words.insert(62625, "trivial");
-        words.insert(62626, "trodden");
-        words.insert(62631, "trolling");
-        words.insert(62632, "trombone");
-        words.insert(62633, "trophy");
-        words.insert(62634, "tropical");
-        words.insert(62635, "tropics");
-        words.insert(62636, "trouble");
-        words.insert(62641, "troubling");
-        words.insert(62642, "trough");
-        words.insert(62643, "trousers");
-        words.insert(62644, "trout");
-        words.insert(62645, "trowel");
-        words.insert(62646, "truce");
-        words.insert(62651, "truck");
-        words.insert(62652, "truffle");
-        words.insert(62653, "trump");
-        words.insert(62654, "trunks");
-        words.insert(62655, "trustable");
-        words.insert(62656, "trustee");
-        words.insert(62661, "trustful");
-        words.insert(62662, "trusting");
-        words.insert(62663, "trustless");
-        words.insert(62664, "truth");
-        words.insert(62665, "try");
-        words.insert(62666, "tubby");
-        words.insert(63111, "tubeless");
-        words.insert(63112, "tubular");
-        words.insert(63113, "tucking");
-        words.insert(63114, "tuesday");
-        words.insert(63115, "tug");
-        words.insert(63116, "tuition");
-        words.insert(63121, "tulip");
-        words.insert(63122, "tumble");
-        words.insert(63123, "tumbling");
-        words.insert(63124, "tummy");
-        words.insert(63125, "turban");
-        words.insert(63126, "turbine");
-        words.insert(63131, "turbofan");
-        words.insert(63132, "turbojet");
-        words.insert(63133, "turbulent");
-        words.insert(63134, "turf");
-        words.insert(63135, "turkey");
-        words.insert(63136, "turmoil");
-        words.insert(63141, "turret");
-        words.insert(63142, "turtle");
-        words.insert(63143, "tusk");
-        words.insert(63144, "tutor");
-        words.insert(63145, "tutu");
-        words.insert(63146, "tux");
-        words.insert(63151, "tweak");
-        words.insert(63152, "tweed");
-        words.insert(63153, "tweet");
-        words.insert(63154, "tweezers");
-        words.insert(63155, "twelve");
-        words.insert(63156, "twentieth");
-        words.insert(63161, "twenty");
-        words.insert(63162, "twerp");
-        words.insert(63163, "twice");
-        words.insert(63164, "twiddle");
-        words.insert(63165, "twiddling");
-        words.insert(63166, "twig");
-        words.insert(63211, "twilight");
-        words.insert(63212, "twine");
-        words.insert(63213, "twins");
-        words.insert(63214, "twirl");
-        words.insert(63215, "twistable");
-        words.insert(63216, "twisted");
-        words.insert(63221, "twister");
-        words.insert(63222, "twisting");
-        words.insert(63223, "twisty");
-        words.insert(63224, "twitch");
-        words.insert(63225, "twitter");
-        words.insert(63226, "tycoon");
-        words.insert(63231, "tying");
-        words.insert(63232, "tyke");
-        words.insert(63233, "udder");
-        words.insert(63234, "ultimate");
-        words.insert(63235, "ultimatum");
-        words.insert(63236, "ultra");
-        words.insert(63241, "umbilical");
-        words.insert(63242, "umbrella");
-        words.insert(63243, "umpire");
-        words.insert(63244, "unabashed");
-        words.insert(63245, "unable");
-        words.insert(63246, "unadorned");
-        words.insert(63251, "unadvised");
-        words.insert(63252, "unafraid");
-        words.insert(63253, "unaired");
-        words.insert(63254, "unaligned");
-        words.insert(63255, "unaltered");
-        words.insert(63256, "unarmored");
-        words.insert(63261, "unashamed");
-        words.insert(63262, "unaudited");
-        words.insert(63263, "unawake");
-        words.insert(63264, "unaware");
-        words.insert(63265, "unbaked");
-        words.insert(63266, "unbalance");
-        words.insert(63311, "unbeaten");
-        words.insert(63312, "unbend");
-        words.insert(63313, "unbent");
-        words.insert(63314, "unbiased");
-        words.insert(63315, "unbitten");
-        words.insert(63316, "unblended");
-        words.insert(63321, "unblessed");
-        words.insert(63322, "unblock");
-        words.insert(63323, "unbolted");
-        words.insert(63324, "unbounded");
-        words.insert(63325, "unboxed");
-        words.insert(63326, "unbraided");
-        words.insert(63331, "unbridle");
-        words.insert(63332, "unbroken");
-        words.insert(63333, "unbuckled");
-        words.insert(63334, "unbundle");
-        words.insert(63335, "unburned");
-        words.insert(63336, "unbutton");
-        words.insert(63341, "uncanny");
-        words.insert(63342, "uncapped");
-        words.insert(63343, "uncaring");
-        words.insert(63344, "uncertain");
-        words.insert(63345, "unchain");
-        words.insert(63346, "unchanged");
-        words.insert(63351, "uncharted");
-        words.insert(63352, "uncheck");
-        words.insert(63353, "uncivil");
-        words.insert(63354, "unclad");
-        words.insert(63355, "unclaimed");
-        words.insert(63356, "unclamped");
-        words.insert(63361, "unclasp");
-        words.insert(63362, "uncle");
-        words.insert(63363, "unclip");
-        words.insert(63364, "uncloak");
-        words.insert(63365, "unclog");
-        words.insert(63366, "unclothed");
-        words.insert(63411, "uncoated");
-        words.insert(63412, "uncoiled");
-        words.insert(63413, "uncolored");
-        words.insert(63414, "uncombed");
-        words.insert(63415, "uncommon");
-        words.insert(63416, "uncooked");
-        words.insert(63421, "uncork");
-        words.insert(63422, "uncorrupt");
-        words.insert(63423, "uncounted");
-        words.insert(63424, "uncouple");
-        words.insert(63425, "uncouth");
-        words.insert(63426, "uncover");
-        words.insert(63431, "uncross");
-        words.insert(63432, "uncrown");
-        words.insert(63433, "uncrushed");
-        words.insert(63434, "uncured");
-        words.insert(63435, "uncurious");
-        words.insert(63436, "uncurled");
-        words.insert(63441, "uncut");
-        words.insert(63442, "undamaged");
-        words.insert(63443, "undated");
-        words.insert(63444, "undaunted");
-        words.insert(63445, "undead");
-        words.insert(63446, "undecided");
-        words.insert(63451, "undefined");
-        words.insert(63452, "underage");
-        words.insert(63453, "underarm");
-        words.insert(63454, "undercoat");
-        words.insert(63455, "undercook");
-        words.insert(63456, "undercut");
-        words.insert(63461, "underdog");
-        words.insert(63462, "underdone");
-        words.insert(63463, "underfed");
-        words.insert(63464, "underfeed");
-        words.insert(63465, "underfoot");
-        words.insert(63466, "undergo");
-        words.insert(63511, "undergrad");
-        words.insert(63512, "underhand");
-        words.insert(63513, "underline");
-        words.insert(63514, "underling");
-        words.insert(63515, "undermine");
-        words.insert(63516, "undermost");
-        words.insert(63521, "underpaid");
-        words.insert(63522, "underpass");
-        words.insert(63523, "underpay");
-        words.insert(63524, "underrate");
-        words.insert(63525, "undertake");
-        words.insert(63526, "undertone");
-        words.insert(63531, "undertook");
-        words.insert(63532, "undertow");
-        words.insert(63533, "underuse");
-        words.insert(63534, "underwear");
-        words.insert(63535, "underwent");
-        words.insert(63536, "underwire");
-        words.insert(63541, "undesired");
-        words.insert(63542, "undiluted");
-        words.insert(63543, "undivided");
-        words.insert(63544, "undocked");
-        words.insert(63545, "undoing");
-        words.insert(63546, "undone");
-        words.insert(63551, "undrafted");
-        words.insert(63552, "undress");
-        words.insert(63553, "undrilled");
-        words.insert(63554, "undusted");
-        words.insert(63555, "undying");
-        words.insert(63556, "unearned");
-        words.insert(63561, "unearth");
-        words.insert(63562, "unease");
-        words.insert(63563, "uneasily");
-        words.insert(63564, "uneasy");
-        words.insert(63565, "uneatable");
-        words.insert(63566, "uneaten");
-        words.insert(63611, "unedited");
-        words.insert(63612, "unelected");
-        words.insert(63613, "unending");
-        words.insert(63614, "unengaged");
-        words.insert(63615, "unenvied");
-        words.insert(63616, "unequal");
-        words.insert(63621, "unethical");
-        words.insert(63622, "uneven");
-        words.insert(63623, "unexpired");
-        words.insert(63624, "unexposed");
-        words.insert(63625, "unfailing");
-        words.insert(63626, "unfair");
-        words.insert(63631, "unfasten");
-        words.insert(63632, "unfazed");
-        words.insert(63633, "unfeeling");
-        words.insert(63634, "unfiled");
-        words.insert(63635, "unfilled");
-        words.insert(63636, "unfitted");
-        words.insert(63641, "unfitting");
-        words.insert(63642, "unfixable");
-        words.insert(63643, "unfixed");
-        words.insert(63644, "unflawed");
-        words.insert(63645, "unfocused");
-        words.insert(63646, "unfold");
-        words.insert(63651, "unfounded");
-        words.insert(63652, "unframed");
-        words.insert(63653, "unfreeze");
-        words.insert(63654, "unfrosted");
-        words.insert(63655, "unfrozen");
-        words.insert(63656, "unfunded");
-        words.insert(63661, "unglazed");
-        words.insert(63662, "ungloved");
-        words.insert(63663, "unglue");
-        words.insert(63664, "ungodly");
-        words.insert(63665, "ungraded");
-        words.insert(63666, "ungreased");
-        words.insert(64111, "unguarded");
-        words.insert(64112, "unguided");
-        words.insert(64113, "unhappily");
-        words.insert(64114, "unhappy");
-        words.insert(64115, "unharmed");
-        words.insert(64116, "unhealthy");
-        words.insert(64121, "unheard");
-        words.insert(64122, "unhearing");
-        words.insert(64123, "unheated");
-        words.insert(64124, "unhelpful");
-        words.insert(64125, "unhidden");
-        words.insert(64126, "unhinge");
-        words.insert(64131, "unhitched");
-        words.insert(64132, "unholy");
-        words.insert(64133, "unhook");
-        words.insert(64134, "unicorn");
-        words.insert(64135, "unicycle");
-        words.insert(64136, "unified");
-        words.insert(64141, "unifier");
-        words.insert(64142, "uniformed");
-        words.insert(64143, "uniformly");
-        words.insert(64144, "unify");
-        words.insert(64145, "unimpeded");
-        words.insert(64146, "uninjured");
-        words.insert(64151, "uninstall");
-        words.insert(64152, "uninsured");
-        words.insert(64153, "uninvited");
-        words.insert(64154, "union");
-        words.insert(64155, "uniquely");
-        words.insert(64156, "unisexual");
-        words.insert(64161, "unison");
-        words.insert(64162, "unissued");
-        words.insert(64163, "unit");
-        words.insert(64164, "universal");
-        words.insert(64165, "universe");
-        words.insert(64166, "unjustly");
-        words.insert(64211, "unkempt");
-        words.insert(64212, "unkind");
-        words.insert(64213, "unknotted");
-        words.insert(64214, "unknowing");
-        words.insert(64215, "unknown");
-        words.insert(64216, "unlaced");
-        words.insert(64221, "unlatch");
-        words.insert(64222, "unlawful");
-        words.insert(64223, "unleaded");
-        words.insert(64224, "unlearned");
-        words.insert(64225, "unleash");
-        words.insert(64226, "unless");
-        words.insert(64231, "unleveled");
-        words.insert(64232, "unlighted");
-        words.insert(64233, "unlikable");
-        words.insert(64234, "unlimited");
-        words.insert(64235, "unlined");
-        words.insert(64236, "unlinked");
-        words.insert(64241, "unlisted");
-        words.insert(64242, "unlit");
-        words.insert(64243, "unlivable");
-        words.insert(64244, "unloaded");
-        words.insert(64245, "unloader");
-        words.insert(64246, "unlocked");
-        words.insert(64251, "unlocking");
-        words.insert(64252, "unlovable");
-        words.insert(64253, "unloved");
-        words.insert(64254, "unlovely");
-        words.insert(64255, "unloving");
-        words.insert(64256, "unluckily");
-        words.insert(64261, "unlucky");
-        words.insert(64262, "unmade");
-        words.insert(64263, "unmanaged");
-        words.insert(64264, "unmanned");
-        words.insert(64265, "unmapped");
-        words.insert(64266, "unmarked");
-        words.insert(64311, "unmasked");
-        words.insert(64312, "unmasking");
-        words.insert(64313, "unmatched");
-        words.insert(64314, "unmindful");
-        words.insert(64315, "unmixable");
-        words.insert(64316, "unmixed");
-        words.insert(64321, "unmolded");
-        words.insert(64322, "unmoral");
-        words.insert(64323, "unmovable");
-        words.insert(64324, "unmoved");
-        words.insert(64325, "unmoving");
-        words.insert(64326, "unnamable");
-        words.insert(64331, "unnamed");
-        words.insert(64332, "unnatural");
-        words.insert(64333, "unneeded");
-        words.insert(64334, "unnerve");
-        words.insert(64335, "unnerving");
-        words.insert(64336, "unnoticed");
-        words.insert(64341, "unopened");
-        words.insert(64342, "unopposed");
-        words.insert(64343, "unpack");
-        words.insert(64344, "unpadded");
-        words.insert(64345, "unpaid");
-        words.insert(64346, "unpainted");
-        words.insert(64351, "unpaired");
-        words.insert(64352, "unpaved");
-        words.insert(64353, "unpeeled");
-        words.insert(64354, "unpicked");
-        words.insert(64355, "unpiloted");
-        words.insert(64356, "unpinned");
-        words.insert(64361, "unplanned");
-        words.insert(64362, "unplanted");
-        words.insert(64363, "unpleased");
-        words.insert(64364, "unpledged");
-        words.insert(64365, "unplowed");
-        words.insert(64366, "unplug");
-        words.insert(64411, "unpopular");
-        words.insert(64412, "unproven");
-        words.insert(64413, "unquote");
-        words.insert(64414, "unranked");
-        words.insert(64415, "unrated");
-        words.insert(64416, "unraveled");
-        words.insert(64421, "unreached");
-        words.insert(64422, "unread");
-        words.insert(64423, "unreal");
-        words.insert(64424, "unreeling");
-        words.insert(64425, "unrefined");
-        words.insert(64426, "unrelated");
-        words.insert(64431, "unrented");
-        words.insert(64432, "unrest");
-        words.insert(64433, "unretired");
-        words.insert(64434, "unrevised");
-        words.insert(64435, "unrigged");
-        words.insert(64436, "unripe");
-        words.insert(64441, "unrivaled");
-        words.insert(64442, "unroasted");
-        words.insert(64443, "unrobed");
-        words.insert(64444, "unroll");
-        words.insert(64445, "unruffled");
-        words.insert(64446, "unruly");
-        words.insert(64451, "unrushed");
-        words.insert(64452, "unsaddle");
-        words.insert(64453, "unsafe");
-        words.insert(64454, "unsaid");
-        words.insert(64455, "unsalted");
-        words.insert(64456, "unsaved");
-        words.insert(64461, "unsavory");
-        words.insert(64462, "unscathed");
-        words.insert(64463, "unscented");
-        words.insert(64464, "unscrew");
-        words.insert(64465, "unsealed");
-        words.insert(64466, "unseated");
-        words.insert(64511, "unsecured");
-        words.insert(64512, "unseeing");
-        words.insert(64513, "unseemly");
-        words.insert(64514, "unseen");
-        words.insert(64515, "unselect");
-        words.insert(64516, "unselfish");
-        words.insert(64521, "unsent");
-        words.insert(64522, "unsettled");
-        words.insert(64523, "unshackle");
-        words.insert(64524, "unshaken");
-        words.insert(64525, "unshaved");
-        words.insert(64526, "unshaven");
-        words.insert(64531, "unsheathe");
-        words.insert(64532, "unshipped");
-        words.insert(64533, "unsightly");
-        words.insert(64534, "unsigned");
-        words.insert(64535, "unskilled");
-        words.insert(64536, "unsliced");
-        words.insert(64541, "unsmooth");
-        words.insert(64542, "unsnap");
-        words.insert(64543, "unsocial");
-        words.insert(64544, "unsoiled");
-        words.insert(64545, "unsold");
-        words.insert(64546, "unsolved");
-        words.insert(64551, "unsorted");
-        words.insert(64552, "unspoiled");
-        words.insert(64553, "unspoken");
-        words.insert(64554, "unstable");
-        words.insert(64555, "unstaffed");
-        words.insert(64556, "unstamped");
-        words.insert(64561, "unsteady");
-        words.insert(64562, "unsterile");
-        words.insert(64563, "unstirred");
-        words.insert(64564, "unstitch");
-        words.insert(64565, "unstopped");
-        words.insert(64566, "unstuck");
-        words.insert(64611, "unstuffed");
-        words.insert(64612, "unstylish");
-        words.insert(64613, "unsubtle");
-        words.insert(64614, "unsubtly");
-        words.insert(64615, "unsuited");
-        words.insert(64616, "unsure");
-        words.insert(64621, "unsworn");
-        words.insert(64622, "untagged");
-        words.insert(64623, "untainted");
-        words.insert(64624, "untaken");
-        words.insert(64625, "untamed");
-        words.insert(64626, "untangled");
-        words.insert(64631, "untapped");
-        words.insert(64632, "untaxed");
-        words.insert(64633, "unthawed");
-        words.insert(64634, "unthread");
-        words.insert(64635, "untidy");
-        words.insert(64636, "untie");
-        words.insert(64641, "until");
-        words.insert(64642, "untimed");
-        words.insert(64643, "untimely");
-        words.insert(64644, "untitled");
-        words.insert(64645, "untoasted");
-        words.insert(64646, "untold");
-        words.insert(64651, "untouched");
-        words.insert(64652, "untracked");
-        words.insert(64653, "untrained");
-        words.insert(64654, "untreated");
-        words.insert(64655, "untried");
-        words.insert(64656, "untrimmed");
-        words.insert(64661, "untrue");
-        words.insert(64662, "untruth");
-        words.insert(64663, "unturned");
-        words.insert(64664, "untwist");
-        words.insert(64665, "untying");
-        words.insert(64666, "unusable");
-        words.insert(65111, "unused");
-        words.insert(65112, "unusual");
-        words.insert(65113, "unvalued");
-        words.insert(65114, "unvaried");
-        words.insert(65115, "unvarying");
-        words.insert(65116, "unveiled");
-        words.insert(65121, "unveiling");
-        words.insert(65122, "unvented");
-        words.insert(65123, "unviable");
-        words.insert(65124, "unvisited");
-        words.insert(65125, "unvocal");
-        words.insert(65126, "unwanted");
-        words.insert(65131, "unwarlike");
-        words.insert(65132, "unwary");
-        words.insert(65133, "unwashed");
-        words.insert(65134, "unwatched");
-        words.insert(65135, "unweave");
-        words.insert(65136, "unwed");
-        words.insert(65141, "unwelcome");
-        words.insert(65142, "unwell");
-        words.insert(65143, "unwieldy");
-        words.insert(65144, "unwilling");
-        words.insert(65145, "unwind");
-        words.insert(65146, "unwired");
-        words.insert(65151, "unwitting");
-        words.insert(65152, "unwomanly");
-        words.insert(65153, "unworldly");
-        words.insert(65154, "unworn");
-        words.insert(65155, "unworried");
-        words.insert(65156, "unworthy");
-        words.insert(65161, "unwound");
-        words.insert(65162, "unwoven");
-        words.insert(65163, "unwrapped");
-        words.insert(65164, "unwritten");
-        words.insert(65165, "unzip");
-        words.insert(65166, "upbeat");
-        words.insert(65211, "upchuck");
-        words.insert(65212, "upcoming");
-        words.insert(65213, "upcountry");
-        words.insert(65214, "update");
-        words.insert(65215, "upfront");
-        words.insert(65216, "upgrade");
-        words.insert(65221, "upheaval");
-        words.insert(65222, "upheld");
-        words.insert(65223, "uphill");
-        words.insert(65224, "uphold");
-        words.insert(65225, "uplifted");
-        words.insert(65226, "uplifting");
-        words.insert(65231, "upload");
-        words.insert(65232, "upon");
-        words.insert(65233, "upper");
-        words.insert(65234, "upright");
-        words.insert(65235, "uprising");
-        words.insert(65236, "upriver");
-        words.insert(65241, "uproar");
-        words.insert(65242, "uproot");
-        words.insert(65243, "upscale");
-        words.insert(65244, "upside");
-        words.insert(65245, "upstage");
-        words.insert(65246, "upstairs");
-        words.insert(65251, "upstart");
-        words.insert(65252, "upstate");
-        words.insert(65253, "upstream");
-        words.insert(65254, "upstroke");
-        words.insert(65255, "upswing");
-        words.insert(65256, "uptake");
-        words.insert(65261, "uptight");
-        words.insert(65262, "uptown");
-        words.insert(65263, "upturned");
-        words.insert(65264, "upward");
-        words.insert(65265, "upwind");
-        words.insert(65266, "uranium");
-        words.insert(65311, "urban");
-        words.insert(65312, "urchin");
-        words.insert(65313, "urethane");
-        words.insert(65314, "urgency");
-        words.insert(65315, "urgent");
-        words.insert(65316, "urging");
-        words.insert(65321, "urologist");
-        words.insert(65322, "urology");
-        words.insert(65323, "usable");
-        words.insert(65324, "usage");
-        words.insert(65325, "useable");
-        words.insert(65326, "used");
-        words.insert(65331, "uselessly");
-        words.insert(65332, "user");
-        words.insert(65333, "usher");
-        words.insert(65334, "usual");
-        words.insert(65335, "utensil");
-        words.insert(65336, "utility");
-        words.insert(65341, "utilize");
-        words.insert(65342, "utmost");
-        words.insert(65343, "utopia");
-        words.insert(65344, "utter");
-        words.insert(65345, "vacancy");
-        words.insert(65346, "vacant");
-        words.insert(65351, "vacate");
-        words.insert(65352, "vacation");
-        words.insert(65353, "vagabond");
-        words.insert(65354, "vagrancy");
-        words.insert(65355, "vagrantly");
-        words.insert(65356, "vaguely");
-        words.insert(65361, "vagueness");
-        words.insert(65362, "valiant");
-        words.insert(65363, "valid");
-        words.insert(65364, "valium");
-        words.insert(65365, "valley");
-        words.insert(65366, "valuables");
-        words.insert(65411, "value");
-        words.insert(65412, "vanilla");
-        words.insert(65413, "vanish");
-        words.insert(65414, "vanity");
-        words.insert(65415, "vanquish");
-        words.insert(65416, "vantage");
-        words.insert(65421, "vaporizer");
-        words.insert(65422, "variable");
-        words.insert(65423, "variably");
-        words.insert(65424, "varied");
-        words.insert(65425, "variety");
-        words.insert(65426, "various");
-        words.insert(65431, "varmint");
-        words.insert(65432, "varnish");
-        words.insert(65433, "varsity");
-        words.insert(65434, "varying");
-        words.insert(65435, "vascular");
-        words.insert(65436, "vaseline");
-        words.insert(65441, "vastly");
-        words.insert(65442, "vastness");
-        words.insert(65443, "veal");
-        words.insert(65444, "vegan");
-        words.insert(65445, "veggie");
-        words.insert(65446, "vehicular");
-        words.insert(65451, "velcro");
-        words.insert(65452, "velocity");
-        words.insert(65453, "velvet");
-        words.insert(65454, "vendetta");
-        words.insert(65455, "vending");
-        words.insert(65456, "vendor");
-        words.insert(65461, "veneering");
-        words.insert(65462, "vengeful");
-        words.insert(65463, "venomous");
-        words.insert(65464, "ventricle");
-        words.insert(65465, "venture");
-        words.insert(65466, "venue");
-        words.insert(65511, "venus");
-        words.insert(65512, "verbalize");
-        words.insert(65513, "verbally");
-        words.insert(65514, "verbose");
-        words.insert(65515, "verdict");
-        words.insert(65516, "verify");
-        words.insert(65521, "verse");
-        words.insert(65522, "version");
-        words.insert(65523, "versus");
-        words.insert(65524, "vertebrae");
-        words.insert(65525, "vertical");
-        words.insert(65526, "vertigo");
-        words.insert(65531, "very");
-        words.insert(65532, "vessel");
-        words.insert(65533, "vest");
-        words.insert(65534, "veteran");
-        words.insert(65535, "veto");
-        words.insert(65536, "vexingly");
-        words.insert(65541, "viability");
-        words.insert(65542, "viable");
-        words.insert(65543, "vibes");
-        words.insert(65544, "vice");
-        words.insert(65545, "vicinity");
-        words.insert(65546, "victory");
-        words.insert(65551, "video");
-        words.insert(65552, "viewable");
-        words.insert(65553, "viewer");
-        words.insert(65554, "viewing");
-        words.insert(65555, "viewless");
-        words.insert(65556, "viewpoint");
-        words.insert(65561, "vigorous");
-        words.insert(65562, "village");
-        words.insert(65563, "villain");
-        words.insert(65564, "vindicate");
-        words.insert(65565, "vineyard");
-        words.insert(65566, "vintage");
-        words.insert(65611, "violate");
-        words.insert(65612, "violation");
-        words.insert(65613, "violator");
-        words.insert(65614, "violet");
-        words.insert(65615, "violin");
-        words.insert(65616, "viper");
-        words.insert(65621, "viral");
-        words.insert(65622, "virtual");
-        words.insert(65623, "virtuous");
-        words.insert(65624, "virus");
-        words.insert(65625, "visa");
-        words.insert(65626, "viscosity");
-        words.insert(65631, "viscous");
-        words.insert(65632, "viselike");
-        words.insert(65633, "visible");
-        words.insert(65634, "visibly");
-        words.insert(65635, "vision");
-        words.insert(65636, "visiting");
-        words.insert(65641, "visitor");
-        words.insert(65642, "visor");
-        words.insert(65643, "vista");
-        words.insert(65644, "vitality");
-        words.insert(65645, "vitalize");
-        words.insert(65646, "vitally");
-        words.insert(65651, "vitamins");
-        words.insert(65652, "vivacious");
-        words.insert(65653, "vividly");
-        words.insert(65654, "vividness");
-        words.insert(65655, "vixen");
-        words.insert(65656, "vocalist");
-        words.insert(65661, "vocalize");
-        words.insert(65662, "vocally");
-        words.insert(65663, "vocation");
-        words.insert(65664, "voice");
-        words.insert(65665, "voicing");
-        words.insert(65666, "void");
-        words.insert(66111, "volatile");
-        words.insert(66112, "volley");
-        words.insert(66113, "voltage");
-        words.insert(66114, "volumes");
-        words.insert(66115, "voter");
-        words.insert(66116, "voting");
-        words.insert(66121, "voucher");
-        words.insert(66122, "vowed");
-        words.insert(66123, "vowel");
-        words.insert(66124, "voyage");
-        words.insert(66125, "wackiness");
-        words.insert(66126, "wad");
-        words.insert(66131, "wafer");
-        words.insert(66132, "waffle");
-        words.insert(66133, "waged");
-        words.insert(66134, "wager");
-        words.insert(66135, "wages");
-        words.insert(66136, "waggle");
-        words.insert(66141, "wagon");
-        words.insert(66142, "wake");
-        words.insert(66143, "waking");
-        words.insert(66144, "walk");
-        words.insert(66145, "walmart");
-        words.insert(66146, "walnut");
-        words.insert(66151, "walrus");
-        words.insert(66152, "waltz");
-        words.insert(66153, "wand");
-        words.insert(66154, "wannabe");
-        words.insert(66155, "wanted");
-        words.insert(66156, "wanting");
-        words.insert(66161, "wasabi");
-        words.insert(66162, "washable");
-        words.insert(66163, "washbasin");
-        words.insert(66164, "washboard");
-        words.insert(66165, "washbowl");
-        words.insert(66166, "washcloth");
-        words.insert(66211, "washday");
-        words.insert(66212, "washed");
-        words.insert(66213, "washer");
-        words.insert(66214, "washhouse");
-        words.insert(66215, "washing");
-        words.insert(66216, "washout");
-        words.insert(66221, "washroom");
-        words.insert(66222, "washstand");
-        words.insert(66223, "washtub");
-        words.insert(66224, "wasp");
-        words.insert(66225, "wasting");
-        words.insert(66226, "watch");
-        words.insert(66231, "water");
-        words.insert(66232, "waviness");
-        words.insert(66233, "waving");
-        words.insert(66234, "wavy");
-        words.insert(66235, "whacking");
-        words.insert(66236, "whacky");
-        words.insert(66241, "wham");
-        words.insert(66242, "wharf");
-        words.insert(66243, "wheat");
-        words.insert(66244, "whenever");
-        words.insert(66245, "whiff");
-        words.insert(66246, "whimsical");
-        words.insert(66251, "whinny");
-        words.insert(66252, "whiny");
-        words.insert(66253, "whisking");
-        words.insert(66254, "whoever");
-        words.insert(66255, "whole");
-        words.insert(66256, "whomever");
-        words.insert(66261, "whoopee");
-        words.insert(66262, "whooping");
-        words.insert(66263, "whoops");
-        words.insert(66264, "why");
-        words.insert(66265, "wick");
-        words.insert(66266, "widely");
-        words.insert(66311, "widen");
-        words.insert(66312, "widget");
-        words.insert(66313, "widow");
-        words.insert(66314, "width");
-        words.insert(66315, "wieldable");
-        words.insert(66316, "wielder");
-        words.insert(66321, "wife");
-        words.insert(66322, "wifi");
-        words.insert(66323, "wikipedia");
-        words.insert(66324, "wildcard");
-        words.insert(66325, "wildcat");
-        words.insert(66326, "wilder");
-        words.insert(66331, "wildfire");
-        words.insert(66332, "wildfowl");
-        words.insert(66333, "wildland");
-        words.insert(66334, "wildlife");
-        words.insert(66335, "wildly");
-        words.insert(66336, "wildness");
-        words.insert(66341, "willed");
-        words.insert(66342, "willfully");
-        words.insert(66343, "willing");
-        words.insert(66344, "willow");
-        words.insert(66345, "willpower");
-        words.insert(66346, "wilt");
-        words.insert(66351, "wimp");
-        words.insert(66352, "wince");
-        words.insert(66353, "wincing");
-        words.insert(66354, "wind");
-        words.insert(66355, "wing");
-        words.insert(66356, "winking");
-        words.insert(66361, "winner");
-        words.insert(66362, "winnings");
-        words.insert(66363, "winter");
-        words.insert(66364, "wipe");
-        words.insert(66365, "wired");
-        words.insert(66366, "wireless");
-        words.insert(66411, "wiring");
-        words.insert(66412, "wiry");
-        words.insert(66413, "wisdom");
-        words.insert(66414, "wise");
-        words.insert(66415, "wish");
-        words.insert(66416, "wisplike");
-        words.insert(66421, "wispy");
-        words.insert(66422, "wistful");
-        words.insert(66423, "wizard");
-        words.insert(66424, "wobble");
-        words.insert(66425, "wobbling");
-        words.insert(66426, "wobbly");
-        words.insert(66431, "wok");
-        words.insert(66432, "wolf");
-        words.insert(66433, "wolverine");
-        words.insert(66434, "womanhood");
-        words.insert(66435, "womankind");
-        words.insert(66436, "womanless");
-        words.insert(66441, "womanlike");
-        words.insert(66442, "womanly");
-        words.insert(66443, "womb");
-        words.insert(66444, "woof");
-        words.insert(66445, "wooing");
-        words.insert(66446, "wool");
-        words.insert(66451, "woozy");
-        words.insert(66452, "word");
-        words.insert(66453, "work");
-        words.insert(66454, "worried");
-        words.insert(66455, "worrier");
-        words.insert(66456, "worrisome");
-        words.insert(66461, "worry");
-        words.insert(66462, "worsening");
-        words.insert(66463, "worshiper");
-        words.insert(66464, "worst");
-        words.insert(66465, "wound");
-        words.insert(66466, "woven");
-        words.insert(66511, "wow");
-        words.insert(66512, "wrangle");
-        words.insert(66513, "wrath");
-        words.insert(66514, "wreath");
-        words.insert(66515, "wreckage");
-        words.insert(66516, "wrecker");
-        words.insert(66521, "wrecking");
-        words.insert(66522, "wrench");
-        words.insert(66523, "wriggle");
-        words.insert(66524, "wriggly");
-        words.insert(66525, "wrinkle");
-        words.insert(66526, "wrinkly");
-        words.insert(66531, "wrist");
-        words.insert(66532, "writing");
-        words.insert(66533, "written");
-        words.insert(66534, "wrongdoer");
-        words.insert(66535, "wronged");
-        words.insert(66536, "wrongful");
-        words.insert(66541, "wrongly");
-        words.insert(66542, "wrongness");
-        words.insert(66543, "wrought");
-        words.insert(66544, "xbox");
-        words.insert(66545, "xerox");
-        words.insert(66546, "yahoo");
-        words.insert(66551, "yam");
-        words.insert(66552, "yanking");
-        words.insert(66553, "yapping");
-        words.insert(66554, "yard");
-        words.insert(66555, "yarn");
-        words.insert(66556, "yeah");
-        words.insert(66561, "yearbook");
-        words.insert(66562, "yearling");
-        words.insert(66563, "yearly");
-        words.insert(66564, "yearning");
-        words.insert(66565, "yeast");
-        words.insert(66566, "yelling");
-        words.insert(66611, "yelp");
-        words.insert(66612, "yen");
-        words.insert(66613, "yesterday");
-        words.insert(66614, "yiddish");
-        words.insert(66615, "yield");
-        words.insert(66616, "yin");
-        words.insert(66621, "yippee");
-        words.insert(66622, "yo-yo");
-        words.insert(66623, "yodel");
-        words.insert(66624, "yoga");
-        words.insert(66625, "yogurt");
-        words.insert(66626, "yonder");
-        words.insert(66631, "yoyo");
-        words.insert(66632, "yummy");
-        words.insert(66633, "zap");
-        words.insert(66634, "zealous");
-        words.insert(66635, "zebra");
-        words.insert(66636, "zen");
-        words.insert(66641, "zeppelin");
-        words.insert(66642, "zero");
-        words.insert(66643, "zestfully");
-        words.insert(66644, "zesty");
-        words.insert(66645, "zigzagged");
-        words.insert(66646, "zipfile");
-        words.insert(66651, "zipping");
-        words.insert(66652, "zippy");
-        words.insert(66653, "zips");
-        words.insert(66654, "zit");
-        words.insert(66655, "zodiac");
-        words.insert(66656, "zombie");
-        words.insert(66661, "zone");
-        words.insert(66662, "zoning");
-        words.insert(66663, "zookeeper");
-        words.insert(66664, "zoologist");
-        words.insert(66665, "zoology");
-        words.insert(66666, "zoom");
-        words
-    };
-}
@@ -0,0 +1,321 @@
+use crate::errors::PortalError::*;
+use crate::{
+    CodecVersion, Direction, Durability, Portal, Priority, ReceivePolicy, TransferLimits,
+    TransferTuning, WireFormat,
+};
+use std::error::Error;
+
+/// The secret a Portal request is initialized from
+#[derive(Debug, Clone)]
+enum Credential {
+    Password(String),
+    Psk([u8; 32]),
+    Shard { master: String, index: u32 },
+}
+
+/// Builds a Portal request in a single expression, rather than through
+/// [`Portal::init`] followed by individual setters. Every option is
+/// validated together by [`PortalBuilder::build`], so a conflicting
+/// combination is reported before the handshake rather than during it.
+///
+/// ```
+/// use portal_lib::{Direction, PortalBuilder, Priority};
+///
+/// let portal = PortalBuilder::new(Direction::Sender)
+///     .id("id")
+///     .password("password")
+///     .checksums(true)
+///     .adaptive_chunks(false)
+///     .priority(Priority::Bulk)
+///     .build()
+///     .unwrap();
+///
+/// assert!(portal.get_checksums());
+/// ```
+#[derive(Debug, Clone)]
+pub struct PortalBuilder {
+    direction: Direction,
+    id: Option<String>,
+    credential: Option<Credential>,
+    conflict: bool,
+    checksums: Option<bool>,
+    digests: Option<bool>,
+    adaptive_chunks: Option<bool>,
+    tuning: Option<TransferTuning>,
+    mmap_window: Option<usize>,
+    constant_memory: Option<bool>,
+    dedup: Option<bool>,
+    bind_chunks: Option<bool>,
+    durability: Option<Durability>,
+    limits: Option<TransferLimits>,
+    receive_policy: Option<ReceivePolicy>,
+    strict: Option<bool>,
+    relay_secret: Option<Vec<u8>>,
+    namespace: Option<String>,
+    priority: Option<Priority>,
+    codec: Option<CodecVersion>,
+    format: Option<WireFormat>,
+}
+
+impl PortalBuilder {
+    /// Start building a Portal request for one side of a transfer
+    pub fn new(direction: Direction) -> PortalBuilder {
+        PortalBuilder {
+            direction,
+            id: None,
+            credential: None,
+            conflict: false,
+            checksums: None,
+            digests: None,
+            adaptive_chunks: None,
+            tuning: None,
+            mmap_window: None,
+            constant_memory: None,
+            dedup: None,
+            bind_chunks: None,
+            durability: None,
+            limits: None,
+            receive_policy: None,
+            strict: None,
+            relay_secret: None,
+            namespace: None,
+            priority: None,
+            codec: None,
+            format: None,
+        }
+    }
+
+    /// The ID shared with the peer, required
+    pub fn id(mut self, id: &str) -> Self {
+        self.id = Some(id.to_string());
+        self
+    }
+
+    /// Authenticate the peer with a pass-phrase, see [`Portal::init`]
+    pub fn password(self, password: &str) -> Self {
+        self.credential(Credential::Password(password.to_string()))
+    }
+
+    /// Authenticate a direct peer with a pre-shared key, see [`Portal::init_psk`]
+    pub fn psk(self, psk: [u8; 32]) -> Self {
+        self.credential(Credential::Psk(psk))
+    }
+
+    /// Derive the credentials of one receiver of a group transfer from
+    /// the master pass-phrase, see [`Portal::init_shard`]
+    pub fn shard(self, master: &str, index: u32) -> Self {
+        self.credential(Credential::Shard {
+            master: master.to_string(),
+            index,
+        })
+    }
+
+    fn credential(mut self, credential: Credential) -> Self {
+        self.conflict |= self.credential.is_some();
+        self.credential = Some(credential);
+        self
+    }
+
+    /// See [`Portal::set_checksums`]
+    pub fn checksums(mut self, checksums: bool) -> Self {
+        self.checksums = Some(checksums);
+        self
+    }
+
+    /// See [`Portal::set_digests`]
+    pub fn digests(mut self, digests: bool) -> Self {
+        self.digests = Some(digests);
+        self
+    }
+
+    /// See [`Portal::set_adaptive_chunks`]
+    pub fn adaptive_chunks(mut self, adaptive: bool) -> Self {
+        self.adaptive_chunks = Some(adaptive);
+        self
+    }
+
+    /// See [`Portal::set_tuning`]
+    pub fn tuning(mut self, tuning: TransferTuning) -> Self {
+        self.tuning = Some(tuning);
+        self
+    }
+
+    /// See [`Portal::set_mmap_window`]
+    pub fn mmap_window(mut self, len: usize) -> Self {
+        self.mmap_window = Some(len);
+        self
+    }
+
+    /// See [`Portal::set_constant_memory`]
+    pub fn constant_memory(mut self, constant: bool) -> Self {
+        self.constant_memory = Some(constant);
+        self
+    }
+
+    /// See [`Portal::set_dedup`]
+    pub fn dedup(mut self, dedup: bool) -> Self {
+        self.dedup = Some(dedup);
+        self
+    }
+
+    /// See [`Portal::set_bind_chunks`]
+    pub fn bind_chunks(mut self, bind: bool) -> Self {
+        self.bind_chunks = Some(bind);
+        self
+    }
+
+    /// See [`Portal::set_durability`]
+    pub fn durability(mut self, durability: Durability) -> Self {
+        self.durability = Some(durability);
+        self
+    }
+
+    /// See [`Portal::set_limits`]
+    pub fn limits(mut self, limits: TransferLimits) -> Self {
+        self.limits = Some(limits);
+        self
+    }
+
+    /// See [`Portal::set_receive_policy`]
+    pub fn receive_policy(mut self, policy: ReceivePolicy) -> Self {
+        self.receive_policy = Some(policy);
+        self
+    }
+
+    /// See [`Portal::set_strict`]
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = Some(strict);
+        self
+    }
+
+    /// See [`Portal::set_relay_secret`]
+    pub fn relay_secret(mut self, secret: &[u8]) -> Self {
+        self.relay_secret = Some(secret.to_vec());
+        self
+    }
+
+    /// See [`Portal::set_namespace`]
+    pub fn namespace(mut self, namespace: &str) -> Self {
+        self.namespace = Some(namespace.to_string());
+        self
+    }
+
+    /// See [`Portal::set_priority`]
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// See [`Portal::set_codec`]
+    pub fn codec(mut self, codec: CodecVersion) -> Self {
+        self.codec = Some(codec);
+        self
+    }
+
+    /// See [`Portal::set_wire_format`]
+    pub fn wire_format(mut self, format: WireFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Validate the options & initialize the Portal request. Fails with
+    /// [`BadConfig`](crate::errors::PortalError::BadConfig) when the ID or
+    /// credential is missing, more than one credential was given, or an
+    /// option only meaningful through a relay is combined with a
+    /// pre-shared key, which is only used with a direct peer.
+    pub fn build(self) -> Result<Portal, Box<dyn Error>> {
+        let id = match self.id {
+            Some(id) if !id.is_empty() => id,
+            _ => return Err(BadConfig("an ID is required".into()).into()),
+        };
+        if self.conflict {
+            return Err(BadConfig("only one of password, psk or shard may be set".into()).into());
+        }
+        let credential = self
+            .credential
+            .ok_or_else(|| BadConfig("a password, psk or shard is required".into()))?;
+
+        if let Credential::Psk(_) = credential {
+            if self.strict.unwrap_or(false)
+                || self.relay_secret.is_some()
+                || self.priority.is_some()
+                || self.dedup.unwrap_or(false)
+            {
+                return Err(BadConfig("relay options can't be used with a psk".into()).into());
+            }
+        }
+        if self.namespace.as_deref() == Some("") {
+            return Err(BadConfig("the namespace can't be empty".into()).into());
+        }
+        if self.mmap_window == Some(0) {
+            return Err(BadConfig("the mmap window can't be empty".into()).into());
+        }
+        if let Some(codec) = self.codec.filter(|c| !c.is_supported()) {
+            return Err(BadConfig(format!("unsupported codec {:?}", codec)).into());
+        }
+        if let Some(format) = self.format.filter(|f| !f.is_supported()) {
+            return Err(BadConfig(format!("unsupported wire format {:?}", format)).into());
+        }
+
+        let mut portal = match credential {
+            Credential::Password(password) => Portal::init(self.direction, id, password)?,
+            Credential::Psk(psk) => Portal::init_psk(self.direction, id, psk)?,
+            Credential::Shard { master, index } => {
+                Portal::init_shard(self.direction, &id, &master, index)?
+            }
+        };
+
+        if let Some(checksums) = self.checksums {
+            portal.set_checksums(checksums);
+        }
+        if let Some(digests) = self.digests {
+            portal.set_digests(digests);
+        }
+        if let Some(adaptive) = self.adaptive_chunks {
+            portal.set_adaptive_chunks(adaptive);
+        }
+        if let Some(tuning) = self.tuning {
+            portal.set_tuning(tuning);
+        }
+        if let Some(len) = self.mmap_window {
+            portal.set_mmap_window(len);
+        }
+        if let Some(constant) = self.constant_memory {
+            portal.set_constant_memory(constant);
+        }
+        if let Some(dedup) = self.dedup {
+            portal.set_dedup(dedup);
+        }
+        if let Some(bind) = self.bind_chunks {
+            portal.set_bind_chunks(bind);
+        }
+        if let Some(durability) = self.durability {
+            portal.set_durability(durability);
+        }
+        if let Some(limits) = self.limits {
+            portal.set_limits(limits);
+        }
+        if let Some(policy) = self.receive_policy {
+            portal.set_receive_policy(policy);
+        }
+        if let Some(strict) = self.strict {
+            portal.set_strict(strict);
+        }
+        if self.relay_secret.is_some() {
+            portal.set_relay_secret(self.relay_secret);
+        }
+        if self.namespace.is_some() {
+            portal.set_namespace(self.namespace);
+        }
+        if let Some(priority) = self.priority {
+            portal.set_priority(priority);
+        }
+        if let Some(codec) = self.codec {
+            portal.set_codec(codec)?;
+        }
+        if let Some(format) = self.format {
+            portal.set_wire_format(format)?;
+        }
+        Ok(portal)
+    }
+}
@@ -0,0 +1,122 @@
+//! Record everything sent & received on a stream, for debugging interop
+//! with other implementations. Wrap the stream handed to a [`Portal`](crate::Portal)
+//! in a [`Capture`] and each read or write is appended to a log as a line
+//! of JSON:
+//!
+//! ```text
+//! {"ts":1700000000123456,"dir":"tx","len":33,"data":"02a4..."}
+//! ```
+//!
+//! `ts` is microseconds since the UNIX epoch. Records follow the individual
+//! reads & writes on the stream rather than protocol messages, so a single
+//! message may span several records.
+//!
+//! ```no_run
+//! use std::net::TcpStream;
+//! use portal_lib::capture::{Capture, Payloads};
+//! use portal_lib::{Direction, Portal};
+//!
+//! let mut portal = Portal::init(Direction::Sender, "id".into(), "password".into()).unwrap();
+//! let stream = TcpStream::connect("127.0.0.1:34254").unwrap();
+//! let mut stream = Capture::to_file(stream, "portal.jsonl", Payloads::Redacted).unwrap();
+//! portal.handshake(&mut stream).unwrap();
+//! ```
+use serde::Serialize;
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Whether captured records include the bytes themselves
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Payloads {
+    /// Record only the timing, direction & length of each read or write
+    Redacted,
+
+    /// Also record the bytes, hex encoded
+    Kept,
+}
+
+/// A single captured read or write
+#[derive(Serialize)]
+struct Record<'a> {
+    ts: u128,
+    dir: &'a str,
+    len: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<String>,
+}
+
+/// A stream that logs all traffic passing through it
+pub struct Capture<S, L: Write> {
+    inner: S,
+    log: L,
+    payloads: Payloads,
+}
+
+impl<S> Capture<S, BufWriter<File>> {
+    /// Capture traffic on `inner` to a newly created file at `path`
+    pub fn to_file<P: AsRef<Path>>(inner: S, path: P, payloads: Payloads) -> io::Result<Self> {
+        let log = BufWriter::new(File::create(path)?);
+        Ok(Self::new(inner, log, payloads))
+    }
+}
+
+impl<S, L: Write> Capture<S, L> {
+    /// Capture traffic on `inner`, writing records to `log`
+    pub fn new(inner: S, log: L, payloads: Payloads) -> Self {
+        Capture {
+            inner,
+            log,
+            payloads,
+        }
+    }
+
+    /// Stop capturing, returning the wrapped stream & the log
+    pub fn into_inner(self) -> (S, L) {
+        (self.inner, self.log)
+    }
+
+    /// Helper: append a record. Failures writing the log are
+    /// ignored so that they never disturb the transfer itself.
+    fn record(&mut self, dir: &str, data: &[u8]) {
+        let record = Record {
+            ts: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_micros())
+                .unwrap_or_default(),
+            dir,
+            len: data.len(),
+            data: match self.payloads {
+                Payloads::Kept => Some(hex::encode(data)),
+                Payloads::Redacted => None,
+            },
+        };
+        if serde_json::to_writer(&mut self.log, &record).is_ok() {
+            let _ = self.log.write_all(b"\n");
+        }
+    }
+}
+
+impl<S: Read, L: Write> Read for Capture<S, L> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        if len > 0 {
+            self.record("rx", &buf[..len]);
+        }
+        Ok(len)
+    }
+}
+
+impl<S: Write, L: Write> Write for Capture<S, L> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.inner.write(buf)?;
+        self.record("tx", &buf[..len]);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let _ = self.log.flush();
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,294 @@
+//! Encrypted chunk transport over an established pairing
+use crate::errors::PortalError::*;
+use crate::protocol::{CodecVersion, EncryptedMessage, NonceSequence, Protocol, MAX_DATA_HEADER};
+use sha2::{Digest, Sha256};
+use std::error::Error;
+use std::io::{self, IoSlice, Read, Write};
+
+/// Moves arbitrary encrypted chunks between two paired peers. Each chunk
+/// is sent as an EncryptedDataHeader followed by the ciphertext, the same
+/// framing used for files, so any payload (database rows, telemetry) can
+/// be carried over a Portal pairing. An empty chunk marks the end of
+/// an open-ended stream.
+///
+/// A channel borrows the session key and NonceSequence from the Portal
+/// it was created from, see [`crate::Portal::channel`], so that nonces are
+/// never re-used across the session.
+///
+/// On transports that may corrupt data without failing, such as serial or
+/// radio links, enable [`EncryptedChannel::with_checksums`] on both ends.
+/// Each chunk is then followed by a CRC32C of its header & ciphertext, so
+/// corruption is reported as [`Corrupted`] & may be retransmitted, while
+/// tampering still fails decryption.
+///
+/// [`Corrupted`]: crate::errors::PortalError::Corrupted
+///
+/// Headers are framed with the v1 [`WireCodec`](crate::WireCodec) unless the session
+/// negotiated another, see [`EncryptedChannel::with_codec`].
+///
+/// Chunks of a file may be bound to the file's number in the session &
+/// their offset within it, see [`EncryptedChannel::with_file`], so that
+/// they can't be spliced into another file or another place in the same.
+///
+/// With [`EncryptedChannel::with_digest`] the plaintext of every chunk is
+/// hashed as it passes through, see [`EncryptedChannel::digest`].
+///
+/// ```
+/// use portal_lib::{EncryptedChannel, NonceSequence};
+///
+/// let key = [1u8; 32];
+/// let mut nseq = NonceSequence::new();
+///
+/// // Write a chunk into an in-memory buffer
+/// let mut wire = Vec::new();
+/// let mut channel = EncryptedChannel::new(&mut wire, &key, &mut nseq);
+/// channel.write_chunk(b"telemetry").unwrap();
+///
+/// // Read it back out
+/// let mut reader = &wire[..];
+/// let mut channel = EncryptedChannel::new(&mut reader, &key, &mut nseq);
+/// let mut storage = [0u8; 64];
+/// let len = channel.read_chunk(&mut storage).unwrap();
+/// assert_eq!(&storage[..len], b"telemetry");
+/// ```
+pub struct EncryptedChannel<'a, P> {
+    peer: &'a mut P,
+    key: &'a [u8],
+    nseq: &'a mut NonceSequence,
+    checksums: bool,
+    codec: CodecVersion,
+
+    // The file chunks are bound to & the offset of the next one
+    file: Option<(u32, u64)>,
+
+    // SHA-256 of the plaintext sent or received so far, if enabled
+    digest: Option<Sha256>,
+
+    // Reused by write_chunk, rather than allocating a copy per chunk
+    scratch: Vec<u8>,
+}
+
+impl<'a, P> EncryptedChannel<'a, P> {
+    /// Create a channel from a derived session key and the
+    /// NonceSequence used for the remainder of the session
+    pub fn new(peer: &'a mut P, key: &'a [u8], nseq: &'a mut NonceSequence) -> Self {
+        EncryptedChannel {
+            peer,
+            key,
+            nseq,
+            checksums: false,
+            codec: CodecVersion::V1,
+            file: None,
+            digest: None,
+            scratch: Vec::new(),
+        }
+    }
+
+    /// Follow each chunk with a checksum trailer. Both peers must agree.
+    pub fn with_checksums(mut self, checksums: bool) -> Self {
+        self.checksums = checksums;
+        self
+    }
+
+    /// Frame headers with the session's negotiated codec
+    pub fn with_codec(mut self, codec: CodecVersion) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    /// Authenticate the number of the file each chunk belongs to & its
+    /// offset within the file's data, counted from the first chunk sent
+    /// on this channel. Both peers must agree.
+    pub fn with_file(mut self, file: Option<u32>) -> Self {
+        self.file = file.map(|file| (file, 0));
+        self
+    }
+
+    /// Hash the plaintext of each chunk sent or received, so a file's
+    /// digest is known once its last chunk is, without another pass
+    pub fn with_digest(mut self, digest: bool) -> Self {
+        self.digest = digest.then(Sha256::new);
+        self
+    }
+
+    /// SHA-256 of the plaintext of every chunk sent or received so far,
+    /// if enabled with [`EncryptedChannel::with_digest`]
+    pub fn digest(&self) -> Option<[u8; 32]> {
+        self.digest.as_ref().map(|h| h.clone().finalize().into())
+    }
+
+    /// Helper: the associated data of the next chunk, empty unless bound
+    fn aad(&self) -> ChunkAad {
+        let mut aad = ChunkAad::default();
+        if let Some((file, offset)) = self.file {
+            aad.data[..4].copy_from_slice(&file.to_le_bytes());
+            aad.data[4..].copy_from_slice(&offset.to_le_bytes());
+            aad.len = aad.data.len();
+        }
+        aad
+    }
+
+    /// Helper: move past a chunk sent or received
+    fn advance(&mut self, len: usize) {
+        if let Some((_, offset)) = &mut self.file {
+            *offset += len as u64;
+        }
+    }
+
+    /// Helper: hash & move past a chunk received
+    fn received(&mut self, plaintext: &[u8]) {
+        if let Some(hasher) = self.digest.as_mut() {
+            hasher.update(plaintext);
+        }
+        self.advance(plaintext.len());
+    }
+}
+
+/// The associated data of a chunk, a file number & offset when bound
+#[derive(Default)]
+struct ChunkAad {
+    data: [u8; 12],
+    len: usize,
+}
+
+impl ChunkAad {
+    fn as_bytes(&self) -> &[u8] {
+        &self.data[..self.len]
+    }
+}
+
+/// Numbers the files of a session in the order they're sent, which
+/// both peers count to bind each file's chunks to it
+#[derive(PartialEq, Eq, Debug, Default)]
+pub(crate) struct FileNumbers(u32);
+
+impl FileNumbers {
+    /// Number the next file, returning it if chunks are bound
+    pub(crate) fn next(&mut self, bind: bool) -> Option<u32> {
+        let file = self.0;
+        self.0 = self.0.wrapping_add(1);
+        bind.then_some(file)
+    }
+}
+
+/// Helper: CRC32C of a chunk's header & ciphertext
+fn checksum(header: &EncryptedMessage, data: &[u8]) -> u32 {
+    let crc = crc32c::crc32c(&header.nonce);
+    let crc = crc32c::crc32c_append(crc, &header.tag);
+    let crc = match header.commitment.as_ref() {
+        Some(commitment) => crc32c::crc32c_append(crc, commitment),
+        None => crc,
+    };
+    let crc = crc32c::crc32c_append(crc, &(header.len as u64).to_le_bytes());
+    crc32c::crc32c_append(crc, data)
+}
+
+/// Helper: write every buffer, in as few calls as the writer allows. A
+/// TcpStream issues a single writev, writers without vectored support
+/// fall back to writing one buffer per call.
+fn write_all_vectored<W: Write>(writer: &mut W, mut bufs: &mut [IoSlice]) -> io::Result<()> {
+    IoSlice::advance_slices(&mut bufs, 0);
+    while !bufs.is_empty() {
+        match writer.write_vectored(bufs) {
+            Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+            Ok(n) => IoSlice::advance_slices(&mut bufs, n),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+impl<'a, P: Write> EncryptedChannel<'a, P> {
+    /// Encrypt a copy of the chunk & send it to the peer. The copy is
+    /// made into a buffer the channel reuses for every chunk.
+    pub fn write_chunk(&mut self, chunk: &[u8]) -> Result<usize, Box<dyn Error>> {
+        let mut scratch = std::mem::take(&mut self.scratch);
+        scratch.clear();
+        scratch.extend_from_slice(chunk);
+        let res = self.write_chunk_in_place(&mut scratch);
+        self.scratch = scratch;
+        res
+    }
+
+    /// Encrypt the chunk in-place & send it to the peer. Avoids a copy
+    /// when the caller no longer needs the plaintext.
+    pub fn write_chunk_in_place(&mut self, chunk: &mut [u8]) -> Result<usize, Box<dyn Error>> {
+        // Hash the plaintext if enabled, encrypt the chunk in-place, then
+        // checksum the ciphertext if enabled
+        if let Some(hasher) = self.digest.as_mut() {
+            hasher.update(&*chunk);
+        }
+        let aad = self.aad();
+        let header = EncryptedMessage::encrypt_with(self.key, self.nseq, chunk, aad.as_bytes())?
+            .committed(self.key, self.codec)?;
+        let crc = match self.checksums {
+            true => Some(checksum(&header, chunk).to_le_bytes()),
+            false => None,
+        };
+
+        // Send the header, ciphertext & trailer together
+        let mut encoded = [0u8; MAX_DATA_HEADER];
+        let len = self.codec.encode_data_header(&header, &mut encoded)?;
+        let trailer = crc.as_ref().map_or(&[][..], |c| &c[..]);
+        let mut bufs = [
+            IoSlice::new(&encoded[..len]),
+            IoSlice::new(chunk),
+            IoSlice::new(trailer),
+        ];
+        write_all_vectored(self.peer, &mut bufs).or(Err(IOError))?;
+        self.advance(chunk.len());
+        Ok(chunk.len())
+    }
+
+    /// Send the end-of-stream marker, an empty chunk
+    pub fn finish(&mut self) -> Result<(), Box<dyn Error>> {
+        self.write_chunk_in_place(&mut [])?;
+        Ok(())
+    }
+
+    /// Tell the peer that the rest of the current file won't be sent, such
+    /// as when it shrank or became unreadable. The peer's next
+    /// [`EncryptedChannel::read_chunk`] fails with [`FileChangedDuringTransfer`]
+    /// & the session may continue with the next file.
+    ///
+    /// [`FileChangedDuringTransfer`]: crate::errors::PortalError::FileChangedDuringTransfer
+    pub fn abort(&mut self) -> Result<(), Box<dyn Error>> {
+        Protocol::send_file_aborted(self.peer, self.key, self.nseq, self.codec)
+    }
+}
+
+impl<'a, P: Read> EncryptedChannel<'a, P> {
+    /// Receive the next chunk from the peer, decrypting it into the
+    /// provided storage region. Returns the length of the chunk, or
+    /// BufferTooSmall if the storage cannot hold it. A length of zero
+    /// is the end-of-stream marker.
+    pub fn read_chunk(&mut self, storage: &mut [u8]) -> Result<usize, Box<dyn Error>> {
+        let aad = self.aad();
+        let mut header = Protocol::recv_encrypted_header(self.peer, self.key, self.codec)?;
+        if !self.checksums {
+            let len = Protocol::read_encrypted_body(
+                self.peer,
+                self.key,
+                header,
+                storage,
+                aad.as_bytes(),
+            )?;
+            self.received(&storage[..len]);
+            return Ok(len);
+        }
+
+        let data = storage.get_mut(..header.len).ok_or(BufferTooSmall)?;
+        self.peer.read_exact(data).or(Err(IOError))?;
+
+        // Corruption is detected before decryption, which would fail anyway
+        let mut trailer = [0u8; 4];
+        self.peer.read_exact(&mut trailer).or(Err(IOError))?;
+        if u32::from_le_bytes(trailer) != checksum(&header, data) {
+            return Err(Corrupted.into());
+        }
+        let len = header.decrypt_with(self.key, data, aad.as_bytes())?;
+        self.received(&data[..len]);
+        Ok(len)
+    }
+}
@@ -0,0 +1,149 @@
+//! Adaptive chunk sizing for file transfers
+use crate::{CHUNK_SIZE, MAX_CHUNK_SIZE};
+use std::fmt;
+use std::time::Duration;
+
+/// How long to measure throughput before adjusting the chunk size
+const SAMPLE_TIME: Duration = Duration::from_millis(100);
+
+/// Round trips a throughput sample spans at least. Shorter samples on
+/// high latency links mostly measure TCP's congestion window opening.
+const SAMPLE_RTTS: u32 = 4;
+
+/// Chunks that take longer than this to send are shrunk straight away,
+/// keeping progress responsive on slow or high latency links
+const MAX_CHUNK_TIME: Duration = Duration::from_millis(250);
+
+/// Grows or shrinks the chunk length during a transfer, within fixed
+/// bounds, based on the throughput observed while sending. Larger chunks
+/// amortize per-chunk overhead on fast links, smaller ones keep each write
+/// short on slow ones.
+///
+/// The size moves in powers of two, continuing in the same direction while
+/// throughput improves and turning back when it drops.
+///
+/// ```
+/// use std::time::Duration;
+/// use portal_lib::ChunkSizer;
+///
+/// let mut sizer = ChunkSizer::new(16 * 1024, 1024 * 1024);
+/// let before = sizer.size();
+///
+/// // A chunk that took a full second to send is too large
+/// sizer.record(before, Duration::from_secs(1));
+/// assert!(sizer.size() < before);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChunkSizer {
+    size: usize,
+    min: usize,
+    max: usize,
+
+    // Direction of the last adjustment & the throughput that led to it
+    grow: bool,
+    last_rate: f64,
+
+    // The current throughput sample
+    bytes: usize,
+    elapsed: Duration,
+    sample_time: Duration,
+}
+
+impl ChunkSizer {
+    /// Start at the default [`CHUNK_SIZE`], adapting between `min` & `max`
+    pub fn new(min: usize, max: usize) -> Self {
+        ChunkSizer {
+            size: CHUNK_SIZE.clamp(min, max),
+            min,
+            max,
+            grow: true,
+            last_rate: 0.0,
+            bytes: 0,
+            elapsed: Duration::ZERO,
+            sample_time: SAMPLE_TIME,
+        }
+    }
+
+    /// Always use the same chunk size
+    pub fn fixed(size: usize) -> Self {
+        Self::new(size, size)
+    }
+
+    /// Take the round-trip time to the peer into account, sampling
+    /// throughput over several round trips on high latency links
+    pub fn set_rtt(&mut self, rtt: Duration) {
+        self.sample_time = SAMPLE_TIME.max(rtt * SAMPLE_RTTS);
+    }
+
+    /// The length to use for the next chunk
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The largest chunk this may use
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    /// Record how long a chunk of `len` bytes took to send
+    pub fn record(&mut self, len: usize, elapsed: Duration) {
+        if elapsed > MAX_CHUNK_TIME {
+            self.grow = false;
+            self.adjust();
+            return;
+        }
+
+        self.bytes += len;
+        self.elapsed += elapsed;
+        if self.elapsed < self.sample_time {
+            return;
+        }
+
+        // Turn back once a change made throughput worse
+        let rate = self.bytes as f64 / self.elapsed.as_secs_f64();
+        if rate < self.last_rate {
+            self.grow = !self.grow;
+        }
+        self.last_rate = rate;
+        self.adjust();
+    }
+
+    /// Helper: move the size one step & start a new sample
+    fn adjust(&mut self) {
+        let size = match self.grow {
+            true => self.size.saturating_mul(2),
+            false => self.size / 2,
+        };
+        self.size = size.clamp(self.min, self.max);
+        self.bytes = 0;
+        self.elapsed = Duration::ZERO;
+    }
+}
+
+/// Storage for the chunks of every file a Portal transfers, kept between
+/// files. Grows to the largest chunk needed, unless it was reserved up
+/// front for constant memory mode.
+#[derive(Default, PartialEq, Eq)]
+pub(crate) struct ChunkBuffer(Vec<u8>);
+
+impl ChunkBuffer {
+    /// Allocate room for the largest chunk any peer may send, so that
+    /// nothing is allocated once a transfer starts
+    pub fn reserve(&mut self) {
+        self.0.resize(MAX_CHUNK_SIZE, 0);
+    }
+
+    /// Room for a chunk of `len` bytes
+    pub fn get(&mut self, len: usize) -> &mut [u8] {
+        if self.0.len() < len {
+            self.0.resize(len, 0);
+        }
+        &mut self.0[..len]
+    }
+}
+
+impl fmt::Debug for ChunkBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ChunkBuffer({} bytes)", self.0.len())
+    }
+}
@@ -0,0 +1,218 @@
+//! Content-defined chunking & deduplication of repeated data within a transfer
+use crate::errors::PortalError::*;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
+use std::convert::TryInto;
+use std::error::Error;
+use std::fs::File;
+
+/// Smallest chunk cut from a deduplicated file, other than its last
+pub const DEDUP_MIN_CHUNK: usize = 16 * 1024;
+
+/// Largest chunk cut from a deduplicated file
+pub const DEDUP_MAX_CHUNK: usize = 256 * 1024;
+
+/// Files a reference may point back into, including the one being sent.
+/// The receiver keeps the files before it open until they leave the window.
+pub const DEDUP_WINDOW: usize = 16;
+
+/// Most chunks the sender remembers per session, about 16 MiB of index
+pub const MAX_INDEXED_CHUNKS: usize = 1 << 18;
+
+/// Length of an encoded reference, including its tag
+pub const REFERENCE_SIZE: usize = 17;
+
+/// Cut where the top bits of the rolling hash are zero, about 64 KiB
+/// past the minimum on average
+const CUT_MASK: u64 = 0xffff << 48;
+
+/// Tags leading each chunk of a deduplicated file
+const LITERAL: u8 = 0;
+const REFERENCE: u8 = 1;
+
+/// Random values mixed into the rolling hash for each byte
+static GEAR: [u64; 256] = gear();
+
+/// Helper: fill the gear table from splitmix64, so that every build
+/// cuts the same data at the same boundaries
+const fn gear() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut state = 0u64;
+    let mut i = 0;
+    while i < table.len() {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        table[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    table
+}
+
+/// Find the length of the next content-defined chunk at the start of
+/// `data`. Boundaries depend only on the 64 bytes before them, so data
+/// repeated at any offset is cut the same way once a boundary is found
+/// within it. `data` must hold at least [`DEDUP_MAX_CHUNK`] bytes unless
+/// it is the end of the file.
+///
+/// ```
+/// use portal_lib::dedup::{cut, DEDUP_MAX_CHUNK, DEDUP_MIN_CHUNK};
+///
+/// let data = vec![0u8; 2 * DEDUP_MAX_CHUNK];
+/// assert_eq!(cut(&data), DEDUP_MAX_CHUNK);
+/// assert_eq!(cut(&data[..DEDUP_MIN_CHUNK]), DEDUP_MIN_CHUNK);
+/// ```
+pub fn cut(data: &[u8]) -> usize {
+    let end = data.len().min(DEDUP_MAX_CHUNK);
+    if end <= DEDUP_MIN_CHUNK {
+        return end;
+    }
+
+    let mut hash = 0u64;
+    for (i, byte) in data[DEDUP_MIN_CHUNK..end].iter().enumerate() {
+        hash = (hash << 1).wrapping_add(GEAR[*byte as usize]);
+        if hash & CUT_MASK == 0 {
+            return DEDUP_MIN_CHUNK + i + 1;
+        }
+    }
+    end
+}
+
+/// A chunk sent earlier in the session, which a later chunk of a
+/// deduplicated file refers to rather than repeating its data
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct ChunkRef {
+    /// The deduplicated file it was sent in, numbered from zero in the
+    /// order they were sent
+    pub file: u32,
+    pub offset: u64,
+    pub len: u32,
+}
+
+/// One chunk of a deduplicated file, as decrypted by the receiver
+#[derive(PartialEq, Eq, Debug)]
+pub enum DedupChunk<'a> {
+    /// Data sent for the first time
+    Literal(&'a [u8]),
+    /// Data the receiver already has
+    Reference(ChunkRef),
+}
+
+impl ChunkRef {
+    /// Encode the reference as a chunk, tag included
+    pub fn encode(&self) -> [u8; REFERENCE_SIZE] {
+        let mut out = [0u8; REFERENCE_SIZE];
+        out[0] = REFERENCE;
+        out[1..5].copy_from_slice(&self.file.to_le_bytes());
+        out[5..13].copy_from_slice(&self.offset.to_le_bytes());
+        out[13..].copy_from_slice(&self.len.to_le_bytes());
+        out
+    }
+}
+
+impl<'a> DedupChunk<'a> {
+    /// Tag the data in `chunk[1..]` as a literal, returning the chunk to send
+    pub fn literal(chunk: &mut [u8]) -> &mut [u8] {
+        chunk[0] = LITERAL;
+        chunk
+    }
+
+    /// Parse a decrypted chunk of a deduplicated file
+    ///
+    /// ```
+    /// use portal_lib::dedup::{ChunkRef, DedupChunk};
+    ///
+    /// let reference = ChunkRef { file: 1, offset: 4096, len: 512 };
+    /// let encoded = reference.encode();
+    /// assert_eq!(DedupChunk::parse(&encoded).unwrap(), DedupChunk::Reference(reference));
+    /// assert_eq!(DedupChunk::parse(&[0, 7, 7]).unwrap(), DedupChunk::Literal(&[7, 7]));
+    /// ```
+    pub fn parse(chunk: &'a [u8]) -> Result<Self, Box<dyn Error>> {
+        match chunk.split_first() {
+            Some((&LITERAL, data)) => Ok(DedupChunk::Literal(data)),
+            Some((&REFERENCE, data)) if data.len() == REFERENCE_SIZE - 1 => {
+                Ok(DedupChunk::Reference(ChunkRef {
+                    file: u32::from_le_bytes(data[..4].try_into()?),
+                    offset: u64::from_le_bytes(data[4..12].try_into()?),
+                    len: u32::from_le_bytes(data[12..].try_into()?),
+                }))
+            }
+            _ => Err(BadMsg.into()),
+        }
+    }
+}
+
+/// Per-session state of deduplicated files. The sender indexes the
+/// chunks it sent by their SHA-256 digest, the receiver keeps the files
+/// those chunks were written to open so references can be copied from
+/// them, even if the files have since been moved.
+#[derive(Debug, Default)]
+pub(crate) struct DedupIndex {
+    // Deduplicated files started so far, by either side
+    files: u32,
+
+    // The sender's chunks & where it first sent them
+    chunks: HashMap<[u8; 32], ChunkRef>,
+
+    // The receiver's files before the one being received, most recent
+    // last, or None where a file was aborted
+    recent: VecDeque<Option<File>>,
+}
+
+/// Session state is ignored when comparing Portals
+impl PartialEq for DedupIndex {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for DedupIndex {}
+
+impl DedupIndex {
+    /// Number the next deduplicated file, forgetting any chunks sent in
+    /// files that have left the window
+    pub(crate) fn start_file(&mut self) -> u32 {
+        let file = self.files;
+        self.files += 1;
+        self.chunks
+            .retain(|_, r| r.file as usize + DEDUP_WINDOW > file as usize);
+        file
+    }
+
+    /// Find an earlier chunk with the same contents, or remember this one
+    pub(crate) fn lookup(&mut self, data: &[u8], file: u32, offset: u64) -> Option<ChunkRef> {
+        let digest: [u8; 32] = Sha256::digest(data).into();
+        if let Some(found) = self.chunks.get(&digest) {
+            return Some(*found);
+        }
+        if self.chunks.len() < MAX_INDEXED_CHUNKS {
+            let len = data.len() as u32;
+            self.chunks.insert(digest, ChunkRef { file, offset, len });
+        }
+        None
+    }
+
+    /// A file received before the current one that references may still
+    /// point into
+    pub(crate) fn received(&self, file: u32) -> Option<&File> {
+        let current = self.files.checked_sub(1)?;
+        let first = current - self.recent.len() as u32;
+        self.recent.get(file.checked_sub(first)? as usize)?.as_ref()
+    }
+
+    /// Forget the chunks of a file the sender aborted, which the receiver
+    /// discards
+    pub(crate) fn forget_file(&mut self, file: u32) {
+        self.chunks.retain(|_, r| r.file != file);
+    }
+
+    /// Keep a completely received file open for later references, or
+    /// None if it was aborted
+    pub(crate) fn finish_file(&mut self, file: Option<File>) {
+        self.recent.push_back(file);
+        while self.recent.len() >= DEDUP_WINDOW {
+            self.recent.pop_front();
+        }
+    }
+}
@@ -40,4 +40,46 @@ pub enum PortalError {
     WouldBlock,
     #[error("Object could not be serialized")]
     SerializeError,
+    #[error("Preview exceeds the maximum size")]
+    PreviewTooLarge,
+    #[error("Note exceeds the maximum size")]
+    NoteTooLarge,
+    #[error("Message exceeds the maximum size")]
+    MessageTooLarge,
+    #[error("Unexpected data after the message")]
+    TrailingBytes,
+    #[error("Message received out of order")]
+    UnexpectedMessage,
+    #[error("Paired with a peer for a different request")]
+    PeerMismatch,
+    #[error("Repeated failed confirmations, the pass-phrase may be under attack")]
+    PossibleInterference,
+    #[error("Chunk was corrupted in transit")]
+    Corrupted,
+    #[error("Another Receiver already claimed this session, check the pass-phrase")]
+    SessionClaimed,
+    #[error("The relay is at capacity, try one of {0:?}")]
+    Redirected(Vec<String>),
+    #[error("The relay ended the transfer, it limits sessions to {0} bytes")]
+    TransferLimit(u64),
+    #[error("Invalid portal:// URI, {0}")]
+    BadUri(String),
+    #[error("portal:// URI version {0} isn't supported, try updating")]
+    UnsupportedUriVersion(u32),
+    #[error("Invalid Portal configuration, {0}")]
+    BadConfig(String),
+    #[error("Transfer exceeds the receive quota, {0}")]
+    QuotaExceeded(String),
+    #[error("The relay is too old, it speaks protocol version {0}")]
+    RelayTooOld(u32),
+    #[error("The relay requires protocol version {0} or newer, try updating")]
+    RelayTooNew(u32),
+    #[error("The peer's version of portal is too old to transfer with, it must be updated")]
+    PeerTooOld,
+    #[error("The file changed or became unreadable after it was offered, it was skipped")]
+    FileChangedDuringTransfer,
+    #[error("The received file doesn't match the sender's digest")]
+    DigestMismatch,
+    #[error("The relay ended the transfer, it limits sessions to {0} seconds")]
+    SessionExpired(u64),
 }
@@ -0,0 +1,227 @@
+//! Exercises Portal over a transport that misbehaves the way real networks
+//! do: reads & writes that only move part of the buffer, interrupted calls,
+//! stalls, and connections that drop mid-transfer.
+use crate::tests::MockTcpStream;
+use crate::{Direction, Portal, TransferInfo, TransferInfoBuilder, NO_PROGRESS_CALLBACK};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::thread;
+use std::time::Duration;
+use tempdir::TempDir;
+
+/// Seeds tried by each test, keeping failures reproducible
+const SEEDS: [u64; 4] = [1, 7, 42, 1337];
+
+/// Wraps a stream, injecting faults at configurable probabilities
+pub struct FaultyTransport<S> {
+    inner: S,
+    rng: StdRng,
+    partial: f64,
+    interrupt: f64,
+    delay: f64,
+    delay_for: Duration,
+    disconnect: f64,
+    disconnected: bool,
+}
+
+impl<S> FaultyTransport<S> {
+    /// A transport that behaves until faults are enabled
+    pub fn new(inner: S, seed: u64) -> Self {
+        FaultyTransport {
+            inner,
+            rng: StdRng::seed_from_u64(seed),
+            partial: 0.0,
+            interrupt: 0.0,
+            delay: 0.0,
+            delay_for: Duration::from_millis(1),
+            disconnect: 0.0,
+            disconnected: false,
+        }
+    }
+
+    /// Only read or write part of the buffer
+    pub fn partial(mut self, p: f64) -> Self {
+        self.partial = p;
+        self
+    }
+
+    /// Fail with ErrorKind::Interrupted, which callers must retry
+    pub fn interrupts(mut self, p: f64) -> Self {
+        self.interrupt = p;
+        self
+    }
+
+    /// Stall before the operation
+    pub fn delays(mut self, p: f64, delay_for: Duration) -> Self {
+        self.delay = p;
+        self.delay_for = delay_for;
+        self
+    }
+
+    /// Drop the connection, failing this & every later operation
+    pub fn disconnects(mut self, p: f64) -> Self {
+        self.disconnect = p;
+        self
+    }
+
+    /// Helper: decide the fate of the next operation on `len` bytes,
+    /// returning how many bytes it may move
+    fn inject(&mut self, len: usize) -> io::Result<usize> {
+        if self.disconnected || self.rng.gen_bool(self.disconnect) {
+            self.disconnected = true;
+            return Err(io::ErrorKind::ConnectionReset.into());
+        }
+        if self.rng.gen_bool(self.interrupt) {
+            return Err(io::ErrorKind::Interrupted.into());
+        }
+        if self.rng.gen_bool(self.delay) {
+            thread::sleep(self.delay_for);
+        }
+        match len > 1 && self.rng.gen_bool(self.partial) {
+            true => Ok(self.rng.gen_range(1, len)),
+            false => Ok(len),
+        }
+    }
+}
+
+impl<S: Read> Read for FaultyTransport<S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inject(buf.len())?;
+        self.inner.read(&mut buf[..len])
+    }
+}
+
+impl<S: Write> Write for FaultyTransport<S> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.inject(buf.len())?;
+        self.inner.write(&buf[..len])
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Helper: a file large enough to span many chunks
+fn random_file(dir: &TempDir) -> (std::path::PathBuf, Vec<u8>) {
+    let mut data = vec![0u8; 300_000];
+    rand::thread_rng().fill(&mut data[..]);
+    let path = dir.path().join("randomfile.bin");
+    File::create(&path).unwrap().write_all(&data).unwrap();
+    (path, data)
+}
+
+/// Helper: a sender & receiver over a mock channel, each
+/// wrapped in a transport that misbehaves but never disconnects
+fn faulty_pair(
+    seed: u64,
+) -> (
+    FaultyTransport<MockTcpStream>,
+    FaultyTransport<MockTcpStream>,
+) {
+    let (senderstream, receiverstream) = MockTcpStream::channel();
+    let faulty = |stream, seed| {
+        FaultyTransport::new(stream, seed)
+            .partial(0.5)
+            .interrupts(0.1)
+            .delays(0.01, Duration::from_millis(1))
+    };
+    (faulty(senderstream, seed), faulty(receiverstream, !seed))
+}
+
+#[test]
+fn faulty_handshake_suceeds() {
+    for seed in SEEDS {
+        let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+        let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+        let (mut senderstream, mut receiverstream) = faulty_pair(seed);
+
+        let sender_thread = thread::spawn(move || {
+            sender.handshake(&mut senderstream).unwrap();
+            sender.get_key().clone()
+        });
+
+        receiver.handshake(&mut receiverstream).unwrap();
+        assert_eq!(receiver.get_key(), &sender_thread.join().unwrap());
+    }
+}
+
+#[test]
+fn faulty_transfer_roundtrip() {
+    for seed in SEEDS {
+        let tmp_dir = TempDir::new("faulty_transfer_roundtrip").unwrap();
+        let out_dir = TempDir::new("faulty_transfer_roundtrip_out").unwrap();
+        let (path, data) = random_file(&tmp_dir);
+
+        let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+        let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+        let (mut senderstream, mut receiverstream) = faulty_pair(seed);
+
+        let sender_thread = thread::spawn(move || {
+            sender.handshake(&mut senderstream).unwrap();
+            let info = TransferInfoBuilder::new()
+                .add_file(&path)
+                .unwrap()
+                .finalize();
+            for (path, _) in sender.outgoing(&mut senderstream, &info).unwrap() {
+                sender
+                    .send_file(&mut senderstream, path, NO_PROGRESS_CALLBACK)
+                    .unwrap();
+            }
+        });
+
+        receiver.handshake(&mut receiverstream).unwrap();
+        let accept = Some(|_: &TransferInfo| true);
+        for m in receiver.incoming(&mut receiverstream, accept).unwrap() {
+            receiver
+                .recv_file(
+                    &mut receiverstream,
+                    out_dir.path(),
+                    Some(&m),
+                    NO_PROGRESS_CALLBACK,
+                )
+                .unwrap();
+        }
+        sender_thread.join().unwrap();
+
+        let received = std::fs::read(out_dir.path().join("randomfile.bin")).unwrap();
+        assert!(received == data);
+    }
+}
+
+#[test]
+fn faulty_disconnect_fails_cleanly() {
+    for seed in SEEDS {
+        let tmp_dir = TempDir::new("faulty_disconnect").unwrap();
+        let out_dir = TempDir::new("faulty_disconnect_out").unwrap();
+        let (path, _) = random_file(&tmp_dir);
+
+        let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+        let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+        let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+
+        let sender_thread = thread::spawn(move || {
+            sender.handshake(&mut senderstream).unwrap();
+            sender
+                .send_file(&mut senderstream, &path, NO_PROGRESS_CALLBACK)
+                .is_ok()
+        });
+
+        // Only the receiver drops, the mock never blocks the sender's writes
+        receiver.handshake(&mut receiverstream).unwrap();
+        let mut receiverstream = FaultyTransport::new(receiverstream, seed)
+            .partial(0.5)
+            .disconnects(0.2);
+
+        let result = receiver.recv_file(
+            &mut receiverstream,
+            out_dir.path(),
+            None,
+            NO_PROGRESS_CALLBACK,
+        );
+        assert!(result.is_err());
+        assert!(sender_thread.join().unwrap());
+    }
+}
@@ -0,0 +1,299 @@
+//! File IO helpers shared by the higher and lower level APIs
+use memmap::{MmapMut, MmapOptions};
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::path::Path;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use std::os::unix::io::AsRawFd;
+
+/// Kernel hints applied to files during a transfer. Useful on servers
+/// moving very large files, where the default behavior would otherwise
+/// evict much of the page cache. All hints are best-effort and are
+/// silently skipped on platforms that don't support them.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default)]
+pub struct TransferTuning {
+    /// Advise the kernel that mapped files will be accessed sequentially,
+    /// allowing more aggressive read-ahead and early page reclaim
+    pub sequential: bool,
+
+    /// Drop the file's pages from the page cache once it has been
+    /// completely sent or received
+    pub drop_cache: bool,
+}
+
+/// Default length of the region of a file mapped at once while sending
+/// or receiving, bounding the memory a transfer keeps resident
+pub const MMAP_WINDOW_SIZE: usize = 64 * 1024 * 1024;
+
+/// Windows start at a multiple of this, which covers the page size &
+/// mapping granularity of every supported platform
+const MMAP_ALIGN: usize = 64 * 1024;
+
+/// Round a requested window length to one that is aligned, and that
+/// always fits the largest chunk however the window is placed
+pub(crate) fn window_size(len: usize) -> usize {
+    let len = len.max(crate::MAX_CHUNK_SIZE + MMAP_ALIGN);
+    len.div_ceil(MMAP_ALIGN) * MMAP_ALIGN
+}
+
+/// A window over a file mapped for writing, slid forward through the
+/// file as it is received so only one window is ever mapped
+pub(crate) struct WriteWindow {
+    file: File,
+    map: MmapMut,
+    start: usize,
+    size: usize,
+    window: usize,
+    sequential: bool,
+    write_back: bool,
+}
+
+impl WriteWindow {
+    /// Map the first `window` bytes of a file already allocated to `size`
+    /// bytes, see [`window_size`]. With `write_back` each window is
+    /// flushed before it is unmapped.
+    pub(crate) fn new(
+        file: File,
+        size: usize,
+        window: usize,
+        sequential: bool,
+        write_back: bool,
+    ) -> io::Result<Self> {
+        let map = Self::map(&file, 0, size.min(window), sequential)?;
+        Ok(WriteWindow {
+            file,
+            map,
+            start: 0,
+            size,
+            window,
+            sequential,
+            write_back,
+        })
+    }
+
+    /// Helper: map a window, applying the sequential hint if requested
+    fn map(file: &File, start: usize, len: usize, sequential: bool) -> io::Result<MmapMut> {
+        let map = unsafe {
+            MmapOptions::new()
+                .offset(start as u64)
+                .len(len)
+                .map_mut(file)?
+        };
+
+        // Hints are best-effort, failures are not fatal
+        if sequential {
+            let _ = advise_sequential(&map);
+        }
+        Ok(map)
+    }
+
+    /// Length of the whole file
+    pub(crate) fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Storage from `pos` to the end of the window, first sliding the
+    /// window forward if fewer than `needs` bytes remain in it
+    pub(crate) fn at(&mut self, pos: usize, needs: usize) -> io::Result<&mut [u8]> {
+        if pos + needs > self.start + self.map.len() {
+            if self.write_back {
+                self.map.flush()?;
+            }
+            let start = pos - pos % MMAP_ALIGN;
+            let len = (self.size - start).min(self.window);
+            self.map = Self::map(&self.file, start, len, self.sequential)?;
+            self.start = start;
+        }
+        Ok(&mut self.map[pos - self.start..])
+    }
+
+    /// Write back the part of `from..to` still mapped. Earlier
+    /// windows were written back as they were unmapped.
+    pub(crate) fn flush_range(&self, from: usize, to: usize) -> io::Result<()> {
+        let from = from.max(self.start);
+        match to > from {
+            true => self.map.flush_range(from - self.start, to - from),
+            false => Ok(()),
+        }
+    }
+
+    /// Write back the current window
+    pub(crate) fn flush(&self) -> io::Result<()> {
+        self.map.flush()
+    }
+}
+
+/// How hard the receiver works to ensure a file survives a crash or power
+/// loss before reporting it complete. Stronger guarantees cost throughput,
+/// so by default the data is left to the kernel to write back.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default)]
+pub enum Durability {
+    /// Leave write back to the kernel
+    #[default]
+    None,
+
+    /// Sync each file, and its directory entry, once it is received
+    PerFile,
+
+    /// Also sync every time this many bytes have been received
+    EveryBytes(u64),
+
+    /// Sync each chunk as it is written, using O_DSYNC where available
+    Dsync,
+}
+
+impl Durability {
+    /// Bytes to receive between syncs, if synced during the transfer.
+    /// `kernel_dsync` is true if the file was opened with O_DSYNC.
+    pub(crate) fn interval(&self, kernel_dsync: bool) -> Option<usize> {
+        match *self {
+            Durability::EveryBytes(n) => Some((n as usize).max(1)),
+            Durability::Dsync if !kernel_dsync => Some(1),
+            _ => None,
+        }
+    }
+}
+
+/// Open files with O_DSYNC if requested, returning whether it was applied
+#[cfg(unix)]
+pub(crate) fn apply_dsync(options: &mut OpenOptions, durability: Durability) -> bool {
+    use std::os::unix::fs::OpenOptionsExt;
+    if durability != Durability::Dsync {
+        return false;
+    }
+    options.custom_flags(libc::O_DSYNC);
+    true
+}
+
+/// Open files with O_DSYNC if requested, returning whether it was applied
+#[cfg(not(unix))]
+pub(crate) fn apply_dsync(_options: &mut OpenOptions, _durability: Durability) -> bool {
+    false
+}
+
+/// Flush a file, and the directory entry naming it, to stable storage
+pub fn sync_path(path: &Path) -> io::Result<()> {
+    File::open(path)?.sync_all()?;
+    sync_parent(path)
+}
+
+/// Flush the directory containing `path` so that a newly created
+/// entry survives a crash
+#[cfg(unix)]
+fn sync_parent(path: &Path) -> io::Result<()> {
+    let parent = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    File::open(parent)?.sync_all()
+}
+
+/// Directories can't be opened for syncing on this platform
+#[cfg(not(unix))]
+fn sync_parent(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+/// Fill the buffer from the file at the provided offset, without
+/// depending on or moving a shared cursor
+#[cfg(unix)]
+pub fn read_exact_at(file: &File, buf: &mut [u8], offset: u64) -> io::Result<()> {
+    use std::os::unix::fs::FileExt;
+    file.read_exact_at(buf, offset)
+}
+
+/// Fill the buffer from the file at the provided offset, without
+/// depending on or moving a shared cursor
+#[cfg(windows)]
+pub fn read_exact_at(file: &File, mut buf: &mut [u8], mut offset: u64) -> io::Result<()> {
+    use std::os::windows::fs::FileExt;
+    while !buf.is_empty() {
+        match file.seek_read(buf, offset) {
+            Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(n) => {
+                buf = &mut buf[n..];
+                offset += n as u64;
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+/// Write the entire buffer into the file at the provided offset. Unlike
+/// a sequential write this doesn't depend on, or move, a shared cursor
+/// so chunks may be written in any order.
+#[cfg(unix)]
+pub fn write_all_at(file: &File, buf: &[u8], offset: u64) -> io::Result<()> {
+    use std::os::unix::fs::FileExt;
+    file.write_all_at(buf, offset)
+}
+
+/// Write the entire buffer into the file at the provided offset. Unlike
+/// a sequential write this doesn't depend on, or move, a shared cursor
+/// so chunks may be written in any order.
+#[cfg(windows)]
+pub fn write_all_at(file: &File, mut buf: &[u8], mut offset: u64) -> io::Result<()> {
+    use std::os::windows::fs::FileExt;
+    while !buf.is_empty() {
+        match file.seek_write(buf, offset) {
+            Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+            Ok(n) => {
+                buf = &buf[n..];
+                offset += n as u64;
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+/// Advise the kernel that the mapped region will be accessed sequentially
+#[cfg(unix)]
+pub fn advise_sequential(region: &[u8]) -> io::Result<()> {
+    if region.is_empty() {
+        return Ok(());
+    }
+
+    let res = unsafe {
+        libc::madvise(
+            region.as_ptr() as *mut libc::c_void,
+            region.len(),
+            libc::MADV_SEQUENTIAL,
+        )
+    };
+
+    if res != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Advise the kernel that the mapped region will be accessed sequentially
+#[cfg(not(unix))]
+pub fn advise_sequential(_region: &[u8]) -> io::Result<()> {
+    Ok(())
+}
+
+/// Ask the kernel to drop any cached pages for this file. Dirty pages
+/// must be flushed beforehand or they will remain cached.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn drop_cache(file: &File) -> io::Result<()> {
+    let res = unsafe { libc::posix_fadvise(file.as_raw_fd(), 0, 0, libc::POSIX_FADV_DONTNEED) };
+
+    // posix_fadvise returns the error number instead of setting errno
+    if res != 0 {
+        return Err(io::Error::from_raw_os_error(res));
+    }
+    Ok(())
+}
+
+/// Ask the kernel to drop any cached pages for this file. Dirty pages
+/// must be flushed beforehand or they will remain cached.
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub fn drop_cache(_file: &File) -> io::Result<()> {
+    Ok(())
+}
@@ -5,7 +5,7 @@
 //! - Create/serialize/deserialize Portal request/response messages.
 //! - Negoticate a symmetric key with a peer using [SPAKE2](https://docs.rs/spake2/0.2.0/spake2)
 //! - Encrypt files with [Chacha20-Poly1305](https://blog.cloudflare.com/it-takes-two-to-chacha-poly/) using either the
-//!     [RustCrypto](https://docs.rs/chacha20poly1305) implementation or [Ring's](https://briansmith.org/rustdoc/ring/aead/index.html)
+//!   [RustCrypto](https://docs.rs/chacha20poly1305) implementation or [Ring's](https://briansmith.org/rustdoc/ring/aead/index.html)
 //! - Send/receive files through a Portal relay
 //!
 //! The library is broken up into two abstractions:
@@ -13,11 +13,15 @@
 //! - A higher level API, exposted via the `Portal` struct, to facilitate automating transfers easily
 //! - A lower level API, exposed via the `protocol::Protocol` struct, if you need access to lower-level facilities
 use memmap::{MmapMut, MmapOptions};
+use std::collections::BTreeMap;
 use std::convert::TryInto;
 use std::error::Error;
 use std::fs::{File, OpenOptions};
 use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 // Key Exchange
 use sha2::{Digest, Sha256};
@@ -26,9 +30,62 @@ use spake2::{Ed25519Group, Identity, Password, Spake2};
 #[cfg(test)]
 mod tests;
 
+#[cfg(test)]
+mod faulty;
+
 // Allow users to access errors
 pub mod errors;
+
+/// Encrypted chunk transport for non-file payloads
+pub mod channel;
+pub use channel::EncryptedChannel;
+use channel::FileNumbers;
+
+/// File IO helpers, such as positioned writes
+pub mod file;
+
+/// Adaptive chunk sizing
+pub mod chunking;
+use chunking::ChunkBuffer;
+pub use chunking::ChunkSizer;
+
+/// Content-defined chunking to deduplicate data within a transfer
+pub mod dedup;
+use dedup::{DedupChunk, DEDUP_MAX_CHUNK};
+
+/// Soft limits & context for confirming incoming transfers
+pub mod policy;
+pub use policy::{ReceivePolicy, TransferLimits, TransferPolicy, Verify, WithPolicy};
+
+/// Pass-phrase generation & strength estimation
+pub mod passphrase;
+
+/// Session state shared between clones of a Portal
+mod shared;
+use shared::Shared;
+
+/// Fluent construction of a Portal request
+pub mod builder;
+pub use builder::PortalBuilder;
+
+/// portal:// URIs for sharing transfers as links
+pub mod uri;
+pub use uri::PortalUri;
+
+/// MAC-authenticated records of a session, for the peers to compare after the fact
+pub mod transcript;
+pub use transcript::Transcript;
+use transcript::TranscriptLog;
+
+/// Magic-wormhole transit relays as a fallback relay
+#[cfg(feature = "wormhole")]
+pub mod wormhole;
+
+/// Traffic capture for debugging
+#[cfg(feature = "wire-capture")]
+pub mod capture;
 use errors::PortalError::*;
+pub use file::{Durability, TransferTuning, MMAP_WINDOW_SIZE};
 
 /// Lower level protocol methods. Use these
 /// if the higher-level Portal interface is
@@ -46,11 +103,27 @@ pub const DEFAULT_PORT: u16 = 13265;
  */
 pub const CHUNK_SIZE: usize = 65536;
 
+/// Smallest chunk used when adapting the chunk size
+pub const MIN_CHUNK_SIZE: usize = 16 * 1024;
+
+/// Largest chunk used when adapting the chunk size, or accepted from a peer
+pub const MAX_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Words in a session fingerprint, about 51 bits
+pub const FINGERPRINT_WORDS: usize = 4;
+
+/// Delay between attempts to reconnect to the relay
+const RECONNECT_DELAY: Duration = Duration::from_millis(500);
+
+/// Failed key confirmations in a row by one Portal before they are reported
+/// as possible interference rather than a mistyped pass-phrase
+pub const INTERFERENCE_THRESHOLD: u32 = 3;
+
 /// None constant for optional verify callbacks - Helper
 pub const NO_VERIFY_CALLBACK: Option<fn(&TransferInfo) -> bool> = None::<fn(&TransferInfo) -> bool>;
 
 /// None constant for optional progress callbacks - Helper
-pub const NO_PROGRESS_CALLBACK: Option<fn(usize)> = None::<fn(usize)>;
+pub const NO_PROGRESS_CALLBACK: Option<fn(usize, usize)> = None::<fn(usize, usize)>;
 
 /**
  * The primary interface into the library.
@@ -73,8 +146,118 @@ pub struct Portal {
     // once we receive a confirmation msg from the peer
     pub state: Option<Spake2<Ed25519Group>>,
 
+    // Inputs to SPAKE2, kept to start over after a failed handshake
+    secret: Option<Secret>,
+
     // Derived session key
     key: Option<Vec<u8>>,
+
+    // Kernel hints applied to transferred files
+    tuning: TransferTuning,
+
+    // Preferred encoding for encrypted objects, replaced
+    // by the negotiated encoding after the handshake
+    format: WireFormat,
+
+    // Whether relay-provided messages are strictly validated
+    strict: bool,
+
+    // Pre-shared key used instead of SPAKE2, if initialized with one
+    psk: Option<[u8; 32]>,
+
+    // Whether chunks carry a checksum trailer
+    checksums: bool,
+
+    // Whether files are hashed as they are sent or received, & the
+    // digest of the last one
+    digests: bool,
+    last_digest: Option<[u8; 32]>,
+
+    // Whether files are sent with an adaptive chunk size
+    adaptive_chunks: bool,
+
+    // Soft limits reported to the verify callback
+    limits: TransferLimits,
+
+    // Hard limits on what is received, & what has been so far
+    quota: Shared<policy::Quota>,
+
+    // How received files are synced to storage
+    durability: Durability,
+
+    // Length of the region of a file mapped at once
+    mmap_window: usize,
+
+    // Whether files are only read & written through the chunk buffer,
+    // which is then allocated up front
+    constant_memory: bool,
+    buffer: ChunkBuffer,
+
+    // Whether repeated chunks are sent as references, replaced by
+    // whether both peers offered to after the handshake
+    dedup: bool,
+    index: Shared<dedup::DedupIndex>,
+
+    // Whether chunks are bound to their file's number & offset, replaced
+    // by whether both peers offered to after the handshake
+    bind_chunks: bool,
+    files: Shared<FileNumbers>,
+
+    // Files offered by the last call to outgoing & not yet sent, as
+    // they were when the transfer was offered
+    offered: Shared<BTreeMap<PathBuf, FileStamp>>,
+
+    // Shared secret for relays requiring an access token
+    relay_secret: Option<Vec<u8>>,
+
+    // Application namespace mixed into key derivation & confirmation
+    namespace: Option<String>,
+
+    // What crossed the wire since the handshake, for the transcript
+    log: Shared<TranscriptLog>,
+
+    // Priority advertised to the relay
+    priority: Priority,
+
+    // Newest wire codec offered, replaced by the
+    // negotiated codec after the handshake
+    codec: CodecVersion,
+
+    // Round-trip time to the peer, measured during the handshake
+    rtt: Option<Duration>,
+
+    // The banner of the relay that paired us, if it sent one
+    relay: Option<RelayBanner>,
+
+    // The salt the relay paired us with, if any
+    salt: Option<SessionSalt>,
+
+    // Failed key confirmations since the last successful one
+    failures: u32,
+}
+
+/// The SPAKE2 identity & password of a Portal request
+#[derive(PartialEq, Eq, Clone)]
+struct Secret {
+    identity: Vec<u8>,
+    password: Vec<u8>,
+}
+
+impl Secret {
+    /// Start a new key exchange
+    fn start(&self) -> Result<(Spake2<Ed25519Group>, PortalKeyExchange), Box<dyn Error>> {
+        let (state, outbound_msg) = Spake2::<Ed25519Group>::start_symmetric(
+            &Password::new(&self.password),
+            &Identity::new(&self.identity),
+        );
+        Ok((state, outbound_msg.try_into().or(Err(CryptoError))?))
+    }
+}
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("Secret")
+    }
 }
 
 impl Portal {
@@ -103,24 +286,127 @@ impl Portal {
         let id_hash = hex::encode(id_bytes);
 
         // Initialize the state
-        let (s1, outbound_msg) = Spake2::<Ed25519Group>::start_symmetric(
-            &Password::new(password.as_bytes()),
-            &Identity::new(&id_bytes),
-        );
+        let secret = Secret {
+            identity: id_bytes.to_vec(),
+            password: password.into_bytes(),
+        };
+        let (s1, exchange) = secret.start()?;
 
         Ok(Portal {
             direction,
             id: id_hash,
-            exchange: outbound_msg.try_into().or(Err(CryptoError))?,
+            exchange,
             nseq: NonceSequence::new(),
             state: Some(s1),
+            secret: Some(secret),
             key: None,
+            tuning: TransferTuning::default(),
+            format: WireFormat::default(),
+            strict: false,
+            psk: None,
+            checksums: false,
+            digests: false,
+            last_digest: None,
+            adaptive_chunks: true,
+            limits: TransferLimits::default(),
+            quota: Shared::default(),
+            durability: Durability::default(),
+            mmap_window: MMAP_WINDOW_SIZE,
+            constant_memory: false,
+            buffer: ChunkBuffer::default(),
+            dedup: false,
+            index: Shared::default(),
+            bind_chunks: true,
+            files: Shared::default(),
+            offered: Shared::default(),
+            relay_secret: None,
+            namespace: None,
+            log: Shared::default(),
+            priority: Priority::default(),
+            codec: CodecVersion::LATEST,
+            rtt: None,
+            relay: None,
+            salt: None,
+            failures: 0,
         })
     }
 
+    /// Initialize a portal keyed by a 32-byte pre-shared key rather than a
+    /// password, for peers that already share a secure channel such as SSH
+    /// or TLS. Complete it with [`Portal::handshake_psk`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use portal_lib::{Portal,Direction};
+    ///
+    /// // the key must be random & only known to both peers
+    /// let psk = [7u8; 32];
+    /// let portal = Portal::init_psk(Direction::Sender, "id".into(), psk).unwrap();
+    /// ```
+    pub fn init_psk(
+        direction: Direction,
+        id: String,
+        psk: [u8; 32],
+    ) -> Result<Portal, Box<dyn Error>> {
+        let mut portal = Portal::init(direction, id, String::new())?;
+        portal.state = None;
+        portal.secret = None;
+        portal.psk = Some(psk);
+        Ok(portal)
+    }
+
+    /// Initialize a new portal request for one receiver of a group transfer.
+    /// The credentials are derived from the master ID & password using the
+    /// receiver's index, see [`Shard`]. The receiver should call
+    /// [`Portal::init`] with the derived `id` and `password`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use portal_lib::{Portal, Direction, Shard};
+    ///
+    /// // Authorize two distinct receivers with the same master password
+    /// for index in 0..2 {
+    ///     let shard = Shard::derive("id", "testpasswd", index).unwrap();
+    ///     let portal = Portal::init_shard(Direction::Sender, "id", "testpasswd", index).unwrap();
+    ///
+    ///     // shard.id & shard.password are given to receiver `index`
+    /// }
+    /// ```
+    pub fn init_shard(
+        direction: Direction,
+        id: &str,
+        master: &str,
+        index: u32,
+    ) -> Result<Portal, Box<dyn Error>> {
+        let shard = Shard::derive(id, master, index)?;
+        Portal::init(direction, shard.id, shard.password)
+    }
+
     /// Negotiate a secure connection over the insecure channel by performing the portal
     /// handshake. Subsequent communication will be encrypted.
     ///
+    /// A relay at capacity may turn the request away with [`Redirected`],
+    /// listing other relays to connect to instead. The Portal can then be
+    /// used to handshake again through one of them.
+    ///
+    /// [`Redirected`]: errors::PortalError::Redirected
+    ///
+    /// A relay too old or too new for this build is reported as
+    /// [`RelayTooOld`] or [`RelayTooNew`], from the banner it sends first.
+    ///
+    /// [`RelayTooOld`]: errors::PortalError::RelayTooOld
+    /// [`RelayTooNew`]: errors::PortalError::RelayTooNew
+    ///
+    /// A peer too old to read this build's file entries, one that sent no
+    /// capabilities, is refused with [`PeerTooOld`] before key confirmation.
+    ///
+    /// [`PeerTooOld`]: errors::PortalError::PeerTooOld
+    ///
+    /// A failed handshake starts a fresh key exchange, so the Portal can
+    /// be used to try again over a new connection.
+    ///
     /// # Example
     ///
     /// ```no_run
@@ -134,10 +420,60 @@ impl Portal {
     /// portal.handshake(&mut stream).unwrap();
     /// ```
     pub fn handshake<P: Read + Write>(&mut self, peer: &mut P) -> Result<(), Box<dyn Error>> {
+        let result = self.try_handshake(peer);
+        self.recover(result)
+    }
+
+    /// Helper: perform the handshake through the relay
+    fn try_handshake<P: Read + Write>(&mut self, peer: &mut P) -> Result<(), Box<dyn Error>> {
         // Send the connection message. If the relay cannot
         // match us with a peer this will fail.
-        let confirm =
-            Protocol::connect(peer, &self.id, self.direction, self.exchange).or(Err(NoPeer))?;
+        let request = ConnectMessage {
+            id: self.id.clone(),
+            direction: self.direction,
+        };
+        let options = ConnectOptions {
+            format: self.format,
+            priority: self.priority,
+            codec: self.codec,
+        };
+        let secret = self.relay_secret.as_deref();
+        let capabilities = Capabilities::default()
+            .with(Capabilities::DEDUP, self.dedup)
+            .with(Capabilities::BIND_CHUNKS, self.bind_chunks)
+            .with(Capabilities::RTT, true);
+        let connected = match self.strict {
+            true => Protocol::connect_strict_with(
+                peer,
+                request,
+                options,
+                secret,
+                Some(capabilities),
+                self.exchange,
+            ),
+            false => Protocol::connect_with(
+                peer,
+                request,
+                options,
+                secret,
+                Some(capabilities),
+                self.exchange,
+            ),
+        };
+
+        // Violations of strict mode & redirects are reported as is
+        let (confirm, info) = connected.map_err(|e| match e.downcast_ref() {
+            Some(
+                MessageTooLarge | UnexpectedMessage | PeerMismatch | Redirected(_) | SessionClaimed
+                | RelayTooOld(_) | RelayTooNew(_),
+            ) => e,
+            _ => NoPeer.into(),
+        })?;
+
+        // Peers that send no capabilities predate versioned file entries,
+        // & couldn't read ours or send theirs in a layout we read. They'd
+        // fail key confirmation too, as though the pass-phrase were wrong.
+        let theirs_capabilities = info.capabilities.ok_or(PeerTooOld)?;
 
         // after calling finish() the SPAKE2 struct will be consumed
         // so we must replace the value stored in self.state
@@ -145,18 +481,339 @@ impl Portal {
 
         // Derive the session key
         let key = Protocol::derive_key(state, &confirm).or(Err(BadMsg))?;
+        let key = Protocol::derive_namespaced_key(key, self.namespace.as_deref())?;
+
+        // confirm that the peer has the same key, and saw the same offers
+        let ours = Offer {
+            format: self.format,
+            codec: self.codec,
+            capabilities: Some(capabilities),
+        };
+        let theirs = Offer {
+            format: info.format,
+            codec: info.codec,
+            capabilities: info.capabilities,
+        };
+        let offers = Offers::new(self.direction, ours, theirs);
+        self.relay = info.relay;
+        self.salt = info.salt;
+        self.confirm_peer(peer, info.salt.as_ref(), Some(&offers), &key)?;
+
+        // Peers that don't measure the round-trip time would never answer a Ping
+        let common = capabilities.common(theirs_capabilities);
+        self.rtt = match common.contains(Capabilities::RTT) {
+            true => Some(Protocol::measure_rtt(peer)?),
+            false => None,
+        };
+
+        // Set key & agreed upon options for further use
+        self.key = Some(key);
+        self.log = Shared::new(TranscriptLog::start());
+        self.format = WireFormat::negotiate(self.format, info.format);
+        self.codec = CodecVersion::negotiate(self.codec, info.codec);
+        self.dedup = common.contains(Capabilities::DEDUP);
+        self.bind_chunks = common.contains(Capabilities::BIND_CHUNKS);
+        Ok(())
+    }
+
+    /// Helper: start a new key exchange if the handshake failed. The
+    /// SPAKE2 state may have been consumed, and the exchange data sent
+    /// to a peer that never completed it shouldn't be reused.
+    fn recover(&mut self, result: Result<(), Box<dyn Error>>) -> Result<(), Box<dyn Error>> {
+        if result.is_err() {
+            if let Some(secret) = self.secret.as_ref() {
+                let (state, exchange) = secret.start()?;
+                self.state = Some(state);
+                self.exchange = exchange;
+            }
+        }
+        result
+    }
+
+    /// Confirm that the peer derived the same key, counting failures since
+    /// the last success. A failure is most likely a mistyped pass-phrase, but
+    /// [`INTERFERENCE_THRESHOLD`] in a row are reported as [`PossibleInterference`].
+    ///
+    /// [`PossibleInterference`]: errors::PortalError::PossibleInterference
+    fn confirm_peer<P: Read + Write>(
+        &mut self,
+        peer: &mut P,
+        salt: Option<&SessionSalt>,
+        offers: Option<&Offers>,
+        key: &[u8],
+    ) -> Result<(), Box<dyn Error>> {
+        let id = namespaced(self.namespace.as_deref(), &self.id);
+        let result = Protocol::confirm_peer_with(peer, &id, salt, offers, self.direction, key);
+        match result {
+            Ok(()) => {
+                self.failures = 0;
+                Ok(())
+            }
+            Err(e) if matches!(e.downcast_ref(), Some(PeerKeyMismatch)) => {
+                self.failures += 1;
+                match self.failures >= INTERFERENCE_THRESHOLD {
+                    true => Err(PossibleInterference.into()),
+                    false => Err(e),
+                }
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The number of failed key confirmations by this Portal since its last
+    /// successful one, across every handshake it attempted
+    pub fn failed_confirmations(&self) -> u32 {
+        self.failures
+    }
+
+    /// Perform the handshake with a directly connected peer, without a
+    /// relay. The Connect & pairing messages are skipped, so the default
+    /// [`WireFormat`] is used rather than a negotiated one.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::net::TcpStream;
+    /// use portal_lib::{Portal,Direction};
+    ///
+    /// let mut portal = Portal::init(Direction::Receiver, "id".into(), "password".into()).unwrap();
+    /// let mut stream = TcpStream::connect("192.168.1.20:13265").unwrap();
+    ///
+    /// // conduct the handshake with the Sender itself
+    /// portal.handshake_direct(&mut stream).unwrap();
+    /// ```
+    pub fn handshake_direct<P: Read + Write>(
+        &mut self,
+        peer: &mut P,
+    ) -> Result<(), Box<dyn Error>> {
+        let result = self.try_handshake_direct(peer);
+        self.recover(result)
+    }
+
+    /// Helper: perform the handshake with a directly connected peer
+    fn try_handshake_direct<P: Read + Write>(
+        &mut self,
+        peer: &mut P,
+    ) -> Result<(), Box<dyn Error>> {
+        let confirm = Protocol::exchange_direct(peer, self.exchange)?;
+
+        // after calling finish() the SPAKE2 struct will be consumed
+        // so we must replace the value stored in self.state
+        let state = self.state.take().ok_or(BadState)?;
+
+        // Derive the session key & confirm the peer has the same key
+        let key = Protocol::derive_key(state, &confirm).or(Err(BadMsg))?;
+        let key = Protocol::derive_namespaced_key(key, self.namespace.as_deref())?;
+        self.confirm_peer(peer, None, None, &key)?;
+
+        // No capabilities are exchanged, so the round-trip time isn't
+        // measured, a peer that doesn't would never answer a Ping
+        self.rtt = None;
+        self.key = Some(key);
+        self.log = Shared::new(TranscriptLog::start());
+        self.format = WireFormat::default();
+        self.codec = CodecVersion::V1;
+        self.dedup = false;
+        self.bind_chunks = false;
+        Ok(())
+    }
+
+    /// Perform the handshake of a portal created with [`Portal::init_psk`]
+    /// with a directly connected peer. SPAKE2 is skipped, the session key is
+    /// derived from the pre-shared key & a nonce from each peer, then
+    /// confirmed like any other session.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::net::TcpStream;
+    /// use portal_lib::{Portal,Direction};
+    ///
+    /// let mut portal = Portal::init_psk(Direction::Receiver, "id".into(), [7u8; 32]).unwrap();
+    /// let mut stream = TcpStream::connect("127.0.0.1:2222").unwrap();
+    /// portal.handshake_psk(&mut stream).unwrap();
+    /// ```
+    pub fn handshake_psk<P: Read + Write>(&mut self, peer: &mut P) -> Result<(), Box<dyn Error>> {
+        // The pre-shared key is only used for a single session, but
+        // is kept to try again if this one can't be established
+        let psk = self.psk.take().ok_or(BadState)?;
+        let result = self.try_handshake_psk(peer, &psk);
+        if result.is_err() {
+            self.psk = Some(psk);
+        }
+        result
+    }
+
+    /// Helper: perform the handshake keyed by a pre-shared key
+    fn try_handshake_psk<P: Read + Write>(
+        &mut self,
+        peer: &mut P,
+        psk: &[u8; 32],
+    ) -> Result<(), Box<dyn Error>> {
+        let ours = SessionSalt::generate();
+        let theirs = Protocol::exchange_nonces(peer, ours)?;
+        let (sender, receiver) = match self.direction {
+            Direction::Sender => (ours, theirs),
+            Direction::Receiver => (theirs, ours),
+        };
 
-        // confirm that the peer has the same key
-        Protocol::confirm_peer(peer, &self.id, self.direction, &key)?;
+        // Derive the session key & confirm the peer has the same key
+        let key = Protocol::derive_psk_key(psk, &self.id, &sender, &receiver)?;
+        let key = Protocol::derive_namespaced_key(key, self.namespace.as_deref())?;
+        self.confirm_peer(peer, None, None, &key)?;
 
-        // Set key for further use
+        // No capabilities are exchanged, so the round-trip time isn't
+        // measured, a peer that doesn't would never answer a Ping
+        self.rtt = None;
         self.key = Some(key);
+        self.log = Shared::new(TranscriptLog::start());
+        self.format = WireFormat::default();
+        self.codec = CodecVersion::V1;
+        self.dedup = false;
+        self.bind_chunks = false;
         Ok(())
     }
 
+    /// Wait for a single peer to connect to `bind_addr`, then perform the
+    /// handshake with it directly. Returns the connection to transfer over.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use portal_lib::{Portal,Direction};
+    ///
+    /// let mut portal = Portal::init(Direction::Sender, "id".into(), "password".into()).unwrap();
+    /// let mut stream = portal.listen_direct("0.0.0.0:13265").unwrap();
+    /// ```
+    pub fn listen_direct<A: ToSocketAddrs>(
+        &mut self,
+        bind_addr: A,
+    ) -> Result<TcpStream, Box<dyn Error>> {
+        let (mut peer, _) = TcpListener::bind(bind_addr)?.accept()?;
+        self.handshake_direct(&mut peer)?;
+        Ok(peer)
+    }
+
+    /// Connect to a peer waiting in [`Portal::listen_direct`] and perform
+    /// the handshake with it. Returns the connection to transfer over.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use portal_lib::{Portal,Direction};
+    ///
+    /// let mut portal = Portal::init(Direction::Receiver, "id".into(), "password".into()).unwrap();
+    /// let mut stream = portal.connect_direct("192.168.1.20:13265").unwrap();
+    /// ```
+    pub fn connect_direct<A: ToSocketAddrs>(
+        &mut self,
+        addr: A,
+    ) -> Result<TcpStream, Box<dyn Error>> {
+        let mut peer = TcpStream::connect(addr)?;
+        self.handshake_direct(&mut peer)?;
+        Ok(peer)
+    }
+
+    /// Attempt the handshake, reconnecting to the relay if the connection
+    /// is lost before a peer is found. A relay that restarted re-registers
+    /// a Sender that reconnects with the same ID within its grace window,
+    /// so the existing pass-phrase remains valid.
+    ///
+    /// `reconnect` is called to establish each new connection, which
+    /// replaces `peer`. Gives up once `grace` has elapsed without a
+    /// successful handshake.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::net::TcpStream;
+    /// use std::time::{Duration, Instant};
+    /// use portal_lib::{Portal, Direction};
+    ///
+    /// let relay = "127.0.0.1:34254";
+    /// let mut portal = Portal::init(Direction::Sender, "id".into(), "password".into()).unwrap();
+    /// let mut stream = TcpStream::connect(relay).unwrap();
+    ///
+    /// portal
+    ///     .handshake_reconnecting(&mut stream, || TcpStream::connect(relay), Duration::from_secs(60))
+    ///     .unwrap();
+    /// ```
+    pub fn handshake_reconnecting<P, C>(
+        &mut self,
+        peer: &mut P,
+        mut reconnect: C,
+        grace: Duration,
+    ) -> Result<(), Box<dyn Error>>
+    where
+        P: Read + Write,
+        C: FnMut() -> std::io::Result<P>,
+    {
+        let mut lost: Option<Instant> = None;
+        loop {
+            let attempt = Instant::now();
+            let err = match self.handshake(peer) {
+                Ok(()) => return Ok(()),
+                Err(e) => e,
+            };
+
+            // Only retry connections lost before the key exchange completed
+            if !matches!(err.downcast_ref(), Some(NoPeer)) {
+                return Err(err);
+            }
+
+            // The grace window starts when the connection is lost. A connection
+            // that outlived the window was registered, so starts a new one.
+            if lost.is_none() || attempt.elapsed() >= grace {
+                lost = Some(Instant::now());
+            }
+            let lost = lost.unwrap();
+
+            *peer = loop {
+                if lost.elapsed() >= grace {
+                    return Err(err);
+                }
+                std::thread::sleep(RECONNECT_DELAY);
+                if let Ok(p) = reconnect() {
+                    break p;
+                }
+            };
+        }
+    }
+
+    /// Send a control request directly to the relay, such as cancelling or
+    /// extending this Portal's pending registration. Must be sent over a
+    /// separate connection from the one used for the handshake.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::net::TcpStream;
+    /// use portal_lib::{Portal, Direction, RelayControl};
+    ///
+    /// let portal = Portal::init(Direction::Sender, "id".into(), "password".into()).unwrap();
+    /// let mut relay = TcpStream::connect("127.0.0.1:34254").unwrap();
+    ///
+    /// // Ask the relay to keep our registration for another 10 minutes
+    /// let request = RelayControl::ExtendTtl {
+    ///     id: portal.get_id().clone(),
+    ///     seconds: 600,
+    /// };
+    /// match portal.relay_control(&mut relay, request).unwrap() {
+    ///     RelayControl::Ack => println!("extended"),
+    ///     other => println!("relay responded with {:?}", other),
+    /// }
+    /// ```
+    pub fn relay_control<P: Read + Write>(
+        &self,
+        relay: &mut P,
+        request: RelayControl,
+    ) -> Result<RelayControl, Box<dyn Error>> {
+        Protocol::relay_control(relay, request)
+    }
+
     /// As the sender, communicate a TransferInfo struct to the receiver
     /// so that they may confirm/deny the transfer. Returns an iterator
-    /// over the fullpath + Metadata to pass to send_file(). Allows the user
+    /// over the fullpath + FileEntry to pass to send_file(). Allows the user
     /// to send multiple files in one session.
     ///
     /// # Example
@@ -186,8 +843,8 @@ impl Portal {
     ///         .finalize();
     ///
     ///     // Optional: implement a custom callback to display how much
-    ///     // has been transferred
-    ///     fn progress(transferred: usize) {
+    ///     // has been transferred, and how much was just sent
+    ///     fn progress(transferred: usize, _delta: usize) {
     ///         println!("sent {:?} bytes", transferred);
     ///     }
     ///
@@ -202,7 +859,7 @@ impl Portal {
         &mut self,
         peer: &mut W,
         info: &'a TransferInfo,
-    ) -> Result<impl Iterator<Item = (&'a PathBuf, &'a Metadata)>, Box<dyn Error>>
+    ) -> Result<impl Iterator<Item = (&'a PathBuf, &'a FileEntry)>, Box<dyn Error>>
     where
         W: Write,
     {
@@ -210,7 +867,24 @@ impl Portal {
         let key = self.key.as_ref().ok_or(NoPeer)?;
 
         // Send all TransferInfo for peer to confirm
-        Protocol::encrypt_and_write_object(peer, key, &mut self.nseq, info)?;
+        Protocol::encrypt_and_write_object(
+            peer,
+            key,
+            &mut self.nseq,
+            self.codec,
+            self.format,
+            info,
+        )?;
+
+        self.log.lock().manifest(info, self.format)?;
+
+        // Remember the files as they were offered, to check before sending
+        *self.offered.lock() = info
+            .localpaths
+            .iter()
+            .zip(&info.stamps)
+            .filter_map(|(path, stamp)| Some((path.clone(), (*stamp)?)))
+            .collect();
 
         // Return an iterator that returns metadata for each outgoing file
         Ok(info.localpaths.iter().zip(info.all.iter()))
@@ -218,7 +892,7 @@ impl Portal {
 
     /// As the receiver, receive a TransferInfo struct which will be passed
     /// to your optional verify callback. And may be used to confirm/deny
-    /// the transfer. Returns an iterator over the Metadata of incoming files.
+    /// the transfer. Returns an iterator over the entries of incoming files.
     ///
     /// # Example
     ///
@@ -244,12 +918,13 @@ impl Portal {
     ///
     ///     // Optional: User callback to confirm/deny a transfer. If
     ///     // none is provided, this will default accept the incoming file.
-    ///     // Return true to accept, false to reject the transfer.
+    ///     // Return true to accept, false to reject the transfer. Wrap
+    ///     // a closure in WithPolicy to also receive a TransferPolicy.
     ///     fn confirm_download(_info: &TransferInfo) -> bool { true }
     ///
     ///     // Optional: implement a custom callback to display how much
     ///     // has been transferred
-    ///     fn progress(transferred: usize) {
+    ///     fn progress(transferred: usize, _delta: usize) {
     ///         println!("received {:?} bytes", transferred);
     ///     }
     ///
@@ -266,20 +941,31 @@ impl Portal {
     pub fn incoming<R, V>(
         &mut self,
         peer: &mut R,
-        verify: Option<V>,
-    ) -> Result<impl Iterator<Item = Metadata>, Box<dyn Error>>
+        mut verify: Option<V>,
+    ) -> Result<impl Iterator<Item = FileEntry>, Box<dyn Error>>
     where
         R: Read,
-        V: Fn(&TransferInfo) -> bool,
+        V: Verify,
     {
         // Check that the key exists to confirm the handshake is complete
         let key = self.key.as_ref().ok_or(NoPeer)?;
 
         // Receive the TransferInfo
-        let info: TransferInfo = Protocol::read_encrypted_from(peer, key)?;
+        let info: TransferInfo = Protocol::read_encrypted_from(peer, key, self.codec, self.format)?;
+        if info.note.as_ref().is_some_and(|n| n.len() > MAX_NOTE_SIZE) {
+            return Err(NoteTooLarge.into());
+        }
+
+        // Reject transfers exceeding the quota before asking the user
+        self.quota.lock().check_transfer(&info)?;
 
         // Process the verify callback if applicable
-        match verify.as_ref().map_or(true, |c| c(&info)) {
+        self.log.lock().manifest(&info, self.format)?;
+        let policy = TransferPolicy::new(&info, self.limits);
+        match verify
+            .as_mut()
+            .is_none_or(|c| c.verify_with_policy(&info, &policy))
+        {
             true => {}
             false => return Err(Cancelled.into()),
         }
@@ -291,6 +977,14 @@ impl Portal {
     /// Send a given file over the portal. Must be called after performing the
     /// handshake or this method will return an error.
     ///
+    /// A file offered with [`Portal::outgoing`] is skipped if its size or
+    /// modification time, or quick hash if one was taken, changed since.
+    /// Likewise if the file shrinks or can't be read partway through, the receiver
+    /// is told to discard it & this returns
+    /// [`FileChangedDuringTransfer`](errors::PortalError::FileChangedDuringTransfer),
+    /// after which the session can continue with the next file. A file
+    /// truncated while a window of it is mapped can still raise SIGBUS.
+    ///
     /// # Example
     ///
     /// ```no_run
@@ -306,10 +1000,13 @@ impl Portal {
     /// portal.handshake(&mut stream);
     ///
     /// // Optional: implement a custom callback to display how much
-    /// // has been transferred
-    /// fn progress(transferred: usize) {
-    ///     println!("sent {:?} bytes", transferred);
-    /// }
+    /// // has been transferred. Stateful closures work as well,
+    /// // the second argument is the amount just sent
+    /// let mut chunks = 0;
+    /// let progress = |transferred: usize, _delta: usize| {
+    ///     chunks += 1;
+    ///     println!("sent {:?} bytes in {} chunks", transferred, chunks);
+    /// };
     ///
     /// // Begin sending the file
     /// let file = Path::new("/etc/passwd").to_path_buf();
@@ -323,10 +1020,10 @@ impl Portal {
     ) -> Result<usize, Box<dyn Error>>
     where
         W: Write,
-        D: Fn(usize),
+        D: FnMut(usize, usize),
     {
         // Check that the key exists to confirm the handshake is complete
-        let key = self.key.as_ref().ok_or(NoPeer)?;
+        self.key.as_ref().ok_or(NoPeer)?;
 
         // Obtain the file name stub from the path
         let filename = path
@@ -335,72 +1032,584 @@ impl Portal {
             .to_str()
             .ok_or(BadFileName)?;
 
-        // Map the file into memory
-        let mut mmap = self.map_readable_file(path)?;
-
-        // Create the metatada object
-        let metadata = Metadata {
-            filesize: mmap.len() as u64,
-            filename: filename.to_string(),
+        // Skip a file that changed since it was offered, the receiver
+        // only accepted it as it was
+        let offered = self.offered.lock().remove(path);
+        let file = match offered {
+            Some(stamp) => match Self::check_unchanged(path, &stamp) {
+                Ok(file) => file,
+                Err(e) => return Err(self.skip_file(peer, filename, stamp.size, &e)),
+            },
+            None => File::open(path)?,
         };
+        self.send_file_handle(peer, &file, filename, callback)
+    }
 
-        // Write the file metadata over the encrypted channel
-        Protocol::encrypt_and_write_object(peer, key, &mut self.nseq, &metadata)?;
-
-        // Send the encrypted region in chunks
-        let mut total_sent = 0;
-        for chunk in mmap[..].chunks_mut(CHUNK_SIZE) {
-            // Encrypt the chunk in-place & send the header
-            Protocol::encrypt_and_write_header_only(peer, key, &mut self.nseq, chunk)?;
-
-            // Write the entire chunk
-            peer.write_all(chunk)?;
+    /// Helper: open a file, checking it is as it was when offered
+    fn check_unchanged(path: &Path, stamp: &FileStamp) -> Result<File, Box<dyn Error>> {
+        let mut file = File::open(path)?;
+        match FileStamp::of(&mut file, stamp.quick_hash.is_some())? == *stamp {
+            true => Ok(file),
+            false => Err("file changed since it was offered".into()),
+        }
+    }
 
-            // Increment and optionally invoke callback
-            total_sent += chunk.len();
-            if let Some(c) = callback.as_ref() {
-                c(total_sent);
-            }
+    /// Helper: send the metadata the receiver expects for a file, then
+    /// abort it in place of its data
+    fn skip_file<W: Write>(
+        &mut self,
+        peer: &mut W,
+        filename: &str,
+        filesize: u64,
+        reason: &dyn std::fmt::Display,
+    ) -> Box<dyn Error> {
+        let key = match self.key.as_ref() {
+            Some(key) => key,
+            None => return NoPeer.into(),
+        };
+        let metadata = FileEntry {
+            filesize,
+            filename: filename.to_string(),
+            ..Default::default()
+        };
+        let sent = Protocol::encrypt_and_write_object(
+            peer,
+            key,
+            &mut self.nseq,
+            self.codec,
+            self.format,
+            &metadata,
+        );
+        if let Err(e) = sent {
+            return e;
         }
-        Ok(total_sent)
+        let file_number = self.files.lock().next(self.bind_chunks);
+        let mut channel = EncryptedChannel::new(peer, key, &mut self.nseq)
+            .with_codec(self.codec)
+            .with_file(file_number);
+        Self::abort_file(&mut channel, filename, reason)
     }
 
-    /// Receive the next file over the portal. Must be called after performing
-    /// the handshake or this method will return an error.
+    /// Send an already opened file over the portal under the given name,
+    /// for callers that hold a descriptor but no path the process can
+    /// re-open, such as a file picker result or a sandboxed descriptor.
+    /// Must be called after performing the handshake or this method will
+    /// return an error.
     ///
     /// # Example
     ///
     /// ```no_run
-    /// use std::path::Path;
+    /// use std::fs::File;
     /// use std::net::TcpStream;
-    /// use portal_lib::{Portal,Direction};
+    /// use portal_lib::{Portal, Direction, NO_PROGRESS_CALLBACK};
     ///
     /// let mut portal = Portal::init(Direction::Sender,"id".into(), "password".into()).unwrap();
     /// let mut stream = TcpStream::connect("127.0.0.1:34254").unwrap();
+    /// portal.handshake(&mut stream).unwrap();
     ///
-    /// // The handshake must be performed first, otherwise
-    /// // there is no shared key to encrypt the file with
-    /// portal.handshake(&mut stream);
-    ///
-    /// // Optional: implement a custom callback to display how much
-    /// // has been transferred
-    /// fn progress(transferred: usize) {
-    ///     println!("received {:?} bytes", transferred);
-    /// }
-    ///
-    /// // Begin receiving the file into /tmp
-    /// portal.recv_file(&mut stream, Path::new("/tmp"), None, Some(progress));
+    /// // The name is all the receiver learns about the file
+    /// let file = File::open("/etc/passwd").unwrap();
+    /// portal.send_file_handle(&mut stream, &file, "passwd", NO_PROGRESS_CALLBACK).unwrap();
     /// ```
-    pub fn recv_file<R, D>(
+    pub fn send_file_handle<W, D>(
         &mut self,
-        peer: &mut R,
-        outdir: &Path,
-        expected: Option<&Metadata>,
-        display: Option<D>,
-    ) -> Result<Metadata, Box<dyn Error>>
+        peer: &mut W,
+        file: &File,
+        name: &str,
+        mut callback: Option<D>,
+    ) -> Result<usize, Box<dyn Error>>
     where
-        R: Read,
-        D: Fn(usize),
+        W: Write,
+        D: FnMut(usize, usize),
+    {
+        // Check that the key exists to confirm the handshake is complete
+        let key = self.key.as_ref().ok_or(NoPeer)?;
+
+        // The name must be only a name component
+        let filename = match Path::new(name).file_name() {
+            Some(s) if s == name => name,
+            _ => return Err(BadFileName.into()),
+        };
+        self.last_digest = None;
+
+        // Deduplicate repeated data if both peers offered to, except in
+        // constant memory mode where the index would grow with the transfer
+        if self.dedup && !self.constant_memory {
+            return self.send_deduplicated(peer, file, filename, callback);
+        }
+
+        // Adapt the chunk size to the link unless disabled
+        let mut sizer = match self.adaptive_chunks {
+            true => ChunkSizer::new(MIN_CHUNK_SIZE, MAX_CHUNK_SIZE),
+            false => ChunkSizer::fixed(CHUNK_SIZE),
+        };
+        if let Some(rtt) = self.rtt {
+            sizer.set_rtt(rtt);
+        }
+
+        // Create the metatada object
+        let metadata = FileEntry {
+            max_chunk: sizer.max() as u32,
+            ..FileEntry::of(file, filename)?
+        };
+        let filesize = metadata.filesize as usize;
+
+        // Write the file metadata over the encrypted channel
+        Protocol::encrypt_and_write_object(
+            peer,
+            key,
+            &mut self.nseq,
+            self.codec,
+            self.format,
+            &metadata,
+        )?;
+
+        // Send the encrypted region in chunks, a window at a time
+        let sequential = self.tuning.sequential;
+        let window = self.mmap_window;
+        let file_number = self.files.lock().next(self.bind_chunks);
+        let mut channel = EncryptedChannel::new(peer, key, &mut self.nseq)
+            .with_checksums(self.checksums)
+            .with_codec(self.codec)
+            .with_file(file_number)
+            .with_digest(self.digests);
+        let mut total_sent = 0;
+        let mut mappable = !self.constant_memory;
+        while total_sent < filesize {
+            let len = (filesize - total_sent).min(window);
+
+            // Map the next window of the file into memory. Where the file
+            // can't be mapped (network mounts, FUSE, locked files) read
+            // it into a buffer instead, a chunk at a time
+            // Stop before mapping past the end of a file that shrank, as
+            // touching the missing pages would raise SIGBUS
+            if mappable {
+                match file.metadata() {
+                    Ok(m) if m.len() >= (total_sent + len) as u64 => {}
+                    Ok(_) => return Err(Self::abort_file(&mut channel, filename, &"file shrank")),
+                    Err(e) => return Err(Self::abort_file(&mut channel, filename, &e)),
+                }
+            }
+
+            let mut mmap;
+            let region: &mut [u8] = match mappable {
+                true => match Self::map_readable_file(file, total_sent, len, sequential) {
+                    Ok(m) => {
+                        mmap = m;
+                        &mut mmap[..]
+                    }
+                    Err(e) => {
+                        log::info!("{}: can't map ({}), using buffered reads", filename, e);
+                        mappable = false;
+                        continue;
+                    }
+                },
+                false => {
+                    let buffer = self.buffer.get(len.min(sizer.max()));
+                    if let Err(e) = file::read_exact_at(file, buffer, total_sent as u64) {
+                        return Err(Self::abort_file(&mut channel, filename, &e));
+                    }
+                    buffer
+                }
+            };
+
+            let mut remaining = region;
+            while !remaining.is_empty() {
+                let len = sizer.size().min(remaining.len());
+                let (chunk, rest) = std::mem::take(&mut remaining).split_at_mut(len);
+                remaining = rest;
+
+                // Encrypt the chunk in-place & send it, timing the send
+                let started = Instant::now();
+                let sent = channel.write_chunk_in_place(chunk)?;
+                sizer.record(sent, started.elapsed());
+
+                // Increment and optionally invoke callback
+                total_sent += sent;
+                if let Some(c) = callback.as_mut() {
+                    c(total_sent, sent);
+                }
+            }
+        }
+
+        self.last_digest = channel.digest();
+        let digest = self.last_digest;
+        self.log.lock().file(filename, total_sent as u64, digest);
+
+        // Release the sent file from the page cache if requested
+        if self.tuning.drop_cache {
+            let _ = file::drop_cache(file);
+        }
+        Ok(total_sent)
+    }
+
+    /// Helper: send a file as content-defined chunks, each replaced by a
+    /// reference if the same data was already sent in the session
+    fn send_deduplicated<W, D>(
+        &mut self,
+        peer: &mut W,
+        file: &File,
+        filename: &str,
+        mut callback: Option<D>,
+    ) -> Result<usize, Box<dyn Error>>
+    where
+        W: Write,
+        D: FnMut(usize, usize),
+    {
+        let key = self.key.as_ref().ok_or(NoPeer)?;
+
+        // Create the metadata object, chunks have a leading tag
+        let metadata = FileEntry {
+            max_chunk: (1 + DEDUP_MAX_CHUNK) as u32,
+            dedup: true,
+            ..FileEntry::of(file, filename)?
+        };
+        let filesize = metadata.filesize as usize;
+
+        // Write the file metadata over the encrypted channel
+        Protocol::encrypt_and_write_object(
+            peer,
+            key,
+            &mut self.nseq,
+            self.codec,
+            self.format,
+            &metadata,
+        )?;
+
+        let mut index = self.index.lock();
+        let number = index.start_file();
+        let file_number = self.files.lock().next(self.bind_chunks);
+        let mut channel = EncryptedChannel::new(peer, key, &mut self.nseq)
+            .with_checksums(self.checksums)
+            .with_codec(self.codec)
+            .with_file(file_number);
+        let buffer = self.buffer.get(1 + DEDUP_MAX_CHUNK);
+        let mut digest = self.digests.then(Sha256::new);
+        let mut read = 0;
+        let mut filled = 0;
+        let mut total_sent = 0;
+        while total_sent < filesize {
+            // Top up the data after the tag, then cut the next chunk from it
+            let len = (DEDUP_MAX_CHUNK - filled).min(filesize - read);
+            let end = 1 + filled + len;
+            if let Err(e) = file::read_exact_at(file, &mut buffer[1 + filled..end], read as u64) {
+                index.forget_file(number);
+                return Err(Self::abort_file(&mut channel, filename, &e));
+            }
+            read += len;
+            filled += len;
+            let len = dedup::cut(&buffer[1..1 + filled]);
+
+            // Send a reference to where the chunk was sent before, or the
+            // chunk itself. The data past it is kept for the next.
+            let data = &buffer[1..1 + len];
+            if let Some(digest) = digest.as_mut() {
+                digest.update(data);
+            }
+            match index.lookup(data, number, total_sent as u64) {
+                Some(found) => channel.write_chunk_in_place(&mut found.encode())?,
+                None => {
+                    channel.write_chunk_in_place(DedupChunk::literal(&mut buffer[..1 + len]))?
+                }
+            };
+            buffer.copy_within(1 + len..1 + filled, 1);
+            filled -= len;
+
+            // Increment and optionally invoke callback
+            total_sent += len;
+            if let Some(c) = callback.as_mut() {
+                c(total_sent, len);
+            }
+        }
+        self.last_digest = digest.map(|d| d.finalize().into());
+        self.log
+            .lock()
+            .file(filename, total_sent as u64, self.last_digest);
+
+        // Release the sent file from the page cache if requested
+        if self.tuning.drop_cache {
+            let _ = file::drop_cache(file);
+        }
+        Ok(total_sent)
+    }
+
+    /// Send a file that is still growing, such as a log, over the portal.
+    /// Existing contents are sent first, then new bytes are streamed as
+    /// they are appended until `cancel` is set. The receiver's `recv_file`
+    /// appends to its copy until the end-of-stream marker arrives.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    /// use std::net::TcpStream;
+    /// use std::sync::atomic::AtomicBool;
+    /// use std::time::{Duration, Instant};
+    /// use portal_lib::{Portal, Direction, NO_PROGRESS_CALLBACK};
+    ///
+    /// let mut portal = Portal::init(Direction::Sender,"id".into(), "password".into()).unwrap();
+    /// let mut stream = TcpStream::connect("127.0.0.1:34254").unwrap();
+    /// portal.handshake(&mut stream).unwrap();
+    ///
+    /// // Set from another thread (i.e. a signal handler) to stop following
+    /// let cancel = AtomicBool::new(false);
+    ///
+    /// let file = Path::new("/var/log/syslog").to_path_buf();
+    /// let poll = Duration::from_millis(500);
+    /// portal.tail_file(&mut stream, &file, poll, &cancel, NO_PROGRESS_CALLBACK);
+    /// ```
+    pub fn tail_file<W, D>(
+        &mut self,
+        peer: &mut W,
+        path: &PathBuf,
+        poll: Duration,
+        cancel: &AtomicBool,
+        mut callback: Option<D>,
+    ) -> Result<usize, Box<dyn Error>>
+    where
+        W: Write,
+        D: FnMut(usize, usize),
+    {
+        // Check that the key exists to confirm the handshake is complete
+        let key = self.key.as_ref().ok_or(NoPeer)?;
+
+        // Obtain the file name stub from the path
+        let filename = path
+            .file_name()
+            .ok_or(BadFileName)?
+            .to_str()
+            .ok_or(BadFileName)?;
+
+        let mut file = File::open(path)?;
+
+        // The length is unknown, mark the file as open-ended
+        let metadata = FileEntry {
+            filename: filename.to_string(),
+            kind: FileKind::Stream,
+            ..Default::default()
+        };
+
+        // Write the file metadata over the encrypted channel
+        Protocol::encrypt_and_write_object(
+            peer,
+            key,
+            &mut self.nseq,
+            self.codec,
+            self.format,
+            &metadata,
+        )?;
+
+        // Send new data as it appears until cancelled
+        self.last_digest = None;
+        let file_number = self.files.lock().next(self.bind_chunks);
+        let mut channel = EncryptedChannel::new(peer, key, &mut self.nseq)
+            .with_checksums(self.checksums)
+            .with_codec(self.codec)
+            .with_file(file_number)
+            .with_digest(self.digests);
+        let buffer = self.buffer.get(CHUNK_SIZE);
+        let mut total_sent = 0;
+        loop {
+            // Checked before reading so that anything appended
+            // prior to cancellation is still sent
+            let cancelled = cancel.load(Ordering::Acquire);
+
+            let len = match file.read(buffer) {
+                Ok(len) => len,
+                Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(Self::abort_file(&mut channel, filename, &e)),
+            };
+
+            // Caught up with the writer, wait for more data. A file that
+            // was truncated, i.e. rotated by copying, won't grow from here
+            if len == 0 {
+                if file
+                    .metadata()
+                    .map_or(true, |m| m.len() < total_sent as u64)
+                {
+                    return Err(Self::abort_file(&mut channel, filename, &"file shrank"));
+                }
+                if cancelled {
+                    break;
+                }
+                std::thread::sleep(poll);
+                continue;
+            }
+
+            // Encrypt the chunk in-place & send it
+            let sent = channel.write_chunk_in_place(&mut buffer[..len])?;
+
+            // Increment and optionally invoke callback
+            total_sent += sent;
+            if let Some(c) = callback.as_mut() {
+                c(total_sent, sent);
+            }
+        }
+
+        // Inform the receiver that the file is complete
+        channel.finish()?;
+        self.last_digest = channel.digest();
+        let digest = self.last_digest;
+        self.log.lock().file(filename, total_sent as u64, digest);
+        Ok(total_sent)
+    }
+
+    /// Send everything read from `reader` over the portal under the given
+    /// name, for data whose length isn't known in advance such as an
+    /// archive built on the fly. Like [`Portal::tail_file`] the receiver
+    /// appends to its copy until the end-of-stream marker, which is sent
+    /// once the reader is exhausted.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::net::TcpStream;
+    /// use std::process::{Command, Stdio};
+    /// use portal_lib::{Portal, Direction, NO_PROGRESS_CALLBACK};
+    ///
+    /// let mut portal = Portal::init(Direction::Sender,"id".into(), "password".into()).unwrap();
+    /// let mut stream = TcpStream::connect("127.0.0.1:34254").unwrap();
+    /// portal.handshake(&mut stream).unwrap();
+    ///
+    /// // Stream the output of a command
+    /// let mut child = Command::new("ps").stdout(Stdio::piped()).spawn().unwrap();
+    /// let mut output = child.stdout.take().unwrap();
+    /// portal.send_stream(&mut stream, &mut output, "ps.txt", NO_PROGRESS_CALLBACK).unwrap();
+    /// ```
+    pub fn send_stream<W, R, D>(
+        &mut self,
+        peer: &mut W,
+        reader: &mut R,
+        name: &str,
+        mut callback: Option<D>,
+    ) -> Result<usize, Box<dyn Error>>
+    where
+        W: Write,
+        R: Read,
+        D: FnMut(usize, usize),
+    {
+        // Check that the key exists to confirm the handshake is complete
+        let key = self.key.as_ref().ok_or(NoPeer)?;
+
+        // The name must be only a name component
+        let filename = match Path::new(name).file_name() {
+            Some(s) if s == name => name,
+            _ => return Err(BadFileName.into()),
+        };
+
+        // The length is unknown, mark the file as open-ended
+        let metadata = FileEntry {
+            filename: filename.to_string(),
+            kind: FileKind::Stream,
+            ..Default::default()
+        };
+
+        // Write the file metadata over the encrypted channel
+        Protocol::encrypt_and_write_object(
+            peer,
+            key,
+            &mut self.nseq,
+            self.codec,
+            self.format,
+            &metadata,
+        )?;
+
+        // Send data as it is read until the end of the stream
+        self.last_digest = None;
+        let file_number = self.files.lock().next(self.bind_chunks);
+        let mut channel = EncryptedChannel::new(peer, key, &mut self.nseq)
+            .with_checksums(self.checksums)
+            .with_codec(self.codec)
+            .with_file(file_number)
+            .with_digest(self.digests);
+        let buffer = self.buffer.get(CHUNK_SIZE);
+        let mut total_sent = 0;
+        loop {
+            let len = match reader.read(buffer) {
+                Ok(0) => break,
+                Ok(len) => len,
+                Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(Self::abort_file(&mut channel, filename, &e)),
+            };
+
+            // Encrypt the chunk in-place & send it
+            let sent = channel.write_chunk_in_place(&mut buffer[..len])?;
+
+            // Increment and optionally invoke callback
+            total_sent += sent;
+            if let Some(c) = callback.as_mut() {
+                c(total_sent, sent);
+            }
+        }
+
+        // Inform the receiver that the file is complete
+        channel.finish()?;
+        self.last_digest = channel.digest();
+        let digest = self.last_digest;
+        self.log.lock().file(filename, total_sent as u64, digest);
+        Ok(total_sent)
+    }
+
+    /// Helper: tell the receiver to discard the file being sent after it
+    /// shrank or couldn't be read, so the session can continue with the
+    /// next file
+    fn abort_file<W: Write>(
+        channel: &mut EncryptedChannel<W>,
+        filename: &str,
+        reason: &dyn std::fmt::Display,
+    ) -> Box<dyn Error> {
+        log::warn!("{}: {}, aborting the file", filename, reason);
+        match channel.abort() {
+            Ok(()) => FileChangedDuringTransfer.into(),
+            Err(e) => e,
+        }
+    }
+
+    /// Receive the next file over the portal. Must be called after performing
+    /// the handshake or this method will return an error.
+    ///
+    /// If the sender aborts the file, what was received of it is removed
+    /// & this returns
+    /// [`FileChangedDuringTransfer`](errors::PortalError::FileChangedDuringTransfer).
+    /// The next file can still be received.
+    ///
+    /// Copies of the file are only written when `expected` is given, as
+    /// their names come from the approved metadata, & never in place of
+    /// an existing file.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    /// use std::net::TcpStream;
+    /// use portal_lib::{Portal,Direction};
+    ///
+    /// let mut portal = Portal::init(Direction::Sender,"id".into(), "password".into()).unwrap();
+    /// let mut stream = TcpStream::connect("127.0.0.1:34254").unwrap();
+    ///
+    /// // The handshake must be performed first, otherwise
+    /// // there is no shared key to encrypt the file with
+    /// portal.handshake(&mut stream);
+    ///
+    /// // Optional: implement a custom callback to display how much
+    /// // has been transferred
+    /// fn progress(transferred: usize, _delta: usize) {
+    ///     println!("received {:?} bytes", transferred);
+    /// }
+    ///
+    /// // Begin receiving the file into /tmp
+    /// portal.recv_file(&mut stream, Path::new("/tmp"), None, Some(progress));
+    /// ```
+    pub fn recv_file<R, D>(
+        &mut self,
+        peer: &mut R,
+        outdir: &Path,
+        expected: Option<&FileEntry>,
+        mut display: Option<D>,
+    ) -> Result<FileEntry, Box<dyn Error>>
+    where
+        R: Read,
+        D: FnMut(usize, usize),
     {
         // Check that the key exists to confirm the handshake is complete
         let key = self.key.as_ref().ok_or(NoPeer)?;
@@ -409,12 +1618,15 @@ impl Portal {
         if !outdir.is_dir() {
             return Err(BadDirectory.into());
         }
+        self.last_digest = None;
 
-        // Receive the metadata
-        let metadata: Metadata = Protocol::read_encrypted_from(peer, key)?;
+        // Receive the metadata, numbering the file
+        let mut metadata: FileEntry =
+            Protocol::read_encrypted_from(peer, key, self.codec, self.format)?;
+        let file_number = self.files.lock().next(self.bind_chunks);
 
         // Verify the metadata is expected, if a comparison is provided
-        if expected.map_or(false, |exp| metadata != *exp) {
+        if expected.is_some_and(|exp| metadata != *exp) {
             return Err(BadMsg.into());
         }
 
@@ -424,46 +1636,719 @@ impl Portal {
             _ => return Err(BadFileName.into()),
         };
 
-        // Map the region into memory for writing
-        let mut mmap = self.map_writeable_file(&path, metadata.filesize)?;
+        // Deduplicated files are only sent if we offered to receive them
+        if metadata.dedup && !self.dedup {
+            return Err(UnexpectedMessage.into());
+        }
 
-        let mut total = 0;
-        for chunk in mmap[..].chunks_mut(CHUNK_SIZE) {
-            // Receive the entire chunk in-place
-            Protocol::read_encrypted_zero_copy(peer, key, chunk)?;
+        // Nor are copies written under names that weren't approved
+        if expected.is_none() && !metadata.copies.is_empty() {
+            return Err(UnexpectedMessage.into());
+        }
 
-            // Increment and optionally invoke callback
-            total += chunk.len();
-            if let Some(c) = display.as_ref() {
-                c(total);
+        // The sender chooses its chunk sizes within this limit
+        let max_chunk = metadata.chunk_limit()?;
+
+        // Open-ended files are appended to until the end-of-stream marker
+        if metadata.is_stream() {
+            metadata.strategy = WriteStrategy::Buffered;
+            let quota = self.quota.lock().admit_open_ended()?;
+            let mut channel = EncryptedChannel::new(peer, key, &mut self.nseq)
+                .with_checksums(self.checksums)
+                .with_codec(self.codec)
+                .with_file(file_number)
+                .with_digest(self.digests);
+            let durability = self.durability;
+            let buffer = self.buffer.get(max_chunk);
+            let display = display.as_mut();
+            let total =
+                Self::recv_appended(&mut channel, &path, buffer, quota, durability, display)?;
+            self.quota.lock().consume(total as u64);
+            metadata.filesize = total as u64;
+            metadata.digest = channel.digest();
+            self.last_digest = metadata.digest;
+            if durability != Durability::None {
+                file::sync_path(&path)?;
             }
+            self.log
+                .lock()
+                .file(&metadata.filename, total as u64, metadata.digest);
+            return Ok(metadata);
         }
 
+        // Count the file & its copies against the quota before allocating it
+        let copies = expected.map_or(0, |exp| exp.copies.len());
+        self.quota.lock().admit(metadata.filesize, 1 + copies)?;
+
+        // Map the region into memory for writing. If the file cannot be
+        // allocated or mapped (no space, mmap limits) fall back to buffered
+        // writes, which are all that's used in constant memory mode &
+        // for deduplicated files
+        let mapped = match self.constant_memory || metadata.dedup {
+            true => None,
+            false => match self.map_writeable_file(&path, metadata.filesize) {
+                Ok(window) => Some(window),
+                Err(e) => {
+                    log::info!(
+                        "{}: can't map ({}), using buffered writes",
+                        path.display(),
+                        e
+                    );
+                    None
+                }
+            },
+        };
+        let mut channel = EncryptedChannel::new(peer, key, &mut self.nseq)
+            .with_checksums(self.checksums)
+            .with_codec(self.codec)
+            .with_file(file_number)
+            .with_digest(self.digests && !metadata.dedup);
+
+        // Deduplicated chunks are tagged, so the reconstructed data is hashed instead
+        let mut digest = (self.digests && metadata.dedup).then(Sha256::new);
+        let received = match mapped {
+            Some(mut window) => {
+                metadata.strategy = WriteStrategy::Mapped;
+                let durability = self.durability;
+                let display = display.as_mut();
+                let received =
+                    Self::recv_mapped(&mut channel, &mut window, max_chunk, durability, display);
+
+                // Write back so the page cache can be released, or the file synced below
+                if received.is_ok() && (self.tuning.drop_cache || durability != Durability::None) {
+                    window.flush()?;
+                }
+                received
+            }
+            None if metadata.dedup => {
+                metadata.strategy = WriteStrategy::Buffered;
+                let size = metadata.filesize;
+                let durability = self.durability;
+                let buffer = self.buffer.get(max_chunk);
+                let mut index = self.index.lock();
+                let display = display.as_mut();
+                let received = Self::recv_deduplicated(
+                    &mut channel,
+                    &path,
+                    size,
+                    buffer,
+                    &mut index,
+                    durability,
+                    digest.as_mut(),
+                    display,
+                );
+
+                // An aborted file still takes its place in the window
+                if let Err(e) = &received {
+                    if Self::aborted(&**e) {
+                        index.finish_file(None);
+                    }
+                }
+                received
+            }
+            None => {
+                metadata.strategy = WriteStrategy::Buffered;
+                let size = metadata.filesize;
+                let durability = self.durability;
+                let buffer = self.buffer.get(max_chunk);
+                let display = display.as_mut();
+                Self::recv_buffered(&mut channel, &path, size, buffer, durability, display)
+            }
+        };
+
+        // Discard what was received of a file the sender aborted, the
+        // session continues with the next file
+        let total = match received {
+            Err(e) if Self::aborted(&*e) => {
+                let _ = std::fs::remove_file(&path);
+                return Err(e);
+            }
+            received => received?,
+        };
+
         // Check for incomplete transfers
         if total != metadata.filesize as usize {
             return Err(Incomplete.into());
         }
+
+        // Check the data against the sender's digest, if both are known
+        metadata.digest = match digest {
+            Some(digest) => Some(digest.finalize().into()),
+            None => channel.digest(),
+        };
+        self.last_digest = metadata.digest;
+        if let (Some(theirs), Some(ours)) = (expected.and_then(|e| e.digest), metadata.digest) {
+            if theirs != ours {
+                let _ = std::fs::remove_file(&path);
+                return Err(DigestMismatch.into());
+            }
+        }
+
+        // Create any byte-identical copies of this file locally
+        let copies = expected.map_or(&[][..], |exp| &exp.copies[..]);
+        Self::expand_copies(&path, outdir, copies)?;
+
+        // Only report completion once the data is on stable storage, if requested
+        if self.durability != Durability::None {
+            file::sync_path(&path)?;
+            for copy in copies.iter().filter_map(|c| Path::new(c).file_name()) {
+                file::sync_path(&outdir.join(copy))?;
+            }
+        }
+
+        // Release the received file from the page cache if requested
+        if self.tuning.drop_cache {
+            let _ = file::drop_cache(&File::open(&path)?);
+        }
+        self.log
+            .lock()
+            .file(&metadata.filename, total as u64, metadata.digest);
+        Ok(metadata)
+    }
+
+    /// Receive the next file over the portal into a writer, such as stdout,
+    /// instead of a file in a directory. Nothing is written to disk, so the
+    /// file's copies are left out. Must be called after performing the
+    /// handshake or this method will return an error.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::net::TcpStream;
+    /// use portal_lib::{Portal, Direction, NO_PROGRESS_CALLBACK};
+    ///
+    /// let mut portal = Portal::init(Direction::Receiver,"id".into(), "password".into()).unwrap();
+    /// let mut stream = TcpStream::connect("127.0.0.1:34254").unwrap();
+    /// portal.handshake(&mut stream).unwrap();
+    ///
+    /// // Pipe the file to another program
+    /// let mut stdout = std::io::stdout().lock();
+    /// portal.recv_into(&mut stream, &mut stdout, None, NO_PROGRESS_CALLBACK).unwrap();
+    /// ```
+    pub fn recv_into<R, W, D>(
+        &mut self,
+        peer: &mut R,
+        writer: &mut W,
+        expected: Option<&FileEntry>,
+        mut display: Option<D>,
+    ) -> Result<FileEntry, Box<dyn Error>>
+    where
+        R: Read,
+        W: Write,
+        D: FnMut(usize, usize),
+    {
+        // Check that the key exists to confirm the handshake is complete
+        let key = self.key.as_ref().ok_or(NoPeer)?;
+        self.last_digest = None;
+
+        // Receive the metadata, numbering the file
+        let mut metadata: FileEntry =
+            Protocol::read_encrypted_from(peer, key, self.codec, self.format)?;
+        let file_number = self.files.lock().next(self.bind_chunks);
+
+        // Verify the metadata is expected, if a comparison is provided
+        if expected.is_some_and(|exp| metadata != *exp) {
+            return Err(BadMsg.into());
+        }
+
+        // References in deduplicated files are copied from disk
+        if metadata.dedup {
+            let reason = "deduplicated files can only be received into a directory";
+            return Err(BadConfig(reason.into()).into());
+        }
+
+        // Open-ended files are written until the end-of-stream marker
+        let max_chunk = metadata.chunk_limit()?;
+        let (size, quota) = match metadata.is_stream() {
+            true => (None, self.quota.lock().admit_open_ended()?),
+            false => {
+                self.quota.lock().admit(metadata.filesize, 1)?;
+                (Some(metadata.filesize as usize), None)
+            }
+        };
+
+        metadata.strategy = WriteStrategy::Buffered;
+        let mut channel = EncryptedChannel::new(peer, key, &mut self.nseq)
+            .with_checksums(self.checksums)
+            .with_codec(self.codec)
+            .with_file(file_number)
+            .with_digest(self.digests);
+        let buffer = self.buffer.get(max_chunk);
+        let display = display.as_mut();
+        let total = Self::recv_written(&mut channel, writer, size, buffer, quota, display)?;
+        metadata.digest = channel.digest();
+        self.last_digest = metadata.digest;
+
+        match size {
+            Some(size) if total != size => return Err(Incomplete.into()),
+            Some(_) => {}
+            None => {
+                self.quota.lock().consume(total as u64);
+                metadata.filesize = total as u64;
+            }
+        }
+
+        // The data was already written, but the caller learns it's wrong
+        if let (Some(theirs), Some(ours)) = (expected.and_then(|e| e.digest), metadata.digest) {
+            if theirs != ours {
+                return Err(DigestMismatch.into());
+            }
+        }
+        self.log
+            .lock()
+            .file(&metadata.filename, total as u64, metadata.digest);
         Ok(metadata)
     }
 
-    /// Helper: mmap's a file into memory for reading
-    fn map_readable_file(&self, f: &PathBuf) -> Result<MmapMut, Box<dyn Error>> {
-        let file = File::open(f)?;
-        let mmap = unsafe { MmapOptions::new().map_copy(&file)? };
+    /// Open an EncryptedChannel to the peer for payloads other than
+    /// files. Must be called after performing the handshake or this
+    /// method will return an error.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::net::TcpStream;
+    /// use portal_lib::{Portal,Direction};
+    ///
+    /// let mut portal = Portal::init(Direction::Sender,"id".into(), "password".into()).unwrap();
+    /// let mut stream = TcpStream::connect("127.0.0.1:34254").unwrap();
+    /// portal.handshake(&mut stream).unwrap();
+    ///
+    /// // Send each row as its own encrypted chunk
+    /// let mut channel = portal.channel(&mut stream).unwrap();
+    /// for row in ["id,name", "1,portal"] {
+    ///     channel.write_chunk(row.as_bytes()).unwrap();
+    /// }
+    /// ```
+    pub fn channel<'a, P>(
+        &'a mut self,
+        peer: &'a mut P,
+    ) -> Result<EncryptedChannel<'a, P>, Box<dyn Error>> {
+        // Check that the key exists to confirm the handshake is complete
+        let key = self.key.as_ref().ok_or(NoPeer)?;
+        Ok(EncryptedChannel::new(peer, key, &mut self.nseq)
+            .with_checksums(self.checksums)
+            .with_codec(self.codec))
+    }
+
+    /// Create an independent handle to this session, for use from another
+    /// thread. The copy shares the session key but has its own randomly
+    /// seeded NonceSequence, just as each peer already does, so neither
+    /// needs to lock the other when encrypting. The numbering of files,
+    /// the [`ReceivePolicy`] quota & the transcript are shared, as the peer
+    /// keeps one of each for the whole session. So are the index of
+    /// deduplicated files & the files offered with [`Portal::outgoing`],
+    /// which either handle may then send. Deduplicated files may refer to
+    /// those before them, so must be received in the order they were sent.
+    ///
+    /// Fails until the handshake is complete.
+    ///
+    /// ```no_run
+    /// use std::net::TcpStream;
+    /// use std::thread;
+    /// use portal_lib::{Portal,Direction};
+    ///
+    /// let mut portal = Portal::init(Direction::Sender,"id".into(), "password".into()).unwrap();
+    /// let mut stream = TcpStream::connect("127.0.0.1:34254").unwrap();
+    /// portal.handshake(&mut stream).unwrap();
+    ///
+    /// // Send on one thread & receive on another
+    /// let mut sender = portal.try_clone().unwrap();
+    /// let mut writer = stream.try_clone().unwrap();
+    /// let handle = thread::spawn(move || {
+    ///     sender.channel(&mut writer).unwrap().write_chunk(b"ping").unwrap();
+    /// });
+    ///
+    /// let mut buf = [0u8; 4];
+    /// portal.channel(&mut stream).unwrap().read_chunk(&mut buf).unwrap();
+    /// handle.join().unwrap();
+    /// ```
+    pub fn try_clone(&self) -> Result<Portal, Box<dyn Error>> {
+        let key = self.key.clone().ok_or(NoPeer)?;
+        let mut buffer = ChunkBuffer::default();
+        if self.constant_memory {
+            buffer.reserve();
+        }
+        Ok(Portal {
+            id: self.id.clone(),
+            direction: self.direction,
+            exchange: self.exchange,
+            nseq: NonceSequence::new(),
+            state: None,
+            secret: None,
+            key: Some(key),
+            tuning: self.tuning,
+            format: self.format,
+            strict: self.strict,
+            psk: None,
+            checksums: self.checksums,
+            digests: self.digests,
+            last_digest: None,
+            adaptive_chunks: self.adaptive_chunks,
+            limits: self.limits,
+            quota: self.quota.clone(),
+            durability: self.durability,
+            mmap_window: self.mmap_window,
+            constant_memory: self.constant_memory,
+            buffer,
+            dedup: self.dedup,
+            index: self.index.clone(),
+            bind_chunks: self.bind_chunks,
+            files: self.files.clone(),
+            offered: self.offered.clone(),
+            relay_secret: self.relay_secret.clone(),
+            namespace: self.namespace.clone(),
+            log: self.log.clone(),
+            priority: self.priority,
+            codec: self.codec,
+            rtt: self.rtt,
+            relay: self.relay,
+            salt: self.salt,
+            failures: 0,
+        })
+    }
+
+    /// Helper: receive every chunk of a file in-place into a window of the
+    /// file mapped for writing, sliding it forward through the file
+    fn recv_mapped<R, D>(
+        channel: &mut EncryptedChannel<R>,
+        window: &mut file::WriteWindow,
+        max_chunk: usize,
+        durability: Durability,
+        mut display: Option<&mut D>,
+    ) -> Result<usize, Box<dyn Error>>
+    where
+        R: Read,
+        D: FnMut(usize, usize),
+    {
+        let interval = durability.interval(false);
+        let size = window.size();
+        let mut synced = 0;
+        let mut total = 0;
+        while total < size {
+            // Receive the next chunk in-place, whatever its size
+            let needs = max_chunk.min(size - total);
+            let len = channel.read_chunk(window.at(total, needs)?)?;
+            if len == 0 {
+                return Err(Incomplete.into());
+            }
+
+            // Increment and write back the region received since the last sync
+            total += len;
+            if interval.is_some_and(|n| total - synced >= n) {
+                window.flush_range(synced, total)?;
+                synced = total;
+            }
+
+            // Optionally invoke callback
+            if let Some(c) = display.as_mut() {
+                c(total, len);
+            }
+        }
+        Ok(total)
+    }
+
+    /// Helper: receive every chunk of a file into a single buffer and stream
+    /// it to disk. Used when the file cannot be allocated or mapped up front,
+    /// or in constant memory mode.
+    fn recv_buffered<R, D>(
+        channel: &mut EncryptedChannel<R>,
+        path: &Path,
+        size: u64,
+        buffer: &mut [u8],
+        durability: Durability,
+        mut display: Option<&mut D>,
+    ) -> Result<usize, Box<dyn Error>>
+    where
+        R: Read,
+        D: FnMut(usize, usize),
+    {
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        let interval = durability.interval(file::apply_dsync(&mut options, durability));
+        let mut writer = options.open(path)?;
+
+        let max_chunk = buffer.len();
+        let mut synced = 0;
+        let mut total = 0;
+        while total < size as usize {
+            // Receive the next chunk into the buffer & write it out
+            let limit = std::cmp::min(max_chunk, size as usize - total);
+            let len = channel.read_chunk(&mut buffer[..limit])?;
+            if len == 0 {
+                return Err(Incomplete.into());
+            }
+            writer.write_all(&buffer[..len])?;
+
+            // Increment and sync the data written since the last sync
+            total += len;
+            if interval.is_some_and(|n| total - synced >= n) {
+                writer.sync_data()?;
+                synced = total;
+            }
+
+            // Optionally invoke callback
+            if let Some(c) = display.as_mut() {
+                c(total, len);
+            }
+        }
+
+        writer.flush()?;
+        Ok(total)
+    }
+
+    /// Helper: receive every chunk of a deduplicated file into a single
+    /// buffer, writing its data or copying the data it refers to from
+    /// where it was written earlier in the session. The data is hashed as
+    /// it is reconstructed, if a digest is given.
+    #[allow(clippy::too_many_arguments)]
+    fn recv_deduplicated<R, D>(
+        channel: &mut EncryptedChannel<R>,
+        path: &Path,
+        size: u64,
+        buffer: &mut [u8],
+        index: &mut dedup::DedupIndex,
+        durability: Durability,
+        mut digest: Option<&mut Sha256>,
+        mut display: Option<&mut D>,
+    ) -> Result<usize, Box<dyn Error>>
+    where
+        R: Read,
+        D: FnMut(usize, usize),
+    {
+        // The file is read back for references into itself
+        let mut options = OpenOptions::new();
+        options.read(true).write(true).create(true).truncate(true);
+        let interval = durability.interval(file::apply_dsync(&mut options, durability));
+        let file = options.open(path)?;
+        let number = index.start_file();
+
+        let size = size as usize;
+        let mut synced = 0;
+        let mut total = 0;
+        while total < size {
+            let len = channel.read_chunk(buffer)?;
+            if len == 0 {
+                return Err(Incomplete.into());
+            }
+
+            // References must be to data already written & fit the buffer
+            let len = match DedupChunk::parse(&buffer[..len])? {
+                DedupChunk::Literal(data) if data.len() <= size - total => {
+                    file::write_all_at(&file, data, total as u64)?;
+                    if let Some(digest) = digest.as_mut() {
+                        digest.update(data);
+                    }
+                    data.len()
+                }
+                DedupChunk::Reference(found) if found.len as usize <= size - total => {
+                    let source = match found.file == number {
+                        true if found.offset.saturating_add(found.len as u64) <= total as u64 => {
+                            &file
+                        }
+                        true => return Err(BadMsg.into()),
+                        false => index.received(found.file).ok_or(BadMsg)?,
+                    };
+                    let data = buffer.get_mut(..found.len as usize).ok_or(BadMsg)?;
+                    file::read_exact_at(source, data, found.offset)?;
+                    file::write_all_at(&file, data, total as u64)?;
+                    if let Some(digest) = digest.as_mut() {
+                        digest.update(&*data);
+                    }
+                    data.len()
+                }
+                _ => return Err(BadMsg.into()),
+            };
+            if len == 0 {
+                return Err(BadMsg.into());
+            }
+
+            // Increment and sync the data written since the last sync
+            total += len;
+            if interval.is_some_and(|n| total - synced >= n) {
+                file.sync_data()?;
+                synced = total;
+            }
+
+            // Optionally invoke callback
+            if let Some(c) = display.as_mut() {
+                c(total, len);
+            }
+        }
+
+        index.finish_file(Some(file));
+        Ok(total)
+    }
+
+    /// Helper: write every chunk of an open-ended file to disk until
+    /// the end-of-stream marker is received, replacing what the file held
+    fn recv_appended<R, D>(
+        channel: &mut EncryptedChannel<R>,
+        path: &Path,
+        buffer: &mut [u8],
+        quota: Option<u64>,
+        durability: Durability,
+        mut display: Option<&mut D>,
+    ) -> Result<usize, Box<dyn Error>>
+    where
+        R: Read,
+        D: FnMut(usize, usize),
+    {
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        let interval = durability.interval(file::apply_dsync(&mut options, durability));
+        let mut file = options.open(path)?;
+
+        let mut synced = 0;
+        let mut total = 0;
+        loop {
+            // Receive the next chunk, an empty chunk ends the stream. If the
+            // sender aborts the file, drop what was written of it
+            let len = match channel.read_chunk(buffer) {
+                Ok(0) => break,
+                Ok(len) => len,
+                Err(e) if Self::aborted(&*e) => {
+                    std::fs::remove_file(path)?;
+                    return Err(e);
+                }
+                Err(e) => return Err(e),
+            };
+
+            // Stop before writing past the quota
+            if let Some(max) = quota.filter(|max| (total + len) as u64 > *max) {
+                return Err(QuotaExceeded(format!("an open-ended file over {} bytes", max)).into());
+            }
+            file.write_all(&buffer[..len])?;
+
+            // Increment and sync the data written since the last sync
+            total += len;
+            if interval.is_some_and(|n| total - synced >= n) {
+                file.sync_data()?;
+                synced = total;
+            }
+
+            // Optionally invoke callback
+            if let Some(c) = display.as_mut() {
+                c(total, len);
+            }
+        }
+
+        file.flush()?;
+        Ok(total)
+    }
+
+    /// Helper: write every chunk of a file to a writer, until `size` bytes
+    /// have been received or, without a size, the end-of-stream marker
+    fn recv_written<R, W, D>(
+        channel: &mut EncryptedChannel<R>,
+        writer: &mut W,
+        size: Option<usize>,
+        buffer: &mut [u8],
+        quota: Option<u64>,
+        mut display: Option<&mut D>,
+    ) -> Result<usize, Box<dyn Error>>
+    where
+        R: Read,
+        W: Write,
+        D: FnMut(usize, usize),
+    {
+        let max_chunk = buffer.len();
+        let mut total = 0;
+        while size.is_none_or(|size| total < size) {
+            // Receive the next chunk, an empty chunk ends a stream
+            let limit = size.map_or(max_chunk, |size| max_chunk.min(size - total));
+            let len = channel.read_chunk(&mut buffer[..limit])?;
+            match (len, size) {
+                (0, Some(_)) => return Err(Incomplete.into()),
+                (0, None) => break,
+                _ => {}
+            }
+
+            // Stop before writing past the quota
+            if let Some(max) = quota.filter(|max| (total + len) as u64 > *max) {
+                return Err(QuotaExceeded(format!("an open-ended file over {} bytes", max)).into());
+            }
+            writer.write_all(&buffer[..len])?;
+            total += len;
+
+            // Optionally invoke callback
+            if let Some(c) = display.as_mut() {
+                c(total, len);
+            }
+        }
+
+        writer.flush()?;
+        Ok(total)
+    }
+
+    /// Helper: whether the sender aborted the file being received
+    fn aborted(e: &(dyn Error + 'static)) -> bool {
+        e.downcast_ref() == Some(&FileChangedDuringTransfer)
+    }
+
+    /// Helper: copy a received file to each of its duplicate names,
+    /// refusing to replace any file that already exists
+    fn expand_copies(path: &Path, outdir: &Path, copies: &[String]) -> Result<(), Box<dyn Error>> {
+        for copy in copies {
+            // Ensure the filename is only the name component
+            let dest = match Path::new(copy).file_name() {
+                Some(s) => outdir.join(s),
+                _ => return Err(BadFileName.into()),
+            };
+
+            // A copy with the same name is the file itself
+            if dest != path {
+                let mut writer = OpenOptions::new()
+                    .write(true)
+                    .create_new(true)
+                    .open(&dest)?;
+                std::io::copy(&mut File::open(path)?, &mut writer)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Helper: mmap's a window of a file into memory for reading. The
+    /// mapping is a private copy, so may be encrypted in-place.
+    fn map_readable_file(
+        file: &File,
+        start: usize,
+        len: usize,
+        sequential: bool,
+    ) -> Result<MmapMut, Box<dyn Error>> {
+        let mmap = unsafe {
+            MmapOptions::new()
+                .offset(start as u64)
+                .len(len)
+                .map_copy(file)?
+        };
+
+        // Hints are best-effort, failures are not fatal
+        if sequential {
+            let _ = file::advise_sequential(&mmap);
+        }
         Ok(mmap)
     }
 
-    /// Helper: mmap's a file into memory for writing
-    fn map_writeable_file(&self, f: &PathBuf, size: u64) -> Result<MmapMut, Box<dyn Error>> {
+    /// Helper: allocate a file & map its first window into memory for writing
+    fn map_writeable_file(
+        &self,
+        f: &PathBuf,
+        size: u64,
+    ) -> Result<file::WriteWindow, Box<dyn Error>> {
         let file = OpenOptions::new()
             .read(true)
             .write(true)
             .create(true)
+            .truncate(false)
             .open(f)?;
 
         file.set_len(size)?;
-        let mmap = unsafe { MmapOptions::new().map_mut(&file)? };
-        Ok(mmap)
+        let write_back = self.tuning.drop_cache || self.durability != Durability::None;
+        let size = size as usize;
+        let sequential = self.tuning.sequential;
+        let window = file::WriteWindow::new(file, size, self.mmap_window, sequential, write_back)?;
+        Ok(window)
     }
 
     /// Returns a copy of the Portal::Direction associated with
@@ -498,4 +2383,356 @@ impl Portal {
     pub fn set_key(&mut self, key: Vec<u8>) {
         self.key = Some(key);
     }
+
+    /// Returns true if file & channel chunks carry a checksum trailer
+    pub fn get_checksums(&self) -> bool {
+        self.checksums
+    }
+
+    /// Follow every file & channel chunk with a CRC32C trailer, so data
+    /// corrupted by an unreliable transport is reported as `Corrupted`
+    /// rather than failing decryption. Both peers must enable this.
+    pub fn set_checksums(&mut self, checksums: bool) {
+        self.checksums = checksums;
+    }
+
+    /// Returns true if files are hashed as they are sent or received
+    pub fn get_digests(&self) -> bool {
+        self.digests
+    }
+
+    /// Compute the SHA-256 digest of each file while its chunks are
+    /// encrypted or decrypted, rather than reading it again. Received files
+    /// are returned with their digest, & checked against the one in the
+    /// expected entry if the sender provided one. Deduplicated files are
+    /// hashed as their data is read or reconstructed, rather than as the
+    /// chunks on the wire. Peers needn't agree.
+    ///
+    /// Progress callbacks aren't given the digest, which is only known once
+    /// the whole file has passed. Read it from the entry returned by
+    /// [`Portal::recv_file`], or [`Portal::get_last_digest`] on either side.
+    ///
+    /// ```
+    /// use portal_lib::{Portal, Direction};
+    ///
+    /// let mut portal = Portal::init(Direction::Receiver, "id".into(), "password".into()).unwrap();
+    /// assert!(!portal.get_digests());
+    /// portal.set_digests(true);
+    /// assert!(portal.get_digests());
+    /// assert_eq!(portal.get_last_digest(), None);
+    /// ```
+    pub fn set_digests(&mut self, digests: bool) {
+        self.digests = digests;
+    }
+
+    /// Returns the digest of the last file sent or received, if it was hashed
+    pub fn get_last_digest(&self) -> Option<[u8; 32]> {
+        self.last_digest
+    }
+
+    /// Returns true if files are sent with an adaptive chunk size
+    pub fn get_adaptive_chunks(&self) -> bool {
+        self.adaptive_chunks
+    }
+
+    /// Grow or shrink the chunk size while sending files, based on the
+    /// observed throughput, see [`ChunkSizer`]. Enabled by default,
+    /// otherwise every chunk is [`CHUNK_SIZE`].
+    pub fn set_adaptive_chunks(&mut self, adaptive: bool) {
+        self.adaptive_chunks = adaptive;
+    }
+
+    /// Returns a copy of the TransferTuning hints applied
+    /// to files sent or received by this Portal request
+    pub fn get_tuning(&self) -> TransferTuning {
+        self.tuning
+    }
+
+    /// Sets the TransferTuning hints applied to files sent
+    /// or received by this Portal request
+    ///
+    /// ```
+    /// use portal_lib::{Portal, Direction, TransferTuning};
+    ///
+    /// let mut portal = Portal::init(Direction::Sender, "id".into(), "password".into()).unwrap();
+    /// portal.set_tuning(TransferTuning {
+    ///     sequential: true,
+    ///     drop_cache: true,
+    /// });
+    /// ```
+    pub fn set_tuning(&mut self, tuning: TransferTuning) {
+        self.tuning = tuning;
+    }
+
+    /// Returns the length of the region of a file mapped at once
+    pub fn get_mmap_window(&self) -> usize {
+        self.mmap_window
+    }
+
+    /// Bound the memory a transfer keeps resident by mapping at most this
+    /// many bytes of a file at once, [`MMAP_WINDOW_SIZE`] by default. The
+    /// length is rounded up so that any chunk fits within a window.
+    pub fn set_mmap_window(&mut self, len: usize) {
+        self.mmap_window = file::window_size(len);
+    }
+
+    /// Returns whether transfers are limited to a buffer allocated up front
+    pub fn get_constant_memory(&self) -> bool {
+        self.constant_memory
+    }
+
+    /// Transfer every file through a single buffer of [`MAX_CHUNK_SIZE`]
+    /// bytes, allocated when enabled, rather than mapping files into
+    /// memory. Nothing is then allocated for any chunk, for consumers that
+    /// must certify how much memory a transfer uses. Messages other than
+    /// chunks, such as each file's metadata, are still decoded into
+    /// allocations bounded by [`MAX_OBJECT_SIZE`].
+    ///
+    /// ```
+    /// use portal_lib::{Portal, Direction};
+    ///
+    /// let mut portal = Portal::init(Direction::Receiver, "id".into(), "password".into()).unwrap();
+    /// portal.set_constant_memory(true);
+    /// assert!(portal.get_constant_memory());
+    /// ```
+    pub fn set_constant_memory(&mut self, constant: bool) {
+        self.constant_memory = constant;
+        match constant {
+            true => self.buffer.reserve(),
+            false => self.buffer = ChunkBuffer::default(),
+        }
+    }
+
+    /// Returns whether repeated data is deduplicated. After the handshake,
+    /// whether both peers offered to.
+    pub fn get_dedup(&self) -> bool {
+        self.dedup
+    }
+
+    /// Offer to deduplicate repeated data within the transfer, such as the
+    /// duplicated regions of a disk image. Files are then cut into chunks
+    /// at boundaries chosen by their content, & a chunk already sent in
+    /// the session is sent as a reference to the earlier copy instead.
+    /// Only used when both peers offer it through a relay, otherwise files
+    /// are sent as usual. Receivers must then use [`Portal::recv_file`],
+    /// as references are copied from the received files.
+    ///
+    /// The relay can tell which chunks are references from their size,
+    /// learning where a transfer repeats itself.
+    ///
+    /// ```
+    /// use portal_lib::{Portal, Direction};
+    ///
+    /// let mut portal = Portal::init(Direction::Sender, "id".into(), "password".into()).unwrap();
+    /// portal.set_dedup(true);
+    /// assert!(portal.get_dedup());
+    /// ```
+    pub fn set_dedup(&mut self, dedup: bool) {
+        self.dedup = dedup;
+    }
+
+    /// Returns whether chunks are bound to their file. After the handshake,
+    /// whether both peers offered to.
+    pub fn get_bind_chunks(&self) -> bool {
+        self.bind_chunks
+    }
+
+    /// Offer to authenticate the number of the file each chunk belongs to
+    /// & its offset within the file, so that a chunk can't be spliced
+    /// into another file or moved within the same one. Enabled by default,
+    /// & only used when both peers offer it through a relay. Direct & PSK
+    /// sessions rely on the nonce sequence alone to keep chunks in order.
+    ///
+    /// ```
+    /// use portal_lib::{Portal, Direction};
+    ///
+    /// let mut portal = Portal::init(Direction::Sender, "id".into(), "password".into()).unwrap();
+    /// assert!(portal.get_bind_chunks());
+    /// portal.set_bind_chunks(false);
+    /// assert!(!portal.get_bind_chunks());
+    /// ```
+    pub fn set_bind_chunks(&mut self, bind: bool) {
+        self.bind_chunks = bind;
+    }
+
+    /// Returns how received files are synced to storage
+    pub fn get_durability(&self) -> Durability {
+        self.durability
+    }
+
+    /// Choose how received files are synced to storage before
+    /// [`Portal::recv_file`] reports them complete, see [`Durability`]
+    pub fn set_durability(&mut self, durability: Durability) {
+        self.durability = durability;
+    }
+
+    /// Returns the soft limits reported to the verify callback
+    pub fn get_limits(&self) -> TransferLimits {
+        self.limits
+    }
+
+    /// Set soft limits on incoming transfers. Transfers exceeding them are
+    /// not rejected, the verify callback of [`Portal::incoming`] is told
+    /// through its [`TransferPolicy`].
+    pub fn set_limits(&mut self, limits: TransferLimits) {
+        self.limits = limits;
+    }
+
+    /// Returns the hard limits on what this Portal receives
+    pub fn get_receive_policy(&self) -> ReceivePolicy {
+        self.quota.lock().policy
+    }
+
+    /// Set hard limits on what this Portal receives, see [`ReceivePolicy`].
+    /// Anything already received continues to count against them.
+    pub fn set_receive_policy(&mut self, policy: ReceivePolicy) {
+        self.quota.lock().policy = policy;
+    }
+
+    /// Returns true if the handshake will strictly validate
+    /// messages provided by the relay
+    pub fn get_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Enables strict validation of relay-provided messages during the
+    /// handshake, see [`Protocol::connect_strict`]. Violations fail the
+    /// handshake with a dedicated error rather than NoPeer.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Returns the secret used to authenticate to a private relay, if set
+    pub fn get_relay_secret(&self) -> Option<&[u8]> {
+        self.relay_secret.as_deref()
+    }
+
+    /// Sets the secret shared with a private relay. The handshake then
+    /// presents an access token derived from it, see [`AuthenticatedConnect`].
+    /// Relays predating access tokens will refuse the handshake.
+    pub fn set_relay_secret(&mut self, secret: Option<Vec<u8>>) {
+        self.relay_secret = secret;
+    }
+
+    /// Returns the application namespace, if set
+    pub fn get_namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
+    /// Sets an application namespace for products embedding portal, which
+    /// is mixed into the session key & the key confirmation labels. Peers
+    /// in different namespaces, or a namespaced & a vanilla peer, then
+    /// fail key confirmation even with identical pass-phrases. Both peers
+    /// must set the same namespace before the handshake.
+    ///
+    /// ```
+    /// use portal_lib::{Portal, Direction};
+    ///
+    /// let mut portal = Portal::init(Direction::Sender, "id".into(), "password".into()).unwrap();
+    /// portal.set_namespace(Some("com.example.app".into()));
+    /// assert_eq!(portal.get_namespace(), Some("com.example.app"));
+    /// ```
+    pub fn set_namespace(&mut self, namespace: Option<String>) {
+        self.namespace = namespace;
+    }
+
+    /// Returns the round-trip time to the peer, measured during the
+    /// handshake. None until the handshake has completed, or if either
+    /// peer doesn't advertise [`Capabilities::RTT`].
+    pub fn get_rtt(&self) -> Option<Duration> {
+        self.rtt
+    }
+
+    /// Returns words derived from the session key, once the handshake has
+    /// completed. Both peers get the same words only if they share the
+    /// same key, so reading them to each other over another channel rules
+    /// out an active attacker who learned the pass-phrase.
+    ///
+    /// ```no_run
+    /// use std::net::TcpStream;
+    /// use portal_lib::{Portal, Direction};
+    ///
+    /// let mut portal = Portal::init(Direction::Sender, "id".into(), "password".into()).unwrap();
+    /// let mut stream = TcpStream::connect("127.0.0.1:34254").unwrap();
+    /// portal.handshake(&mut stream).unwrap();
+    ///
+    /// println!("confirm with your peer: {}", portal.session_fingerprint().unwrap());
+    /// ```
+    pub fn session_fingerprint(&self) -> Option<String> {
+        let key = self.key.as_ref()?;
+        let value = Protocol::derive_fingerprint(key).ok()?;
+        Some(passphrase::from_value(value, FINGERPRINT_WORDS))
+    }
+
+    /// Export a transcript of the session so far: the fingerprint,
+    /// a digest of each TransferInfo exchanged, & each file completely sent
+    /// or received with its size & digest if hashed. The peer's transcript
+    /// of the same session matches it, see [`Transcript::matches`], if both
+    /// enabled [`Portal::set_digests`] alike. Fails until the handshake is
+    /// complete.
+    pub fn transcript(&self) -> Result<Transcript, Box<dyn Error>> {
+        let key = self.key.as_ref().ok_or(NoPeer)?;
+        let fingerprint = self.session_fingerprint().ok_or(CryptoError)?;
+        self.log.lock().seal(&self.id, fingerprint, key)
+    }
+
+    /// Returns the banner of the relay the handshake went through, which
+    /// describes it, such as its limit on the size of a session. None for
+    /// relays predating banners & direct sessions.
+    pub fn get_relay_banner(&self) -> Option<RelayBanner> {
+        self.relay
+    }
+
+    /// The token a Receiver presents to resume the session after its
+    /// connection to the relay drops, see [`Reconnecting`]. None until
+    /// paired by a relay.
+    ///
+    /// [`Reconnecting`]: protocol::Reconnecting
+    pub fn resume_token(&self) -> Option<ResumeToken> {
+        self.salt.map(|salt| salt.resume_token(&self.id))
+    }
+
+    /// Returns the priority advertised to the relay
+    pub fn get_priority(&self) -> Priority {
+        self.priority
+    }
+
+    /// Sets the priority advertised to the relay, which marks the traffic
+    /// it forwards for the session accordingly. The caller owns the socket
+    /// to the relay, see [`Priority::mark`] to mark it too.
+    pub fn set_priority(&mut self, priority: Priority) {
+        self.priority = priority;
+    }
+
+    /// Returns the wire codec. Before the handshake this is the newest
+    /// codec offered to the peer, afterwards the negotiated one.
+    pub fn get_codec(&self) -> CodecVersion {
+        self.codec
+    }
+
+    /// Sets the newest wire codec offered to the peer, must be called
+    /// before the handshake. Unsupported versions are rejected.
+    pub fn set_codec(&mut self, codec: CodecVersion) -> Result<(), Box<dyn Error>> {
+        if !codec.is_supported() {
+            return Err(SerializeError.into());
+        }
+        self.codec = codec;
+        Ok(())
+    }
+
+    /// Returns the WireFormat for encrypted objects. Before the handshake
+    /// this is the preferred format, afterwards the negotiated one.
+    pub fn get_wire_format(&self) -> WireFormat {
+        self.format
+    }
+
+    /// Sets the preferred WireFormat for encrypted objects, must be
+    /// called before the handshake. Unsupported formats are rejected.
+    pub fn set_wire_format(&mut self, format: WireFormat) -> Result<(), Box<dyn Error>> {
+        if !format.is_supported() {
+            return Err(SerializeError.into());
+        }
+        self.format = format;
+        Ok(())
+    }
 }
@@ -0,0 +1,105 @@
+use rand::Rng;
+
+// EFF's dice generated word list
+mod wordlist;
+use wordlist::{WORDS, WORD_COUNT};
+
+/// Separator placed between words of a pass-phrase
+pub const SEPARATOR: char = '-';
+
+/// Generates a pass-phrase with the EFF's dice generated
+/// word list, with each word joined by a `-`
+///
+/// <https://www.eff.org/dice>
+///
+/// ```
+/// use portal_lib::passphrase;
+///
+/// let phrase = passphrase::generate(3);
+/// assert_eq!(phrase.split('-').count(), 3);
+/// ```
+pub fn generate(words: usize) -> String {
+    let mut rng = rand::thread_rng();
+    (0..words)
+        .map(|_| WORDS[rng.gen_range(0, WORD_COUNT)])
+        .collect::<Vec<&str>>()
+        .join(&SEPARATOR.to_string())
+}
+
+/// Spell out `value` as the given number of words from the same word
+/// list, most significant first. Used to show values meant to be read
+/// aloud & compared, such as [`Portal::session_fingerprint`](crate::Portal::session_fingerprint).
+/// Only about 12.9 bits of `value` are represented by each word.
+///
+/// ```
+/// use portal_lib::passphrase;
+///
+/// let words = passphrase::from_value(0x1234_5678, 3);
+/// assert_eq!(words.split('-').count(), 3);
+/// assert_eq!(words, passphrase::from_value(0x1234_5678, 3));
+/// assert_ne!(words, passphrase::from_value(0x1234_5679, 3));
+/// ```
+pub fn from_value(mut value: u64, words: usize) -> String {
+    let mut spelled = (0..words)
+        .map(|_| {
+            let word = WORDS[(value % WORD_COUNT as u64) as usize];
+            value /= WORD_COUNT as u64;
+            word
+        })
+        .collect::<Vec<&str>>();
+    spelled.reverse();
+    spelled.join(&SEPARATOR.to_string())
+}
+
+/// Returns the entropy in bits of a pass-phrase created
+/// by `generate()` with the provided number of words
+pub fn entropy(words: usize) -> f64 {
+    words as f64 * (WORD_COUNT as f64).log2()
+}
+
+/// Estimate the entropy in bits of an arbitrary pass-phrase. Components
+/// found in the word list are counted as a random word, everything else
+/// is estimated by its length and the character classes it uses. This is
+/// an upper bound, user chosen phrases are usually far less random.
+///
+/// ```
+/// use portal_lib::passphrase;
+///
+/// let phrase = passphrase::generate(4);
+/// let estimate = passphrase::estimate_entropy(&phrase);
+/// assert!((estimate - passphrase::entropy(4)).abs() < 1e-9);
+/// assert!(passphrase::estimate_entropy("ab") < passphrase::entropy(1));
+/// ```
+pub fn estimate_entropy(phrase: &str) -> f64 {
+    phrase
+        .split(SEPARATOR)
+        .filter(|component| !component.is_empty())
+        .map(|component| match is_word(component) {
+            true => entropy(1),
+            false => component.chars().count() as f64 * charset_size(component).log2(),
+        })
+        .sum()
+}
+
+/// Returns true if the provided word is in the word list
+pub fn is_word(word: &str) -> bool {
+    WORDS.binary_search(&word).is_ok()
+}
+
+/// Helper: size of the character set a component appears to be drawn from
+fn charset_size(component: &str) -> f64 {
+    let mut size = 0;
+    if component.chars().any(|c| c.is_ascii_lowercase()) {
+        size += 26;
+    }
+    if component.chars().any(|c| c.is_ascii_uppercase()) {
+        size += 26;
+    }
+    if component.chars().any(|c| c.is_ascii_digit()) {
+        size += 10;
+    }
+    if component.chars().any(|c| !c.is_ascii_alphanumeric()) {
+        size += 33;
+    }
+    size as f64
+}
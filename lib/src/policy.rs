@@ -0,0 +1,251 @@
+//! Context handed to the verify callback of [`Portal::incoming`](crate::Portal::incoming),
+//! so a receiver can explain why a transfer deserves a closer look.
+//!
+//! Plain closures over `&TransferInfo` keep working as verify callbacks.
+//! To also receive the [`TransferPolicy`], wrap a closure in [`WithPolicy`]
+//! or implement [`Verify::verify_with_policy`].
+//!
+//! ```no_run
+//! use std::net::TcpStream;
+//! use portal_lib::{Direction, Portal, TransferInfo, TransferLimits, TransferPolicy, WithPolicy};
+//!
+//! let mut portal = Portal::init(Direction::Receiver, "id".into(), "password".into()).unwrap();
+//! portal.set_limits(TransferLimits {
+//!     max_size: Some(1 << 30),
+//!     max_files: Some(100),
+//! });
+//!
+//! let mut stream = TcpStream::connect("127.0.0.1:34254").unwrap();
+//! portal.handshake(&mut stream).unwrap();
+//!
+//! let confirm = WithPolicy(|_info: &TransferInfo, policy: &TransferPolicy| {
+//!     if policy.exceeds_size() {
+//!         println!("{} bytes is more than usual", policy.total_size);
+//!     }
+//!     policy.duplicate_names.is_empty()
+//! });
+//! for metadata in portal.incoming(&mut stream, Some(confirm)).unwrap() {
+//!     println!("receiving {}", metadata.filename);
+//! }
+//! ```
+//!
+//! Unattended receivers that accept every transfer should also set a
+//! [`ReceivePolicy`]. Unlike the soft limits it is enforced by the
+//! library, before the verify callback is consulted.
+use crate::errors::PortalError::{self, QuotaExceeded};
+use crate::TransferInfo;
+use std::collections::BTreeMap;
+
+/// Soft limits on incoming transfers. Exceeding them doesn't reject a
+/// transfer, it is reported to the verify callback to decide.
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default)]
+pub struct TransferLimits {
+    /// Total bytes, across every file
+    pub max_size: Option<u64>,
+
+    /// Number of files, counting each copy of a duplicated file
+    pub max_files: Option<usize>,
+}
+
+/// Hard limits on what a Portal receives over its lifetime. A transfer
+/// announced to exceed them is rejected by [`Portal::incoming`](crate::Portal::incoming),
+/// and a file exceeding them by [`Portal::recv_file`](crate::Portal::recv_file),
+/// with [`QuotaExceeded`](crate::errors::PortalError::QuotaExceeded).
+///
+/// Bytes & files count every copy of a duplicated file, as each is
+/// written to disk. An open-ended file is received until it exceeds the
+/// quota, leaving what was received so far in place.
+///
+/// ```
+/// use portal_lib::{Direction, Portal, ReceivePolicy};
+///
+/// let mut portal = Portal::init(Direction::Receiver, "id".into(), "password".into()).unwrap();
+/// portal.set_receive_policy(ReceivePolicy {
+///     max_total_bytes: Some(10 << 30),
+///     max_files: Some(1000),
+///     max_single_file: Some(4 << 30),
+/// });
+/// ```
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default)]
+pub struct ReceivePolicy {
+    /// Total bytes written, across every file
+    pub max_total_bytes: Option<u64>,
+
+    /// Number of files written
+    pub max_files: Option<usize>,
+
+    /// Bytes in any one file
+    pub max_single_file: Option<u64>,
+}
+
+/// A ReceivePolicy & what has been received against it so far
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default)]
+pub(crate) struct Quota {
+    pub(crate) policy: ReceivePolicy,
+    bytes: u64,
+    files: usize,
+}
+
+impl Quota {
+    /// Check that everything announced in a TransferInfo fits
+    pub(crate) fn check_transfer(&self, info: &TransferInfo) -> Result<(), PortalError> {
+        let mut bytes = self.bytes;
+        let mut files = self.files;
+        for metadata in info.all.iter() {
+            let writes = 1 + metadata.copies.len();
+            self.check_file(metadata.filesize)?;
+            bytes = bytes.saturating_add(metadata.filesize.saturating_mul(writes as u64));
+            files = files.saturating_add(writes);
+        }
+        self.check_totals(bytes, files)
+    }
+
+    /// Check then record a file of known size, written under `writes` names
+    pub(crate) fn admit(&mut self, size: u64, writes: usize) -> Result<(), PortalError> {
+        let bytes = self
+            .bytes
+            .saturating_add(size.saturating_mul(writes as u64));
+        let files = self.files.saturating_add(writes);
+        self.check_file(size)?;
+        self.check_totals(bytes, files)?;
+        self.bytes = bytes;
+        self.files = files;
+        Ok(())
+    }
+
+    /// Check then record a file of unknown size, returning the most bytes
+    /// it may grow to. Record its final size with [`Quota::consume`].
+    pub(crate) fn admit_open_ended(&mut self) -> Result<Option<u64>, PortalError> {
+        self.check_totals(self.bytes, self.files.saturating_add(1))?;
+        self.files += 1;
+        let remaining = self.policy.max_total_bytes.map(|max| max - self.bytes);
+        Ok(match (remaining, self.policy.max_single_file) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        })
+    }
+
+    /// Record bytes received into an open-ended file
+    pub(crate) fn consume(&mut self, bytes: u64) {
+        self.bytes = self.bytes.saturating_add(bytes);
+    }
+
+    fn check_file(&self, size: u64) -> Result<(), PortalError> {
+        match self.policy.max_single_file {
+            Some(max) if size > max => Err(QuotaExceeded(format!(
+                "a {} byte file, the limit is {} bytes",
+                size, max
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_totals(&self, bytes: u64, files: usize) -> Result<(), PortalError> {
+        if let Some(max) = self.policy.max_files.filter(|max| files > *max) {
+            return Err(QuotaExceeded(format!(
+                "{} files, the limit is {}",
+                files, max
+            )));
+        }
+        match self.policy.max_total_bytes {
+            Some(max) if bytes > max => Err(QuotaExceeded(format!(
+                "{} bytes, the limit is {} bytes",
+                bytes, max
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// What is known about an incoming transfer, beyond its TransferInfo
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct TransferPolicy {
+    /// The limits configured on the receiving Portal
+    pub limits: TransferLimits,
+
+    /// Total bytes that will be received
+    pub total_size: u64,
+
+    /// Number of files that will be written, including copies
+    pub file_count: usize,
+
+    /// Number of files sent once but written under several names
+    pub copies: usize,
+
+    /// File names that appear more than once, each overwriting the last
+    pub duplicate_names: Vec<String>,
+}
+
+impl TransferPolicy {
+    /// Compute the policy context of an incoming transfer
+    pub fn new(info: &TransferInfo, limits: TransferLimits) -> Self {
+        let mut names = BTreeMap::new();
+        for name in info
+            .all
+            .iter()
+            .flat_map(|m| std::iter::once(&m.filename).chain(&m.copies))
+        {
+            *names.entry(name.as_str()).or_insert(0) += 1;
+        }
+
+        TransferPolicy {
+            limits,
+            total_size: info.all.iter().map(|m| m.filesize).sum(),
+            file_count: names.values().sum(),
+            copies: info.all.iter().map(|m| m.copies.len()).sum(),
+            duplicate_names: names
+                .into_iter()
+                .filter(|(_, count)| *count > 1)
+                .map(|(name, _)| name.to_string())
+                .collect(),
+        }
+    }
+
+    /// True if the transfer is larger than the configured size limit
+    pub fn exceeds_size(&self) -> bool {
+        self.limits
+            .max_size
+            .is_some_and(|max| self.total_size > max)
+    }
+
+    /// True if the transfer has more files than the configured limit
+    pub fn exceeds_files(&self) -> bool {
+        self.limits
+            .max_files
+            .is_some_and(|max| self.file_count > max)
+    }
+}
+
+/// Decides whether to accept an incoming transfer. Implemented for any
+/// `FnMut(&TransferInfo) -> bool`, which ignores the policy context.
+pub trait Verify {
+    /// Return true to accept the transfer
+    fn verify(&mut self, info: &TransferInfo) -> bool;
+
+    /// Return true to accept the transfer, given its policy context.
+    /// Defaults to [`Verify::verify`].
+    fn verify_with_policy(&mut self, info: &TransferInfo, _policy: &TransferPolicy) -> bool {
+        self.verify(info)
+    }
+}
+
+impl<F: FnMut(&TransferInfo) -> bool> Verify for F {
+    fn verify(&mut self, info: &TransferInfo) -> bool {
+        self(info)
+    }
+}
+
+/// Adapts a closure over both the TransferInfo & TransferPolicy
+/// into a verify callback
+pub struct WithPolicy<F>(pub F);
+
+impl<F: FnMut(&TransferInfo, &TransferPolicy) -> bool> Verify for WithPolicy<F> {
+    fn verify(&mut self, info: &TransferInfo) -> bool {
+        let policy = TransferPolicy::new(info, TransferLimits::default());
+        (self.0)(info, &policy)
+    }
+
+    fn verify_with_policy(&mut self, info: &TransferInfo, policy: &TransferPolicy) -> bool {
+        (self.0)(info, policy)
+    }
+}
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+/// Optional features a peer supports, advertised in a Capabilities
+/// message sent right after its ConnectMessage & options. The
+/// ConnectMessage keeps the layout every relay & peer can read: relays
+/// that predate this message parse the request & ignore what follows, and
+/// relays only pass the peer's capabilities on to clients that sent their
+/// own.
+///
+/// Each capability is a flag, flags this build doesn't know are kept as
+/// is, so new ones can be added without changing the layout.
+///
+/// ```
+/// use portal_lib::Capabilities;
+///
+/// let ours = Capabilities::default().with(Capabilities::DEDUP, true);
+/// let theirs = Capabilities(Capabilities::DEDUP.0 | 1 << 63);
+/// assert!(ours.common(theirs).contains(Capabilities::DEDUP));
+/// assert!(!ours.common(theirs).contains(Capabilities::BIND_CHUNKS));
+/// ```
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Copy, Clone, Default, Hash)]
+pub struct Capabilities(pub u64);
+
+impl Capabilities {
+    /// Repeated chunks may be sent as references, see
+    /// [`crate::Portal::set_dedup`]
+    pub const DEDUP: Capabilities = Capabilities(1);
+
+    /// Chunks are bound to their file & offset, see
+    /// [`crate::Portal::set_bind_chunks`]
+    pub const BIND_CHUNKS: Capabilities = Capabilities(1 << 1);
+
+    /// The round-trip time is measured after key confirmation, see
+    /// [`crate::Portal::get_rtt`]
+    pub const RTT: Capabilities = Capabilities(1 << 2);
+
+    /// Returns true if every flag of `other` is set
+    pub fn contains(&self, other: Capabilities) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// These capabilities with the flags of `other` set or cleared
+    pub fn with(self, other: Capabilities, enabled: bool) -> Capabilities {
+        match enabled {
+            true => Capabilities(self.0 | other.0),
+            false => Capabilities(self.0 & !other.0),
+        }
+    }
+
+    /// The capabilities both peers support
+    pub fn common(self, theirs: Capabilities) -> Capabilities {
+        Capabilities(self.0 & theirs.0)
+    }
+}
@@ -0,0 +1,1188 @@
+//! Codecs for PortalMessages on the wire, each a version of the layout.
+//!
+//! # Negotiation
+//!
+//! - Connect, AuthConnect, Options, Capabilities, Paired & RelayControl
+//!   messages, the key exchange, key confirmation & round-trip
+//!   measurement always use v1, so that any relay & peer can read them.
+//! - Each peer advertises the newest version it supports in its
+//!   ConnectOptions. Peers paired by a relay use the older of the two for
+//!   every message after key confirmation, which binds both offers.
+//! - Sessions without ConnectMessages, such as direct & pre-shared key
+//!   sessions, stay on v1.
+//! - Relays accept the first message of a connection in either version,
+//!   see [`CodecVersion::detect`], & answer in the same one. Clients
+//!   using v2 for their Connect are paired with v1 clients as usual.
+//!
+//! # v1
+//!
+//! bincode 1.x with its default options. Integers are fixed-width little
+//! endian, lengths are u64, enum variants are u32 indexes & structs are
+//! their fields in order, without names. Messages aren't framed, reading
+//! one requires knowing the exact layout of every type in it.
+//!
+//! # v2
+//!
+//! A self-describing tagged layout. Each message is a frame, the value of
+//! the PortalMessage preceded by its length in bytes:
+//!
+//! ```text
+//! frame := length:u32be value
+//! value := type:u8 payload
+//! ```
+//!
+//! | type | value   | payload                                        |
+//! |------|---------|------------------------------------------------|
+//! | 0x00 | unit    | none, also written for `None`                  |
+//! | 0x01 | false   | none                                           |
+//! | 0x02 | true    | none                                           |
+//! | 0x03 | uint    | varint                                         |
+//! | 0x04 | int     | varint of the zigzag encoding                  |
+//! | 0x05 | float   | IEEE 754 binary64, big endian                  |
+//! | 0x06 | bytes   | varint length, then the bytes                  |
+//! | 0x07 | string  | varint length, then UTF-8                      |
+//! | 0x08 | some    | value                                          |
+//! | 0x09 | list    | varint count, then each value                  |
+//! | 0x0a | map     | varint count, then each key & value            |
+//! | 0x0b | struct  | varint count, then each field's name & value   |
+//! | 0x0c | variant | name, value                                    |
+//!
+//! Varints are unsigned LEB128 of at most 10 bytes. Names are a varint
+//! length followed by UTF-8.
+//!
+//! - Integers of any width are uints or ints, chars are strings.
+//! - Sequences & tuples are lists, except that sequences of `u8` such as
+//!   keys & nonces are written as bytes. Bytes are accepted wherever a
+//!   list of `u8` is expected.
+//! - Structs are written with their field names, newtype structs as
+//!   their inner value & tuple structs as lists.
+//! - Enum variants are written by name. Their value is a unit, the inner
+//!   value, a list or a struct for unit, newtype, tuple & struct variants.
+//!
+//! Readers skip fields they don't know & default missing fields marked
+//! `#[serde(default)]`, so fields can be added without a new version.
+//! Anything else, such as a new variant of PortalMessage, requires one.
+//!
+//! EncryptedMessages carry a commitment to the key they were encrypted
+//! with, see [`CodecVersion::commits`]. v1 has no room for one.
+//!
+//! ```
+//! use portal_lib::{CodecVersion, PortalMessage, WireCodec};
+//!
+//! let encoded = CodecVersion::V2.encode(&PortalMessage::TransferLimit(5)).unwrap();
+//! assert_eq!(
+//!     encoded,
+//!     [
+//!         0, 0, 0, 17, // frame length
+//!         0x0c, 13, // variant, 13 byte name
+//!         b'T', b'r', b'a', b'n', b's', b'f', b'e', b'r', b'L', b'i', b'm', b'i', b't',
+//!         0x03, 5, // uint 5
+//!     ]
+//! );
+//! ```
+use super::{EncryptedMessage, PortalMessage};
+use crate::errors::PortalError::*;
+use bincode::Options;
+use serde::de::{self, DeserializeSeed, Visitor};
+use serde::ser::{self, Serialize};
+use serde::{Deserialize, Deserializer};
+use std::convert::{TryFrom, TryInto};
+use std::error::Error;
+use std::fmt;
+use std::io::{Read, Write};
+
+/// Encodes & decodes PortalMessages in one version of the wire layout
+pub trait WireCodec {
+    /// The version of the layout
+    fn version(&self) -> CodecVersion;
+
+    /// Encode a message as it is sent on the wire
+    fn encode(&self, msg: &PortalMessage) -> Result<Vec<u8>, Box<dyn Error>>;
+
+    /// Receive a single message, failing with MessageTooLarge
+    /// rather than reading more than `limit` bytes
+    fn decode(&self, reader: &mut dyn Read, limit: u64) -> Result<PortalMessage, Box<dyn Error>>;
+
+    /// Encode & send a message, returning the bytes sent
+    fn send(&self, msg: &PortalMessage, writer: &mut dyn Write) -> Result<usize, Box<dyn Error>> {
+        let data = self.encode(msg)?;
+        writer.write_all(&data).or(Err(IOError))?;
+        Ok(data.len())
+    }
+}
+
+/// Longest encoding of the header sent before each chunk, in any version
+pub const MAX_DATA_HEADER: usize = 160;
+
+/// A version of the wire layout, as advertised in the ConnectOptions.
+/// Versions this build doesn't know are kept as is, so that offers from
+/// newer peers can still be negotiated down & bound into confirmation.
+///
+/// ```
+/// use portal_lib::CodecVersion;
+///
+/// let newer = CodecVersion(9);
+/// assert!(!newer.is_supported());
+/// assert_eq!(CodecVersion::negotiate(CodecVersion::LATEST, newer), CodecVersion::LATEST);
+/// assert_eq!(CodecVersion::negotiate(CodecVersion::V2, CodecVersion::V1), CodecVersion::V1);
+/// ```
+#[derive(
+    serde::Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug, Copy, Clone, Hash,
+)]
+pub struct CodecVersion(pub u8);
+
+impl CodecVersion {
+    /// bincode, see [`BincodeCodec`]
+    pub const V1: CodecVersion = CodecVersion(1);
+
+    /// Self-describing tags & lengths, see [`TlvCodec`]
+    pub const V2: CodecVersion = CodecVersion(2);
+
+    /// The newest version this build supports
+    pub const LATEST: CodecVersion = CodecVersion::V2;
+
+    /// The version to use given both peers' offers, the newest both support
+    pub fn negotiate(ours: CodecVersion, theirs: CodecVersion) -> CodecVersion {
+        ours.min(theirs).max(CodecVersion::V1)
+    }
+
+    /// Returns true if this build of the library implements the version
+    pub fn is_supported(&self) -> bool {
+        (CodecVersion::V1..=CodecVersion::LATEST).contains(self)
+    }
+
+    /// Whether encrypted messages carry a commitment to their key, see
+    /// [`EncryptedMessage::commitment`]. Those received without one in a
+    /// session on such a version are rejected.
+    pub fn commits(&self) -> bool {
+        *self >= CodecVersion::V2
+    }
+
+    /// The version a message sent to the relay was encoded with, given
+    /// at least its first 4 bytes. A v1 message begins with its variant
+    /// index as a little endian u32, a v2 frame with its length as a big
+    /// endian u32. Frames are never as long as 2^24 bytes, so a leading
+    /// zero followed by a non-zero length can only be a frame.
+    ///
+    /// ```
+    /// use portal_lib::{CodecVersion, PortalMessage, WireCodec};
+    ///
+    /// let msg = PortalMessage::TransferLimit(5);
+    /// let v1 = CodecVersion::V1.encode(&msg).unwrap();
+    /// let v2 = CodecVersion::V2.encode(&msg).unwrap();
+    /// assert_eq!(CodecVersion::detect(&v1), Some(CodecVersion::V1));
+    /// assert_eq!(CodecVersion::detect(&v2), Some(CodecVersion::V2));
+    /// assert_eq!(CodecVersion::detect(&v2[..3]), None);
+    /// ```
+    pub fn detect(prefix: &[u8]) -> Option<CodecVersion> {
+        let len: [u8; FRAME_PREFIX] = prefix.get(..FRAME_PREFIX)?.try_into().ok()?;
+        match (len[0], u32::from_be_bytes(len)) {
+            (0, 1..) => Some(CodecVersion::V2),
+            _ => Some(CodecVersion::V1),
+        }
+    }
+
+    /// Whether `data` holds at least one whole message sent to the relay.
+    /// A v2 frame's length is known up front, v1 messages are assumed
+    /// whole once their version can be detected.
+    pub fn is_complete(data: &[u8]) -> bool {
+        match CodecVersion::detect(data) {
+            Some(CodecVersion::V2) => {
+                TlvCodec::frame_len(data).is_some_and(|len| data.len() as u64 >= len)
+            }
+            Some(_) => true,
+            None => false,
+        }
+    }
+
+    /// Encode the header sent before each chunk into `out`, returning its
+    /// length. The layout is the same as [`WireCodec::encode`]'s, but
+    /// nothing is allocated, as this is done for every chunk.
+    ///
+    /// ```
+    /// use portal_lib::{CodecVersion, EncryptedMessage, PortalMessage, WireCodec, MAX_DATA_HEADER};
+    ///
+    /// let header = EncryptedMessage::default();
+    /// let mut out = [0u8; MAX_DATA_HEADER];
+    /// let len = CodecVersion::V2.encode_data_header(&header, &mut out).unwrap();
+    /// let msg = PortalMessage::EncryptedDataHeader(header);
+    /// assert_eq!(out[..len], CodecVersion::V2.encode(&msg).unwrap()[..]);
+    /// ```
+    pub fn encode_data_header(
+        &self,
+        header: &EncryptedMessage,
+        out: &mut [u8; MAX_DATA_HEADER],
+    ) -> Result<usize, Box<dyn Error>> {
+        match *self {
+            CodecVersion::V1 => {
+                let msg = PortalMessage::EncryptedDataHeader(header.clone());
+                let mut rest = &mut out[..];
+                bincode::serialize_into(&mut rest, &msg).or(Err(SerializeError))?;
+                Ok(MAX_DATA_HEADER - rest.len())
+            }
+            CodecVersion::V2 => TlvCodec::encode_data_header(header, out),
+            _ => Err(SerializeError.into()),
+        }
+    }
+
+    /// Receive a message no longer than [`MAX_DATA_HEADER`], such as the
+    /// header sent before each chunk. Its frame is read into a buffer on
+    /// the stack rather than allocated.
+    pub fn decode_data_header(
+        &self,
+        reader: &mut dyn Read,
+    ) -> Result<PortalMessage, Box<dyn Error>> {
+        match *self {
+            CodecVersion::V2 => TlvCodec::decode_in(reader, &mut [0u8; MAX_DATA_HEADER]),
+            _ => self.decode(reader, MAX_DATA_HEADER as u64),
+        }
+    }
+
+    /// The codec implementing this version
+    pub fn codec(&self) -> Result<&'static dyn WireCodec, Box<dyn Error>> {
+        match *self {
+            CodecVersion::V1 => Ok(&BincodeCodec),
+            CodecVersion::V2 => Ok(&TlvCodec),
+            _ => Err(SerializeError.into()),
+        }
+    }
+}
+
+/// A peer that didn't advertise a version only speaks v1
+impl Default for CodecVersion {
+    fn default() -> Self {
+        CodecVersion::V1
+    }
+}
+
+impl WireCodec for CodecVersion {
+    fn version(&self) -> CodecVersion {
+        *self
+    }
+
+    fn encode(&self, msg: &PortalMessage) -> Result<Vec<u8>, Box<dyn Error>> {
+        self.codec()?.encode(msg)
+    }
+
+    fn decode(&self, reader: &mut dyn Read, limit: u64) -> Result<PortalMessage, Box<dyn Error>> {
+        self.codec()?.decode(reader, limit)
+    }
+}
+
+/// Version 1, bincode's default layout
+#[derive(Debug, Copy, Clone, Default)]
+pub struct BincodeCodec;
+
+impl WireCodec for BincodeCodec {
+    fn version(&self) -> CodecVersion {
+        CodecVersion::V1
+    }
+
+    fn encode(&self, msg: &PortalMessage) -> Result<Vec<u8>, Box<dyn Error>> {
+        Ok(bincode::serialize(msg).or(Err(SerializeError))?)
+    }
+
+    fn decode(&self, reader: &mut dyn Read, limit: u64) -> Result<PortalMessage, Box<dyn Error>> {
+        bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .with_limit(limit)
+            .deserialize_from(reader)
+            .map_err(|e| match *e {
+                bincode::ErrorKind::SizeLimit => MessageTooLarge.into(),
+                _ => e.into(),
+            })
+    }
+}
+
+/// Version 2, length-prefixed frames of tagged values
+#[derive(Debug, Copy, Clone, Default)]
+pub struct TlvCodec;
+
+/// Bytes before the value in a v2 frame
+const FRAME_PREFIX: usize = 4;
+
+/// Deepest nesting of values accepted from the peer
+const MAX_DEPTH: usize = 32;
+
+impl TlvCodec {
+    /// Size of the frame starting with `prefix`, including its length.
+    /// None until enough of the frame has been read to know.
+    pub fn frame_len(prefix: &[u8]) -> Option<u64> {
+        let len: [u8; FRAME_PREFIX] = prefix.get(..FRAME_PREFIX)?.try_into().ok()?;
+        Some(FRAME_PREFIX as u64 + u64::from(u32::from_be_bytes(len)))
+    }
+
+    /// Helper: encode an EncryptedDataHeader into a fixed buffer, field by
+    /// field in the order its struct serializes them
+    fn encode_data_header(
+        header: &EncryptedMessage,
+        out: &mut [u8],
+    ) -> Result<usize, Box<dyn Error>> {
+        let (prefix, value) = out.split_at_mut(FRAME_PREFIX);
+        let mut value = FixedWriter::new(value);
+        value.push(tag::VARIANT);
+        write_str(&mut value, "EncryptedDataHeader");
+        value.push(tag::STRUCT);
+        write_varint(&mut value, 3 + header.commitment.is_some() as u64);
+        for (name, bytes) in [("nonce", &header.nonce[..]), ("tag", &header.tag[..])] {
+            write_str(&mut value, name);
+            value.push(tag::BYTES);
+            write_varint(&mut value, bytes.len() as u64);
+            value.extend_from_slice(bytes);
+        }
+        if let Some(commitment) = header.commitment.as_ref() {
+            write_str(&mut value, "commitment");
+            value.push(tag::SOME);
+            value.push(tag::BYTES);
+            write_varint(&mut value, commitment.len() as u64);
+            value.extend_from_slice(commitment);
+        }
+        write_str(&mut value, "len");
+        value.push(tag::UINT);
+        write_varint(&mut value, header.len as u64);
+
+        let len = value.finish()?;
+        prefix.copy_from_slice(&(len as u32).to_be_bytes());
+        Ok(FRAME_PREFIX + len)
+    }
+
+    /// Helper: receive a frame into `buf`, failing with MessageTooLarge
+    /// if it doesn't fit, & decode it
+    fn decode_in(reader: &mut dyn Read, buf: &mut [u8]) -> Result<PortalMessage, Box<dyn Error>> {
+        let mut prefix = [0u8; FRAME_PREFIX];
+        reader.read_exact(&mut prefix).or(Err(IOError))?;
+        let len = TlvCodec::frame_len(&prefix).ok_or(BadMsg)?;
+        let frame = usize::try_from(len - FRAME_PREFIX as u64)
+            .ok()
+            .and_then(|body| buf.get_mut(..body))
+            .ok_or(MessageTooLarge)?;
+        reader.read_exact(frame).or(Err(IOError))?;
+        TlvCodec::parse(frame)
+    }
+
+    /// Helper: decode the value of a whole frame
+    fn parse(frame: &[u8]) -> Result<PortalMessage, Box<dyn Error>> {
+        let mut de = TlvReader {
+            input: frame,
+            depth: 0,
+        };
+        let msg = PortalMessage::deserialize(&mut de).or(Err(BadMsg))?;
+        match de.input.is_empty() {
+            true => Ok(msg),
+            false => Err(TrailingBytes.into()),
+        }
+    }
+}
+
+impl WireCodec for TlvCodec {
+    fn version(&self) -> CodecVersion {
+        CodecVersion::V2
+    }
+
+    fn encode(&self, msg: &PortalMessage) -> Result<Vec<u8>, Box<dyn Error>> {
+        let mut writer = TlvWriter {
+            out: vec![0; FRAME_PREFIX],
+            ..Default::default()
+        };
+        msg.serialize(&mut writer).or(Err(SerializeError))?;
+
+        let len = u32::try_from(writer.out.len() - FRAME_PREFIX).or(Err(SerializeError))?;
+        writer.out[..FRAME_PREFIX].copy_from_slice(&len.to_be_bytes());
+        Ok(writer.out)
+    }
+
+    fn decode(&self, reader: &mut dyn Read, limit: u64) -> Result<PortalMessage, Box<dyn Error>> {
+        let mut prefix = [0u8; FRAME_PREFIX];
+        reader.read_exact(&mut prefix).or(Err(IOError))?;
+        let len = TlvCodec::frame_len(&prefix).ok_or(BadMsg)?;
+        if len > limit {
+            return Err(MessageTooLarge.into());
+        }
+
+        // Grows as data arrives, rather than trusting the length up front
+        let body = len - FRAME_PREFIX as u64;
+        let mut frame = Vec::new();
+        reader.take(body).read_to_end(&mut frame).or(Err(IOError))?;
+        if frame.len() as u64 != body {
+            return Err(IOError.into());
+        }
+        TlvCodec::parse(&frame)
+    }
+}
+
+/// Types of values in the v2 layout
+mod tag {
+    pub const UNIT: u8 = 0x00;
+    pub const FALSE: u8 = 0x01;
+    pub const TRUE: u8 = 0x02;
+    pub const UINT: u8 = 0x03;
+    pub const INT: u8 = 0x04;
+    pub const FLOAT: u8 = 0x05;
+    pub const BYTES: u8 = 0x06;
+    pub const STRING: u8 = 0x07;
+    pub const SOME: u8 = 0x08;
+    pub const LIST: u8 = 0x09;
+    pub const MAP: u8 = 0x0a;
+    pub const STRUCT: u8 = 0x0b;
+    pub const VARIANT: u8 = 0x0c;
+}
+
+/// Errors within the v2 layout, reported as SerializeError or BadMsg
+#[derive(Debug)]
+struct TlvError(String);
+
+impl fmt::Display for TlvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for TlvError {}
+
+impl ser::Error for TlvError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        TlvError(msg.to_string())
+    }
+}
+
+impl de::Error for TlvError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        TlvError(msg.to_string())
+    }
+}
+
+/// Where values in the v2 layout are written
+trait Output {
+    fn push(&mut self, byte: u8);
+    fn extend_from_slice(&mut self, bytes: &[u8]);
+}
+
+impl Output for Vec<u8> {
+    fn push(&mut self, byte: u8) {
+        Vec::push(self, byte)
+    }
+
+    fn extend_from_slice(&mut self, bytes: &[u8]) {
+        Vec::extend_from_slice(self, bytes)
+    }
+}
+
+/// Writes into a fixed buffer rather than allocating, remembering
+/// whether anything didn't fit
+struct FixedWriter<'a> {
+    out: &'a mut [u8],
+    len: usize,
+    overflow: bool,
+}
+
+impl<'a> FixedWriter<'a> {
+    fn new(out: &'a mut [u8]) -> Self {
+        FixedWriter {
+            out,
+            len: 0,
+            overflow: false,
+        }
+    }
+
+    /// The length written, unless it didn't fit
+    fn finish(self) -> Result<usize, Box<dyn Error>> {
+        match self.overflow {
+            false => Ok(self.len),
+            true => Err(SerializeError.into()),
+        }
+    }
+}
+
+impl Output for FixedWriter<'_> {
+    fn push(&mut self, byte: u8) {
+        self.extend_from_slice(&[byte])
+    }
+
+    fn extend_from_slice(&mut self, bytes: &[u8]) {
+        let end = self.len + bytes.len();
+        match self.out.get_mut(self.len..end) {
+            Some(dest) => dest.copy_from_slice(bytes),
+            None => self.overflow = true,
+        }
+        self.len = end.min(self.out.len());
+    }
+}
+
+/// Helper: append an unsigned LEB128 varint
+fn write_varint<O: Output + ?Sized>(out: &mut O, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Helper: append a name or string's length & UTF-8
+fn write_str<O: Output + ?Sized>(out: &mut O, value: &str) {
+    write_varint(out, value.len() as u64);
+    out.extend_from_slice(value.as_bytes());
+}
+
+/// Serializes values in the v2 layout
+#[derive(Default)]
+struct TlvWriter {
+    out: Vec<u8>,
+
+    // Where the current list item starts, & its value if it's just a u8
+    item: usize,
+    byte: Option<u8>,
+}
+
+impl TlvWriter {
+    /// Helper: start a variant, its value follows
+    fn variant(&mut self, name: &str) {
+        self.out.push(tag::VARIANT);
+        write_str(&mut self.out, name);
+    }
+
+    /// Helper: start a list, map or struct
+    fn compound(&mut self, kind: u8) -> Compound<'_> {
+        Compound {
+            parent: self,
+            kind,
+            count: 0,
+            items: TlvWriter::default(),
+            raw: Some(Vec::new()),
+        }
+    }
+}
+
+/// A list, map or struct whose items are buffered, so that its count is
+/// known before they are written
+struct Compound<'a> {
+    parent: &'a mut TlvWriter,
+    kind: u8,
+    count: u64,
+    items: TlvWriter,
+
+    // Every item so far, while they are all u8
+    raw: Option<Vec<u8>>,
+}
+
+impl<'a> Compound<'a> {
+    fn item<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), TlvError> {
+        self.items.item = self.items.out.len();
+        self.items.byte = None;
+        value.serialize(&mut self.items)?;
+        self.count += 1;
+        match (&mut self.raw, self.items.byte) {
+            (Some(raw), Some(byte)) => raw.push(byte),
+            _ => self.raw = None,
+        }
+        Ok(())
+    }
+
+    fn field<T: ?Sized + Serialize>(&mut self, name: &str, value: &T) -> Result<(), TlvError> {
+        write_str(&mut self.items.out, name);
+        value.serialize(&mut self.items)?;
+        self.count += 1;
+        Ok(())
+    }
+
+    fn finish(self) -> Result<(), TlvError> {
+        let out = &mut self.parent.out;
+        match self.raw {
+            Some(raw) if self.kind == tag::LIST && self.count > 0 => {
+                out.push(tag::BYTES);
+                write_varint(out, raw.len() as u64);
+                out.extend_from_slice(&raw);
+            }
+            _ => {
+                out.push(self.kind);
+                write_varint(out, self.count);
+                out.extend_from_slice(&self.items.out);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> ser::Serializer for &'a mut TlvWriter {
+    type Ok = ();
+    type Error = TlvError;
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn serialize_bool(self, v: bool) -> Result<(), TlvError> {
+        self.out.push(if v { tag::TRUE } else { tag::FALSE });
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), TlvError> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), TlvError> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), TlvError> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), TlvError> {
+        self.out.push(tag::INT);
+        write_varint(&mut self.out, ((v << 1) ^ (v >> 63)) as u64);
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), TlvError> {
+        if self.out.len() == self.item {
+            self.byte = Some(v);
+        }
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), TlvError> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), TlvError> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), TlvError> {
+        self.out.push(tag::UINT);
+        write_varint(&mut self.out, v);
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), TlvError> {
+        self.serialize_f64(v.into())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), TlvError> {
+        self.out.push(tag::FLOAT);
+        self.out.extend_from_slice(&v.to_be_bytes());
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), TlvError> {
+        self.serialize_str(v.encode_utf8(&mut [0u8; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), TlvError> {
+        self.out.push(tag::STRING);
+        write_str(&mut self.out, v);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), TlvError> {
+        self.out.push(tag::BYTES);
+        write_varint(&mut self.out, v.len() as u64);
+        self.out.extend_from_slice(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), TlvError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<(), TlvError> {
+        self.out.push(tag::SOME);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), TlvError> {
+        self.out.push(tag::UNIT);
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), TlvError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<(), TlvError> {
+        self.variant(variant);
+        self.serialize_unit()
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), TlvError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), TlvError> {
+        self.variant(variant);
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Compound<'a>, TlvError> {
+        Ok(self.compound(tag::LIST))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Compound<'a>, TlvError> {
+        Ok(self.compound(tag::LIST))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, TlvError> {
+        Ok(self.compound(tag::LIST))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, TlvError> {
+        self.variant(variant);
+        Ok(self.compound(tag::LIST))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Compound<'a>, TlvError> {
+        Ok(self.compound(tag::MAP))
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Compound<'a>, TlvError> {
+        Ok(self.compound(tag::STRUCT))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, TlvError> {
+        self.variant(variant);
+        Ok(self.compound(tag::STRUCT))
+    }
+}
+
+impl<'a> ser::SerializeSeq for Compound<'a> {
+    type Ok = ();
+    type Error = TlvError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), TlvError> {
+        self.item(value)
+    }
+
+    fn end(self) -> Result<(), TlvError> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeTuple for Compound<'a> {
+    type Ok = ();
+    type Error = TlvError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), TlvError> {
+        self.item(value)
+    }
+
+    fn end(self) -> Result<(), TlvError> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeTupleStruct for Compound<'a> {
+    type Ok = ();
+    type Error = TlvError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), TlvError> {
+        self.item(value)
+    }
+
+    fn end(self) -> Result<(), TlvError> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeTupleVariant for Compound<'a> {
+    type Ok = ();
+    type Error = TlvError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), TlvError> {
+        self.item(value)
+    }
+
+    fn end(self) -> Result<(), TlvError> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeMap for Compound<'a> {
+    type Ok = ();
+    type Error = TlvError;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), TlvError> {
+        self.count += 1;
+        key.serialize(&mut self.items)
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), TlvError> {
+        value.serialize(&mut self.items)
+    }
+
+    fn end(self) -> Result<(), TlvError> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeStruct for Compound<'a> {
+    type Ok = ();
+    type Error = TlvError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<(), TlvError> {
+        self.field(name, value)
+    }
+
+    fn end(self) -> Result<(), TlvError> {
+        self.finish()
+    }
+}
+
+impl<'a> ser::SerializeStructVariant for Compound<'a> {
+    type Ok = ();
+    type Error = TlvError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<(), TlvError> {
+        self.field(name, value)
+    }
+
+    fn end(self) -> Result<(), TlvError> {
+        self.finish()
+    }
+}
+
+/// Deserializes values in the v2 layout from a received frame
+struct TlvReader<'de> {
+    input: &'de [u8],
+    depth: usize,
+}
+
+impl<'de> TlvReader<'de> {
+    fn take(&mut self, len: usize) -> Result<&'de [u8], TlvError> {
+        if len > self.input.len() {
+            return Err(TlvError("truncated value".into()));
+        }
+        let (taken, rest) = self.input.split_at(len);
+        self.input = rest;
+        Ok(taken)
+    }
+
+    fn peek(&self) -> Result<u8, TlvError> {
+        self.input
+            .first()
+            .copied()
+            .ok_or_else(|| TlvError("truncated value".into()))
+    }
+
+    fn tag(&mut self) -> Result<u8, TlvError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn varint(&mut self) -> Result<u64, TlvError> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.tag()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(TlvError("varint too long".into()))
+    }
+
+    /// A length or count, which can't exceed the bytes remaining as
+    /// every item takes at least one
+    fn len(&mut self) -> Result<usize, TlvError> {
+        match self.varint()? {
+            len if len <= self.input.len() as u64 => Ok(len as usize),
+            _ => Err(TlvError("length exceeds the frame".into())),
+        }
+    }
+
+    fn name(&mut self) -> Result<&'de str, TlvError> {
+        let len = self.len()?;
+        std::str::from_utf8(self.take(len)?).map_err(|_| TlvError("invalid UTF-8".into()))
+    }
+
+    /// Descend into a nested value, bounding the recursion
+    fn nested<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<T, TlvError>,
+    ) -> Result<T, TlvError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(TlvError("nested too deeply".into()));
+        }
+        self.depth += 1;
+        let res = f(self);
+        self.depth -= 1;
+        res
+    }
+}
+
+impl<'de> Deserializer<'de> for &mut TlvReader<'de> {
+    type Error = TlvError;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TlvError> {
+        match self.tag()? {
+            tag::UNIT => visitor.visit_unit(),
+            tag::FALSE => visitor.visit_bool(false),
+            tag::TRUE => visitor.visit_bool(true),
+            tag::UINT => visitor.visit_u64(self.varint()?),
+            tag::INT => {
+                let zigzag = self.varint()?;
+                visitor.visit_i64((zigzag >> 1) as i64 ^ -((zigzag & 1) as i64))
+            }
+            tag::FLOAT => {
+                let bytes = self.take(8)?.try_into().unwrap_or_default();
+                visitor.visit_f64(f64::from_be_bytes(bytes))
+            }
+            tag::BYTES => {
+                let len = self.len()?;
+                visitor.visit_borrowed_bytes(self.take(len)?)
+            }
+            tag::STRING => visitor.visit_borrowed_str(self.name()?),
+            tag::SOME => self.nested(|de| visitor.visit_some(de)),
+            tag::LIST => {
+                let remaining = self.len()?;
+                self.nested(|de| visitor.visit_seq(Items { de, remaining }))
+            }
+            tag::MAP => {
+                let remaining = self.len()?;
+                self.nested(|de| {
+                    visitor.visit_map(Entries {
+                        de,
+                        remaining,
+                        named: false,
+                    })
+                })
+            }
+            tag::STRUCT => {
+                let remaining = self.len()?;
+                self.nested(|de| {
+                    visitor.visit_map(Entries {
+                        de,
+                        remaining,
+                        named: true,
+                    })
+                })
+            }
+            tag::VARIANT => self.nested(|de| visitor.visit_enum(Variant { de })),
+            other => Err(TlvError(format!("unknown type {:#04x}", other))),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TlvError> {
+        match self.peek()? {
+            tag::UNIT => {
+                self.tag()?;
+                visitor.visit_none()
+            }
+            tag::SOME => {
+                self.tag()?;
+                self.nested(|de| visitor.visit_some(de))
+            }
+            _ => Err(TlvError("expected an option".into())),
+        }
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, TlvError> {
+        match self.peek()? {
+            tag::BYTES => {
+                self.tag()?;
+                let len = self.len()?;
+                let bytes = self.take(len)?;
+                let items: de::value::SeqDeserializer<_, TlvError> =
+                    de::value::SeqDeserializer::new(bytes.iter().copied());
+                visitor.visit_seq(items)
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, TlvError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, TlvError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, TlvError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, TlvError> {
+        match self.peek()? {
+            tag::VARIANT => self.deserialize_any(visitor),
+            _ => Err(TlvError("expected a variant".into())),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct map struct identifier ignored_any
+    }
+}
+
+/// The items of a list
+struct Items<'a, 'de> {
+    de: &'a mut TlvReader<'de>,
+    remaining: usize,
+}
+
+impl<'de, 'a> de::SeqAccess<'de> for Items<'a, 'de> {
+    type Error = TlvError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, TlvError> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+/// The entries of a map, or a struct's fields keyed by name
+struct Entries<'a, 'de> {
+    de: &'a mut TlvReader<'de>,
+    remaining: usize,
+    named: bool,
+}
+
+impl<'de, 'a> de::MapAccess<'de> for Entries<'a, 'de> {
+    type Error = TlvError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, TlvError> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        match self.named {
+            true => {
+                let name = de::value::BorrowedStrDeserializer::<TlvError>::new(self.de.name()?);
+                seed.deserialize(name).map(Some)
+            }
+            false => seed.deserialize(&mut *self.de).map(Some),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, TlvError> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+/// An enum variant, its name followed by its value
+struct Variant<'a, 'de> {
+    de: &'a mut TlvReader<'de>,
+}
+
+impl<'de, 'a> de::EnumAccess<'de> for Variant<'a, 'de> {
+    type Error = TlvError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), TlvError> {
+        let name = de::value::BorrowedStrDeserializer::<TlvError>::new(self.de.name()?);
+        Ok((seed.deserialize(name)?, self))
+    }
+}
+
+impl<'de, 'a> de::VariantAccess<'de> for Variant<'a, 'de> {
+    type Error = TlvError;
+
+    fn unit_variant(self) -> Result<(), TlvError> {
+        match self.de.tag()? {
+            tag::UNIT => Ok(()),
+            _ => Err(TlvError("expected a unit variant".into())),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, TlvError> {
+        seed.deserialize(self.de)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, TlvError> {
+        self.de.deserialize_seq(visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, TlvError> {
+        match self.de.peek()? {
+            tag::STRUCT => self.de.deserialize_any(visitor),
+            _ => Err(TlvError("expected a struct variant".into())),
+        }
+    }
+}
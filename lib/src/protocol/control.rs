@@ -0,0 +1,135 @@
+use super::ConnectMessage;
+use crate::errors::PortalError::{self, RelayTooNew, RelayTooOld};
+use serde::{Deserialize, Serialize};
+
+/// Version of the protocol between clients & relays spoken by this build.
+/// Raised whenever either side can no longer understand the other.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Oldest relay protocol version this build can pair through
+pub const MIN_RELAY_VERSION: u32 = 1;
+
+/// Error codes the relay may respond with when
+/// it cannot fulfill a RelayControl request
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Copy, Clone)]
+pub enum RelayControlError {
+    /// No pending registration exists for the ID
+    UnknownId,
+    /// The ID has already been paired with a peer. Also sent to a
+    /// Receiver connecting to a session another Receiver claimed.
+    AlreadyPaired,
+    /// The request came from a different address than the registration
+    NotPermitted,
+    /// The relay doesn't support this request
+    Unsupported,
+    /// Data sent before the Receiver disconnected never reached it,
+    /// so the transfer can't be resumed
+    DataLost,
+}
+
+/// Maximum filler the relay sends in response to a Probe
+pub const MAX_PROBE_SIZE: u32 = 1024 * 1024;
+
+/// Messages exchanged with the relay itself, rather than the peer.
+/// Each request is answered with either `Ack` or `Error`, except
+/// a `Probe` which is answered with `ProbeData` & `Version` which is
+/// answered with `Banner`.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub enum RelayControl {
+    /// Register with the relay, equivalent to PortalMessage::Connect
+    Register(ConnectMessage),
+
+    /// Cancel a pending (unpaired) registration for the ID
+    Cancel(String),
+
+    /// Keep a pending registration for the ID around longer
+    ExtendTtl { id: String, seconds: u64 },
+
+    /// The request was successful
+    Ack,
+
+    /// The request failed
+    Error(RelayControlError),
+
+    /// Ask the relay for this many bytes of filler, to estimate
+    /// the bandwidth available before a large transfer
+    Probe(u32),
+
+    /// The relay's response to a Probe
+    ProbeData(Vec<u8>),
+
+    /// Sent by a Receiver reconnecting mid-transfer to a relay buffering
+    /// data while it was away, along with the total bytes it had read from
+    /// the relay & the session's [`ResumeToken`](super::ResumeToken).
+    /// After an Ack the connection continues where the previous one left
+    /// off, with whatever the Receiver missed replayed first.
+    Resume {
+        id: String,
+        received: u64,
+        token: [u8; 32],
+    },
+
+    /// Sent instead of pairing by a relay at capacity, listing other
+    /// relays (host:port) to connect to instead, in order of preference
+    Redirect(Vec<String>),
+
+    /// Sent between relays in a cluster, asking whether a Sender with
+    /// the ID is waiting there. Answered with `Ack` if so.
+    Lookup(String),
+
+    /// Ask the relay which protocol versions it supports, without
+    /// pairing. Relays predating it close the connection instead.
+    Version,
+
+    /// The relay's response to `Version`
+    Banner(RelayBanner),
+}
+
+/// Sent by the relay ahead of anything else in response to a pairing
+/// request, describing the relay. Relays predating it send none.
+///
+/// ```
+/// use portal_lib::{RelayBanner, PROTOCOL_VERSION};
+///
+/// let banner = RelayBanner::new(Some(1 << 30));
+/// assert_eq!(banner.version, PROTOCOL_VERSION);
+/// assert!(banner.check().is_ok());
+///
+/// // A relay that no longer pairs clients this old
+/// let newer = RelayBanner { min_version: PROTOCOL_VERSION + 1, ..banner };
+/// assert!(newer.check().is_err());
+/// ```
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Copy, Clone)]
+pub struct RelayBanner {
+    /// The protocol version the relay speaks
+    pub version: u32,
+
+    /// The oldest client protocol version the relay pairs
+    pub min_version: u32,
+
+    /// Sessions are ended after this many bytes, if limited
+    pub max_transfer: Option<u64>,
+}
+
+impl RelayBanner {
+    /// Describe a relay of this build
+    pub fn new(max_transfer: Option<u64>) -> Self {
+        RelayBanner {
+            version: PROTOCOL_VERSION,
+            min_version: PROTOCOL_VERSION,
+            max_transfer,
+        }
+    }
+
+    /// Check that this build can pair through the relay, failing with
+    /// `RelayTooOld` or `RelayTooNew` otherwise
+    pub fn check(&self) -> Result<(), PortalError> {
+        if self.version < MIN_RELAY_VERSION {
+            return Err(RelayTooOld(self.version));
+        }
+        if PROTOCOL_VERSION < self.min_version {
+            return Err(RelayTooNew(self.min_version));
+        }
+        Ok(())
+    }
+}
@@ -1,11 +1,18 @@
+use super::CodecVersion;
 use crate::errors::PortalError::*;
+use serde::de::{self, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
 use serde::{Deserialize, Serialize};
 use std::convert::TryInto;
 use std::error::Error;
+use std::fmt;
 
 // Nonce generation
 use rand::Rng;
 
+// Key commitment
+use hkdf::Hkdf;
+use sha2::Sha256;
+
 // Encryption
 #[cfg(not(feature = "ring-backend"))]
 use chacha20poly1305::{aead::AeadInPlace, aead::NewAead, ChaCha20Poly1305, Key, Nonce, Tag};
@@ -16,6 +23,10 @@ use ring::aead::{Aad, LessSafeKey, Nonce, Tag, UnboundKey, CHACHA20_POLY1305};
 /// We store 128bits but only need 96bit nonces
 const NONCE_SIZE: usize = 12;
 const TAG_SIZE: usize = 16;
+const COMMITMENT_SIZE: usize = 32;
+
+/// HKDF info used to derive the key commitment
+const COMMITMENT_INFO: &[u8] = b"portal-key-commitment";
 
 /// An abstraction around a nonce sequence. Safely
 /// ensures there is no nonce re-use during a session
@@ -24,12 +35,18 @@ const TAG_SIZE: usize = 16;
 pub struct NonceSequence([u8; TAG_SIZE]);
 
 /// All encrypted messages must have associated state data (nonce, tag)
-#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Default)]
+#[derive(Serialize, PartialEq, Eq, Debug, Clone, Default)]
 pub struct EncryptedMessage {
     /// Provides storage for chacha20poly1305::Nonce
     pub nonce: [u8; NONCE_SIZE],
     /// Provides storage for chacha20poly1305::Tag
     pub tag: [u8; TAG_SIZE],
+    /// Commits the message to the key it was encrypted with. Poly1305
+    /// alone allows a ciphertext to be crafted that decrypts under
+    /// multiple keys. Only carried by codecs that commit, see
+    /// [`CodecVersion::commits`], v1 messages keep their layout.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commitment: Option<[u8; COMMITMENT_SIZE]>,
     /// Length of follow-on data. Data is not owned
     /// directly to prevent copies
     pub len: usize,
@@ -37,12 +54,13 @@ pub struct EncryptedMessage {
 
 #[cfg(not(feature = "ring-backend"))]
 impl EncryptedMessage {
-    /// Create an encrypted message out of an arbitrary serializable
-    /// type
-    pub fn encrypt(
+    /// Encrypt like [`EncryptedMessage::encrypt`], also authenticating
+    /// `aad`. The same must be provided to decrypt the message.
+    pub fn encrypt_with(
         key: &[u8],
         nseq: &mut NonceSequence,
         data: &mut [u8],
+        aad: &[u8],
     ) -> Result<Self, Box<dyn Error>> {
         // Init state to send
         let mut state = Self {
@@ -62,7 +80,7 @@ impl EncryptedMessage {
 
         // Encrypt the data in-place
         let tag = cipher
-            .encrypt_in_place_detached(nonce, b"", data)
+            .encrypt_in_place_detached(nonce, aad, data)
             .or(Err(EncryptError))?;
 
         // Save the tag in our current state
@@ -70,8 +88,17 @@ impl EncryptedMessage {
         Ok(state)
     }
 
-    /// Decrypt the provided data in-place
-    pub fn decrypt(&mut self, key: &[u8], data: &mut [u8]) -> Result<usize, Box<dyn Error>> {
+    /// Decrypt like [`EncryptedMessage::decrypt`], a message that was
+    /// encrypted with `aad`
+    pub fn decrypt_with(
+        &mut self,
+        key: &[u8],
+        data: &mut [u8],
+        aad: &[u8],
+    ) -> Result<usize, Box<dyn Error>> {
+        // Verify the message was encrypted with this key, if committed
+        self.verify_commitment(key)?;
+
         // Obtain the cipher from the key
         let cha_key = Key::from_slice(key);
         let cipher = ChaCha20Poly1305::new(cha_key);
@@ -82,7 +109,7 @@ impl EncryptedMessage {
 
         // Decrypt the data in place
         cipher
-            .decrypt_in_place_detached(nonce, b"", data, tag)
+            .decrypt_in_place_detached(nonce, aad, data, tag)
             .or(Err(DecryptError))?;
 
         Ok(data.len())
@@ -91,12 +118,13 @@ impl EncryptedMessage {
 
 #[cfg(feature = "ring-backend")]
 impl EncryptedMessage {
-    /// Create an encrypted message out of an arbitrary serializable
-    /// type
-    pub fn encrypt(
+    /// Encrypt like [`EncryptedMessage::encrypt`], also authenticating
+    /// `aad`. The same must be provided to decrypt the message.
+    pub fn encrypt_with(
         key: &[u8],
         nseq: &mut NonceSequence,
         data: &mut [u8],
+        aad: &[u8],
     ) -> Result<Self, Box<dyn Error>> {
         // Init state to send
         let mut state = Self::default();
@@ -106,7 +134,7 @@ impl EncryptedMessage {
             LessSafeKey::new(UnboundKey::new(&CHACHA20_POLY1305, key).or(Err(CryptoError))?);
 
         // Obtain the next nonce
-        state.nonce = nseq.next()?;
+        state.nonce = nseq.next_unique()?;
         let ring_nonce = Nonce::assume_unique_for_key(state.nonce);
 
         // Set the length
@@ -114,7 +142,7 @@ impl EncryptedMessage {
 
         // Encrypt the data in-place.
         let tag = ring_key_chacha20
-            .seal_in_place_separate_tag(ring_nonce, Aad::empty(), data)
+            .seal_in_place_separate_tag(ring_nonce, Aad::from(aad), data)
             .or(Err(EncryptError))?;
 
         // Save the tag in our current state
@@ -122,25 +150,174 @@ impl EncryptedMessage {
         Ok(state)
     }
 
-    /// Decrypt the provided data in-place
-    pub fn decrypt(&mut self, key: &[u8], data: &mut [u8]) -> Result<usize, Box<dyn Error>> {
+    /// Decrypt like [`EncryptedMessage::decrypt`], a message that was
+    /// encrypted with `aad`
+    pub fn decrypt_with(
+        &mut self,
+        key: &[u8],
+        data: &mut [u8],
+        aad: &[u8],
+    ) -> Result<usize, Box<dyn Error>> {
+        // Verify the message was encrypted with this key, if committed
+        self.verify_commitment(key)?;
+
         // Init the key
         let ring_key_chacha20 =
             LessSafeKey::new(UnboundKey::new(&CHACHA20_POLY1305, key).or(Err(CryptoError))?);
 
         // The nonce & tag are self contained
-        let ring_tag: Tag = self.tag.try_into().or(Err(DecryptError))?;
+        let ring_tag = Tag::from(self.tag);
         let ring_nonce = Nonce::assume_unique_for_key(self.nonce);
 
         // Decrypt the data in place
         ring_key_chacha20
-            .open_in_place_separate_tag(ring_nonce, Aad::empty(), ring_tag, data, 0..)
+            .open_in_place_separate_tag(ring_nonce, Aad::from(aad), ring_tag, data, 0..)
             .or(Err(DecryptError))?;
 
         Ok(data.len())
     }
 }
 
+impl EncryptedMessage {
+    /// Create an encrypted message out of an arbitrary serializable
+    /// type
+    pub fn encrypt(
+        key: &[u8],
+        nseq: &mut NonceSequence,
+        data: &mut [u8],
+    ) -> Result<Self, Box<dyn Error>> {
+        Self::encrypt_with(key, nseq, data, b"")
+    }
+
+    /// Decrypt the provided data in-place
+    pub fn decrypt(&mut self, key: &[u8], data: &mut [u8]) -> Result<usize, Box<dyn Error>> {
+        self.decrypt_with(key, data, b"")
+    }
+
+    /// Commit the message to the key it was encrypted with, if the codec
+    /// it will be sent with carries commitments
+    pub fn committed(mut self, key: &[u8], codec: CodecVersion) -> Result<Self, Box<dyn Error>> {
+        if codec.commits() {
+            self.commitment = Some(commitment(key, &self.nonce)?);
+        }
+        Ok(self)
+    }
+
+    /// Verify that the message commits to the provided key, if it
+    /// commits to one
+    fn verify_commitment(&self, key: &[u8]) -> Result<(), Box<dyn Error>> {
+        let theirs = match self.commitment.as_ref() {
+            Some(theirs) => theirs,
+            None => return Ok(()),
+        };
+        let expected = commitment(key, &self.nonce)?;
+
+        // Constant time comparison
+        let diff = expected
+            .iter()
+            .zip(theirs.iter())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b));
+
+        match diff {
+            0 => Ok(()),
+            _ => Err(DecryptError.into()),
+        }
+    }
+}
+
+/// Helper: derive the key commitment for a message with this nonce
+fn commitment(
+    key: &[u8],
+    nonce: &[u8; NONCE_SIZE],
+) -> Result<[u8; COMMITMENT_SIZE], Box<dyn Error>> {
+    let h = Hkdf::<Sha256>::new(Some(nonce), key);
+    let mut out = [0u8; COMMITMENT_SIZE];
+    h.expand(COMMITMENT_INFO, &mut out).or(Err(CryptoError))?;
+    Ok(out)
+}
+
+/// The fields of an EncryptedMessage, by name
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum Field {
+    Nonce,
+    Tag,
+    Commitment,
+    Len,
+    #[serde(other)]
+    Other,
+}
+
+/// Provide a serde visitor for either layout of an EncryptedMessage
+struct EncryptedMessageVisitor;
+
+impl<'de> Visitor<'de> for EncryptedMessageVisitor {
+    type Value = EncryptedMessage;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "an EncryptedMessage")
+    }
+
+    /// Fields in order, as in v1, which has no room for a commitment
+    fn visit_seq<A>(self, mut seq: A) -> Result<EncryptedMessage, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let nonce = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let tag = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        let len = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+        Ok(EncryptedMessage {
+            nonce,
+            tag,
+            commitment: None,
+            len,
+        })
+    }
+
+    /// Fields by name, as in v2, where a commitment may be
+    fn visit_map<A>(self, mut map: A) -> Result<EncryptedMessage, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let (mut nonce, mut tag, mut commitment, mut len) = (None, None, None, None);
+        while let Some(field) = map.next_key()? {
+            match field {
+                Field::Nonce => nonce = Some(map.next_value()?),
+                Field::Tag => tag = Some(map.next_value()?),
+                Field::Commitment => commitment = map.next_value()?,
+                Field::Len => len = Some(map.next_value()?),
+                Field::Other => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(EncryptedMessage {
+            nonce: nonce.ok_or_else(|| de::Error::missing_field("nonce"))?,
+            tag: tag.ok_or_else(|| de::Error::missing_field("tag"))?,
+            commitment,
+            len: len.ok_or_else(|| de::Error::missing_field("len"))?,
+        })
+    }
+}
+
+/// Not derived, as v1 reads the fields in order & must skip the
+/// commitment, which would otherwise be expected in its place
+impl<'de> Deserialize<'de> for EncryptedMessage {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &["nonce", "tag", "commitment", "len"];
+        deserializer.deserialize_struct("EncryptedMessage", FIELDS, EncryptedMessageVisitor)
+    }
+}
+
 impl Default for NonceSequence {
     fn default() -> Self {
         Self::new()
@@ -154,6 +331,12 @@ impl NonceSequence {
         Self(rng.gen::<[u8; 16]>())
     }
 
+    /// Start the sequence from a chosen state, to test its boundaries
+    #[cfg(test)]
+    pub(crate) fn from_state(state: [u8; 16]) -> Self {
+        Self(state)
+    }
+
     /// Advance the sequence by incrementing the internal state
     /// and returning the current state. Similar nonces in TLS 1.3
     pub fn next_unique(&mut self) -> Result<[u8; NONCE_SIZE], Box<dyn Error>> {
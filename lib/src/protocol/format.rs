@@ -0,0 +1,77 @@
+use crate::errors::PortalError::*;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::error::Error;
+
+/// The encoding used for objects exchanged over the encrypted channel,
+/// such as TransferInfo & FileEntry. Each peer advertises its preferred
+/// format in its ConnectOptions, bincode is used unless both agree.
+///
+/// The JSON & CBOR representations follow serde's defaults for each type,
+/// and are intended for third-party implementations that would rather not
+/// re-implement bincode. They require the `json` & `cbor` features.
+///
+/// ```
+/// use portal_lib::{FileEntry, WireFormat};
+///
+/// let entry = FileEntry {
+///     filesize: 10,
+///     filename: "file.txt".into(),
+///     ..Default::default()
+/// };
+///
+/// let encoded = WireFormat::Bincode.serialize(&entry).unwrap();
+/// let decoded: FileEntry = WireFormat::Bincode.deserialize(&encoded).unwrap();
+/// assert_eq!(decoded, entry);
+/// ```
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Copy, Clone, Default)]
+pub enum WireFormat {
+    #[default]
+    Bincode,
+    Json,
+    Cbor,
+}
+
+impl WireFormat {
+    /// Determine the format to use given both peers' preferences
+    pub fn negotiate(ours: WireFormat, theirs: WireFormat) -> WireFormat {
+        match ours == theirs {
+            true => ours,
+            false => WireFormat::Bincode,
+        }
+    }
+
+    /// Returns true if this build of the library can encode the format
+    pub fn is_supported(&self) -> bool {
+        match self {
+            WireFormat::Bincode => true,
+            WireFormat::Json => cfg!(feature = "json"),
+            WireFormat::Cbor => cfg!(feature = "cbor"),
+        }
+    }
+
+    /// Serialize an object with this format
+    pub fn serialize<S: Serialize>(&self, msg: &S) -> Result<Vec<u8>, Box<dyn Error>> {
+        match self {
+            WireFormat::Bincode => Ok(bincode::serialize(msg).or(Err(SerializeError))?),
+            #[cfg(feature = "json")]
+            WireFormat::Json => Ok(serde_json::to_vec(msg).or(Err(SerializeError))?),
+            #[cfg(feature = "cbor")]
+            WireFormat::Cbor => Ok(serde_cbor::to_vec(msg).or(Err(SerializeError))?),
+            #[allow(unreachable_patterns)]
+            _ => Err(SerializeError.into()),
+        }
+    }
+
+    /// Deserialize an object with this format
+    pub fn deserialize<D: DeserializeOwned>(&self, data: &[u8]) -> Result<D, Box<dyn Error>> {
+        match self {
+            WireFormat::Bincode => Ok(bincode::deserialize(data).or(Err(BadMsg))?),
+            #[cfg(feature = "json")]
+            WireFormat::Json => Ok(serde_json::from_slice(data).or(Err(BadMsg))?),
+            #[cfg(feature = "cbor")]
+            WireFormat::Cbor => Ok(serde_cbor::from_slice(data).or(Err(BadMsg))?),
+            #[allow(unreachable_patterns)]
+            _ => Err(BadMsg.into()),
+        }
+    }
+}
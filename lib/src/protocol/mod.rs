@@ -1,10 +1,13 @@
 use crate::errors::PortalError::*;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::error::Error;
+use std::fs::File;
 use std::io::{Read, Write};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 // Crypto
 use hkdf::Hkdf;
+use rand::Rng;
 use sha2::Sha256;
 use spake2::{Ed25519Group, Spake2};
 
@@ -20,17 +23,89 @@ pub use encrypted::*;
 mod transferinfo;
 pub use transferinfo::*;
 
+// Per-receiver credentials for group transfers
+mod shard;
+pub use shard::*;
+
+// Messages exchanged with the relay
+mod control;
+pub use control::*;
+
+// Encodings for encrypted objects
+mod format;
+pub use format::*;
+
+// Retrying transient failures during the handshake
+mod retry;
+pub use retry::*;
+
+// Resuming a Receiver's dropped connection to the relay
+mod resume;
+pub use resume::*;
+
+// Hardened parsing of relay-provided messages
+mod strict;
+pub use strict::*;
+
+// Access tokens for private relays
+mod auth;
+pub use auth::*;
+
+// Traffic priority & DSCP marking
+mod priority;
+pub use priority::*;
+
+// Versioned layouts of messages on the wire
+mod codec;
+pub use codec::*;
+
+// Optional features advertised alongside the ConnectMessage
+mod capabilities;
+pub use capabilities::*;
+
 #[cfg(test)]
 mod tests;
 
+#[cfg(test)]
+mod props;
+
 /// Lower-level abstraction around the protocol. Use this
 /// directly if you'd like more control than what the
 /// higher-level Portal interface provides
 pub struct Protocol;
 
+/// Maximum size of an encrypted object, such as TransferInfo
+/// including any previews, that will be accepted from the peer
+pub const MAX_OBJECT_SIZE: usize = 8 * 1024 * 1024;
+
+/// Associated data of a FileAborted record, so that the relay can't
+/// turn it into an empty chunk or an empty chunk into one
+pub const FILE_ABORTED_AAD: &[u8] = b"portal-file-aborted";
+
+/// HKDF label of the session key of a namespaced deployment
+const NAMESPACE_KEY_LABEL: &str = "portal-session-key";
+
+/// Qualify an HKDF label with an application namespace, see
+/// [`crate::Portal::set_namespace`]. Without one the label is unchanged, so
+/// vanilla peers derive what they always have. The namespace is length
+/// prefixed, so that no two namespaces qualify labels the same way.
+///
+/// ```
+/// use portal_lib::namespaced;
+///
+/// assert_eq!(namespaced(None, "portal-fingerprint"), "portal-fingerprint");
+/// assert_eq!(namespaced(Some("acme"), "id"), "4:acme/id");
+/// ```
+pub fn namespaced(namespace: Option<&str>, label: &str) -> String {
+    match namespace {
+        Some(namespace) => format!("{}:{}/{}", namespace.len(), namespace, label),
+        None => label.to_string(),
+    }
+}
+
 /// An enum to describe the direction of each file transfer
 /// participant (i.e Sender/Receiver)
-#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug, Copy, Clone)]
 pub enum Direction {
     Sender,
     Receiver,
@@ -44,6 +119,98 @@ pub struct ConnectMessage {
     pub direction: Direction,
 }
 
+/// What a peer would like for the session, sent in an Options message
+/// right after its ConnectMessage. The ConnectMessage keeps the layout
+/// every relay & peer can read, those that predate these options ignore
+/// the message, and peers that don't send one get the defaults.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Copy, Clone, Default)]
+pub struct ConnectOptions {
+    /// The preferred encoding for encrypted objects
+    pub format: WireFormat,
+    /// How the relay should treat the session's traffic
+    pub priority: Priority,
+    /// The newest wire codec supported
+    pub codec: CodecVersion,
+}
+
+/// Information about the peer learned while connecting
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default)]
+pub struct PeerInfo {
+    /// The peer's preferred encoding for encrypted objects
+    pub format: WireFormat,
+    /// The newest wire codec the peer supports
+    pub codec: CodecVersion,
+    /// The peer's capabilities, if both of us sent them & the relay
+    /// passed them on
+    pub capabilities: Option<Capabilities>,
+    /// The session salt, if paired by a relay
+    pub salt: Option<SessionSalt>,
+    /// The relay's banner, if it sent one
+    pub relay: Option<RelayBanner>,
+}
+
+/// The negotiable options a peer advertised while connecting
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Copy, Clone, Default)]
+pub struct Offer {
+    pub format: WireFormat,
+    pub codec: CodecVersion,
+    pub capabilities: Option<Capabilities>,
+}
+
+/// The options each peer advertised before pairing, as seen by one of
+/// them. Both peers bind these into key confirmation, so a relay or
+/// attacker that strips or alters either peer's options to force a weaker
+/// choice makes confirmation fail. Any option negotiated in the future
+/// belongs here.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Copy, Clone)]
+pub struct Offers {
+    pub sender: Offer,
+    pub receiver: Offer,
+}
+
+impl Offers {
+    /// Our own offer & the one the relay says the peer made
+    pub fn new(direction: Direction, ours: Offer, theirs: Offer) -> Self {
+        let (sender, receiver) = match direction {
+            Direction::Sender => (ours, theirs),
+            Direction::Receiver => (theirs, ours),
+        };
+        Offers { sender, receiver }
+    }
+
+    /// What is bound into key confirmation. Each peer binds the offer it
+    /// sent & the one it was told of as they are, so capabilities withheld
+    /// from either peer make confirmation fail rather than quietly turning
+    /// them off.
+    pub fn binding(&self) -> Result<Vec<u8>, Box<dyn Error>> {
+        Ok(bincode::serialize(self).or(Err(SerializeError))?)
+    }
+}
+
+/// A random value contributed by the relay when pairing two peers.
+/// Both peers mix it into key confirmation, so confirmation messages
+/// from one session can't be replayed into another that reuses the
+/// same ID & password.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Copy, Clone)]
+pub struct SessionSalt(pub [u8; 32]);
+
+impl SessionSalt {
+    /// Generate a new random salt
+    pub fn generate() -> Self {
+        let mut rng = rand::thread_rng();
+        Self(rng.gen::<[u8; 32]>())
+    }
+}
+
+/// Sent by the relay to each peer that sent its options once they have
+/// been paired, in place of forwarding the peer's ConnectMessage directly
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct PairedMessage {
+    pub peer: ConnectMessage,
+    pub options: ConnectOptions,
+    pub salt: SessionSalt,
+}
+
 /// The wrapped message type for every exchanged message
 #[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
 pub enum PortalMessage {
@@ -60,19 +227,65 @@ pub enum PortalMessage {
     /// All other messages are encrypted. This
     /// can be either metadata or a file chunk
     EncryptedDataHeader(EncryptedMessage),
+
+    /// Requests & responses exchanged with the relay
+    RelayControl(RelayControl),
+
+    /// The relay has paired you with the peer
+    Paired(PairedMessage),
+
+    /// Each peer's random contribution to a pre-shared key session
+    Nonce(SessionSalt),
+
+    /// Connect, presenting an access token to a private relay
+    AuthConnect(AuthenticatedConnect),
+
+    /// Sent by the relay in place of the next message once the session
+    /// has reached its limit of this many bytes, before closing it
+    TransferLimit(u64),
+
+    /// A timestamp from the peer's clock, in microseconds, to echo back
+    Ping(u64),
+
+    /// The timestamp of one of our Pings, echoed back by the peer
+    Pong(u64),
+
+    /// Describes the relay, sent before any other response to a request
+    RelayHello(RelayBanner),
+
+    /// Sent by the Sender in place of the next chunk when the rest of
+    /// the file can't be read, such as when it shrank. Authenticated like
+    /// a chunk without any data, see [`FILE_ABORTED_AAD`].
+    FileAborted(EncryptedMessage),
+
+    /// Sent right after a Connect, or by the relay before Paired, to
+    /// advertise optional features, see [`Capabilities`]
+    Capabilities(Capabilities),
+
+    /// Sent by the relay in place of the next message once the session
+    /// has lasted its limit of this many seconds, before closing it
+    SessionExpired(u64),
+
+    /// Sent right after a Connect to describe the session the peer
+    /// would like, see [`ConnectOptions`]
+    Options(ConnectOptions),
 }
 
 impl PortalMessage {
-    /// Send an arbitrary PortalMessage
+    /// Serialize a PortalMessage as it is sent on the wire, with the v1
+    /// codec. See [`WireCodec`] for other versions.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn Error>> {
+        BincodeCodec.encode(self)
+    }
+
+    /// Send an arbitrary PortalMessage with the v1 codec
     pub fn send<W: Write>(&mut self, writer: &mut W) -> Result<usize, Box<dyn Error>> {
-        let data = bincode::serialize(&self).or(Err(SerializeError))?;
-        writer.write_all(&data).or(Err(IOError))?;
-        Ok(data.len())
+        BincodeCodec.send(self, writer)
     }
 
-    /// Receive an arbitrary PortalMessage
+    /// Receive an arbitrary PortalMessage with the v1 codec
     pub fn recv<R: Read>(reader: &mut R) -> Result<Self, Box<dyn Error>> {
-        Ok(bincode::deserialize_from::<&mut R, PortalMessage>(reader)?)
+        BincodeCodec.decode(reader, u64::MAX)
     }
 
     /// Deserialize from existing data
@@ -81,35 +294,315 @@ impl PortalMessage {
     }
 }
 
+/// The response to a Connect message, with the relay's banner & the
+/// peer's capabilities if they were sent before it
+type Response = (PortalMessage, Option<RelayBanner>, Option<Capabilities>);
+
 impl Protocol {
-    /// Connect to a peer & receive the initial exchange data
+    /// Connect to a peer & receive the initial exchange data, along
+    /// with what could be learned about the peer. Reads & writes failing
+    /// with a transient error are retried, see [`Retrying`].
     pub fn connect<P: Read + Write>(
         peer: &mut P,
-        id: &str,
-        direction: Direction,
+        request: ConnectMessage,
+        options: ConnectOptions,
         msg: PortalKeyExchange,
-    ) -> Result<PortalKeyExchange, Box<dyn Error>> {
-        // Initial connect message
-        let c = ConnectMessage {
-            id: id.to_owned(),
-            direction,
+    ) -> Result<(PortalKeyExchange, PeerInfo), Box<dyn Error>> {
+        Self::connect_with(peer, request, options, None, None, msg)
+    }
+
+    /// Connect like `connect`, presenting an access token made with
+    /// `secret` if the relay requires one & advertising `capabilities`
+    pub fn connect_with<P: Read + Write>(
+        peer: &mut P,
+        request: ConnectMessage,
+        options: ConnectOptions,
+        secret: Option<&[u8]>,
+        capabilities: Option<Capabilities>,
+        msg: PortalKeyExchange,
+    ) -> Result<(PortalKeyExchange, PeerInfo), Box<dyn Error>> {
+        let peer = &mut Retrying::new(peer);
+        // Send the connect message.
+        Self::send_request(peer, request, options, secret, capabilities)?;
+
+        // Recv the peer's equivalent peering/connect message. A relay
+        // will wrap it along with the peer's options & the session salt,
+        // a directly connected peer sends its messages as is.
+        let offered = capabilities.is_some();
+        let (response, relay, theirs) = Self::recv_response(peer, u64::MAX, offered)?;
+        let direct = matches!(response, PortalMessage::Connect(_));
+        let mut info = match response {
+            PortalMessage::Paired(paired) => PeerInfo {
+                format: paired.options.format,
+                codec: paired.options.codec,
+                capabilities: theirs,
+                salt: Some(paired.salt),
+                relay,
+            },
+            PortalMessage::Connect(_) => PeerInfo {
+                relay,
+                ..Default::default()
+            },
+            PortalMessage::RelayControl(RelayControl::Redirect(relays)) => {
+                return Err(Redirected(relays).into())
+            }
+            PortalMessage::RelayControl(RelayControl::Error(RelayControlError::AlreadyPaired)) => {
+                return Err(SessionClaimed.into())
+            }
+            _ => PeerInfo::default(),
         };
 
+        // Send the exchange data
+        PortalMessage::KeyExchange(msg).send(peer)?;
+
+        // Recv the peer's data
+        let exchange = Self::recv_exchange(peer, u64::MAX, direct, offered, &mut info);
+        match exchange.or(Err(IOError))? {
+            PortalMessage::KeyExchange(data) => Ok((data, info)),
+            _ => Err(Box::new(BadMsg)),
+        }
+    }
+
+    /// Connect to a peer like `connect`, but without trusting the relay.
+    /// Messages are size limited, must arrive in the expected order, and
+    /// the peer must be the counterpart of this request.
+    pub fn connect_strict<P: Read + Write>(
+        peer: &mut P,
+        request: ConnectMessage,
+        options: ConnectOptions,
+        msg: PortalKeyExchange,
+    ) -> Result<(PortalKeyExchange, PeerInfo), Box<dyn Error>> {
+        Self::connect_strict_with(peer, request, options, None, None, msg)
+    }
+
+    /// Connect like `connect_strict`, presenting an access token made
+    /// with `secret` if the relay requires one & advertising `capabilities`
+    pub fn connect_strict_with<P: Read + Write>(
+        peer: &mut P,
+        request: ConnectMessage,
+        options: ConnectOptions,
+        secret: Option<&[u8]>,
+        capabilities: Option<Capabilities>,
+        msg: PortalKeyExchange,
+    ) -> Result<(PortalKeyExchange, PeerInfo), Box<dyn Error>> {
+        let peer = &mut Retrying::new(peer);
+        let mut state = HandshakeState::AwaitingPeer;
+
         // Send the connect message.
-        PortalMessage::Connect(c).send(peer)?;
+        Self::send_request(peer, request.clone(), options, secret, capabilities)?;
 
         // Recv the peer's equivalent peering/connect message
-        // TODO: currently nothing is done with this, however
-        // this may be useful for future P2P protocols
-        let _info = PortalMessage::recv(peer)?;
+        let offered = capabilities.is_some();
+        let limit = MAX_HANDSHAKE_MESSAGE_SIZE;
+        let (response, relay, theirs) = Self::recv_response(peer, limit, offered)?;
+        match response {
+            PortalMessage::RelayControl(RelayControl::Redirect(relays)) => {
+                return Err(Redirected(relays).into())
+            }
+            PortalMessage::RelayControl(RelayControl::Error(RelayControlError::AlreadyPaired)) => {
+                return Err(SessionClaimed.into())
+            }
+            _ => {}
+        }
+        state.advance(&response)?;
+        let direct = matches!(response, PortalMessage::Connect(_));
+        let (counterpart, theirs_options, salt) = match response {
+            PortalMessage::Paired(paired) => (paired.peer, paired.options, Some(paired.salt)),
+            PortalMessage::Connect(c) => (c, ConnectOptions::default(), None),
+            _ => return Err(UnexpectedMessage.into()),
+        };
+
+        // The peer must share our ID and be going the other direction
+        if counterpart.id != request.id || counterpart.direction == request.direction {
+            return Err(PeerMismatch.into());
+        }
+        let mut info = PeerInfo {
+            format: theirs_options.format,
+            codec: theirs_options.codec,
+            capabilities: theirs,
+            salt,
+            relay,
+        };
 
         // Send the exchange data
         PortalMessage::KeyExchange(msg).send(peer)?;
 
         // Recv the peer's data
-        match PortalMessage::recv(peer).or(Err(IOError))? {
+        let response = Self::recv_exchange(peer, limit, direct, offered, &mut info)?;
+        state.advance(&response)?;
+        match response {
+            PortalMessage::KeyExchange(data) => Ok((data, info)),
+            _ => Err(UnexpectedMessage.into()),
+        }
+    }
+
+    /// Helper: receive the response to a Connect message, after the
+    /// relay's banner if it sends one. The banner is checked first, so
+    /// that version skew is reported as such.
+    /// Relays pass on the peer's capabilities just before it, if we
+    /// `offered` our own.
+    fn recv_response<P: Read>(
+        peer: &mut P,
+        limit: u64,
+        offered: bool,
+    ) -> Result<Response, Box<dyn Error>> {
+        let mut banner = None;
+        let mut msg = PortalMessage::recv_limited(peer, limit)?;
+        if let PortalMessage::RelayHello(hello) = msg {
+            hello.check()?;
+            banner = Some(hello);
+            msg = PortalMessage::recv_limited(peer, limit)?;
+        }
+        match msg {
+            PortalMessage::Capabilities(theirs) if offered => {
+                let msg = PortalMessage::recv_limited(peer, limit)?;
+                Ok((msg, banner, Some(theirs)))
+            }
+            msg => Ok((msg, banner, None)),
+        }
+    }
+
+    /// Helper: receive the peer's exchange data. A `direct`ly connected
+    /// peer sends its options & capabilities between its ConnectMessage &
+    /// exchange data, the capabilities only if we `offered` ours.
+    fn recv_exchange<P: Read>(
+        peer: &mut P,
+        limit: u64,
+        direct: bool,
+        offered: bool,
+        info: &mut PeerInfo,
+    ) -> Result<PortalMessage, Box<dyn Error>> {
+        let mut msg = PortalMessage::recv_limited(peer, limit)?;
+        if let (PortalMessage::Options(theirs), true) = (&msg, direct) {
+            info.format = theirs.format;
+            info.codec = theirs.codec;
+            msg = PortalMessage::recv_limited(peer, limit)?;
+        }
+        match msg {
+            PortalMessage::Capabilities(theirs) if offered && direct => {
+                info.capabilities = Some(theirs);
+                PortalMessage::recv_limited(peer, limit)
+            }
+            msg => Ok(msg),
+        }
+    }
+
+    /// Helper: send the first message to the relay, authenticated when a
+    /// secret is provided. Options & capabilities follow in the same write,
+    /// so that relays read them all at once. Relays that don't know them
+    /// only parse the first message, which keeps the layout they expect.
+    fn send_request<W: Write>(
+        peer: &mut W,
+        request: ConnectMessage,
+        options: ConnectOptions,
+        secret: Option<&[u8]>,
+        capabilities: Option<Capabilities>,
+    ) -> Result<(), Box<dyn Error>> {
+        let message = match secret {
+            Some(secret) => PortalMessage::AuthConnect(AuthenticatedConnect::new(request, secret)?),
+            None => PortalMessage::Connect(request),
+        };
+        let mut data = message.to_bytes()?;
+        data.extend(PortalMessage::Options(options).to_bytes()?);
+        if let Some(capabilities) = capabilities {
+            data.extend(PortalMessage::Capabilities(capabilities).to_bytes()?);
+        }
+        peer.write_all(&data).or(Err(IOError))?;
+        Ok(())
+    }
+
+    /// Exchange key data with a directly connected peer. Without a relay
+    /// to pair us there are no Connect messages, each side sends its
+    /// exchange data straight away.
+    pub fn exchange_direct<P: Read + Write>(
+        peer: &mut P,
+        msg: PortalKeyExchange,
+    ) -> Result<PortalKeyExchange, Box<dyn Error>> {
+        let peer = &mut Retrying::new(peer);
+        PortalMessage::KeyExchange(msg).send(peer)?;
+
+        match PortalMessage::recv_limited(peer, MAX_HANDSHAKE_MESSAGE_SIZE)? {
             PortalMessage::KeyExchange(data) => Ok(data),
-            _ => Err(Box::new(BadMsg)),
+            _ => Err(UnexpectedMessage.into()),
+        }
+    }
+
+    /// Exchange random nonces with the peer, for sessions keyed by a
+    /// pre-shared key instead of SPAKE2
+    pub fn exchange_nonces<P: Read + Write>(
+        peer: &mut P,
+        ours: SessionSalt,
+    ) -> Result<SessionSalt, Box<dyn Error>> {
+        let peer = &mut Retrying::new(peer);
+        PortalMessage::Nonce(ours).send(peer)?;
+
+        match PortalMessage::recv_limited(peer, MAX_HANDSHAKE_MESSAGE_SIZE)? {
+            PortalMessage::Nonce(theirs) => Ok(theirs),
+            _ => Err(UnexpectedMessage.into()),
+        }
+    }
+
+    /// Derive a session key from a pre-shared key & both peers' nonces,
+    /// so that each session uses a distinct key
+    pub fn derive_psk_key(
+        psk: &[u8; 32],
+        id: &str,
+        sender: &SessionSalt,
+        receiver: &SessionSalt,
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        let salt = [sender.0, receiver.0].concat();
+        let h = Hkdf::<Sha256>::new(Some(&salt), psk);
+        let mut key = vec![0u8; 32];
+        h.expand(format!("{}-psk", id).as_bytes(), &mut key)
+            .or(Err(BadMsg))?;
+        Ok(key)
+    }
+
+    /// Bind a derived session key to an application namespace, so that
+    /// every value later expanded from it, such as the confirmation
+    /// messages & fingerprint, differs between namespaces. The key is
+    /// returned as is without one.
+    pub fn derive_namespaced_key(
+        key: Vec<u8>,
+        namespace: Option<&str>,
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        if namespace.is_none() {
+            return Ok(key);
+        }
+        let h = Hkdf::<Sha256>::new(None, &key);
+        let mut namespaced_key = vec![0u8; key.len()];
+        h.expand(
+            namespaced(namespace, NAMESPACE_KEY_LABEL).as_bytes(),
+            &mut namespaced_key,
+        )
+        .or(Err(BadMsg))?;
+        Ok(namespaced_key)
+    }
+
+    /// Derive a value from the session key for both peers to compare
+    /// out-of-band. It reveals nothing about the key itself.
+    pub fn derive_fingerprint(key: &[u8]) -> Result<u64, Box<dyn Error>> {
+        let h = Hkdf::<Sha256>::new(None, key);
+        let mut value = [0u8; 8];
+        h.expand(b"portal-fingerprint", &mut value)
+            .or(Err(BadMsg))?;
+        Ok(u64::from_be_bytes(value))
+    }
+
+    /// Send a control request to the relay and return its response. An
+    /// `Error` response from the relay is returned as `Ok`, the caller
+    /// decides how to handle it.
+    pub fn relay_control<P: Read + Write>(
+        relay: &mut P,
+        request: RelayControl,
+    ) -> Result<RelayControl, Box<dyn Error>> {
+        // Send the request
+        PortalMessage::RelayControl(request).send(relay)?;
+
+        // Recv the relay's response
+        match PortalMessage::recv(relay).or(Err(IOError))? {
+            PortalMessage::RelayControl(response) => Ok(response),
+            _ => Err(BadMsg.into()),
         }
     }
 
@@ -125,15 +618,40 @@ impl Protocol {
 
     /// Use the derived session key to verify that our peer has derived
     /// the same key as us. After this the peer will be fully confirmed.
+    /// The session salt, if provided by the relay, is bound into the
+    /// confirmation messages.
     pub fn confirm_peer<P: Read + Write>(
         peer: &mut P,
         id: &str,
+        salt: Option<&SessionSalt>,
         direction: Direction,
         key: &[u8],
     ) -> Result<(), Box<dyn Error>> {
+        Self::confirm_peer_with(peer, id, salt, None, direction, key)
+    }
+
+    /// Confirm the peer like `confirm_peer`, also binding both peers'
+    /// [`Offers`] into the confirmation messages. Confirmation then fails
+    /// unless both peers saw the same offers.
+    pub fn confirm_peer_with<P: Read + Write>(
+        peer: &mut P,
+        id: &str,
+        salt: Option<&SessionSalt>,
+        offers: Option<&Offers>,
+        direction: Direction,
+        key: &[u8],
+    ) -> Result<(), Box<dyn Error>> {
+        let peer = &mut Retrying::new(peer);
         // Arbitrary info that both sides can derive
-        let sender_info = format!("{}-{}", id, "senderinfo");
-        let receiver_info = format!("{}-{}", id, "receiverinfo");
+        let mut context = match salt {
+            Some(salt) => format!("{}-{}", id, hex::encode(salt.0)),
+            None => id.to_string(),
+        };
+        if let Some(offers) = offers {
+            context = format!("{}-{}", context, hex::encode(offers.binding()?));
+        }
+        let sender_info = format!("{}-{}", context, "senderinfo");
+        let receiver_info = format!("{}-{}", context, "receiverinfo");
 
         // Perform key confirmation step via HKDF
         let h = Hkdf::<Sha256>::new(None, key);
@@ -171,20 +689,54 @@ impl Protocol {
         Ok(())
     }
 
+    /// Measure the round-trip time to the peer. Each side sends a Ping
+    /// timestamped by its own clock & echoes the peer's back in a Pong,
+    /// so the clocks never need to agree.
+    pub fn measure_rtt<P: Read + Write>(peer: &mut P) -> Result<Duration, Box<dyn Error>> {
+        let peer = &mut Retrying::new(peer);
+        let started = Instant::now();
+        let ours = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_micros() as u64;
+        PortalMessage::Ping(ours).send(peer)?;
+
+        // Echo the peer's Ping, which is always sent before its Pong
+        match PortalMessage::recv_limited(peer, MAX_HANDSHAKE_MESSAGE_SIZE)? {
+            PortalMessage::Ping(theirs) => PortalMessage::Pong(theirs).send(peer)?,
+            _ => return Err(UnexpectedMessage.into()),
+        };
+        match PortalMessage::recv_limited(peer, MAX_HANDSHAKE_MESSAGE_SIZE)? {
+            PortalMessage::Pong(echo) if echo == ours => Ok(started.elapsed()),
+            _ => Err(UnexpectedMessage.into()),
+        }
+    }
+
     /// Read an encrypted owned & deserialize-able object from the peer.
-    pub fn read_encrypted_from<R, D>(reader: &mut R, key: &[u8]) -> Result<D, Box<dyn Error>>
+    pub fn read_encrypted_from<R, D>(
+        reader: &mut R,
+        key: &[u8],
+        codec: CodecVersion,
+        format: WireFormat,
+    ) -> Result<D, Box<dyn Error>>
     where
         R: Read,
         D: DeserializeOwned,
     {
-        // Create temporary storage for the object
-        let mut storage = [0u8; 2048];
+        // Receive the message header, return error if not EncryptedDataHeader
+        let msg = Protocol::recv_encrypted_header(reader, key, codec)?;
+
+        // Create storage for the object, within reason
+        if msg.len > MAX_OBJECT_SIZE {
+            return Err(BufferTooSmall.into());
+        }
+        let mut storage = vec![0u8; msg.len];
 
         // Receive the message into the storage region
-        Protocol::read_encrypted_zero_copy(reader, key, &mut storage)?;
+        let len = Protocol::read_encrypted_body(reader, key, msg, &mut storage, b"")?;
 
         // Deserialize the result
-        Ok(bincode::deserialize(&storage).or(Err(BadMsg))?)
+        format.deserialize(&storage[..len])
     }
 
     /// Read an encrypted message from the peer, writing the resulting
@@ -194,17 +746,66 @@ impl Protocol {
     pub fn read_encrypted_zero_copy<R>(
         reader: &mut R,
         key: &[u8],
+        codec: CodecVersion,
         storage: &mut [u8],
     ) -> Result<usize, Box<dyn Error>>
     where
         R: Read,
     {
         // Receive the message header, return error if not EncryptedDataHeader
-        let mut msg = match PortalMessage::recv(reader).or(Err(IOError))? {
-            PortalMessage::EncryptedDataHeader(inner) => inner,
-            _ => return Err(BadMsg.into()),
-        };
+        let msg = Protocol::recv_encrypted_header(reader, key, codec)?;
+        Protocol::read_encrypted_body(reader, key, msg, storage, b"")
+    }
+
+    /// Helper: receive an EncryptedDataHeader from the peer. A FileAborted
+    /// record in its place is authenticated, then reported as an error.
+    /// Either must commit to its key if the codec carries commitments.
+    pub(crate) fn recv_encrypted_header<R: Read>(
+        reader: &mut R,
+        key: &[u8],
+        codec: CodecVersion,
+    ) -> Result<EncryptedMessage, Box<dyn Error>> {
+        match codec.decode_data_header(reader).or(Err(IOError))? {
+            PortalMessage::EncryptedDataHeader(inner) | PortalMessage::FileAborted(inner)
+                if codec.commits() && inner.commitment.is_none() =>
+            {
+                Err(BadMsg.into())
+            }
+            PortalMessage::EncryptedDataHeader(inner) => Ok(inner),
+            PortalMessage::TransferLimit(limit) => Err(TransferLimit(limit).into()),
+            PortalMessage::SessionExpired(limit) => Err(SessionExpired(limit).into()),
+            PortalMessage::FileAborted(mut inner) if inner.len == 0 => {
+                inner.decrypt_with(key, &mut [], FILE_ABORTED_AAD)?;
+                Err(FileChangedDuringTransfer.into())
+            }
+            _ => Err(BadMsg.into()),
+        }
+    }
+
+    /// Helper: tell the peer the rest of the current file won't be sent
+    pub(crate) fn send_file_aborted<W: Write>(
+        writer: &mut W,
+        key: &[u8],
+        nseq: &mut NonceSequence,
+        codec: CodecVersion,
+    ) -> Result<(), Box<dyn Error>> {
+        let header = EncryptedMessage::encrypt_with(key, nseq, &mut [], FILE_ABORTED_AAD)?
+            .committed(key, codec)?;
+        let bytes = codec.encode(&PortalMessage::FileAborted(header))?;
+        writer.write_all(&bytes).or(Err(IOError))?;
+        Ok(())
+    }
 
+    /// Helper: receive the data following an EncryptedDataHeader
+    /// into the storage region & decrypt it in-place, with the
+    /// associated data it was encrypted with
+    pub(crate) fn read_encrypted_body<R: Read>(
+        reader: &mut R,
+        key: &[u8],
+        mut msg: EncryptedMessage,
+        storage: &mut [u8],
+        aad: &[u8],
+    ) -> Result<usize, Box<dyn Error>> {
         // Check that the storage region has enough room
         if storage.len() < msg.len {
             return Err(BufferTooSmall.into());
@@ -224,7 +825,30 @@ impl Protocol {
         }
 
         // Decrypt the region in-place
-        msg.decrypt(key, &mut storage[..pos])
+        msg.decrypt_with(key, &mut storage[..pos], aad)
+    }
+
+    /// Read an encrypted chunk from the peer and write the decrypted data
+    /// into the file at the provided offset. The storage region is used
+    /// as scratch space and must be large enough to hold the chunk. This
+    /// allows chunks that arrive out of order to be placed correctly.
+    pub fn read_encrypted_at<R>(
+        reader: &mut R,
+        key: &[u8],
+        codec: CodecVersion,
+        file: &File,
+        offset: u64,
+        storage: &mut [u8],
+    ) -> Result<usize, Box<dyn Error>>
+    where
+        R: Read,
+    {
+        // Receive & decrypt the chunk into the scratch region
+        let len = Protocol::read_encrypted_zero_copy(reader, key, codec, storage)?;
+
+        // Place the chunk at its offset
+        crate::file::write_all_at(file, &storage[..len], offset).or(Err(IOError))?;
+        Ok(len)
     }
 
     /// Encrypt & send an EncryptedDataHeader + the entire object to the peer
@@ -232,6 +856,8 @@ impl Protocol {
         writer: &mut W,
         key: &[u8],
         nseq: &mut NonceSequence,
+        codec: CodecVersion,
+        format: WireFormat,
         msg: &S,
     ) -> Result<usize, Box<dyn Error>>
     where
@@ -239,13 +865,13 @@ impl Protocol {
         S: Serialize,
     {
         // Serialize the object
-        let mut data = bincode::serialize(msg)?;
+        let mut data = format.serialize(msg)?;
 
         // Encrypt the data
-        let encmsg = EncryptedMessage::encrypt(key, nseq, &mut data)?;
+        let encmsg = EncryptedMessage::encrypt(key, nseq, &mut data)?.committed(key, codec)?;
 
         // Wrap and send the header
-        PortalMessage::EncryptedDataHeader(encmsg).send(writer)?;
+        Protocol::send_data_header(writer, codec, &encmsg)?;
 
         // Send the data
         writer.write_all(&data).or(Err(IOError))?;
@@ -258,15 +884,28 @@ impl Protocol {
         writer: &mut W,
         key: &[u8],
         nseq: &mut NonceSequence,
+        codec: CodecVersion,
         data: &mut [u8],
     ) -> Result<usize, Box<dyn Error>>
     where
         W: Write,
     {
         // Encrypt the entire region in-place
-        let header = EncryptedMessage::encrypt(key, nseq, data)?;
+        let header = EncryptedMessage::encrypt(key, nseq, data)?.committed(key, codec)?;
 
         // Send the EncryptedMessage header
-        PortalMessage::EncryptedDataHeader(header).send(writer)
+        Protocol::send_data_header(writer, codec, &header)
+    }
+
+    /// Helper: send an EncryptedDataHeader, encoded on the stack
+    fn send_data_header<W: Write>(
+        writer: &mut W,
+        codec: CodecVersion,
+        header: &EncryptedMessage,
+    ) -> Result<usize, Box<dyn Error>> {
+        let mut encoded = [0u8; MAX_DATA_HEADER];
+        let len = codec.encode_data_header(header, &mut encoded)?;
+        writer.write_all(&encoded[..len]).or(Err(IOError))?;
+        Ok(len)
     }
 }
@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::net::TcpStream;
+
+#[cfg(unix)]
+use std::os::unix::io::AsRawFd;
+
+/// DSCP of the scavenger class (CS1), for traffic that should yield to
+/// everything else on a congested link
+pub const DSCP_SCAVENGER: u8 = 8;
+
+/// How the network should treat a transfer's traffic. Each peer advertises
+/// its priority in its ConnectOptions, and the relay marks both of a
+/// session's connections as bulk if either peer asks for it.
+///
+/// ```
+/// use portal_lib::Priority;
+///
+/// assert_eq!(Priority::Bulk.dscp(), 8);
+/// assert_eq!(Priority::combine(Priority::Normal, Priority::Bulk), Priority::Bulk);
+/// ```
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Copy, Clone, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    #[default]
+    Normal,
+
+    /// A background transfer, marked scavenger class so that managed
+    /// networks can give way to other traffic
+    Bulk,
+}
+
+impl Priority {
+    /// The priority of a session given both peers' priorities
+    pub fn combine(ours: Priority, theirs: Priority) -> Priority {
+        match ours == Priority::Bulk || theirs == Priority::Bulk {
+            true => Priority::Bulk,
+            false => Priority::Normal,
+        }
+    }
+
+    /// The DSCP packets are marked with
+    pub fn dscp(&self) -> u8 {
+        match self {
+            Priority::Normal => 0,
+            Priority::Bulk => DSCP_SCAVENGER,
+        }
+    }
+
+    /// Mark the socket's outgoing packets with this priority's DSCP. Fails
+    /// with `Unsupported` on platforms that don't expose the IP header.
+    pub fn mark(&self, socket: &TcpStream) -> io::Result<()> {
+        #[cfg(unix)]
+        return set_dscp(socket, socket.local_addr()?.is_ipv6(), self.dscp());
+
+        #[cfg(not(unix))]
+        return Err(io::ErrorKind::Unsupported.into());
+    }
+}
+
+/// Set the DSCP of a socket's outgoing packets, in IP_TOS for IPv4 or
+/// IPV6_TCLASS for IPv6. The ECN bits are left clear.
+#[cfg(unix)]
+pub fn set_dscp<S: AsRawFd>(socket: &S, ipv6: bool, dscp: u8) -> io::Result<()> {
+    let (level, name) = match ipv6 {
+        true => (libc::IPPROTO_IPV6, libc::IPV6_TCLASS),
+        false => (libc::IPPROTO_IP, libc::IP_TOS),
+    };
+    let value = libc::c_int::from(dscp & 0x3f) << 2;
+    let res = unsafe {
+        libc::setsockopt(
+            socket.as_raw_fd(),
+            level,
+            name,
+            &value as *const libc::c_int as *const libc::c_void,
+            std::mem::size_of::<libc::c_int>() as libc::socklen_t,
+        )
+    };
+    match res {
+        0 => Ok(()),
+        _ => Err(io::Error::last_os_error()),
+    }
+}
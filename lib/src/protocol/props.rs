@@ -0,0 +1,473 @@
+//! Property-based tests of the wire format: the nonce sequence, framing
+//! of every PortalMessage, and the encoding of TransferInfo.
+use super::Direction;
+use crate::dedup::{self, ChunkRef, DedupChunk, DEDUP_MAX_CHUNK, DEDUP_MIN_CHUNK};
+use crate::errors::PortalError;
+use crate::protocol::{
+    AuthenticatedConnect, Capabilities, CodecVersion, ConnectMessage, ConnectOptions,
+    EncryptedMessage, FileEntry, FileKind, NonceSequence, PairedMessage, PortalConfirmation,
+    PortalKeyExchange, PortalMessage, Priority, RelayBanner, RelayControl, RelayControlError,
+    SessionSalt, TlvCodec, TransferInfo, WireCodec, WireFormat, MAX_DATA_HEADER,
+};
+use proptest::collection::vec;
+use proptest::prelude::*;
+use std::convert::{TryFrom, TryInto};
+
+/// Largest value of the 96 bit counter in a nonce
+const MAX_COUNTER: u128 = (1 << 96) - 1;
+
+/// Nonces taken from each sequence
+const STEPS: usize = 300;
+
+/// Counters on a boundary where a carry ripples into the next byte,
+/// including the wrap of the whole counter
+fn boundary() -> impl Strategy<Value = u128> {
+    (1..=12u32, 0..STEPS as u128).prop_map(|(bytes, before)| {
+        let edge = (1u128 << (8 * bytes)) - 1;
+        edge.saturating_sub(before) & MAX_COUNTER
+    })
+}
+
+fn counter() -> impl Strategy<Value = u128> {
+    prop_oneof![any::<u128>().prop_map(|c| c & MAX_COUNTER), boundary()]
+}
+
+fn direction() -> impl Strategy<Value = Direction> {
+    prop_oneof![Just(Direction::Sender), Just(Direction::Receiver)]
+}
+
+fn format() -> impl Strategy<Value = WireFormat> {
+    prop_oneof![
+        Just(WireFormat::Bincode),
+        Just(WireFormat::Json),
+        Just(WireFormat::Cbor)
+    ]
+}
+
+fn priority() -> impl Strategy<Value = Priority> {
+    prop_oneof![Just(Priority::Normal), Just(Priority::Bulk)]
+}
+
+fn connect() -> impl Strategy<Value = ConnectMessage> {
+    (any::<String>(), direction()).prop_map(|(id, direction)| ConnectMessage { id, direction })
+}
+
+fn options() -> impl Strategy<Value = ConnectOptions> {
+    (format(), priority(), any::<u8>()).prop_map(|(format, priority, codec)| ConnectOptions {
+        format,
+        priority,
+        codec: CodecVersion(codec),
+    })
+}
+
+fn salt() -> impl Strategy<Value = SessionSalt> {
+    any::<[u8; 32]>().prop_map(SessionSalt)
+}
+
+fn control_error() -> impl Strategy<Value = RelayControlError> {
+    prop_oneof![
+        Just(RelayControlError::UnknownId),
+        Just(RelayControlError::AlreadyPaired),
+        Just(RelayControlError::NotPermitted),
+        Just(RelayControlError::Unsupported),
+        Just(RelayControlError::DataLost),
+    ]
+}
+
+fn control() -> impl Strategy<Value = RelayControl> {
+    prop_oneof![
+        connect().prop_map(RelayControl::Register),
+        any::<String>().prop_map(RelayControl::Cancel),
+        (any::<String>(), any::<u64>())
+            .prop_map(|(id, seconds)| RelayControl::ExtendTtl { id, seconds }),
+        Just(RelayControl::Ack),
+        control_error().prop_map(RelayControl::Error),
+        any::<u32>().prop_map(RelayControl::Probe),
+        vec(any::<u8>(), 0..64).prop_map(RelayControl::ProbeData),
+        (any::<String>(), any::<u64>(), any::<[u8; 32]>()).prop_map(|(id, received, token)| {
+            RelayControl::Resume {
+                id,
+                received,
+                token,
+            }
+        }),
+        vec(any::<String>(), 0..4).prop_map(RelayControl::Redirect),
+        any::<String>().prop_map(RelayControl::Lookup),
+        Just(RelayControl::Version),
+        banner().prop_map(RelayControl::Banner),
+    ]
+}
+
+fn banner() -> impl Strategy<Value = RelayBanner> {
+    (any::<u32>(), any::<u32>(), any::<Option<u64>>()).prop_map(
+        |(version, min_version, max_transfer)| RelayBanner {
+            version,
+            min_version,
+            max_transfer,
+        },
+    )
+}
+
+/// Every variant of PortalMessage
+fn data_header() -> impl Strategy<Value = EncryptedMessage> {
+    (any::<[u8; 12]>(), any::<[u8; 16]>(), any::<usize>()).prop_map(|(nonce, tag, len)| {
+        EncryptedMessage {
+            nonce,
+            tag,
+            commitment: None,
+            len,
+        }
+    })
+}
+
+fn message() -> impl Strategy<Value = PortalMessage> {
+    prop_oneof![
+        connect().prop_map(PortalMessage::Connect),
+        vec(any::<u8>(), 33)
+            .prop_map(|v| { PortalMessage::KeyExchange(PortalKeyExchange::try_from(v).unwrap()) }),
+        vec(any::<u8>(), 42)
+            .prop_map(|v| { PortalMessage::Confirm(PortalConfirmation(v.try_into().unwrap())) }),
+        data_header().prop_map(PortalMessage::EncryptedDataHeader),
+        control().prop_map(PortalMessage::RelayControl),
+        (connect(), options(), salt()).prop_map(|(peer, options, salt)| {
+            PortalMessage::Paired(PairedMessage {
+                peer,
+                options,
+                salt,
+            })
+        }),
+        salt().prop_map(PortalMessage::Nonce),
+        (connect(), any::<u64>(), any::<[u8; 32]>()).prop_map(|(connect, timestamp, mac)| {
+            PortalMessage::AuthConnect(AuthenticatedConnect {
+                connect,
+                timestamp,
+                mac,
+            })
+        }),
+        any::<u64>().prop_map(PortalMessage::TransferLimit),
+        any::<u64>().prop_map(PortalMessage::Ping),
+        any::<u64>().prop_map(PortalMessage::Pong),
+        banner().prop_map(PortalMessage::RelayHello),
+        data_header().prop_map(PortalMessage::FileAborted),
+        any::<u64>().prop_map(|c| PortalMessage::Capabilities(Capabilities(c))),
+        any::<u64>().prop_map(PortalMessage::SessionExpired),
+        options().prop_map(PortalMessage::Options),
+    ]
+}
+
+fn file_entry() -> impl Strategy<Value = FileEntry> {
+    let kind = prop_oneof![Just(FileKind::Regular), Just(FileKind::Stream)];
+    (
+        (
+            any::<u64>(),
+            any::<String>(),
+            kind,
+            vec(any::<String>(), 0..3),
+            proptest::option::of(vec(any::<u8>(), 0..64)),
+            any::<u32>(),
+            any::<bool>(),
+        ),
+        (
+            proptest::option::of(any::<u32>()),
+            proptest::option::of(any::<u64>()),
+            proptest::option::of(any::<[u8; 32]>()),
+            proptest::option::of(any::<String>()),
+        ),
+    )
+        .prop_map(
+            |(
+                (filesize, filename, kind, copies, preview, max_chunk, dedup),
+                (mode, modified, digest, mime),
+            )| FileEntry {
+                filesize,
+                filename,
+                kind,
+                copies,
+                preview,
+                max_chunk,
+                dedup,
+                mode,
+                modified,
+                digest,
+                mime,
+                ..Default::default()
+            },
+        )
+}
+
+fn transfer_info() -> impl Strategy<Value = TransferInfo> {
+    (
+        vec(file_entry(), 0..4),
+        proptest::option::of(any::<String>()),
+    )
+        .prop_map(|(all, note)| {
+            let mut info = TransferInfo::empty();
+            info.all = all;
+            info.note = note;
+            info
+        })
+}
+
+/// Every codec this build implements
+const CODECS: [CodecVersion; 2] = [CodecVersion::V1, CodecVersion::V2];
+
+/// Helper: a counter as the nonce it produces
+fn nonce(counter: u128) -> [u8; 12] {
+    counter.to_be_bytes()[4..].try_into().unwrap()
+}
+
+proptest! {
+    #[test]
+    fn nonces_count_up_from_any_state(state in any::<[u8; 16]>()) {
+        let mut seq = NonceSequence::from_state(state);
+        let first = seq.next_unique().unwrap();
+        prop_assert_eq!(&first[..], &state[..12]);
+
+        // The low bits of the state are dropped after the first nonce
+        let counter = u128::from_be_bytes(state) >> 32;
+        prop_assert_eq!(seq.next_unique().unwrap(), nonce((counter + 1) & MAX_COUNTER));
+    }
+
+    #[test]
+    fn nonces_increase_across_carries(start in counter()) {
+        let mut seq = NonceSequence::from_state((start << 32).to_be_bytes());
+        let mut last = seq.next_unique().unwrap();
+        prop_assert_eq!(last, nonce(start));
+        for step in 1..STEPS as u128 {
+            let next = seq.next_unique().unwrap();
+            prop_assert_eq!(next, nonce((start + step) & MAX_COUNTER));
+
+            // Only the wrap of the whole counter goes backwards
+            match start + step == MAX_COUNTER + 1 {
+                true => prop_assert_eq!((last, next), ([0xff; 12], [0; 12])),
+                false => prop_assert!(next > last),
+            }
+            last = next;
+        }
+    }
+
+    #[test]
+    fn nonces_unique_across_wrap(before in 1..STEPS as u128) {
+        let start = MAX_COUNTER + 1 - before;
+        let mut seq = NonceSequence::from_state((start << 32).to_be_bytes());
+        let mut seen = std::collections::HashSet::new();
+        for _ in 0..STEPS {
+            prop_assert!(seen.insert(seq.next_unique().unwrap()));
+        }
+    }
+
+    #[test]
+    fn messages_roundtrip(msg in message()) {
+        let bytes = msg.to_bytes().unwrap();
+        prop_assert_eq!(&PortalMessage::parse(&bytes).unwrap(), &msg);
+        prop_assert_eq!(&PortalMessage::parse_strict(&bytes, u64::MAX).unwrap(), &msg);
+    }
+
+    #[test]
+    fn messages_roundtrip_every_codec(msg in message()) {
+        for codec in CODECS {
+            let bytes = codec.encode(&msg).unwrap();
+            let decoded = codec.decode(&mut &bytes[..], bytes.len() as u64).unwrap();
+            prop_assert_eq!(&decoded, &msg);
+
+            // Nothing is read past the limit
+            let err = codec.decode(&mut &bytes[..], bytes.len() as u64 - 1).unwrap_err();
+            prop_assert_eq!(err.downcast_ref(), Some(&PortalError::MessageTooLarge));
+        }
+    }
+
+    #[test]
+    fn data_headers_encoded_in_place(header in data_header()) {
+        for codec in CODECS {
+            let mut out = [0u8; MAX_DATA_HEADER];
+            let len = codec.encode_data_header(&header, &mut out).unwrap();
+            let msg = PortalMessage::EncryptedDataHeader(header.clone());
+            prop_assert_eq!(&out[..len], &codec.encode(&msg).unwrap()[..]);
+            prop_assert_eq!(&codec.decode_data_header(&mut &out[..len]).unwrap(), &msg);
+        }
+    }
+
+    #[test]
+    fn committed_data_headers_encoded_in_place(
+        header in data_header(),
+        commitment in any::<[u8; 32]>(),
+    ) {
+        let header = EncryptedMessage { commitment: Some(commitment), ..header };
+        let codec = CodecVersion::V2;
+        let mut out = [0u8; MAX_DATA_HEADER];
+        let len = codec.encode_data_header(&header, &mut out).unwrap();
+        let msg = PortalMessage::EncryptedDataHeader(header);
+        prop_assert_eq!(&out[..len], &codec.encode(&msg).unwrap()[..]);
+        prop_assert_eq!(&codec.decode_data_header(&mut &out[..len]).unwrap(), &msg);
+    }
+
+    #[test]
+    fn data_headers_bound_other_messages(msg in message()) {
+        for codec in CODECS {
+            let bytes = codec.encode(&msg).unwrap();
+            match codec.decode_data_header(&mut &bytes[..]) {
+                Ok(decoded) => prop_assert_eq!(&decoded, &msg),
+                Err(e) => {
+                    prop_assert!(bytes.len() > MAX_DATA_HEADER);
+                    prop_assert_eq!(e.downcast_ref(), Some(&PortalError::MessageTooLarge));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn codecs_framed_back_to_back(first in message(), second in message()) {
+        for codec in CODECS {
+            let mut stream = codec.encode(&first).unwrap();
+            stream.extend(codec.encode(&second).unwrap());
+            let mut reader = &stream[..];
+            prop_assert_eq!(&codec.decode(&mut reader, u64::MAX).unwrap(), &first);
+            prop_assert_eq!(&codec.decode(&mut reader, u64::MAX).unwrap(), &second);
+            prop_assert!(reader.is_empty());
+        }
+    }
+
+    #[test]
+    fn codecs_reject_truncated(msg in message(), cut in any::<prop::sample::Index>()) {
+        for codec in CODECS {
+            let bytes = codec.encode(&msg).unwrap();
+            let len = cut.index(bytes.len());
+            prop_assert!(codec.decode(&mut &bytes[..len], u64::MAX).is_err());
+        }
+    }
+
+    #[test]
+    fn tlv_frames_are_self_delimiting(msg in message()) {
+        let bytes = TlvCodec.encode(&msg).unwrap();
+        prop_assert_eq!(TlvCodec::frame_len(&bytes[..3]), None);
+        prop_assert_eq!(TlvCodec::frame_len(&bytes), Some(bytes.len() as u64));
+    }
+
+    #[test]
+    fn tlv_survives_garbage(body in vec(any::<u8>(), 0..256)) {
+        // Whatever the peer sends is an error at worst, never a panic
+        let mut frame = (body.len() as u32).to_be_bytes().to_vec();
+        frame.extend(&body);
+        let _ = TlvCodec.decode(&mut &frame[..], u64::MAX);
+    }
+
+    #[test]
+    fn messages_framed_back_to_back(first in message(), second in message()) {
+        // Each message is read exactly, leaving the next intact
+        let mut stream = first.to_bytes().unwrap();
+        stream.extend(second.to_bytes().unwrap());
+        let mut reader = &stream[..];
+        prop_assert_eq!(&PortalMessage::recv(&mut reader).unwrap(), &first);
+        prop_assert_eq!(&PortalMessage::recv(&mut reader).unwrap(), &second);
+        prop_assert!(reader.is_empty());
+
+        // Strict parsing refuses anything after the first message
+        let err = PortalMessage::parse_strict(&stream, u64::MAX).unwrap_err();
+        prop_assert_eq!(err.downcast_ref(), Some(&PortalError::TrailingBytes));
+    }
+
+    #[test]
+    fn messages_truncated_rejected(msg in message(), cut in any::<prop::sample::Index>()) {
+        let bytes = msg.to_bytes().unwrap();
+        let len = cut.index(bytes.len());
+        prop_assert!(PortalMessage::recv(&mut &bytes[..len]).is_err());
+    }
+
+    #[test]
+    fn transfer_info_roundtrips(info in transfer_info()) {
+        for format in [WireFormat::Bincode, WireFormat::Json, WireFormat::Cbor] {
+            if !format.is_supported() {
+                continue;
+            }
+            let bytes = format.serialize(&info).unwrap();
+            let decoded: TransferInfo = format.deserialize(&bytes).unwrap();
+            prop_assert_eq!(&decoded, &info);
+
+            // FileEntry only compares some fields, so compare encodings too
+            prop_assert_eq!(format.serialize(&decoded).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn dedup_references_roundtrip(file in any::<u32>(), offset in any::<u64>(), len in any::<u32>()) {
+        let reference = ChunkRef { file, offset, len };
+        let encoded = reference.encode();
+        prop_assert_eq!(DedupChunk::parse(&encoded).unwrap(), DedupChunk::Reference(reference));
+
+        // Truncated or extended references are rejected
+        prop_assert!(DedupChunk::parse(&encoded[..encoded.len() - 1]).is_err());
+        prop_assert!(DedupChunk::parse(&[&encoded[..], &[0]].concat()).is_err());
+    }
+
+    #[test]
+    fn dedup_cuts_bounded(data in vec(any::<u8>(), 0..2 * DEDUP_MAX_CHUNK)) {
+        let len = dedup::cut(&data);
+        prop_assert!(len <= DEDUP_MAX_CHUNK.min(data.len()));
+        prop_assert!(len >= DEDUP_MIN_CHUNK.min(data.len()));
+
+        // Data after a boundary doesn't move it
+        if len < data.len() {
+            let extended = [&data[..], &data[..]].concat();
+            prop_assert_eq!(dedup::cut(&extended), len);
+        }
+    }
+}
+
+/// A TransferInfo with every field set, as encoded with bincode. Peers
+/// must agree on this encoding to exchange TransferInfo, so any change
+/// to it breaks compatibility with released versions.
+const TRANSFER_INFO_BINCODE: &str = concat!(
+    // all: 1 entry in the V1 layout, with its filesize & filename "a.txt"
+    "0100000000000000",
+    "00000000",
+    "0500000000000000",
+    "0500000000000000612e747874",
+    // kind: Regular, then copies: ["b.txt"]
+    "00000000",
+    "01000000000000000500000000000000622e747874",
+    // preview: Some([1, 2]), then max_chunk: 65536 & dedup
+    "0102000000000000000102",
+    "00000100",
+    "01",
+    // mode: Some(0o644), modified: Some(1), no digest & mime: Some("text/plain")
+    "01a4010000",
+    "010100000000000000",
+    "00",
+    "010a00000000000000746578742f706c61696e",
+    // note: Some("hi")
+    "0102000000000000006869",
+);
+
+#[test]
+fn transfer_info_encoding_stable() {
+    let mut info = TransferInfo::empty();
+    info.all.push(FileEntry {
+        filesize: 5,
+        filename: "a.txt".into(),
+        copies: vec!["b.txt".into()],
+        preview: Some(vec![1, 2]),
+        max_chunk: 64 * 1024,
+        dedup: true,
+        mode: Some(0o644),
+        modified: Some(1),
+        mime: Some("text/plain".into()),
+        ..Default::default()
+    });
+    info.set_note("hi").unwrap();
+
+    let bytes = WireFormat::Bincode.serialize(&info).unwrap();
+    assert_eq!(hex::encode(&bytes), TRANSFER_INFO_BINCODE);
+
+    let decoded: TransferInfo = WireFormat::Bincode
+        .deserialize(&hex::decode(TRANSFER_INFO_BINCODE).unwrap())
+        .unwrap();
+    assert_eq!(decoded, info);
+    assert_eq!(decoded.all[0].copies, info.all[0].copies);
+    assert_eq!(decoded.all[0].preview, info.all[0].preview);
+    assert_eq!(decoded.all[0].max_chunk, info.all[0].max_chunk);
+    assert_eq!(decoded.all[0].dedup, info.all[0].dedup);
+    assert_eq!(decoded.all[0].mode, info.all[0].mode);
+    assert_eq!(decoded.all[0].modified, info.all[0].modified);
+    assert_eq!(decoded.all[0].mime, info.all[0].mime);
+    assert_eq!(decoded.note, info.note);
+}
@@ -0,0 +1,202 @@
+use super::{PortalMessage, RelayControl, RelayControlError, SessionSalt};
+use hmac::{Hmac, Mac, NewMac};
+use sha2::Sha256;
+use std::io::{self, Read, Write};
+use std::thread;
+use std::time::Duration;
+
+/// Domain separation for resume tokens
+const RESUME_CONTEXT: &[u8] = b"portal-resume";
+
+/// Attempts to resume a dropped connection before giving up
+pub const RESUME_ATTEMPTS: u32 = 5;
+
+/// Delay before the first attempt to resume, doubled for each further one
+pub const RESUME_DELAY: Duration = Duration::from_millis(500);
+
+/// Proves to the relay that a reconnecting Receiver is the one it paired.
+/// Derived from the session salt, which only the two peers were sent.
+pub type ResumeToken = [u8; 32];
+
+impl SessionSalt {
+    /// The token a Receiver presents to resume the session for `id`
+    pub fn resume_token(&self, id: &str) -> ResumeToken {
+        let mut token = [0u8; 32];
+        token.copy_from_slice(&self.resume_mac(id).finalize().into_bytes());
+        token
+    }
+
+    /// Check a resume token in constant time
+    pub fn verify_resume(&self, id: &str, token: &ResumeToken) -> bool {
+        self.resume_mac(id).verify(token).is_ok()
+    }
+
+    /// Helper: the MAC state over the ID, ready to be finalized or compared
+    fn resume_mac(&self, id: &str) -> Hmac<Sha256> {
+        // HMAC accepts keys of any length
+        let mut mac = Hmac::<Sha256>::new_varkey(&self.0).unwrap();
+        mac.update(RESUME_CONTEXT);
+        mac.update(id.as_bytes());
+        mac
+    }
+}
+
+/// Opens a new connection to the relay
+pub type Reconnect<S> = Box<dyn FnMut() -> io::Result<S> + Send>;
+
+/// A Receiver's connection to the relay, which resumes the session over a
+/// new connection if it drops mid-transfer. The relay must be buffering
+/// (`--buffer-mb`), it replays whatever the Receiver missed so that reads
+/// continue where they left off. Until [`Reconnecting::arm`] is called,
+/// once the session is paired, errors are returned as is.
+///
+/// ```no_run
+/// use std::net::TcpStream;
+/// use portal_lib::{Direction, Portal, Reconnecting};
+///
+/// let relay = "127.0.0.1:13265";
+/// let mut portal = Portal::init(Direction::Receiver, "id".into(), "password".into()).unwrap();
+/// let mut stream = Reconnecting::new(TcpStream::connect(relay).unwrap())
+///     .with_reconnect(Box::new(move || TcpStream::connect(relay)));
+/// portal.handshake(&mut stream).unwrap();
+/// if let Some(token) = portal.resume_token() {
+///     stream.arm(portal.get_id(), token);
+/// }
+///
+/// // ... receive files from `stream` as usual ...
+/// ```
+pub struct Reconnecting<S> {
+    inner: S,
+    received: u64,
+    reconnect: Option<Reconnect<S>>,
+    session: Option<(String, ResumeToken)>,
+}
+
+impl<S: Read + Write> Reconnecting<S> {
+    /// Wrap a connection to the relay, which is never resumed until a
+    /// way to reconnect is given
+    pub fn new(inner: S) -> Self {
+        Reconnecting {
+            inner,
+            received: 0,
+            reconnect: None,
+            session: None,
+        }
+    }
+
+    /// Open new connections to the relay with `reconnect`
+    pub fn with_reconnect(mut self, reconnect: Reconnect<S>) -> Self {
+        self.reconnect = Some(reconnect);
+        self
+    }
+
+    /// Resume the session for `id` with `token` if the connection drops,
+    /// see [`Portal::resume_token`](crate::Portal::resume_token)
+    pub fn arm(&mut self, id: &str, token: ResumeToken) {
+        self.session = Some((id.to_string(), token));
+    }
+
+    /// Total bytes read from the relay, across every connection
+    pub fn received(&self) -> u64 {
+        self.received
+    }
+
+    /// The current connection
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    /// The current connection
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Helper: replace the connection, returning the original error if
+    /// the session can't be resumed
+    fn resume(&mut self, error: io::Error) -> io::Result<()> {
+        let (id, token) = match &self.session {
+            Some(session) => session.clone(),
+            None => return Err(error),
+        };
+        let reconnect = match &mut self.reconnect {
+            Some(reconnect) => reconnect,
+            None => return Err(error),
+        };
+
+        let request = RelayControl::Resume {
+            id,
+            received: self.received,
+            token,
+        };
+        for attempt in 0..RESUME_ATTEMPTS {
+            thread::sleep(RESUME_DELAY * 2u32.pow(attempt));
+            let mut stream = match reconnect() {
+                Ok(stream) => stream,
+                Err(_) => continue,
+            };
+            if PortalMessage::RelayControl(request.clone())
+                .send(&mut stream)
+                .is_err()
+            {
+                continue;
+            }
+            match PortalMessage::recv(&mut stream) {
+                Ok(PortalMessage::RelayControl(RelayControl::Ack)) => {
+                    self.inner = stream;
+                    return Ok(());
+                }
+                // Nothing can bring back data the relay didn't keep
+                Ok(PortalMessage::RelayControl(RelayControl::Error(
+                    RelayControlError::DataLost,
+                ))) => break,
+                // The relay may not have noticed the old connection drop yet
+                _ => continue,
+            }
+        }
+        Err(error)
+    }
+}
+
+/// True if the connection may have dropped, rather than a transient
+/// failure that transferred no data
+fn is_dropped(e: &io::Error) -> bool {
+    !matches!(
+        e.kind(),
+        io::ErrorKind::WouldBlock | io::ErrorKind::Interrupted
+    )
+}
+
+impl<S: Read + Write> Read for Reconnecting<S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            match self.inner.read(buf) {
+                Ok(n) => {
+                    self.received += n as u64;
+                    return Ok(n);
+                }
+                Err(e) if is_dropped(&e) => self.resume(e)?,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+impl<S: Read + Write> Write for Reconnecting<S> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        loop {
+            match self.inner.write(buf) {
+                Err(e) if is_dropped(&e) => self.resume(e)?,
+                result => return result,
+            }
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        loop {
+            match self.inner.flush() {
+                Err(e) if is_dropped(&e) => self.resume(e)?,
+                result => return result,
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::io::{self, Read, Write};
+use std::thread;
+use std::time::Duration;
+
+/// Consecutive transient failures of one read or write during the
+/// handshake, before the error is returned
+pub const HANDSHAKE_RETRIES: u32 = 5;
+
+/// Delay before retrying a transient failure, doubled for each further attempt
+pub const HANDSHAKE_RETRY_DELAY: Duration = Duration::from_millis(10);
+
+/// Retries reads & writes that fail with a transient error, such as a
+/// non-blocking socket that isn't ready or a call interrupted by a signal.
+/// Such a failure transfers no data, so retrying it leaves the stream
+/// exactly where a successful call would have, unlike retrying a message
+/// that was partially read. Used internally by every handshake step.
+///
+/// On Unix a socket read timeout is reported as `WouldBlock`, so a read
+/// may wait up to `HANDSHAKE_RETRIES + 1` times the timeout.
+///
+/// ```
+/// use std::io::Read;
+/// use portal_lib::Retrying;
+///
+/// let mut stream: &[u8] = b"data";
+/// let mut buf = [0u8; 4];
+/// Retrying::new(&mut stream).read_exact(&mut buf).unwrap();
+/// assert_eq!(&buf, b"data");
+/// ```
+pub struct Retrying<'a, P> {
+    inner: &'a mut P,
+    retries: u32,
+}
+
+impl<'a, P> Retrying<'a, P> {
+    /// Wrap a stream, retrying up to [`HANDSHAKE_RETRIES`] times
+    pub fn new(inner: &'a mut P) -> Self {
+        Retrying {
+            inner,
+            retries: HANDSHAKE_RETRIES,
+        }
+    }
+
+    /// Retry a transient failure up to this many times
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Helper: run an IO operation, retrying transient failures
+    fn retry<T>(&mut self, mut op: impl FnMut(&mut P) -> io::Result<T>) -> io::Result<T> {
+        let mut attempt = 0;
+        loop {
+            match op(self.inner) {
+                Err(e) if is_transient(&e) && attempt < self.retries => {
+                    thread::sleep(HANDSHAKE_RETRY_DELAY * 2u32.pow(attempt));
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// True if an IO error transferred no data & may succeed if retried
+fn is_transient(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        io::ErrorKind::WouldBlock | io::ErrorKind::Interrupted
+    )
+}
+
+impl<P: Read> Read for Retrying<'_, P> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.retry(|inner| inner.read(buf))
+    }
+}
+
+impl<P: Write> Write for Retrying<'_, P> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.retry(|inner| inner.write(buf))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.retry(|inner| inner.flush())
+    }
+}
@@ -0,0 +1,51 @@
+use crate::errors::PortalError::*;
+use hkdf::Hkdf;
+use sha2::Sha256;
+use std::error::Error;
+
+/// Length in bytes of the derived sub-password before hex encoding
+const SHARD_PASSWORD_SIZE: usize = 16;
+
+/// A per-receiver credential derived from the sender's master
+/// pass-phrase. Allows a sender to authorize several distinct
+/// receivers for the same set of files, where each receiver is
+/// paired and confirmed independently.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Shard {
+    /// The receiver index this shard was derived for
+    pub index: u32,
+    /// The ID the relay will use to pair this receiver
+    pub id: String,
+    /// The password this receiver must use
+    pub password: String,
+}
+
+impl Shard {
+    /// Derive the credentials for receiver `index` from the sender's
+    /// master ID and pass-phrase.
+    ///
+    /// ```
+    /// use portal_lib::Shard;
+    ///
+    /// let first = Shard::derive("id", "master password", 0).unwrap();
+    /// let second = Shard::derive("id", "master password", 1).unwrap();
+    /// assert_ne!(first.password, second.password);
+    /// ```
+    pub fn derive(id: &str, master: &str, index: u32) -> Result<Shard, Box<dyn Error>> {
+        // Bind the ID and receiver index into the expansion
+        let info = format!("{}-shard-{}", id, index);
+
+        // Expand the master pass-phrase into this receiver's password
+        let h = Hkdf::<Sha256>::new(None, master.as_bytes());
+        let mut password = [0u8; SHARD_PASSWORD_SIZE];
+        h.expand(info.as_bytes(), &mut password)
+            .or(Err(CryptoError))?;
+
+        // The index follows the last dot, so "id1" & 1 can't pass for "id" & 11
+        Ok(Shard {
+            index,
+            id: format!("{}.{}", id, index),
+            password: hex::encode(password),
+        })
+    }
+}
@@ -0,0 +1,59 @@
+use super::{BincodeCodec, PortalMessage, WireCodec};
+use crate::errors::PortalError::*;
+use std::error::Error;
+use std::io::Read;
+
+/// Maximum size of any message received during a strict handshake.
+/// Connect, Paired & KeyExchange messages are all far smaller.
+pub const MAX_HANDSHAKE_MESSAGE_SIZE: u64 = 1024;
+
+/// The order in which messages must be received while connecting in
+/// strict mode. Anything else received from the relay is rejected.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum HandshakeState {
+    /// Waiting on the relay's Paired message, or a direct peer's Connect
+    AwaitingPeer,
+    /// Waiting on the peer's KeyExchange
+    AwaitingExchange,
+    /// The key exchange data has been received
+    Exchanged,
+}
+
+impl HandshakeState {
+    /// Check that the message is allowed in the current state
+    /// and advance to the next one
+    pub fn advance(&mut self, msg: &PortalMessage) -> Result<(), Box<dyn Error>> {
+        *self = match (*self, msg) {
+            (HandshakeState::AwaitingPeer, PortalMessage::Paired(_)) => {
+                HandshakeState::AwaitingExchange
+            }
+            (HandshakeState::AwaitingPeer, PortalMessage::Connect(_)) => {
+                HandshakeState::AwaitingExchange
+            }
+            (HandshakeState::AwaitingExchange, PortalMessage::KeyExchange(_)) => {
+                HandshakeState::Exchanged
+            }
+            _ => return Err(UnexpectedMessage.into()),
+        };
+        Ok(())
+    }
+}
+
+impl PortalMessage {
+    /// Receive an arbitrary PortalMessage with the v1 codec, failing with
+    /// MessageTooLarge rather than reading more than `limit` bytes
+    pub fn recv_limited<R: Read>(reader: &mut R, limit: u64) -> Result<Self, Box<dyn Error>> {
+        BincodeCodec.decode(reader, limit)
+    }
+
+    /// Deserialize a single message from existing data. Unlike `parse` the
+    /// data must contain exactly one message no larger than `limit`
+    pub fn parse_strict(data: &[u8], limit: u64) -> Result<Self, Box<dyn Error>> {
+        let mut remaining = data;
+        let msg = Self::recv_limited(&mut remaining, limit)?;
+        if !remaining.is_empty() {
+            return Err(TrailingBytes.into());
+        }
+        Ok(msg)
+    }
+}
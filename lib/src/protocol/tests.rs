@@ -1,15 +1,23 @@
 use super::{Direction, Protocol};
 use crate::errors::PortalError;
 use crate::protocol::{
-    ConnectMessage, EncryptedMessage, NonceSequence, PortalConfirmation, PortalMessage,
-    TransferInfo, TransferInfoBuilder,
+    AuthenticatedConnect, Capabilities, CodecVersion, ConnectMessage, ConnectOptions,
+    EncryptedMessage, FileEntry, FileKind, Metadata, NonceSequence, Offer, Offers, PairedMessage,
+    PeerInfo, PortalConfirmation, PortalKeyExchange, PortalMessage, Priority, Reconnecting,
+    RelayBanner, RelayControl, RelayControlError, Retrying, SessionSalt, Shard, TransferInfo,
+    TransferInfoBuilder, WireCodec, WireFormat, MAX_AUTH_SKEW, MAX_DATA_HEADER,
+    MAX_HANDSHAKE_MESSAGE_SIZE, MAX_OBJECT_SIZE, MAX_PREVIEW_SIZE, MIN_RELAY_VERSION,
+    PROTOCOL_VERSION,
 };
 use crate::tests::MockTcpStream;
 use crate::Portal;
 use mockstream::SyncMockStream;
 use std::convert::TryInto;
+use std::fs::File;
+use std::io::{self, Read, Write};
 use std::path::Path;
 use std::thread;
+use tempdir::TempDir;
 
 macro_rules! assert_err {
     ($expression:expr, $($pattern:tt)+) => {
@@ -25,7 +33,7 @@ fn test_nonce() {
     let mut n = NonceSequence::new();
     let mut old = [0u8; 12];
     for _ in 0..5_000_000 {
-        let new = n.next().unwrap();
+        let new = n.next_unique().unwrap();
         // test that every nonce is greater than the last
         // which means it is larger & different than all previous
         assert!(new > old);
@@ -46,12 +54,19 @@ fn test_connect() {
     let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
 
     // Save sender.exchange before move
-    let senderexchange = sender.exchange.clone();
+    let senderexchange = sender.exchange;
     let handle = thread::spawn(move || {
         Protocol::connect(
             &mut senderstream,
-            sender.get_id(),
-            sender.get_direction(),
+            ConnectMessage {
+                id: sender.get_id().to_owned(),
+                direction: sender.get_direction(),
+            },
+            ConnectOptions {
+                format: sender.get_wire_format(),
+                priority: sender.get_priority(),
+                codec: sender.get_codec(),
+            },
             sender.exchange,
         )
         .unwrap()
@@ -59,15 +74,100 @@ fn test_connect() {
 
     let receiver_got = Protocol::connect(
         &mut receiverstream,
-        receiver.get_id(),
-        receiver.get_direction(),
+        ConnectMessage {
+            id: receiver.get_id().to_owned(),
+            direction: receiver.get_direction(),
+        },
+        ConnectOptions {
+            format: receiver.get_wire_format(),
+            priority: receiver.get_priority(),
+            codec: receiver.get_codec(),
+        },
         receiver.exchange,
     )
     .unwrap();
 
     let sender_got = handle.join().unwrap();
-    assert_eq!(sender_got, receiver.exchange);
-    assert_eq!(receiver_got, senderexchange);
+    assert_eq!(sender_got.0, receiver.exchange);
+    assert_eq!(receiver_got.0, senderexchange);
+    let expected = PeerInfo {
+        codec: CodecVersion::LATEST,
+        ..Default::default()
+    };
+    assert_eq!(sender_got.1, expected);
+    assert_eq!(receiver_got.1, expected);
+}
+
+/// Fails every other read & write with WouldBlock, without transferring data
+struct Flaky<S> {
+    inner: S,
+    ready: bool,
+}
+
+impl<S> Flaky<S> {
+    fn new(inner: S) -> Self {
+        Flaky {
+            inner,
+            ready: false,
+        }
+    }
+
+    fn poll(&mut self) -> io::Result<()> {
+        self.ready = !self.ready;
+        match self.ready {
+            true => Ok(()),
+            false => Err(io::ErrorKind::WouldBlock.into()),
+        }
+    }
+}
+
+impl<S: Read> Read for Flaky<S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.poll()?;
+        self.inner.read(buf)
+    }
+}
+
+impl<S: Write> Write for Flaky<S> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.poll()?;
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[test]
+fn test_connect_retries_transient_errors() {
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+    let (senderstream, receiverstream) = MockTcpStream::channel();
+
+    // Every step of the handshake sees WouldBlock before each read & write
+    let handle = thread::spawn(move || {
+        let mut stream = Flaky::new(senderstream);
+        sender.handshake(&mut stream).unwrap();
+        sender.get_key().clone()
+    });
+    let mut stream = Flaky::new(receiverstream);
+    receiver.handshake(&mut stream).unwrap();
+    assert_eq!(&handle.join().unwrap(), receiver.get_key());
+
+    // Retries are bounded, a stream that never becomes ready fails
+    struct Blocked;
+    impl Read for Blocked {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::WouldBlock.into())
+        }
+    }
+    let mut buf = [0u8; 1];
+    let err = Retrying::new(&mut Blocked)
+        .with_retries(2)
+        .read(&mut buf)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
 }
 
 #[test]
@@ -84,10 +184,17 @@ fn test_key_derivation() {
 
     // Save sender.exchange before move
     let handle = thread::spawn(move || {
-        let msg = Protocol::connect(
+        let (msg, _) = Protocol::connect(
             &mut senderstream,
-            sender.get_id(),
-            sender.get_direction(),
+            ConnectMessage {
+                id: sender.get_id().to_owned(),
+                direction: sender.get_direction(),
+            },
+            ConnectOptions {
+                format: sender.get_wire_format(),
+                priority: sender.get_priority(),
+                codec: sender.get_codec(),
+            },
             sender.exchange,
         )
         .unwrap();
@@ -98,10 +205,17 @@ fn test_key_derivation() {
         Protocol::derive_key(state, &msg).unwrap()
     });
 
-    let receiver_got = Protocol::connect(
+    let (receiver_got, _) = Protocol::connect(
         &mut receiverstream,
-        receiver.get_id(),
-        receiver.get_direction(),
+        ConnectMessage {
+            id: receiver.get_id().to_owned(),
+            direction: receiver.get_direction(),
+        },
+        ConnectOptions {
+            format: receiver.get_wire_format(),
+            priority: receiver.get_priority(),
+            codec: receiver.get_codec(),
+        },
         receiver.exchange,
     )
     .unwrap();
@@ -129,10 +243,17 @@ fn test_key_confirmation() {
 
     // Save sender.exchange before move
     let handle = thread::spawn(move || {
-        let msg = Protocol::connect(
+        let (msg, _) = Protocol::connect(
             &mut senderstream,
-            sender.get_id(),
-            sender.get_direction(),
+            ConnectMessage {
+                id: sender.get_id().to_owned(),
+                direction: sender.get_direction(),
+            },
+            ConnectOptions {
+                format: sender.get_wire_format(),
+                priority: sender.get_priority(),
+                codec: sender.get_codec(),
+            },
             sender.exchange,
         )
         .unwrap();
@@ -147,19 +268,27 @@ fn test_key_confirmation() {
             Protocol::confirm_peer(
                 &mut senderstream,
                 sender.get_id(),
+                None,
                 sender.get_direction(),
                 &skey,
             )
-            .unwrap(),
+            .map_err(|e| e.to_string()),
             skey,
         )
     });
 
     // Receiver connect
-    let receiver_got = Protocol::connect(
+    let (receiver_got, _) = Protocol::connect(
         &mut receiverstream,
-        receiver.get_id(),
-        receiver.get_direction(),
+        ConnectMessage {
+            id: receiver.get_id().to_owned(),
+            direction: receiver.get_direction(),
+        },
+        ConnectOptions {
+            format: receiver.get_wire_format(),
+            priority: receiver.get_priority(),
+            codec: receiver.get_codec(),
+        },
         receiver.exchange,
     )
     .unwrap();
@@ -172,10 +301,11 @@ fn test_key_confirmation() {
     let receiver_result = Protocol::confirm_peer(
         &mut receiverstream,
         receiver.get_id(),
+        None,
         receiver.get_direction(),
         &rkey,
     )
-    .unwrap();
+    .map_err(|e| e.to_string());
 
     // Join sender
     let (sender_result, skey) = handle.join().unwrap();
@@ -183,6 +313,7 @@ fn test_key_confirmation() {
     // Assert key and confirm result are equal
     assert_eq!(rkey, skey);
     assert_eq!(sender_result, receiver_result);
+    assert_eq!(receiver_result, Ok(()));
 }
 
 #[test]
@@ -208,6 +339,129 @@ fn test_serialize_deserialize_message() {
     assert_eq!(res.direction, values.direction);
 }
 
+#[test]
+fn test_priority() {
+    let values = ConnectOptions {
+        priority: Priority::Bulk,
+        ..Default::default()
+    };
+    let ser = bincode::serialize(&PortalMessage::Options(values)).unwrap();
+    match PortalMessage::parse(&ser).unwrap() {
+        PortalMessage::Options(inner) => assert_eq!(inner, values),
+        _ => panic!("Incorrect message type"),
+    }
+
+    // Bulk if either peer asks for it
+    assert_eq!(
+        Priority::combine(Priority::Normal, Priority::Normal),
+        Priority::Normal
+    );
+    assert_eq!(
+        Priority::combine(Priority::Bulk, Priority::Normal),
+        Priority::Bulk
+    );
+
+    // The DSCP sits above the ECN bits of the TOS byte
+    #[cfg(unix)]
+    {
+        use std::os::unix::io::AsRawFd;
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let stream = std::net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        Priority::Bulk.mark(&stream).unwrap();
+        let mut tos: libc::c_int = 0;
+        let mut len = std::mem::size_of::<libc::c_int>() as libc::socklen_t;
+        let res = unsafe {
+            libc::getsockopt(
+                stream.as_raw_fd(),
+                libc::IPPROTO_IP,
+                libc::IP_TOS,
+                &mut tos as *mut libc::c_int as *mut libc::c_void,
+                &mut len,
+            )
+        };
+        assert_eq!(res, 0);
+        assert_eq!(tos, 0x20);
+    }
+}
+
+#[test]
+fn test_capabilities() {
+    // A request laid out by hand as every client has sent it: Connect's
+    // variant index, then the ConnectMessage's fields in order
+    let baseline = bincode::serialize(&(0u32, "id", Direction::Receiver)).unwrap();
+    let request = ConnectMessage {
+        id: "id".to_string(),
+        direction: Direction::Receiver,
+    };
+    let connect = PortalMessage::Connect(request.clone());
+    assert_eq!(PortalMessage::parse(&baseline).unwrap(), connect);
+    assert_eq!(connect.to_bytes().unwrap(), baseline);
+
+    // Options & capabilities follow the request, which relays that
+    // don't know them read as it is
+    let options = ConnectOptions {
+        format: WireFormat::Json,
+        priority: Priority::Bulk,
+        codec: CodecVersion::V2,
+    };
+    let ours = Capabilities::DEDUP.with(Capabilities::BIND_CHUNKS, true);
+    let mut old = baseline.clone();
+    old.extend(PortalMessage::Options(options).to_bytes().unwrap());
+    let mut new = old.clone();
+    new.extend(PortalMessage::Capabilities(ours).to_bytes().unwrap());
+    assert_eq!(PortalMessage::parse(&new).unwrap(), connect);
+
+    // The relay passes on the peer's only to clients that sent theirs
+    let paired = PortalMessage::Paired(PairedMessage {
+        peer: ConnectMessage {
+            direction: Direction::Sender,
+            ..request.clone()
+        },
+        options,
+        salt: SessionSalt::generate(),
+    });
+    let exchange = PortalMessage::KeyExchange(vec![1u8; 33].try_into().unwrap());
+    let connect = |messages: &[&PortalMessage], capabilities| {
+        let mut stream = SyncMockStream::new();
+        for message in messages {
+            stream.push_bytes_to_read(&message.to_bytes().unwrap());
+        }
+        let key = vec![0u8; 33].try_into().unwrap();
+        let result = Protocol::connect_strict_with(
+            &mut stream,
+            request.clone(),
+            options,
+            None,
+            capabilities,
+            key,
+        );
+        (result.map(|(_, info)| info), stream.pop_bytes_written())
+    };
+    let theirs = PortalMessage::Capabilities(Capabilities::DEDUP);
+    let (info, written) = connect(&[&theirs, &paired, &exchange], Some(ours));
+    assert_eq!(info.unwrap().capabilities, Some(Capabilities::DEDUP));
+    assert!(written.starts_with(&new));
+
+    // Relays & peers that don't send any support none
+    let (info, _) = connect(&[&paired, &exchange], Some(ours));
+    assert_eq!(info.unwrap().capabilities, None);
+
+    // Nor may they be sent to a client that didn't offer its own
+    let (info, written) = connect(&[&theirs, &paired, &exchange], None);
+    assert_err!(
+        info.err().unwrap().downcast_ref::<PortalError>(),
+        Some(PortalError::UnexpectedMessage)
+    );
+    assert!(written.starts_with(&old));
+    assert!(!written.starts_with(&new));
+
+    // Unknown flags are kept, but never in common
+    let newer = Capabilities(1 << 63);
+    assert_eq!(ours.common(newer), Capabilities::default());
+    let decoded = PortalMessage::parse(&PortalMessage::Capabilities(newer).to_bytes().unwrap());
+    assert_eq!(decoded.unwrap(), PortalMessage::Capabilities(newer));
+}
+
 #[test]
 fn test_connect_badmsg() {
     let id = "id".to_string();
@@ -230,8 +484,11 @@ fn test_connect_badmsg() {
     let handle = thread::spawn(move || {
         Protocol::connect(
             &mut stream,
-            &id,
-            Direction::Receiver,
+            ConnectMessage {
+                id: id.clone(),
+                direction: Direction::Receiver,
+            },
+            ConnectOptions::default(),
             vec![0u8; 33].try_into().unwrap(),
         )
         .unwrap_err()
@@ -261,7 +518,7 @@ fn test_confirm_peer_badmsg() {
 
     // Call the function under test
     let handle = thread::spawn(move || {
-        Protocol::confirm_peer(&mut stream, &id, Direction::Receiver, &[0u8; 32])
+        Protocol::confirm_peer(&mut stream, &id, None, Direction::Receiver, &[0u8; 32])
             .unwrap_err()
             .downcast::<PortalError>()
             .unwrap()
@@ -280,13 +537,13 @@ fn test_confirm_peer_unexpected_hkdf() {
     // Serialize and push a properly formatted Confirm
     // message that doesn't match what we should send
     // if we know the key
-    let values = PortalConfirmation { 0: [1u8; 42] };
+    let values = PortalConfirmation([1u8; 42]);
     let message = PortalMessage::Confirm(values);
     stream.push_bytes_to_read(&bincode::serialize(&message).unwrap());
 
     // Call the function under test
     let handle = thread::spawn(move || {
-        Protocol::confirm_peer(&mut stream, &id, Direction::Receiver, &[0u8; 32])
+        Protocol::confirm_peer(&mut stream, &id, None, Direction::Receiver, &[0u8; 32])
             .unwrap_err()
             .downcast::<PortalError>()
             .unwrap()
@@ -315,7 +572,7 @@ fn test_read_encrypted_zero_copy_badmsg() {
     // Call the function under test
     let mut storage = vec![0u8; 1024];
     let handle = thread::spawn(move || {
-        Protocol::read_encrypted_zero_copy(&mut stream, &[0u8; 32], &mut storage)
+        Protocol::read_encrypted_zero_copy(&mut stream, &[0u8; 32], CodecVersion::V1, &mut storage)
             .unwrap_err()
             .downcast::<PortalError>()
             .unwrap()
@@ -336,14 +593,16 @@ fn test_read_encrypted_zero_copy_buffertoosmall() {
     // Serialize and push a EncryptedDataHeader message
     // but with an extremely large msg.len that exceeds
     // our storage size.
-    let mut values = EncryptedMessage::default();
-    values.len = 1_000_000;
+    let values = EncryptedMessage {
+        len: 1_000_000,
+        ..Default::default()
+    };
     let message = PortalMessage::EncryptedDataHeader(values);
     stream.push_bytes_to_read(&bincode::serialize(&message).unwrap());
 
     // Call the function under test
     let handle = thread::spawn(move || {
-        Protocol::read_encrypted_zero_copy(&mut stream, &[0u8; 32], &mut storage)
+        Protocol::read_encrypted_zero_copy(&mut stream, &[0u8; 32], CodecVersion::V1, &mut storage)
             .unwrap_err()
             .downcast::<PortalError>()
             .unwrap()
@@ -376,6 +635,160 @@ fn transferinfo_strips_paths() {
     }
 }
 
+#[test]
+fn transferinfo_baseline_layout_is_refused() {
+    // A TransferInfo laid out by hand as the first clients sent it: the
+    // file list alone, each file's size & name in order
+    let baseline = bincode::serialize(&(vec![(5u64, "file")],)).unwrap();
+
+    // Versioned entries can't be read from it, so such peers are
+    // refused at handshake time instead, see handshake_refuses_older_peers
+    assert!(bincode::deserialize::<TransferInfo>(&baseline).is_err());
+}
+
+#[test]
+fn transferinfo_records_attributes() {
+    let tmp_dir = TempDir::new("transferinfo_records_attributes").unwrap();
+    let path = tmp_dir.path().join("script.sh");
+    std::fs::write(&path, b"#!/bin/sh").unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o750)).unwrap();
+    }
+
+    let info = TransferInfoBuilder::new()
+        .add_file(&path)
+        .unwrap()
+        .finalize();
+    let entry = &info.all[0];
+    assert_eq!(entry.kind, FileKind::Regular);
+    assert_eq!(entry.filesize, 9);
+    assert!(entry.modified.is_some());
+    #[cfg(unix)]
+    assert_eq!(entry.mode, Some(0o750));
+
+    // The attributes reach the receiver, though entries aren't compared by them
+    let other: TransferInfo = bincode::deserialize(&bincode::serialize(&info).unwrap()).unwrap();
+    assert_eq!(other.all[0].mode, entry.mode);
+    assert_eq!(other.all[0].modified, entry.modified);
+    let touched = FileEntry {
+        modified: None,
+        ..entry.clone()
+    };
+    assert_eq!(touched, *entry);
+}
+
+#[test]
+fn transferinfo_dedups_identical_files() {
+    let tmp_dir = TempDir::new("transferinfo_dedups_identical_files").unwrap();
+    let write = |name: &str, contents: &[u8]| {
+        let path = tmp_dir.path().join(name);
+        std::fs::write(&path, contents).unwrap();
+        path
+    };
+    let original = write("a.png", b"asset");
+    let copy = write("b.png", b"asset");
+    let same_size = write("c.png", b"other");
+
+    let info = TransferInfoBuilder::new()
+        .add_file(&original)
+        .unwrap()
+        .add_file(&copy)
+        .unwrap()
+        .add_file(&same_size)
+        .unwrap()
+        .finalize();
+
+    // The copy is recorded against the original, not sent separately
+    assert_eq!(info.all.len(), 2);
+    assert_eq!(info.localpaths, vec![original, same_size]);
+    assert_eq!(info.all[0].copies, vec!["b.png".to_string()]);
+    assert!(info.all[1].copies.is_empty());
+}
+
+#[test]
+fn transferinfo_previews() {
+    let tmp_dir = TempDir::new("transferinfo_previews").unwrap();
+    let path = tmp_dir.path().join("notes.txt");
+    std::fs::write(&path, b"first line\nsecond line\n").unwrap();
+
+    // The head of the file
+    let info = TransferInfoBuilder::new()
+        .add_file(&path)
+        .unwrap()
+        .head_previews(10)
+        .unwrap()
+        .finalize();
+    assert_eq!(info.all[0].preview.as_deref(), Some(&b"first line"[..]));
+
+    // A user generated preview
+    let info = TransferInfoBuilder::new()
+        .add_file(&path)
+        .unwrap()
+        .previews(|_| Some(b"thumbnail".to_vec()))
+        .unwrap()
+        .finalize();
+    assert_eq!(info.all[0].preview.as_deref(), Some(&b"thumbnail"[..]));
+
+    // Previews are bounded
+    let result = TransferInfoBuilder::new()
+        .add_file(&path)
+        .unwrap()
+        .previews(|_| Some(vec![0u8; MAX_PREVIEW_SIZE + 1]));
+    assert_err!(
+        result.err().unwrap().downcast_ref::<PortalError>(),
+        Some(PortalError::PreviewTooLarge)
+    );
+}
+
+#[test]
+fn test_read_encrypted_from_large_object() {
+    let key = [4u8; 32];
+    let mut nseq = NonceSequence::new();
+    let mut stream = SyncMockStream::new();
+
+    // Objects carrying previews exceed a single small buffer
+    let mut info = TransferInfo::empty();
+    info.all.push(Metadata {
+        filename: "image.png".into(),
+        preview: Some(vec![0xff; MAX_PREVIEW_SIZE]),
+        ..Default::default()
+    });
+    Protocol::encrypt_and_write_object(
+        &mut stream,
+        &key,
+        &mut nseq,
+        CodecVersion::V1,
+        WireFormat::Bincode,
+        &info,
+    )
+    .unwrap();
+    let written = stream.pop_bytes_written();
+    stream.push_bytes_to_read(&written);
+    let got: TransferInfo =
+        Protocol::read_encrypted_from(&mut stream, &key, CodecVersion::V1, WireFormat::Bincode)
+            .unwrap();
+    assert_eq!(got.all[0].preview, info.all[0].preview);
+
+    // But are still bounded
+    let header = PortalMessage::EncryptedDataHeader(EncryptedMessage {
+        len: MAX_OBJECT_SIZE + 1,
+        ..Default::default()
+    });
+    stream.push_bytes_to_read(&bincode::serialize(&header).unwrap());
+    let result = Protocol::read_encrypted_from::<_, TransferInfo>(
+        &mut stream,
+        &key,
+        CodecVersion::V1,
+        WireFormat::Bincode,
+    );
+    assert_err!(
+        result.err().unwrap().downcast_ref::<PortalError>(),
+        Some(PortalError::BufferTooSmall)
+    );
+}
+
 #[test]
 fn transferinfo_add_bad_path() {
     let result = TransferInfoBuilder::new().add_file(Path::new("/etc/.."));
@@ -385,3 +798,969 @@ fn transferinfo_add_bad_path() {
         Some(PortalError::BadFileName)
     );
 }
+
+#[test]
+fn shard_derivation_is_unique_per_receiver() {
+    let first = Shard::derive("id", "master", 0).unwrap();
+    let again = Shard::derive("id", "master", 0).unwrap();
+    let second = Shard::derive("id", "master", 1).unwrap();
+
+    // Derivation is deterministic
+    assert_eq!(first, again);
+
+    // Each receiver gets a distinct ID & password
+    assert_ne!(first.id, second.id);
+    assert_ne!(first.password, second.password);
+
+    // Nor do the ID & index run together
+    let eleventh = Shard::derive("id", "master", 11).unwrap();
+    let first_of_id1 = Shard::derive("id1", "master", 1).unwrap();
+    assert_ne!(eleventh.id, first_of_id1.id);
+
+    // A different master password yields different credentials
+    let other = Shard::derive("id", "other", 0).unwrap();
+    assert_eq!(first.id, other.id);
+    assert_ne!(first.password, other.password);
+}
+
+#[test]
+fn test_read_encrypted_at_out_of_order() {
+    let key = [7u8; 32];
+    let mut nseq = NonceSequence::new();
+    let mut stream = SyncMockStream::new();
+
+    // Encrypt two chunks, the second one is queued first
+    let mut first = *b"first-";
+    let mut second = *b"second";
+    let mut wire = Vec::new();
+    Protocol::encrypt_and_write_header_only(
+        &mut wire,
+        &key,
+        &mut nseq,
+        CodecVersion::V1,
+        &mut second,
+    )
+    .unwrap();
+    wire.extend_from_slice(&second);
+    Protocol::encrypt_and_write_header_only(
+        &mut wire,
+        &key,
+        &mut nseq,
+        CodecVersion::V1,
+        &mut first,
+    )
+    .unwrap();
+    wire.extend_from_slice(&first);
+    stream.push_bytes_to_read(&wire);
+
+    // Receive each chunk at its correct offset
+    let tmp_dir = TempDir::new("test_read_encrypted_at").unwrap();
+    let path = tmp_dir.path().join("out");
+    let file = File::create(&path).unwrap();
+    let mut storage = [0u8; 16];
+    Protocol::read_encrypted_at(&mut stream, &key, CodecVersion::V1, &file, 6, &mut storage)
+        .unwrap();
+    Protocol::read_encrypted_at(&mut stream, &key, CodecVersion::V1, &file, 0, &mut storage)
+        .unwrap();
+
+    // Contents are in order on disk
+    let mut contents = String::new();
+    File::open(&path)
+        .unwrap()
+        .read_to_string(&mut contents)
+        .unwrap();
+    assert_eq!(contents, "first-second");
+}
+
+#[test]
+fn test_relay_control_response() {
+    let mut stream = SyncMockStream::new();
+
+    // Queue the relay's response
+    let response = RelayControl::Error(RelayControlError::UnknownId);
+    let message = PortalMessage::RelayControl(response.clone());
+    stream.push_bytes_to_read(&bincode::serialize(&message).unwrap());
+
+    // The request is sent and the response returned to the caller
+    let result = Protocol::relay_control(&mut stream, RelayControl::Cancel("id".into())).unwrap();
+    assert_eq!(result, response);
+
+    // Verify the request that was written
+    let sent = PortalMessage::parse(&stream.pop_bytes_written()).unwrap();
+    assert_eq!(
+        sent,
+        PortalMessage::RelayControl(RelayControl::Cancel("id".into()))
+    );
+}
+
+#[test]
+fn test_relay_control_badmsg() {
+    let mut stream = SyncMockStream::new();
+
+    // Queue a message that isn't a RelayControl response
+    let message = PortalMessage::Connect(ConnectMessage {
+        id: "id".into(),
+        direction: Direction::Sender,
+    });
+    stream.push_bytes_to_read(&bincode::serialize(&message).unwrap());
+
+    let result = Protocol::relay_control(&mut stream, RelayControl::Cancel("id".into()))
+        .unwrap_err()
+        .downcast::<PortalError>()
+        .unwrap();
+    assert_eq!(*result, PortalError::BadMsg);
+}
+
+#[test]
+fn test_encrypted_message_key_commitment() {
+    let key = [1u8; 32];
+    let other_key = [2u8; 32];
+    let mut nseq = NonceSequence::new();
+
+    // Encrypt some data
+    let plaintext = *b"committed data";
+    let mut data = plaintext;
+    let mut msg = EncryptedMessage::encrypt(&key, &mut nseq, &mut data)
+        .unwrap()
+        .committed(&key, CodecVersion::V2)
+        .unwrap();
+
+    // A different key is rejected before decryption
+    let mut attempt = data;
+    let result = msg
+        .decrypt(&other_key, &mut attempt)
+        .unwrap_err()
+        .downcast::<PortalError>()
+        .unwrap();
+    assert_eq!(*result, PortalError::DecryptError);
+
+    // A modified commitment is rejected
+    let mut tampered = msg.clone();
+    tampered.commitment.as_mut().unwrap()[0] ^= 1;
+    let mut attempt = data;
+    assert!(tampered.decrypt(&key, &mut attempt).is_err());
+
+    // The correct key & commitment decrypt successfully
+    msg.decrypt(&key, &mut data).unwrap();
+    assert_eq!(data, plaintext);
+}
+
+#[test]
+fn test_encrypted_message_commitment_negotiated() {
+    let key = [1u8; 32];
+    let mut nseq = NonceSequence::new();
+    let mut data = *b"committed data";
+    let msg = EncryptedMessage::encrypt(&key, &mut nseq, &mut data).unwrap();
+
+    // v1 headers keep their layout, without a commitment
+    let v1 = msg.clone().committed(&key, CodecVersion::V1).unwrap();
+    assert_eq!(v1.commitment, None);
+    let header = PortalMessage::EncryptedDataHeader(v1.clone());
+    assert_eq!(
+        CodecVersion::V1.encode(&header).unwrap().len(),
+        4 + 12 + 16 + 8
+    );
+
+    // v2 headers commit to the key
+    let v2 = msg.committed(&key, CodecVersion::V2).unwrap();
+    assert!(v2.commitment.is_some());
+
+    // & are required to, so a commitment can't be stripped
+    for (codec, header, expected) in [
+        (CodecVersion::V1, &v1, None),
+        (CodecVersion::V2, &v2, None),
+        (CodecVersion::V2, &v1, Some(PortalError::BadMsg)),
+    ] {
+        let mut out = [0u8; MAX_DATA_HEADER];
+        let len = codec.encode_data_header(header, &mut out).unwrap();
+        let mut wire = out[..len].to_vec();
+        wire.extend_from_slice(&data);
+
+        let mut storage = [0u8; 64];
+        let result = Protocol::read_encrypted_zero_copy(&mut &wire[..], &key, codec, &mut storage);
+        assert_eq!(
+            result.err().and_then(|e| e.downcast().ok()).map(|e| *e),
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_connect_returns_relay_salt() {
+    let mut stream = SyncMockStream::new();
+    let salt = SessionSalt::generate();
+
+    // Queue the relay's pairing message followed by the peer's exchange
+    let message = PortalMessage::Paired(PairedMessage {
+        peer: ConnectMessage {
+            id: "id".to_string(),
+            direction: Direction::Sender,
+        },
+        options: ConnectOptions::default(),
+        salt,
+    });
+    stream.push_bytes_to_read(&bincode::serialize(&message).unwrap());
+    let exchange: PortalKeyExchange = vec![1u8; 33].try_into().unwrap();
+    let message = PortalMessage::KeyExchange(exchange);
+    stream.push_bytes_to_read(&bincode::serialize(&message).unwrap());
+
+    let result = Protocol::connect(
+        &mut stream,
+        ConnectMessage {
+            id: "id".to_string(),
+            direction: Direction::Receiver,
+        },
+        ConnectOptions::default(),
+        vec![0u8; 33].try_into().unwrap(),
+    )
+    .unwrap();
+    assert_eq!(result.0, exchange);
+    assert_eq!(result.1.salt, Some(salt));
+}
+
+#[test]
+fn test_key_confirmation_salt_mismatch() {
+    let key = [3u8; 32];
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+
+    // Each peer was given a different salt
+    let handle = thread::spawn(move || {
+        let salt = SessionSalt([1u8; 32]);
+        Protocol::confirm_peer(
+            &mut senderstream,
+            "id",
+            Some(&salt),
+            Direction::Sender,
+            &key,
+        )
+        .unwrap_err()
+        .downcast::<PortalError>()
+        .unwrap()
+    });
+
+    let salt = SessionSalt([2u8; 32]);
+    let result = Protocol::confirm_peer(
+        &mut receiverstream,
+        "id",
+        Some(&salt),
+        Direction::Receiver,
+        &key,
+    )
+    .unwrap_err()
+    .downcast::<PortalError>()
+    .unwrap();
+
+    // Both sides detect the mismatch
+    assert_eq!(*result, PortalError::PeerKeyMismatch);
+    assert_eq!(*handle.join().unwrap(), PortalError::PeerKeyMismatch);
+}
+
+#[test]
+fn test_key_confirmation_offers() {
+    let key = [3u8; 32];
+    let salt = SessionSalt([1u8; 32]);
+    let confirm = |sender_saw: Offers, receiver_saw: Offers| {
+        let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+        let handle = thread::spawn(move || {
+            Protocol::confirm_peer_with(
+                &mut senderstream,
+                "id",
+                Some(&salt),
+                Some(&sender_saw),
+                Direction::Sender,
+                &key,
+            )
+            .map_err(|e| *e.downcast::<PortalError>().unwrap())
+        });
+        let result = Protocol::confirm_peer_with(
+            &mut receiverstream,
+            "id",
+            Some(&salt),
+            Some(&receiver_saw),
+            Direction::Receiver,
+            &key,
+        )
+        .map_err(|e| *e.downcast::<PortalError>().unwrap());
+        (handle.join().unwrap(), result)
+    };
+
+    // Both peers saw the same offers
+    let all = Capabilities::DEDUP.with(Capabilities::BIND_CHUNKS, true);
+    let offer = |format, codec| Offer {
+        format,
+        codec,
+        capabilities: Some(all),
+    };
+    let json = offer(WireFormat::Json, CodecVersion::V2);
+    let offers = Offers::new(Direction::Sender, json, json);
+    assert_eq!(confirm(offers, offers), (Ok(()), Ok(())));
+
+    // The relay told the Receiver the Sender only offered bincode
+    let stripped = Offers::new(
+        Direction::Receiver,
+        json,
+        offer(WireFormat::Bincode, CodecVersion::V2),
+    );
+    assert_eq!(
+        confirm(offers, stripped),
+        (
+            Err(PortalError::PeerKeyMismatch),
+            Err(PortalError::PeerKeyMismatch)
+        )
+    );
+
+    // Or that the Receiver only offered the v1 codec
+    let stripped = Offers::new(
+        Direction::Sender,
+        json,
+        offer(WireFormat::Json, CodecVersion::V1),
+    );
+    assert_eq!(
+        confirm(stripped, offers),
+        (
+            Err(PortalError::PeerKeyMismatch),
+            Err(PortalError::PeerKeyMismatch)
+        )
+    );
+
+    // Or that the Sender didn't offer deduplication
+    let stripped = Offers::new(
+        Direction::Receiver,
+        json,
+        Offer {
+            capabilities: Some(Capabilities::BIND_CHUNKS),
+            ..json
+        },
+    );
+    assert_eq!(
+        confirm(offers, stripped),
+        (
+            Err(PortalError::PeerKeyMismatch),
+            Err(PortalError::PeerKeyMismatch)
+        )
+    );
+
+    // Or to bind chunks to their files
+    let stripped = Offers::new(
+        Direction::Sender,
+        json,
+        Offer {
+            capabilities: Some(Capabilities::DEDUP),
+            ..json
+        },
+    );
+    assert_eq!(
+        confirm(stripped, offers),
+        (
+            Err(PortalError::PeerKeyMismatch),
+            Err(PortalError::PeerKeyMismatch)
+        )
+    );
+
+    // Or withheld the Sender's capabilities from the Receiver
+    let stripped = Offers::new(
+        Direction::Receiver,
+        json,
+        Offer {
+            capabilities: None,
+            ..json
+        },
+    );
+    assert_eq!(
+        confirm(offers, stripped),
+        (
+            Err(PortalError::PeerKeyMismatch),
+            Err(PortalError::PeerKeyMismatch)
+        )
+    );
+
+    // Or withheld both peers' capabilities from each other
+    let withheld = Offer {
+        capabilities: None,
+        ..json
+    };
+    assert_eq!(
+        confirm(
+            Offers::new(Direction::Sender, json, withheld),
+            Offers::new(Direction::Receiver, json, withheld)
+        ),
+        (
+            Err(PortalError::PeerKeyMismatch),
+            Err(PortalError::PeerKeyMismatch)
+        )
+    );
+}
+
+#[test]
+fn test_wire_format_negotiation() {
+    assert_eq!(
+        WireFormat::negotiate(WireFormat::Json, WireFormat::Json),
+        WireFormat::Json
+    );
+    assert_eq!(
+        WireFormat::negotiate(WireFormat::Json, WireFormat::Cbor),
+        WireFormat::Bincode
+    );
+    assert_eq!(
+        WireFormat::negotiate(WireFormat::Bincode, WireFormat::Cbor),
+        WireFormat::Bincode
+    );
+}
+
+#[test]
+fn test_encrypted_object_roundtrip() {
+    let key = [7u8; 32];
+    let metadata = Metadata {
+        filesize: 1337,
+        filename: "file.txt".into(),
+        ..Default::default()
+    };
+
+    let mut formats = vec![WireFormat::Bincode];
+    if cfg!(feature = "json") {
+        formats.push(WireFormat::Json);
+    }
+    if cfg!(feature = "cbor") {
+        formats.push(WireFormat::Cbor);
+    }
+
+    for format in formats {
+        let mut stream = SyncMockStream::new();
+        let mut nseq = NonceSequence::new();
+        Protocol::encrypt_and_write_object(
+            &mut stream,
+            &key,
+            &mut nseq,
+            CodecVersion::V1,
+            format,
+            &metadata,
+        )
+        .unwrap();
+
+        let written = stream.pop_bytes_written();
+        stream.push_bytes_to_read(&written);
+        let got: Metadata =
+            Protocol::read_encrypted_from(&mut stream, &key, CodecVersion::V1, format).unwrap();
+        assert_eq!(got, metadata);
+    }
+}
+
+#[test]
+fn test_wire_format_unsupported() {
+    let metadata = Metadata::default();
+    for format in [WireFormat::Json, WireFormat::Cbor] {
+        if format.is_supported() {
+            continue;
+        }
+        let result = format
+            .serialize(&metadata)
+            .unwrap_err()
+            .downcast::<PortalError>()
+            .unwrap();
+        assert_eq!(*result, PortalError::SerializeError);
+    }
+
+    let mut portal = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+    let result = portal.set_wire_format(WireFormat::Cbor);
+    assert_eq!(result.is_ok(), cfg!(feature = "cbor"));
+}
+
+/// Helper: run a strict connect as the Receiver against queued relay messages
+fn connect_strict_with(messages: &[PortalMessage]) -> Result<PeerInfo, Box<dyn std::error::Error>> {
+    let mut stream = SyncMockStream::new();
+    for message in messages {
+        stream.push_bytes_to_read(&bincode::serialize(message).unwrap());
+    }
+
+    let request = ConnectMessage {
+        id: "id".to_string(),
+        direction: Direction::Receiver,
+    };
+    let options = ConnectOptions::default();
+    Protocol::connect_strict(
+        &mut stream,
+        request,
+        options,
+        vec![0u8; 33].try_into().unwrap(),
+    )
+    .map(|(_, info)| info)
+}
+
+#[test]
+fn test_connect_strict() {
+    let salt = SessionSalt::generate();
+    let paired = |id: &str, direction| {
+        PortalMessage::Paired(PairedMessage {
+            peer: ConnectMessage {
+                id: id.to_string(),
+                direction,
+            },
+            options: ConnectOptions::default(),
+            salt,
+        })
+    };
+    let exchange = PortalMessage::KeyExchange(vec![1u8; 33].try_into().unwrap());
+
+    // The expected ordering succeeds
+    let info = connect_strict_with(&[paired("id", Direction::Sender), exchange.clone()]).unwrap();
+    assert_eq!(info.salt, Some(salt));
+
+    // Out of order
+    let result = connect_strict_with(&[exchange.clone(), paired("id", Direction::Sender)]);
+    assert_err!(
+        result.err().unwrap().downcast_ref::<PortalError>(),
+        Some(PortalError::UnexpectedMessage)
+    );
+
+    // Paired with the wrong ID, or with another Receiver
+    for bad in [
+        paired("other", Direction::Sender),
+        paired("id", Direction::Receiver),
+    ] {
+        let result = connect_strict_with(&[bad, exchange.clone()]);
+        assert_err!(
+            result.err().unwrap().downcast_ref::<PortalError>(),
+            Some(PortalError::PeerMismatch)
+        );
+    }
+
+    // Oversized peer information
+    let huge = "a".repeat(MAX_HANDSHAKE_MESSAGE_SIZE as usize);
+    let result = connect_strict_with(&[paired(&huge, Direction::Sender), exchange]);
+    assert_err!(
+        result.err().unwrap().downcast_ref::<PortalError>(),
+        Some(PortalError::MessageTooLarge)
+    );
+}
+
+#[test]
+fn test_connect_relay_banner() {
+    let paired = PortalMessage::Paired(PairedMessage {
+        peer: ConnectMessage {
+            id: "id".to_string(),
+            direction: Direction::Sender,
+        },
+        options: ConnectOptions::default(),
+        salt: SessionSalt::generate(),
+    });
+    let exchange = PortalMessage::KeyExchange(vec![1u8; 33].try_into().unwrap());
+    let banner = RelayBanner::new(Some(1024));
+    let hello = |banner| PortalMessage::RelayHello(banner);
+
+    // The banner precedes pairing & is optional
+    let info = connect_strict_with(&[hello(banner), paired.clone(), exchange.clone()]).unwrap();
+    assert_eq!(info.relay, Some(banner));
+    let info = connect_strict_with(&[paired.clone(), exchange.clone()]).unwrap();
+    assert_eq!(info.relay, None);
+
+    // Only a single banner is accepted
+    let result = connect_strict_with(&[hello(banner), hello(banner), paired.clone()]);
+    assert_err!(
+        result.err().unwrap().downcast_ref::<PortalError>(),
+        Some(PortalError::UnexpectedMessage)
+    );
+
+    // Version skew is reported before anything else is read
+    let newer = RelayBanner {
+        min_version: PROTOCOL_VERSION + 1,
+        ..banner
+    };
+    let older = RelayBanner {
+        version: MIN_RELAY_VERSION - 1,
+        ..banner
+    };
+    for (banner, expected) in [
+        (newer, PortalError::RelayTooNew(PROTOCOL_VERSION + 1)),
+        (older, PortalError::RelayTooOld(MIN_RELAY_VERSION - 1)),
+    ] {
+        let result = connect_strict_with(&[hello(banner)]);
+        let err = result.unwrap_err().downcast::<PortalError>().unwrap();
+        assert_eq!(*err, expected);
+    }
+}
+
+#[test]
+fn test_parse_strict_trailing_bytes() {
+    let message = PortalMessage::RelayControl(RelayControl::Ack);
+    let mut data = bincode::serialize(&message).unwrap();
+    assert_eq!(PortalMessage::parse_strict(&data, 64).unwrap(), message);
+
+    data.push(0);
+    let result = PortalMessage::parse_strict(&data, 64);
+    assert_err!(
+        result.err().unwrap().downcast_ref::<PortalError>(),
+        Some(PortalError::TrailingBytes)
+    );
+}
+
+#[test]
+fn test_authenticated_connect() {
+    let secrets = vec![b"old secret".to_vec(), b"relay secret".to_vec()];
+    let request = ConnectMessage {
+        id: "id".to_string(),
+        direction: Direction::Sender,
+    };
+
+    // Any of the relay's secrets is accepted
+    let auth = AuthenticatedConnect::new(request.clone(), b"relay secret").unwrap();
+    assert!(auth.verify(&secrets));
+    assert!(!auth.verify(&[b"another secret".to_vec()]));
+
+    // The token is bound to the request & its timestamp
+    let mut tampered = auth.clone();
+    tampered.connect.id = "other".to_string();
+    assert!(!tampered.verify(&secrets));
+
+    let mut stale = AuthenticatedConnect::new(request.clone(), b"relay secret").unwrap();
+    stale.timestamp -= MAX_AUTH_SKEW + 1;
+    assert!(!stale.verify(&secrets));
+
+    // A Portal with a secret presents the token in place of a plain Connect
+    let mut stream = SyncMockStream::new();
+    let _ = Protocol::connect_with(
+        &mut stream,
+        request,
+        ConnectOptions::default(),
+        Some(b"relay secret"),
+        None,
+        vec![0u8; 33].try_into().unwrap(),
+    );
+    match PortalMessage::parse(&stream.pop_bytes_written()).unwrap() {
+        PortalMessage::AuthConnect(sent) => assert!(sent.verify(&secrets)),
+        other => panic!("expected AuthConnect but got {:?}", other),
+    }
+}
+
+#[test]
+fn test_connect_redirect() {
+    let relays = vec!["relay2:13265".to_string(), "relay3:13265".to_string()];
+    let redirect = PortalMessage::RelayControl(RelayControl::Redirect(relays.clone()));
+    let expected = PortalError::Redirected(relays);
+
+    // The alternates are returned to the caller instead of continuing
+    let mut stream = SyncMockStream::new();
+    stream.push_bytes_to_read(&bincode::serialize(&redirect).unwrap());
+    let result = Protocol::connect(
+        &mut stream,
+        ConnectMessage {
+            id: "id".to_string(),
+            direction: Direction::Sender,
+        },
+        ConnectOptions::default(),
+        vec![0u8; 33].try_into().unwrap(),
+    );
+    assert_eq!(
+        result.err().unwrap().downcast_ref::<PortalError>(),
+        Some(&expected)
+    );
+
+    // Likewise in strict mode
+    let result = connect_strict_with(&[redirect]);
+    assert_eq!(
+        result.err().unwrap().downcast_ref::<PortalError>(),
+        Some(&expected)
+    );
+}
+
+#[test]
+fn test_connect_session_claimed() {
+    let claimed =
+        PortalMessage::RelayControl(RelayControl::Error(RelayControlError::AlreadyPaired));
+
+    // A Receiver arriving after another has paired is told so
+    let mut stream = SyncMockStream::new();
+    stream.push_bytes_to_read(&bincode::serialize(&claimed).unwrap());
+    let result = Protocol::connect(
+        &mut stream,
+        ConnectMessage {
+            id: "id".to_string(),
+            direction: Direction::Receiver,
+        },
+        ConnectOptions::default(),
+        vec![0u8; 33].try_into().unwrap(),
+    );
+    assert_eq!(
+        result.err().unwrap().downcast_ref::<PortalError>(),
+        Some(&PortalError::SessionClaimed)
+    );
+
+    // Likewise in strict mode
+    let result = connect_strict_with(&[claimed]);
+    assert_eq!(
+        result.err().unwrap().downcast_ref::<PortalError>(),
+        Some(&PortalError::SessionClaimed)
+    );
+}
+
+#[test]
+fn test_read_encrypted_transfer_limit() {
+    let mut stream = SyncMockStream::new();
+
+    // The relay's notice arrives where the next chunk was expected
+    let message = PortalMessage::TransferLimit(1024);
+    stream.push_bytes_to_read(&bincode::serialize(&message).unwrap());
+
+    let mut storage = vec![0u8; 1024];
+    let result =
+        Protocol::read_encrypted_zero_copy(&mut stream, &[0u8; 32], CodecVersion::V1, &mut storage);
+    assert_eq!(
+        result.err().unwrap().downcast_ref::<PortalError>(),
+        Some(&PortalError::TransferLimit(1024))
+    );
+}
+
+#[test]
+fn test_read_encrypted_session_expired() {
+    let mut stream = SyncMockStream::new();
+
+    // The relay's notice arrives where the next chunk was expected
+    let message = PortalMessage::SessionExpired(3600);
+    stream.push_bytes_to_read(&bincode::serialize(&message).unwrap());
+
+    let mut storage = vec![0u8; 1024];
+    let result =
+        Protocol::read_encrypted_zero_copy(&mut stream, &[0u8; 32], CodecVersion::V1, &mut storage);
+    assert_eq!(
+        result.err().unwrap().downcast_ref::<PortalError>(),
+        Some(&PortalError::SessionExpired(3600))
+    );
+}
+
+#[test]
+fn test_codec_negotiation() {
+    use CodecVersion as V;
+    assert_eq!(V::negotiate(V::V2, V::V2), V::V2);
+    assert_eq!(V::negotiate(V::V2, V::V1), V::V1);
+    assert_eq!(V::negotiate(V::V1, V::V2), V::V1);
+
+    // Newer peers are met at our newest version, peers without one at v1
+    assert_eq!(V::negotiate(V::LATEST, CodecVersion(200)), V::LATEST);
+    assert_eq!(V::negotiate(V::LATEST, CodecVersion(0)), V::V1);
+    assert!(CodecVersion(200).codec().is_err());
+}
+
+/// Helper: a v2 name, or the payload of a string
+fn tlv_name(out: &mut Vec<u8>, name: &str) {
+    out.push(name.len() as u8);
+    out.extend_from_slice(name.as_bytes());
+}
+
+/// Helper: a ConnectMessage in the v2 layout, with an extra field
+fn tlv_connect(extra: &[u8]) -> Vec<u8> {
+    let mut value = vec![0x0c];
+    tlv_name(&mut value, "Connect");
+    value.extend([0x0b, 3]);
+    tlv_name(&mut value, "direction");
+    value.push(0x0c);
+    tlv_name(&mut value, "Sender");
+    value.push(0x00);
+
+    // Fields are matched by name, in any order
+    tlv_name(&mut value, "future");
+    value.extend_from_slice(extra);
+    tlv_name(&mut value, "id");
+    value.push(0x07);
+    tlv_name(&mut value, "id");
+
+    let mut frame = (value.len() as u32).to_be_bytes().to_vec();
+    frame.extend(value);
+    frame
+}
+
+#[test]
+fn test_tlv_evolution() {
+    // Fields added by newer peers are skipped
+    let frame = tlv_connect(&[0x09, 2, 0x03, 1, 0x07, 1, b'x']);
+    let expected = PortalMessage::Connect(ConnectMessage {
+        id: "id".into(),
+        direction: Direction::Sender,
+    });
+    let decoded = CodecVersion::V2.decode(&mut &frame[..], MAX_HANDSHAKE_MESSAGE_SIZE);
+    assert_eq!(decoded.unwrap(), expected);
+
+    // But only so deep
+    let mut nested = [0x09, 1].repeat(64);
+    nested.push(0x00);
+    let frame = tlv_connect(&nested);
+    let decoded = CodecVersion::V2.decode(&mut &frame[..], MAX_HANDSHAKE_MESSAGE_SIZE);
+    assert_err!(
+        decoded.unwrap_err().downcast_ref::<PortalError>(),
+        Some(PortalError::BadMsg)
+    );
+
+    // Anything after the value in a frame is rejected
+    let mut frame = tlv_connect(&[0x00]);
+    frame[3] += 1;
+    frame.push(0x00);
+    let decoded = CodecVersion::V2.decode(&mut &frame[..], MAX_HANDSHAKE_MESSAGE_SIZE);
+    assert_err!(
+        decoded.unwrap_err().downcast_ref::<PortalError>(),
+        Some(PortalError::TrailingBytes)
+    );
+}
+
+#[test]
+fn test_codec_detection() {
+    // Every first message a relay accepts, in either version
+    let connect = ConnectMessage {
+        id: "id".into(),
+        direction: Direction::Receiver,
+    };
+    let requests = [
+        PortalMessage::Connect(connect.clone()),
+        PortalMessage::AuthConnect(AuthenticatedConnect::new(connect, b"secret").unwrap()),
+        PortalMessage::RelayControl(RelayControl::Cancel("id".into())),
+    ];
+    for msg in requests.iter() {
+        for version in [CodecVersion::V1, CodecVersion::V2] {
+            let data = version.encode(msg).unwrap();
+            assert_eq!(CodecVersion::detect(&data), Some(version));
+            assert!(CodecVersion::is_complete(&data));
+            let decoded = version.decode(&mut &data[..], MAX_HANDSHAKE_MESSAGE_SIZE);
+            assert_eq!(&decoded.unwrap(), msg);
+        }
+    }
+
+    // A frame is only complete once all of it has arrived
+    let frame = tlv_connect(&[0x00]);
+    assert!(!CodecVersion::is_complete(&frame[..3]));
+    assert!(!CodecVersion::is_complete(&frame[..frame.len() - 1]));
+    assert!(CodecVersion::is_complete(&frame));
+}
+
+#[test]
+fn test_read_encrypted_v2() {
+    let key = [5u8; 32];
+    let mut nseq = NonceSequence::new();
+    let mut wire = Vec::new();
+    let metadata = Metadata {
+        filesize: 6,
+        filename: "v2.txt".into(),
+        ..Default::default()
+    };
+    let codec = CodecVersion::V2;
+    Protocol::encrypt_and_write_object(
+        &mut wire,
+        &key,
+        &mut nseq,
+        codec,
+        WireFormat::Bincode,
+        &metadata,
+    )
+    .unwrap();
+    codec
+        .send(&PortalMessage::TransferLimit(64), &mut wire)
+        .unwrap();
+
+    // The header is framed as v2, so can't be read as v1
+    assert!(Protocol::read_encrypted_from::<_, Metadata>(
+        &mut &wire[..],
+        &key,
+        CodecVersion::V1,
+        WireFormat::Bincode
+    )
+    .is_err());
+
+    let mut reader = &wire[..];
+    let got: Metadata =
+        Protocol::read_encrypted_from(&mut reader, &key, codec, WireFormat::Bincode).unwrap();
+    assert_eq!(got, metadata);
+
+    // A relay's notice is understood in the session's codec
+    let mut storage = [0u8; 64];
+    let result = Protocol::read_encrypted_zero_copy(&mut reader, &key, codec, &mut storage);
+    assert_eq!(
+        result.err().unwrap().downcast_ref::<PortalError>(),
+        Some(&PortalError::TransferLimit(64))
+    );
+}
+
+/// A connection to the relay that drops after `limit` bytes have been read
+struct Dropping {
+    data: io::Cursor<Vec<u8>>,
+    limit: usize,
+    written: std::sync::Arc<std::sync::Mutex<Vec<u8>>>,
+}
+
+impl Read for Dropping {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let pos = self.data.position() as usize;
+        if pos >= self.limit {
+            return Err(io::ErrorKind::ConnectionReset.into());
+        }
+        let len = buf.len().min(self.limit - pos);
+        self.data.read(&mut buf[..len])
+    }
+}
+
+impl Write for Dropping {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.written.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_resume_tokens() {
+    let salt = SessionSalt::generate();
+    let token = salt.resume_token("id");
+    assert!(salt.verify_resume("id", &token));
+    assert!(!salt.verify_resume("other", &token));
+    assert!(!SessionSalt::generate().verify_resume("id", &token));
+}
+
+#[test]
+fn test_reconnecting_resumes() {
+    let salt = SessionSalt::generate();
+    let token = salt.resume_token("id");
+    let requests = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+
+    // The first connection drops 4 bytes in, the relay then acks the
+    // resume & sends the rest
+    let first = Dropping {
+        data: io::Cursor::new(b"0123".to_vec()),
+        limit: 4,
+        written: Default::default(),
+    };
+    let mut rest = PortalMessage::RelayControl(RelayControl::Ack)
+        .to_bytes()
+        .unwrap();
+    rest.extend_from_slice(b"456789");
+    let written = requests.clone();
+    let mut stream = Reconnecting::new(first).with_reconnect(Box::new(move || {
+        Ok(Dropping {
+            data: io::Cursor::new(rest.clone()),
+            limit: usize::MAX,
+            written: written.clone(),
+        })
+    }));
+
+    // Unarmed, the error is returned as is
+    let mut buf = [0u8; 10];
+    stream.read_exact(&mut buf[..4]).unwrap();
+    assert_eq!(
+        stream.read(&mut buf).unwrap_err().kind(),
+        io::ErrorKind::ConnectionReset
+    );
+
+    // Armed, the session is resumed where it left off
+    stream.arm("id", token);
+    stream.read_exact(&mut buf[4..]).unwrap();
+    assert_eq!(&buf, b"0123456789");
+    assert_eq!(stream.received(), 10);
+
+    // Having presented the bytes read & token
+    let request = PortalMessage::parse(&requests.lock().unwrap()).unwrap();
+    assert_eq!(
+        request,
+        PortalMessage::RelayControl(RelayControl::Resume {
+            id: "id".into(),
+            received: 4,
+            token,
+        })
+    );
+}
@@ -1,15 +1,238 @@
 use crate::errors::PortalError::*;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::error::Error;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 
-/// Metadata about the transfer to be exchanged
-/// between peers after key derivation (encrypted)
-#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Default)]
-pub struct Metadata {
-    //pub id: u32,
+/// Maximum size of a preview attached to a single file
+pub const MAX_PREVIEW_SIZE: usize = 16 * 1024;
+
+/// Maximum size of the sender's note
+pub const MAX_NOTE_SIZE: usize = 256;
+
+/// How a received file was written to disk
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default)]
+pub enum WriteStrategy {
+    /// The file was allocated up front and mapped into memory
+    #[default]
+    Mapped,
+    /// Allocation or mapping failed, chunks were streamed
+    /// through a buffered writer instead
+    Buffered,
+}
+
+/// What a sender recorded about a local file when it was added to a
+/// transfer, to notice if it changes before it is sent
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct FileStamp {
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+
+    /// SHA-256 of the file's first & last [`crate::CHUNK_SIZE`] bytes,
+    /// if requested with [`TransferInfo::add_quick_hashes`]
+    pub quick_hash: Option<[u8; 32]>,
+}
+
+impl FileStamp {
+    /// Stamp an open file, taking its quick hash if requested
+    pub fn of(file: &mut File, quick_hash: bool) -> Result<FileStamp, Box<dyn Error>> {
+        let metadata = file.metadata()?;
+        let size = metadata.len();
+        let quick_hash = match quick_hash {
+            true => Some(quick_hash_file(file, size)?),
+            false => None,
+        };
+        Ok(FileStamp {
+            size,
+            modified: metadata.modified().ok(),
+            quick_hash,
+        })
+    }
+}
+
+/// What kind of data an entry holds
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Copy, Clone, Default)]
+pub enum FileKind {
+    /// A file of known size
+    #[default]
+    Regular,
+    /// The file is still growing, its size is unknown and the data ends
+    /// with an end-of-stream marker instead, see [`crate::Portal::tail_file`]
+    Stream,
+}
+
+/// A file in a transfer, exchanged between peers after key derivation
+/// (encrypted). Sent as part of the [`TransferInfo`] & again before the
+/// file's data.
+///
+/// Entries are encoded as a versioned layout, see [`VersionedEntry`].
+/// Fields added later are added in a new version, which older peers
+/// reject rather than misread.
+#[derive(Serialize, Deserialize, Eq, Debug, Clone, Default)]
+#[serde(from = "VersionedEntry", into = "VersionedEntry")]
+pub struct FileEntry {
+    pub filesize: u64,
+
+    /// Path of the file relative to the transfer, with components
+    /// separated by `/`. Receivers only use the final component
+    pub filename: String,
+
+    /// Whether the size is known in advance
+    pub kind: FileKind,
+
+    /// Names of byte-identical files in the same transfer. Their content
+    /// is only sent once, the receiver creates the copies locally
+    pub copies: Vec<String>,
+
+    /// An optional preview of the file, such as its first few KB or a
+    /// thumbnail, for receivers to inspect before accepting the transfer
+    pub preview: Option<Vec<u8>>,
+
+    /// The largest chunk the sender may use for this file, at most
+    /// [`crate::MAX_CHUNK_SIZE`]. Zero means [`crate::CHUNK_SIZE`].
+    pub max_chunk: u32,
+
+    /// Chunks of this file are content-defined, & may refer to data sent
+    /// earlier in the session instead, see [`crate::Portal::set_dedup`]
+    pub dedup: bool,
+
+    /// Unix permission bits of the sender's file, if known
+    pub mode: Option<u32>,
+
+    /// Modification time of the sender's file, in seconds since the Unix epoch
+    pub modified: Option<u64>,
+
+    /// SHA-256 digest of the file's contents, if known
+    pub digest: Option<[u8; 32]>,
+
+    /// The media type of the file, i.e. "image/png", if the sender set one
+    pub mime: Option<String>,
+
+    /// Local diagnostics only, set by recv_file. Not sent to the peer
+    pub strategy: WriteStrategy,
+}
+
+/// Former name of [`FileEntry`]
+pub type Metadata = FileEntry;
+
+/// Local diagnostics are ignored when comparing entries, as are the
+/// attributes only sent along with the TransferInfo
+impl PartialEq for FileEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.filesize == other.filesize
+            && self.filename == other.filename
+            && self.kind == other.kind
+    }
+}
+
+impl FileEntry {
+    /// Describe a regular file, with its size, mode & modification time
+    pub fn of(file: &File, filename: &str) -> Result<FileEntry, Box<dyn Error>> {
+        let metadata = file.metadata()?;
+        Ok(FileEntry {
+            filesize: metadata.len(),
+            filename: filename.to_string(),
+            mode: file_mode(&metadata),
+            modified: metadata
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_secs()),
+            ..Default::default()
+        })
+    }
+
+    /// Returns true if the size isn't known in advance
+    pub fn is_stream(&self) -> bool {
+        self.kind == FileKind::Stream
+    }
+
+    /// The largest chunk that may be received for this file
+    pub fn chunk_limit(&self) -> Result<usize, Box<dyn Error>> {
+        match self.max_chunk as usize {
+            0 => Ok(crate::CHUNK_SIZE),
+            n if n <= crate::MAX_CHUNK_SIZE => Ok(n),
+            _ => Err(MessageTooLarge.into()),
+        }
+    }
+}
+
+/// Helper: the permission bits of a file, on platforms that have them
+#[cfg(unix)]
+fn file_mode(metadata: &std::fs::Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    Some(metadata.permissions().mode() & 0o7777)
+}
+
+#[cfg(not(unix))]
+fn file_mode(_metadata: &std::fs::Metadata) -> Option<u32> {
+    None
+}
+
+/// Every layout of a [`FileEntry`] on the wire. A new layout is added as
+/// a new variant, converted into a FileEntry with defaults for the fields
+/// it lacks, & sent only once peers that can't read it are unsupported.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum VersionedEntry {
+    V1(FileEntryV1),
+}
+
+/// The first layout of a [`FileEntry`]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct FileEntryV1 {
     pub filesize: u64,
     pub filename: String,
+    pub kind: FileKind,
+    pub copies: Vec<String>,
+    pub preview: Option<Vec<u8>>,
+    pub max_chunk: u32,
+    pub dedup: bool,
+    pub mode: Option<u32>,
+    pub modified: Option<u64>,
+    pub digest: Option<[u8; 32]>,
+    pub mime: Option<String>,
+}
+
+impl From<VersionedEntry> for FileEntry {
+    fn from(entry: VersionedEntry) -> FileEntry {
+        match entry {
+            VersionedEntry::V1(v1) => FileEntry {
+                filesize: v1.filesize,
+                filename: v1.filename,
+                kind: v1.kind,
+                copies: v1.copies,
+                preview: v1.preview,
+                max_chunk: v1.max_chunk,
+                dedup: v1.dedup,
+                mode: v1.mode,
+                modified: v1.modified,
+                digest: v1.digest,
+                mime: v1.mime,
+                strategy: WriteStrategy::default(),
+            },
+        }
+    }
+}
+
+impl From<FileEntry> for VersionedEntry {
+    fn from(entry: FileEntry) -> VersionedEntry {
+        VersionedEntry::V1(FileEntryV1 {
+            filesize: entry.filesize,
+            filename: entry.filename,
+            kind: entry.kind,
+            copies: entry.copies,
+            preview: entry.preview,
+            max_chunk: entry.max_chunk,
+            dedup: entry.dedup,
+            mode: entry.mode,
+            modified: entry.modified,
+            digest: entry.digest,
+            mime: entry.mime,
+        })
+    }
 }
 
 /// Contains the metadata for all files that will be sent
@@ -18,11 +241,26 @@ pub struct Metadata {
 pub struct TransferInfo {
     /// The metadata to send to the peer. These
     /// filenames are striped of their path information
-    pub all: Vec<Metadata>,
+    pub all: Vec<FileEntry>,
+
+    /// An optional note from the sender, such as who it is, to tell
+    /// transfers apart. Shown to the receiver before they accept, it is
+    /// chosen by the sender & only as trustworthy as the pass-phrase.
+    pub note: Option<String>,
 
     /// Internal state for a sender to locate files
     #[serde(skip)]
     pub localpaths: Vec<PathBuf>,
+
+    /// Content digests of local files, computed only when
+    /// another file of the same size is added
+    #[serde(skip)]
+    digests: Vec<Option<[u8; 32]>>,
+
+    /// Size & modification time of local files when they were added,
+    /// None for streams. Checked again before each file is sent.
+    #[serde(skip)]
+    pub(crate) stamps: Vec<Option<FileStamp>>,
 }
 
 /// Builder for TransferInfo
@@ -49,24 +287,203 @@ impl TransferInfo {
     pub fn empty() -> TransferInfo {
         TransferInfo {
             all: Vec::new(),
+            note: None,
             localpaths: Vec::new(),
+            digests: Vec::new(),
+            stamps: Vec::new(),
         }
     }
 
-    /// Add a file to this transfer
+    /// Add a file to this transfer. A file identical to one already
+    /// added is recorded as a copy of it rather than a new entry. Its
+    /// size & modification time are recorded, so that it isn't sent if
+    /// it changes after the receiver accepts the transfer.
     pub fn add_file<'a>(&'a mut self, path: &Path) -> Result<&'a mut TransferInfo, Box<dyn Error>> {
+        let mut file = File::open(path)?;
+        let stamp = FileStamp::of(&mut file, false)?;
+        let filesize = stamp.size;
+        let filename = path
+            .file_name()
+            .ok_or(BadFileName)?
+            .to_str()
+            .ok_or(BadFileName)?;
+
+        // Identical content is sent once, the receiver copies it locally
+        let mut digest = None;
+        if let Some(index) = self.find_duplicate(path, filesize, &mut digest)? {
+            self.all[index].copies.push(filename.to_string());
+            return Ok(self);
+        }
+
         self.localpaths.push(path.to_path_buf());
-        self.all.push(Metadata {
-            filesize: path.metadata()?.len(),
-            filename: path
-                .file_name()
-                .ok_or(BadFileName)?
-                .to_str()
-                .ok_or(BadFileName)?
-                .to_string(),
+        self.digests.push(digest);
+        self.stamps.push(Some(stamp));
+        self.all.push(FileEntry {
+            filesize,
+            ..FileEntry::of(&file, filename)?
         });
         Ok(self)
     }
+
+    /// Add an entry whose length isn't known in advance, to be sent under
+    /// `filename` with [`crate::Portal::send_stream`]. The path is only
+    /// kept for the sender to locate its source, such as a directory to
+    /// archive on the fly.
+    pub fn add_stream<'a>(
+        &'a mut self,
+        path: &Path,
+        filename: &str,
+    ) -> Result<&'a mut TransferInfo, Box<dyn Error>> {
+        if Path::new(filename)
+            .file_name()
+            .is_none_or(|s| s != filename)
+        {
+            return Err(BadFileName.into());
+        }
+        self.localpaths.push(path.to_path_buf());
+        self.digests.push(None);
+        self.stamps.push(None);
+        self.all.push(FileEntry {
+            filename: filename.to_string(),
+            kind: FileKind::Stream,
+            ..Default::default()
+        });
+        Ok(self)
+    }
+
+    /// Attach the first `len` bytes of each file as its preview. Useful
+    /// for text formats, where the head of the file is representative.
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    /// use portal_lib::TransferInfo;
+    ///
+    /// let mut info = TransferInfo::empty();
+    /// info.add_file(Path::new("/var/log/syslog")).unwrap();
+    /// info.add_head_previews(1024).unwrap();
+    /// ```
+    pub fn add_head_previews(&mut self, len: usize) -> Result<&mut TransferInfo, Box<dyn Error>> {
+        self.add_previews(|path| {
+            let mut head = Vec::with_capacity(len);
+            File::open(path)
+                .ok()?
+                .take(len as u64)
+                .read_to_end(&mut head)
+                .ok()?;
+            Some(head)
+        })
+    }
+
+    /// Attach a preview generated by the callback to each file, i.e. an
+    /// image thumbnail. Files the callback returns None for have no preview.
+    /// Each preview must not exceed MAX_PREVIEW_SIZE.
+    pub fn add_previews<F>(&mut self, generate: F) -> Result<&mut TransferInfo, Box<dyn Error>>
+    where
+        F: Fn(&Path) -> Option<Vec<u8>>,
+    {
+        for (path, metadata) in self.localpaths.iter().zip(self.all.iter_mut()) {
+            let preview = generate(path);
+            if preview.as_ref().is_some_and(|p| p.len() > MAX_PREVIEW_SIZE) {
+                return Err(PreviewTooLarge.into());
+            }
+            metadata.preview = preview;
+        }
+        Ok(self)
+    }
+
+    /// Also record a hash of the head & tail of each file added so far,
+    /// to notice changes that keep the size & modification time. Only a
+    /// fraction of each file is read.
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    /// use portal_lib::TransferInfo;
+    ///
+    /// let mut info = TransferInfo::empty();
+    /// info.add_file(Path::new("/var/log/syslog")).unwrap();
+    /// info.add_quick_hashes().unwrap();
+    /// ```
+    pub fn add_quick_hashes(&mut self) -> Result<&mut TransferInfo, Box<dyn Error>> {
+        for (path, stamp) in self.localpaths.iter().zip(self.stamps.iter_mut()) {
+            if let Some(stamp) = stamp {
+                let size = stamp.size;
+                stamp.quick_hash = Some(quick_hash_file(&mut File::open(path)?, size)?);
+            }
+        }
+        Ok(self)
+    }
+
+    /// Attach a note for the receiver, i.e. "From: build server 7".
+    /// It must not exceed MAX_NOTE_SIZE.
+    pub fn set_note(&mut self, note: &str) -> Result<&mut TransferInfo, Box<dyn Error>> {
+        if note.len() > MAX_NOTE_SIZE {
+            return Err(NoteTooLarge.into());
+        }
+        self.note = Some(note.to_string());
+        Ok(self)
+    }
+
+    /// Helper: find an entry with the same content as the file at path.
+    /// The file's digest is stored in `digest`, if it had to be computed.
+    fn find_duplicate(
+        &mut self,
+        path: &Path,
+        filesize: u64,
+        digest: &mut Option<[u8; 32]>,
+    ) -> Result<Option<usize>, Box<dyn Error>> {
+        for index in 0..self.all.len() {
+            // Only files of the same size need to be hashed, streams
+            // have no content to compare yet
+            if self.all[index].filesize != filesize || self.all[index].is_stream() {
+                continue;
+            }
+
+            let theirs = match self.digests[index] {
+                Some(d) => d,
+                None => *self.digests[index].insert(digest_file(&self.localpaths[index])?),
+            };
+            let ours = match *digest {
+                Some(d) => d,
+                None => *digest.insert(digest_file(path)?),
+            };
+
+            if ours == theirs {
+                return Ok(Some(index));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Helper: SHA-256 digest of a file's contents
+fn digest_file(path: &Path) -> Result<[u8; 32], Box<dyn Error>> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; crate::CHUNK_SIZE];
+    loop {
+        match file.read(&mut buffer)? {
+            0 => break,
+            len => hasher.update(&buffer[..len]),
+        }
+    }
+    Ok(hasher.finalize().into())
+}
+
+/// Helper: SHA-256 of a file's size, first & last CHUNK_SIZE bytes
+fn quick_hash_file(file: &mut File, size: u64) -> Result<[u8; 32], Box<dyn Error>> {
+    let mut hasher = Sha256::new();
+    hasher.update(size.to_le_bytes());
+    let mut buffer = Vec::with_capacity(crate::CHUNK_SIZE);
+    let tail = size.saturating_sub(crate::CHUNK_SIZE as u64);
+    for offset in [0, tail] {
+        buffer.clear();
+        file.seek(SeekFrom::Start(offset))?;
+        file.by_ref()
+            .take(crate::CHUNK_SIZE as u64)
+            .read_to_end(&mut buffer)?;
+        hasher.update(&buffer);
+    }
+    Ok(hasher.finalize().into())
 }
 
 impl Default for TransferInfoBuilder {
@@ -102,6 +519,33 @@ impl TransferInfoBuilder {
         Ok(self)
     }
 
+    /// Attach the first `len` bytes of each file added so far as its preview
+    pub fn head_previews(mut self, len: usize) -> Result<TransferInfoBuilder, Box<dyn Error>> {
+        let _ = self.0.add_head_previews(len)?;
+        Ok(self)
+    }
+
+    /// Attach a preview generated by the callback to each file added so far
+    pub fn previews<F>(mut self, generate: F) -> Result<TransferInfoBuilder, Box<dyn Error>>
+    where
+        F: Fn(&Path) -> Option<Vec<u8>>,
+    {
+        let _ = self.0.add_previews(generate)?;
+        Ok(self)
+    }
+
+    /// Also hash the head & tail of each file added so far
+    pub fn quick_hashes(mut self) -> Result<TransferInfoBuilder, Box<dyn Error>> {
+        let _ = self.0.add_quick_hashes()?;
+        Ok(self)
+    }
+
+    /// Attach a note for the receiver
+    pub fn note(mut self, note: &str) -> Result<TransferInfoBuilder, Box<dyn Error>> {
+        let _ = self.0.set_note(note)?;
+        Ok(self)
+    }
+
     /// Finalize the builder into a TransferInfo object
     pub fn finalize(self) -> TransferInfo {
         self.0
@@ -1,42 +1,66 @@
 //! Provides primary tests for the PortalFile abstraction
 //!
-use crate::protocol::{EncryptedMessage, PortalMessage};
-use crate::{errors::PortalError, Direction, Portal, TransferInfo, TransferInfoBuilder};
-use crate::{NO_PROGRESS_CALLBACK, NO_VERIFY_CALLBACK};
+use crate::protocol::{
+    Capabilities, CodecVersion, ConnectMessage, ConnectOptions, EncryptedMessage, NonceSequence,
+    Offer, Offers, PairedMessage, PortalMessage, Protocol, RelayControl, RelayControlError,
+    SessionSalt, WireCodec, WriteStrategy,
+};
+use crate::{
+    errors::PortalError, passphrase, ChunkSizer, Direction, Durability, EncryptedChannel, Portal,
+    PortalBuilder, PortalUri, Priority, ReceivePolicy, Shard, Transcript, TransferInfo,
+    TransferInfoBuilder, TransferLimits, TransferPolicy, TransferTuning, WithPolicy,
+};
+use crate::{
+    CHUNK_SIZE, FINGERPRINT_WORDS, INTERFERENCE_THRESHOLD, MAX_CHUNK_SIZE, MAX_NOTE_SIZE,
+    MIN_CHUNK_SIZE, MMAP_WINDOW_SIZE, NO_PROGRESS_CALLBACK, NO_VERIFY_CALLBACK,
+};
 use mockstream::SyncMockStream;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::convert::TryInto;
 use std::fs::File;
-use std::io::{Read, Write};
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::Path;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::thread;
+use std::time::Duration;
 use tempdir::TempDir;
 
 pub struct MockTcpStream {
+    #[allow(dead_code)]
     pub id: Direction,
     pub waiting_for_write: Arc<AtomicUsize>,
     pub readbuf: SyncMockStream,
     pub write_done: Arc<AtomicUsize>,
     pub writebuf: SyncMockStream,
+
+    /// Set once the peer's end is dropped, after which reads of
+    /// an empty buffer return EOF like a closed socket's
+    pub peer_closed: Arc<AtomicBool>,
+    pub closed: Arc<AtomicBool>,
 }
 
 impl Read for MockTcpStream {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize, std::io::Error> {
-        // Blocking read, wait until data is available
-        while self.waiting_for_write.load(Ordering::Relaxed) == 0 {
-            std::thread::sleep(std::time::Duration::from_millis(10));
-        }
-
-        // Perform the read
-        let res = self.readbuf.read(buf)?;
-
-        // Subtract the amount read from the atomic
-        if self.waiting_for_write.load(Ordering::Relaxed) > res {
+        // Blocking read, wait until data is available. The writer pushes
+        // data before counting it, so the count wraps below zero while
+        // data read early is yet to be counted.
+        loop {
+            let waiting = self.waiting_for_write.load(Ordering::SeqCst);
+            if waiting == 0 && self.peer_closed.load(Ordering::SeqCst) {
+                return Ok(0);
+            }
+            if waiting == 0 || waiting > isize::MAX as usize {
+                std::thread::sleep(std::time::Duration::from_millis(10));
+                continue;
+            }
+
+            // Perform the read & subtract the amount read from the atomic
+            let res = self.readbuf.read(buf)?;
             self.waiting_for_write.fetch_sub(res, Ordering::SeqCst);
-        } else {
-            self.waiting_for_write.store(0, Ordering::SeqCst);
+            return Ok(res);
         }
-        Ok(res)
     }
 }
 
@@ -57,6 +81,12 @@ impl Write for MockTcpStream {
     }
 }
 
+impl Drop for MockTcpStream {
+    fn drop(&mut self) {
+        self.closed.store(true, Ordering::SeqCst);
+    }
+}
+
 impl MockTcpStream {
     pub fn channel() -> (Self, Self) {
         // Backing buffers
@@ -68,6 +98,8 @@ impl MockTcpStream {
         let recvbool = Arc::new(AtomicUsize::default());
         senderbool.store(0, Ordering::Relaxed);
         recvbool.store(0, Ordering::Relaxed);
+        let senderclosed = Arc::new(AtomicBool::new(false));
+        let recvclosed = Arc::new(AtomicBool::new(false));
 
         // Wrap in Mock type
         let senderstream = MockTcpStream {
@@ -76,6 +108,8 @@ impl MockTcpStream {
             readbuf: senderbuf.clone(),
             write_done: recvbool.clone(),
             writebuf: receiverbuf.clone(),
+            peer_closed: recvclosed.clone(),
+            closed: senderclosed.clone(),
         };
 
         let receiverstream = MockTcpStream {
@@ -84,12 +118,73 @@ impl MockTcpStream {
             readbuf: receiverbuf,
             write_done: senderbool,
             writebuf: senderbuf,
+            peer_closed: senderclosed,
+            closed: recvclosed,
         };
 
         (senderstream, receiverstream)
     }
 }
 
+/// Counts the allocations of threads that ask it to, to check that
+/// constant memory mode doesn't allocate while transferring
+struct CountingAllocator;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Allocations made by a thread while counting
+#[derive(Debug, Default, Clone, Copy)]
+struct Allocations {
+    count: usize,
+    largest: usize,
+}
+
+thread_local! {
+    static ALLOCATIONS: Cell<Option<Allocations>> = const { Cell::new(None) };
+}
+
+impl CountingAllocator {
+    fn record(size: usize) {
+        let _ = ALLOCATIONS.try_with(|counted| {
+            if let Some(mut allocations) = counted.get() {
+                allocations.count += 1;
+                allocations.largest = allocations.largest.max(size);
+                counted.set(Some(allocations));
+            }
+        });
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        Self::record(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        Self::record(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        Self::record(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// Helper: run `f`, counting the allocations it makes on this thread
+fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, Allocations) {
+    ALLOCATIONS.with(|counted| counted.set(Some(Allocations::default())));
+    let res = f();
+    let counted = ALLOCATIONS.with(|counted| counted.take()).unwrap();
+    (res, counted)
+}
+
 macro_rules! assert_err {
     ($expression:expr, $($pattern:tt)+) => {
         match $expression {
@@ -120,295 +215,2755 @@ fn handshake_suceeds() {
 
     receiver.handshake(&mut receiverstream).unwrap();
     sender_thread.join().unwrap();
+    assert!(receiver.get_rtt().is_some());
 }
 
 #[test]
-fn test_file_roundtrip() {
-    // Create test file
-    let tmp_dir = TempDir::new("test_recv_file_bad_outdir").unwrap();
-    let file_path = tmp_dir.path().join("randomfile.txt");
-    let file_path_str = Path::new(&file_path.to_str().unwrap().to_owned()).to_path_buf();
-    let mut tmp_file = File::create(file_path).unwrap();
-    writeln!(tmp_file, "Test File").unwrap();
+fn handshake_without_rtt_capability() {
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
 
-    // receiver
-    let dir = Direction::Receiver;
-    let pass = "test".to_string();
-    let mut receiver = Portal::init(dir, "id".to_string(), pass).unwrap();
+    // A Sender that doesn't measure the round-trip time, followed by a
+    // message the Receiver would take for the wrong answer to a Ping
+    let sender_thread = thread::spawn(move || {
+        let request = ConnectMessage {
+            id: sender.get_id().clone(),
+            direction: Direction::Sender,
+        };
+        let options = ConnectOptions {
+            codec: sender.get_codec(),
+            ..Default::default()
+        };
+        let capabilities = Capabilities::DEDUP.with(Capabilities::BIND_CHUNKS, true);
+        let (confirm, info) = Protocol::connect_with(
+            &mut senderstream,
+            request,
+            options,
+            None,
+            Some(capabilities),
+            sender.exchange,
+        )
+        .unwrap();
+        let state = sender.state.take().unwrap();
+        let key = Protocol::derive_key(state, &confirm).unwrap();
+        let ours = Offer {
+            format: options.format,
+            codec: options.codec,
+            capabilities: Some(capabilities),
+        };
+        let theirs = Offer {
+            format: info.format,
+            codec: info.codec,
+            capabilities: info.capabilities,
+        };
+        let offers = Offers::new(Direction::Sender, ours, theirs);
+        let id = sender.get_id();
+        let salt = info.salt.as_ref();
+        Protocol::confirm_peer_with(
+            &mut senderstream,
+            id,
+            salt,
+            Some(&offers),
+            Direction::Sender,
+            &key,
+        )
+        .unwrap();
+        PortalMessage::Pong(0).send(&mut senderstream).unwrap();
+    });
 
-    // sender
-    let dir = Direction::Sender;
-    let pass = "test".to_string();
-    let mut sender = Portal::init(dir, "id".to_string(), pass).unwrap();
+    // The Receiver completes the handshake without sending a Ping
+    receiver.handshake(&mut receiverstream).unwrap();
+    sender_thread.join().unwrap();
+    assert_eq!(receiver.get_rtt(), None);
+}
 
-    // mock channel
+#[test]
+fn handshake_session_fingerprint() {
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+    assert_eq!(sender.session_fingerprint(), None);
     let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
 
     let sender_thread = thread::spawn(move || {
-        // Complete handshake
         sender.handshake(&mut senderstream).unwrap();
+        sender.session_fingerprint().unwrap()
+    });
+    receiver.handshake(&mut receiverstream).unwrap();
 
-        // Send the file
-        let result = sender.send_file(&mut senderstream, &file_path_str, NO_PROGRESS_CALLBACK);
-        assert!(result.is_ok());
-        result.unwrap()
+    // Both peers see the same words, taken from the pass-phrase word list
+    let fingerprint = receiver.session_fingerprint().unwrap();
+    assert_eq!(sender_thread.join().unwrap(), fingerprint);
+    let words = fingerprint.split(passphrase::SEPARATOR).collect::<Vec<_>>();
+    assert_eq!(words.len(), FINGERPRINT_WORDS);
+    assert!(words.iter().all(|word| passphrase::is_word(word)));
+
+    // Different keys give different fingerprints
+    let mut other = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    other.set_key(vec![7u8; 32]);
+    assert_ne!(other.session_fingerprint().unwrap(), fingerprint);
+}
+
+#[test]
+fn handshake_negotiates_codec() {
+    for offered in [CodecVersion::V1, CodecVersion::LATEST] {
+        let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+        let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+        receiver.set_codec(offered).unwrap();
+        let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+
+        let sender_thread = thread::spawn(move || {
+            sender.handshake(&mut senderstream).unwrap();
+            let mut channel = sender.channel(&mut senderstream).unwrap();
+            channel.write_chunk(b"framed").unwrap();
+            sender.get_codec()
+        });
+
+        // Both use the older of the two offers for what follows
+        receiver.handshake(&mut receiverstream).unwrap();
+        let mut storage = [0u8; 16];
+        let mut channel = receiver.channel(&mut receiverstream).unwrap();
+        let len = channel.read_chunk(&mut storage).unwrap();
+        assert_eq!(&storage[..len], b"framed");
+        assert_eq!(receiver.get_codec(), offered);
+        assert_eq!(sender_thread.join().unwrap(), offered);
+    }
+
+    // Versions this build doesn't implement can't be offered
+    let mut portal = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+    assert!(portal.set_codec(CodecVersion(0)).is_err());
+    assert!(portal.set_codec(CodecVersion(9)).is_err());
+}
+
+#[test]
+fn test_channel_roundtrip() {
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+
+    let sender_thread = thread::spawn(move || {
+        sender.handshake(&mut senderstream).unwrap();
+
+        // Send non-file payloads of varying sizes
+        let mut channel = sender.channel(&mut senderstream).unwrap();
+        channel.write_chunk(b"row 1").unwrap();
+        channel.write_chunk(&[0xaa; CHUNK_SIZE]).unwrap();
     });
 
-    // Complete handshake
     receiver.handshake(&mut receiverstream).unwrap();
+    let mut channel = receiver.channel(&mut receiverstream).unwrap();
+    let mut storage = vec![0u8; CHUNK_SIZE];
 
-    // Receive the file
-    let metadata = receiver
-        .recv_file(
-            &mut receiverstream,
-            tmp_dir.path(),
-            None,
-            NO_PROGRESS_CALLBACK,
-        )
+    let len = channel.read_chunk(&mut storage).unwrap();
+    assert_eq!(&storage[..len], b"row 1");
+
+    let len = channel.read_chunk(&mut storage).unwrap();
+    assert_eq!(len, CHUNK_SIZE);
+    assert!(storage.iter().all(|b| *b == 0xaa));
+    sender_thread.join().unwrap();
+}
+
+#[test]
+fn test_channel_checksum_corruption() {
+    let key = [1u8; 32];
+    let mut nseq = NonceSequence::new();
+    let mut wire = Vec::new();
+    EncryptedChannel::new(&mut wire, &key, &mut nseq)
+        .with_checksums(true)
+        .write_chunk(b"over the radio")
         .unwrap();
 
-    // Wait for sending to complete
-    let sent_size = sender_thread.join().unwrap();
+    // Intact chunks are received as usual
+    let mut storage = [0u8; 64];
+    let mut reader = &wire[..];
+    let len = EncryptedChannel::new(&mut reader, &key, &mut nseq)
+        .with_checksums(true)
+        .read_chunk(&mut storage)
+        .unwrap();
+    assert_eq!(&storage[..len], b"over the radio");
 
-    // Compare sizes
-    assert_eq!(metadata.filesize, sent_size as u64);
+    // A flipped bit in the ciphertext is reported as corruption, not tampering
+    let last = wire.len() - 5;
+    wire[last] ^= 1;
+    let mut reader = &wire[..];
+    assert_err!(
+        EncryptedChannel::new(&mut reader, &key, &mut nseq)
+            .with_checksums(true)
+            .read_chunk(&mut storage)
+            .err()
+            .unwrap()
+            .downcast_ref::<PortalError>(),
+        Some(PortalError::Corrupted)
+    );
 }
 
 #[test]
-fn test_incoming_outgoing_roundtrip() {
-    // Create test file
-    let tmp_dir = TempDir::new("test_recv_file_bad_outdir").unwrap();
-    let file_path = tmp_dir.path().join("randomfile.txt");
-    let file_path_str = Path::new(&file_path.to_str().unwrap().to_owned()).to_path_buf();
-    let mut tmp_file = File::create(file_path).unwrap();
-    writeln!(tmp_file, "Test File").unwrap();
+fn test_channel_bound_chunks() {
+    let key = [1u8; 32];
+
+    // Helper: send the chunks of a file, returning each as it was framed
+    let send = |file: Option<u32>, chunks: &[&[u8]]| {
+        let mut nseq = NonceSequence::new();
+        let mut wire = Vec::new();
+        let mut channel = EncryptedChannel::new(&mut wire, &key, &mut nseq).with_file(file);
+        for chunk in chunks {
+            channel.write_chunk(chunk).unwrap();
+        }
+        let len = wire.len() / chunks.len();
+        wire.chunks(len).map(<[u8]>::to_vec).collect::<Vec<_>>()
+    };
 
-    // receiver
-    let dir = Direction::Receiver;
-    let pass = "test".to_string();
-    let mut receiver = Portal::init(dir, "id".to_string(), pass).unwrap();
+    // Helper: receive every chunk of a file, framed as given
+    let recv = |file: Option<u32>, framed: &[&Vec<u8>]| {
+        let wire = framed
+            .iter()
+            .flat_map(|f| f.iter().copied())
+            .collect::<Vec<_>>();
+        let mut nseq = NonceSequence::new();
+        let mut reader = &wire[..];
+        let mut channel = EncryptedChannel::new(&mut reader, &key, &mut nseq).with_file(file);
+        let mut storage = [0u8; 16];
+        let mut received = Vec::new();
+        for _ in framed {
+            let len = channel.read_chunk(&mut storage)?;
+            received.extend_from_slice(&storage[..len]);
+        }
+        Ok::<_, Box<dyn std::error::Error>>(received)
+    };
 
-    // sender
-    let dir = Direction::Sender;
-    let pass = "test".to_string();
-    let mut sender = Portal::init(dir, "id".to_string(), pass).unwrap();
+    // Without binding, a chunk of one file passes for a chunk of another
+    let first = send(None, &[b"aaaa", b"bbbb"]);
+    let second = send(None, &[b"cccc"]);
+    assert_eq!(recv(None, &[&second[0], &first[1]]).unwrap(), b"ccccbbbb");
+
+    // Bound chunks are only received in their own file, in order
+    let first = send(Some(0), &[b"aaaa", b"bbbb"]);
+    let second = send(Some(1), &[b"cccc"]);
+    assert_eq!(recv(Some(0), &[&first[0], &first[1]]).unwrap(), b"aaaabbbb");
+    assert_eq!(recv(Some(1), &[&second[0]]).unwrap(), b"cccc");
+    assert!(recv(Some(0), &[&second[0], &first[1]]).is_err());
+    assert!(recv(Some(1), &[&first[0]]).is_err());
+    assert!(recv(Some(0), &[&first[1], &first[0]]).is_err());
+    assert!(recv(None, &[&first[0]]).is_err());
+}
 
-    // mock channel
+/// Accepts at most `limit` bytes per vectored write, counting the calls
+struct VectoredWriter {
+    wire: Vec<u8>,
+    calls: usize,
+    limit: usize,
+}
+
+impl Write for VectoredWriter {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, std::io::Error> {
+        self.write_vectored(&[std::io::IoSlice::new(buf)])
+    }
+
+    fn write_vectored(&mut self, bufs: &[std::io::IoSlice]) -> Result<usize, std::io::Error> {
+        self.calls += 1;
+        let start = self.wire.len();
+        for buf in bufs {
+            let room = self.limit - (self.wire.len() - start);
+            self.wire.extend_from_slice(&buf[..buf.len().min(room)]);
+        }
+        Ok(self.wire.len() - start)
+    }
+
+    fn flush(&mut self) -> Result<(), std::io::Error> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_channel_vectored_writes() {
+    let key = [2u8; 32];
+    let mut nseq = NonceSequence::new();
+
+    for limit in [usize::MAX, 7] {
+        let mut writer = VectoredWriter {
+            wire: Vec::new(),
+            calls: 0,
+            limit,
+        };
+        EncryptedChannel::new(&mut writer, &key, &mut nseq)
+            .with_checksums(true)
+            .write_chunk(b"header, data & trailer")
+            .unwrap();
+
+        // Header, ciphertext & trailer are written in one call, or
+        // resumed correctly across short writes
+        let len = writer.wire.len();
+        assert_eq!(writer.calls, len.div_ceil(limit.min(len)));
+        let mut storage = [0u8; 64];
+        let mut reader = &writer.wire[..];
+        let len = EncryptedChannel::new(&mut reader, &key, &mut nseq)
+            .with_checksums(true)
+            .read_chunk(&mut storage)
+            .unwrap();
+        assert_eq!(&storage[..len], b"header, data & trailer");
+    }
+}
+
+#[test]
+fn portal_channel_no_peer() {
+    let mut portal = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+    let mut stream = SyncMockStream::new();
+    assert_err!(
+        portal
+            .channel(&mut stream)
+            .err()
+            .unwrap()
+            .downcast_ref::<PortalError>(),
+        Some(PortalError::NoPeer)
+    );
+}
+
+#[test]
+fn handshake_recovers_after_failure() {
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+
+    // A peer that sends its exchange data then disconnects, after
+    // the receiver's SPAKE2 state has been consumed
+    let mut stream = SyncMockStream::new();
+    let connect = ConnectMessage {
+        id: sender.get_id().clone(),
+        direction: Direction::Sender,
+    };
+    for message in [
+        PortalMessage::Connect(connect),
+        PortalMessage::KeyExchange(sender.exchange),
+    ] {
+        stream.push_bytes_to_read(&message.to_bytes().unwrap());
+    }
+    let first = receiver.exchange;
+    assert!(receiver.handshake(&mut stream).is_err());
+    assert!(receiver.state.is_some());
+    assert_ne!(receiver.exchange, first);
+
+    // The same Portal completes a handshake over a new connection
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+    let sender_thread = thread::spawn(move || {
+        sender.handshake(&mut senderstream).unwrap();
+    });
+    receiver.handshake(&mut receiverstream).unwrap();
+    sender_thread.join().unwrap();
+}
+
+#[test]
+fn handshake_strict_suceeds() {
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+    receiver.set_strict(true);
+    sender.set_strict(true);
+
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+    let sender_thread = thread::spawn(move || {
+        sender.handshake(&mut senderstream).unwrap();
+    });
+
+    receiver.handshake(&mut receiverstream).unwrap();
+    sender_thread.join().unwrap();
+}
+
+/// Drops the Capabilities messages written through it, as a relay
+/// stripping them to downgrade the session would
+struct StripCapabilities(MockTcpStream);
+
+impl Read for StripCapabilities {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, std::io::Error> {
+        self.0.read(buf)
+    }
+}
+
+impl Write for StripCapabilities {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, std::io::Error> {
+        let mut remaining = buf;
+        let mut kept = Vec::new();
+        while !remaining.is_empty() {
+            match CodecVersion::V1.decode(&mut remaining, u64::MAX) {
+                Ok(PortalMessage::Capabilities(_)) => {}
+                Ok(msg) => kept.extend(msg.to_bytes().unwrap()),
+                Err(_) => return self.0.write(buf),
+            }
+        }
+        self.0.write_all(&kept)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<(), std::io::Error> {
+        self.0.flush()
+    }
+}
+
+#[test]
+fn handshake_detects_stripped_capabilities() {
+    // Helper: whether the handshake succeeds for each peer, with the
+    // Capabilities of the Sender, Receiver or both withheld
+    let pair = |strip_sender: bool, strip_receiver: bool| {
+        let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+        let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+        let (senderstream, receiverstream) = MockTcpStream::channel();
+        let sender_thread = thread::spawn(move || {
+            let result = match strip_sender {
+                true => sender.handshake(&mut StripCapabilities(senderstream)),
+                false => sender.handshake(&mut { senderstream }),
+            };
+            result.is_ok()
+        });
+        let result = match strip_receiver {
+            true => receiver.handshake(&mut StripCapabilities(receiverstream)),
+            false => receiver.handshake(&mut { receiverstream }),
+        };
+        (sender_thread.join().unwrap(), result.is_ok())
+    };
+
+    // Neither peer completes a session that would quietly lose them
+    assert_eq!(pair(false, false), (true, true));
+    for (strip_sender, strip_receiver) in [(true, false), (false, true), (true, true)] {
+        assert_eq!(pair(strip_sender, strip_receiver), (false, false));
+    }
+}
+
+#[test]
+fn handshake_direct_suceeds() {
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+    assert_eq!(receiver.get_rtt(), None);
+
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+    let sender_thread = thread::spawn(move || {
+        sender.handshake_direct(&mut senderstream).unwrap();
+        sender.get_rtt()
+    });
+
+    // Without capabilities, neither peer knows the other measures it
+    receiver.handshake_direct(&mut receiverstream).unwrap();
+    assert!(sender_thread.join().unwrap().is_none());
+    assert!(receiver.get_rtt().is_none());
+}
+
+#[test]
+fn measure_rtt_rejects_wrong_echo() {
+    let mut stream = SyncMockStream::new();
+    stream.push_bytes_to_read(&PortalMessage::Ping(1).to_bytes().unwrap());
+    stream.push_bytes_to_read(&PortalMessage::Pong(2).to_bytes().unwrap());
+    assert_err!(
+        Protocol::measure_rtt(&mut stream)
+            .err()
+            .unwrap()
+            .downcast_ref::<PortalError>(),
+        Some(PortalError::UnexpectedMessage)
+    );
+}
+
+#[test]
+fn handshake_reports_claimed_session() {
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let claimed =
+        PortalMessage::RelayControl(RelayControl::Error(RelayControlError::AlreadyPaired));
+    let mut stream = SyncMockStream::new();
+    stream.push_bytes_to_read(&claimed.to_bytes().unwrap());
+    assert_err!(
+        receiver
+            .handshake(&mut stream)
+            .err()
+            .unwrap()
+            .downcast_ref::<PortalError>(),
+        Some(PortalError::SessionClaimed)
+    );
+}
+
+#[test]
+fn handshake_refuses_older_peers() {
+    // Paired with a peer that sent no options or capabilities, as the
+    // relay pairs clients that predate them
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let paired = PortalMessage::Paired(PairedMessage {
+        peer: ConnectMessage {
+            id: "id".into(),
+            direction: Direction::Sender,
+        },
+        options: ConnectOptions::default(),
+        salt: SessionSalt::generate(),
+    });
+    let exchange = PortalMessage::KeyExchange(vec![1u8; 33].try_into().unwrap());
+    let mut stream = SyncMockStream::new();
+    stream.push_bytes_to_read(&paired.to_bytes().unwrap());
+    stream.push_bytes_to_read(&exchange.to_bytes().unwrap());
+
+    // Refused as such, rather than as a mistyped pass-phrase
+    assert_err!(
+        receiver
+            .handshake(&mut stream)
+            .err()
+            .unwrap()
+            .downcast_ref::<PortalError>(),
+        Some(PortalError::PeerTooOld)
+    );
+    assert_eq!(receiver.failed_confirmations(), 0);
+}
+
+#[test]
+fn try_clone_concurrent_channels() {
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+    assert!(sender.try_clone().is_err());
+
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+    let sender_thread = thread::spawn(move || {
+        sender.handshake_direct(&mut senderstream).unwrap();
+        sender
+    });
+    receiver.handshake_direct(&mut receiverstream).unwrap();
+    let sender = sender_thread.join().unwrap();
+
+    // Each clone encrypts on its own thread without coordinating
+    let mut streams = Vec::new();
+    let mut handles = Vec::new();
+    for i in 0..4u8 {
+        let (mut senderstream, receiverstream) = MockTcpStream::channel();
+        let mut portal = sender.try_clone().unwrap();
+        handles.push(thread::spawn(move || {
+            let mut channel = portal.channel(&mut senderstream).unwrap();
+            channel.write_chunk(&[i; 32]).unwrap();
+        }));
+        streams.push(receiverstream);
+    }
+
+    for (i, mut stream) in streams.into_iter().enumerate() {
+        let mut portal = receiver.try_clone().unwrap();
+        let mut channel = portal.channel(&mut stream).unwrap();
+        let mut buf = [0u8; 32];
+        assert_eq!(channel.read_chunk(&mut buf).unwrap(), 32);
+        assert_eq!(buf, [i as u8; 32]);
+    }
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+#[test]
+fn try_clone_shares_session() {
+    let tmp_dir = TempDir::new("try_clone_shares_session").unwrap();
+    let out_dir = TempDir::new("try_clone_shares_session_out").unwrap();
+    let paths: Vec<_> = ["a.txt", "b.txt", "c.txt", "d.txt"]
+        .iter()
+        .map(|name| {
+            let path = tmp_dir.path().join(name);
+            std::fs::write(&path, name.repeat(100)).unwrap();
+            path
+        })
+        .collect();
+
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+    receiver.set_bind_chunks(true);
+    sender.set_bind_chunks(true);
+    receiver.set_receive_policy(ReceivePolicy {
+        max_files: Some(3),
+        ..Default::default()
+    });
+
+    // Files are sent through the original & the clone alternately
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+    let sender_thread = thread::spawn(move || {
+        sender.handshake(&mut senderstream).unwrap();
+        let mut clone = sender.try_clone().unwrap();
+        sender
+            .send_file(&mut senderstream, &paths[0], NO_PROGRESS_CALLBACK)
+            .unwrap();
+        clone
+            .send_file(&mut senderstream, &paths[1], NO_PROGRESS_CALLBACK)
+            .unwrap();
+        sender
+            .send_file(&mut senderstream, &paths[2], NO_PROGRESS_CALLBACK)
+            .unwrap();
+        let transcripts = (sender.transcript().unwrap(), clone.transcript().unwrap());
+        clone
+            .send_file(&mut senderstream, &paths[3], NO_PROGRESS_CALLBACK)
+            .unwrap();
+        transcripts
+    });
+
+    // And received in another order, each bound to the file the peer
+    // numbered
+    receiver.handshake(&mut receiverstream).unwrap();
+    let mut clone = receiver.try_clone().unwrap();
+    for (i, name) in ["a.txt", "b.txt", "c.txt"].iter().enumerate() {
+        let portal = match i {
+            2 => &mut clone,
+            _ => &mut receiver,
+        };
+        let metadata = portal
+            .recv_file(
+                &mut receiverstream,
+                out_dir.path(),
+                None,
+                NO_PROGRESS_CALLBACK,
+            )
+            .unwrap();
+        assert_eq!(&metadata.filename, name);
+        let received = std::fs::read(out_dir.path().join(name)).unwrap();
+        assert_eq!(received, name.repeat(100).as_bytes());
+    }
+
+    // Both handles record the whole session
+    let (ours, theirs) = sender_thread.join().unwrap();
+    assert!(ours.matches(&theirs));
+    assert!(receiver.transcript().unwrap().matches(&ours));
+    assert!(clone.transcript().unwrap().matches(&ours));
+
+    // And count against the same quota
+    let err = clone
+        .recv_file(
+            &mut receiverstream,
+            out_dir.path(),
+            None,
+            NO_PROGRESS_CALLBACK,
+        )
+        .err()
+        .unwrap();
+    assert!(matches!(
+        err.downcast_ref::<PortalError>(),
+        Some(PortalError::QuotaExceeded(_))
+    ));
+}
+
+#[test]
+fn try_clone_shares_dedup_and_offers() {
+    let tmp_dir = TempDir::new("try_clone_shares_dedup_and_offers").unwrap();
+    let indir = tmp_dir.path().join("in");
+    let outdir = tmp_dir.path().join("out");
+    std::fs::create_dir_all(&indir).unwrap();
+    std::fs::create_dir_all(&outdir).unwrap();
+
+    // The second file repeats the first, so refers back into it
+    let region = noise(4 * CHUNK_SIZE, 3);
+    let first = region.clone();
+    let second = [&b"shifted"[..], &region].concat();
+    let files = [("first.img", &first), ("second.img", &second)];
+    for (name, contents) in &files {
+        std::fs::write(indir.join(name), contents).unwrap();
+    }
+    let build = |direction| {
+        let mut portal = PortalBuilder::new(direction)
+            .id("id")
+            .password("test")
+            .dedup(true)
+            .build()
+            .unwrap();
+        portal.set_key(vec![1u8; 32]);
+        portal
+    };
+
+    // Each file is sent & received through a different handle
+    let mut sender = build(Direction::Sender);
+    let mut receiver = build(Direction::Receiver);
+    let mut sender_clone = sender.try_clone().unwrap();
+    let mut receiver_clone = receiver.try_clone().unwrap();
+    let mut wire = Vec::new();
+    sender
+        .send_file(&mut wire, &indir.join("first.img"), NO_PROGRESS_CALLBACK)
+        .unwrap();
+    sender_clone
+        .send_file(&mut wire, &indir.join("second.img"), NO_PROGRESS_CALLBACK)
+        .unwrap();
+    assert!(wire.len() < first.len() + second.len() / 2);
+
+    let mut reader = &wire[..];
+    for (portal, (name, contents)) in [&mut receiver, &mut receiver_clone].iter_mut().zip(&files) {
+        let metadata = portal
+            .recv_file(&mut reader, &outdir, None, NO_PROGRESS_CALLBACK)
+            .unwrap();
+        assert!(metadata.dedup);
+        assert_eq!(std::fs::read(outdir.join(name)).unwrap(), **contents);
+    }
+
+    // A file offered through the original is checked before the clone
+    // sends it
+    let path = indir.join("offered.txt");
+    std::fs::write(&path, b"as offered").unwrap();
+    let mut info = TransferInfo::empty();
+    info.add_file(&path).unwrap();
+    let _ = sender.outgoing(&mut Vec::new(), &info).unwrap();
+    std::fs::write(&path, b"changed since it was offered").unwrap();
+    let err = sender_clone
+        .send_file(&mut Vec::new(), &path, NO_PROGRESS_CALLBACK)
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.downcast_ref(),
+        Some(&PortalError::FileChangedDuringTransfer)
+    );
+}
+
+#[cfg(feature = "wire-capture")]
+#[test]
+fn capture_records_handshake() {
+    use crate::capture::{Capture, Payloads};
+
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+
+    let (mut senderstream, receiverstream) = MockTcpStream::channel();
+    let sender_thread = thread::spawn(move || {
+        sender.handshake_direct(&mut senderstream).unwrap();
+    });
+
+    let mut stream = Capture::new(receiverstream, Vec::new(), Payloads::Kept);
+    receiver.handshake_direct(&mut stream).unwrap();
+    sender_thread.join().unwrap();
+
+    let (_, log) = stream.into_inner();
+    let records: Vec<serde_json::Value> = std::str::from_utf8(&log)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert!(records.iter().any(|r| r["dir"] == "tx"));
+    assert!(records.iter().any(|r| r["dir"] == "rx"));
+    for record in records {
+        let len = record["len"].as_u64().unwrap() as usize;
+        assert_eq!(record["data"].as_str().unwrap().len(), len * 2);
+    }
+}
+
+#[cfg(feature = "wormhole")]
+#[test]
+fn wormhole_relay_handshake() {
+    use crate::wormhole::relay_handshake;
+
+    // Helper: the relay handshake's result & what was left to read, with
+    // the transit relay replying `reply`
+    let handshake = |reply: &[u8]| {
+        let mut stream = SyncMockStream::new();
+        stream.push_bytes_to_read(reply);
+        let result = relay_handshake(&mut stream, "token", "side");
+        let request = stream.pop_bytes_written();
+        assert_eq!(request, b"please relay token for side side\n");
+        let mut rest = Vec::new();
+        stream.read_to_end(&mut rest).unwrap();
+        (
+            result.map_err(|e| *e.downcast::<PortalError>().unwrap()),
+            rest,
+        )
+    };
+
+    // Data after the reply belongs to the peer & is left unread
+    assert_eq!(handshake(b"ok\npeer"), (Ok(()), b"peer".to_vec()));
+
+    // Anything else means no peer, including a reply that never ends
+    assert_eq!(handshake(b"impatient\n").0, Err(PortalError::NoPeer));
+    assert_eq!(handshake(b"ok").0, Err(PortalError::NoPeer));
+    assert_eq!(handshake(&[b'o'; 100]).0, Err(PortalError::NoPeer));
+}
+
+#[cfg(feature = "wormhole")]
+#[test]
+fn wormhole_connect_through_fake_relay() {
+    use crate::wormhole;
+    use std::io::BufRead;
+    use std::net::{TcpListener, TcpStream};
+
+    // A transit relay pairing the first two requests, then copying
+    // between them
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let relay = thread::spawn(move || {
+        let mut requests = Vec::new();
+        for stream in listener.incoming().take(2) {
+            let stream = stream.unwrap();
+            let mut line = String::new();
+            std::io::BufReader::new(&stream)
+                .read_line(&mut line)
+                .unwrap();
+            requests.push((stream, line));
+        }
+        let token = |line: &str| line.split(' ').nth(2).unwrap().to_string();
+        assert_eq!(token(&requests[0].1), token(&requests[1].1));
+        assert_ne!(requests[0].1, requests[1].1);
+
+        let pipe = |mut from: TcpStream, mut to: TcpStream| {
+            thread::spawn(move || std::io::copy(&mut from, &mut to))
+        };
+        let (a, b) = (&requests[0].0, &requests[1].0);
+        for mut stream in [a, b] {
+            stream.write_all(b"ok\n").unwrap();
+        }
+        pipe(a.try_clone().unwrap(), b.try_clone().unwrap());
+        pipe(b.try_clone().unwrap(), a.try_clone().unwrap());
+    });
+
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+    let sender_thread = thread::spawn(move || {
+        let mut stream = wormhole::connect(addr, &sender).unwrap();
+        sender.handshake_direct(&mut stream).unwrap();
+        sender.get_key().clone()
+    });
+    let mut stream = wormhole::connect(addr, &receiver).unwrap();
+    receiver.handshake_direct(&mut stream).unwrap();
+    assert_eq!(receiver.get_key(), &sender_thread.join().unwrap());
+    relay.join().unwrap();
+}
+
+#[test]
+fn handshake_psk_suceeds() {
+    let psk = [42u8; 32];
+    let mut receiver = Portal::init_psk(Direction::Receiver, "id".into(), psk).unwrap();
+    let mut sender = Portal::init_psk(Direction::Sender, "id".into(), psk).unwrap();
+
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+    let sender_thread = thread::spawn(move || {
+        sender.handshake_psk(&mut senderstream).unwrap();
+        sender.get_key().clone()
+    });
+
+    receiver.handshake_psk(&mut receiverstream).unwrap();
+    assert_eq!(receiver.get_key(), &sender_thread.join().unwrap());
+}
+
+#[test]
+fn handshake_psk_mismatch() {
+    let id = "psk mismatch".to_string();
+    let mut receiver = Portal::init_psk(Direction::Receiver, id.clone(), [1u8; 32]).unwrap();
+    let mut sender = Portal::init_psk(Direction::Sender, id, [2u8; 32]).unwrap();
+
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+    let sender_thread = thread::spawn(move || {
+        let _ = sender.handshake_psk(&mut senderstream);
+    });
+
+    assert_err!(
+        receiver
+            .handshake_psk(&mut receiverstream)
+            .err()
+            .unwrap()
+            .downcast_ref::<PortalError>(),
+        Some(PortalError::PeerKeyMismatch)
+    );
+    sender_thread.join().unwrap();
+}
+
+#[test]
+fn handshake_namespaces() {
+    // Helper: the handshake's result for the receiver & each key
+    let pair = |ours: Option<&str>, theirs: Option<&str>| {
+        let id = format!("namespaces {:?} {:?}", ours, theirs);
+        let mut receiver = Portal::init(Direction::Receiver, id.clone(), "test".into()).unwrap();
+        let mut sender = Portal::init(Direction::Sender, id, "test".into()).unwrap();
+        receiver.set_namespace(ours.map(String::from));
+        sender.set_namespace(theirs.map(String::from));
+
+        let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+        let sender_thread = thread::spawn(move || {
+            let _ = sender.handshake_direct(&mut senderstream);
+            sender.session_fingerprint()
+        });
+        let result = receiver
+            .handshake_direct(&mut receiverstream)
+            .map_err(|e| *e.downcast::<PortalError>().unwrap());
+        let fingerprints = (
+            receiver.session_fingerprint(),
+            sender_thread.join().unwrap(),
+        );
+        (result, fingerprints)
+    };
+
+    // Peers in the same namespace pair as usual, with their own fingerprint
+    let (result, (ours, theirs)) = pair(Some("com.example"), Some("com.example"));
+    assert_eq!(result, Ok(()));
+    assert_eq!(ours, theirs);
+    let (_, (vanilla, _)) = pair(None, None);
+    assert_ne!(ours, vanilla);
+
+    // Others can't, even with the same pass-phrase
+    for (ours, theirs) in [
+        (Some("com.example"), None),
+        (None, Some("com.example")),
+        (Some("com.example"), Some("org.example")),
+    ] {
+        assert_eq!(pair(ours, theirs).0, Err(PortalError::PeerKeyMismatch));
+    }
+}
+
+#[test]
+fn handshake_repeated_mismatch_interference() {
+    let id = "interference".to_string();
+    let mut receiver = Portal::init(Direction::Receiver, id.clone(), "right".into()).unwrap();
+    let attempt = |receiver: &mut Portal, password: &str| {
+        let mut sender = Portal::init(Direction::Sender, id.clone(), password.into()).unwrap();
+        let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+        let sender_thread = thread::spawn(move || {
+            let _ = sender.handshake_direct(&mut senderstream);
+        });
+        let result = receiver.handshake_direct(&mut receiverstream);
+        sender_thread.join().unwrap();
+        result.map_err(|e| *e.downcast::<PortalError>().unwrap())
+    };
+
+    // Failures look like typos, until they keep happening to the same Portal
+    let mut results = Vec::new();
+    for _ in 0..INTERFERENCE_THRESHOLD {
+        results.push(attempt(&mut receiver, "wrong").unwrap_err());
+    }
+    let (last, typos) = results.split_last().unwrap();
+    assert!(typos.iter().all(|e| *e == PortalError::PeerKeyMismatch));
+    assert_eq!(*last, PortalError::PossibleInterference);
+    assert_eq!(receiver.failed_confirmations(), INTERFERENCE_THRESHOLD);
+
+    // Other Portals under the same ID aren't affected
+    let other = Portal::init(Direction::Receiver, id.clone(), "right".into()).unwrap();
+    assert_eq!(other.failed_confirmations(), 0);
+
+    // A successful confirmation starts the count over
+    attempt(&mut receiver, "right").unwrap();
+    assert_eq!(receiver.failed_confirmations(), 0);
+}
+
+#[test]
+fn listen_direct_roundtrip() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+    let sender_thread = thread::spawn(move || {
+        let mut stream = sender.listen_direct(addr).unwrap();
+        let mut channel = sender.channel(&mut stream).unwrap();
+        channel.write_chunk(b"direct").unwrap();
+    });
+
+    // Wait for the Sender to start listening
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let mut stream = loop {
+        match std::net::TcpStream::connect(addr) {
+            Ok(stream) => break stream,
+            Err(_) => thread::sleep(Duration::from_millis(10)),
+        }
+    };
+    receiver.handshake_direct(&mut stream).unwrap();
+
+    let mut storage = vec![0u8; CHUNK_SIZE];
+    let mut channel = receiver.channel(&mut stream).unwrap();
+    let len = channel.read_chunk(&mut storage).unwrap();
+    assert_eq!(&storage[..len], b"direct");
+    sender_thread.join().unwrap();
+}
+
+#[test]
+fn handshake_reconnecting_suceeds() {
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+
+    // The first connection is closed before a peer arrives
+    let (mut dead, _) = MockTcpStream::channel();
+    dead.waiting_for_write.store(1, Ordering::SeqCst);
+
+    let (senderstream, mut receiverstream) = MockTcpStream::channel();
+    let mut next = Some(senderstream);
+    let sender_thread = thread::spawn(move || {
+        let reconnect = || next.take().ok_or(std::io::ErrorKind::NotConnected.into());
+        sender
+            .handshake_reconnecting(&mut dead, reconnect, Duration::from_secs(5))
+            .unwrap();
+    });
+
+    receiver.handshake(&mut receiverstream).unwrap();
+    sender_thread.join().unwrap();
+}
+
+#[test]
+fn handshake_reconnecting_gives_up() {
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+    let (mut dead, _) = MockTcpStream::channel();
+    dead.waiting_for_write.store(1, Ordering::SeqCst);
+
+    let reconnect = || Err(std::io::ErrorKind::ConnectionRefused.into());
+    assert_err!(
+        sender
+            .handshake_reconnecting(&mut dead, reconnect, Duration::from_millis(100))
+            .err()
+            .unwrap()
+            .downcast_ref::<PortalError>(),
+        Some(PortalError::NoPeer)
+    );
+}
+
+#[test]
+fn handshake_shard_suceeds() {
+    // receiver only knows its own derived credentials
+    let shard = Shard::derive("id", "master", 3).unwrap();
+    let mut receiver = Portal::init(Direction::Receiver, shard.id, shard.password).unwrap();
+
+    // sender derives the same credentials from the master password
+    let mut sender = Portal::init_shard(Direction::Sender, "id", "master", 3).unwrap();
+
+    // mock channel
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+
+    let sender_thread = thread::spawn(move || {
+        sender.handshake(&mut senderstream).unwrap();
+    });
+
+    receiver.handshake(&mut receiverstream).unwrap();
+    sender_thread.join().unwrap();
+}
+
+#[test]
+fn test_file_roundtrip() {
+    // Create test file
+    let tmp_dir = TempDir::new("test_recv_file_bad_outdir").unwrap();
+    let file_path = tmp_dir.path().join("randomfile.txt");
+    let file_path_str = Path::new(&file_path.to_str().unwrap().to_owned()).to_path_buf();
+    let mut tmp_file = File::create(file_path).unwrap();
+    writeln!(tmp_file, "Test File").unwrap();
+
+    // receiver
+    let dir = Direction::Receiver;
+    let pass = "test".to_string();
+    let mut receiver = Portal::init(dir, "id".to_string(), pass).unwrap();
+
+    // sender
+    let dir = Direction::Sender;
+    let pass = "test".to_string();
+    let mut sender = Portal::init(dir, "id".to_string(), pass).unwrap();
+
+    // mock channel
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+
+    let sender_thread = thread::spawn(move || {
+        // Complete handshake
+        sender.handshake(&mut senderstream).unwrap();
+
+        // Send the file
+        let result = sender.send_file(&mut senderstream, &file_path_str, NO_PROGRESS_CALLBACK);
+        assert!(result.is_ok());
+        result.unwrap()
+    });
+
+    // Complete handshake
+    receiver.handshake(&mut receiverstream).unwrap();
+
+    // Receive the file
+    let metadata = receiver
+        .recv_file(
+            &mut receiverstream,
+            tmp_dir.path(),
+            None,
+            NO_PROGRESS_CALLBACK,
+        )
+        .unwrap();
+
+    // Wait for sending to complete
+    let sent_size = sender_thread.join().unwrap();
+
+    // Compare sizes
+    assert_eq!(metadata.filesize, sent_size as u64);
+}
+
+#[test]
+fn test_file_roundtrip_tuned() {
+    // Create test file
+    let tmp_dir = TempDir::new("test_file_roundtrip_tuned").unwrap();
+    let file_path = tmp_dir.path().join("randomfile.txt");
+    let file_path_str = Path::new(&file_path.to_str().unwrap().to_owned()).to_path_buf();
+    let mut tmp_file = File::create(file_path).unwrap();
+    writeln!(tmp_file, "Test File").unwrap();
+
+    // Enable every hint on both peers
+    let tuning = TransferTuning {
+        sequential: true,
+        drop_cache: true,
+    };
+
+    // receiver
+    let pass = "test".to_string();
+    let mut receiver = Portal::init(Direction::Receiver, "id".to_string(), pass).unwrap();
+    receiver.set_tuning(tuning);
+
+    // sender
+    let pass = "test".to_string();
+    let mut sender = Portal::init(Direction::Sender, "id".to_string(), pass).unwrap();
+    sender.set_tuning(tuning);
+
+    // mock channel
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+
+    let sender_thread = thread::spawn(move || {
+        sender.handshake(&mut senderstream).unwrap();
+        sender
+            .send_file(&mut senderstream, &file_path_str, NO_PROGRESS_CALLBACK)
+            .unwrap()
+    });
+
+    // Receive into a separate directory
+    let out_dir = TempDir::new("test_file_roundtrip_tuned_out").unwrap();
+    receiver.handshake(&mut receiverstream).unwrap();
+    let metadata = receiver
+        .recv_file(
+            &mut receiverstream,
+            out_dir.path(),
+            None,
+            NO_PROGRESS_CALLBACK,
+        )
+        .unwrap();
+
+    let sent_size = sender_thread.join().unwrap();
+    assert_eq!(metadata.filesize, sent_size as u64);
+
+    // Contents are intact
+    let received = std::fs::read(out_dir.path().join("randomfile.txt")).unwrap();
+    assert_eq!(received, b"Test File\n");
+}
+
+#[test]
+fn test_file_roundtrip_windows() {
+    // Spans several mapping windows, with chunks straddling the boundaries
+    let tmp_dir = TempDir::new("test_file_roundtrip_windows").unwrap();
+    let file_path = tmp_dir.path().join("large.bin");
+    let window = MAX_CHUNK_SIZE + 100;
+    let size = 3 * window + 7;
+    let mut file = File::create(&file_path).unwrap();
+    file.set_len(size as u64).unwrap();
+    file.write_all(b"head").unwrap();
+    file.seek(SeekFrom::Start(window as u64 - 2)).unwrap();
+    file.write_all(b"straddle").unwrap();
+    file.seek(SeekFrom::End(-4)).unwrap();
+    file.write_all(b"tail").unwrap();
+    drop(file);
+
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+    assert_eq!(receiver.get_mmap_window(), MMAP_WINDOW_SIZE);
+    receiver.set_mmap_window(window);
+    sender.set_mmap_window(2 * window);
+    assert_eq!(receiver.get_mmap_window() % (64 * 1024), 0);
+    assert!(receiver.get_mmap_window() >= window);
+
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+    let path = file_path.clone();
+    let sender_thread = thread::spawn(move || {
+        sender.handshake(&mut senderstream).unwrap();
+        sender
+            .send_file(&mut senderstream, &path, NO_PROGRESS_CALLBACK)
+            .unwrap()
+    });
+
+    let out_dir = TempDir::new("test_file_roundtrip_windows_out").unwrap();
+    receiver.handshake(&mut receiverstream).unwrap();
+    let metadata = receiver
+        .recv_file(
+            &mut receiverstream,
+            out_dir.path(),
+            None,
+            NO_PROGRESS_CALLBACK,
+        )
+        .unwrap();
+    assert_eq!(metadata.strategy, WriteStrategy::Mapped);
+    assert_eq!(sender_thread.join().unwrap(), size);
+
+    let received = std::fs::read(out_dir.path().join("large.bin")).unwrap();
+    assert!(received == std::fs::read(&file_path).unwrap());
+}
+
+#[test]
+fn test_file_handle_roundtrip() {
+    // Open the file, then remove its path entirely
+    let tmp_dir = TempDir::new("test_file_handle_roundtrip").unwrap();
+    let file_path = tmp_dir.path().join("unlinked.txt");
+    writeln!(File::create(&file_path).unwrap(), "Test File").unwrap();
+    let file = File::open(&file_path).unwrap();
+    std::fs::remove_file(&file_path).unwrap();
+
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+    let sender_thread = thread::spawn(move || {
+        sender.handshake(&mut senderstream).unwrap();
+
+        // Names with path components are rejected
+        let result = sender.send_file_handle(
+            &mut senderstream,
+            &file,
+            "../picked.txt",
+            NO_PROGRESS_CALLBACK,
+        );
+        assert_err!(
+            result.err().unwrap().downcast_ref::<PortalError>(),
+            Some(PortalError::BadFileName)
+        );
+
+        sender
+            .send_file_handle(&mut senderstream, &file, "picked.txt", NO_PROGRESS_CALLBACK)
+            .unwrap()
+    });
+
+    let out_dir = TempDir::new("test_file_handle_roundtrip_out").unwrap();
+    receiver.handshake(&mut receiverstream).unwrap();
+    let metadata = receiver
+        .recv_file(
+            &mut receiverstream,
+            out_dir.path(),
+            None,
+            NO_PROGRESS_CALLBACK,
+        )
+        .unwrap();
+    assert_eq!(metadata.filesize, sender_thread.join().unwrap() as u64);
+
+    let received = std::fs::read(out_dir.path().join("picked.txt")).unwrap();
+    assert_eq!(received, b"Test File\n");
+}
+
+#[test]
+fn test_file_roundtrip_durable() {
+    // A file spanning several chunks
+    let tmp_dir = TempDir::new("test_file_roundtrip_durable").unwrap();
+    let file_path = tmp_dir.path().join("randomfile.bin");
+    let contents = (0..3 * CHUNK_SIZE).map(|i| i as u8).collect::<Vec<u8>>();
+    File::create(&file_path)
+        .unwrap()
+        .write_all(&contents)
+        .unwrap();
+
+    for durability in [
+        Durability::PerFile,
+        Durability::EveryBytes(CHUNK_SIZE as u64),
+        Durability::Dsync,
+    ] {
+        let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+        let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+        receiver.set_durability(durability);
+        assert_eq!(receiver.get_durability(), durability);
+
+        let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+        let path = file_path.clone();
+        let sender_thread = thread::spawn(move || {
+            sender.handshake(&mut senderstream).unwrap();
+            sender
+                .send_file(&mut senderstream, &path, NO_PROGRESS_CALLBACK)
+                .unwrap()
+        });
+
+        let out_dir = TempDir::new("test_file_roundtrip_durable_out").unwrap();
+        receiver.handshake(&mut receiverstream).unwrap();
+        receiver
+            .recv_file(
+                &mut receiverstream,
+                out_dir.path(),
+                None,
+                NO_PROGRESS_CALLBACK,
+            )
+            .unwrap();
+        sender_thread.join().unwrap();
+
+        let received = std::fs::read(out_dir.path().join("randomfile.bin")).unwrap();
+        assert!(received == contents);
+    }
+}
+
+#[test]
+fn test_recv_buffered_fallback() {
+    let key = [5u8; 32];
+    let mut nseq = NonceSequence::new();
+    let mut stream = SyncMockStream::new();
+
+    // Encrypt a file spanning multiple chunks
+    let size = CHUNK_SIZE + 100;
+    let contents = (0..size).map(|i| i as u8).collect::<Vec<u8>>();
+    let mut wire = Vec::new();
+    for chunk in contents.clone().chunks_mut(CHUNK_SIZE) {
+        Protocol::encrypt_and_write_header_only(
+            &mut wire,
+            &key,
+            &mut nseq,
+            CodecVersion::V1,
+            chunk,
+        )
+        .unwrap();
+        wire.extend_from_slice(chunk);
+    }
+    stream.push_bytes_to_read(&wire);
+
+    // Receive through the buffered path
+    let tmp_dir = TempDir::new("test_recv_buffered_fallback").unwrap();
+    let path = tmp_dir.path().join("out");
+    let mut channel = EncryptedChannel::new(&mut stream, &key, &mut nseq);
+    let total = Portal::recv_buffered(
+        &mut channel,
+        &path,
+        size as u64,
+        &mut vec![0u8; CHUNK_SIZE],
+        Durability::EveryBytes(CHUNK_SIZE as u64),
+        None::<&mut fn(usize, usize)>,
+    )
+    .unwrap();
+
+    assert_eq!(total, size);
+    assert_eq!(std::fs::read(&path).unwrap(), contents);
+}
+
+#[test]
+fn test_tail_file_roundtrip() {
+    let tmp_dir = TempDir::new("test_tail_file_roundtrip").unwrap();
+    let outdir = TempDir::new("test_tail_file_roundtrip_out").unwrap();
+    let file_path = tmp_dir.path().join("growing.log");
+    let mut log = File::create(&file_path).unwrap();
+    writeln!(log, "first line").unwrap();
+
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+
+    // Append to the file while it is being followed, then cancel
+    let cancel = Arc::new(AtomicBool::new(false));
+    let writer_cancel = cancel.clone();
+    let writer_thread = thread::spawn(move || {
+        for i in 0..3 {
+            thread::sleep(Duration::from_millis(20));
+            writeln!(log, "appended line {}", i).unwrap();
+        }
+        writer_cancel.store(true, Ordering::Release);
+    });
+
+    let sender_path = file_path.clone();
+    let sender_thread = thread::spawn(move || {
+        sender.handshake(&mut senderstream).unwrap();
+        let poll = Duration::from_millis(5);
+        sender
+            .tail_file(
+                &mut senderstream,
+                &sender_path,
+                poll,
+                &cancel,
+                NO_PROGRESS_CALLBACK,
+            )
+            .unwrap()
+    });
+
+    receiver.handshake(&mut receiverstream).unwrap();
+    let metadata = receiver
+        .recv_file(
+            &mut receiverstream,
+            outdir.path(),
+            None,
+            NO_PROGRESS_CALLBACK,
+        )
+        .unwrap();
+
+    writer_thread.join().unwrap();
+    let sent = sender_thread.join().unwrap();
+
+    // Everything written before cancellation was received
+    let expected = std::fs::read(&file_path).unwrap();
+    let received = std::fs::read(outdir.path().join("growing.log")).unwrap();
+    assert!(metadata.is_stream());
+    assert_eq!(metadata.filesize as usize, sent);
+    assert_eq!(received, expected);
+}
+
+#[test]
+fn test_send_stream_roundtrip() {
+    let tmp_dir = TempDir::new("test_send_stream_roundtrip").unwrap();
+    let outdir = TempDir::new("test_send_stream_roundtrip_out").unwrap();
+    let contents = (0..3 * CHUNK_SIZE + 7)
+        .map(|i| (i % 251) as u8)
+        .collect::<Vec<u8>>();
+
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+
+    // What a previous transfer left behind is replaced, not appended to
+    std::fs::write(outdir.path().join("stream.bin"), b"stale").unwrap();
+
+    // A stream is announced without a size, and can't be a copy of a file
+    let empty = tmp_dir.path().join("empty.txt");
+    File::create(&empty).unwrap();
+    let mut info = TransferInfo::empty();
+    info.add_stream(tmp_dir.path(), "stream.bin").unwrap();
+    info.add_file(&empty).unwrap();
+    assert_eq!(info.all.len(), 2);
+    assert!(info.all[0].is_stream());
+    assert!(info.add_stream(tmp_dir.path(), "../stream.bin").is_err());
+
+    let data = contents.clone();
+    let sender_thread = thread::spawn(move || {
+        sender.handshake(&mut senderstream).unwrap();
+        let mut outgoing = sender.outgoing(&mut senderstream, &info).unwrap();
+        let (_, metadata) = outgoing.next().unwrap();
+        let mut reader = std::io::Cursor::new(data);
+        sender
+            .send_stream(
+                &mut senderstream,
+                &mut reader,
+                &metadata.filename,
+                NO_PROGRESS_CALLBACK,
+            )
+            .unwrap()
+    });
+
+    receiver.handshake(&mut receiverstream).unwrap();
+    let expected = receiver
+        .incoming(&mut receiverstream, NO_VERIFY_CALLBACK)
+        .unwrap()
+        .next()
+        .unwrap();
+    let metadata = receiver
+        .recv_file(
+            &mut receiverstream,
+            outdir.path(),
+            Some(&expected),
+            NO_PROGRESS_CALLBACK,
+        )
+        .unwrap();
+
+    let sent = sender_thread.join().unwrap();
+    let received = std::fs::read(outdir.path().join("stream.bin")).unwrap();
+    assert!(metadata.is_stream());
+    assert_eq!(sent, contents.len());
+    assert_eq!(metadata.filesize as usize, sent);
+    assert_eq!(received, contents);
+}
+
+#[test]
+fn test_incoming_outgoing_roundtrip() {
+    // Create test file
+    let tmp_dir = TempDir::new("test_recv_file_bad_outdir").unwrap();
+    let file_path = tmp_dir.path().join("randomfile.txt");
+    let file_path_str = Path::new(&file_path.to_str().unwrap().to_owned()).to_path_buf();
+    let mut tmp_file = File::create(file_path).unwrap();
+    writeln!(tmp_file, "Test File").unwrap();
+
+    // receiver
+    let dir = Direction::Receiver;
+    let pass = "test".to_string();
+    let mut receiver = Portal::init(dir, "id".to_string(), pass).unwrap();
+
+    // sender
+    let dir = Direction::Sender;
+    let pass = "test".to_string();
+    let mut sender = Portal::init(dir, "id".to_string(), pass).unwrap();
+
+    // mock channel
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+
+    let sender_thread = thread::spawn(move || {
+        // Complete handshake
+        sender.handshake(&mut senderstream).unwrap();
+
+        let info = TransferInfoBuilder::new()
+            .add_file(Path::new(&file_path_str))
+            .unwrap()
+            .finalize();
+
+        for (path, _metadata) in sender.outgoing(&mut senderstream, &info).unwrap() {
+            // Send the file
+            let result = sender.send_file(&mut senderstream, path, NO_PROGRESS_CALLBACK);
+            assert!(result.is_ok());
+        }
+    });
+
+    // VerifyCallback
+    fn verify_callback(info: &TransferInfo) -> bool {
+        assert!(info
+            .all
+            .iter()
+            .any(|m| m.filename.as_str() == "randomfile.txt"));
+        true
+    }
+
+    // Complete handshake
+    receiver.handshake(&mut receiverstream).unwrap();
+
+    for m in receiver
+        .incoming(&mut receiverstream, Some(verify_callback))
+        .unwrap()
+    {
+        let d = receiver
+            .recv_file(
+                &mut receiverstream,
+                tmp_dir.path(),
+                Some(&m),
+                NO_PROGRESS_CALLBACK,
+            )
+            .unwrap();
+        assert_eq!(d, m);
+        assert_eq!(d.strategy, WriteStrategy::Mapped);
+    }
+
+    sender_thread.join().unwrap();
+}
+
+#[test]
+fn test_transfer_note_roundtrip() {
+    let tmp_dir = TempDir::new("test_transfer_note_roundtrip").unwrap();
+    let file_path = tmp_dir.path().join("build.log");
+    File::create(&file_path).unwrap();
+
+    // Notes are limited in size
+    let long = "x".repeat(MAX_NOTE_SIZE + 1);
+    assert!(TransferInfo::empty().set_note(&long).is_err());
+
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+
+    let sender_thread = thread::spawn(move || {
+        sender.handshake(&mut senderstream).unwrap();
+        let info = TransferInfoBuilder::new()
+            .add_file(&file_path)
+            .unwrap()
+            .note("From: build server 7")
+            .unwrap()
+            .finalize();
+        sender.outgoing(&mut senderstream, &info).unwrap().count()
+    });
+
+    // The note is seen before accepting
+    let mut note = None;
+    let verify = |info: &TransferInfo| {
+        note = info.note.clone();
+        true
+    };
+    receiver.handshake(&mut receiverstream).unwrap();
+    let incoming = receiver
+        .incoming(&mut receiverstream, Some(verify))
+        .unwrap()
+        .count();
+
+    assert_eq!(incoming, sender_thread.join().unwrap());
+    assert_eq!(note.as_deref(), Some("From: build server 7"));
+}
+
+#[test]
+fn test_duplicate_files_roundtrip() {
+    let tmp_dir = TempDir::new("test_duplicate_files_roundtrip").unwrap();
+    let outdir = TempDir::new("test_duplicate_files_roundtrip_out").unwrap();
+    for name in ["one.txt", "two.txt"] {
+        std::fs::write(tmp_dir.path().join(name), b"Repeated asset").unwrap();
+    }
+
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+
+    let info = TransferInfoBuilder::new()
+        .add_file(&tmp_dir.path().join("one.txt"))
+        .unwrap()
+        .add_file(&tmp_dir.path().join("two.txt"))
+        .unwrap()
+        .finalize();
+
+    let sender_thread = thread::spawn(move || {
+        sender.handshake(&mut senderstream).unwrap();
+
+        // Only the original is sent
+        let mut sent = 0;
+        for (path, _metadata) in sender.outgoing(&mut senderstream, &info).unwrap() {
+            sender
+                .send_file(&mut senderstream, path, NO_PROGRESS_CALLBACK)
+                .unwrap();
+            sent += 1;
+        }
+        sent
+    });
+
+    receiver.handshake(&mut receiverstream).unwrap();
+
+    // Verify callbacks may record their decisions
+    let mut offered = Vec::new();
+    let verify = |info: &TransferInfo| {
+        offered.extend(info.all.iter().map(|m| m.copies.len()));
+        true
+    };
+    for m in receiver
+        .incoming(&mut receiverstream, Some(verify))
+        .unwrap()
+    {
+        receiver
+            .recv_file(
+                &mut receiverstream,
+                outdir.path(),
+                Some(&m),
+                NO_PROGRESS_CALLBACK,
+            )
+            .unwrap();
+    }
+
+    // Both files exist on the receiving side
+    assert_eq!(offered, vec![1]);
+    assert_eq!(sender_thread.join().unwrap(), 1);
+    for name in ["one.txt", "two.txt"] {
+        let contents = std::fs::read(outdir.path().join(name)).unwrap();
+        assert_eq!(contents, b"Repeated asset");
+    }
+}
+
+#[test]
+fn test_duplicate_files_need_approval() {
+    let tmp_dir = TempDir::new("test_duplicate_files_need_approval").unwrap();
+    let outdir = TempDir::new("test_duplicate_files_need_approval_out").unwrap();
+    let path = tmp_dir.path().join("one.txt");
+    std::fs::write(&path, b"Repeated asset").unwrap();
+
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+    let info = TransferInfoBuilder::new()
+        .add_file(&path)
+        .unwrap()
+        .finalize();
+
+    // The file's own metadata names a copy that wasn't offered
+    let sender_thread = thread::spawn(move || {
+        sender.handshake(&mut senderstream).unwrap();
+        let _ = sender.outgoing(&mut senderstream, &info).unwrap();
+        let metadata = crate::FileEntry {
+            copies: vec!["two.txt".into()],
+            ..crate::FileEntry::of(&File::open(&path).unwrap(), "one.txt").unwrap()
+        };
+        Protocol::encrypt_and_write_object(
+            &mut senderstream,
+            sender.key.as_ref().unwrap(),
+            &mut sender.nseq,
+            sender.codec,
+            sender.format,
+            &metadata,
+        )
+        .unwrap();
+    });
+
+    receiver.handshake(&mut receiverstream).unwrap();
+    receiver
+        .incoming(&mut receiverstream, NO_VERIFY_CALLBACK)
+        .unwrap()
+        .for_each(drop);
+    sender_thread.join().unwrap();
+    let result = receiver.recv_file(
+        &mut receiverstream,
+        outdir.path(),
+        None,
+        NO_PROGRESS_CALLBACK,
+    );
+    assert_eq!(
+        result.unwrap_err().downcast_ref(),
+        Some(&PortalError::UnexpectedMessage)
+    );
+    assert!(!outdir.path().join("two.txt").exists());
+}
+
+#[test]
+fn test_duplicate_files_keep_existing() {
+    let tmp_dir = TempDir::new("test_duplicate_files_keep_existing").unwrap();
+    let outdir = TempDir::new("test_duplicate_files_keep_existing_out").unwrap();
+    for name in ["one.txt", "two.txt"] {
+        std::fs::write(tmp_dir.path().join(name), b"Repeated asset").unwrap();
+    }
+    std::fs::write(outdir.path().join("two.txt"), b"Mine").unwrap();
+
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+    let info = TransferInfoBuilder::new()
+        .add_file(&tmp_dir.path().join("one.txt"))
+        .unwrap()
+        .add_file(&tmp_dir.path().join("two.txt"))
+        .unwrap()
+        .finalize();
+
+    let sender_thread = thread::spawn(move || {
+        sender.handshake(&mut senderstream).unwrap();
+        for (path, _metadata) in sender.outgoing(&mut senderstream, &info).unwrap() {
+            sender
+                .send_file(&mut senderstream, path, NO_PROGRESS_CALLBACK)
+                .unwrap();
+        }
+    });
+
+    // The copy isn't written over a file that was already there
+    receiver.handshake(&mut receiverstream).unwrap();
+    for m in receiver
+        .incoming(&mut receiverstream, NO_VERIFY_CALLBACK)
+        .unwrap()
+    {
+        let result = receiver.recv_file(
+            &mut receiverstream,
+            outdir.path(),
+            Some(&m),
+            NO_PROGRESS_CALLBACK,
+        );
+        assert!(result.is_err());
+    }
+    sender_thread.join().unwrap();
+    let contents = std::fs::read(outdir.path().join("two.txt")).unwrap();
+    assert_eq!(contents, b"Mine");
+}
+
+#[test]
+fn portal_map_bad_path() {
+    let dir = Direction::Receiver;
+    let pass = "test".to_string();
+    let receiver = Portal::init(dir, "id".to_string(), pass).unwrap();
+    let result = receiver.map_writeable_file(&Path::new("/notafile").to_path_buf(), 12);
+    assert!(result.is_err());
+}
+
+#[test]
+fn portal_handshake_no_peer() {
+    let dir = Direction::Receiver;
+    let pass = "test".to_string();
+    let mut receiver = Portal::init(dir, "id".to_string(), pass).unwrap();
+
+    let mut stream = SyncMockStream::new();
+
+    // Queue a message that will make Protocol::connect() mad
+    let values = EncryptedMessage::default();
+    let message = PortalMessage::EncryptedDataHeader(values);
+    stream.push_bytes_to_read(&bincode::serialize(&message).unwrap());
+
+    let result = receiver.handshake(&mut stream);
+    assert!(result.is_err());
+    assert_err!(
+        result.err().unwrap().downcast_ref::<PortalError>(),
+        Some(PortalError::NoPeer)
+    );
+}
+
+#[test]
+fn portal_send_file_no_peer() {
+    let dir = Direction::Sender;
+    let pass = "test".to_string();
+    let mut portal = Portal::init(dir, "id".to_string(), pass).unwrap();
+
+    // will return error
+    let mut stream = SyncMockStream::new();
+    let result = portal.send_file(
+        &mut stream,
+        &Path::new("/tmp/passwd").to_path_buf(),
+        NO_PROGRESS_CALLBACK,
+    );
+    assert!(result.is_err());
+    assert_err!(
+        result.err().unwrap().downcast_ref::<PortalError>(),
+        Some(PortalError::NoPeer)
+    );
+}
+
+#[test]
+fn portal_recv_file_no_peer() {
+    let dir = Direction::Sender;
+    let pass = "test".to_string();
+    let mut portal = Portal::init(dir, "id".to_string(), pass).unwrap();
+
+    // will panic due to lack of peer
+    let mut stream = SyncMockStream::new();
+    let result = portal.recv_file(&mut stream, Path::new("/tmp"), None, NO_PROGRESS_CALLBACK);
+    assert!(result.is_err());
+    assert_err!(
+        result.err().unwrap().downcast_ref::<PortalError>(),
+        Some(PortalError::NoPeer)
+    );
+}
+
+#[test]
+fn test_recv_file_bad_outdir() {
+    // Create test file
+    let tmp_dir = TempDir::new("test_recv_file_bad_outdir").unwrap();
+    let file_path = tmp_dir.path().join("randomfile.txt");
+    let file_path_str = Path::new(&file_path.to_str().unwrap().to_owned()).to_path_buf();
+    let mut tmp_file = File::create(file_path).unwrap();
+    writeln!(tmp_file, "Test File").unwrap();
+
+    // receiver
+    let dir = Direction::Receiver;
+    let pass = "test".to_string();
+    let mut receiver = Portal::init(dir, "id".to_string(), pass).unwrap();
+
+    // sender
+    let dir = Direction::Sender;
+    let pass = "test".to_string();
+    let mut sender = Portal::init(dir, "id".to_string(), pass).unwrap();
+
+    // mock channel
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+
+    let sender_thread = thread::spawn(move || {
+        // Complete handshake
+        sender.handshake(&mut senderstream).unwrap();
+
+        // Send the file
+        let result = sender.send_file(&mut senderstream, &file_path_str, NO_PROGRESS_CALLBACK);
+        assert!(result.is_ok());
+        result.unwrap()
+    });
+
+    // Complete handshake
+    receiver.handshake(&mut receiverstream).unwrap();
+
+    let result = receiver.recv_file(
+        &mut receiverstream,
+        Path::new("/tmp/test.txt"),
+        None,
+        NO_PROGRESS_CALLBACK,
+    );
+    assert!(result.is_err());
+    assert_err!(
+        result.err().unwrap().downcast_ref::<PortalError>(),
+        Some(PortalError::BadDirectory)
+    );
+
+    sender_thread.join().unwrap();
+}
+
+#[test]
+fn test_incoming_cancel() {
+    // Create test file
+    let tmp_dir = TempDir::new("test_recv_file_bad_outdir").unwrap();
+    let file_path = tmp_dir.path().join("randomfile.txt");
+    let file_path_str = Path::new(&file_path.to_str().unwrap().to_owned()).to_path_buf();
+    let mut tmp_file = File::create(file_path).unwrap();
+    writeln!(tmp_file, "Test File").unwrap();
+
+    // receiver
+    let dir = Direction::Receiver;
+    let pass = "test".to_string();
+    let mut receiver = Portal::init(dir, "id".to_string(), pass).unwrap();
+
+    // sender
+    let dir = Direction::Sender;
+    let pass = "test".to_string();
+    let mut sender = Portal::init(dir, "id".to_string(), pass).unwrap();
+
+    // mock channel
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+
+    let sender_thread = thread::spawn(move || {
+        // Complete handshake
+        sender.handshake(&mut senderstream).unwrap();
+
+        let info = TransferInfoBuilder::new()
+            .add_file(Path::new(&file_path_str))
+            .unwrap()
+            .finalize();
+
+        let result = sender.outgoing(&mut senderstream, &info);
+        assert!(result.is_ok());
+    });
+
+    // VerifyCallback that cancels every download
+    fn cancel_all(_info: &TransferInfo) -> bool {
+        false
+    }
+
+    // Complete handshake
+    receiver.handshake(&mut receiverstream).unwrap();
+
+    let result = receiver.incoming(&mut receiverstream, Some(cancel_all));
+    assert!(result.is_err());
+    assert_err!(
+        result.err().unwrap().downcast_ref::<PortalError>(),
+        Some(PortalError::Cancelled)
+    );
+
+    sender_thread.join().unwrap();
+}
+
+#[test]
+fn test_incoming_policy_context() {
+    // Two identical files, sent once & copied by the receiver
+    let tmp_dir = TempDir::new("test_incoming_policy_context").unwrap();
+    let paths: Vec<_> = ["first.txt", "second.txt"]
+        .iter()
+        .map(|name| {
+            let path = tmp_dir.path().join(name);
+            writeln!(File::create(&path).unwrap(), "Test File").unwrap();
+            path
+        })
+        .collect();
+
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+    let limits = TransferLimits {
+        max_size: Some(4),
+        max_files: Some(2),
+    };
+    receiver.set_limits(limits);
+    assert_eq!(receiver.get_limits(), limits);
+
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+    let sender_thread = thread::spawn(move || {
+        sender.handshake(&mut senderstream).unwrap();
+        let mut info = TransferInfoBuilder::new();
+        for path in &paths {
+            info = info.add_file(path).unwrap();
+        }
+        assert!(sender.outgoing(&mut senderstream, &info.finalize()).is_ok());
+    });
+
+    receiver.handshake(&mut receiverstream).unwrap();
+    let mut seen = None;
+    let verify = WithPolicy(|_: &TransferInfo, policy: &TransferPolicy| {
+        seen = Some(policy.clone());
+        false
+    });
+    assert_err!(
+        receiver
+            .incoming(&mut receiverstream, Some(verify))
+            .err()
+            .unwrap()
+            .downcast_ref::<PortalError>(),
+        Some(PortalError::Cancelled)
+    );
+    sender_thread.join().unwrap();
+
+    let policy = seen.unwrap();
+    assert_eq!(policy.limits, limits);
+    assert_eq!(policy.total_size, 10);
+    assert_eq!(policy.file_count, 2);
+    assert_eq!(policy.copies, 1);
+    assert!(policy.duplicate_names.is_empty());
+    assert!(policy.exceeds_size());
+    assert!(!policy.exceeds_files());
+}
+
+#[test]
+fn test_incoming_quota() {
+    // Two identical files, sent once & copied by the receiver
+    let tmp_dir = TempDir::new("test_incoming_quota").unwrap();
+    let paths: Vec<_> = ["first.txt", "second.txt"]
+        .iter()
+        .map(|name| {
+            let path = tmp_dir.path().join(name);
+            writeln!(File::create(&path).unwrap(), "Test File").unwrap();
+            path
+        })
+        .collect();
+
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+
+    // The copy counts as a second file, even though it is only sent once
+    let policy = ReceivePolicy {
+        max_files: Some(1),
+        ..Default::default()
+    };
+    receiver.set_receive_policy(policy);
+    assert_eq!(receiver.get_receive_policy(), policy);
+
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+    let sender_thread = thread::spawn(move || {
+        sender.handshake(&mut senderstream).unwrap();
+        let mut info = TransferInfoBuilder::new();
+        for path in &paths {
+            info = info.add_file(path).unwrap();
+        }
+        assert!(sender.outgoing(&mut senderstream, &info.finalize()).is_ok());
+    });
+
+    // The transfer is rejected without consulting the verify callback
+    receiver.handshake(&mut receiverstream).unwrap();
+    let verify = |_: &TransferInfo| -> bool { panic!("verify callback was consulted") };
+    let err = receiver
+        .incoming(&mut receiverstream, Some(verify))
+        .err()
+        .unwrap();
+    assert!(matches!(
+        err.downcast_ref::<PortalError>(),
+        Some(PortalError::QuotaExceeded(_))
+    ));
+    sender_thread.join().unwrap();
+}
+
+#[test]
+fn test_recv_stream_quota() {
+    let tmp_dir = TempDir::new("test_recv_stream_quota").unwrap();
+    let outdir = TempDir::new("test_recv_stream_quota_out").unwrap();
+
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+    receiver.set_receive_policy(ReceivePolicy {
+        max_total_bytes: Some(2 * CHUNK_SIZE as u64),
+        ..Default::default()
+    });
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+
+    // A stream announces no size, so the quota is enforced as it arrives
+    let mut info = TransferInfo::empty();
+    info.add_stream(tmp_dir.path(), "stream.bin").unwrap();
+    let sender_thread = thread::spawn(move || {
+        sender.handshake(&mut senderstream).unwrap();
+        let mut outgoing = sender.outgoing(&mut senderstream, &info).unwrap();
+        let (_, metadata) = outgoing.next().unwrap();
+        let mut reader = std::io::Cursor::new(vec![7u8; 4 * CHUNK_SIZE]);
+        let _ = sender.send_stream(
+            &mut senderstream,
+            &mut reader,
+            &metadata.filename,
+            NO_PROGRESS_CALLBACK,
+        );
+    });
+
+    receiver.handshake(&mut receiverstream).unwrap();
+    let expected = receiver
+        .incoming(&mut receiverstream, NO_VERIFY_CALLBACK)
+        .unwrap()
+        .next()
+        .unwrap();
+    let err = receiver
+        .recv_file(
+            &mut receiverstream,
+            outdir.path(),
+            Some(&expected),
+            NO_PROGRESS_CALLBACK,
+        )
+        .err()
+        .unwrap();
+    assert!(matches!(
+        err.downcast_ref::<PortalError>(),
+        Some(PortalError::QuotaExceeded(_))
+    ));
+    sender_thread.join().unwrap();
+
+    // Nothing past the quota was written
+    let received = std::fs::metadata(outdir.path().join("stream.bin")).unwrap();
+    assert!(received.len() <= 2 * CHUNK_SIZE as u64);
+}
+
+#[test]
+fn test_constant_memory() {
+    let tmp_dir = TempDir::new("test_constant_memory").unwrap();
+    let indir = tmp_dir.path().join("in");
+    let outdir = tmp_dir.path().join("out");
+    std::fs::create_dir_all(&indir).unwrap();
+    std::fs::create_dir_all(&outdir).unwrap();
+
+    // Transfer a file then a stream of this many chunks, returning the
+    // allocations made by each side
+    let transfer = |codec: CodecVersion, chunks: usize| {
+        let contents = vec![7u8; chunks * CHUNK_SIZE];
+        let path = indir.join("file.bin");
+        std::fs::write(&path, &contents).unwrap();
+
+        let build = |direction| {
+            let mut portal = PortalBuilder::new(direction)
+                .id("id")
+                .password("test")
+                .codec(codec)
+                .constant_memory(true)
+                .build()
+                .unwrap();
+            portal.set_key(vec![1u8; 32]);
+            portal
+        };
+        let mut sender = build(Direction::Sender);
+        let mut receiver = build(Direction::Receiver);
+
+        // The wire & output are allocated up front, so that only the
+        // Portals are counted
+        let mut wire = vec![0u8; 2 * contents.len() + chunks * 1024];
+        let mut output = vec![0u8; contents.len()];
+        let (_, sent) = count_allocations(|| {
+            let mut writer = &mut wire[..];
+            sender
+                .send_file(&mut writer, &path, NO_PROGRESS_CALLBACK)
+                .unwrap();
+            let mut reader = &contents[..];
+            sender
+                .send_stream(&mut writer, &mut reader, "stream.bin", NO_PROGRESS_CALLBACK)
+                .unwrap();
+        });
+        let (_, received) = count_allocations(|| {
+            let mut reader = &wire[..];
+            let metadata = receiver
+                .recv_file(&mut reader, &outdir, None, NO_PROGRESS_CALLBACK)
+                .unwrap();
+            assert_eq!(metadata.strategy, WriteStrategy::Buffered);
+            let mut writer = &mut output[..];
+            receiver
+                .recv_into(&mut reader, &mut writer, None, NO_PROGRESS_CALLBACK)
+                .unwrap();
+        });
+        assert_eq!(std::fs::read(outdir.join("file.bin")).unwrap(), contents);
+        assert_eq!(output, contents);
+        (sent, received)
+    };
+
+    for codec in [CodecVersion::V1, CodecVersion::V2] {
+        let (small_sent, small_received) = transfer(codec, 2);
+        let (large_sent, large_received) = transfer(codec, 40);
+
+        // Nothing is allocated per chunk, nor as large as a chunk
+        assert_eq!(small_sent.count, large_sent.count, "{:?}", codec);
+        assert_eq!(small_received.count, large_received.count, "{:?}", codec);
+        assert!(large_sent.largest < CHUNK_SIZE, "{:?}", large_sent);
+        assert!(large_received.largest < CHUNK_SIZE, "{:?}", large_received);
+    }
+}
+
+#[test]
+fn test_recv_into_writer() {
+    let tmp_dir = TempDir::new("test_recv_into_writer").unwrap();
+    let contents = (0..3 * CHUNK_SIZE + 5)
+        .map(|i| i as u8)
+        .collect::<Vec<u8>>();
+    let file_path = tmp_dir.path().join("file.bin");
+    std::fs::write(&file_path, &contents).unwrap();
+
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+
+    // A file of known size, then a stream
+    let mut info = TransferInfo::empty();
+    info.add_file(&file_path).unwrap();
+    info.add_stream(tmp_dir.path(), "stream.bin").unwrap();
+    let streamed = contents.clone();
+    let sender_thread = thread::spawn(move || {
+        sender.handshake(&mut senderstream).unwrap();
+        let outgoing = sender.outgoing(&mut senderstream, &info).unwrap();
+        let outgoing = outgoing
+            .map(|(path, metadata)| (path.clone(), metadata.clone()))
+            .collect::<Vec<_>>();
+        let (path, _) = &outgoing[0];
+        sender
+            .send_file(&mut senderstream, path, NO_PROGRESS_CALLBACK)
+            .unwrap();
+        let (_, metadata) = &outgoing[1];
+        let mut reader = std::io::Cursor::new(streamed);
+        sender
+            .send_stream(
+                &mut senderstream,
+                &mut reader,
+                &metadata.filename,
+                NO_PROGRESS_CALLBACK,
+            )
+            .unwrap();
+    });
+
+    receiver.handshake(&mut receiverstream).unwrap();
+    let incoming = receiver
+        .incoming(&mut receiverstream, NO_VERIFY_CALLBACK)
+        .unwrap()
+        .collect::<Vec<_>>();
+    for expected in &incoming {
+        let mut output = Vec::new();
+        let metadata = receiver
+            .recv_into(
+                &mut receiverstream,
+                &mut output,
+                Some(expected),
+                NO_PROGRESS_CALLBACK,
+            )
+            .unwrap();
+        assert_eq!(output, contents);
+        assert_eq!(metadata.filesize, contents.len() as u64);
+    }
+    sender_thread.join().unwrap();
+}
+
+#[test]
+fn test_incremental_digests() {
+    use sha2::{Digest, Sha256};
+    let tmp_dir = TempDir::new("test_incremental_digests").unwrap();
+    let contents = noise(2 * CHUNK_SIZE + 7, 8);
+    let file_path = tmp_dir.path().join("file.bin");
+    std::fs::write(&file_path, &contents).unwrap();
+    let digest: [u8; 32] = Sha256::digest(&contents).into();
+
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+    receiver.set_digests(true);
+    sender.set_digests(true);
+    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+
+    let sender_thread = thread::spawn(move || {
+        sender.handshake(&mut senderstream).unwrap();
+        for _ in 0..2 {
+            sender
+                .send_file(&mut senderstream, &file_path, NO_PROGRESS_CALLBACK)
+                .unwrap();
+        }
+        sender.get_last_digest()
+    });
+
+    // Both sides hash the data as it passes through
+    let out_dir = TempDir::new("test_incremental_digests_out").unwrap();
+    receiver.handshake(&mut receiverstream).unwrap();
+    let metadata = receiver
+        .recv_file(
+            &mut receiverstream,
+            out_dir.path(),
+            None,
+            NO_PROGRESS_CALLBACK,
+        )
+        .unwrap();
+    assert_eq!(metadata.digest, Some(digest));
+    assert_eq!(receiver.get_last_digest(), Some(digest));
+
+    // A file that doesn't match the expected digest is discarded
+    let expected = crate::FileEntry {
+        digest: Some([0u8; 32]),
+        ..metadata.clone()
+    };
+    let err = receiver
+        .recv_file(
+            &mut receiverstream,
+            out_dir.path(),
+            Some(&expected),
+            NO_PROGRESS_CALLBACK,
+        )
+        .unwrap_err();
+    assert_eq!(err.downcast_ref(), Some(&PortalError::DigestMismatch));
+    assert!(!out_dir.path().join("file.bin").exists());
+    assert_eq!(sender_thread.join().unwrap(), Some(digest));
+}
+
+#[test]
+fn test_transcripts_match() {
+    let tmp_dir = TempDir::new("test_transcripts_match").unwrap();
+    let mut info = TransferInfo::empty();
+    for (name, len) in [("first.bin", 2 * CHUNK_SIZE + 3), ("second.bin", 10)] {
+        let path = tmp_dir.path().join(name);
+        std::fs::write(&path, noise(len, len as u64)).unwrap();
+        info.add_file(&path).unwrap();
+    }
+
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+    receiver.set_digests(true);
+    sender.set_digests(true);
     let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
 
     let sender_thread = thread::spawn(move || {
-        // Complete handshake
+        assert!(sender.transcript().is_err());
         sender.handshake(&mut senderstream).unwrap();
-
-        let info = TransferInfoBuilder::new()
-            .add_file(&Path::new(&file_path_str))
-            .unwrap()
-            .finalize();
-
-        for (path, _metadata) in sender.outgoing(&mut senderstream, &info).unwrap() {
-            // Send the file
-            let result = sender.send_file(&mut senderstream, &path, NO_PROGRESS_CALLBACK);
-            assert!(result.is_ok());
+        let outgoing = sender.outgoing(&mut senderstream, &info).unwrap();
+        let paths = outgoing.map(|(path, _)| path.clone()).collect::<Vec<_>>();
+        for path in &paths {
+            sender
+                .send_file(&mut senderstream, path, NO_PROGRESS_CALLBACK)
+                .unwrap();
         }
+        sender.transcript().unwrap()
     });
 
-    // VerifyCallback
-    fn verify_callback(info: &TransferInfo) -> bool {
-        assert!(info
-            .all
-            .iter()
-            .any(|m| m.filename.as_str() == "randomfile.txt"));
-        true
-    }
-
-    // Complete handshake
+    let out_dir = TempDir::new("test_transcripts_match_out").unwrap();
     receiver.handshake(&mut receiverstream).unwrap();
-
-    for m in receiver
-        .incoming(&mut receiverstream, Some(verify_callback))
+    let incoming = receiver
+        .incoming(&mut receiverstream, NO_VERIFY_CALLBACK)
         .unwrap()
-    {
-        let d = receiver
+        .collect::<Vec<_>>();
+    for expected in &incoming {
+        receiver
             .recv_file(
                 &mut receiverstream,
-                tmp_dir.path(),
-                Some(&m),
+                out_dir.path(),
+                Some(expected),
                 NO_PROGRESS_CALLBACK,
             )
             .unwrap();
-        assert_eq!(d, m);
     }
 
-    sender_thread.join().unwrap();
+    // Both peers record the same session & seal it alike
+    let ours = receiver.transcript().unwrap();
+    let theirs = sender_thread.join().unwrap();
+    assert!(ours.matches(&theirs));
+    assert_eq!(ours.body.manifests.len(), 1);
+    assert_eq!(ours.body.files.len(), 2);
+    assert_eq!(ours.body.bytes, 2 * CHUNK_SIZE as u64 + 13);
+    assert!(ours.body.files.iter().all(|f| f.digest.is_some()));
+
+    // A stored transcript can be checked with the session key
+    let key = receiver.get_key().clone().unwrap();
+    let stored = Transcript::from_bytes(&theirs.to_bytes().unwrap()).unwrap();
+    assert!(stored.verify(&key));
+    let mut altered = stored.clone();
+    altered.body.bytes += 1;
+    assert!(!altered.verify(&key));
+    assert!(!altered.matches(&ours));
+
+    // But it isn't a signature, either peer can seal any body
+    let resealed = Transcript::seal(altered.body, &key, stored.started).unwrap();
+    assert!(resealed.verify(&key));
 }
 
-#[test]
-fn portal_map_bad_path() {
-    let dir = Direction::Receiver;
-    let pass = "test".to_string();
-    let receiver = Portal::init(dir, "id".to_string(), pass).unwrap();
-    let result = receiver.map_writeable_file(&Path::new("/notafile").to_path_buf(), 12);
-    assert!(result.is_err());
+/// Pseudo-random data that doesn't repeat itself, unlike the patterns above
+fn noise(len: usize, seed: u64) -> Vec<u8> {
+    let mut state = seed;
+    (0..len)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 56) as u8
+        })
+        .collect()
 }
 
 #[test]
-fn portal_handshake_no_peer() {
-    let dir = Direction::Receiver;
-    let pass = "test".to_string();
-    let mut receiver = Portal::init(dir, "id".to_string(), pass).unwrap();
-
-    let mut stream = SyncMockStream::new();
+fn test_dedup_roundtrip() {
+    let tmp_dir = TempDir::new("test_dedup_roundtrip").unwrap();
+    let indir = tmp_dir.path().join("in");
+    let outdir = tmp_dir.path().join("out");
+    std::fs::create_dir_all(&indir).unwrap();
+    std::fs::create_dir_all(&outdir).unwrap();
+
+    // A region repeated within a file, then in another at a new offset
+    let region = noise(10 * CHUNK_SIZE, 1);
+    let between = noise(CHUNK_SIZE + 7, 2);
+    let first = [&region[..], &between, &region].concat();
+    let second = [&b"shifted"[..], &between, &region].concat();
+    let files = [("first.img", &first), ("second.img", &second)];
+    for (name, contents) in &files {
+        std::fs::write(indir.join(name), contents).unwrap();
+    }
 
-    // Queue a message that will make Protocol::connect() mad
-    let values = EncryptedMessage::default();
-    let message = PortalMessage::EncryptedDataHeader(values);
-    stream.push_bytes_to_read(&bincode::serialize(&message).unwrap());
+    // Returns the length of the transfer on the wire
+    let transfer = |dedup: bool| {
+        let build = |direction| {
+            let mut portal = PortalBuilder::new(direction)
+                .id("id")
+                .password("test")
+                .dedup(dedup)
+                .build()
+                .unwrap();
+            portal.set_key(vec![1u8; 32]);
+            portal
+        };
+        let mut sender = build(Direction::Sender);
+        let mut receiver = build(Direction::Receiver);
+
+        let mut wire = Vec::new();
+        for (name, _) in &files {
+            let path = indir.join(name);
+            sender
+                .send_file(&mut wire, &path, NO_PROGRESS_CALLBACK)
+                .unwrap();
+        }
+        let mut reader = &wire[..];
+        for (name, contents) in &files {
+            let metadata = receiver
+                .recv_file(&mut reader, &outdir, None, NO_PROGRESS_CALLBACK)
+                .unwrap();
+            assert_eq!(metadata.dedup, dedup);
+            assert_eq!(std::fs::read(outdir.join(name)).unwrap(), **contents);
+        }
+        assert!(reader.is_empty());
+        wire.len()
+    };
 
-    let result = receiver.handshake(&mut stream);
-    assert!(result.is_err());
-    assert_err!(
-        result.err().unwrap().downcast_ref::<PortalError>(),
-        Some(PortalError::NoPeer)
-    );
+    // Repeated data is sent once, & referred to thereafter
+    let total = first.len() + second.len();
+    let plain = transfer(false);
+    let deduplicated = transfer(true);
+    assert!(plain > total);
+    assert!(deduplicated < total / 2, "{} of {}", deduplicated, total);
 }
 
 #[test]
-fn portal_send_file_no_peer() {
-    let dir = Direction::Sender;
-    let pass = "test".to_string();
-    let mut portal = Portal::init(dir, "id".to_string(), pass).unwrap();
+fn test_dedup_digests() {
+    use sha2::{Digest, Sha256};
+    let tmp_dir = TempDir::new("test_dedup_digests").unwrap();
+    let outdir = tmp_dir.path().join("out");
+    std::fs::create_dir_all(&outdir).unwrap();
+
+    // The second half is sent as references to the first
+    let region = noise(4 * CHUNK_SIZE, 3);
+    let contents = [&region[..], &region].concat();
+    let file_path = tmp_dir.path().join("file.img");
+    std::fs::write(&file_path, &contents).unwrap();
+    let digest: [u8; 32] = Sha256::digest(&contents).into();
+
+    let build = |direction| {
+        let mut portal = PortalBuilder::new(direction)
+            .id("id")
+            .password("test")
+            .dedup(true)
+            .digests(true)
+            .build()
+            .unwrap();
+        portal.set_key(vec![1u8; 32]);
+        portal
+    };
+    let mut sender = build(Direction::Sender);
+    let mut receiver = build(Direction::Receiver);
 
-    // will return error
-    let mut stream = SyncMockStream::new();
-    let result = portal.send_file(
-        &mut stream,
-        &Path::new("/tmp/passwd").to_path_buf(),
-        NO_PROGRESS_CALLBACK,
-    );
-    assert!(result.is_err());
-    assert_err!(
-        result.err().unwrap().downcast_ref::<PortalError>(),
-        Some(PortalError::NoPeer)
-    );
+    let mut wire = Vec::new();
+    sender
+        .send_file(&mut wire, &file_path, NO_PROGRESS_CALLBACK)
+        .unwrap();
+    assert!(wire.len() < contents.len());
+
+    // Both peers hash the file's data, not the chunks on the wire
+    let metadata = receiver
+        .recv_file(&mut &wire[..], &outdir, None, NO_PROGRESS_CALLBACK)
+        .unwrap();
+    assert!(metadata.dedup);
+    assert_eq!(metadata.digest, Some(digest));
+    assert_eq!(receiver.get_last_digest(), Some(digest));
+    assert_eq!(sender.get_last_digest(), Some(digest));
 }
 
 #[test]
-fn portal_recv_file_no_peer() {
-    let dir = Direction::Sender;
-    let pass = "test".to_string();
-    let mut portal = Portal::init(dir, "id".to_string(), pass).unwrap();
+fn test_dedup_negotiation() {
+    let tmp_dir = TempDir::new("test_dedup_negotiation").unwrap();
+    let file_path = tmp_dir.path().join("file.bin");
+    let contents = [noise(3 * CHUNK_SIZE, 3), noise(3 * CHUNK_SIZE, 3)].concat();
+    std::fs::write(&file_path, &contents).unwrap();
+
+    // Deduplication is only used when both peers offer it
+    for offered in [false, true] {
+        let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+        let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+        sender.set_dedup(true);
+        receiver.set_dedup(offered);
+        let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+
+        let path = file_path.clone();
+        let sender_thread = thread::spawn(move || {
+            sender.handshake(&mut senderstream).unwrap();
+            sender
+                .send_file(&mut senderstream, &path, NO_PROGRESS_CALLBACK)
+                .unwrap();
+            sender.get_dedup()
+        });
+
+        let out_dir = TempDir::new("test_dedup_negotiation_out").unwrap();
+        receiver.handshake(&mut receiverstream).unwrap();
+        let metadata = receiver
+            .recv_file(
+                &mut receiverstream,
+                out_dir.path(),
+                None,
+                NO_PROGRESS_CALLBACK,
+            )
+            .unwrap();
+        assert_eq!(metadata.dedup, offered);
+        assert_eq!(receiver.get_dedup(), offered);
+        assert_eq!(sender_thread.join().unwrap(), offered);
 
-    // will panic due to lack of peer
-    let mut stream = SyncMockStream::new();
-    let result = portal.recv_file(&mut stream, Path::new("/tmp"), None, NO_PROGRESS_CALLBACK);
-    assert!(result.is_err());
-    assert_err!(
-        result.err().unwrap().downcast_ref::<PortalError>(),
-        Some(PortalError::NoPeer)
-    );
+        let received = std::fs::read(out_dir.path().join("file.bin")).unwrap();
+        assert!(received == contents);
+    }
 }
 
 #[test]
-fn test_recv_file_bad_outdir() {
-    // Create test file
-    let tmp_dir = TempDir::new("test_recv_file_bad_outdir").unwrap();
-    let file_path = tmp_dir.path().join("randomfile.txt");
-    let file_path_str = Path::new(&file_path.to_str().unwrap().to_owned()).to_path_buf();
-    let mut tmp_file = File::create(file_path).unwrap();
-    writeln!(tmp_file, "Test File").unwrap();
-
-    // receiver
-    let dir = Direction::Receiver;
-    let pass = "test".to_string();
-    let mut receiver = Portal::init(dir, "id".to_string(), pass).unwrap();
-
-    // sender
-    let dir = Direction::Sender;
-    let pass = "test".to_string();
-    let mut sender = Portal::init(dir, "id".to_string(), pass).unwrap();
-
-    // mock channel
-    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
-
-    let sender_thread = thread::spawn(move || {
-        // Complete handshake
-        sender.handshake(&mut senderstream).unwrap();
+fn test_bind_chunks_negotiation() {
+    let tmp_dir = TempDir::new("test_bind_chunks_negotiation").unwrap();
+    let files = [
+        (tmp_dir.path().join("first.bin"), noise(2 * CHUNK_SIZE, 6)),
+        (tmp_dir.path().join("second.bin"), noise(CHUNK_SIZE + 1, 7)),
+    ];
+    for (path, contents) in &files {
+        std::fs::write(path, contents).unwrap();
+    }
 
-        // Send the file
-        let result = sender.send_file(&mut senderstream, &file_path_str, NO_PROGRESS_CALLBACK);
-        assert!(result.is_ok());
-        result.unwrap()
-    });
+    // Chunks are only bound when both peers offer it
+    for offered in [false, true] {
+        let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+        let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+        receiver.set_bind_chunks(offered);
+        let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+
+        let paths: Vec<_> = files.iter().map(|(path, _)| path.clone()).collect();
+        let sender_thread = thread::spawn(move || {
+            sender.handshake(&mut senderstream).unwrap();
+            for path in &paths {
+                sender
+                    .send_file(&mut senderstream, path, NO_PROGRESS_CALLBACK)
+                    .unwrap();
+            }
+            sender.get_bind_chunks()
+        });
+
+        let out_dir = TempDir::new("test_bind_chunks_negotiation_out").unwrap();
+        receiver.handshake(&mut receiverstream).unwrap();
+        for (path, contents) in &files {
+            receiver
+                .recv_file(
+                    &mut receiverstream,
+                    out_dir.path(),
+                    None,
+                    NO_PROGRESS_CALLBACK,
+                )
+                .unwrap();
+            let name = path.file_name().unwrap();
+            assert_eq!(std::fs::read(out_dir.path().join(name)).unwrap(), *contents);
+        }
+        assert_eq!(receiver.get_bind_chunks(), offered);
+        assert_eq!(sender_thread.join().unwrap(), offered);
+    }
+}
 
-    // Complete handshake
-    receiver.handshake(&mut receiverstream).unwrap();
+#[test]
+fn test_dedup_unexpected() {
+    let tmp_dir = TempDir::new("test_dedup_unexpected").unwrap();
+    let file_path = tmp_dir.path().join("file.bin");
+    std::fs::write(&file_path, noise(CHUNK_SIZE, 4)).unwrap();
+
+    let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+    sender.set_key(vec![1u8; 32]);
+    sender.set_dedup(true);
+    let mut wire = Vec::new();
+    sender
+        .send_file(&mut wire, &file_path, NO_PROGRESS_CALLBACK)
+        .unwrap();
 
-    let result = receiver.recv_file(
-        &mut receiverstream,
-        Path::new("/tmp/test.txt"),
-        None,
-        NO_PROGRESS_CALLBACK,
+    // Receivers that didn't offer deduplication refuse it
+    let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+    receiver.set_key(vec![1u8; 32]);
+    let result = receiver.recv_file(&mut &wire[..], tmp_dir.path(), None, NO_PROGRESS_CALLBACK);
+    assert_err!(
+        result.err().unwrap().downcast_ref::<PortalError>(),
+        Some(PortalError::UnexpectedMessage)
     );
-    assert!(result.is_err());
+
+    // References can't be copied back out of a writer
+    receiver.set_dedup(true);
+    let mut output = Vec::new();
+    let result = receiver.recv_into(&mut &wire[..], &mut output, None, NO_PROGRESS_CALLBACK);
     assert_err!(
         result.err().unwrap().downcast_ref::<PortalError>(),
-        Some(PortalError::BadDirectory)
+        Some(PortalError::BadConfig(_))
     );
+}
 
-    sender_thread.join().unwrap();
+#[test]
+fn test_file_changed_during_transfer() {
+    let tmp_dir = TempDir::new("test_file_changed_during_transfer").unwrap();
+    let indir = tmp_dir.path().join("in");
+    let outdir = tmp_dir.path().join("out");
+    std::fs::create_dir_all(&indir).unwrap();
+    std::fs::create_dir_all(&outdir).unwrap();
+    let next = noise(CHUNK_SIZE + 3, 4);
+    std::fs::write(indir.join("next.txt"), &next).unwrap();
+
+    // The file shrinks between windows when mapped, otherwise after its
+    // first chunk, & the next file is still received
+    let modes = [
+        (
+            "mapped",
+            PortalBuilder::new(Direction::Sender).mmap_window(CHUNK_SIZE),
+        ),
+        (
+            "buffered",
+            PortalBuilder::new(Direction::Sender).constant_memory(true),
+        ),
+        (
+            "deduplicated",
+            PortalBuilder::new(Direction::Sender).dedup(true),
+        ),
+    ];
+    for (mode, builder) in modes {
+        let mut sender = builder.id("id").password("test").build().unwrap();
+        sender.set_key(vec![1u8; 32]);
+        let window = sender.get_mmap_window();
+        let threshold = if mode == "mapped" { window } else { 1 };
+        let path = indir.join("rotated.log");
+        std::fs::write(&path, noise(3 * window, 5)).unwrap();
+        let mut receiver = PortalBuilder::new(Direction::Receiver)
+            .id("id")
+            .password("test")
+            .dedup(true)
+            .build()
+            .unwrap();
+        receiver.set_key(vec![1u8; 32]);
+
+        let mut wire = Vec::new();
+        let mut truncated = false;
+        let shrink = |transferred: usize, _delta: usize| {
+            if !truncated && transferred >= threshold {
+                File::options()
+                    .write(true)
+                    .open(&path)
+                    .unwrap()
+                    .set_len(transferred as u64 + 10)
+                    .unwrap();
+                truncated = true;
+            }
+        };
+        let err = sender
+            .send_file(&mut wire, &path, Some(shrink))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref(),
+            Some(&PortalError::FileChangedDuringTransfer),
+            "{}",
+            mode
+        );
+        sender
+            .send_file(&mut wire, &indir.join("next.txt"), NO_PROGRESS_CALLBACK)
+            .unwrap();
+
+        let mut reader = &wire[..];
+        let err = receiver
+            .recv_file(&mut reader, &outdir, None, NO_PROGRESS_CALLBACK)
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref(),
+            Some(&PortalError::FileChangedDuringTransfer),
+            "{}",
+            mode
+        );
+        assert!(!outdir.join("rotated.log").exists(), "{}", mode);
+        receiver
+            .recv_file(&mut reader, &outdir, None, NO_PROGRESS_CALLBACK)
+            .unwrap();
+        assert_eq!(std::fs::read(outdir.join("next.txt")).unwrap(), next);
+        assert!(reader.is_empty());
+    }
 }
 
 #[test]
-fn test_incoming_cancel() {
-    // Create test file
-    let tmp_dir = TempDir::new("test_recv_file_bad_outdir").unwrap();
-    let file_path = tmp_dir.path().join("randomfile.txt");
-    let file_path_str = Path::new(&file_path.to_str().unwrap().to_owned()).to_path_buf();
-    let mut tmp_file = File::create(file_path).unwrap();
-    writeln!(tmp_file, "Test File").unwrap();
+fn test_file_aborted_authenticated() {
+    let key = [1u8; 32];
+    let codec = CodecVersion::V1;
+
+    // Helper: re-frame the only header on the wire as another message
+    let reframe = |wire: Vec<u8>, aborted: bool| {
+        let header = match PortalMessage::parse(&wire).unwrap() {
+            PortalMessage::EncryptedDataHeader(h) | PortalMessage::FileAborted(h) => h,
+            _ => unreachable!(),
+        };
+        let msg = match aborted {
+            true => PortalMessage::FileAborted(header),
+            false => PortalMessage::EncryptedDataHeader(header),
+        };
+        codec.encode(&msg).unwrap()
+    };
+    let read = |wire: &[u8]| {
+        let mut reader = wire;
+        let mut nseq = NonceSequence::new();
+        EncryptedChannel::new(&mut reader, &key, &mut nseq)
+            .read_chunk(&mut [0u8; 16])
+            .err()
+    };
 
-    // receiver
-    let dir = Direction::Receiver;
-    let pass = "test".to_string();
-    let mut receiver = Portal::init(dir, "id".to_string(), pass).unwrap();
+    // A genuine abort is reported as such
+    let mut nseq = NonceSequence::new();
+    let mut wire = Vec::new();
+    EncryptedChannel::new(&mut wire, &key, &mut nseq)
+        .abort()
+        .unwrap();
+    assert_eq!(
+        read(&wire).unwrap().downcast_ref(),
+        Some(&PortalError::FileChangedDuringTransfer)
+    );
 
-    // sender
-    let dir = Direction::Sender;
-    let pass = "test".to_string();
-    let mut sender = Portal::init(dir, "id".to_string(), pass).unwrap();
+    // Neither an abort nor an end-of-stream marker can pass for the other
+    let forged = reframe(wire, false);
+    assert_ne!(
+        read(&forged).unwrap().downcast_ref(),
+        Some(&PortalError::FileChangedDuringTransfer)
+    );
+    let mut nseq = NonceSequence::new();
+    let mut wire = Vec::new();
+    EncryptedChannel::new(&mut wire, &key, &mut nseq)
+        .finish()
+        .unwrap();
+    assert!(read(&wire).is_none());
+    let forged = reframe(wire, true);
+    assert_ne!(
+        read(&forged).unwrap().downcast_ref(),
+        Some(&PortalError::FileChangedDuringTransfer)
+    );
+}
 
-    // mock channel
-    let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
+#[test]
+fn test_file_changed_since_offered() {
+    let tmp_dir = TempDir::new("test_file_changed_since_offered").unwrap();
+    let indir = tmp_dir.path().join("in");
+    let outdir = tmp_dir.path().join("out");
+    std::fs::create_dir_all(&indir).unwrap();
+    std::fs::create_dir_all(&outdir).unwrap();
+    let (grown, rewritten, unchanged) = (
+        indir.join("grown.log"),
+        indir.join("rewritten.txt"),
+        indir.join("unchanged.txt"),
+    );
 
-    let sender_thread = thread::spawn(move || {
-        // Complete handshake
-        sender.handshake(&mut senderstream).unwrap();
+    // A rewrite keeping the size & modification time is only noticed
+    // with a quick hash
+    for quick in [false, true] {
+        std::fs::write(&grown, b"first").unwrap();
+        std::fs::write(&rewritten, b"original").unwrap();
+        std::fs::write(&unchanged, b"unchanged").unwrap();
+        let mut info = TransferInfo::empty();
+        for path in [&grown, &rewritten, &unchanged] {
+            info.add_file(path).unwrap();
+        }
+        if quick {
+            info.add_quick_hashes().unwrap();
+        }
 
-        let info = TransferInfoBuilder::new()
-            .add_file(&Path::new(&file_path_str))
+        let mut sender = Portal::init(Direction::Sender, "id".into(), "test".into()).unwrap();
+        let mut receiver = Portal::init(Direction::Receiver, "id".into(), "test".into()).unwrap();
+        sender.set_key(vec![1u8; 32]);
+        receiver.set_key(vec![1u8; 32]);
+        let mut wire = Vec::new();
+        let paths: Vec<_> = sender
+            .outgoing(&mut wire, &info)
             .unwrap()
-            .finalize();
-
-        let result = sender.outgoing(&mut senderstream, &info);
-        assert!(result.is_ok());
-    });
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        // Changed after the receiver accepted the transfer
+        std::fs::write(&grown, b"first, then much more").unwrap();
+        let modified = rewritten.metadata().unwrap().modified().unwrap();
+        std::fs::write(&rewritten, b"replaced").unwrap();
+        File::options()
+            .write(true)
+            .open(&rewritten)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
 
-    // VerifyCallback that cancels every download
-    fn cancel_all(_info: &TransferInfo) -> bool {
-        false
+        let skipped = |result: Result<(), Box<dyn std::error::Error>>| match result {
+            Ok(_) => false,
+            Err(e) => {
+                assert_eq!(
+                    e.downcast_ref(),
+                    Some(&PortalError::FileChangedDuringTransfer)
+                );
+                true
+            }
+        };
+        let sent: Vec<_> = paths
+            .iter()
+            .map(|path| {
+                skipped(
+                    sender
+                        .send_file(&mut wire, path, NO_PROGRESS_CALLBACK)
+                        .map(|_| ()),
+                )
+            })
+            .collect();
+        assert_eq!(sent, [true, quick, false]);
+
+        // The receiver discards the skipped files & gets the rest
+        let mut reader = &wire[..];
+        let incoming: Vec<_> = receiver
+            .incoming(&mut reader, NO_VERIFY_CALLBACK)
+            .unwrap()
+            .collect();
+        let received: Vec<_> = incoming
+            .iter()
+            .map(|expected| {
+                let result =
+                    receiver.recv_file(&mut reader, &outdir, Some(expected), NO_PROGRESS_CALLBACK);
+                let skipped = skipped(result.map(|_| ()));
+                assert_eq!(outdir.join(&expected.filename).exists(), !skipped);
+                skipped
+            })
+            .collect();
+        assert_eq!(received, sent);
+        assert!(reader.is_empty());
+        std::fs::remove_dir_all(&outdir).unwrap();
+        std::fs::create_dir_all(&outdir).unwrap();
     }
-
-    // Complete handshake
-    receiver.handshake(&mut receiverstream).unwrap();
-
-    let result = receiver.incoming(&mut receiverstream, Some(cancel_all));
-    assert!(result.is_err());
-    assert_err!(
-        result.err().unwrap().downcast_ref::<PortalError>(),
-        Some(PortalError::Cancelled)
-    );
-
-    sender_thread.join().unwrap();
 }
 
 #[test]
@@ -438,7 +2993,7 @@ fn test_incoming_default_verify_accepts() {
         sender.handshake(&mut senderstream).unwrap();
 
         let info = TransferInfoBuilder::new()
-            .add_file(&Path::new(&file_path_str))
+            .add_file(Path::new(&file_path_str))
             .unwrap()
             .finalize();
 
@@ -477,9 +3032,11 @@ fn test_display_callback() {
     // mock channel
     let (mut senderstream, mut receiverstream) = MockTcpStream::channel();
 
-    // Display callback
-    let progress = move |size: usize| {
+    // Display callback, tracking the deltas
+    let mut received = 0;
+    let progress = move |size: usize, delta: usize| {
         assert!(size as u64 <= file_size);
+        assert!(delta <= size);
     };
 
     let sender_thread = thread::spawn(move || {
@@ -496,11 +3053,53 @@ fn test_display_callback() {
     receiver.handshake(&mut receiverstream).unwrap();
 
     // Receive the file
-    let _result = receiver.recv_file(&mut receiverstream, tmp_dir.path(), None, Some(progress));
+    let _result = receiver.recv_file(
+        &mut receiverstream,
+        tmp_dir.path(),
+        None,
+        Some(|size: usize, delta: usize| {
+            received += delta;
+            assert_eq!(received, size);
+        }),
+    );
+    assert_eq!(received as u64, file_size);
 
     sender_thread.join().unwrap();
 }
 
+#[test]
+fn test_chunk_sizer_bounds() {
+    let mut sizer = ChunkSizer::new(MIN_CHUNK_SIZE, MAX_CHUNK_SIZE);
+    assert_eq!(sizer.size(), CHUNK_SIZE);
+
+    // Steadily improving throughput grows the chunks up to the maximum
+    for i in 1..64 {
+        let len = sizer.size();
+        sizer.record(len, Duration::from_millis(200) / i);
+    }
+    assert_eq!(sizer.size(), MAX_CHUNK_SIZE);
+
+    // Slow chunks shrink them down to the minimum
+    for _ in 0..64 {
+        sizer.record(sizer.size(), Duration::from_secs(1));
+    }
+    assert_eq!(sizer.size(), MIN_CHUNK_SIZE);
+
+    // High latency links are sampled over several round trips
+    let mut sizer = ChunkSizer::new(MIN_CHUNK_SIZE, MAX_CHUNK_SIZE);
+    sizer.set_rtt(Duration::from_millis(100));
+    sizer.record(CHUNK_SIZE, Duration::from_millis(200));
+    assert_eq!(sizer.size(), CHUNK_SIZE);
+    sizer.record(CHUNK_SIZE, Duration::from_millis(200));
+    assert_eq!(sizer.size(), CHUNK_SIZE * 2);
+
+    // A fixed size never changes
+    let mut fixed = ChunkSizer::fixed(CHUNK_SIZE);
+    fixed.record(CHUNK_SIZE, Duration::from_secs(1));
+    fixed.record(CHUNK_SIZE, Duration::from_millis(100));
+    assert_eq!(fixed.size(), CHUNK_SIZE);
+}
+
 #[test]
 fn test_compressed_edwards_size() {
     // The exchanged message is the CompressedEdwardsY + 1 byte for the SPAKE direction
@@ -528,4 +3127,175 @@ fn test_getters_setters() {
     // get/set key
     portal.set_key(vec![0, 1, 2, 3]);
     assert_eq!(&Some(vec![0, 1, 2, 3]), portal.get_key());
+
+    // get/set tuning
+    let tuning = TransferTuning {
+        sequential: true,
+        drop_cache: true,
+    };
+    portal.set_tuning(tuning);
+    assert_eq!(portal.get_tuning(), tuning);
+}
+
+#[test]
+fn test_builder() {
+    let portal = PortalBuilder::new(Direction::Receiver)
+        .id("id")
+        .password("test")
+        .checksums(true)
+        .adaptive_chunks(false)
+        .durability(Durability::PerFile)
+        .strict(true)
+        .relay_secret(b"secret")
+        .priority(Priority::Bulk)
+        .codec(CodecVersion::V1)
+        .build()
+        .unwrap();
+    assert_eq!(portal.get_direction(), Direction::Receiver);
+    assert!(portal.get_checksums());
+    assert!(!portal.get_adaptive_chunks());
+    assert_eq!(portal.get_durability(), Durability::PerFile);
+    assert!(portal.get_strict());
+    assert_eq!(portal.get_relay_secret(), Some(&b"secret"[..]));
+    assert_eq!(portal.get_priority(), Priority::Bulk);
+    assert_eq!(portal.get_codec(), CodecVersion::V1);
+
+    // a shard matches the credentials derived by init_shard
+    let shard = PortalBuilder::new(Direction::Sender)
+        .id("id")
+        .shard("master", 2)
+        .build()
+        .unwrap();
+    let expected = Portal::init_shard(Direction::Sender, "id", "master", 2).unwrap();
+    assert_eq!(shard.get_id(), expected.get_id());
+
+    // invalid combinations are rejected before the handshake
+    let invalid = [
+        PortalBuilder::new(Direction::Sender).password("test"),
+        PortalBuilder::new(Direction::Sender).id("id"),
+        PortalBuilder::new(Direction::Sender)
+            .id("id")
+            .password("test")
+            .psk([7u8; 32]),
+        PortalBuilder::new(Direction::Sender)
+            .id("id")
+            .psk([7u8; 32])
+            .strict(true),
+        PortalBuilder::new(Direction::Sender)
+            .id("id")
+            .password("test")
+            .mmap_window(0),
+        PortalBuilder::new(Direction::Sender)
+            .id("id")
+            .password("test")
+            .codec(CodecVersion(0xff)),
+    ];
+    for builder in invalid.iter() {
+        let err = builder.clone().build().unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<PortalError>(),
+            Some(PortalError::BadConfig(_))
+        ));
+    }
+}
+
+#[test]
+fn test_passphrase_generate() {
+    let phrase = passphrase::generate(3);
+    let words = phrase.split('-').collect::<Vec<&str>>();
+
+    // Every component comes from the word list
+    assert_eq!(words.len(), 3);
+    assert!(words.iter().all(|w| passphrase::is_word(w)));
+
+    // Generated phrases are estimated at full strength
+    assert_eq!(
+        passphrase::estimate_entropy(&phrase),
+        passphrase::entropy(3)
+    );
+
+    // Non-list components are estimated by character class
+    assert!(passphrase::estimate_entropy("password") < passphrase::entropy(3));
+    assert_eq!(passphrase::estimate_entropy(""), 0.0);
+}
+
+#[test]
+fn test_passphrase_words_exclude_separator() {
+    // Spelling out each index with a single word walks the whole list
+    for index in 0..7776 {
+        let word = passphrase::from_value(index, 1);
+        assert!(!word.contains(passphrase::SEPARATOR), "{}", word);
+        assert!(passphrase::is_word(&word), "{}", word);
+        assert_eq!(passphrase::estimate_entropy(&word), passphrase::entropy(1));
+    }
+}
+
+#[test]
+fn test_uri_roundtrip() {
+    // The password is only included when asked for
+    let uri = PortalUri::new("tucking", "relay.example.com", 13265);
+    assert_eq!(
+        uri.to_string(),
+        "portal://tucking@relay.example.com:13265/?v=1"
+    );
+    let with = uri.clone().with_password("ecard-cursive-occupy");
+    assert_eq!(
+        with.to_string(),
+        "portal://tucking@relay.example.com:13265/?v=1&p=ecard-cursive-occupy"
+    );
+
+    // Formatting & parsing are inverses, including for IPv6 relays and
+    // components that need encoding
+    for uri in [
+        uri,
+        with,
+        PortalUri::new("id", "::1", 4000),
+        PortalUri::new("an id@/?", "10.0.0.1", 1).with_password("p&ss=wörd%"),
+    ] {
+        assert_eq!(uri.to_string().parse::<PortalUri>().unwrap(), uri);
+    }
+}
+
+#[test]
+fn test_uri_parse_rules() {
+    let parse = |s: &str| s.parse::<PortalUri>();
+
+    // The port defaults, the path & version are optional
+    let uri = parse("portal://id@relay.example.com").unwrap();
+    assert_eq!(uri.port, crate::DEFAULT_PORT);
+    assert_eq!(
+        uri,
+        parse("portal://id@relay.example.com:13265/?v=1").unwrap()
+    );
+    assert_eq!(parse("portal://id@[::1]/?v=1").unwrap().relay, "::1");
+
+    // Unknown parameters are ignored, newer versions refused
+    assert_eq!(
+        uri,
+        parse("portal://id@relay.example.com/?v=1&qr=1").unwrap()
+    );
+    let err = parse("portal://id@relay.example.com/?v=2").unwrap_err();
+    assert_eq!(
+        err.downcast_ref(),
+        Some(&PortalError::UnsupportedUriVersion(2))
+    );
+
+    // Malformed URIs
+    for bad in [
+        "https://id@relay.example.com",
+        "portal://relay.example.com",
+        "portal://@relay.example.com",
+        "portal://id@",
+        "portal://id@relay.example.com:notaport",
+        "portal://id@[::1/?v=1",
+        "portal://id@relay.example.com/?v=one",
+        "portal://i%zzd@relay.example.com",
+    ] {
+        let err = parse(bad).unwrap_err();
+        assert!(
+            matches!(err.downcast_ref(), Some(PortalError::BadUri(_))),
+            "{}",
+            bad
+        );
+    }
 }
@@ -0,0 +1,189 @@
+//! A record of what crossed the wire in a session, which both peers can
+//! produce independently & compare later, to check that they saw the same
+//! transfer. See [`Portal::transcript`](crate::Portal::transcript).
+//!
+//! The manifests, files, byte counts & fingerprint are authenticated with
+//! an HMAC keyed by the session key, so both peers' transcripts of the
+//! same session carry the same MAC. This isn't a signature: either peer
+//! can compute the MAC of any body, & only holders of the session key can
+//! check it, so a transcript can't prove to anyone else what was sent.
+//! The times are each peer's own & unauthenticated.
+use crate::errors::PortalError::*;
+use crate::protocol::{TransferInfo, WireFormat};
+use hkdf::Hkdf;
+use hmac::{Hmac, Mac, NewMac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::error::Error;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// HKDF label of the key transcripts are authenticated with
+const TRANSCRIPT_LABEL: &[u8] = b"portal-transcript";
+
+/// A file that was completely sent or received
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct TranscriptFile {
+    pub filename: String,
+    pub bytes: u64,
+    /// SHA-256 of the file's data, if hashed, see [`crate::Portal::set_digests`]
+    pub digest: Option<[u8; 32]>,
+}
+
+/// The authenticated part of a transcript
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct TranscriptBody {
+    pub id: String,
+    /// The session fingerprint, see [`crate::Portal::session_fingerprint`]
+    pub fingerprint: String,
+    /// SHA-256 of each TransferInfo exchanged, as encoded for the peer
+    pub manifests: Vec<[u8; 32]>,
+    /// Each file completed, in the order it was sent
+    pub files: Vec<TranscriptFile>,
+    /// Total bytes of file data
+    pub bytes: u64,
+}
+
+/// A compact, authenticated summary of a session
+///
+/// ```no_run
+/// use std::net::TcpStream;
+/// use portal_lib::{Portal, Direction, Transcript};
+///
+/// let mut portal = Portal::init(Direction::Sender, "id".into(), "password".into()).unwrap();
+/// let mut stream = TcpStream::connect("127.0.0.1:34254").unwrap();
+/// portal.handshake(&mut stream).unwrap();
+///
+/// // ... send files ...
+///
+/// // Keep the transcript, to compare with the receiver's later
+/// let ours = portal.transcript().unwrap();
+/// std::fs::write("transfer.transcript", ours.to_bytes().unwrap()).unwrap();
+/// # let theirs = Transcript::from_bytes(&ours.to_bytes().unwrap()).unwrap();
+/// assert!(ours.matches(&theirs));
+/// ```
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct Transcript {
+    pub body: TranscriptBody,
+    /// When this peer completed the handshake, in seconds since the Unix
+    /// epoch. Unauthenticated, as the peers' clocks differ.
+    pub started: u64,
+    /// When this peer exported the transcript
+    pub finished: u64,
+    /// HMAC-SHA256 over the body, keyed by the session key
+    pub mac: [u8; 32],
+}
+
+impl Transcript {
+    /// Authenticate a body with a key derived from the session key
+    pub fn seal(
+        body: TranscriptBody,
+        key: &[u8],
+        started: u64,
+    ) -> Result<Transcript, Box<dyn Error>> {
+        let mut mac = [0u8; 32];
+        mac.copy_from_slice(&Self::hmac(&body, key)?.finalize().into_bytes());
+        Ok(Transcript {
+            body,
+            started,
+            finished: now(),
+            mac,
+        })
+    }
+
+    /// Check that the transcript was sealed with the session key
+    pub fn verify(&self, key: &[u8]) -> bool {
+        Self::hmac(&self.body, key)
+            .map(|mac| mac.verify(&self.mac).is_ok())
+            .unwrap_or(false)
+    }
+
+    /// Returns true if both transcripts record the same session under
+    /// the same key. The times are ignored.
+    pub fn matches(&self, other: &Transcript) -> bool {
+        self.body == other.body && self.mac == other.mac
+    }
+
+    /// Encode the transcript compactly, for storage
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn Error>> {
+        WireFormat::Bincode.serialize(self)
+    }
+
+    /// Decode a stored transcript
+    pub fn from_bytes(data: &[u8]) -> Result<Transcript, Box<dyn Error>> {
+        WireFormat::Bincode.deserialize(data)
+    }
+
+    /// Helper: the MAC state over the body, ready to be finalized or
+    /// compared in constant time
+    fn hmac(body: &TranscriptBody, key: &[u8]) -> Result<Hmac<Sha256>, Box<dyn Error>> {
+        let h = Hkdf::<Sha256>::new(None, key);
+        let mut mac_key = [0u8; 32];
+        h.expand(TRANSCRIPT_LABEL, &mut mac_key)
+            .or(Err(CryptoError))?;
+        let mut mac = Hmac::<Sha256>::new_varkey(&mac_key).or(Err(CryptoError))?;
+        mac.update(&bincode::serialize(body).or(Err(SerializeError))?);
+        Ok(mac)
+    }
+}
+
+/// What a Portal records of its session for its transcript
+#[derive(PartialEq, Eq, Debug, Default)]
+pub(crate) struct TranscriptLog {
+    started: u64,
+    manifests: Vec<[u8; 32]>,
+    files: Vec<TranscriptFile>,
+}
+
+impl TranscriptLog {
+    /// Start recording a session, once the handshake completes
+    pub(crate) fn start() -> TranscriptLog {
+        TranscriptLog {
+            started: now(),
+            ..Default::default()
+        }
+    }
+
+    /// Record a TransferInfo exchanged with the peer
+    pub(crate) fn manifest(
+        &mut self,
+        info: &TransferInfo,
+        format: WireFormat,
+    ) -> Result<(), Box<dyn Error>> {
+        let encoded = format.serialize(info)?;
+        self.manifests.push(Sha256::digest(&encoded).into());
+        Ok(())
+    }
+
+    /// Record a file that was completely sent or received
+    pub(crate) fn file(&mut self, filename: &str, bytes: u64, digest: Option<[u8; 32]>) {
+        self.files.push(TranscriptFile {
+            filename: filename.to_string(),
+            bytes,
+            digest,
+        });
+    }
+
+    /// Seal what was recorded
+    pub(crate) fn seal(
+        &self,
+        id: &str,
+        fingerprint: String,
+        key: &[u8],
+    ) -> Result<Transcript, Box<dyn Error>> {
+        let body = TranscriptBody {
+            id: id.to_string(),
+            fingerprint,
+            manifests: self.manifests.clone(),
+            files: self.files.clone(),
+            bytes: self.files.iter().map(|f| f.bytes).sum(),
+        };
+        Transcript::seal(body, key, self.started)
+    }
+}
+
+/// Helper: seconds since the UNIX epoch
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
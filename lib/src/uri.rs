@@ -0,0 +1,192 @@
+//! `portal://` URIs, naming a transfer so it can be shared as a link or
+//! QR code and opened by any client:
+//!
+//! ```text
+//! portal://<id>@<relay>[:<port>]/?v=1[&p=<password>]
+//! ```
+//!
+//! The password is left out unless explicitly included, a URI without it
+//! only says where to find the peer. Components are percent-encoded, IPv6
+//! relays are written in brackets and the port defaults to
+//! [`crate::DEFAULT_PORT`].
+//!
+//! # Versioning
+//!
+//! - `v` is the version of this format, a URI without it is version 1.
+//! - Parsers reject versions newer than [`URI_VERSION`], as their
+//!   meaning may have changed.
+//! - Parameters a parser doesn't know are ignored, so optional parameters
+//!   can be added without a new version. Anything older parsers would
+//!   misinterpret if ignored requires incrementing the version.
+use crate::errors::PortalError::*;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Scheme of Portal URIs
+pub const URI_SCHEME: &str = "portal";
+
+/// Newest version of the URI format understood & produced
+pub const URI_VERSION: u32 = 1;
+
+/// A transfer's ID & relay, and optionally its password
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortalUri {
+    pub id: String,
+
+    /// Host name or IP address of the relay
+    pub relay: String,
+    pub port: u16,
+
+    /// Only included if the URI is meant to be enough to receive
+    pub password: Option<String>,
+}
+
+impl PortalUri {
+    /// A URI for the ID at the relay, without the password
+    ///
+    /// ```
+    /// use portal_lib::uri::PortalUri;
+    ///
+    /// let uri = PortalUri::new("id", "relay.example.com", 13265);
+    /// assert_eq!(uri.to_string(), "portal://id@relay.example.com:13265/?v=1");
+    /// assert_eq!(uri, "portal://id@relay.example.com/?v=1".parse().unwrap());
+    /// ```
+    pub fn new(id: &str, relay: &str, port: u16) -> Self {
+        Self {
+            id: id.to_string(),
+            relay: relay.to_string(),
+            port,
+            password: None,
+        }
+    }
+
+    /// Include the password, so that the URI alone is enough to receive
+    pub fn with_password(mut self, password: &str) -> Self {
+        self.password = Some(password.to_string());
+        self
+    }
+}
+
+impl fmt::Display for PortalUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let relay = match self.relay.contains(':') {
+            true => format!("[{}]", self.relay),
+            false => self.relay.clone(),
+        };
+        write!(
+            f,
+            "{}://{}@{}:{}/?v={}",
+            URI_SCHEME,
+            encode(&self.id),
+            relay,
+            self.port,
+            URI_VERSION
+        )?;
+        if let Some(password) = &self.password {
+            write!(f, "&p={}", encode(password))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PortalUri {
+    type Err = Box<dyn Error>;
+
+    fn from_str(uri: &str) -> Result<Self, Self::Err> {
+        let rest = uri
+            .strip_prefix(URI_SCHEME)
+            .and_then(|r| r.strip_prefix("://"))
+            .ok_or_else(|| BadUri("not a portal:// URI".into()))?;
+
+        // Split off the query, the path carries nothing
+        let (authority, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let authority = authority.strip_suffix('/').unwrap_or(authority);
+        let (id, relay) = authority
+            .split_once('@')
+            .ok_or_else(|| BadUri("missing the ID".into()))?;
+
+        // The port is optional, and IPv6 addresses are bracketed
+        let (relay, port) = match relay.strip_prefix('[') {
+            Some(v6) => {
+                let (host, rest) = v6
+                    .split_once(']')
+                    .ok_or_else(|| BadUri("unclosed IPv6 address".into()))?;
+                (host, rest.strip_prefix(':'))
+            }
+            None => match relay.split_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (relay, None),
+            },
+        };
+        let port = match port {
+            Some(port) => port.parse().map_err(|_| BadUri("invalid port".into()))?,
+            None => crate::DEFAULT_PORT,
+        };
+
+        let id = decode(id)?;
+        if id.is_empty() || relay.is_empty() || relay.contains(['/', '@', '#']) {
+            return Err(BadUri("missing the ID or relay".into()).into());
+        }
+
+        // Unknown parameters are ignored, later versions are refused
+        let mut version = 1;
+        let mut password = None;
+        for (key, value) in query.split('&').filter_map(|p| p.split_once('=')) {
+            match key {
+                "v" => {
+                    version = value
+                        .parse()
+                        .map_err(|_| BadUri("invalid version".into()))?
+                }
+                "p" => password = Some(decode(value)?),
+                _ => {}
+            }
+        }
+        if version > URI_VERSION {
+            return Err(UnsupportedUriVersion(version).into());
+        }
+
+        Ok(Self {
+            id,
+            relay: relay.to_string(),
+            port,
+            password,
+        })
+    }
+}
+
+/// Helper: percent-encode all but unreserved characters
+fn encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Helper: decode a percent-encoded component
+fn decode(value: &str) -> Result<String, Box<dyn Error>> {
+    let mut bytes = value.bytes();
+    let mut decoded = Vec::with_capacity(value.len());
+    while let Some(byte) = bytes.next() {
+        if byte != b'%' {
+            decoded.push(byte);
+            continue;
+        }
+        let hex = [bytes.next(), bytes.next()];
+        let byte = match hex {
+            [Some(hi), Some(lo)] => std::str::from_utf8(&[hi, lo])
+                .ok()
+                .and_then(|h| u8::from_str_radix(h, 16).ok()),
+            _ => None,
+        };
+        decoded.push(byte.ok_or_else(|| BadUri("invalid percent-encoding".into()))?);
+    }
+    Ok(String::from_utf8(decoded).map_err(|_| BadUri("invalid UTF-8".into()))?)
+}
@@ -0,0 +1,71 @@
+//! Use a [magic-wormhole](https://github.com/magic-wormhole/magic-wormhole-transit-relay)
+//! transit relay as a dumb pipe between two portal peers, for when no portal
+//! relay is reachable. The transit relay pairs connections presenting the same
+//! token, after which the peers complete [`Portal::handshake_direct`] over it.
+//!
+//! ```no_run
+//! use portal_lib::{wormhole, Direction, Portal};
+//!
+//! let mut portal = Portal::init(Direction::Sender, "id".into(), "password".into()).unwrap();
+//! let mut stream = wormhole::connect(wormhole::DEFAULT_TRANSIT_RELAY, &portal).unwrap();
+//! portal.handshake_direct(&mut stream).unwrap();
+//! ```
+use crate::errors::PortalError::*;
+use crate::Portal;
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use std::error::Error;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+
+/// The public transit relay run by the magic-wormhole project
+pub const DEFAULT_TRANSIT_RELAY: &str = "transit.magic-wormhole.io:4001";
+
+/// Longest reply expected from the transit relay
+const MAX_REPLY_SIZE: usize = 64;
+
+/// Derive the token both peers present to the transit relay from the
+/// Portal's ID, so that they are paired with each other
+pub fn relay_token(portal: &Portal) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(b"portal-wormhole-transit-");
+    hasher.update(portal.get_id());
+    hex::encode(hasher.finalize())
+}
+
+/// Perform the transit relay handshake over an existing connection. Blocks
+/// until the relay has paired us with a peer presenting the same token, but
+/// a different side.
+pub fn relay_handshake<S: Read + Write>(
+    stream: &mut S,
+    token: &str,
+    side: &str,
+) -> Result<(), Box<dyn Error>> {
+    let request = format!("please relay {} for side {}\n", token, side);
+    stream.write_all(request.as_bytes()).or(Err(IOError))?;
+
+    // Read a byte at a time, anything after the reply belongs to the peer
+    let mut reply = Vec::new();
+    let mut byte = [0u8; 1];
+    while reply.len() < MAX_REPLY_SIZE {
+        stream.read_exact(&mut byte).or(Err(NoPeer))?;
+        if byte[0] == b'\n' {
+            break;
+        }
+        reply.push(byte[0]);
+    }
+
+    match reply.as_slice() {
+        b"ok" => Ok(()),
+        _ => Err(NoPeer.into()),
+    }
+}
+
+/// Connect to a transit relay & wait to be paired with the peer of this
+/// Portal. The returned stream is ready for [`Portal::handshake_direct`].
+pub fn connect<A: ToSocketAddrs>(relay: A, portal: &Portal) -> Result<TcpStream, Box<dyn Error>> {
+    let mut stream = TcpStream::connect(relay)?;
+    let side = hex::encode(rand::thread_rng().gen::<[u8; 8]>());
+    relay_handshake(&mut stream, &relay_token(portal), &side)?;
+    Ok(stream)
+}
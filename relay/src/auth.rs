@@ -0,0 +1,76 @@
+use portal_lib::protocol::AuthenticatedConnect;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use structopt::StructOpt;
+
+/// Restricts a private relay to clients holding a shared secret
+#[derive(Debug, Clone, StructOpt)]
+pub struct Auth {
+    /// Only pair clients presenting an access token made with one of the
+    /// secrets in this file, one per line. Read before dropping privileges.
+    #[structopt(long)]
+    pub auth_secrets: Option<PathBuf>,
+}
+
+lazy_static! {
+    static ref SECRETS: Mutex<Option<Vec<Vec<u8>>>> = Mutex::new(None);
+}
+
+impl Auth {
+    /**
+     * Load the configured secrets, after which every client must
+     * authenticate before it may register or pair
+     */
+    pub fn init(&self) -> Result<(), Box<dyn Error>> {
+        let path = match &self.auth_secrets {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        let secrets = fs::read_to_string(path)?
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| line.as_bytes().to_vec())
+            .collect::<Vec<Vec<u8>>>();
+        if secrets.is_empty() {
+            return Err(format!("No secrets found in {:?}", path).into());
+        }
+        log::info!(
+            "Requiring access tokens, {} secret(s) loaded",
+            secrets.len()
+        );
+        *SECRETS.lock().unwrap() = Some(secrets);
+        Ok(())
+    }
+}
+
+/**
+ * Whether clients must present an access token
+ */
+pub fn required() -> bool {
+    SECRETS.lock().unwrap().is_some()
+}
+
+/**
+ * A secret the relay accepts, used by the health self-test
+ */
+pub fn secret() -> Option<Vec<u8>> {
+    SECRETS
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|secrets| secrets.first().cloned())
+}
+
+/**
+ * Check an access token against the configured secrets. Tokens are
+ * accepted as is when the relay doesn't require one.
+ */
+pub fn verify(request: &AuthenticatedConnect) -> bool {
+    match SECRETS.lock().unwrap().as_ref() {
+        Some(secrets) => request.verify(secrets),
+        None => true,
+    }
+}
@@ -0,0 +1,590 @@
+use mio::net::TcpStream;
+use os_pipe::PipeReader;
+use portal_lib::protocol::{ResumeToken, SessionSalt};
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fs::{self, File, OpenOptions};
+use std::io;
+use std::net::SocketAddr;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use structopt::StructOpt;
+
+use crate::{Endpoint, MAX_SPLICE_SIZE};
+
+/// Distinguishes the spool files created by this process
+static SPOOL_COUNT: AtomicU64 = AtomicU64::new(0);
+
+/// Store-and-forward for Receivers that briefly disconnect mid-transfer
+#[derive(Debug, Clone, StructOpt)]
+pub struct Buffering {
+    /// Buffer up to this many MB of a transfer to disk when the Receiver
+    /// disconnects, replaying it if the Receiver resumes in time. The
+    /// data is encrypted end-to-end, the relay can't read it.
+    #[structopt(long)]
+    pub buffer_mb: Option<u64>,
+
+    /// Seconds a disconnected Receiver has to resume the transfer
+    #[structopt(long, default_value = "30")]
+    pub buffer_grace: u64,
+
+    /// Directory to buffer data in, the system's temporary directory by
+    /// default. Files are unlinked as soon as they are created.
+    #[structopt(long)]
+    pub buffer_dir: Option<PathBuf>,
+
+    /// MB of the data most recently sent to each Receiver to keep in
+    /// memory, so that what was still in flight when it disconnected
+    /// can be sent again. Larger socket buffers need a larger tail.
+    #[structopt(long, default_value = "8")]
+    pub buffer_tail_mb: usize,
+}
+
+impl Buffering {
+    pub fn enabled(&self) -> bool {
+        self.buffer_mb.is_some()
+    }
+
+    /**
+     * Create an empty spool for a session whose Receiver just left.
+     * The file is removed straight away so that nothing outlives the
+     * session, even if the relay is killed.
+     */
+    pub fn spool(&self) -> io::Result<Spool> {
+        let dir = self.buffer_dir.clone().unwrap_or_else(std::env::temp_dir);
+        let path = dir.join(format!(
+            "portal-relay-{}-{}",
+            std::process::id(),
+            SPOOL_COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        fs::remove_file(&path)?;
+
+        Ok(Spool {
+            file,
+            limit: self.buffer_mb.unwrap_or_default() * 1024 * 1024,
+            written: 0,
+            replayed: 0,
+            deadline: None,
+            paused: false,
+        })
+    }
+
+    /// How long a disconnected Receiver has to resume
+    pub fn grace(&self) -> Duration {
+        Duration::from_secs(self.buffer_grace)
+    }
+
+    /**
+     * Start keeping the tail of what is sent to a Receiver reading from
+     * `pipe`, which is read from directly from now on
+     */
+    pub fn tail(&self, pipe: &PipeReader) -> io::Result<Tail> {
+        unsafe {
+            let flags = libc::fcntl(pipe.as_raw_fd(), libc::F_GETFL);
+            if flags < 0
+                || libc::fcntl(pipe.as_raw_fd(), libc::F_SETFL, flags | libc::O_NONBLOCK) < 0
+            {
+                return Err(io::Error::last_os_error());
+            }
+        }
+        Ok(Tail::new(self.buffer_tail_mb * 1024 * 1024))
+    }
+}
+
+/// What a resuming Receiver must prove, and what was sent to it before
+/// splicing began
+#[derive(Debug, Copy, Clone)]
+pub struct Ticket {
+    pub salt: SessionSalt,
+
+    /// Bytes sent to the Receiver directly, i.e. the relay's banner
+    pub preamble: u64,
+}
+
+/// A Receiver reconnecting to a session with buffered data, handed
+/// from the registration threadpool to the event loop
+#[derive(Debug)]
+pub struct Resume {
+    pub id: String,
+    pub received: u64,
+    pub token: ResumeToken,
+    pub stream: TcpStream,
+    pub addr: SocketAddr,
+    pub framing: portal_lib::CodecVersion,
+}
+
+/// Data from the Sender held back from the Receiver, either while it is
+/// away or until it has caught up after resuming
+#[derive(Debug)]
+pub struct Spool {
+    file: File,
+    limit: u64,
+    written: u64,
+    replayed: u64,
+
+    // Set while the Receiver is disconnected
+    deadline: Option<Instant>,
+
+    /// Reading from the Sender is paused until the spool drains
+    pub paused: bool,
+}
+
+impl Spool {
+    /// Whether the Receiver is currently disconnected
+    pub fn away(&self) -> bool {
+        self.deadline.is_some()
+    }
+
+    /// Whether the Receiver has been away longer than allowed
+    pub fn expired(&self) -> bool {
+        self.deadline.is_some_and(|d| Instant::now() > d)
+    }
+
+    /// The Receiver left (again), start its grace window
+    pub fn leave(&mut self, grace: Duration) {
+        self.deadline = Some(Instant::now() + grace);
+    }
+
+    /// The Receiver is back, replay what it missed
+    pub fn rejoin(&mut self) {
+        self.deadline = None;
+    }
+
+    /// Whether the Receiver has caught up
+    pub fn is_empty(&self) -> bool {
+        self.replayed == self.written
+    }
+
+    /// Whether the limit has been reached
+    pub fn is_full(&self) -> bool {
+        self.written >= self.limit
+    }
+
+    /**
+     * Move data from the Sender into the spool rather than to the
+     * Receiver. Returns true once the Sender is done, like tcp_splice
+     */
+    pub fn fill(
+        &mut self,
+        sender: &mut Endpoint,
+        pipe: &PipeReader,
+    ) -> Result<bool, Box<dyn Error>> {
+        let src_fd = sender.stream.as_raw_fd();
+        let p_in = sender.peer_writer.as_ref().unwrap().as_raw_fd();
+
+        // Anything held back while following the Sender's messages
+        if let Some(frames) = &mut sender.frames {
+            if !frames.flush(p_in)? {
+                self.flush_pipe(pipe)?;
+                return Ok(false);
+            }
+        }
+
+        loop {
+            // Data already in the pipe comes first
+            self.flush_pipe(pipe)?;
+            if self.is_full() {
+                return Ok(false);
+            }
+
+            let len = MAX_SPLICE_SIZE.min((self.limit - self.written) as usize);
+            let rx = unsafe {
+                *libc::__errno_location() = 0;
+                libc::splice(
+                    src_fd,
+                    std::ptr::null_mut::<libc::loff_t>(),
+                    p_in,
+                    std::ptr::null_mut::<libc::loff_t>(),
+                    len,
+                    libc::SPLICE_F_MOVE | libc::SPLICE_F_NONBLOCK,
+                )
+            };
+            let errno = io::Error::last_os_error().raw_os_error().unwrap();
+            sender.stats.record_recv(rx);
+            if let Some(frames) = &mut sender.frames {
+                frames.advance(rx);
+            }
+
+            match rx {
+                0 => return Ok(true),
+                x if x > 0 => continue,
+                _ if errno == libc::EWOULDBLOCK || errno == libc::EAGAIN => {
+                    self.flush_pipe(pipe)?;
+                    return Ok(false);
+                }
+                _ => {
+                    log::error!(
+                        "[{:.6}] Error buffering data from {:?}: errno: {}",
+                        sender.id,
+                        sender.dir,
+                        errno
+                    );
+                    return Ok(true);
+                }
+            }
+        }
+    }
+
+    /**
+     * Append whatever is waiting in the pipe to the spool, up to its limit
+     */
+    pub fn flush_pipe(&mut self, pipe: &PipeReader) -> io::Result<()> {
+        while !self.is_full() {
+            let mut offset = self.written as libc::loff_t;
+            let len = MAX_SPLICE_SIZE.min((self.limit - self.written) as usize);
+            let moved = unsafe {
+                libc::splice(
+                    pipe.as_raw_fd(),
+                    std::ptr::null_mut::<libc::loff_t>(),
+                    self.file.as_raw_fd(),
+                    &mut offset,
+                    len,
+                    libc::SPLICE_F_MOVE | libc::SPLICE_F_NONBLOCK,
+                )
+            };
+            match moved {
+                0 => break,
+                x if x > 0 => self.written += x as u64,
+                _ => {
+                    let err = io::Error::last_os_error();
+                    if err.kind() == io::ErrorKind::WouldBlock {
+                        break;
+                    }
+                    return Err(err);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /**
+     * Send buffered data to the resumed Receiver, until it would block
+     * or has caught up. Returns true if the Receiver went away again.
+     */
+    pub fn replay(&mut self, receiver: &mut Endpoint) -> Result<bool, Box<dyn Error>> {
+        let dst_fd = receiver.stream.as_raw_fd();
+        while !self.is_empty() || receiver.tail.as_ref().is_some_and(Tail::is_pending) {
+            let mut offset = self.replayed as libc::off_t;
+            let len = MAX_SPLICE_SIZE.min((self.written - self.replayed) as usize);
+            let (tx, taken) = unsafe {
+                *libc::__errno_location() = 0;
+                match &mut receiver.tail {
+                    // Anything the Receiver missed goes first
+                    Some(tail) => {
+                        let file = self.file.as_raw_fd();
+                        let tx = tail.send(dst_fd, len, |buf| {
+                            libc::pread(
+                                file,
+                                buf.as_mut_ptr() as *mut libc::c_void,
+                                buf.len(),
+                                offset,
+                            )
+                        });
+                        (tx, tail.taken())
+                    }
+                    None => {
+                        let tx = libc::sendfile(dst_fd, self.file.as_raw_fd(), &mut offset, len);
+                        (tx, tx.max(0) as usize)
+                    }
+                }
+            };
+            let errno = io::Error::last_os_error().raw_os_error().unwrap();
+            receiver.stats.record_send(tx);
+            self.replayed += taken as u64;
+
+            match tx {
+                0 => return Ok(true),
+                x if x > 0 => {}
+                _ if errno == libc::EWOULDBLOCK || errno == libc::EAGAIN => return Ok(false),
+                _ => {
+                    log::error!(
+                        "[{:.6}] Error replaying buffered data to {:?}: errno: {}",
+                        receiver.id,
+                        receiver.dir,
+                        errno
+                    );
+                    return Ok(true);
+                }
+            }
+        }
+        Ok(false)
+    }
+}
+
+/**
+ * The most recent data sent to a Receiver, kept when buffering so that
+ * a Receiver that disconnects can be sent again what never reached it.
+ * Data sent through a tail is copied through userspace rather than
+ * spliced, and what the socket doesn't accept is held until it does.
+ */
+#[derive(Debug)]
+pub struct Tail {
+    sent: VecDeque<u8>,
+    limit: usize,
+
+    // Data to send before anything else
+    pending: VecDeque<u8>,
+
+    // Bytes taken from the source by the last send
+    taken: usize,
+    buf: Vec<u8>,
+}
+
+impl Tail {
+    pub fn new(limit: usize) -> Self {
+        Tail {
+            sent: VecDeque::new(),
+            limit,
+            pending: VecDeque::new(),
+            taken: 0,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Whether data is waiting to be sent ahead of anything else
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Bytes taken from the source by the last call to `send`, whether or
+    /// not the socket accepted all of them
+    pub fn taken(&self) -> usize {
+        self.taken
+    }
+
+    /**
+     * Move the last `len` bytes sent back to be sent again, for a Receiver
+     * that resumed having missed them. False if fewer are kept.
+     */
+    pub fn rewind(&mut self, len: u64) -> bool {
+        if len > self.sent.len() as u64 {
+            return false;
+        }
+        let mut missed = self.sent.split_off(self.sent.len() - len as usize);
+        missed.append(&mut self.pending);
+        self.pending = missed;
+        true
+    }
+
+    /**
+     * Write pending data to `dst`. Returns like write(), with errno set
+     */
+    pub fn flush(&mut self, dst: RawFd) -> isize {
+        let (front, _) = self.pending.as_slices();
+        let tx = unsafe { libc::write(dst, front.as_ptr() as *const libc::c_void, front.len()) };
+        let errno = unsafe { *libc::__errno_location() };
+        if tx > 0 {
+            let sent = self.pending.drain(..tx as usize).collect::<Vec<u8>>();
+            self.record(&sent);
+        }
+        unsafe { *libc::__errno_location() = errno };
+        tx
+    }
+
+    /**
+     * Send pending data to `dst`, or else up to `len` bytes that `read`
+     * fills the buffer with. Returns bytes sent like splice(), 0 once
+     * `read` returns 0, or -1 with errno set. Whatever was read but not
+     * accepted by the socket is held as pending.
+     */
+    pub fn send(&mut self, dst: RawFd, len: usize, read: impl FnOnce(&mut [u8]) -> isize) -> isize {
+        self.taken = 0;
+        if self.is_pending() {
+            return self.flush(dst);
+        }
+
+        self.buf.resize(len, 0);
+        let rx = read(&mut self.buf);
+        if rx <= 0 {
+            return rx;
+        }
+        let data = &self.buf[..rx as usize];
+        self.taken = data.len();
+
+        let tx = unsafe { libc::write(dst, data.as_ptr() as *const libc::c_void, data.len()) };
+        let errno = unsafe { *libc::__errno_location() };
+        let accepted = tx.max(0) as usize;
+        self.pending.extend(&data[accepted..]);
+        let sent = data[..accepted].to_vec();
+        self.record(&sent);
+        unsafe { *libc::__errno_location() = errno };
+        tx
+    }
+
+    /**
+     * Send data from `pipe` to `dst`, like splice()
+     */
+    pub fn splice(&mut self, pipe: RawFd, dst: RawFd, len: usize) -> isize {
+        self.send(dst, len, |buf| unsafe {
+            libc::read(pipe, buf.as_mut_ptr() as *mut libc::c_void, buf.len())
+        })
+    }
+
+    /// Helper: keep what was sent, up to the limit
+    fn record(&mut self, data: &[u8]) {
+        self.sent.extend(data);
+        let excess = self.sent.len().saturating_sub(self.limit);
+        self.sent.drain(..excess);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::SpliceStats;
+    use portal_lib::Direction;
+    use std::io::{Read, Write};
+    use std::os::unix::net::UnixStream;
+    use std::time::SystemTime;
+
+    /// Helper: send everything the tail holds or reads from `data`
+    fn send_all(tail: &mut Tail, dst: &UnixStream, mut data: &[u8]) {
+        loop {
+            let tx = tail.send(dst.as_raw_fd(), 4, |buf| {
+                let len = buf.len().min(data.len());
+                buf[..len].copy_from_slice(&data[..len]);
+                data = &data[len..];
+                len as isize
+            });
+            if tx == 0 {
+                return;
+            }
+        }
+    }
+
+    /// Helper: a Receiver connected over TCP to the returned stream
+    fn receiver() -> (Endpoint, std::net::TcpStream) {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let peer = std::net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (stream, _) = listener.accept().unwrap();
+        let endpoint = Endpoint {
+            id: "id".into(),
+            dir: Direction::Receiver,
+            stream: TcpStream::from_stream(stream).unwrap(),
+            peer_writer: None,
+            peer_reader: None,
+            has_peer: true,
+            time_added: SystemTime::now(),
+            ttl: Duration::ZERO,
+            format: Default::default(),
+            priority: Default::default(),
+            codec: Default::default(),
+            capabilities: None,
+            legacy: false,
+            framing: Default::default(),
+            owner: None,
+            stats: SpliceStats::new(),
+            frames: None,
+            tail: None,
+        };
+        (endpoint, peer)
+    }
+
+    #[test]
+    fn spool_replays_through_tail() {
+        let buffering = Buffering {
+            buffer_mb: Some(1),
+            buffer_grace: 30,
+            buffer_dir: None,
+            buffer_tail_mb: 1,
+        };
+        let mut spool = buffering.spool().unwrap();
+        spool.file.write_all(b"spooled data").unwrap();
+        spool.written = 12;
+
+        // A Receiver that resumed having missed some of what was sent
+        let (mut receiver, mut peer) = receiver();
+        let mut tail = Tail::new(1024);
+        tail.record(b"missed ");
+        assert!(tail.rewind(7));
+        receiver.tail = Some(tail);
+
+        // Replayed under the relay's seccomp filter, failing what it doesn't allow
+        let replayed = std::thread::spawn(move || {
+            let program =
+                crate::sandbox::filter(seccompiler::SeccompAction::Errno(libc::ENOSYS as u32))
+                    .unwrap();
+            seccompiler::apply_filter(&program).unwrap();
+            let gone = spool.replay(&mut receiver).unwrap();
+            (gone, spool.is_empty())
+        });
+        assert_eq!(replayed.join().unwrap(), (false, true));
+
+        let mut received = [0u8; 19];
+        peer.read_exact(&mut received).unwrap();
+        assert_eq!(&received, b"missed spooled data");
+    }
+
+    #[test]
+    fn tail_keeps_what_was_sent() {
+        let (dst, mut peer) = UnixStream::pair().unwrap();
+        let mut tail = Tail::new(8);
+        send_all(&mut tail, &dst, b"0123456789");
+
+        let mut received = [0u8; 10];
+        peer.read_exact(&mut received).unwrap();
+        assert_eq!(&received, b"0123456789");
+
+        // Only the limit is kept
+        assert!(!tail.rewind(9));
+
+        // What is rewound is sent again first
+        assert!(tail.rewind(3));
+        assert!(tail.is_pending());
+        send_all(&mut tail, &dst, b"ab");
+        let mut again = [0u8; 5];
+        peer.read_exact(&mut again).unwrap();
+        assert_eq!(&again, b"789ab");
+        assert!(!tail.is_pending());
+    }
+
+    #[test]
+    fn tail_holds_what_the_socket_refuses() {
+        let (dst, mut peer) = UnixStream::pair().unwrap();
+        dst.set_nonblocking(true).unwrap();
+        let mut tail = Tail::new(1024);
+        let chunk = vec![7u8; 64 * 1024];
+
+        // Fill the socket until it would block
+        let mut taken = 0;
+        loop {
+            let tx = tail.send(dst.as_raw_fd(), chunk.len(), |buf| {
+                buf.copy_from_slice(&chunk[..buf.len()]);
+                buf.len() as isize
+            });
+            taken += tail.taken();
+            if tx < 0 {
+                let err = io::Error::last_os_error();
+                assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+                break;
+            }
+        }
+        assert!(tail.is_pending());
+
+        // Nothing is lost once the peer catches up
+        let reader = std::thread::spawn(move || {
+            let mut total = 0;
+            let mut buf = [0u8; 64 * 1024];
+            while let Ok(n) = peer.read(&mut buf) {
+                if n == 0 {
+                    break;
+                }
+                total += n;
+            }
+            total
+        });
+        while tail.is_pending() {
+            tail.flush(dst.as_raw_fd());
+        }
+        let _ = (&dst).flush();
+        drop(dst);
+        assert_eq!(reader.join().unwrap(), taken);
+    }
+}
@@ -0,0 +1,199 @@
+use mio::net::TcpStream;
+use mio::Token;
+use rand::Rng;
+use socket2::SockRef;
+use std::collections::HashMap;
+use std::error::Error;
+use std::time::{Duration, Instant};
+use structopt::StructOpt;
+
+/// How long a throttled endpoint waits before its allowance is checked again
+const REFILL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The most a throttled endpoint may send at once, in seconds of its rate
+const BURST: f64 = 0.1;
+
+/// Simulated network conditions, to test how clients cope with a poor
+/// connection without setting up tc/netem
+#[derive(Debug, Clone, StructOpt)]
+pub struct Chaos {
+    /// Developer mode: degrade every relayed session with the conditions
+    /// below. Never enable this on a relay others depend on.
+    #[structopt(long)]
+    pub chaos: bool,
+
+    /// Milliseconds to hold back data arriving at the relay before
+    /// forwarding it, in both directions
+    #[structopt(long, requires = "chaos")]
+    pub chaos_latency: Option<u64>,
+
+    /// Limit each direction of a session to this many KB per second
+    #[structopt(long, requires = "chaos")]
+    pub chaos_bandwidth: Option<u64>,
+
+    /// Chance of abruptly closing a session whenever data arrives,
+    /// between 0 and 1
+    #[structopt(long, requires = "chaos")]
+    pub chaos_reset: Option<f64>,
+}
+
+/// What to do with data that just arrived on an endpoint
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    /// Forward it, at most this many bytes when throttled
+    Forward(Option<usize>),
+    /// Stop polling the endpoint until it is released
+    Hold,
+    /// Close the session
+    Reset,
+}
+
+/// Remaining allowance of a throttled endpoint
+#[derive(Debug)]
+struct Bucket {
+    allowance: f64,
+    refilled: Instant,
+}
+
+/// The conditions applied to a relay's sessions, and the state of each
+/// endpoint they've affected
+#[derive(Debug)]
+pub struct Weather {
+    latency: Duration,
+    rate: Option<f64>,
+    reset: f64,
+
+    // When the data waiting on an endpoint may be forwarded
+    due: HashMap<Token, Instant>,
+    buckets: HashMap<Token, Bucket>,
+    held: HashMap<Token, Instant>,
+}
+
+impl Chaos {
+    /**
+     * Validate the conditions, returning None when chaos mode is off
+     */
+    pub fn start(&self) -> Result<Option<Weather>, Box<dyn Error>> {
+        if !self.chaos {
+            return Ok(None);
+        }
+
+        let reset = self.chaos_reset.unwrap_or_default();
+        if !(0.0..=1.0).contains(&reset) {
+            return Err("--chaos-reset must be between 0 and 1".into());
+        }
+        if self.chaos_bandwidth == Some(0) {
+            return Err("--chaos-bandwidth must be greater than 0".into());
+        }
+
+        log::warn!("Chaos mode is enabled, relayed sessions will be degraded on purpose");
+        Ok(Some(Weather {
+            latency: Duration::from_millis(self.chaos_latency.unwrap_or_default()),
+            rate: self.chaos_bandwidth.map(|kb| (kb * 1024) as f64),
+            reset,
+            due: HashMap::new(),
+            buckets: HashMap::new(),
+            held: HashMap::new(),
+        }))
+    }
+}
+
+impl Weather {
+    /**
+     * Decide what happens to data that arrived on an endpoint. Held
+     * endpoints are released by [`Weather::release`] once they're due.
+     */
+    pub fn arrive(&mut self, token: Token, now: Instant) -> Verdict {
+        if self.reset > 0.0 && rand::thread_rng().gen_bool(self.reset) {
+            return Verdict::Reset;
+        }
+
+        // Start delaying a new burst of data
+        let due = *self.due.entry(token).or_insert(now + self.latency);
+        if due > now {
+            self.held.insert(token, due);
+            return Verdict::Hold;
+        }
+
+        let rate = match self.rate {
+            Some(rate) => rate,
+            None => return Verdict::Forward(None),
+        };
+        let bucket = self.buckets.entry(token).or_insert(Bucket {
+            allowance: rate * BURST,
+            refilled: now,
+        });
+        let elapsed = now.duration_since(bucket.refilled).as_secs_f64();
+        bucket.allowance = (bucket.allowance + elapsed * rate).min(rate * BURST);
+        bucket.refilled = now;
+
+        match bucket.allowance as usize {
+            0 => {
+                self.held.insert(token, now + REFILL_INTERVAL);
+                Verdict::Hold
+            }
+            allowance => Verdict::Forward(Some(allowance)),
+        }
+    }
+
+    /**
+     * Record what was forwarded from an endpoint. Once it has caught up,
+     * the next data to arrive is delayed again.
+     */
+    pub fn forwarded(&mut self, token: Token, bytes: u64) {
+        let throttled = match self.buckets.get_mut(&token) {
+            Some(bucket) => {
+                bucket.allowance -= bytes as f64;
+                bucket.allowance < 1.0
+            }
+            None => false,
+        };
+        if !throttled {
+            self.due.remove(&token);
+        }
+    }
+
+    /// Endpoints whose hold has passed, to start polling again
+    pub fn release(&mut self, now: Instant) -> Vec<Token> {
+        let due: Vec<Token> = self
+            .held
+            .iter()
+            .filter(|(_, until)| **until <= now)
+            .map(|(token, _)| *token)
+            .collect();
+        for token in &due {
+            self.held.remove(token);
+        }
+        due
+    }
+
+    /// How long until the next held endpoint is due
+    pub fn next_release(&self, now: Instant) -> Option<Duration> {
+        self.held
+            .values()
+            .min()
+            .map(|until| until.saturating_duration_since(now))
+    }
+
+    /// Stop affecting an endpoint, e.g. when it's drained for the last time
+    pub fn forget(&mut self, token: Token) {
+        self.due.remove(&token);
+        self.buckets.remove(&token);
+        self.held.remove(&token);
+    }
+
+    /// Forget endpoints that no longer exist
+    pub fn retain(&mut self, mut exists: impl FnMut(&Token) -> bool) {
+        self.due.retain(|token, _| exists(token));
+        self.buckets.retain(|token, _| exists(token));
+        self.held.retain(|token, _| exists(token));
+    }
+}
+
+/**
+ * Make the close that follows send a RST rather than a FIN, the way a
+ * dropped connection looks to a peer
+ */
+pub fn reset(stream: &TcpStream) {
+    let _ = SockRef::from(stream).set_linger(Some(Duration::ZERO));
+}
@@ -0,0 +1,86 @@
+use mio::net::TcpStream;
+use portal_lib::protocol::{
+    CodecVersion, PortalMessage, RelayControl, RelayControlError, WireCodec,
+};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::metrics;
+
+lazy_static! {
+    /// IDs whose Sender has been paired with a Receiver, until the session ends
+    static ref CLAIMED: Mutex<HashMap<String, Instant>> = Mutex::new(HashMap::new());
+}
+
+/**
+ * Claim the session for an ID on behalf of its Receiver, until
+ * `release` is called once the session ends
+ */
+pub fn claim(id: &str) {
+    CLAIMED
+        .lock()
+        .unwrap()
+        .entry(id.to_string())
+        .or_insert_with(Instant::now);
+}
+
+/**
+ * Whether a Receiver has claimed the session for this ID
+ */
+pub fn is_claimed(id: &str) -> bool {
+    CLAIMED.lock().unwrap().contains_key(id)
+}
+
+/**
+ * Release the ID once its session has ended
+ */
+pub fn release(id: &str) {
+    CLAIMED.lock().unwrap().remove(id);
+}
+
+/**
+ * Release claims older than `grace` whose session isn't `live`,
+ * returning their IDs
+ */
+pub fn scrub(grace: Duration, live: impl Fn(&str) -> bool) -> Vec<String> {
+    let mut released = Vec::new();
+    CLAIMED.lock().unwrap().retain(|id, at| {
+        if at.elapsed() < grace || live(id) {
+            return true;
+        }
+        released.push(id.clone());
+        false
+    });
+    released
+}
+
+/**
+ * The number of IDs currently paired
+ */
+pub fn count() -> usize {
+    CLAIMED.lock().unwrap().len()
+}
+
+/**
+ * Tell a Receiver arriving for a session that was already claimed
+ * so, rather than leaving it to guess why the connection closed
+ */
+pub fn refuse(id: &str, addr: &SocketAddr, mut connection: TcpStream, framing: CodecVersion) {
+    let since = CLAIMED.lock().unwrap().get(id).map(Instant::elapsed);
+    log::warn!(
+        "[{:.6}] Refused an extra Receiver from {:?}, the session was claimed {:?} ago",
+        id,
+        addr,
+        since.unwrap_or_default()
+    );
+    metrics::record_rejected_receiver();
+
+    let response =
+        PortalMessage::RelayControl(RelayControl::Error(RelayControlError::AlreadyPaired));
+    if let Err(e) = framing.send(&response, &mut connection) {
+        log::debug!("[{:.6}] Error refusing {:?}: {}", id, addr, e);
+    }
+    let _ = connection.shutdown(std::net::Shutdown::Both);
+}
@@ -0,0 +1,197 @@
+use mio::Token;
+use os_pipe::pipe;
+use portal_lib::errors::PortalError;
+use portal_lib::protocol::{
+    PortalMessage, Protocol, RelayControl, WireCodec, MAX_HANDSHAKE_MESSAGE_SIZE,
+};
+use portal_lib::{CodecVersion, Direction, Priority, WireFormat};
+use std::error::Error;
+use std::io::Write;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::os::unix::io::AsRawFd;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use structopt::StructOpt;
+
+use crate::handlers::SpliceStats;
+use crate::{networking, Endpoint, EndpointPair, MAX_SPLICE_SIZE};
+
+/// How long to wait on another relay in the cluster
+const PEER_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Several relays behind one address, sharing their pending Senders
+#[derive(Debug, Clone, StructOpt)]
+pub struct Cluster {
+    /// Another relay (host:port) in this cluster. A Receiver whose Sender
+    /// is waiting on a peer instead is proxied to it. May be given more
+    /// than once, every relay in the cluster should list the others.
+    #[structopt(long = "cluster-peer", number_of_values = 1)]
+    pub cluster_peers: Vec<String>,
+}
+
+lazy_static! {
+    static ref PEERS: Mutex<Vec<SocketAddr>> = Mutex::new(Vec::new());
+}
+
+impl Cluster {
+    /**
+     * Resolve the peers' addresses, before dropping privileges
+     */
+    pub fn init(&self) -> Result<(), Box<dyn Error>> {
+        let mut peers = Vec::new();
+        for peer in &self.cluster_peers {
+            peers.extend(peer.to_socket_addrs()?);
+        }
+        if !peers.is_empty() {
+            log::info!("Clustered with {:?}", peers);
+        }
+        *PEERS.lock().unwrap() = peers;
+        Ok(())
+    }
+}
+
+/**
+ * Whether a connection comes from another relay in the cluster
+ */
+pub fn is_peer(addr: &SocketAddr) -> bool {
+    PEERS.lock().unwrap().iter().any(|p| p.ip() == addr.ip())
+}
+
+/**
+ * Ask the other relays in the cluster for the Sender with this ID. If one
+ * has it, forward the Receiver's request there & return the connection,
+ * past that relay's banner, which is sent with the request's codec to
+ * clients that sent their options, `hello` for this request.
+ * Requests already forwarded by a peer aren't forwarded again.
+ */
+pub fn find(
+    id: &str,
+    addr: &SocketAddr,
+    request: &[u8],
+    framing: CodecVersion,
+    hello: bool,
+) -> Option<TcpStream> {
+    if is_peer(addr) {
+        return None;
+    }
+    let peers = PEERS.lock().unwrap().clone();
+    peers
+        .iter()
+        .find_map(|peer| match forward(id, peer, request, framing, hello) {
+            Ok(found) => found,
+            Err(e) => {
+                log::warn!(
+                    "[{:.6}] Error looking up the Sender on {:?}: {}",
+                    id,
+                    peer,
+                    e
+                );
+                None
+            }
+        })
+}
+
+/**
+ * Helper: look the ID up on a single peer, forwarding the request if found
+ */
+fn forward(
+    id: &str,
+    peer: &SocketAddr,
+    request: &[u8],
+    framing: CodecVersion,
+    hello: bool,
+) -> Result<Option<TcpStream>, Box<dyn Error>> {
+    let connect = || -> std::io::Result<TcpStream> {
+        let stream = TcpStream::connect_timeout(peer, PEER_TIMEOUT)?;
+        stream.set_read_timeout(Some(PEER_TIMEOUT))?;
+        stream.set_write_timeout(Some(PEER_TIMEOUT))?;
+        Ok(stream)
+    };
+
+    let mut lookup = connect()?;
+    if Protocol::relay_control(&mut lookup, RelayControl::Lookup(id.to_string()))?
+        != RelayControl::Ack
+    {
+        return Ok(None);
+    }
+
+    log::info!("[{:.6}] Sender is waiting on {:?}", id, peer);
+    let mut upstream = connect()?;
+    upstream.write_all(request)?;
+
+    // The Receiver already has this relay's banner, if it was sent one
+    if hello {
+        match framing.decode(&mut upstream, MAX_HANDSHAKE_MESSAGE_SIZE)? {
+            PortalMessage::RelayHello(_) => {}
+            _ => return Err(PortalError::UnexpectedMessage.into()),
+        }
+    }
+    upstream.set_read_timeout(None)?;
+    upstream.set_write_timeout(None)?;
+    Ok(Some(upstream))
+}
+
+/**
+ * Pair the Receiver with the connection to the relay holding its Sender,
+ * which stands in for the Sender. That relay does the actual pairing, so
+ * its messages are passed through as is. Only the Receiver's offers are
+ * known here, a limited session that can't be followed with its codec is
+ * counted in bytes instead.
+ */
+pub fn proxy(
+    id: String,
+    format: WireFormat,
+    priority: Priority,
+    codec: CodecVersion,
+    framing: CodecVersion,
+    receiver: mio::net::TcpStream,
+    upstream: TcpStream,
+) -> Result<EndpointPair, Box<dyn Error>> {
+    let upstream = mio::net::TcpStream::from_stream(upstream)?;
+
+    // Upstream->Receiver & Receiver->Upstream
+    let (down_reader, down_writer) = pipe()?;
+    let (up_reader, up_writer) = pipe()?;
+    if unsafe { libc::fcntl(down_reader.as_raw_fd(), libc::F_SETPIPE_SZ, MAX_SPLICE_SIZE) } < 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+
+    // Only the Receiver's priority is known here, the other
+    // relay marks its side from both
+    if priority != Priority::Normal {
+        networking::mark(&receiver, priority);
+        networking::mark(&upstream, priority);
+    }
+
+    let endpoint = |dir, stream, peer_writer, peer_reader| Endpoint {
+        id: id.clone(),
+        dir,
+        stream,
+        peer_writer: Some(peer_writer),
+        peer_reader: Some(peer_reader),
+        has_peer: true,
+        time_added: SystemTime::now(),
+        ttl: Duration::ZERO,
+        format,
+        priority,
+        codec,
+        // Only used for pairing, which the other relay does
+        capabilities: None,
+        legacy: false,
+        framing,
+        owner: None,
+        stats: SpliceStats::new(),
+        frames: None,
+        tail: None,
+    };
+
+    Ok(EndpointPair {
+        sender: endpoint(Direction::Sender, upstream, down_writer, up_reader),
+        sender_token: Token(0),
+        receiver: endpoint(Direction::Receiver, receiver, up_writer, down_reader),
+        receiver_token: Token(0),
+        spool: None,
+        ticket: None,
+        expiring: None,
+    })
+}
@@ -1,17 +1,133 @@
 extern crate portal_lib as portal;
 
+use crate::limit::Next;
 use crate::Endpoint;
 use crate::MAX_SPLICE_SIZE;
 use std::error::Error;
 use std::os::unix::io::AsRawFd;
+use std::time::{Duration, Instant};
+
+/// Bytes & splice() calls in each direction of an Endpoint's socket
+#[derive(Debug)]
+pub struct SpliceStats {
+    started: Instant,
+    last_active: Instant,
+    received: u64,
+    recv_calls: u64,
+    sent: u64,
+    send_calls: u64,
+
+    // Calls that would have blocked, moving nothing
+    blocked_calls: u64,
+}
+
+impl SpliceStats {
+    pub fn new() -> Self {
+        SpliceStats {
+            started: Instant::now(),
+            last_active: Instant::now(),
+            received: 0,
+            recv_calls: 0,
+            sent: 0,
+            send_calls: 0,
+            blocked_calls: 0,
+        }
+    }
+
+    pub fn record_recv(&mut self, len: isize) {
+        self.recv_calls += 1;
+        self.received += len.max(0) as u64;
+        if len < 0 {
+            self.blocked_calls += 1;
+        }
+        if len > 0 {
+            self.last_active = Instant::now();
+        }
+    }
+
+    pub fn record_send(&mut self, len: isize) {
+        self.send_calls += 1;
+        self.sent += len.max(0) as u64;
+        if len < 0 {
+            self.blocked_calls += 1;
+        }
+        if len > 0 {
+            self.last_active = Instant::now();
+        }
+    }
+
+    /// Bytes read from this Endpoint's socket so far
+    pub fn received(&self) -> u64 {
+        self.received
+    }
+
+    /// Bytes written to this Endpoint's socket so far
+    pub fn sent(&self) -> u64 {
+        self.sent
+    }
+
+    /// Count only the bytes a resumed Receiver actually read
+    pub fn rewind(&mut self, sent: u64) {
+        self.sent = sent;
+    }
+
+    /// Bytes moved through this Endpoint's socket in either direction
+    pub fn total(&self) -> u64 {
+        self.received + self.sent
+    }
+
+    /// Share of splice() calls that would have blocked. Errors end the
+    /// session, so nearly every call moving nothing is counted here.
+    pub fn would_block(&self) -> f64 {
+        self.blocked_calls as f64 / (self.recv_calls + self.send_calls).max(1) as f64
+    }
+
+    /// Time since the session started
+    pub fn age(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Time since any bytes moved through this Endpoint. The relay can't
+    /// see inside encrypted frames, so heartbeats or rekeying between
+    /// peers count as activity like any other data.
+    pub fn idle(&self) -> Duration {
+        self.last_active.elapsed()
+    }
+
+    /**
+     * Log a summary of the session, called at teardown
+     */
+    pub fn log_summary(&self, id: &str, dir: portal::Direction) {
+        let elapsed = self.started.elapsed();
+        let rate = |bytes: u64| bytes as f64 / elapsed.max(Duration::from_millis(1)).as_secs_f64();
+        log::debug!(
+            "[{:.6}] {:?} session: {:?}, received {} bytes in {} splices ({:.0} B/s), sent {} bytes in {} splices ({:.0} B/s), {:.1}% would block",
+            id,
+            dir,
+            elapsed,
+            self.received,
+            self.recv_calls,
+            rate(self.received),
+            self.sent,
+            self.send_calls,
+            rate(self.sent),
+            100.0 * self.would_block()
+        );
+    }
+}
 
 /**
  *  Handles TCP splicing without utilizing a userpace intermediary buffer
  *
  *  When the src_fd is readable, we will attempt to splice data into the dst_fd,
- *  using an intermediary pipe
+ *  using an intermediary pipe. A budget stops reading from the src_fd once that
+ *  many bytes have been received.
  */
-pub fn tcp_splice(endpoint: &Endpoint, peer: &Endpoint) -> Result<bool, Box<dyn Error>> {
+pub fn tcp_splice(
+    endpoint: &mut Endpoint,
+    peer: &mut Endpoint,
+    budget: Option<usize>,
+) -> Result<bool, Box<dyn Error>> {
     let mut rx;
     let mut tx;
 
@@ -25,21 +141,59 @@ pub fn tcp_splice(endpoint: &Endpoint, peer: &Endpoint) -> Result<bool, Box<dyn
 
     // Connection ID
     let id = endpoint.id.clone();
+    let mut budget = budget.unwrap_or(usize::MAX);
 
     loop {
+        if budget == 0 {
+            break;
+        }
+
+        // A limited session is spliced one message at a time
+        let len = match endpoint.frames.as_mut().map(|f| f.next(src_fd, p_in)) {
+            None => MAX_SPLICE_SIZE,
+            Some(Ok(Next::Splice(len))) => len.min(MAX_SPLICE_SIZE),
+            Some(Ok(Next::Wait)) => 0,
+            Some(Ok(Next::Limit)) => {
+                log::warn!(
+                    "[{:.6}] Session reached its limit, the Receiver was notified",
+                    id
+                );
+                return Ok(true);
+            }
+            Some(Ok(Next::Closed)) => return Ok(true),
+            Some(Err(e)) => {
+                log::error!("[{:.6}] Error following {:?}: {}", id, endpoint.dir, e);
+                return Ok(true);
+            }
+        }
+        .min(budget);
+
         unsafe {
             *libc::__errno_location() = 0;
-            rx = libc::splice(
-                src_fd,
-                std::ptr::null_mut::<libc::loff_t>(),
-                p_in,
-                std::ptr::null_mut::<libc::loff_t>(),
-                MAX_SPLICE_SIZE,
-                libc::SPLICE_F_MOVE | libc::SPLICE_F_NONBLOCK,
-            );
+            rx = match len {
+                0 => {
+                    *libc::__errno_location() = libc::EAGAIN;
+                    -1
+                }
+                _ => libc::splice(
+                    src_fd,
+                    std::ptr::null_mut::<libc::loff_t>(),
+                    p_in,
+                    std::ptr::null_mut::<libc::loff_t>(),
+                    len,
+                    libc::SPLICE_F_MOVE | libc::SPLICE_F_NONBLOCK,
+                ),
+            };
         }
 
         let errno = std::io::Error::last_os_error().raw_os_error().unwrap();
+        endpoint.stats.record_recv(rx);
+        if let Some(frames) = &mut endpoint.frames {
+            frames.advance(rx);
+        }
+        if rx > 0 {
+            budget -= rx as usize;
+        }
 
         // check if connection is closed
         if rx < 0 && errno != 0 && errno != libc::EWOULDBLOCK && errno != libc::EAGAIN {
@@ -63,18 +217,23 @@ pub fn tcp_splice(endpoint: &Endpoint, peer: &Endpoint) -> Result<bool, Box<dyn
             return Ok(true);
         }
 
-        unsafe {
-            tx = libc::splice(
-                p_out,
-                std::ptr::null_mut::<libc::loff_t>(),
-                dst_fd,
-                std::ptr::null_mut::<libc::loff_t>(),
-                MAX_SPLICE_SIZE,
-                libc::SPLICE_F_MOVE | libc::SPLICE_F_NONBLOCK,
-            );
-        }
+        // A Receiver that may resume keeps a copy of what it was sent
+        tx = match &mut peer.tail {
+            Some(tail) => tail.splice(p_out, dst_fd, MAX_SPLICE_SIZE),
+            None => unsafe {
+                libc::splice(
+                    p_out,
+                    std::ptr::null_mut::<libc::loff_t>(),
+                    dst_fd,
+                    std::ptr::null_mut::<libc::loff_t>(),
+                    MAX_SPLICE_SIZE,
+                    libc::SPLICE_F_MOVE | libc::SPLICE_F_NONBLOCK,
+                )
+            },
+        };
 
         let errno = std::io::Error::last_os_error().raw_os_error().unwrap();
+        peer.stats.record_send(tx);
 
         // check for errors
         if tx < 0 && errno != 0 && errno != libc::EWOULDBLOCK && errno != libc::EAGAIN {
@@ -106,7 +265,7 @@ pub fn tcp_splice(endpoint: &Endpoint, peer: &Endpoint) -> Result<bool, Box<dyn
 /**
  * Drain the pipe of any additional data destined for an Endpoint
  */
-pub fn drain_pipe(endpoint: &Endpoint) -> Result<bool, Box<dyn Error>> {
+pub fn drain_pipe(endpoint: &mut Endpoint) -> Result<bool, Box<dyn Error>> {
     let reader = match &endpoint.peer_reader {
         Some(p) => p,
         None => {
@@ -127,18 +286,22 @@ pub fn drain_pipe(endpoint: &Endpoint) -> Result<bool, Box<dyn Error>> {
         *errno = 0;
     }
     loop {
-        unsafe {
-            trx = libc::splice(
-                src_fd,
-                std::ptr::null_mut::<libc::loff_t>(),
-                dst_fd,
-                std::ptr::null_mut::<libc::loff_t>(),
-                MAX_SPLICE_SIZE,
-                libc::SPLICE_F_MOVE | libc::SPLICE_F_NONBLOCK,
-            );
-        }
+        trx = match &mut endpoint.tail {
+            Some(tail) => tail.splice(src_fd, dst_fd, MAX_SPLICE_SIZE),
+            None => unsafe {
+                libc::splice(
+                    src_fd,
+                    std::ptr::null_mut::<libc::loff_t>(),
+                    dst_fd,
+                    std::ptr::null_mut::<libc::loff_t>(),
+                    MAX_SPLICE_SIZE,
+                    libc::SPLICE_F_MOVE | libc::SPLICE_F_NONBLOCK,
+                )
+            },
+        };
 
         let errno = std::io::Error::last_os_error().raw_os_error().unwrap();
+        endpoint.stats.record_send(trx);
 
         // check for errors
         if trx < 0 && errno != 0 && errno != libc::EWOULDBLOCK && errno != libc::EAGAIN {
@@ -0,0 +1,248 @@
+use portal::errors::PortalError;
+use portal::{passphrase, Direction, Portal};
+use std::error::Error;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::auth;
+
+/// Payload exchanged between the two self-test clients
+const PROBE: &[u8] = b"portal-relay-healthz";
+
+/// How long each self-test connection may block
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Health requests answered at once, further ones are dropped
+const MAX_REQUESTS: usize = 16;
+
+/// Time given to the relay to register the self-test Sender
+const REGISTER_DELAY: Duration = Duration::from_millis(100);
+
+/// Results of the periodic self-tests
+#[derive(Debug, Default)]
+struct HealthStatus {
+    checks: u64,
+    failures: u64,
+    last_success: Option<Instant>,
+    last_latency: Option<Duration>,
+    last_error: Option<String>,
+}
+
+lazy_static! {
+    static ref HEALTH: Mutex<HealthStatus> = Mutex::new(HealthStatus::default());
+    static ref REQUESTS: AtomicUsize = AtomicUsize::new(0);
+}
+
+/**
+ * Start the self-test loop against the relay's own listener, and
+ * serve the results over HTTP on /healthz and /metrics
+ */
+pub fn spawn(
+    relay: SocketAddr,
+    listen: SocketAddr,
+    interval: Duration,
+) -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind(listen)?;
+    log::info!("Serving health checks on {}", listen);
+
+    thread::spawn(move || loop {
+        check(relay);
+        thread::sleep(interval);
+    });
+    thread::spawn(move || serve(listener, interval));
+    Ok(())
+}
+
+/**
+ * Run a single self-test and record the result
+ */
+fn check(relay: SocketAddr) {
+    let result = selftest(relay);
+    let mut status = HEALTH.lock().unwrap();
+    status.checks += 1;
+    match result {
+        Ok(latency) => {
+            log::debug!("Self-test succeeded in {:?}", latency);
+            status.last_success = Some(Instant::now());
+            status.last_latency = Some(latency);
+            status.last_error = None;
+        }
+        Err(e) => {
+            log::error!("Self-test failed: {}", e);
+            status.failures += 1;
+            status.last_error = Some(e.to_string());
+        }
+    }
+}
+
+/**
+ * Pair two loopback clients through the relay, exchange a probe over
+ * the encrypted channel, and return how long the Receiver took
+ */
+fn selftest(relay: SocketAddr) -> Result<Duration, Box<dyn Error>> {
+    // Random credentials so the check never collides with real transfers
+    let id = passphrase::generate(4);
+    let password = passphrase::generate(4);
+    let mut sender = Portal::init(Direction::Sender, id.clone(), password.clone())?;
+    let mut receiver = Portal::init(Direction::Receiver, id, password)?;
+    sender.set_relay_secret(auth::secret());
+    receiver.set_relay_secret(auth::secret());
+
+    let mut sender_stream = connect(relay)?;
+    let handle = thread::spawn(move || -> Result<(), String> {
+        sender
+            .handshake(&mut sender_stream)
+            .map_err(|e| e.to_string())?;
+        sender
+            .channel(&mut sender_stream)
+            .and_then(|mut c| c.write_chunk(PROBE))
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    });
+
+    // The Receiver is only paired once the Sender is pending
+    thread::sleep(REGISTER_DELAY);
+    let start = Instant::now();
+
+    let mut receiver_stream = connect(relay)?;
+    receiver.handshake(&mut receiver_stream)?;
+    let mut storage = [0u8; 64];
+    let len = receiver
+        .channel(&mut receiver_stream)?
+        .read_chunk(&mut storage)?;
+    let latency = start.elapsed();
+
+    handle.join().or(Err(PortalError::BadState))??;
+    if &storage[..len] != PROBE {
+        return Err(PortalError::BadMsg.into());
+    }
+    Ok(latency)
+}
+
+/**
+ * Connect to the relay with timeouts applied
+ */
+fn connect(relay: SocketAddr) -> Result<TcpStream, Box<dyn Error>> {
+    let stream = TcpStream::connect_timeout(&relay, TIMEOUT)?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    Ok(stream)
+}
+
+/**
+ * Answer HTTP requests, each on its own thread so that a slow client
+ * can't hold up the rest
+ */
+fn serve(listener: TcpListener, interval: Duration) {
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(s) => s,
+            Err(_) => continue,
+        };
+        if REQUESTS.fetch_add(1, Ordering::SeqCst) >= MAX_REQUESTS {
+            REQUESTS.fetch_sub(1, Ordering::SeqCst);
+            log::warn!("Too many health requests, dropping one");
+            continue;
+        }
+        thread::spawn(move || {
+            respond(stream, interval);
+            REQUESTS.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+}
+
+/**
+ * Answer a single HTTP request. The relay is healthy if the most
+ * recent self-test succeeded within two intervals.
+ */
+fn respond(mut stream: TcpStream, interval: Duration) {
+    let _ = stream.set_read_timeout(Some(TIMEOUT));
+    let _ = stream.set_write_timeout(Some(TIMEOUT));
+
+    // Only the request line matters
+    let mut request = [0u8; 1024];
+    let len = match stream.read(&mut request) {
+        Ok(len) => len,
+        Err(_) => return,
+    };
+    let path = String::from_utf8_lossy(&request[..len])
+        .split_whitespace()
+        .nth(1)
+        .unwrap_or_default()
+        .to_string();
+
+    let status = HEALTH.lock().unwrap();
+    let (code, body) = match path.as_str() {
+        "/healthz" => {
+            let healthy = status.last_error.is_none()
+                && status
+                    .last_success
+                    .is_some_and(|t| t.elapsed() < interval * 2);
+            match healthy {
+                true => ("200 OK", format!("ok {:?}\n", status.last_latency.unwrap())),
+                false => (
+                    "503 Service Unavailable",
+                    format!(
+                        "unhealthy: {}\n",
+                        status
+                            .last_error
+                            .as_deref()
+                            .unwrap_or("no recent self-test")
+                    ),
+                ),
+            }
+        }
+        "/metrics" => ("200 OK", metrics(&status) + &crate::metrics::render()),
+        _ => ("404 Not Found", String::new()),
+    };
+    drop(status);
+
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        code,
+        body.len(),
+        body
+    );
+}
+
+/**
+ * Self-test results in the Prometheus text format
+ */
+fn metrics(status: &HealthStatus) -> String {
+    let latency = status.last_latency.map_or(0.0, |l| l.as_secs_f64());
+    format!(
+        "# TYPE portal_relay_selftest_total counter\n\
+         portal_relay_selftest_total {}\n\
+         # TYPE portal_relay_selftest_failures_total counter\n\
+         portal_relay_selftest_failures_total {}\n\
+         # TYPE portal_relay_selftest_latency_seconds gauge\n\
+         portal_relay_selftest_latency_seconds {}\n",
+        status.checks, status.failures, latency
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slow_clients_dont_block_others() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || serve(listener, Duration::from_secs(60)));
+
+        // Connects but never sends its request
+        let _slow = TcpStream::connect(addr).unwrap();
+
+        let mut stream = connect(addr).unwrap();
+        stream.write_all(b"GET /nowhere HTTP/1.1\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 404"), "{}", response);
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::{metrics, EndpointPair};
+
+/// How often the event loop's counters are reported & reset
+const REPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Iterations taking longer than this hold up every other session
+const SLOW_ITERATION: Duration = Duration::from_millis(100);
+
+/// Events for a single pair in one interval before it may be busy looping
+const BUSY_EVENTS: u64 = 10_000;
+
+/// Share of an interval's events one pair needs to dominate the loop
+const DOMINANT_SHARE: f64 = 0.9;
+
+/// Events seen for one pair during the current interval
+#[derive(Debug, Default)]
+struct PairEvents {
+    events: u64,
+    spurious: u64,
+}
+
+/**
+ * Counters for the event loop, to diagnose it spinning. An event that
+ * moves no data in either direction is counted as a spurious wakeup,
+ * a pair that keeps receiving those is the usual cause of 100% CPU.
+ */
+#[derive(Debug)]
+pub struct LoopStats {
+    interval_start: Instant,
+    iteration_start: Instant,
+    iterations: u64,
+    events: u64,
+    spurious: u64,
+    busy: Duration,
+    slowest: Duration,
+    pairs: HashMap<String, PairEvents>,
+}
+
+impl LoopStats {
+    pub fn new() -> Self {
+        LoopStats {
+            interval_start: Instant::now(),
+            iteration_start: Instant::now(),
+            iterations: 0,
+            events: 0,
+            spurious: 0,
+            busy: Duration::ZERO,
+            slowest: Duration::ZERO,
+            pairs: HashMap::new(),
+        }
+    }
+
+    /**
+     * Start timing an iteration, once poll() has returned
+     */
+    pub fn begin(&mut self) {
+        self.iteration_start = Instant::now();
+        self.iterations += 1;
+        metrics::record_loop(1, 0, 0);
+    }
+
+    /**
+     * Record an event for a pair, and whether it moved any data
+     */
+    pub fn event(&mut self, id: &str, moved: bool) {
+        let pair = self.pairs.entry(id.to_string()).or_default();
+        pair.events += 1;
+        self.events += 1;
+        metrics::record_loop(0, 1, !moved as u64);
+        if !moved {
+            pair.spurious += 1;
+            self.spurious += 1;
+        }
+    }
+
+    /**
+     * Finish timing an iteration, reporting on the loop when due
+     */
+    pub fn end(&mut self, endpoints: &HashMap<String, EndpointPair>) {
+        let elapsed = self.iteration_start.elapsed();
+        if elapsed > SLOW_ITERATION {
+            log::warn!("Event loop iteration took {:?}", elapsed);
+        }
+        self.busy += elapsed;
+        self.slowest = self.slowest.max(elapsed);
+
+        if self.interval_start.elapsed() >= REPORT_INTERVAL {
+            self.report(endpoints);
+            *self = LoopStats::new();
+        }
+    }
+
+    /**
+     * Helper: log the interval's counters, warning about a pair that
+     * accounted for nearly all of the loop's events
+     */
+    fn report(&self, endpoints: &HashMap<String, EndpointPair>) {
+        let interval = self.interval_start.elapsed();
+        log::debug!(
+            "Event loop: {} iterations & {} events in {:?}, {} moved no data, {:.1}% busy, slowest {:?}",
+            self.iterations,
+            self.events,
+            interval,
+            self.spurious,
+            100.0 * self.busy.as_secs_f64() / interval.as_secs_f64(),
+            self.slowest
+        );
+
+        let (id, busiest) = match self.pairs.iter().max_by_key(|(_, p)| p.events) {
+            Some(busiest) => busiest,
+            None => return,
+        };
+        if busiest.events < BUSY_EVENTS
+            || (busiest.events as f64) < DOMINANT_SHARE * self.events as f64
+        {
+            return;
+        }
+        let would_block = |pair: &EndpointPair| {
+            (
+                100.0 * pair.sender.stats.would_block(),
+                100.0 * pair.receiver.stats.would_block(),
+            )
+        };
+        let (sender, receiver) = endpoints.get(id).map(would_block).unwrap_or_default();
+        log::warn!(
+            "[{:.6}] Session is dominating the event loop: {} of {} events in {:?}, {} moved no data. Splices that would block: {:.1}% Sender, {:.1}% Receiver",
+            id,
+            busiest.events,
+            self.events,
+            interval,
+            busiest.spurious,
+            sender,
+            receiver
+        );
+    }
+}
@@ -0,0 +1,952 @@
+//! The Portal relay, pairing Senders with Receivers by ID and splicing
+//! their connections together. The `portal-relay` binary wraps [`run`],
+//! and the `portal` client embeds it to host a relay of its own.
+extern crate portal_lib as portal;
+
+use mio::net::TcpStream;
+use mio::unix::EventedFd;
+use mio::{Events, Poll, PollOpt, Ready, Token};
+use mio_extras::channel::channel;
+use os_pipe::{PipeReader, PipeWriter};
+use portal::protocol::{PortalMessage, RelayControl, RelayControlError, WireCodec};
+use portal::Direction;
+use socket2::SockRef;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::error::Error;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::os::unix::io::AsRawFd;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+use structopt::StructOpt;
+use threadpool::ThreadPool;
+
+#[macro_use]
+extern crate lazy_static;
+
+mod auth;
+mod buffer;
+mod chaos;
+mod claims;
+mod cluster;
+mod handlers;
+mod health;
+mod instrument;
+mod limit;
+mod local;
+mod metrics;
+mod networking;
+mod persist;
+mod sandbox;
+mod scrub;
+mod tarpit;
+mod webhook;
+
+mod protocol;
+mod redirect;
+mod reload;
+mod replay;
+
+use protocol::register;
+
+// Some tokens to allow us to identify which event is for which socket.
+const SERVER: Token = Token(0);
+const CHANNEL: Token = Token(1);
+const RESUME: Token = Token(2);
+const RELOAD: Token = Token(3);
+const LOCAL: Token = Token(4);
+const SCRUB: Token = Token(5);
+
+/* From the cloudfare blog:
+ * There is no "good" splice buffer size. Anecdotical evidence
+ * says that it should be no larger than 512KiB since this is
+ * the max we can expect realistically to fit into cpu
+ * cache. */
+const MAX_SPLICE_SIZE: usize = 512 * 1024;
+
+/// How often session limits are checked, when configured
+const SWEEP_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long a session past its maximum duration has to pass on the
+/// notice before it is torn down regardless
+const NOTICE_GRACE: Duration = Duration::from_secs(10);
+
+lazy_static! {
+    static ref PENDING_ENDPOINTS: Mutex<HashMap<String, Endpoint>> = Mutex::new(HashMap::new());
+}
+
+#[derive(Debug)]
+pub struct Endpoint {
+    id: String,
+    dir: portal::Direction,
+    stream: TcpStream,
+    peer_writer: Option<PipeWriter>,
+    peer_reader: Option<PipeReader>,
+    has_peer: bool,
+    time_added: SystemTime,
+    ttl: Duration,
+    format: portal::WireFormat,
+    priority: portal::Priority,
+    codec: portal::CodecVersion,
+    capabilities: Option<portal::Capabilities>,
+    stats: handlers::SpliceStats,
+
+    // Whether the client sent no options, as clients that predate
+    // them expect their peer's Connect rather than a Paired message
+    legacy: bool,
+
+    // The codec of the client's request, which the relay answers with
+    framing: portal::CodecVersion,
+
+    // Who registered it, see local::peer_ip
+    owner: Option<IpAddr>,
+
+    // Follows the Sender's messages when sessions are limited
+    frames: Option<limit::Frames>,
+
+    // What was last sent to a Receiver that may resume
+    tail: Option<buffer::Tail>,
+}
+
+#[derive(Debug)]
+pub struct EndpointPair {
+    sender: Endpoint,
+    sender_token: Token,
+
+    receiver: Endpoint,
+    receiver_token: Token,
+
+    // Data held back from a Receiver that disconnected
+    spool: Option<buffer::Spool>,
+
+    // How the Receiver may resume, none for proxied sessions
+    ticket: Option<buffer::Ticket>,
+
+    // When the Receiver was sent notice the session is ending
+    expiring: Option<Instant>,
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "portal-relay", about = "A relay for Portal.")]
+pub struct Opt {
+    /// Activate daemon mode
+    /// short and long flags (-b, --background)
+    #[structopt(short, long)]
+    pub background: bool,
+
+    /// Listen for clients on this port
+    #[structopt(long, default_value = "13265")]
+    port: u16,
+
+    /// Exit once the first paired session has finished
+    #[structopt(long)]
+    one_shot: bool,
+
+    /// Serve /healthz and /metrics on this address, backed by
+    /// periodically pairing two clients through the relay
+    #[structopt(long)]
+    health_addr: Option<SocketAddr>,
+
+    /// Seconds between health self-tests
+    #[structopt(long, default_value = "30")]
+    health_interval: u64,
+
+    /// Save pending Senders to this file, so they may reconnect
+    /// after a restart without generating a new pass-phrase
+    #[structopt(long)]
+    state_file: Option<PathBuf>,
+
+    /// Seconds after a restart that a saved Sender's ID is
+    /// reserved for it to reconnect from the same address
+    #[structopt(long, default_value = "60")]
+    reconnect_grace: u64,
+
+    /// Read the settings that can be changed at runtime from this file,
+    /// reloading it when it changes or on SIGHUP
+    #[structopt(long)]
+    config: Option<PathBuf>,
+
+    #[structopt(flatten)]
+    settings: reload::Settings,
+
+    #[structopt(flatten)]
+    tuning: networking::TcpTuning,
+
+    #[structopt(flatten)]
+    sandbox: sandbox::Sandbox,
+
+    #[structopt(flatten)]
+    auth: auth::Auth,
+
+    #[structopt(flatten)]
+    buffering: buffer::Buffering,
+
+    #[structopt(flatten)]
+    cluster: cluster::Cluster,
+
+    #[structopt(flatten)]
+    webhook: webhook::Webhook,
+
+    #[structopt(flatten)]
+    local: local::Local,
+
+    #[structopt(flatten)]
+    chaos: chaos::Chaos,
+
+    #[structopt(flatten)]
+    scrub: scrub::Scrub,
+}
+
+/**
+ * Tear down paired sessions that have been idle, or open, for longer
+ * than allowed
+ */
+fn expire_sessions(
+    poll: &Poll,
+    endpoints: &mut HashMap<String, EndpointPair>,
+    id_lookup: &mut HashMap<Token, String>,
+    idle_timeout: Option<Duration>,
+    max_session: Option<Duration>,
+) {
+    endpoints.retain(|id, pair| {
+        let age = pair.sender.stats.age();
+        let idle = pair.sender.stats.idle().min(pair.receiver.stats.idle());
+        let reason = match (idle_timeout, max_session) {
+            _ if pair.spool.as_ref().is_some_and(buffer::Spool::expired) => {
+                "the Receiver did not resume"
+            }
+            (Some(limit), _) if idle > limit => "idle",
+            (_, Some(limit)) if age > limit => match end_session(poll, id_lookup, pair, limit) {
+                true => return true,
+                false => "exceeded the maximum duration",
+            },
+            _ => return true,
+        };
+
+        log::warn!("[{:.6}] Closing session after {:?}: {}", id, age, reason);
+        webhook::failed(pair, reason);
+        claims::release(id);
+        for (endpoint, token) in [
+            (&pair.sender, pair.sender_token),
+            (&pair.receiver, pair.receiver_token),
+        ] {
+            endpoint.stats.log_summary(&endpoint.id, endpoint.dir);
+            let _ = poll.deregister(&endpoint.stream);
+            let _ = endpoint.stream.shutdown(std::net::Shutdown::Both);
+            id_lookup.remove(&token);
+        }
+        false
+    });
+}
+
+/**
+ * Helper: end a session past its maximum duration with a SessionExpired
+ * in place of the Sender's next message, then close the Sender so the
+ * Receiver drains the pipe as it would at the end of a transfer. Returns
+ * false once the session should be torn down instead.
+ */
+fn end_session(
+    poll: &Poll,
+    id_lookup: &mut HashMap<Token, String>,
+    pair: &mut EndpointPair,
+    limit: Duration,
+) -> bool {
+    if pair.spool.is_some() {
+        return false;
+    }
+    let started = match pair.expiring {
+        Some(started) => started,
+        None => {
+            let notice = PortalMessage::SessionExpired(limit.as_secs());
+            let ending = pair.sender.frames.as_mut().map(|f| f.end_with(notice));
+            match ending {
+                Some(true) => *pair.expiring.insert(Instant::now()),
+                _ => return false,
+            }
+        }
+    };
+    if started.elapsed() > NOTICE_GRACE {
+        return false;
+    }
+
+    // The Sender's next event passes on the notice as well, this covers
+    // one that has gone quiet
+    let sender = &mut pair.sender;
+    let (frames, writer) = match (&mut sender.frames, &sender.peer_writer) {
+        (Some(frames), Some(writer)) => (frames, writer),
+        _ => return true,
+    };
+    match frames.next(sender.stream.as_raw_fd(), writer.as_raw_fd()) {
+        Ok(limit::Next::Limit) => {}
+        Ok(_) => return true,
+        Err(e) => {
+            log::error!("[{:.6}] Error ending the session: {}", sender.id, e);
+            return false;
+        }
+    }
+
+    log::info!("[{:.6}] Removing {:?} connection", sender.id, sender.dir);
+    sender.stats.log_summary(&sender.id, sender.dir);
+    let _ = poll.deregister(&sender.stream);
+    let _ = sender.stream.shutdown(std::net::Shutdown::Both);
+    id_lookup.remove(&pair.sender_token);
+    drop(sender.peer_writer.take());
+    pair.receiver.has_peer = false;
+    poll.reregister(
+        &pair.receiver.stream,
+        pair.receiver_token,
+        Ready::writable(),
+        PollOpt::edge(),
+    )
+    .is_ok()
+}
+
+/**
+ * Resume polling endpoints whose hold under --chaos has passed
+ */
+fn rearm(
+    poll: &Poll,
+    weather: &mut chaos::Weather,
+    endpoints: &HashMap<String, EndpointPair>,
+    id_lookup: &HashMap<Token, String>,
+) {
+    weather.retain(|token| id_lookup.contains_key(token));
+    for token in weather.release(Instant::now()) {
+        let pair = match id_lookup.get(&token).and_then(|id| endpoints.get(id)) {
+            Some(pair) => pair,
+            None => continue,
+        };
+        let stream = match token {
+            x if x == pair.sender_token => &pair.sender.stream,
+            _ => &pair.receiver.stream,
+        };
+        if let Err(e) = poll.reregister(stream, token, Ready::readable(), PollOpt::level()) {
+            log::error!(
+                "[{:.6}] Error releasing a held endpoint: {}",
+                pair.sender.id,
+                e
+            );
+        }
+    }
+}
+
+// increment the polling token by one
+// for each new client connection
+pub fn next(current: &mut Token) -> Token {
+    let next = current.0;
+    current.0 += 1;
+    Token(next)
+}
+
+/**
+ * Reattach a Receiver that reconnected to a session with buffered data.
+ * The Receiver must present the session's resume token. Whatever was
+ * sent to it that it never read is sent again from the tail, as long
+ * as the tail still holds it, followed by what was held back.
+ */
+fn resume(
+    poll: &Poll,
+    endpoints: &mut HashMap<String, EndpointPair>,
+    id_lookup: &mut HashMap<Token, String>,
+    resume: buffer::Resume,
+    token: Token,
+) -> Result<(), Box<dyn Error>> {
+    let buffer::Resume {
+        id,
+        received,
+        token: resume_token,
+        mut stream,
+        addr,
+        framing,
+    } = resume;
+
+    let result = match endpoints.get_mut(&id) {
+        Some(pair) if pair.spool.as_ref().is_some_and(buffer::Spool::away) => match pair.ticket {
+            Some(ticket) if ticket.salt.verify_resume(&id, &resume_token) => {
+                let sent = ticket.preamble + pair.receiver.stats.sent();
+                let rewound = (ticket.preamble..=sent).contains(&received)
+                    && pair
+                        .receiver
+                        .tail
+                        .as_mut()
+                        .is_some_and(|tail| tail.rewind(sent - received));
+                match rewound {
+                    true => {
+                        pair.receiver.stats.rewind(received - ticket.preamble);
+                        Ok(pair)
+                    }
+                    false => Err(RelayControlError::DataLost),
+                }
+            }
+            _ => Err(RelayControlError::NotPermitted),
+        },
+        _ => Err(RelayControlError::UnknownId),
+    };
+
+    // Respond before any buffered data is replayed
+    let response = match &result {
+        Ok(_) => RelayControl::Ack,
+        Err(e) => RelayControl::Error(*e),
+    };
+    SockRef::from(&stream).set_nonblocking(false)?;
+    framing.send(&PortalMessage::RelayControl(response), &mut stream)?;
+    SockRef::from(&stream).set_nonblocking(true)?;
+
+    let pair = match result {
+        Ok(pair) => pair,
+        Err(e) => {
+            log::info!("[{:.6}] Refused to resume {:?}: {:?}", id, addr, e);
+            let _ = stream.shutdown(std::net::Shutdown::Both);
+            return Ok(());
+        }
+    };
+
+    log::info!("[{:.6}] Receiver resumed from {:?}", id, addr);
+    pair.receiver.stream = stream;
+    pair.receiver_token = token;
+    pair.spool.as_mut().unwrap().rejoin();
+    poll.register(
+        &pair.receiver.stream,
+        token,
+        Ready::readable() | Ready::writable(),
+        PollOpt::level(),
+    )?;
+    id_lookup.insert(token, id);
+    Ok(())
+}
+
+/**
+ * Run the relay until an error occurs, or until the first session
+ * has finished when started with --one-shot
+ */
+pub fn run(opt: Opt) -> Result<(), Box<dyn Error>> {
+    log::info!("Starting portal relay");
+
+    // Create a poll instance.
+    let poll = Poll::new()?;
+
+    // Create storage for events.
+    let mut events = Events::with_capacity(128);
+
+    // Setup the server socket.
+    let addr = format!("0.0.0.0:{}", opt.port).parse()?;
+    let server = opt.tuning.listen(&addr)?;
+
+    log::info!("Listening on {}", addr);
+
+    // Secrets may only be readable before privileges are dropped
+    opt.auth.init()?;
+    opt.cluster.init()?;
+    opt.webhook.init()?;
+    let local_listener = opt.local.listen()?;
+    let mut weather = opt.chaos.start()?;
+
+    // Self-test through the listener we just bound, over loopback
+    // when it listens on every address
+    if let Some(health_addr) = opt.health_addr {
+        let relay = match server.local_addr()? {
+            bound if bound.ip().is_unspecified() => match bound {
+                SocketAddr::V4(_) => SocketAddr::from((Ipv4Addr::LOCALHOST, bound.port())),
+                SocketAddr::V6(_) => SocketAddr::from((Ipv6Addr::LOCALHOST, bound.port())),
+            },
+            bound => bound,
+        };
+        let interval = Duration::from_secs(opt.health_interval);
+        health::spawn(relay, health_addr, interval)?;
+    }
+
+    // Start listening for incoming connections.
+    poll.register(&server, SERVER, Ready::readable(), PollOpt::edge())?;
+    if let Some(listener) = &local_listener {
+        let fd = listener.as_raw_fd();
+        poll.register(&EventedFd(&fd), LOCAL, Ready::readable(), PollOpt::edge())?;
+    }
+
+    // Apply the config file over the command line, this also starts
+    // the tarpit delaying repeated bad handshakes outside of the threadpool
+    let mut base = opt.settings.clone();
+    base.log_level.get_or_insert(log::max_level());
+    let mut settings = reload::load(&base, opt.config.as_ref())?;
+    settings.apply();
+
+    // Pre-allocate a few registration threads
+    let thread_pool = ThreadPool::new(4);
+
+    // Create a channel to receive pairs from threads
+    let (tx, rx) = channel::<EndpointPair>();
+    poll.register(&rx, CHANNEL, Ready::readable(), PollOpt::edge())?;
+
+    // And Receivers resuming a buffered session
+    let (resume_tx, resume_rx) = channel::<buffer::Resume>();
+    poll.register(&resume_rx, RESUME, Ready::readable(), PollOpt::edge())?;
+
+    // And settings changed at runtime
+    let (reload_tx, reload_rx) = channel::<reload::Settings>();
+    poll.register(&reload_rx, RELOAD, Ready::readable(), PollOpt::edge())?;
+    if opt.config.is_some() || opt.auth.auth_secrets.is_some() {
+        reload::spawn(base, opt.config.clone(), opt.auth.clone(), reload_tx)?;
+    }
+
+    // And periodic audits for leaked state
+    let (scrub_tx, scrub_rx) = channel::<()>();
+    poll.register(&scrub_rx, SCRUB, Ready::readable(), PollOpt::edge())?;
+    opt.scrub.spawn(scrub_tx);
+
+    // Everything privileged is done, restrict ourselves from here on
+    opt.sandbox.apply()?;
+
+    // Restore pending Senders from a previous run
+    if let Some(path) = opt.state_file.clone() {
+        persist::init(path, Duration::from_secs(opt.reconnect_grace))?;
+    }
+
+    // Active endpoint pairs
+    let id_lookup: Rc<RefCell<HashMap<Token, String>>> = Rc::new(RefCell::new(HashMap::new()));
+    let endpoints: Rc<RefCell<HashMap<String, EndpointPair>>> =
+        Rc::new(RefCell::new(HashMap::new()));
+
+    let mut unique_token = Token(SCRUB.0 + 1);
+    let buffering = opt.buffering.clone();
+
+    // Whether a session has been paired, for --one-shot
+    let mut paired = false;
+
+    // Watch for the loop spinning
+    let mut loop_stats = instrument::LoopStats::new();
+
+    // Start an event loop.
+    loop {
+        let idle_timeout = settings.idle_timeout.map(Duration::from_secs);
+        let max_session = settings.max_session.map(Duration::from_secs);
+        redirect::set_sessions(endpoints.borrow().len());
+
+        // Wake periodically to check session limits, if any are set
+        let sweep = match idle_timeout.is_some() || max_session.is_some() || buffering.enabled() {
+            true => Some(SWEEP_INTERVAL),
+            false => None,
+        };
+
+        // And to release endpoints held back by --chaos
+        let timeout = match weather
+            .as_ref()
+            .and_then(|w| w.next_release(Instant::now()))
+        {
+            Some(wake) => Some(sweep.map_or(wake, |sweep| sweep.min(wake))),
+            None => sweep,
+        };
+
+        // Poll Mio for events, blocking until we get an event.
+        poll.poll(&mut events, timeout)?;
+        loop_stats.begin();
+        if let Some(weather) = &mut weather {
+            rearm(&poll, weather, &endpoints.borrow(), &id_lookup.borrow());
+        }
+        if sweep.is_some() {
+            expire_sessions(
+                &poll,
+                &mut endpoints.borrow_mut(),
+                &mut id_lookup.borrow_mut(),
+                idle_timeout,
+                max_session,
+            );
+        }
+
+        // Process each event.
+        for event in events.iter() {
+            match event.token() {
+                /*
+                 * When receiving an incoming connection, use the threadpool to accept
+                 * Portal requests without blocking the main loop
+                 */
+                SERVER | LOCAL => loop {
+                    // If this is an event for a listener, it means a connection
+                    // is ready to be accepted.
+                    let accepted = match (event.token(), &local_listener) {
+                        (LOCAL, Some(listener)) => local::accept(listener),
+                        _ => match server.accept() {
+                            Ok(accepted) => Ok(Some(accepted)),
+                            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => Ok(None),
+                            Err(e) => Err(e),
+                        },
+                    };
+                    let (connection, addr) = match accepted {
+                        Ok(Some(accepted)) => accepted,
+                        Ok(None) => {
+                            // go back to polling for connections
+                            break;
+                        }
+                        Err(e) => {
+                            return Err(Box::new(e));
+                        }
+                    };
+
+                    log::debug!("[+] New connection from {:?}", addr);
+
+                    // Accepted sockets don't inherit every option
+                    if event.token() == SERVER {
+                        if let Err(e) = opt.tuning.accepted(&connection) {
+                            log::error!("Error tuning connection from {:?}: {}", addr, e);
+                        }
+                    }
+
+                    // TODO set RECV_TIMEO
+                    let tx_new = tx.clone();
+                    let resume_tx = resume_tx.clone();
+                    let settings = settings.clone();
+                    thread_pool.execute(move || {
+                        match register(addr, connection, tx_new, resume_tx, settings) {
+                            Ok(_) => {}
+                            Err(_e) => {
+                                log::error!("Error creating portal: {}", _e);
+                            }
+                        }
+                    });
+                },
+                /*
+                 * When a worker thread has completed pairing two peers, the EndpointPair
+                 * will be sent over an MPSC channel to be added to the list of file descriptors
+                 * we're polling
+                 */
+                CHANNEL => {
+                    while let Ok(mut pair) = rx.try_recv() {
+                        paired = true;
+                        pair.sender_token = next(&mut unique_token);
+                        pair.receiver_token = next(&mut unique_token);
+
+                        // Measure each session from when splicing begins
+                        pair.sender.stats = handlers::SpliceStats::new();
+                        pair.receiver.stats = handlers::SpliceStats::new();
+                        webhook::paired(&pair.sender.id);
+                        if buffering.enabled() && pair.ticket.is_some() {
+                            let reader = pair.receiver.peer_reader.as_ref().unwrap();
+                            match buffering.tail(reader) {
+                                Ok(tail) => pair.receiver.tail = Some(tail),
+                                Err(e) => log::error!(
+                                    "[{:.6}] Error keeping the Receiver's tail: {}",
+                                    pair.sender.id,
+                                    e
+                                ),
+                            }
+                        }
+                        // Limited sessions are ended on a message boundary
+                        if settings.max_transfer_mb.is_some() || settings.max_session.is_some() {
+                            let codec = portal::CodecVersion::negotiate(
+                                pair.sender.codec,
+                                pair.receiver.codec,
+                            );
+                            let limit = settings
+                                .max_transfer_mb
+                                .map_or(u64::MAX, |mb| mb * 1024 * 1024);
+                            limit::follow(&mut pair.sender, limit, codec)?;
+                        }
+
+                        poll.register(
+                            &pair.sender.stream,
+                            pair.sender_token,
+                            Ready::readable() | Ready::writable(),
+                            PollOpt::edge(),
+                        )?;
+                        poll.register(
+                            &pair.receiver.stream,
+                            pair.receiver_token,
+                            Ready::readable(),
+                            PollOpt::level(),
+                        )?;
+
+                        id_lookup
+                            .borrow_mut()
+                            .entry(pair.sender_token)
+                            .or_insert_with(|| pair.sender.id.clone());
+                        id_lookup
+                            .borrow_mut()
+                            .entry(pair.receiver_token)
+                            .or_insert_with(|| pair.sender.id.clone());
+                        endpoints
+                            .borrow_mut()
+                            .entry(pair.sender.id.clone())
+                            .or_insert_with(|| pair);
+                    }
+                }
+                /*
+                 * The config file has changed, established pairs are left as they are
+                 */
+                RELOAD => {
+                    while let Ok(reloaded) = reload_rx.try_recv() {
+                        log::info!("Applying {:?}", reloaded);
+                        reloaded.apply();
+                        settings = reloaded;
+                    }
+                }
+                /*
+                 * The scrubber has audited pending Senders, audit the sessions as well
+                 */
+                SCRUB => {
+                    while scrub_rx.try_recv().is_ok() {
+                        scrub::sessions(
+                            &poll,
+                            &mut endpoints.borrow_mut(),
+                            &mut id_lookup.borrow_mut(),
+                        );
+                    }
+                }
+                /*
+                 * A Receiver has reconnected to a session whose data was buffered
+                 * while it was away
+                 */
+                RESUME => {
+                    while let Ok(request) = resume_rx.try_recv() {
+                        let token = next(&mut unique_token);
+                        if let Err(e) = resume(
+                            &poll,
+                            &mut endpoints.borrow_mut(),
+                            &mut id_lookup.borrow_mut(),
+                            request,
+                            token,
+                        ) {
+                            log::error!("Error resuming a session: {}", e);
+                        }
+                    }
+                }
+                /*
+                 * Any other events indicate there is data we need to channel between two TCP connections
+                 * at this time we primarily use splice() to do that
+                 */
+                token => {
+                    let mut ref_endpoints = endpoints.borrow_mut();
+                    let lookup = id_lookup.borrow();
+
+                    let id = match lookup.get(&token) {
+                        Some(id) => id,
+                        None => {
+                            continue;
+                        }
+                    }
+                    .clone();
+
+                    // get the EndpointPair that generated the event
+                    let pair = match ref_endpoints.get_mut(&id) {
+                        Some(p) => p,
+                        None => {
+                            continue;
+                        }
+                    };
+
+                    drop(lookup);
+
+                    // determine which Endpoint triggered the event
+                    let (side, endpoint, peer) = match token {
+                        x if x == pair.sender_token => {
+                            (Direction::Sender, &mut pair.sender, &mut pair.receiver)
+                        }
+                        x if x == pair.receiver_token => {
+                            (Direction::Receiver, &mut pair.receiver, &mut pair.sender)
+                        }
+                        _ => {
+                            continue;
+                        }
+                    };
+
+                    log::debug!("[{:.6}] {:?} Event: {:?}", id, side, event);
+                    let before = endpoint.stats.total() + peer.stats.total();
+
+                    let mut done = false;
+
+                    // Under --chaos, data may be held back, throttled or the session reset
+                    let mut held = false;
+                    let mut budget = None;
+                    if let (Some(weather), true) = (&mut weather, event.readiness().is_readable()) {
+                        match weather.arrive(token, Instant::now()) {
+                            chaos::Verdict::Forward(allowance) => budget = allowance,
+                            chaos::Verdict::Hold => held = true,
+                            chaos::Verdict::Reset => {
+                                log::warn!("[{:.6}] Chaos: resetting the {:?}", id, side);
+                                chaos::reset(&endpoint.stream);
+                                done = true;
+                            }
+                        }
+                    }
+
+                    // if we received data on this endpoint, splice it to the peer, unless
+                    // the Receiver is away or catching up
+                    if event.readiness().is_readable() && !held && !done {
+                        let received = endpoint.stats.received();
+                        done = match (&mut pair.spool, side) {
+                            (Some(spool), Direction::Sender) => {
+                                spool.fill(endpoint, peer.peer_reader.as_ref().unwrap())?
+                            }
+                            _ => handlers::tcp_splice(endpoint, peer, budget)?,
+                        };
+                        if let Some(weather) = &mut weather {
+                            weather.forwarded(token, endpoint.stats.received() - received);
+                        }
+                    }
+
+                    // if we got a writable event, then there is pending data in the intermediary pipe,
+                    // or buffered data to replay
+                    if event.readiness().is_writable() {
+                        done = match (&mut pair.spool, side) {
+                            (Some(spool), Direction::Receiver) => spool.replay(endpoint)?,
+                            _ => handlers::drain_pipe(endpoint)?,
+                        };
+
+                        // Turn off writable notifications for the Sender if on, this is only used
+                        // to kick off the initial message exchange by draining the initial pipe
+                        if side == Direction::Sender {
+                            poll.reregister(
+                                &endpoint.stream,
+                                token,
+                                Ready::readable(),
+                                PollOpt::level(),
+                            )?;
+                        }
+                    }
+
+                    // Stop polling a held endpoint until it is released
+                    if held && !done {
+                        poll.reregister(&endpoint.stream, token, Ready::empty(), PollOpt::level())?;
+                    }
+
+                    // Stop reading from the Sender once the buffer is full, and go back to
+                    // splicing directly once a resumed Receiver has caught up
+                    if let (Some(spool), false) = (&mut pair.spool, done) {
+                        if side == Direction::Sender && spool.is_full() && !spool.paused {
+                            log::warn!("[{:.6}] Buffer is full, pausing the Sender", id);
+                            poll.deregister(&endpoint.stream)?;
+                            spool.paused = true;
+                        }
+                        if side == Direction::Receiver && spool.is_empty() {
+                            log::info!("[{:.6}] Receiver caught up", id);
+                            if spool.paused {
+                                poll.register(
+                                    &peer.stream,
+                                    pair.sender_token,
+                                    Ready::readable(),
+                                    PollOpt::level(),
+                                )?;
+                            }
+                            pair.spool = None;
+                            poll.reregister(
+                                &endpoint.stream,
+                                token,
+                                Ready::readable(),
+                                PollOpt::level(),
+                            )?;
+                            done = handlers::drain_pipe(endpoint)?;
+                        }
+                    }
+
+                    log::debug!("[{:.6}] Handler finished. Done: {:?}", id, done);
+                    loop_stats.event(&id, endpoint.stats.total() + peer.stats.total() > before);
+
+                    // A Receiver that disconnects while the Sender is still around
+                    // may resume, buffer the rest of the transfer until it does
+                    if done
+                        && side == Direction::Receiver
+                        && endpoint.has_peer
+                        && buffering.enabled()
+                    {
+                        let spool = match pair.spool.take() {
+                            Some(spool) => Ok(spool),
+                            None => buffering.spool(),
+                        };
+                        match spool {
+                            Ok(mut spool) => {
+                                log::info!(
+                                    "[{:.6}] Receiver disconnected, buffering for up to {:?}",
+                                    id,
+                                    buffering.grace()
+                                );
+                                spool.leave(buffering.grace());
+                                if let Some(frames) = &mut peer.frames {
+                                    frames.unfollow(peer.stats.received());
+                                }
+                                pair.spool = Some(spool);
+                                poll.deregister(&endpoint.stream)?;
+                                id_lookup.borrow_mut().remove(&token);
+                                _ = endpoint.stream.shutdown(std::net::Shutdown::Both);
+                                continue;
+                            }
+                            Err(e) => log::error!("[{:.6}] Error creating buffer: {}", id, e),
+                        }
+                    }
+
+                    // If this connection is finished, or our peer has disconnected
+                    // shutdown the connection
+                    if done {
+                        // There may still be some data in the Receiver's pipe, drain it
+                        // before closing the peer connection. We must register for writeable
+                        // events in case the Receiver's socket is still blocking. An absent
+                        // Receiver gets it from the buffer instead, once it resumes.
+                        let away = match &mut pair.spool {
+                            Some(spool) if spool.away() => {
+                                spool.flush_pipe(peer.peer_reader.as_ref().unwrap())?;
+                                true
+                            }
+                            _ => false,
+                        };
+                        if side == Direction::Sender && !away {
+                            if let Some(weather) = &mut weather {
+                                weather.forget(pair.receiver_token);
+                            }
+                            match poll.reregister(
+                                &peer.stream,
+                                pair.receiver_token,
+                                Ready::writable(),
+                                PollOpt::edge(),
+                            ) {
+                                Ok(_) => {}
+                                Err(e) => {
+                                    log::error!("[{:.6}] Error: {:?}", id, e);
+                                }
+                            }
+                        }
+
+                        log::info!(
+                            "[{:.6}] Removing {:?} connection",
+                            endpoint.id,
+                            endpoint.dir
+                        );
+                        endpoint.stats.log_summary(&endpoint.id, endpoint.dir);
+
+                        // Shutdown this endpoint
+                        poll.deregister(&endpoint.stream)?;
+                        let id = id_lookup.borrow_mut().remove(&token);
+                        _ = endpoint.stream.shutdown(std::net::Shutdown::Both); // ignore shutdown errors
+
+                        // close the write end of the pipe, otherwise splice() will continually
+                        // return EWOULDBLOCK intead of knowing when there is no data left
+                        let old_writer = endpoint.peer_writer.take();
+                        drop(old_writer);
+
+                        // indicate to the peer that this endpoint is gone
+                        peer.has_peer = false;
+
+                        // If our peer is also gone, remove the entire EndpointPair
+                        if !endpoint.has_peer {
+                            let id = id.unwrap_or_else(|| "none".to_string());
+                            if let Some(pair) = ref_endpoints.remove(&id) {
+                                match pair.expiring {
+                                    Some(_) => {
+                                        webhook::failed(&pair, "exceeded the maximum duration")
+                                    }
+                                    None => webhook::completed(&pair),
+                                }
+                                claims::release(&id);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        loop_stats.end(&endpoints.borrow());
+
+        // The first session is over, nothing is left to relay
+        if opt.one_shot && paired && endpoints.borrow().is_empty() {
+            log::info!("Session finished, exiting");
+            return Ok(());
+        }
+    }
+}
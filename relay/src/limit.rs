@@ -0,0 +1,604 @@
+use portal_lib::protocol::{
+    CodecVersion, EncryptedMessage, PortalMessage, TlvCodec, WireCodec, MAX_HANDSHAKE_MESSAGE_SIZE,
+};
+use std::error::Error;
+use std::io::{self, Cursor};
+use std::os::unix::io::{AsRawFd, RawFd};
+
+use crate::Endpoint;
+
+/// Length of the CRC32C trailer that follows each chunk when the peers
+/// enabled checksums, which the chunk's header doesn't count
+const TRAILER: usize = 4;
+
+/// More variants than PortalMessage has. A v1 message starts with its
+/// variant's index as a little endian u32.
+const V1_VARIANTS: u32 = 256;
+
+/// The type of every v2 PortalMessage's value, a variant
+const V2_VARIANT: u8 = 0x0c;
+
+lazy_static! {
+    /// The start of every v1 EncryptedDataHeader & its length on the wire
+    static ref HEADER: (Vec<u8>, usize) = {
+        let header = PortalMessage::EncryptedDataHeader(EncryptedMessage::default())
+            .to_bytes()
+            .unwrap_or_default();
+        (header[..4.min(header.len())].to_vec(), header.len())
+    };
+}
+
+/// What the Sender's stream may do next
+#[derive(Debug, PartialEq, Eq)]
+pub enum Next {
+    /// Splice up to this many bytes of the current message
+    Splice(usize),
+    /// Nothing can move until the socket is readable or the pipe
+    /// has room again
+    Wait,
+    /// The limit was reached, or the session ended, & the Receiver
+    /// notified, close the session
+    Limit,
+    /// The Sender closed the connection
+    Closed,
+}
+
+/**
+ * Follows the messages the Sender passes through the relay, so that a
+ * session reaching its byte limit can be ended on a message boundary
+ * with a TransferLimit the Receiver can read, rather than a reset.
+ *
+ * Message headers are read into userspace to find where each message
+ * ends, everything else is still spliced. Streams that can't be followed
+ * are cut off at the limit without notice.
+ *
+ * The handshake is always in the v1 codec & every message after is in
+ * the session's codec. Peers that measure the round-trip time follow
+ * the Sender's Confirm with a Ping & Pong, which end the handshake
+ * instead, so the codec of the message after the Confirm is detected.
+ *
+ * Peers that enabled checksums follow each chunk with a trailer the relay
+ * isn't told about. What comes after a chunk is taken for a trailer if it
+ * doesn't start like a message would.
+ */
+#[derive(Debug)]
+pub struct Frames {
+    limit: u64,
+    // Bytes of whole messages let through so far
+    forwarded: u64,
+    // Bytes left in the current message
+    remaining: u64,
+    // The next header, as far as it has been read
+    header: Vec<u8>,
+    // Bytes to write to the pipe before anything else
+    pending: Vec<u8>,
+    following: bool,
+    limited: bool,
+    // Whether a trailer may come next
+    after_data: bool,
+    // Sent in place of the next message, see Frames::end_with
+    notice: Option<PortalMessage>,
+    // Codec of the next message & the one the session switches to
+    codec: CodecVersion,
+    session: CodecVersion,
+    // Whether the last message was the Sender's Confirm
+    confirmed: bool,
+}
+
+impl Frames {
+    pub fn new(limit: u64, session: CodecVersion) -> Self {
+        Frames {
+            limit,
+            forwarded: 0,
+            remaining: 0,
+            header: Vec::new(),
+            pending: Vec::new(),
+            following: true,
+            limited: false,
+            after_data: false,
+            notice: None,
+            codec: CodecVersion::V1,
+            session,
+            confirmed: false,
+        }
+    }
+
+    /**
+     * Stop following messages, as the stream is about to be read
+     * elsewhere. The limit is then enforced on `received`, the bytes
+     * read from the Sender so far.
+     */
+    pub fn unfollow(&mut self, received: u64) {
+        if self.following {
+            self.following = false;
+            self.pending.append(&mut self.header);
+            self.remaining = self.limit.saturating_sub(received);
+        }
+    }
+
+    /**
+     * End the session on the next message boundary, sending `notice`
+     * in place of the next message. Returns false if the stream isn't
+     * followed, or is already ending.
+     */
+    pub fn end_with(&mut self, notice: PortalMessage) -> bool {
+        if !self.following || self.limited || self.notice.is_some() {
+            return false;
+        }
+        self.notice = Some(notice);
+        true
+    }
+
+    /**
+     * Write out anything held back, returns true once nothing is
+     */
+    pub fn flush(&mut self, pipe: RawFd) -> io::Result<bool> {
+        if !self.pending.is_empty() {
+            let written = write(pipe, &self.pending)?;
+            self.pending.drain(..written);
+        }
+        Ok(self.pending.is_empty())
+    }
+
+    /**
+     * Work out how much of the Sender's stream may be spliced next,
+     * reading the next message's header if one is due
+     */
+    pub fn next(&mut self, src: RawFd, pipe: RawFd) -> Result<Next, Box<dyn Error>> {
+        loop {
+            // Headers & the final notice are written out first
+            if !self.flush(pipe)? {
+                return Ok(Next::Wait);
+            }
+            if self.limited {
+                return Ok(Next::Limit);
+            }
+            if self.remaining > 0 {
+                return Ok(Next::Splice(self.remaining.min(usize::MAX as u64) as usize));
+            }
+            if !self.following {
+                return Ok(Next::Limit);
+            }
+            if let (Some(notice), false) = (&self.notice, self.after_data) {
+                self.pending = self.codec.encode(notice)?;
+                self.header.clear();
+                self.limited = true;
+                continue;
+            }
+
+            // Read the header a little at a time, never past its end
+            let want = self.want();
+            if want > 0 {
+                let mut buf = vec![0u8; want];
+                match recv(src, &mut buf)? {
+                    Some(0) => {
+                        // Pass on the end of a stream too short to tell apart,
+                        // such as the last trailer
+                        self.pending.append(&mut self.header);
+                        self.flush(pipe)?;
+                        return Ok(Next::Closed);
+                    }
+                    Some(read) => self.header.extend_from_slice(&buf[..read]),
+                    // Messages are sent whole, a message would have
+                    // arrived with its start. Don't hold up the notice.
+                    None if self.after_data
+                        && self.notice.is_some()
+                        && self.header.len() == TRAILER =>
+                    {
+                        self.pending = self.header.drain(..).collect();
+                        self.forwarded += TRAILER as u64;
+                        self.after_data = false;
+                        continue;
+                    }
+                    None => return Ok(Next::Wait),
+                }
+            }
+
+            // A trailer is passed on as part of the chunk before it
+            if self.after_data {
+                match starts_message(self.codec, &self.header) {
+                    None => continue,
+                    Some(true) => {
+                        self.after_data = false;
+                        continue;
+                    }
+                    Some(false) => {
+                        self.pending = self.header.drain(..TRAILER).collect();
+                        self.forwarded += TRAILER as u64;
+                        self.after_data = false;
+                        continue;
+                    }
+                }
+            }
+
+            // The handshake ends with the Confirm unless a Ping follows
+            if self.confirmed {
+                match CodecVersion::detect(&self.header) {
+                    Some(CodecVersion::V1) => self.confirmed = false,
+                    Some(_) => {
+                        self.confirmed = false;
+                        self.codec = self.session;
+                    }
+                    None => continue,
+                }
+            }
+
+            let mut cursor = Cursor::new(&self.header);
+            let decoded = self.codec.decode(&mut cursor, MAX_HANDSHAKE_MESSAGE_SIZE);
+            self.after_data = matches!(decoded, Ok(PortalMessage::EncryptedDataHeader(_)));
+            let body = match decoded {
+                Ok(PortalMessage::EncryptedDataHeader(header)) => header.len as u64,
+                Ok(PortalMessage::Confirm(_)) => {
+                    self.confirmed = true;
+                    0
+                }
+                Ok(PortalMessage::Pong(_)) => {
+                    self.codec = self.session;
+                    0
+                }
+                Ok(_) => 0,
+                Err(_) if want > 0 && (self.header.len() as u64) < MAX_HANDSHAKE_MESSAGE_SIZE => {
+                    continue
+                }
+                Err(_) => {
+                    log::warn!("Can't follow the Sender's messages, counting bytes instead");
+                    let received = self.forwarded + self.header.len() as u64;
+                    self.unfollow(received);
+                    continue;
+                }
+            };
+
+            // Let the message through, or the TransferLimit in its place
+            let size = self.header.len() as u64 + body;
+            if self.forwarded + size > self.limit {
+                self.pending = self
+                    .codec
+                    .encode(&PortalMessage::TransferLimit(self.limit))?;
+                self.header.clear();
+                self.limited = true;
+            } else {
+                self.forwarded += size;
+                self.remaining = body;
+                self.pending = std::mem::take(&mut self.header);
+            }
+        }
+    }
+
+    /**
+     * Bytes of the next header still to be read. v1 headers are read up
+     * to their known length once their start matches, anything else a
+     * byte at a time. v2 frames carry their length.
+     */
+    fn want(&self) -> usize {
+        let read = self.header.len();
+        if self.after_data {
+            return message_start(self.codec).saturating_sub(read);
+        }
+        if self.codec != CodecVersion::V1 {
+            let len = TlvCodec::frame_len(&self.header).unwrap_or(4);
+            return (len.min(MAX_HANDSHAKE_MESSAGE_SIZE) as usize).saturating_sub(read);
+        }
+        let (prefix, len) = (&HEADER.0, HEADER.1);
+        match read {
+            read if read < prefix.len() => prefix.len() - read,
+            read if self.header[..prefix.len()] == prefix[..] => len - read,
+            _ => 1,
+        }
+    }
+
+    /**
+     * Record bytes spliced from the current message
+     */
+    pub fn advance(&mut self, len: isize) {
+        self.remaining = self.remaining.saturating_sub(len.max(0) as u64);
+    }
+}
+
+/**
+ * Helper: bytes needed to tell whether a message starts here, the v1
+ * variant index or the v2 frame length & type
+ */
+fn message_start(codec: CodecVersion) -> usize {
+    match codec {
+        CodecVersion::V1 => 4,
+        _ => 5,
+    }
+}
+
+/**
+ * Helper: whether the data starts like a message, rather than a trailer.
+ * None until enough has been read to tell.
+ */
+fn starts_message(codec: CodecVersion, data: &[u8]) -> Option<bool> {
+    let start = data.get(..message_start(codec))?;
+    Some(match codec {
+        CodecVersion::V1 => {
+            u32::from_le_bytes([start[0], start[1], start[2], start[3]]) < V1_VARIANTS
+        }
+        _ => {
+            let len = TlvCodec::frame_len(start).unwrap_or_default();
+            len <= MAX_HANDSHAKE_MESSAGE_SIZE && start[4] == V2_VARIANT
+        }
+    })
+}
+
+/**
+ * Helper: read what is available without blocking, None if nothing is
+ */
+fn recv(fd: RawFd, buf: &mut [u8]) -> io::Result<Option<usize>> {
+    let read = unsafe {
+        libc::recv(
+            fd,
+            buf.as_mut_ptr() as *mut libc::c_void,
+            buf.len(),
+            libc::MSG_DONTWAIT,
+        )
+    };
+    match read {
+        x if x >= 0 => Ok(Some(x as usize)),
+        _ => match io::Error::last_os_error() {
+            e if e.kind() == io::ErrorKind::WouldBlock => Ok(None),
+            e => Err(e),
+        },
+    }
+}
+
+/**
+ * Helper: write what fits in the pipe without blocking
+ */
+fn write(pipe: RawFd, data: &[u8]) -> io::Result<usize> {
+    let written = unsafe { libc::write(pipe, data.as_ptr() as *const libc::c_void, data.len()) };
+    match written {
+        x if x >= 0 => Ok(x as usize),
+        _ => match io::Error::last_os_error() {
+            e if e.kind() == io::ErrorKind::WouldBlock => Ok(0),
+            e => Err(e),
+        },
+    }
+}
+
+/**
+ * Limit the bytes a Sender may send, following its messages from here on
+ */
+pub fn follow(sender: &mut Endpoint, limit: u64, codec: CodecVersion) -> io::Result<()> {
+    // Headers are written to the pipe from the event loop
+    if let Some(writer) = &sender.peer_writer {
+        let fd = writer.as_raw_fd();
+        let flags = unsafe { libc::fcntl(fd, libc::F_GETFL) };
+        if flags < 0 || unsafe { libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    sender.frames = Some(Frames::new(limit, codec));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use portal_lib::protocol::{NonceSequence, PortalConfirmation, Protocol, WireFormat};
+    use portal_lib::EncryptedChannel;
+    use std::io::{Read, Write};
+    use std::os::unix::net::UnixStream;
+
+    const CODECS: [CodecVersion; 2] = [CodecVersion::V1, CodecVersion::V2];
+
+    /// Helper: what a Sender sends after pairing, the end of the handshake
+    /// then a few files of metadata, chunks & end-of-stream markers
+    fn record(session: CodecVersion, checksums: bool) -> Vec<u8> {
+        record_with(session, checksums, true)
+    }
+
+    /// Helper: record a Sender's stream, with or without the round-trip
+    /// time being measured after its Confirm
+    fn record_with(session: CodecVersion, checksums: bool, probed: bool) -> Vec<u8> {
+        let key = [7u8; 32];
+        let mut nseq = NonceSequence::new();
+        let confirm = PortalMessage::Confirm(PortalConfirmation([3u8; 42]));
+        let mut wire = CodecVersion::V1.encode(&confirm).unwrap();
+        if probed {
+            for msg in [PortalMessage::Ping(1), PortalMessage::Pong(1)].iter() {
+                wire.extend(CodecVersion::V1.encode(msg).unwrap());
+            }
+        }
+        for file in 0..3 {
+            let metadata = format!("file {}", file);
+            let format = WireFormat::default();
+            Protocol::encrypt_and_write_object(
+                &mut wire, &key, &mut nseq, session, format, &metadata,
+            )
+            .unwrap();
+            let mut channel = EncryptedChannel::new(&mut wire, &key, &mut nseq)
+                .with_codec(session)
+                .with_checksums(checksums);
+            for chunk in 0..4 {
+                channel.write_chunk(&[chunk; 1000]).unwrap();
+            }
+            channel.finish().unwrap();
+        }
+        wire
+    }
+
+    /// Helper: pass a recorded stream through Frames as the event loop
+    /// would, ending it once `end_at` bytes were let through, returning
+    /// what reached the Receiver & whether the messages were followed
+    /// throughout
+    fn relay(
+        stream: &[u8],
+        limit: u64,
+        session: CodecVersion,
+        end_at: Option<u64>,
+    ) -> (Vec<u8>, bool) {
+        let (mut sender, src) = UnixStream::pair().unwrap();
+        let (pipe, mut receiver) = UnixStream::pair().unwrap();
+        sender.write_all(stream).unwrap();
+        drop(sender);
+
+        let mut frames = Frames::new(limit, session);
+        loop {
+            if end_at.is_some_and(|end| frames.forwarded >= end) {
+                frames.end_with(PortalMessage::SessionExpired(1));
+            }
+            match frames.next(src.as_raw_fd(), pipe.as_raw_fd()).unwrap() {
+                Next::Splice(len) => {
+                    let mut buf = vec![0u8; len.min(4096)];
+                    let read = (&src).read(&mut buf).unwrap();
+                    if read == 0 {
+                        break;
+                    }
+                    (&pipe).write_all(&buf[..read]).unwrap();
+                    frames.advance(read as isize);
+                }
+                Next::Wait => panic!("Stalled on a recorded stream"),
+                Next::Limit | Next::Closed => break,
+            }
+        }
+        drop(pipe);
+
+        let mut received = Vec::new();
+        receiver.read_to_end(&mut received).unwrap();
+        (received, frames.following)
+    }
+
+    #[test]
+    fn follows_recorded_streams() {
+        for &session in CODECS.iter() {
+            for &checksums in [false, true].iter() {
+                let stream = record(session, checksums);
+                let (received, followed) = relay(&stream, u64::MAX, session, None);
+                assert!(followed, "{:?} checksums: {}", session, checksums);
+                assert!(received == stream, "{:?} checksums: {}", session, checksums);
+            }
+        }
+    }
+
+    #[test]
+    fn follows_streams_without_probes() {
+        for &session in CODECS.iter() {
+            let stream = record_with(session, false, false);
+            let (received, followed) = relay(&stream, u64::MAX, session, None);
+            assert!(followed, "{:?}", session);
+            assert!(received == stream, "{:?}", session);
+
+            // Limited on a boundary of the session's messages
+            let limit = stream.len() as u64 / 2;
+            let (received, followed) = relay(&stream, limit, session, None);
+            assert!(followed, "{:?}", session);
+            let notice = session
+                .encode(&PortalMessage::TransferLimit(limit))
+                .unwrap();
+            assert!(received.ends_with(&notice), "{:?}", session);
+        }
+    }
+
+    #[test]
+    fn limits_on_a_message_boundary() {
+        for &session in CODECS.iter() {
+            for &checksums in [false, true].iter() {
+                let stream = record(session, checksums);
+                let limit = stream.len() as u64 / 2;
+                let (received, followed) = relay(&stream, limit, session, None);
+                assert!(followed, "{:?} checksums: {}", session, checksums);
+
+                // Whole messages, trailers included, then the notice
+                let notice = session
+                    .encode(&PortalMessage::TransferLimit(limit))
+                    .unwrap();
+                let cut = received.len() - notice.len();
+                assert!(cut as u64 <= limit);
+                assert_eq!(received[..cut], stream[..cut]);
+                assert_eq!(received[cut..], notice[..]);
+                let next = session.decode(&mut &stream[cut..], MAX_HANDSHAKE_MESSAGE_SIZE);
+                assert!(
+                    matches!(next, Ok(PortalMessage::EncryptedDataHeader(_))),
+                    "{:?} checksums: {}",
+                    session,
+                    checksums
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn ends_with_a_notice() {
+        for &session in CODECS.iter() {
+            for &checksums in [false, true].iter() {
+                let stream = record(session, checksums);
+                let half = stream.len() as u64 / 2;
+                let (received, followed) = relay(&stream, u64::MAX, session, Some(half));
+                assert!(followed, "{:?} checksums: {}", session, checksums);
+
+                let notice = session.encode(&PortalMessage::SessionExpired(1)).unwrap();
+                let cut = received.len() - notice.len();
+                assert!(cut as u64 >= half);
+                assert_eq!(received[..cut], stream[..cut]);
+                assert_eq!(received[cut..], notice[..]);
+                let next = session.decode(&mut &stream[cut..], MAX_HANDSHAKE_MESSAGE_SIZE);
+                assert!(next.is_ok(), "{:?} checksums: {}", session, checksums);
+            }
+        }
+    }
+
+    #[test]
+    fn idle_senders_are_ended() {
+        for &session in CODECS.iter() {
+            // The Sender waits after its last chunk & trailer
+            let key = [7u8; 32];
+            let mut nseq = NonceSequence::new();
+            let mut stream = CodecVersion::V1.encode(&PortalMessage::Pong(1)).unwrap();
+            EncryptedChannel::new(&mut stream, &key, &mut nseq)
+                .with_codec(session)
+                .with_checksums(true)
+                .write_chunk(&[1; 1000])
+                .unwrap();
+
+            let (mut sender, src) = UnixStream::pair().unwrap();
+            let (pipe, mut receiver) = UnixStream::pair().unwrap();
+            sender.write_all(&stream).unwrap();
+            let mut frames = Frames::new(u64::MAX, session);
+            loop {
+                match frames.next(src.as_raw_fd(), pipe.as_raw_fd()).unwrap() {
+                    Next::Splice(len) => {
+                        let mut buf = vec![0u8; len];
+                        let read = (&src).read(&mut buf).unwrap();
+                        (&pipe).write_all(&buf[..read]).unwrap();
+                        frames.advance(read as isize);
+                    }
+                    Next::Wait if frames.end_with(PortalMessage::SessionExpired(1)) => {}
+                    next => {
+                        assert_eq!(next, Next::Limit, "{:?}", session);
+                        break;
+                    }
+                }
+            }
+            drop(pipe);
+
+            let mut received = Vec::new();
+            receiver.read_to_end(&mut received).unwrap();
+            stream.extend(session.encode(&PortalMessage::SessionExpired(1)).unwrap());
+            assert!(received == stream, "{:?}", session);
+        }
+    }
+
+    #[test]
+    fn trailers_are_told_from_messages() {
+        let header = CodecVersion::V1
+            .encode(&PortalMessage::TransferLimit(1))
+            .unwrap();
+        assert_eq!(starts_message(CodecVersion::V1, &header), Some(true));
+        assert_eq!(
+            starts_message(CodecVersion::V1, &[0xde, 0xad, 0xbe, 0xef]),
+            Some(false)
+        );
+        assert_eq!(starts_message(CodecVersion::V1, &header[..3]), None);
+
+        let frame = CodecVersion::V2
+            .encode(&PortalMessage::TransferLimit(1))
+            .unwrap();
+        assert_eq!(starts_message(CodecVersion::V2, &frame), Some(true));
+        let mut trailed = vec![0, 0, 0, 1];
+        trailed.extend_from_slice(&frame);
+        assert_eq!(starts_message(CodecVersion::V2, &trailed), Some(false));
+        assert_eq!(starts_message(CodecVersion::V2, &frame[..4]), None);
+    }
+}
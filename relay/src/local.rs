@@ -0,0 +1,250 @@
+use mio::net::TcpStream;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+use std::os::unix::fs::FileTypeExt;
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use std::os::unix::net::UnixListener;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use structopt::StructOpt;
+
+/// Clients on the same host, connecting without loopback TCP
+#[derive(Debug, Clone, StructOpt)]
+pub struct Local {
+    /// Also accept clients on this Unix socket, replacing any stale
+    /// socket left at the path
+    #[structopt(long)]
+    pub local_socket: Option<PathBuf>,
+
+    /// Trust local clients running as this user, they may connect
+    /// without an --auth-secrets token. May be given more than once,
+    /// defaults to the user the relay was started as.
+    #[structopt(long = "local-uid", number_of_values = 1)]
+    pub local_uids: Vec<u32>,
+}
+
+/// The kernel's tables of TCP sockets, which say which user owns each
+const PROC_NET_TCP: &[&str] = &["/proc/net/tcp", "/proc/net/tcp6"];
+
+lazy_static! {
+    static ref TRUSTED: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+    static ref SOCKET_TABLES: Mutex<Vec<File>> = Mutex::new(Vec::new());
+}
+
+impl Local {
+    /**
+     * Bind the socket & settle who to trust, before dropping privileges
+     */
+    pub fn listen(&self) -> Result<Option<UnixListener>, Box<dyn Error>> {
+        // Kept open to tell loopback clients apart after a --chroot
+        *SOCKET_TABLES.lock().unwrap() = PROC_NET_TCP
+            .iter()
+            .filter_map(|path| File::open(path).ok())
+            .collect();
+
+        let path = match &self.local_socket {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+        if fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_socket()) {
+            fs::remove_file(path)?;
+        }
+        let listener = UnixListener::bind(path)?;
+        listener.set_nonblocking(true)?;
+
+        let mut trusted = self.local_uids.clone();
+        if trusted.is_empty() {
+            trusted.push(unsafe { libc::getuid() });
+        }
+        log::info!("Listening on {:?}, trusting uids {:?}", path, trusted);
+        *TRUSTED.lock().unwrap() = trusted;
+        Ok(Some(listener))
+    }
+}
+
+/**
+ * Accept a local client if one is waiting. splice() & the event loop
+ * only deal in file descriptors, so the connection is handed to the
+ * pairing logic as a TcpStream, whose reads, writes & shutdown are plain
+ * socket calls. It is given its user's address for logging, tarpitting
+ * & ownership, see `user_addr`.
+ */
+pub fn accept(listener: &UnixListener) -> io::Result<Option<(TcpStream, SocketAddr)>> {
+    let stream = match listener.accept() {
+        Ok((stream, _)) => stream,
+        Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let uid = uid(stream.as_raw_fd());
+    log::debug!("[+] New local connection from uid {:?}", uid);
+
+    // Should the kernel not say, the user is treated as uid -1
+    let stream = unsafe { std::net::TcpStream::from_raw_fd(stream.into_raw_fd()) };
+    let addr = SocketAddr::new(user_addr(uid.unwrap_or(u32::MAX)), 0);
+    Ok(Some((TcpStream::from_stream(stream)?, addr)))
+}
+
+/**
+ * Whether a connection came from a trusted local user
+ */
+pub fn trusted(connection: &TcpStream) -> bool {
+    uid(connection.as_raw_fd()).is_some_and(|uid| TRUSTED.lock().unwrap().contains(&uid))
+}
+
+/**
+ * Who a connection came from. Local users each have their own address,
+ * whether they connected through the Unix socket or over loopback, so
+ * they can't act on each other's registrations or share a tarpit.
+ */
+pub fn peer_ip(connection: &TcpStream) -> Option<IpAddr> {
+    if let Some(uid) = uid(connection.as_raw_fd()) {
+        return Some(user_addr(uid));
+    }
+    let peer = connection.peer_addr().ok()?;
+    if !peer.ip().is_loopback() {
+        return Some(peer.ip());
+    }
+    let local = connection.local_addr().ok()?;
+    match loopback_uid(peer, local) {
+        Some(uid) => Some(user_addr(uid)),
+        None => Some(peer.ip()),
+    }
+}
+
+/**
+ * Helper: the address a local user is known by. It's in 100::/64, the
+ * discard-only prefix, which no remote client can connect from.
+ */
+fn user_addr(uid: u32) -> IpAddr {
+    let (high, low) = ((uid >> 16) as u16, uid as u16);
+    IpAddr::V6(Ipv6Addr::new(0x100, 0, 0, 0, 0, 0, high, low))
+}
+
+/**
+ * Helper: the user that owns the loopback connection from `peer` to
+ * `local`, from the kernel's socket tables
+ */
+fn loopback_uid(peer: SocketAddr, local: SocketAddr) -> Option<u32> {
+    let mut tables = SOCKET_TABLES.lock().unwrap();
+    tables.iter_mut().find_map(|table| {
+        let mut contents = String::new();
+        table.seek(SeekFrom::Start(0)).ok()?;
+        table.read_to_string(&mut contents).ok()?;
+        find_owner(&contents, peer, local)
+    })
+}
+
+/**
+ * Helper: find the socket from `peer` to `local` in a socket table,
+ * returning the uid that owns it
+ */
+fn find_owner(table: &str, peer: SocketAddr, local: SocketAddr) -> Option<u32> {
+    table.lines().skip(1).find_map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let (from, to, uid) = (fields.get(1)?, fields.get(2)?, fields.get(7)?);
+        match parse_addr(from)? == peer && parse_addr(to)? == local {
+            true => uid.parse().ok(),
+            false => None,
+        }
+    })
+}
+
+/**
+ * Helper: parse an address from a socket table, which prints each
+ * 32-bit word of the address in host byte order & the port in hex
+ */
+fn parse_addr(field: &str) -> Option<SocketAddr> {
+    let (ip, port) = field.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+    let mut bytes = Vec::with_capacity(16);
+    for i in (0..ip.len()).step_by(8) {
+        let word = u32::from_str_radix(ip.get(i..i + 8)?, 16).ok()?;
+        bytes.extend_from_slice(&word.to_ne_bytes());
+    }
+    let ip = match bytes.len() {
+        4 => IpAddr::from(<[u8; 4]>::try_from(bytes).ok()?),
+        16 => match Ipv6Addr::from(<[u8; 16]>::try_from(bytes).ok()?) {
+            ip if ip.to_ipv4_mapped().is_some() => IpAddr::V4(ip.to_ipv4_mapped()?),
+            ip => IpAddr::V6(ip),
+        },
+        _ => return None,
+    };
+    Some(SocketAddr::new(ip, port))
+}
+
+/**
+ * Helper: the user on the other end of a Unix socket, None for
+ * any other kind of socket
+ */
+fn uid(fd: RawFd) -> Option<u32> {
+    let mut addr: libc::sockaddr_storage = unsafe { std::mem::zeroed() };
+    let mut len = std::mem::size_of_val(&addr) as libc::socklen_t;
+    let res =
+        unsafe { libc::getsockname(fd, &mut addr as *mut _ as *mut libc::sockaddr, &mut len) };
+    if res < 0 || addr.ss_family != libc::AF_UNIX as libc::sa_family_t {
+        return None;
+    }
+
+    let mut cred: libc::ucred = unsafe { std::mem::zeroed() };
+    let mut len = std::mem::size_of_val(&cred) as libc::socklen_t;
+    let res = unsafe {
+        libc::getsockopt(
+            fd,
+            libc::SOL_SOCKET,
+            libc::SO_PEERCRED,
+            &mut cred as *mut _ as *mut libc::c_void,
+            &mut len,
+        )
+    };
+    match res {
+        0 => Some(cred.uid),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn user_addrs_are_distinct() {
+        assert_ne!(user_addr(1000), user_addr(1001));
+        assert_ne!(user_addr(0), IpAddr::V4(Ipv4Addr::LOCALHOST));
+        assert!(!user_addr(u32::MAX).is_loopback());
+    }
+
+    #[test]
+    fn finds_loopback_owner() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let client = std::net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (_accepted, peer) = listener.accept().unwrap();
+        let local = listener.local_addr().unwrap();
+        assert_eq!(peer, client.local_addr().unwrap());
+
+        // Found by the client's end of the connection
+        let table = fs::read_to_string("/proc/net/tcp").unwrap();
+        let ours = unsafe { libc::getuid() };
+        assert_eq!(find_owner(&table, peer, local), Some(ours));
+        let unrelated = SocketAddr::from(([127, 0, 0, 1], 1));
+        assert_eq!(find_owner(&table, unrelated, local), None);
+    }
+
+    #[test]
+    fn parses_socket_table_addrs() {
+        let v4 = SocketAddr::from(([127, 0, 0, 1], 13265));
+        let word = u32::from_ne_bytes([127, 0, 0, 1]);
+        assert_eq!(parse_addr(&format!("{:08X}:33D1", word)), Some(v4));
+
+        let mapped = [0, 0, u32::from_ne_bytes([0, 0, 0xff, 0xff]), word];
+        let field = mapped
+            .iter()
+            .map(|w| format!("{:08X}", w))
+            .collect::<String>();
+        assert_eq!(parse_addr(&format!("{}:33D1", field)), Some(v4));
+        assert_eq!(parse_addr("garbage"), None);
+    }
+}
@@ -0,0 +1,110 @@
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Upper bounds, in seconds, of the pairing latency buckets. Pairing
+/// includes the time a human takes to share and enter the pass-phrase.
+const PAIRING_BUCKETS: [f64; 11] = [
+    1.0, 5.0, 15.0, 30.0, 60.0, 120.0, 300.0, 600.0, 900.0, 1800.0, 3600.0,
+];
+
+/// A cumulative histogram in the style of Prometheus
+#[derive(Debug)]
+pub struct Histogram {
+    bounds: &'static [f64],
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    pub fn new(bounds: &'static [f64]) -> Self {
+        Histogram {
+            bounds,
+            counts: vec![0; bounds.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    /// Record a single observation
+    pub fn observe(&mut self, value: f64) {
+        for (bound, count) in self.bounds.iter().zip(self.counts.iter_mut()) {
+            if value <= *bound {
+                *count += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+
+    /// Render in the Prometheus text format
+    pub fn render(&self, name: &str, out: &mut String) {
+        let _ = writeln!(out, "# TYPE {} histogram", name);
+        for (bound, count) in self.bounds.iter().zip(self.counts.iter()) {
+            let _ = writeln!(out, "{}_bucket{{le=\"{}\"}} {}", name, bound, count);
+        }
+        let _ = writeln!(out, "{}_bucket{{le=\"+Inf\"}} {}", name, self.count);
+        let _ = writeln!(out, "{}_sum {}", name, self.sum);
+        let _ = writeln!(out, "{}_count {}", name, self.count);
+    }
+}
+
+/// Event loop counters, for diagnosing the loop spinning
+static LOOP_ITERATIONS: AtomicU64 = AtomicU64::new(0);
+static LOOP_EVENTS: AtomicU64 = AtomicU64::new(0);
+static LOOP_SPURIOUS: AtomicU64 = AtomicU64::new(0);
+
+/// Receivers turned away from a session another Receiver claimed
+static REJECTED_RECEIVERS: AtomicU64 = AtomicU64::new(0);
+
+lazy_static! {
+    static ref PAIRING: Mutex<Histogram> = Mutex::new(Histogram::new(&PAIRING_BUCKETS));
+}
+
+/**
+ * Record the time between a Sender registering and its Receiver connecting
+ */
+pub fn record_pairing(waited: Duration) {
+    PAIRING.lock().unwrap().observe(waited.as_secs_f64());
+}
+
+/**
+ * Add to the event loop's iterations, events & events that moved no data
+ */
+pub fn record_loop(iterations: u64, events: u64, spurious: u64) {
+    LOOP_ITERATIONS.fetch_add(iterations, Ordering::Relaxed);
+    LOOP_EVENTS.fetch_add(events, Ordering::Relaxed);
+    LOOP_SPURIOUS.fetch_add(spurious, Ordering::Relaxed);
+}
+
+/**
+ * Count a Receiver refused because its session was already claimed
+ */
+pub fn record_rejected_receiver() {
+    REJECTED_RECEIVERS.fetch_add(1, Ordering::Relaxed);
+}
+
+/**
+ * All relay metrics in the Prometheus text format
+ */
+pub fn render() -> String {
+    let mut out = String::new();
+    PAIRING
+        .lock()
+        .unwrap()
+        .render("portal_relay_pairing_seconds", &mut out);
+    for (name, counter) in [
+        ("portal_relay_loop_iterations_total", &LOOP_ITERATIONS),
+        ("portal_relay_loop_events_total", &LOOP_EVENTS),
+        ("portal_relay_loop_spurious_events_total", &LOOP_SPURIOUS),
+        ("portal_relay_rejected_receivers_total", &REJECTED_RECEIVERS),
+    ] {
+        let _ = writeln!(out, "# TYPE {} counter", name);
+        let _ = writeln!(out, "{} {}", name, counter.load(Ordering::Relaxed));
+    }
+    let _ = writeln!(out, "# TYPE portal_relay_paired_ids gauge");
+    let _ = writeln!(out, "portal_relay_paired_ids {}", crate::claims::count());
+    out
+}
@@ -1,6 +1,137 @@
-use mio::net::TcpStream;
+use mio::net::{TcpListener, TcpStream};
+use portal::Priority;
+use socket2::{Domain, SockRef, Socket, TcpKeepalive, Type};
 use std::error::Error;
 use std::io::{self, Read};
+use std::net::SocketAddr;
+use std::os::unix::io::AsRawFd;
+use std::time::Duration;
+use structopt::StructOpt;
+
+/// Socket options applied to the listener and every accepted connection
+#[derive(Debug, Clone, StructOpt)]
+pub struct TcpTuning {
+    /// Set SO_REUSEADDR on the listener
+    #[structopt(long, default_value = "true", parse(try_from_str))]
+    pub reuse_address: bool,
+
+    /// Set SO_REUSEPORT on the listener, allowing several relays to share the port
+    #[structopt(long)]
+    pub reuse_port: bool,
+
+    /// Maximum length of the queue of pending connections
+    #[structopt(long, default_value = "1024")]
+    pub backlog: i32,
+
+    /// Set TCP_NODELAY, disabling Nagle's algorithm
+    #[structopt(long)]
+    pub nodelay: bool,
+
+    /// Seconds of idle time before TCP keepalive probes are sent
+    #[structopt(long)]
+    pub keepalive: Option<u64>,
+
+    /// Seconds between TCP keepalive probes
+    #[structopt(long, requires = "keepalive")]
+    pub keepalive_interval: Option<u64>,
+
+    /// Size in bytes of the socket send buffer (SO_SNDBUF)
+    #[structopt(long)]
+    pub send_buffer: Option<usize>,
+
+    /// Size in bytes of the socket receive buffer (SO_RCVBUF)
+    #[structopt(long)]
+    pub recv_buffer: Option<usize>,
+
+    /// Enable TCP_FASTOPEN on the listener with this many pending
+    /// Fast Open requests allowed
+    #[structopt(long)]
+    pub fast_open: Option<i32>,
+
+    /// Set TCP_DEFER_ACCEPT on the listener, only waking the relay once
+    /// data has arrived or this many seconds have passed
+    #[structopt(long)]
+    pub defer_accept: Option<i32>,
+}
+
+impl TcpTuning {
+    /**
+     * Options shared by the listener and accepted connections
+     */
+    fn apply(&self, socket: &SockRef) -> io::Result<()> {
+        socket.set_nodelay(self.nodelay)?;
+        if let Some(idle) = self.keepalive {
+            let mut keepalive = TcpKeepalive::new().with_time(Duration::from_secs(idle));
+            if let Some(interval) = self.keepalive_interval {
+                keepalive = keepalive.with_interval(Duration::from_secs(interval));
+            }
+            socket.set_tcp_keepalive(&keepalive)?;
+        }
+        if let Some(size) = self.send_buffer {
+            socket.set_send_buffer_size(size)?;
+        }
+        if let Some(size) = self.recv_buffer {
+            socket.set_recv_buffer_size(size)?;
+        }
+        Ok(())
+    }
+
+    /**
+     * Create a listener bound to addr with these options applied
+     */
+    pub fn listen(&self, addr: &SocketAddr) -> io::Result<TcpListener> {
+        let socket = Socket::new(Domain::for_address(*addr), Type::STREAM, None)?;
+        socket.set_reuse_address(self.reuse_address)?;
+        socket.set_reuse_port(self.reuse_port)?;
+        if let Some(queue) = self.fast_open {
+            setsockopt(&socket, libc::IPPROTO_TCP, libc::TCP_FASTOPEN, queue)?;
+        }
+        if let Some(secs) = self.defer_accept {
+            setsockopt(&socket, libc::IPPROTO_TCP, libc::TCP_DEFER_ACCEPT, secs)?;
+        }
+        self.apply(&SockRef::from(&socket))?;
+        socket.bind(&(*addr).into())?;
+        socket.listen(self.backlog)?;
+        TcpListener::from_std(socket.into())
+    }
+
+    /**
+     * Apply these options to an accepted connection
+     */
+    pub fn accepted(&self, stream: &TcpStream) -> io::Result<()> {
+        self.apply(&SockRef::from(stream))
+    }
+}
+
+/**
+ * Mark a connection's outgoing packets with the DSCP of a session's
+ * priority. Best-effort, as not every network stack allows it.
+ */
+pub fn mark(stream: &TcpStream, priority: Priority) {
+    let ipv6 = stream.local_addr().is_ok_and(|a| a.is_ipv6());
+    if let Err(e) = portal::set_dscp(stream, ipv6, priority.dscp()) {
+        log::debug!("Failed to set the DSCP of {:?}: {}", stream.peer_addr(), e);
+    }
+}
+
+/**
+ * Set an integer socket option that socket2 does not expose
+ */
+fn setsockopt(socket: &Socket, level: i32, name: i32, value: i32) -> io::Result<()> {
+    let res = unsafe {
+        libc::setsockopt(
+            socket.as_raw_fd(),
+            level,
+            name,
+            &value as *const i32 as *const libc::c_void,
+            std::mem::size_of::<i32>() as libc::socklen_t,
+        )
+    };
+    match res {
+        0 => Ok(()),
+        _ => Err(io::Error::last_os_error()),
+    }
+}
 
 fn would_block(err: &io::Error) -> bool {
     err.kind() == io::ErrorKind::WouldBlock
@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::io::Write;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::Endpoint;
+
+/// A pending Sender known from before a restart
+#[derive(Debug)]
+pub struct Restored {
+    pub ip: IpAddr,
+    pub time_added: SystemTime,
+    pub ttl: Duration,
+    deadline: Instant,
+}
+
+lazy_static! {
+    static ref RESTORED: Mutex<HashMap<String, Restored>> = Mutex::new(HashMap::new());
+    static ref SAVER: Mutex<Option<Sender<String>>> = Mutex::new(None);
+}
+
+/**
+ * Load the pending Senders saved by a previous run. Each may reconnect
+ * from the same address within the grace window and keep its original
+ * registration, afterwards the ID is treated like any other. Lines
+ * that can't be parsed are logged & skipped.
+ */
+pub fn init(path: PathBuf, grace: Duration) -> Result<(), Box<dyn Error>> {
+    let mut restored = RESTORED.lock().unwrap();
+    if let Ok(contents) = fs::read_to_string(&path) {
+        for (number, line) in contents.lines().enumerate() {
+            let (id, entry) = match parse(line, grace) {
+                Some(parsed) => parsed,
+                None => {
+                    log::warn!("Skipping line {} of {:?}: {:?}", number + 1, path, line);
+                    continue;
+                }
+            };
+
+            // Skip registrations that expired while the relay was down
+            if entry.time_added.elapsed().unwrap_or_default() < entry.ttl {
+                restored.insert(id, entry);
+            }
+        }
+    }
+    log::info!(
+        "Restored {} pending Sender(s) from {:?}",
+        restored.len(),
+        path
+    );
+
+    // Written by another thread, so that saving never holds up pairing
+    let (tx, rx) = mpsc::channel();
+    *SAVER.lock().unwrap() = Some(tx);
+    thread::spawn(move || write(path, rx));
+    Ok(())
+}
+
+/**
+ * Helper: parse a line of the state file, "id ip added ttl"
+ */
+fn parse(line: &str, grace: Duration) -> Option<(String, Restored)> {
+    let fields = line.split(' ').collect::<Vec<&str>>();
+    let (id, ip, added, ttl) = match fields[..] {
+        [id, ip, added, ttl] if !id.is_empty() => (id, ip, added, ttl),
+        _ => return None,
+    };
+    let entry = Restored {
+        ip: ip.parse().ok()?,
+        time_added: UNIX_EPOCH.checked_add(Duration::from_secs(added.parse().ok()?))?,
+        ttl: Duration::from_secs(ttl.parse().ok()?),
+        deadline: Instant::now() + grace,
+    };
+    Some((id.to_string(), entry))
+}
+
+/**
+ * Claim a restored registration for this ID. Returns None if there
+ * is nothing to restore, or Err if the ID is reserved for a
+ * different owner until its grace window expires.
+ */
+pub fn reclaim(id: &str, owner: IpAddr) -> Result<Option<Restored>, ()> {
+    let mut restored = RESTORED.lock().unwrap();
+    restored.retain(|_, v| v.deadline > Instant::now());
+    match restored.get(id) {
+        Some(entry) if entry.ip != owner => Err(()),
+        Some(_) => Ok(restored.remove(id)),
+        None => Ok(None),
+    }
+}
+
+/**
+ * Save the pending Senders, if a state file is configured. Only the
+ * contents are prepared here, while the caller holds the endpoints,
+ * the file is written by another thread.
+ */
+pub fn save(endpoints: &HashMap<String, Endpoint>) {
+    let saver = match SAVER.lock().unwrap().clone() {
+        Some(saver) => saver,
+        None => return,
+    };
+
+    let mut contents = String::new();
+    for (id, endpoint) in endpoints.iter().filter(|(_, v)| !v.has_peer) {
+        // IDs are chosen by clients, only persist those that can be parsed back
+        if id.is_empty() || id.contains(char::is_whitespace) {
+            continue;
+        }
+        let ip = match endpoint.owner {
+            Some(ip) => ip,
+            None => continue,
+        };
+        let added = endpoint
+            .time_added
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        contents += &format!(
+            "{} {} {} {}\n",
+            id,
+            ip,
+            added.as_secs(),
+            endpoint.ttl.as_secs()
+        );
+    }
+    let _ = saver.send(contents);
+}
+
+/**
+ * Helper: write each saved state to the file, skipping ahead to the
+ * latest when several were saved since the last write. Written to a
+ * temporary file first so a crash never leaves a partial file.
+ */
+fn write(path: PathBuf, rx: Receiver<String>) {
+    let tmp = path.with_extension("tmp");
+    while let Ok(contents) = rx.recv() {
+        let contents = rx.try_iter().last().unwrap_or(contents);
+        let result = fs::File::create(&tmp)
+            .and_then(|mut f| f.write_all(contents.as_bytes()))
+            .and_then(|_| fs::rename(&tmp, &path));
+        if let Err(e) = result {
+            log::error!("Error saving pending Senders to {:?}: {}", path, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRACE: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn parses_saved_lines() {
+        let (id, entry) = parse("some-id 127.0.0.1 1700000000 900", GRACE).unwrap();
+        assert_eq!(id, "some-id");
+        assert_eq!(entry.ip, IpAddr::from([127, 0, 0, 1]));
+        assert_eq!(
+            entry.time_added,
+            UNIX_EPOCH + Duration::from_secs(1700000000)
+        );
+        assert_eq!(entry.ttl, Duration::from_secs(900));
+    }
+
+    #[test]
+    fn skips_corrupt_lines() {
+        let corrupt = [
+            "",
+            "some-id",
+            "some-id not-an-ip 1700000000 900",
+            "some-id 127.0.0.1 yesterday 900",
+            "some-id 127.0.0.1 1700000000 -1",
+            "some-id 127.0.0.1 1700000000 900 extra",
+            " 127.0.0.1 1700000000 900",
+            // Beyond what SystemTime can represent
+            "some-id 127.0.0.1 18446744073709551615 900",
+        ];
+        for line in corrupt.iter() {
+            assert!(parse(line, GRACE).is_none(), "{:?}", line);
+        }
+    }
+}
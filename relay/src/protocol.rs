@@ -2,28 +2,216 @@ use mio::net::TcpStream;
 use mio::Token;
 use os_pipe::pipe;
 use portal_lib::errors::PortalError;
-use portal_lib::protocol::{ConnectMessage, PortalMessage};
+use portal_lib::protocol::{
+    Capabilities, CodecVersion, ConnectMessage, ConnectOptions, PairedMessage, PortalMessage,
+    Priority, RelayBanner, RelayControl, RelayControlError, SessionSalt, TlvCodec, WireCodec,
+    MAX_HANDSHAKE_MESSAGE_SIZE, MAX_PROBE_SIZE,
+};
+use socket2::SockRef;
+use std::collections::HashMap;
 use std::error::Error;
-use std::io::Write;
 use std::net::SocketAddr;
 use std::os::unix::io::AsRawFd;
-use std::time::SystemTime;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
 
-use crate::{networking, Endpoint, EndpointPair, MAX_SPLICE_SIZE, PENDING_ENDPOINTS};
+use crate::handlers::SpliceStats;
+use crate::{
+    auth, buffer, claims, cluster, local, metrics, networking, persist, reload, replay, tarpit,
+    Endpoint, EndpointPair, MAX_SPLICE_SIZE, PENDING_ENDPOINTS,
+};
 
 const PLACEHOLDER: usize = 0;
 
+/// How often to check for a request on a new connection
+const RECV_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// How long a pending Sender is kept before being removed
+const DEFAULT_TTL: Duration = Duration::from_secs(60 * 15);
+
+/// The longest a pending Sender may extend its TTL to
+const MAX_TTL: Duration = Duration::from_secs(60 * 60);
+
+/**
+ * Verify that a pending, unpaired, Sender exists for this ID
+ * and that the request originates from the same address, or
+ * the same user for local clients
+ */
+fn check_owner(
+    endpoints: &HashMap<String, Endpoint>,
+    id: &str,
+    connection: &TcpStream,
+) -> Result<(), RelayControlError> {
+    let endpoint = endpoints.get(id).ok_or(RelayControlError::UnknownId)?;
+    if endpoint.has_peer {
+        return Err(RelayControlError::AlreadyPaired);
+    }
+    if endpoint.owner.is_none() || endpoint.owner != local::peer_ip(connection) {
+        return Err(RelayControlError::NotPermitted);
+    }
+    Ok(())
+}
+
+/**
+ * Whether the request being received is already larger than any
+ * request a client sends the relay. A v2 frame's length is known from
+ * its prefix, so it's refused before the rest of it is buffered.
+ */
+fn oversized(data: &[u8]) -> bool {
+    let len = match CodecVersion::detect(data) {
+        Some(CodecVersion::V2) => TlvCodec::frame_len(data).unwrap_or_default(),
+        _ => data.len() as u64,
+    };
+    len > MAX_HANDSHAKE_MESSAGE_SIZE
+}
+
+/**
+ * Extend a pending Sender's TTL by the seconds requested, up to MAX_TTL.
+ * The request comes from the client, so it mustn't be able to overflow.
+ */
+fn extend_ttl(ttl: Duration, seconds: u64) -> Duration {
+    let extension = Duration::from_secs(seconds.min(MAX_TTL.as_secs()));
+    ttl.saturating_add(extension).min(MAX_TTL)
+}
+
+/**
+ * Handle a RelayControl request from a client. The relay responds
+ * with either an Ack or an Error and then closes the connection
+ */
+fn control(
+    addr: SocketAddr,
+    mut connection: TcpStream,
+    request: RelayControl,
+    framing: CodecVersion,
+    banner: RelayBanner,
+    timeout: Duration,
+) -> Result<(), Box<dyn Error>> {
+    log::debug!("[?] RelayControl request {:?} from {:?}", request, addr);
+
+    let mut ref_endpoints = PENDING_ENDPOINTS.lock().unwrap();
+    let response = match request {
+        RelayControl::Cancel(id) => match check_owner(&ref_endpoints, &id, &connection) {
+            Ok(()) => {
+                log::info!("[{:.6}] Pending Sender cancelled", id);
+                let _ = ref_endpoints.remove(&id);
+                persist::save(&ref_endpoints);
+                RelayControl::Ack
+            }
+            Err(e) => RelayControl::Error(e),
+        },
+        RelayControl::ExtendTtl { id, seconds } => {
+            match check_owner(&ref_endpoints, &id, &connection) {
+                Ok(()) => {
+                    let endpoint = ref_endpoints.get_mut(&id).unwrap();
+                    endpoint.ttl = extend_ttl(endpoint.ttl, seconds);
+                    log::info!("[{:.6}] Pending Sender TTL is now {:?}", id, endpoint.ttl);
+                    persist::save(&ref_endpoints);
+                    RelayControl::Ack
+                }
+                Err(e) => RelayControl::Error(e),
+            }
+        }
+        RelayControl::Lookup(id) if cluster::is_peer(&addr) => match ref_endpoints.get(&id) {
+            Some(endpoint) if !endpoint.has_peer => RelayControl::Ack,
+            Some(_) => RelayControl::Error(RelayControlError::AlreadyPaired),
+            None if claims::is_claimed(&id) => {
+                RelayControl::Error(RelayControlError::AlreadyPaired)
+            }
+            None => RelayControl::Error(RelayControlError::UnknownId),
+        },
+        RelayControl::Lookup(_) => RelayControl::Error(RelayControlError::NotPermitted),
+        // Filler is only handed out to clients that may use the relay
+        RelayControl::Probe(_) if auth::required() && !local::trusted(&connection) => {
+            RelayControl::Error(RelayControlError::NotPermitted)
+        }
+        RelayControl::Probe(size) => {
+            RelayControl::ProbeData(vec![0; size.min(MAX_PROBE_SIZE) as usize])
+        }
+        RelayControl::Version => RelayControl::Banner(banner),
+        _ => RelayControl::Error(RelayControlError::Unsupported),
+    };
+    drop(ref_endpoints);
+
+    // Respond and close the connection. Probe responses are too
+    // large to write without blocking, but a client that doesn't
+    // read them mustn't hold this worker.
+    SockRef::from(&connection).set_nonblocking(false)?;
+    SockRef::from(&connection).set_write_timeout(Some(timeout))?;
+    framing.send(&PortalMessage::RelayControl(response), &mut connection)?;
+    let _ = connection.shutdown(std::net::Shutdown::Both);
+    Ok(())
+}
+
+/// A client's request, with the options & capabilities sent after it
+type Request = (PortalMessage, Option<ConnectOptions>, Option<Capabilities>);
+
+/**
+ * Parse a client's request, along with the options & capabilities that
+ * clients which have them send right after it, in that order. Anything
+ * else that follows the request is ignored, as it always has been.
+ */
+fn parse_request(data: &[u8], framing: CodecVersion) -> Result<Request, Box<dyn Error>> {
+    let mut remaining = data;
+    let msg = framing.decode(&mut remaining, MAX_HANDSHAKE_MESSAGE_SIZE)?;
+    let mut next = || match remaining.is_empty() {
+        true => None,
+        false => framing
+            .decode(&mut remaining, MAX_HANDSHAKE_MESSAGE_SIZE)
+            .ok(),
+    };
+    match next() {
+        Some(PortalMessage::Options(options)) => match next() {
+            Some(PortalMessage::Capabilities(capabilities)) => {
+                Ok((msg, Some(options), Some(capabilities)))
+            }
+            _ => Ok((msg, Some(options), None)),
+        },
+        _ => Ok((msg, None, None)),
+    }
+}
+
+/**
+ * The message telling a client it has been paired with `peer`. Clients
+ * that sent no options predate Paired messages, they're forwarded the
+ * peer's ConnectMessage as they always were, without a salt.
+ */
+fn paired(
+    peer: ConnectMessage,
+    options: ConnectOptions,
+    salt: SessionSalt,
+    legacy: bool,
+) -> PortalMessage {
+    match legacy {
+        true => PortalMessage::Connect(peer),
+        false => PortalMessage::Paired(PairedMessage {
+            peer,
+            options,
+            salt,
+        }),
+    }
+}
+
 /**
  * Attempt to parse a Portal request from the client and match it
- * with a peer. If matched, the pair will be added to an event loop
+ * with a peer. If matched, the pair will be added to an event loop.
+ * Requests may be legacy v1 messages or v2 frames, the relay answers
+ * each client with the codec it used.
  */
 pub fn register(
     addr: SocketAddr,
     mut connection: TcpStream,
     tx: mio_extras::channel::Sender<EndpointPair>,
+    resume_tx: mio_extras::channel::Sender<buffer::Resume>,
+    settings: reload::Settings,
 ) -> Result<(), Box<dyn Error>> {
+    let slow_pair = Duration::from_secs(settings.slow_pair);
+    let handshake_timeout = Duration::from_secs(settings.handshake_timeout);
+    let redirect = settings.redirect;
+    let banner = RelayBanner::new(settings.max_transfer_mb.map(|mb| mb * 1024 * 1024));
+
     let mut received_data = Vec::with_capacity(1024);
-    while received_data.is_empty() {
+    let deadline = Instant::now() + handshake_timeout;
+    while !CodecVersion::is_complete(&received_data) {
         match networking::recv_generic(&mut connection, &mut received_data) {
             Ok(v) if v < 0 => {
                 break; // done recieving
@@ -33,33 +221,121 @@ pub fn register(
                 break;
             }
         }
+
+        // Nor let a client make this worker buffer an arbitrary amount
+        if oversized(&received_data) {
+            log::debug!("[?] Refused an oversized request from {:?}", addr);
+            tarpit::reject(&addr, connection);
+            return Err(PortalError::MessageTooLarge.into());
+        }
+
+        // Don't let idle connections occupy this worker indefinitely
+        if !CodecVersion::is_complete(&received_data) {
+            if Instant::now() >= deadline {
+                log::debug!("[?] Timed out waiting for a request from {:?}", addr);
+                let _ = connection.shutdown(std::net::Shutdown::Both);
+                return Ok(());
+            }
+            thread::sleep(RECV_POLL_INTERVAL);
+        }
     }
 
     log::trace!("[?] Received {:?} bytes", received_data.len());
 
     // attempt to recieve a portal request
-    let req: ConnectMessage = match PortalMessage::parse(&received_data)? {
-        PortalMessage::Connect(r) => r,
+    let framing = CodecVersion::detect(&received_data).unwrap_or_default();
+    let (msg, options, capabilities) = match parse_request(&received_data, framing) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            tarpit::reject(&addr, connection);
+            return Err(e);
+        }
+    };
+    let req: ConnectMessage = match msg {
+        PortalMessage::AuthConnect(r) if auth::verify(&r) => r.connect,
+        PortalMessage::Connect(r) if !auth::required() || local::trusted(&connection) => r,
+        PortalMessage::RelayControl(RelayControl::Register(r))
+            if !auth::required() || local::trusted(&connection) =>
+        {
+            r
+        }
+        PortalMessage::Connect(_)
+        | PortalMessage::AuthConnect(_)
+        | PortalMessage::RelayControl(RelayControl::Register(_)) => {
+            log::debug!("[?] Refused unauthenticated request from {:?}", addr);
+            tarpit::reject(&addr, connection);
+            return Err(PortalError::BadMsg.into());
+        }
+        PortalMessage::RelayControl(RelayControl::Resume {
+            id,
+            received,
+            token,
+        }) => {
+            let stream = connection;
+            resume_tx.send(buffer::Resume {
+                id,
+                received,
+                token,
+                stream,
+                addr,
+                framing,
+            })?;
+            return Ok(());
+        }
+        PortalMessage::RelayControl(request) => {
+            return control(
+                addr,
+                connection,
+                request,
+                framing,
+                banner,
+                handshake_timeout,
+            );
+        }
         x => {
             log::debug!("Got incorrect PortalMessage: {:?}", x);
+            tarpit::reject(&addr, connection);
             return Err(PortalError::BadMsg.into());
         }
     };
 
+    // Describe this relay ahead of any other response, so clients
+    // can tell version skew apart from other failures. Only clients that
+    // sent their options know the banner, older ones would take it for
+    // their peer's response & can ask for it with a Version request.
+    let legacy = options.is_none();
+    let mut preamble = 0;
+    if !legacy {
+        let hello = PortalMessage::RelayHello(banner);
+        if let Err(e) = framing.send(&hello, &mut connection) {
+            log::debug!("[?] Error sending the banner to {:?}: {}", addr, e);
+            return Ok(());
+        }
+        preamble = framing.encode(&hello)?.len() as u64;
+    }
+
     // Lookup existing endpoint with this ID
     let id = req.id;
     let dir = req.direction;
+    let options = options.unwrap_or_default();
+    let ConnectOptions {
+        format,
+        priority,
+        codec,
+    } = options;
 
     log::info!("[{:.6}] New Portal request: {:?}({:?})", id, dir, addr);
 
+    // Someone else may be racing the real peer for this ID
+    if !replay::check(&id, dir, &addr) {
+        tarpit::reject(&addr, connection);
+        return Ok(());
+    }
+
     // Clear old entries before accepting, will keep
-    // connections < 15 min old
+    // connections younger than their TTL (15 min by default)
     let mut ref_endpoints = PENDING_ENDPOINTS.lock().unwrap();
-    ref_endpoints.retain(|_, v| {
-        v.has_peer
-            || (v.time_added.elapsed().unwrap().as_secs()
-                < std::time::Duration::from_secs(60 * 15).as_secs())
-    });
+    ref_endpoints.retain(|_, v| v.has_peer || v.time_added.elapsed().unwrap() < v.ttl);
 
     match dir {
         portal::Direction::Receiver => {
@@ -67,19 +343,56 @@ pub fn register(
             let mut peer = match ref_endpoints.remove(&id.to_string()) {
                 Some(p) => p,
                 None => {
+                    drop(ref_endpoints);
+
+                    // Another Receiver may have beaten this one to the Sender
+                    if claims::is_claimed(&id) {
+                        claims::refuse(&id, &addr, connection, framing);
+                        return Ok(());
+                    }
+
+                    // The Sender may be waiting on another relay in the cluster
+                    if let Some(upstream) =
+                        cluster::find(&id, &addr, &received_data, framing, !legacy)
+                    {
+                        tarpit::forgive(&addr, &connection);
+                        tx.send(cluster::proxy(
+                            id, format, priority, codec, framing, connection, upstream,
+                        )?)?;
+                        return Ok(());
+                    }
+
+                    // Or have been redirected to an alternate
+                    if redirect.enabled() {
+                        return redirect.send(&id, &addr, connection, framing);
+                    }
+
+                    // Possibly guessing IDs, or early for its Sender
+                    tarpit::miss(&addr, &id, connection);
                     return Ok(());
                 }
             };
 
             log::info!("[{:.6}] Receiver matched with Sender", id);
+            tarpit::forgive(&addr, &connection);
 
             // if the peer already has a connection, disregard this one
             if peer.has_peer {
-                let _ = connection.shutdown(std::net::Shutdown::Both);
-                log::info!("[{:.6}] Canceled receiving connection: Sender already has a different connection.", id);
+                claims::refuse(&id, &addr, connection, framing);
                 return Ok(());
             }
 
+            // Record how long the Sender waited for this Receiver
+            let waited = peer.time_added.elapsed().unwrap_or_default();
+            metrics::record_pairing(waited);
+            if waited > slow_pair {
+                log::warn!(
+                    "[{:.6}] Slow pairing: Receiver arrived after {:?}",
+                    id,
+                    waited
+                );
+            }
+
             // This pipe will be used to send data from Receiver->Sender
             // so the Sender will keep the read side, and the Receiver will
             // keep the write side
@@ -95,16 +408,65 @@ pub fn register(
                 }
             };
 
-            // write the acknowledgement response to both pipe endpoints
-            writer2.write_all(&received_data)?;
+            // Generate a salt unique to this session, which both peers
+            // will mix into their key confirmation
+            let salt = SessionSalt::generate();
+
+            // Each peer is told the other's capabilities before being
+            // paired, only if both sent theirs. Older clients wouldn't
+            // expect them.
+            let exchanged = match (peer.capabilities, capabilities) {
+                (Some(sender), Some(receiver)) => Some((sender, receiver)),
+                _ => None,
+            };
+
+            // Inform the Sender that it has been paired with this Receiver,
+            // each with the codec of its own request
+            if let Some((_, receiver)) = exchanged {
+                let capabilities = PortalMessage::Capabilities(receiver);
+                peer.framing.send(&capabilities, &mut writer2)?;
+            }
+            let receiver = ConnectMessage {
+                id: id.clone(),
+                direction: dir,
+            };
+            let message = paired(receiver, options, salt, peer.legacy);
+            peer.framing.send(&message, &mut writer2)?;
+
+            // Inform this Receiver that it has been paired with the Sender
+            let sender_writer = peer.peer_writer.as_mut().ok_or(PortalError::BadState)?;
+            if let Some((sender, _)) = exchanged {
+                framing.send(&PortalMessage::Capabilities(sender), sender_writer)?;
+            }
+            let sender = ConnectMessage {
+                id: peer.id.clone(),
+                direction: peer.dir,
+            };
+            let sender_options = ConnectOptions {
+                format: peer.format,
+                priority: peer.priority,
+                codec: peer.codec,
+            };
+            let message = paired(sender, sender_options, salt, legacy);
+            framing.send(&message, sender_writer)?;
 
             log::debug!("[{:.6}] Acknowledgement sent to peer", id);
 
+            // Bulk sessions are marked on both sides
+            let session = Priority::combine(peer.priority, priority);
+            if session != Priority::Normal {
+                log::info!("[{:.6}] Marking session as {:?}", id, session);
+                networking::mark(&peer.stream, session);
+                networking::mark(&connection, session);
+            }
+
             // update the peer with the pipe information
-            let old_reader = std::mem::replace(&mut peer.peer_reader, Some(reader2));
+            let old_reader = peer.peer_reader.replace(reader2);
             peer.has_peer = true;
+            persist::save(&ref_endpoints);
 
             // create this endpoint
+            let owner = local::peer_ip(&connection);
             let endpoint = Endpoint {
                 id: id.to_string(),
                 dir,
@@ -113,6 +475,17 @@ pub fn register(
                 peer_writer: Some(writer2), //None,
                 has_peer: true,
                 time_added: SystemTime::now(),
+                ttl: DEFAULT_TTL,
+                format,
+                priority,
+                codec,
+                capabilities,
+                legacy,
+                framing,
+                owner,
+                stats: SpliceStats::new(),
+                frames: None,
+                tail: None,
             };
 
             log::debug!("[{:.6}] Added Receiver", id);
@@ -122,11 +495,19 @@ pub fn register(
                 sender_token: Token(PLACEHOLDER),
                 receiver: endpoint,
                 receiver_token: Token(PLACEHOLDER),
+                spool: None,
+                ticket: Some(buffer::Ticket { salt, preamble }),
+                expiring: None,
             };
 
             // Communicate the new pair over the MPSC channel
-            // back to the main event loop
-            tx.send(pair)?;
+            // back to the main event loop, claiming the ID until
+            // the session ends
+            claims::claim(&id);
+            if let Err(e) = tx.send(pair) {
+                claims::release(&id);
+                return Err(e.into());
+            }
         }
         portal::Direction::Sender => {
             // Kill the connection if this ID is being used by another pending sender
@@ -135,12 +516,33 @@ pub fn register(
                     .iter()
                     .find_map(|(key, val)| if *val.id == *id { Some(key) } else { None });
 
-            if search.is_some() {
+            // Or by a session in progress, each ID is paired only once
+            if search.is_some() || claims::is_claimed(&id) {
                 return Ok(());
             }
 
+            // A Sender reconnecting after a restart keeps its registration,
+            // new ones are turned away when the relay is at capacity
+            let pending = ref_endpoints.values().filter(|e| !e.has_peer).count();
+            let owner = local::peer_ip(&connection).unwrap_or(addr.ip());
+            let (time_added, ttl) = match persist::reclaim(&id, owner) {
+                Ok(Some(restored)) => {
+                    log::info!("[{:.6}] Sender re-registered after restart", id);
+                    (restored.time_added, restored.ttl)
+                }
+                Ok(None) if redirect.at_capacity(pending) => {
+                    drop(ref_endpoints);
+                    return redirect.send(&id, &addr, connection, framing);
+                }
+                Ok(None) => (SystemTime::now(), DEFAULT_TTL),
+                Err(()) => {
+                    log::warn!("[{:.6}] ID is reserved for a reconnecting Sender", id);
+                    return Ok(());
+                }
+            };
+
             // This pipe will be used to send data from Sender->Receiver
-            let (reader, mut writer) = pipe().unwrap();
+            let (reader, writer) = pipe().unwrap();
 
             // resize the pipe that we will be using for the actual
             // file transfer
@@ -151,9 +553,6 @@ pub fn register(
                 }
             }
 
-            // Buffer this request in the pipe for when the peer connects
-            writer.write_all(&received_data)?;
-
             let endpoint = Endpoint {
                 id: id.to_string(),
                 dir,
@@ -161,13 +560,145 @@ pub fn register(
                 peer_writer: Some(writer),
                 peer_reader: Some(reader),
                 has_peer: false,
-                time_added: SystemTime::now(),
+                time_added,
+                ttl,
+                format,
+                priority,
+                codec,
+                capabilities,
+                legacy,
+                framing,
+                owner: Some(owner),
+                stats: SpliceStats::new(),
+                frames: None,
+                tail: None,
             };
 
             log::debug!("[{:.6}] Added Sender", id);
 
             ref_endpoints.entry(id.to_string()).or_insert(endpoint);
+            persist::save(&ref_endpoints);
         }
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extend_ttl_is_capped() {
+        assert_eq!(
+            extend_ttl(DEFAULT_TTL, 60),
+            DEFAULT_TTL + Duration::from_secs(60)
+        );
+        assert_eq!(extend_ttl(DEFAULT_TTL, 0), DEFAULT_TTL);
+        assert_eq!(extend_ttl(DEFAULT_TTL, u64::MAX), MAX_TTL);
+        assert_eq!(extend_ttl(MAX_TTL, u64::MAX), MAX_TTL);
+    }
+
+    #[test]
+    fn requests_with_or_without_options() {
+        // Laid out by hand as every client sends it: the variant index,
+        // the ID's length & bytes, then the Sender
+        let mut old = vec![0, 0, 0, 0];
+        old.extend(2u64.to_le_bytes());
+        old.extend(b"id");
+        old.extend([0, 0, 0, 0]);
+        let connect = PortalMessage::Connect(ConnectMessage {
+            id: "id".into(),
+            direction: portal::Direction::Sender,
+        });
+        assert_eq!(
+            parse_request(&old, CodecVersion::V1).unwrap(),
+            (connect.clone(), None, None)
+        );
+
+        // Newer clients follow it with their options & capabilities,
+        // in either codec
+        let options = ConnectOptions {
+            priority: Priority::Bulk,
+            codec: CodecVersion::V2,
+            ..Default::default()
+        };
+        let capabilities = PortalMessage::Capabilities(Capabilities::DEDUP);
+        for version in [CodecVersion::V1, CodecVersion::V2] {
+            let mut data = version.encode(&connect).unwrap();
+            data.extend(version.encode(&PortalMessage::Options(options)).unwrap());
+            assert_eq!(
+                parse_request(&data, version).unwrap(),
+                (connect.clone(), Some(options), None)
+            );
+            data.extend(version.encode(&capabilities).unwrap());
+            assert_eq!(
+                parse_request(&data, version).unwrap(),
+                (connect.clone(), Some(options), Some(Capabilities::DEDUP))
+            );
+        }
+
+        // Whatever else follows a request is ignored
+        let mut data = old.clone();
+        data.extend([0xff; 7]);
+        assert_eq!(
+            parse_request(&data, CodecVersion::V1).unwrap(),
+            (connect, None, None)
+        );
+    }
+
+    #[test]
+    fn legacy_clients_are_paired_as_before() {
+        // Laid out by hand as clients that predate options send it,
+        // & as relays that predate pairing messages forwarded it
+        let connect = |direction: u8| {
+            let mut data = vec![0, 0, 0, 0];
+            data.extend(2u64.to_le_bytes());
+            data.extend(b"id");
+            data.extend([direction, 0, 0, 0]);
+            data
+        };
+        let (_, options, capabilities) = parse_request(&connect(1), CodecVersion::V1).unwrap();
+        assert_eq!((options, capabilities), (None, None));
+
+        // Such a client reads the peer's Connect, without a salt
+        let sender = ConnectMessage {
+            id: "id".into(),
+            direction: portal::Direction::Sender,
+        };
+        let salt = SessionSalt::generate();
+        let options = ConnectOptions::default();
+        let response = paired(sender.clone(), options, salt, true);
+        assert_eq!(response.to_bytes().unwrap(), connect(0));
+
+        // Newer clients are told the peer's options & the salt
+        assert_eq!(
+            paired(sender.clone(), options, salt, false),
+            PortalMessage::Paired(PairedMessage {
+                peer: sender,
+                options,
+                salt,
+            })
+        );
+    }
+
+    #[test]
+    fn oversized_requests() {
+        let connect = PortalMessage::RelayControl(RelayControl::Cancel("id".into()));
+        for version in [CodecVersion::V1, CodecVersion::V2] {
+            let data = version.encode(&connect).unwrap();
+            assert!(!oversized(&data));
+            assert!(!oversized(&data[..2]));
+        }
+
+        // A frame claiming to be huge is refused from its prefix alone
+        assert!(oversized(&0x00ff_ffffu32.to_be_bytes()));
+        let limit = MAX_HANDSHAKE_MESSAGE_SIZE as u32 - 4;
+        assert!(!oversized(&limit.to_be_bytes()));
+        assert!(oversized(&(limit + 1).to_be_bytes()));
+
+        // As is a v1 request once too much of it has arrived
+        let v1 = vec![1u8; MAX_HANDSHAKE_MESSAGE_SIZE as usize + 1];
+        assert!(!oversized(&v1[..MAX_HANDSHAKE_MESSAGE_SIZE as usize]));
+        assert!(oversized(&v1));
+    }
+}
@@ -0,0 +1,89 @@
+use mio::net::TcpStream;
+use portal_lib::protocol::{CodecVersion, PortalMessage, RelayControl, WireCodec};
+use socket2::SockRef;
+use std::error::Error;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use structopt::StructOpt;
+
+/// Paired sessions, published by the event loop
+static SESSIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Simple load sharing between relays, without a real cluster
+#[derive(Debug, Clone, StructOpt)]
+pub struct Redirect {
+    /// Turn new Senders away once this many sessions are pending or
+    /// paired, redirecting them to an --alternate relay
+    #[structopt(long)]
+    pub capacity: Option<usize>,
+
+    /// Another relay (host:port) to redirect clients to when at capacity.
+    /// Receivers with no pending Sender here are redirected too, since
+    /// their Sender may have been. May be given more than once.
+    #[structopt(long = "alternate", number_of_values = 1)]
+    pub alternates: Vec<String>,
+}
+
+impl Redirect {
+    /**
+     * Whether a new Sender should be turned away, given the number of
+     * Senders pending
+     */
+    pub fn at_capacity(&self, pending: usize) -> bool {
+        !self.alternates.is_empty()
+            && self
+                .capacity
+                .is_some_and(|c| pending + SESSIONS.load(Ordering::Relaxed) >= c)
+    }
+
+    /**
+     * Whether there are alternates to send a Receiver that found no
+     * Sender here to
+     */
+    pub fn enabled(&self) -> bool {
+        !self.alternates.is_empty()
+    }
+
+    /**
+     * The alternates in the order this ID should try them. Rotating the
+     * list by the ID spreads the load, while a Sender & Receiver sharing
+     * an ID still try the same relay first.
+     */
+    pub fn alternates_for(&self, id: &str) -> Vec<String> {
+        let mut alternates = self.alternates.clone();
+        if !alternates.is_empty() {
+            let hash = id
+                .bytes()
+                .fold(0usize, |h, b| h.wrapping_mul(31).wrapping_add(b as usize));
+            alternates.rotate_left(hash % self.alternates.len());
+        }
+        alternates
+    }
+
+    /**
+     * Answer the request with the alternates & close the connection
+     */
+    pub fn send(
+        &self,
+        id: &str,
+        addr: &SocketAddr,
+        mut connection: TcpStream,
+        framing: CodecVersion,
+    ) -> Result<(), Box<dyn Error>> {
+        let alternates = self.alternates_for(id);
+        log::info!("[{:.6}] Redirecting {:?} to {:?}", id, addr, alternates);
+
+        SockRef::from(&connection).set_nonblocking(false)?;
+        let response = PortalMessage::RelayControl(RelayControl::Redirect(alternates));
+        framing.send(&response, &mut connection)?;
+        let _ = connection.shutdown(std::net::Shutdown::Both);
+        Ok(())
+    }
+}
+
+/**
+ * Record the number of paired sessions, counted towards --capacity
+ */
+pub fn set_sessions(count: usize) {
+    SESSIONS.store(count, Ordering::Relaxed);
+}
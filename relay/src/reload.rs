@@ -0,0 +1,169 @@
+use log::LevelFilter;
+use mio_extras::channel::Sender;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use structopt::StructOpt;
+
+use crate::{auth, redirect, replay, tarpit};
+
+/// How often the config file is checked for changes
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Set by the SIGHUP handler
+static HANGUP: AtomicBool = AtomicBool::new(false);
+
+/// Settings that can be changed while the relay is running, by editing
+/// the --config file or sending the relay SIGHUP. Established pairs are
+/// kept, new values apply from then on.
+#[derive(Debug, Clone, StructOpt)]
+pub struct Settings {
+    /// Log pairings where the Receiver arrives more than this many
+    /// seconds after the Sender
+    #[structopt(long, default_value = "300")]
+    pub slow_pair: u64,
+
+    /// Close new connections that have not sent their request
+    /// within this many seconds
+    #[structopt(long, default_value = "10")]
+    pub handshake_timeout: u64,
+
+    /// Close paired sessions that have moved no data for this many seconds
+    #[structopt(long)]
+    pub idle_timeout: Option<u64>,
+
+    /// End paired sessions that have lasted longer than this many seconds,
+    /// telling the Receiver why before closing
+    #[structopt(long)]
+    pub max_session: Option<u64>,
+
+    /// End sessions once the Sender has sent this many MB, telling
+    /// the Receiver why before closing
+    #[structopt(long)]
+    pub max_transfer_mb: Option<u64>,
+
+    /// Only log messages at or above this level: off, error, warn, info,
+    /// debug or trace. Can't exceed what RUST_LOG allowed at startup.
+    #[structopt(long)]
+    pub log_level: Option<LevelFilter>,
+
+    #[structopt(flatten)]
+    pub tarpit: tarpit::Tarpit,
+
+    #[structopt(flatten)]
+    pub redirect: redirect::Redirect,
+
+    #[structopt(flatten)]
+    pub replay: replay::Replay,
+}
+
+impl Settings {
+    /**
+     * Layer the `name = value` lines of a config file over these settings.
+     * Names are those of the command line options, without the dashes in
+     * front. Blank lines & lines starting with # are ignored. Each
+     * alternate line adds to those given on the command line.
+     */
+    pub fn merge(&self, contents: &str) -> Result<Settings, Box<dyn Error>> {
+        let mut merged = self.clone();
+        for line in contents.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (name, value) = line
+                .split_once('=')
+                .map(|(n, v)| (n.trim(), v.trim()))
+                .ok_or(format!("Expected name = value, got {:?}", line))?;
+
+            match name {
+                "slow-pair" => merged.slow_pair = value.parse()?,
+                "handshake-timeout" => merged.handshake_timeout = value.parse()?,
+                "idle-timeout" => merged.idle_timeout = Some(value.parse()?),
+                "max-session" => merged.max_session = Some(value.parse()?),
+                "max-transfer-mb" => merged.max_transfer_mb = Some(value.parse()?),
+                "log-level" => merged.log_level = Some(value.parse()?),
+                "tarpit-threshold" => merged.tarpit.tarpit_threshold = value.parse()?,
+                "tarpit-delay" => merged.tarpit.tarpit_delay = value.parse()?,
+                "tarpit-max-delay" => merged.tarpit.tarpit_max_delay = value.parse()?,
+                "capacity" => merged.redirect.capacity = Some(value.parse()?),
+                "alternate" => merged.redirect.alternates.push(value.to_string()),
+                "duplicate-window" => merged.replay.duplicate_window = value.parse()?,
+                "reject-duplicates" => merged.replay.reject_duplicates = value.parse()?,
+                _ => return Err(format!("Unknown setting {:?}", name).into()),
+            }
+        }
+        Ok(merged)
+    }
+
+    /**
+     * Apply the settings held outside of the event loop
+     */
+    pub fn apply(&self) {
+        if let Some(level) = self.log_level {
+            log::set_max_level(level);
+        }
+        tarpit::configure(self.tarpit.clone());
+        replay::configure(self.replay.clone());
+    }
+}
+
+/**
+ * Read the config file, if any, over the command line settings
+ */
+pub fn load(base: &Settings, path: Option<&PathBuf>) -> Result<Settings, Box<dyn Error>> {
+    match path {
+        Some(path) => base.merge(&fs::read_to_string(path)?),
+        None => Ok(base.clone()),
+    }
+}
+
+/**
+ * Reload the settings & access secrets whenever the config file changes
+ * or SIGHUP is received, sending the new settings to the event loop.
+ * Invalid files are logged and the current settings kept.
+ */
+pub fn spawn(
+    base: Settings,
+    path: Option<PathBuf>,
+    auth: auth::Auth,
+    tx: Sender<Settings>,
+) -> Result<(), Box<dyn Error>> {
+    extern "C" fn hangup(_: libc::c_int) {
+        HANGUP.store(true, Ordering::Relaxed);
+    }
+    let handler = hangup as extern "C" fn(libc::c_int);
+    if unsafe { libc::signal(libc::SIGHUP, handler as libc::sighandler_t) } == libc::SIG_ERR {
+        return Err(std::io::Error::last_os_error().into());
+    }
+
+    let modified = |path: &PathBuf| fs::metadata(path).and_then(|m| m.modified()).ok();
+    let mut last: Option<SystemTime> = path.as_ref().and_then(modified);
+    thread::spawn(move || loop {
+        thread::sleep(WATCH_INTERVAL);
+        let current = path.as_ref().and_then(modified);
+        if !HANGUP.swap(false, Ordering::Relaxed) && current == last {
+            continue;
+        }
+        last = current;
+
+        log::info!("Reloading configuration");
+        if let Err(e) = auth.init() {
+            log::error!(
+                "Error reloading access secrets, keeping the current ones: {}",
+                e
+            );
+        }
+        match load(&base, path.as_ref()) {
+            Ok(settings) => {
+                if tx.send(settings).is_err() {
+                    return;
+                }
+            }
+            Err(e) => log::error!("Error reloading the config, keeping the current one: {}", e),
+        }
+    });
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use portal_lib::Direction;
+use std::collections::BTreeMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use structopt::StructOpt;
+
+/// Watches for the same request arriving from several addresses at once
+#[derive(Debug, Clone, StructOpt)]
+pub struct Replay {
+    /// Seconds to remember each ID & direction, flagging requests for
+    /// them from another address within that time. 0 disables this.
+    #[structopt(long, default_value = "10")]
+    pub duplicate_window: u64,
+
+    /// Refuse flagged requests rather than only logging them
+    #[structopt(long)]
+    pub reject_duplicates: bool,
+}
+
+lazy_static! {
+    static ref CONFIG: Mutex<Option<Replay>> = Mutex::new(None);
+    static ref SEEN: Mutex<BTreeMap<(String, Direction), (IpAddr, Instant)>> =
+        Mutex::new(BTreeMap::new());
+}
+
+/**
+ * Apply new settings to a running relay
+ */
+pub fn configure(config: Replay) {
+    *CONFIG.lock().unwrap() = Some(config);
+}
+
+/**
+ * Record a request, returning false if it should be refused. A second
+ * Receiver for an ID from another address moments after the first may
+ * be someone racing the real Receiver to hijack the session.
+ */
+pub fn check(id: &str, dir: Direction, addr: &SocketAddr) -> bool {
+    let (window, reject) = match CONFIG.lock().unwrap().as_ref() {
+        Some(config) if config.duplicate_window > 0 => (
+            Duration::from_secs(config.duplicate_window),
+            config.reject_duplicates,
+        ),
+        _ => return true,
+    };
+
+    let mut seen = SEEN.lock().unwrap();
+    seen.retain(|_, (_, at)| at.elapsed() < window);
+
+    let key = (id.to_string(), dir);
+    if let Some((first, at)) = seen.get(&key) {
+        if *first != addr.ip() {
+            log::warn!(
+                "[{:.6}] Duplicate {:?} from {:?}, {:?} after one from {:?}{}",
+                id,
+                dir,
+                addr,
+                at.elapsed(),
+                first,
+                if reject { ", refused" } else { "" }
+            );
+            return !reject;
+        }
+    }
+    seen.insert(key, (addr.ip(), Instant::now()));
+    true
+}
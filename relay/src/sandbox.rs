@@ -0,0 +1,218 @@
+use seccompiler::{BpfProgram, SeccompAction, SeccompFilter};
+use std::collections::BTreeMap;
+use std::convert::TryInto;
+use std::error::Error;
+use std::ffi::CString;
+use std::io;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// System calls needed once the relay is listening: the event loop,
+/// splicing, the registration threadpool, health checks, state saving,
+/// buffering & webhook delivery, which connects to an address resolved
+/// beforehand. Anything else, such as a DNS lookup, kills the relay.
+const ALLOWED_SYSCALLS: &[libc::c_long] = &[
+    // Event loop & splicing
+    libc::SYS_epoll_create1,
+    libc::SYS_epoll_ctl,
+    libc::SYS_epoll_pwait,
+    libc::SYS_splice,
+    libc::SYS_pipe2,
+    libc::SYS_read,
+    libc::SYS_write,
+    libc::SYS_readv,
+    libc::SYS_writev,
+    libc::SYS_close,
+    libc::SYS_fcntl,
+    libc::SYS_ioctl,
+    libc::SYS_ppoll,
+    // Sockets
+    libc::SYS_accept4,
+    libc::SYS_socket,
+    libc::SYS_connect,
+    libc::SYS_shutdown,
+    libc::SYS_recvfrom,
+    libc::SYS_sendto,
+    libc::SYS_recvmsg,
+    libc::SYS_sendmsg,
+    libc::SYS_getsockopt,
+    libc::SYS_setsockopt,
+    libc::SYS_getpeername,
+    libc::SYS_getsockname,
+    // Threads & memory
+    libc::SYS_clone,
+    libc::SYS_clone3,
+    libc::SYS_futex,
+    libc::SYS_set_robust_list,
+    libc::SYS_rseq,
+    libc::SYS_sched_yield,
+    libc::SYS_sched_getaffinity,
+    libc::SYS_prctl,
+    libc::SYS_sigaltstack,
+    libc::SYS_rt_sigaction,
+    libc::SYS_rt_sigprocmask,
+    libc::SYS_rt_sigreturn,
+    libc::SYS_mmap,
+    libc::SYS_munmap,
+    libc::SYS_mremap,
+    libc::SYS_mprotect,
+    libc::SYS_madvise,
+    libc::SYS_brk,
+    libc::SYS_exit,
+    libc::SYS_exit_group,
+    libc::SYS_getpid,
+    libc::SYS_gettid,
+    libc::SYS_tgkill,
+    // Time & randomness
+    libc::SYS_clock_gettime,
+    libc::SYS_clock_nanosleep,
+    libc::SYS_nanosleep,
+    libc::SYS_gettimeofday,
+    libc::SYS_getrandom,
+    // Saving pending Senders
+    libc::SYS_openat,
+    libc::SYS_renameat,
+    libc::SYS_renameat2,
+    libc::SYS_fstat,
+    libc::SYS_newfstatat,
+    libc::SYS_statx,
+    libc::SYS_lseek,
+    // Buffering for disconnected Receivers
+    libc::SYS_unlinkat,
+    libc::SYS_sendfile,
+    libc::SYS_pread64,
+    // Legacy variants used by older libc versions
+    #[cfg(target_arch = "x86_64")]
+    libc::SYS_epoll_wait,
+    #[cfg(target_arch = "x86_64")]
+    libc::SYS_pipe,
+    #[cfg(target_arch = "x86_64")]
+    libc::SYS_poll,
+    #[cfg(target_arch = "x86_64")]
+    libc::SYS_accept,
+    #[cfg(target_arch = "x86_64")]
+    libc::SYS_rename,
+    #[cfg(target_arch = "x86_64")]
+    libc::SYS_open,
+    #[cfg(target_arch = "x86_64")]
+    libc::SYS_unlink,
+];
+
+/// Defense-in-depth for relays started as root, applied after binding
+#[derive(Debug, Clone, StructOpt)]
+pub struct Sandbox {
+    /// Switch to this user (name or uid) after binding
+    #[structopt(long)]
+    pub user: Option<String>,
+
+    /// Switch to this group (name or gid) after binding,
+    /// defaults to the user's primary group
+    #[structopt(long)]
+    pub group: Option<String>,
+
+    /// Change the root directory after binding. A --state-file
+    /// must then be given relative to the new root.
+    #[structopt(long)]
+    pub chroot: Option<PathBuf>,
+
+    /// Restrict the relay to the system calls it needs with seccomp
+    #[structopt(long)]
+    pub seccomp: bool,
+}
+
+impl Sandbox {
+    /**
+     * Chroot, drop privileges, then install the seccomp filter.
+     * Must be called once all privileged setup has completed.
+     */
+    pub fn apply(&self) -> Result<(), Box<dyn Error>> {
+        // Resolve names before the user database is out of reach
+        let user = self.user.as_deref().map(lookup_user).transpose()?;
+        let gid = match self.group.as_deref() {
+            Some(group) => Some(lookup_group(group)?),
+            None => user.map(|(_, gid)| gid),
+        };
+
+        if let Some(dir) = &self.chroot {
+            let path = CString::new(dir.to_string_lossy().as_bytes())?;
+            check(unsafe { libc::chroot(path.as_ptr()) })?;
+            std::env::set_current_dir("/")?;
+            log::info!("Changed root to {:?}", dir);
+        }
+
+        // The group must be changed while we are still privileged
+        if let Some(gid) = gid {
+            check(unsafe { libc::setgroups(1, &gid) })?;
+            check(unsafe { libc::setgid(gid) })?;
+        }
+        if let Some((uid, _)) = user {
+            check(unsafe { libc::setuid(uid) })?;
+            log::info!("Dropped privileges to uid {} gid {:?}", uid, gid);
+        }
+
+        if self.seccomp {
+            let program = filter(SeccompAction::KillProcess)?;
+            seccompiler::apply_filter_all_threads(&program)?;
+            log::info!("Installed seccomp filter");
+        }
+        Ok(())
+    }
+}
+
+/**
+ * The seccomp filter allowing ALLOWED_SYSCALLS, taking `mismatch` on
+ * anything else
+ */
+pub(crate) fn filter(mismatch: SeccompAction) -> Result<BpfProgram, Box<dyn Error>> {
+    let rules = ALLOWED_SYSCALLS
+        .iter()
+        .map(|s| (*s, vec![]))
+        .collect::<BTreeMap<_, _>>();
+    let filter = SeccompFilter::new(
+        rules,
+        mismatch,
+        SeccompAction::Allow,
+        std::env::consts::ARCH.try_into()?,
+    )?;
+    Ok(filter.try_into()?)
+}
+
+/**
+ * Map a libc return value to the last OS error
+ */
+fn check(res: libc::c_int) -> io::Result<()> {
+    match res {
+        0 => Ok(()),
+        _ => Err(io::Error::last_os_error()),
+    }
+}
+
+/**
+ * Resolve a user name or uid to its uid & primary gid
+ */
+fn lookup_user(user: &str) -> Result<(libc::uid_t, libc::gid_t), Box<dyn Error>> {
+    let name = CString::new(user)?;
+    let entry = match user.parse::<libc::uid_t>() {
+        Ok(uid) => unsafe { libc::getpwuid(uid) },
+        Err(_) => unsafe { libc::getpwnam(name.as_ptr()) },
+    };
+    match entry.is_null() {
+        true => Err(format!("Unknown user: {}", user).into()),
+        false => Ok(unsafe { ((*entry).pw_uid, (*entry).pw_gid) }),
+    }
+}
+
+/**
+ * Resolve a group name or gid
+ */
+fn lookup_group(group: &str) -> Result<libc::gid_t, Box<dyn Error>> {
+    if let Ok(gid) = group.parse::<libc::gid_t>() {
+        return Ok(gid);
+    }
+    let name = CString::new(group)?;
+    let entry = unsafe { libc::getgrnam(name.as_ptr()) };
+    match entry.is_null() {
+        true => Err(format!("Unknown group: {}", group).into()),
+        false => Ok(unsafe { (*entry).gr_gid }),
+    }
+}
@@ -0,0 +1,175 @@
+use mio::net::TcpStream;
+use mio::{Poll, Token};
+use mio_extras::channel::Sender;
+use std::collections::HashMap;
+use std::io;
+use std::os::unix::io::AsRawFd;
+use std::thread;
+use std::time::Duration;
+use structopt::StructOpt;
+
+use crate::{claims, persist, webhook, EndpointPair, PENDING_ENDPOINTS};
+
+/// A session with one peer gone is reclaimed once the other has been
+/// idle this long, e.g. a Receiver that never drains the last of the data
+const ORPHAN_IDLE: Duration = Duration::from_secs(300);
+
+/// Claims younger than this may belong to a pair that hasn't reached
+/// the event loop yet
+const CLAIM_GRACE: Duration = Duration::from_secs(60);
+
+/// Periodic audits reclaiming state leaked under unusual disconnect orderings
+#[derive(Debug, Clone, StructOpt)]
+pub struct Scrub {
+    /// Seconds between audits of pending Senders, sessions & their
+    /// tokens for entries whose peers are gone, 0 disables them
+    #[structopt(long, default_value = "300")]
+    pub scrub_interval: u64,
+}
+
+impl Scrub {
+    /**
+     * Start the thread auditing pending Senders, which then asks the
+     * event loop to audit the sessions only it may touch
+     */
+    pub fn spawn(&self, tx: Sender<()>) {
+        if self.scrub_interval == 0 {
+            return;
+        }
+        let interval = Duration::from_secs(self.scrub_interval);
+        thread::spawn(move || loop {
+            thread::sleep(interval);
+            let reclaimed = pending();
+            if reclaimed > 0 {
+                log::info!("Scrubbed {} pending Sender(s)", reclaimed);
+            }
+            if tx.send(()).is_err() {
+                return;
+            }
+        });
+    }
+}
+
+/**
+ * Remove pending Senders that have expired, were left behind once paired,
+ * or whose connection has closed, releasing their kernel pipes
+ */
+fn pending() -> usize {
+    let mut endpoints = PENDING_ENDPOINTS.lock().unwrap();
+    let before = endpoints.len();
+    endpoints.retain(|id, endpoint| {
+        let reason = match () {
+            _ if endpoint.has_peer => "already paired",
+            _ if endpoint.time_added.elapsed().unwrap_or_default() >= endpoint.ttl => "expired",
+            _ if disconnected(&endpoint.stream) => "disconnected",
+            _ => return true,
+        };
+        log::info!("[{:.6}] Reclaimed pending Sender: {}", id, reason);
+        false
+    });
+    let reclaimed = before - endpoints.len();
+    if reclaimed > 0 {
+        persist::save(&endpoints);
+    }
+    reclaimed
+}
+
+/**
+ * Whether the peer has closed a connection the relay isn't polling.
+ * Pending Senders send nothing until paired, so this only peeks.
+ */
+fn disconnected(stream: &TcpStream) -> bool {
+    let mut byte = 0u8;
+    let res = unsafe {
+        libc::recv(
+            stream.as_raw_fd(),
+            &mut byte as *mut u8 as *mut libc::c_void,
+            1,
+            libc::MSG_PEEK | libc::MSG_DONTWAIT,
+        )
+    };
+    match res {
+        0 => true,
+        n if n > 0 => false,
+        _ => !matches!(
+            io::Error::last_os_error().kind(),
+            io::ErrorKind::WouldBlock | io::ErrorKind::Interrupted
+        ),
+    }
+}
+
+/**
+ * Audit the event loop's sessions, removing tokens that no longer lead
+ * to a session, sessions that neither peer is still connected to (or
+ * only an idle one), and claims on IDs without a session
+ */
+pub fn sessions(
+    poll: &Poll,
+    endpoints: &mut HashMap<String, EndpointPair>,
+    id_lookup: &mut HashMap<Token, String>,
+) {
+    // Tokens for sessions that are gone, or for a side since replaced
+    let mut tokens = 0;
+    id_lookup.retain(|token, id| {
+        let live = endpoints
+            .get(id)
+            .is_some_and(|pair| pair.sender_token == *token || pair.receiver_token == *token);
+        if !live {
+            log::info!("[{:.6}] Reclaimed orphaned token {:?}", id, token);
+            tokens += 1;
+        }
+        live
+    });
+
+    // Sessions left behind by their peers, a Receiver that may still
+    // resume is expired separately
+    let mut sessions = 0;
+    endpoints.retain(|id, pair| {
+        if pair
+            .spool
+            .as_ref()
+            .is_some_and(|s| s.away() && !s.expired())
+        {
+            return true;
+        }
+        let live = [pair.sender_token, pair.receiver_token]
+            .iter()
+            .filter(|token| id_lookup.contains_key(token))
+            .count();
+        let idle = pair.sender.stats.idle().min(pair.receiver.stats.idle());
+        let reason = match live {
+            0 => "both peers are gone",
+            1 if idle > ORPHAN_IDLE => "one peer is gone & the other is idle",
+            _ => return true,
+        };
+
+        log::warn!("[{:.6}] Reclaimed session: {}", id, reason);
+        webhook::failed(pair, reason);
+        for (endpoint, token) in [
+            (&pair.sender, pair.sender_token),
+            (&pair.receiver, pair.receiver_token),
+        ] {
+            let _ = poll.deregister(&endpoint.stream);
+            let _ = endpoint.stream.shutdown(std::net::Shutdown::Both);
+            id_lookup.remove(&token);
+        }
+        claims::release(id);
+        sessions += 1;
+        false
+    });
+
+    // Claims whose session never reached, or has left, the event loop
+    let released = claims::scrub(CLAIM_GRACE, |id| endpoints.contains_key(id));
+    for id in &released {
+        log::info!("[{:.6}] Released orphaned claim", id);
+    }
+
+    if tokens + sessions + released.len() > 0 {
+        log::info!(
+            "Scrubbed {} token(s), {} session(s) & {} claim(s)",
+            tokens,
+            sessions,
+            released.len()
+        );
+    }
+}
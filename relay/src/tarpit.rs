@@ -0,0 +1,228 @@
+use mio::net::TcpStream;
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use structopt::StructOpt;
+
+use crate::local;
+
+/// Failures are forgotten after a source has been quiet this long
+const FORGET_AFTER: Duration = Duration::from_secs(600);
+
+/// Most connections held open at once, any more are closed immediately
+const MAX_HELD: usize = 1024;
+
+/// Receivers that find no Sender are only counted as failing when they
+/// try another ID within this long
+const MISS_WINDOW: Duration = Duration::from_secs(30);
+
+/// Slows down sources that repeatedly send bad handshakes
+#[derive(Debug, Clone, StructOpt)]
+pub struct Tarpit {
+    /// Bad handshakes allowed from an address before delaying it,
+    /// 0 disables tarpitting
+    #[structopt(long, default_value = "3")]
+    pub tarpit_threshold: u32,
+
+    /// Milliseconds to delay closing the first tarpitted connection,
+    /// doubled for every further bad handshake
+    #[structopt(long, default_value = "1000")]
+    pub tarpit_delay: u64,
+
+    /// Maximum seconds a tarpitted connection is held open
+    #[structopt(long, default_value = "60")]
+    pub tarpit_max_delay: u64,
+}
+
+/// Bad handshakes seen from a single address
+#[derive(Debug)]
+struct Offender {
+    failures: u32,
+    last: Instant,
+}
+
+lazy_static! {
+    static ref CONFIG: Mutex<Option<Tarpit>> = Mutex::new(None);
+    static ref OFFENDERS: Mutex<HashMap<IpAddr, Offender>> = Mutex::new(HashMap::new());
+    static ref HOLD: Mutex<Option<Sender<(Instant, TcpStream)>>> = Mutex::new(None);
+    static ref MISSES: Mutex<HashMap<IpAddr, (String, Instant)>> = Mutex::new(HashMap::new());
+}
+
+/**
+ * Start the thread that closes tarpitted connections once their delay
+ * has passed, so they don't occupy the registration threadpool
+ */
+pub fn spawn(config: Tarpit) {
+    if config.tarpit_threshold == 0 {
+        return;
+    }
+    let (tx, rx) = mpsc::channel();
+    *CONFIG.lock().unwrap() = Some(config);
+    *HOLD.lock().unwrap() = Some(tx);
+    thread::spawn(move || hold(rx));
+}
+
+/**
+ * Apply new settings to a running relay, starting the tarpit if it was
+ * disabled until now
+ */
+pub fn configure(config: Tarpit) {
+    if config.tarpit_threshold == 0 {
+        *CONFIG.lock().unwrap() = None;
+        return;
+    }
+    let running = HOLD.lock().unwrap().is_some();
+    match running {
+        true => *CONFIG.lock().unwrap() = Some(config),
+        false => spawn(config),
+    }
+}
+
+/**
+ * Record a bad handshake from addr and close the connection,
+ * after a delay if the source has exceeded the threshold
+ */
+pub fn reject(addr: &SocketAddr, connection: TcpStream) {
+    let delay = match record(source(addr, &connection)) {
+        Some(d) => d,
+        None => {
+            let _ = connection.shutdown(std::net::Shutdown::Both);
+            return;
+        }
+    };
+
+    log::debug!("[?] Tarpitting {:?} for {:?}", addr, delay);
+    if let Some(hold) = HOLD.lock().unwrap().as_ref() {
+        let _ = hold.send((Instant::now() + delay, connection));
+    }
+}
+
+/**
+ * Close the connection of a Receiver whose Sender hasn't registered.
+ * It is rejected only when the same source missed another ID shortly
+ * before, a Receiver that's early for its Sender may retry freely.
+ */
+pub fn miss(addr: &SocketAddr, id: &str, connection: TcpStream) {
+    let ip = source(addr, &connection);
+    match repeated(&mut MISSES.lock().unwrap(), ip, id, Instant::now()) {
+        true => reject(addr, connection),
+        false => {
+            let _ = connection.shutdown(std::net::Shutdown::Both);
+        }
+    }
+}
+
+/**
+ * Helper: record a miss, returning true if the source recently missed
+ * a different ID
+ */
+fn repeated(
+    misses: &mut HashMap<IpAddr, (String, Instant)>,
+    ip: IpAddr,
+    id: &str,
+    now: Instant,
+) -> bool {
+    misses.retain(|_, (_, last)| now.saturating_duration_since(*last) < MISS_WINDOW);
+    match misses.insert(ip, (id.to_string(), now)) {
+        Some((previous, _)) => previous != id,
+        None => false,
+    }
+}
+
+/**
+ * Forget previous failures after a successful handshake
+ */
+pub fn forgive(addr: &SocketAddr, connection: &TcpStream) {
+    OFFENDERS.lock().unwrap().remove(&source(addr, connection));
+}
+
+/**
+ * Helper: who failures are counted against, local users each on
+ * their own rather than all of loopback
+ */
+fn source(addr: &SocketAddr, connection: &TcpStream) -> IpAddr {
+    local::peer_ip(connection).unwrap_or(addr.ip())
+}
+
+/**
+ * Count a failure, returning how long to delay the connection if any
+ */
+fn record(ip: IpAddr) -> Option<Duration> {
+    let config = CONFIG.lock().unwrap().clone()?;
+    let mut offenders = OFFENDERS.lock().unwrap();
+    offenders.retain(|_, v| v.last.elapsed() < FORGET_AFTER);
+
+    let offender = offenders.entry(ip).or_insert(Offender {
+        failures: 0,
+        last: Instant::now(),
+    });
+    offender.failures += 1;
+    offender.last = Instant::now();
+
+    // Double the delay for each failure past the threshold
+    let excess = offender.failures.checked_sub(config.tarpit_threshold + 1)?;
+    let delay = Duration::from_millis(config.tarpit_delay)
+        .checked_mul(1 << excess.min(31))
+        .unwrap_or(Duration::MAX);
+    Some(delay.min(Duration::from_secs(config.tarpit_max_delay)))
+}
+
+/**
+ * Hold connections open until their deadline, then close them
+ */
+fn hold(rx: Receiver<(Instant, TcpStream)>) {
+    let mut held: Vec<(Instant, TcpStream)> = Vec::new();
+    loop {
+        let next = held.iter().map(|(deadline, _)| *deadline).min();
+        let received = match next {
+            Some(deadline) => rx.recv_timeout(deadline.saturating_duration_since(Instant::now())),
+            None => rx.recv().or(Err(RecvTimeoutError::Disconnected)),
+        };
+
+        match received {
+            Ok(entry) if held.len() < MAX_HELD => held.push(entry),
+            Ok((_, connection)) => {
+                let _ = connection.shutdown(std::net::Shutdown::Both);
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+
+        // Close everything that has waited long enough
+        let now = Instant::now();
+        held.retain(|(deadline, connection)| match *deadline <= now {
+            true => {
+                let _ = connection.shutdown(std::net::Shutdown::Both);
+                false
+            }
+            false => true,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn only_repeated_misses_count() {
+        let mut misses = HashMap::new();
+        let ip = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1));
+        let now = Instant::now();
+
+        // Retrying while the Sender is late
+        assert!(!repeated(&mut misses, ip, "early", now));
+        assert!(!repeated(&mut misses, ip, "early", now));
+
+        // Trying other IDs in quick succession
+        assert!(repeated(&mut misses, ip, "guess", now));
+
+        // But not once the window has passed
+        let later = now + MISS_WINDOW;
+        assert!(!repeated(&mut misses, ip, "another", later));
+    }
+}
@@ -0,0 +1,159 @@
+use hmac::{Hmac, Mac, NewMac};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::error::Error;
+use std::fs;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::path::PathBuf;
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use structopt::StructOpt;
+
+use crate::EndpointPair;
+
+/// Events waiting to be delivered before new ones are dropped
+const QUEUE_LEN: usize = 1024;
+
+/// How long the webhook may take to respond
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Session events sent to an external service, for monitoring or billing
+#[derive(Debug, Clone, StructOpt)]
+pub struct Webhook {
+    /// POST a JSON event to this URL whenever a session is paired,
+    /// completes or fails. Its host is resolved once, at startup
+    #[structopt(long)]
+    pub webhook_url: Option<String>,
+
+    /// Sign each event with the secret in this file, sending the
+    /// HMAC-SHA256 of the body as X-Portal-Signature: sha256=<hex>
+    #[structopt(long)]
+    pub webhook_secret: Option<PathBuf>,
+}
+
+lazy_static! {
+    static ref QUEUE: Mutex<Option<SyncSender<Value>>> = Mutex::new(None);
+}
+
+impl Webhook {
+    /**
+     * Read the signing secret & resolve the webhook's address while
+     * files & DNS are still reachable, then start the thread delivering
+     * events. It runs inside the sandbox, so connects by address only.
+     */
+    pub fn init(&self) -> Result<(), Box<dyn Error>> {
+        let url = match &self.webhook_url {
+            Some(url) => url.clone(),
+            None => return Ok(()),
+        };
+        let secret = match &self.webhook_secret {
+            Some(path) => Some(fs::read_to_string(path)?.trim().as_bytes().to_vec()),
+            None => None,
+        };
+        let addrs = resolve(&url)?;
+
+        let (tx, rx) = sync_channel(QUEUE_LEN);
+        thread::spawn(move || deliver(url, addrs, secret, rx));
+        *QUEUE.lock().unwrap() = Some(tx);
+        Ok(())
+    }
+}
+
+/**
+ * Helper: the addresses of the URL's host, as the agent would look
+ * them up for each request
+ */
+fn resolve(url: &str) -> Result<Vec<SocketAddr>, Box<dyn Error>> {
+    let target = ureq::post(url).request_url()?;
+    let port = match (target.port(), target.scheme()) {
+        (Some(port), _) => port,
+        (None, "https") => 443,
+        (None, _) => 80,
+    };
+    let addrs: Vec<SocketAddr> = format!("{}:{}", target.host(), port)
+        .to_socket_addrs()?
+        .collect();
+    if addrs.is_empty() {
+        return Err(format!("No addresses found for {}", target.host()).into());
+    }
+    Ok(addrs)
+}
+
+/**
+ * Helper: post events in the order they happened, logging failures
+ * rather than retrying. Requests still name the URL's host, so TLS
+ * verifies it as usual.
+ */
+fn deliver(url: String, addrs: Vec<SocketAddr>, secret: Option<Vec<u8>>, rx: Receiver<Value>) {
+    let agent = ureq::AgentBuilder::new()
+        .timeout(WEBHOOK_TIMEOUT)
+        .resolver(move |_: &str| Ok(addrs.clone()))
+        .build();
+    for event in rx {
+        let body = event.to_string();
+        let mut request = agent.post(&url).set("Content-Type", "application/json");
+        if let Some(Ok(mut mac)) = secret.as_deref().map(Hmac::<Sha256>::new_varkey) {
+            mac.update(body.as_bytes());
+            let signature = hex::encode(mac.finalize().into_bytes());
+            request = request.set("X-Portal-Signature", &format!("sha256={}", signature));
+        }
+        if let Err(e) = request.send_string(&body) {
+            log::warn!("Error delivering {} to the webhook: {}", event["event"], e);
+        }
+    }
+}
+
+/**
+ * Helper: stamp the event & queue it, if a webhook is configured
+ */
+fn emit(mut event: Value) {
+    let queue = QUEUE.lock().unwrap();
+    let tx = match queue.as_ref() {
+        Some(tx) => tx,
+        None => return,
+    };
+    event["timestamp"] = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+        .into();
+    if let Err(TrySendError::Full(event)) = tx.try_send(event) {
+        log::warn!("Webhook is falling behind, dropped {}", event["event"]);
+    }
+}
+
+/**
+ * A Sender & Receiver have been paired
+ */
+pub fn paired(id: &str) {
+    emit(json!({ "event": "paired", "id": id }));
+}
+
+/**
+ * Both sides of a session have disconnected
+ */
+pub fn completed(pair: &EndpointPair) {
+    emit(json!({
+        "event": "completed",
+        "id": pair.sender.id,
+        "seconds": pair.sender.stats.age().as_secs_f64(),
+        "sender_bytes": pair.sender.stats.received(),
+        "receiver_bytes": pair.receiver.stats.received(),
+    }));
+}
+
+/**
+ * The relay closed a session before it completed
+ */
+pub fn failed(pair: &EndpointPair, reason: &str) {
+    emit(json!({
+        "event": "failed",
+        "id": pair.sender.id,
+        "reason": reason,
+        "seconds": pair.sender.stats.age().as_secs_f64(),
+        "sender_bytes": pair.sender.stats.received(),
+        "receiver_bytes": pair.receiver.stats.received(),
+    }));
+}